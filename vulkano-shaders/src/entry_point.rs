@@ -15,7 +15,7 @@ use vulkano::{
         spirv::ExecutionModel, DescriptorBindingRequirements, DescriptorIdentifier,
         DescriptorRequirements, EntryPointInfo, ShaderExecution, ShaderInterface,
         ShaderInterfaceEntry, ShaderInterfaceEntryType, ShaderStages,
-        SpecializationConstantRequirements,
+        ShaderTransformFeedbackOutput, SpecializationConstantRequirements,
     },
 };
 
@@ -38,6 +38,8 @@ pub(super) fn write_entry_point(
         write_specialization_constant_requirements(&info.specialization_constant_requirements);
     let input_interface = write_interface(&info.input_interface);
     let output_interface = write_interface(&info.output_interface);
+    let transform_feedback_outputs =
+        write_transform_feedback_outputs(info.transform_feedback_outputs.as_deref());
 
     quote! {
         (
@@ -50,6 +52,7 @@ pub(super) fn write_entry_point(
                 specialization_constant_requirements: #specialization_constant_requirements.into_iter().collect(),
                 input_interface: #input_interface,
                 output_interface: #output_interface,
+                transform_feedback_outputs: #transform_feedback_outputs,
             },
         ),
     }
@@ -98,7 +101,22 @@ fn write_shader_execution(execution: &ShaderExecution) -> TokenStream {
         }
         ShaderExecution::Callable => quote! { ::vulkano::shader::ShaderExecution::Callable },
         ShaderExecution::Task => quote! { ::vulkano::shader::ShaderExecution::Task },
-        ShaderExecution::Mesh => quote! { ::vulkano::shader::ShaderExecution::Mesh },
+        ShaderExecution::Mesh(::vulkano::shader::MeshShaderExecution {
+            output,
+            max_vertices,
+            max_primitives,
+        }) => {
+            let output = format_ident!("{}", format!("{:?}", output));
+            quote! {
+                ::vulkano::shader::ShaderExecution::Mesh(
+                    ::vulkano::shader::MeshShaderExecution {
+                        output: ::vulkano::shader::MeshShaderOutput::#output,
+                        max_vertices: #max_vertices,
+                        max_primitives: #max_primitives,
+                    }
+                )
+            }
+        }
         ShaderExecution::SubpassShading => {
             quote! { ::vulkano::shader::ShaderExecution::SubpassShading }
         }
@@ -366,3 +384,38 @@ fn stages_to_items(stages: ShaderStages) -> TokenStream {
         quote! { #(#stages_items)|* }
     }
 }
+
+fn write_transform_feedback_outputs(
+    outputs: Option<&[ShaderTransformFeedbackOutput]>,
+) -> TokenStream {
+    match outputs {
+        Some(outputs) => {
+            let items = outputs.iter().map(
+                |&ShaderTransformFeedbackOutput {
+                     buffer,
+                     stride,
+                     offset,
+                     size,
+                     stream,
+                 }| {
+                    quote! {
+                        ::vulkano::shader::ShaderTransformFeedbackOutput {
+                            buffer: #buffer,
+                            stride: #stride,
+                            offset: #offset,
+                            size: #size,
+                            stream: #stream,
+                        },
+                    }
+                },
+            );
+
+            quote! {
+                Some(vec![
+                    #( #items )*
+                ])
+            }
+        }
+        None => quote! { None },
+    }
+}
@@ -104,6 +104,8 @@
 //! * `tess_ctrl`
 //! * `tess_eval`
 //! * `compute`
+//! * `task`
+//! * `mesh`
 //!
 //! For details on what these shader types mean, [see Vulkano's documentation][pipeline].
 //!
@@ -560,8 +562,11 @@ impl Parse for MacroInput {
                         "miss" => ShaderKind::Miss,
                         "intersection" => ShaderKind::Intersection,
                         "callable" => ShaderKind::Callable,
+                        "task" => ShaderKind::Task,
+                        "mesh" => ShaderKind::Mesh,
                         _ => panic!(concat!("Unexpected shader type, valid values: vertex, fragment, geometry, tess_ctrl, ",
-											"tess_eval, compute, raygen, anyhit, closesthit, miss, intersection, callable"))
+											"tess_eval, compute, raygen, anyhit, closesthit, miss, intersection, callable, ",
+											"task, mesh"))
                     };
 
                     output.0 = Some(ty);
@@ -0,0 +1,1152 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! An opaque data structure that is used to accelerate spatial queries on geometry data.
+//!
+//! Acceleration structures contain geometry data, arranged in such a way that the device can
+//! easily search through the data and check for intersections between the geometry and rays
+//! (lines). The geometry data can consist of either triangles, or axis-aligned bounding boxes
+//! (AABBs).
+//!
+//! Acceleration structures come in two forms: top-level and bottom-level. A bottom-level
+//! acceleration structure holds the actual geometry data, while a top-level structure contains
+//! instances of (references to) one or more bottom-level structures. A top-level structure is
+//! intended to contain the whole rendered scene (or the relevant parts of it), while a
+//! bottom-level structure may contain individual objects within the scene. This two-level
+//! arrangement allows you to easily rearrange the scene, adding and removing parts of it as needed.
+//!
+//! # Building an acceleration structure
+//!
+//! When an acceleration structure object is created, it is in an uninitialized state and contains
+//! garbage data. To be able to use it for anything, you must first *build* the structure on the
+//! device, using the
+//! [`build_acceleration_structure`](crate::command_buffer::AutoCommandBufferBuilder::build_acceleration_structure)
+//! command. The amount of memory that is needed for the structure itself, and for the scratch
+//! data that is used during the build, can be queried beforehand with
+//! [`Device::acceleration_structure_build_sizes`].
+//!
+//! # Accessing an acceleration structure
+//!
+//! Acceleration structures can be bound to a descriptor set, with
+//! [`WriteDescriptorSet::acceleration_structure`](crate::descriptor_set::WriteDescriptorSet::acceleration_structure).
+//! They can then be used in ray query operations in any shader, or in ray tracing pipelines.
+//! The top-level structure must be bound, the bottom-level structures are accessed through it.
+
+use crate::{
+    buffer::{BufferAccess, BufferUsage},
+    device::{Device, DeviceOwned},
+    format::Format,
+    macros::{vulkan_bitflags, vulkan_enum},
+    pipeline::graphics::input_assembly::IndexType,
+    DeviceSize, OomError, RequirementNotMet, RequiresOneOf, VulkanError, VulkanObject,
+};
+use bytemuck::{Pod, Zeroable};
+use smallvec::SmallVec;
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    mem::MaybeUninit,
+    num::NonZeroU64,
+    ptr,
+    sync::Arc,
+};
+
+/// An opaque data structure that is used to accelerate spatial queries on geometry data.
+#[derive(Debug)]
+pub struct AccelerationStructure {
+    handle: ash::vk::AccelerationStructureKHR,
+    device: Arc<Device>,
+    id: NonZeroU64,
+
+    buffer: Arc<dyn BufferAccess>,
+    ty: AccelerationStructureType,
+}
+
+impl AccelerationStructure {
+    /// Creates a new `AccelerationStructure`.
+    ///
+    /// The [`acceleration_structure`] feature must be enabled on the device.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.buffer` is not owned by `device`.
+    ///
+    /// [`acceleration_structure`]: crate::device::Features::acceleration_structure
+    #[inline]
+    pub fn new(
+        device: Arc<Device>,
+        create_info: AccelerationStructureCreateInfo,
+    ) -> Result<Arc<Self>, AccelerationStructureError> {
+        Self::validate_new(&device, &create_info)?;
+
+        unsafe { Ok(Self::new_unchecked(device, create_info)?) }
+    }
+
+    fn validate_new(
+        device: &Device,
+        create_info: &AccelerationStructureCreateInfo,
+    ) -> Result<(), AccelerationStructureError> {
+        let &AccelerationStructureCreateInfo {
+            ref buffer,
+            ty,
+            _ne: _,
+        } = create_info;
+
+        // VUID-vkCreateAccelerationStructureKHR-accelerationStructure-03611
+        if !device.enabled_features().acceleration_structure {
+            return Err(AccelerationStructureError::RequirementNotMet {
+                required_for: "`AccelerationStructure::new`",
+                requires_one_of: RequiresOneOf {
+                    features: &["acceleration_structure"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-VkAccelerationStructureCreateInfoKHR-type-parameter
+        ty.validate_device(device)?;
+
+        // VUID-VkAccelerationStructureCreateInfoKHR-commonparent
+        assert_eq!(device, buffer.device().as_ref());
+
+        // VUID-VkAccelerationStructureCreateInfoKHR-buffer-03614
+        if !buffer
+            .usage()
+            .intersects(BufferUsage::ACCELERATION_STRUCTURE_STORAGE)
+        {
+            return Err(AccelerationStructureError::BufferMissingUsage {
+                usage: "acceleration_structure_storage",
+            });
+        }
+
+        // VUID-VkAccelerationStructureCreateInfoKHR-offset-03734
+        if buffer.inner().offset % 256 != 0 {
+            return Err(AccelerationStructureError::BufferOffsetNotAligned {
+                offset: buffer.inner().offset,
+                required_alignment: 256,
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn new_unchecked(
+        device: Arc<Device>,
+        create_info: AccelerationStructureCreateInfo,
+    ) -> Result<Arc<Self>, VulkanError> {
+        let AccelerationStructureCreateInfo { buffer, ty, _ne: _ } = create_info;
+
+        let buffer_inner = buffer.inner();
+        let create_info_vk = ash::vk::AccelerationStructureCreateInfoKHR {
+            create_flags: ash::vk::AccelerationStructureCreateFlagsKHR::empty(),
+            buffer: buffer_inner.buffer.handle(),
+            offset: buffer_inner.offset,
+            size: buffer.size(),
+            ty: ty.into(),
+            device_address: 0,
+            ..Default::default()
+        };
+
+        let handle = {
+            let fns = device.fns();
+            let mut output = MaybeUninit::uninit();
+            (fns.khr_acceleration_structure
+                .create_acceleration_structure_khr)(
+                device.handle(),
+                &create_info_vk,
+                device.allocation_callbacks(),
+                output.as_mut_ptr(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+            output.assume_init()
+        };
+
+        Ok(Arc::new(AccelerationStructure {
+            handle,
+            device,
+            id: Self::next_id(),
+            buffer,
+            ty,
+        }))
+    }
+
+    /// Returns the type of the acceleration structure.
+    #[inline]
+    pub fn ty(&self) -> AccelerationStructureType {
+        self.ty
+    }
+
+    /// Returns the buffer that holds the acceleration structure data.
+    #[inline]
+    pub fn buffer(&self) -> &Arc<dyn BufferAccess> {
+        &self.buffer
+    }
+
+    /// Returns the size of the acceleration structure, in bytes.
+    #[inline]
+    pub fn size(&self) -> DeviceSize {
+        self.buffer.size()
+    }
+
+    /// Returns the device address of the acceleration structure.
+    ///
+    /// This is the value that must be written to
+    /// [`AccelerationStructureInstance::acceleration_structure_reference`] to refer to a
+    /// bottom-level acceleration structure from a top-level acceleration structure.
+    #[inline]
+    pub fn device_address(&self) -> NonZeroU64 {
+        let info_vk = ash::vk::AccelerationStructureDeviceAddressInfoKHR {
+            acceleration_structure: self.handle,
+            ..Default::default()
+        };
+        let ptr = unsafe {
+            let fns = self.device.fns();
+            (fns.khr_acceleration_structure
+                .get_acceleration_structure_device_address_khr)(
+                self.device.handle(), &info_vk
+            )
+        };
+
+        NonZeroU64::new(ptr).unwrap()
+    }
+}
+
+impl Drop for AccelerationStructure {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let fns = self.device.fns();
+            (fns.khr_acceleration_structure
+                .destroy_acceleration_structure_khr)(
+                self.device.handle(),
+                self.handle,
+                self.device.allocation_callbacks(),
+            );
+        }
+    }
+}
+
+unsafe impl VulkanObject for AccelerationStructure {
+    type Handle = ash::vk::AccelerationStructureKHR;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.handle
+    }
+}
+
+unsafe impl DeviceOwned for AccelerationStructure {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+crate::impl_id_counter!(AccelerationStructure);
+
+/// Parameters to create a new `AccelerationStructure`.
+#[derive(Clone, Debug)]
+pub struct AccelerationStructureCreateInfo {
+    /// The buffer to store the acceleration structure in.
+    ///
+    /// The buffer must have been created with the
+    /// [`ACCELERATION_STRUCTURE_STORAGE`](BufferUsage::ACCELERATION_STRUCTURE_STORAGE) usage,
+    /// and its offset within its memory must be a multiple of 256. The required size can be
+    /// queried with [`Device::acceleration_structure_build_sizes`].
+    ///
+    /// There is no default value.
+    pub buffer: Arc<dyn BufferAccess>,
+
+    /// The type of acceleration structure to create.
+    ///
+    /// The default value is [`AccelerationStructureType::Generic`].
+    pub ty: AccelerationStructureType,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl AccelerationStructureCreateInfo {
+    /// Returns an `AccelerationStructureCreateInfo` with the specified `buffer`.
+    #[inline]
+    pub fn buffer(buffer: Arc<dyn BufferAccess>) -> Self {
+        Self {
+            buffer,
+            ty: AccelerationStructureType::Generic,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The type of an acceleration structure.
+    AccelerationStructureType = AccelerationStructureTypeKHR(i32);
+
+    /// Refers to bottom-level acceleration structures. This type can be bound to a descriptor.
+    TopLevel = TOP_LEVEL,
+
+    /// Contains AABBs or geometry to be intersected.
+    BottomLevel = BOTTOM_LEVEL,
+
+    /// The type is determined at build time.
+    ///
+    /// Use of this type is discouraged, it is preferred to specify the type at create time.
+    Generic = GENERIC,
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// Where the building of an acceleration structure will take place.
+    AccelerationStructureBuildType = AccelerationStructureBuildTypeKHR(i32);
+
+    /// Building will take place on the host.
+    Host = HOST,
+
+    /// Building will take place on the device.
+    Device = DEVICE,
+
+    /// Building will take place on either the host or the device.
+    HostOrDevice = HOST_OR_DEVICE,
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags to control how an acceleration structure should be built.
+    BuildAccelerationStructureFlags = BuildAccelerationStructureFlagsKHR(u32);
+
+    /// The acceleration structure can be updated later, with
+    /// [`BuildAccelerationStructureMode::Update`].
+    ALLOW_UPDATE = ALLOW_UPDATE,
+
+    /// The acceleration structure can be compacted later, with
+    /// [`CopyAccelerationStructureMode::Compact`].
+    ALLOW_COMPACTION = ALLOW_COMPACTION,
+
+    /// Prioritize for best trace performance, with possibly longer build times.
+    PREFER_FAST_TRACE = PREFER_FAST_TRACE,
+
+    /// Prioritize for shorter build time, with possibly suboptimal trace performance.
+    PREFER_FAST_BUILD = PREFER_FAST_BUILD,
+
+    /// Prioritize minimizing memory usage during the build and of the acceleration structure
+    /// itself, possibly at the expense of build time or trace performance.
+    LOW_MEMORY = LOW_MEMORY,
+}
+
+/// What mode an acceleration structure build command should operate in.
+#[derive(Clone, Debug)]
+pub enum BuildAccelerationStructureMode {
+    /// Build a new acceleration structure from scratch.
+    Build,
+
+    /// Update a previously built source acceleration structure with new data, storing the
+    /// updated structure in the destination. The source and destination acceleration structures
+    /// may be the same.
+    ///
+    /// The source acceleration structure must have been built with the
+    /// [`BuildAccelerationStructureFlags::ALLOW_UPDATE`] flag.
+    Update(Arc<AccelerationStructure>),
+}
+
+impl BuildAccelerationStructureMode {
+    pub(crate) fn to_vulkan(&self) -> ash::vk::BuildAccelerationStructureModeKHR {
+        match self {
+            Self::Build => ash::vk::BuildAccelerationStructureModeKHR::BUILD,
+            Self::Update(_) => ash::vk::BuildAccelerationStructureModeKHR::UPDATE,
+        }
+    }
+}
+
+/// Parameters for building an acceleration structure, or for querying the sizes that are needed
+/// to build one.
+#[derive(Clone, Debug)]
+pub struct AccelerationStructureBuildGeometryInfo {
+    /// Specifies how the acceleration structure should be built.
+    ///
+    /// The default value is empty.
+    pub flags: BuildAccelerationStructureFlags,
+
+    /// The mode that the build command should operate in.
+    ///
+    /// This is ignored when querying build sizes.
+    ///
+    /// The default value is [`BuildAccelerationStructureMode::Build`].
+    pub mode: BuildAccelerationStructureMode,
+
+    /// The acceleration structure to build or update.
+    ///
+    /// This can be `None` when querying build sizes, but must be `Some` when building.
+    ///
+    /// The default value is `None`.
+    pub dst_acceleration_structure: Option<Arc<AccelerationStructure>>,
+
+    /// The geometries that will be built into `dst_acceleration_structure`.
+    ///
+    /// The variant must match the type of `dst_acceleration_structure`: `Instances` for a
+    /// top-level acceleration structure, and `Triangles` or `Aabbs` for a bottom-level
+    /// acceleration structure.
+    ///
+    /// There is no default value.
+    pub geometries: AccelerationStructureGeometries,
+
+    /// Scratch memory to be used for the build.
+    ///
+    /// The buffer must have been created with the [`STORAGE_BUFFER`](BufferUsage::STORAGE_BUFFER)
+    /// and [`SHADER_DEVICE_ADDRESS`](BufferUsage::SHADER_DEVICE_ADDRESS) usages, and must be at
+    /// least as large as the `build_scratch_size` or `update_scratch_size` that is returned by
+    /// [`Device::acceleration_structure_build_sizes`].
+    ///
+    /// This can be `None` when querying build sizes, but must be `Some` when building.
+    ///
+    /// The default value is `None`.
+    pub scratch_data: Option<Arc<dyn BufferAccess>>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl AccelerationStructureBuildGeometryInfo {
+    /// Returns an `AccelerationStructureBuildGeometryInfo` with the specified `geometries`.
+    #[inline]
+    pub fn geometries(geometries: AccelerationStructureGeometries) -> Self {
+        Self {
+            flags: BuildAccelerationStructureFlags::empty(),
+            mode: BuildAccelerationStructureMode::Build,
+            dst_acceleration_structure: None,
+            geometries,
+            scratch_data: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+
+    /// Returns the type of acceleration structure that `geometries` should be built into.
+    #[inline]
+    pub fn ty(&self) -> AccelerationStructureType {
+        match self.geometries {
+            AccelerationStructureGeometries::Triangles(_)
+            | AccelerationStructureGeometries::Aabbs(_) => AccelerationStructureType::BottomLevel,
+            AccelerationStructureGeometries::Instances(_) => AccelerationStructureType::TopLevel,
+        }
+    }
+
+    // The returned geometry info does not have its `p_geometries` set yet, as the geometries must
+    // be kept alive by the caller.
+    pub(crate) fn to_vulkan(
+        &self,
+    ) -> (
+        ash::vk::AccelerationStructureBuildGeometryInfoKHR,
+        SmallVec<[ash::vk::AccelerationStructureGeometryKHR; 8]>,
+    ) {
+        let &Self {
+            flags,
+            ref mode,
+            ref dst_acceleration_structure,
+            ref geometries,
+            ref scratch_data,
+            _ne: _,
+        } = self;
+
+        let geometries_vk: SmallVec<[_; 8]> = match geometries {
+            AccelerationStructureGeometries::Triangles(geometries) => geometries
+                .iter()
+                .map(AccelerationStructureGeometryTrianglesData::to_vulkan)
+                .collect(),
+            AccelerationStructureGeometries::Aabbs(geometries) => geometries
+                .iter()
+                .map(AccelerationStructureGeometryAabbsData::to_vulkan)
+                .collect(),
+            AccelerationStructureGeometries::Instances(geometry) => {
+                [geometry.to_vulkan()].into_iter().collect()
+            }
+        };
+
+        let info_vk = ash::vk::AccelerationStructureBuildGeometryInfoKHR {
+            ty: self.ty().into(),
+            flags: flags.into(),
+            mode: mode.to_vulkan(),
+            src_acceleration_structure: match mode {
+                BuildAccelerationStructureMode::Build => Default::default(),
+                BuildAccelerationStructureMode::Update(src) => src.handle(),
+            },
+            dst_acceleration_structure: dst_acceleration_structure
+                .as_ref()
+                .map_or_else(Default::default, VulkanObject::handle),
+            geometry_count: 0,
+            p_geometries: ptr::null(),
+            pp_geometries: ptr::null(),
+            scratch_data: ash::vk::DeviceOrHostAddressKHR {
+                device_address: scratch_data
+                    .as_ref()
+                    .map_or(0, |buffer| device_address(buffer.as_ref())),
+            },
+            ..Default::default()
+        };
+
+        (info_vk, geometries_vk)
+    }
+}
+
+/// The geometries that an acceleration structure is built from.
+#[derive(Clone, Debug)]
+pub enum AccelerationStructureGeometries {
+    /// The geometries consist of bottom-level triangles data.
+    Triangles(Vec<AccelerationStructureGeometryTrianglesData>),
+
+    /// The geometries consist of bottom-level axis-aligned bounding box data.
+    Aabbs(Vec<AccelerationStructureGeometryAabbsData>),
+
+    /// The geometries consist of top-level instance data.
+    Instances(AccelerationStructureGeometryInstancesData),
+}
+
+impl AccelerationStructureGeometries {
+    /// Returns the number of geometries.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Triangles(geometries) => geometries.len(),
+            Self::Aabbs(geometries) => geometries.len(),
+            Self::Instances(_) => 1,
+        }
+    }
+
+    /// Returns whether there are no geometries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags to control how an acceleration structure geometry should be built.
+    GeometryFlags = GeometryFlagsKHR(u32);
+
+    /// The geometry does not invoke the any-hit shaders, even if it is present in a hit group.
+    OPAQUE = OPAQUE,
+
+    /// The any-hit shader will never be called more than once for each primitive in the geometry.
+    NO_DUPLICATE_ANY_HIT_INVOCATION = NO_DUPLICATE_ANY_HIT_INVOCATION,
+}
+
+/// A bottom-level geometry consisting of triangles.
+#[derive(Clone, Debug)]
+pub struct AccelerationStructureGeometryTrianglesData {
+    /// Specifies how the geometry should be built.
+    ///
+    /// The default value is empty.
+    pub flags: GeometryFlags,
+
+    /// The format of each vertex in `vertex_data`.
+    ///
+    /// The format must support the [`ACCELERATION_STRUCTURE_VERTEX_BUFFER`] format feature.
+    ///
+    /// There is no default value.
+    ///
+    /// [`ACCELERATION_STRUCTURE_VERTEX_BUFFER`]: crate::format::FormatFeatures::ACCELERATION_STRUCTURE_VERTEX_BUFFER
+    pub vertex_format: Format,
+
+    /// The vertex data itself, consisting of an array of `vertex_format` values.
+    ///
+    /// This can be `None` when querying build sizes, but must be `Some` when building.
+    ///
+    /// The default value is `None`.
+    pub vertex_data: Option<Arc<dyn BufferAccess>>,
+
+    /// The number of bytes between the start of successive elements in `vertex_data`.
+    ///
+    /// The default value is `0`, which must be overridden.
+    pub vertex_stride: u32,
+
+    /// The highest vertex index that may be read from `vertex_data`.
+    ///
+    /// The default value is `0`, which must be overridden.
+    pub max_vertex: u32,
+
+    /// If indices are used, the buffer holding the index data.
+    ///
+    /// This must be `Some` when building if `index_type` is `Some`, and is ignored otherwise.
+    ///
+    /// The default value is `None`.
+    pub index_data: Option<Arc<dyn BufferAccess>>,
+
+    /// If indices are used, the type of the indices in `index_data`.
+    ///
+    /// The default value is `None`.
+    pub index_type: Option<IndexType>,
+
+    /// Optionally, a 3x4 matrix that will be used to transform the vertices in `vertex_data` to
+    /// the space in which the acceleration structure is defined. The matrix is a
+    /// [`ash::vk::TransformMatrixKHR`]: three rows of four `f32` values each. If this is `None`,
+    /// the vertices are not transformed.
+    ///
+    /// The default value is `None`.
+    pub transform_data: Option<Arc<dyn BufferAccess>>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl AccelerationStructureGeometryTrianglesData {
+    /// Returns an `AccelerationStructureGeometryTrianglesData` with the specified
+    /// `vertex_format`.
+    #[inline]
+    pub fn vertex_format(vertex_format: Format) -> Self {
+        Self {
+            flags: GeometryFlags::empty(),
+            vertex_format,
+            vertex_data: None,
+            vertex_stride: 0,
+            max_vertex: 0,
+            index_data: None,
+            index_type: None,
+            transform_data: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+
+    fn to_vulkan(&self) -> ash::vk::AccelerationStructureGeometryKHR {
+        let &Self {
+            flags,
+            vertex_format,
+            ref vertex_data,
+            vertex_stride,
+            max_vertex,
+            ref index_data,
+            index_type,
+            ref transform_data,
+            _ne: _,
+        } = self;
+
+        ash::vk::AccelerationStructureGeometryKHR {
+            geometry_type: ash::vk::GeometryTypeKHR::TRIANGLES,
+            geometry: ash::vk::AccelerationStructureGeometryDataKHR {
+                triangles: ash::vk::AccelerationStructureGeometryTrianglesDataKHR {
+                    vertex_format: vertex_format.into(),
+                    vertex_data: ash::vk::DeviceOrHostAddressConstKHR {
+                        device_address: vertex_data
+                            .as_ref()
+                            .map_or(0, |buffer| device_address(buffer.as_ref())),
+                    },
+                    vertex_stride: vertex_stride as DeviceSize,
+                    max_vertex,
+                    index_type: index_type.map_or(ash::vk::IndexType::NONE_KHR, Into::into),
+                    index_data: ash::vk::DeviceOrHostAddressConstKHR {
+                        device_address: index_data
+                            .as_ref()
+                            .map_or(0, |buffer| device_address(buffer.as_ref())),
+                    },
+                    transform_data: ash::vk::DeviceOrHostAddressConstKHR {
+                        device_address: transform_data
+                            .as_ref()
+                            .map_or(0, |buffer| device_address(buffer.as_ref())),
+                    },
+                    ..Default::default()
+                },
+            },
+            flags: flags.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// A bottom-level geometry consisting of axis-aligned bounding boxes.
+#[derive(Clone, Debug)]
+pub struct AccelerationStructureGeometryAabbsData {
+    /// Specifies how the geometry should be built.
+    ///
+    /// The default value is empty.
+    pub flags: GeometryFlags,
+
+    /// The AABB data itself, consisting of an array of [`ash::vk::AabbPositionsKHR`] values.
+    ///
+    /// This can be `None` when querying build sizes, but must be `Some` when building.
+    ///
+    /// The default value is `None`.
+    pub data: Option<Arc<dyn BufferAccess>>,
+
+    /// The number of bytes between the start of successive elements in `data`.
+    ///
+    /// This must be a multiple of 8.
+    ///
+    /// The default value is `24`, the size of [`ash::vk::AabbPositionsKHR`].
+    pub stride: u32,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for AccelerationStructureGeometryAabbsData {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            flags: GeometryFlags::empty(),
+            data: None,
+            stride: 24,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl AccelerationStructureGeometryAabbsData {
+    fn to_vulkan(&self) -> ash::vk::AccelerationStructureGeometryKHR {
+        let &Self {
+            flags,
+            ref data,
+            stride,
+            _ne: _,
+        } = self;
+
+        ash::vk::AccelerationStructureGeometryKHR {
+            geometry_type: ash::vk::GeometryTypeKHR::AABBS,
+            geometry: ash::vk::AccelerationStructureGeometryDataKHR {
+                aabbs: ash::vk::AccelerationStructureGeometryAabbsDataKHR {
+                    data: ash::vk::DeviceOrHostAddressConstKHR {
+                        device_address: data
+                            .as_ref()
+                            .map_or(0, |buffer| device_address(buffer.as_ref())),
+                    },
+                    stride: stride as DeviceSize,
+                    ..Default::default()
+                },
+            },
+            flags: flags.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// A top-level geometry consisting of instances of bottom-level acceleration structures.
+#[derive(Clone, Debug)]
+pub struct AccelerationStructureGeometryInstancesData {
+    /// Specifies how the geometry should be built.
+    ///
+    /// The default value is empty.
+    pub flags: GeometryFlags,
+
+    /// The instance data itself.
+    ///
+    /// There is no default value.
+    pub data: AccelerationStructureGeometryInstancesDataType,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl AccelerationStructureGeometryInstancesData {
+    /// Returns an `AccelerationStructureGeometryInstancesData` with the specified `data`.
+    #[inline]
+    pub fn data(data: AccelerationStructureGeometryInstancesDataType) -> Self {
+        Self {
+            flags: GeometryFlags::empty(),
+            data,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+
+    fn to_vulkan(&self) -> ash::vk::AccelerationStructureGeometryKHR {
+        let &Self {
+            flags,
+            ref data,
+            _ne: _,
+        } = self;
+
+        let (array_of_pointers, data) = match data {
+            AccelerationStructureGeometryInstancesDataType::Values(data) => (false, data),
+            AccelerationStructureGeometryInstancesDataType::Pointers(data) => (true, data),
+        };
+
+        ash::vk::AccelerationStructureGeometryKHR {
+            geometry_type: ash::vk::GeometryTypeKHR::INSTANCES,
+            geometry: ash::vk::AccelerationStructureGeometryDataKHR {
+                instances: ash::vk::AccelerationStructureGeometryInstancesDataKHR {
+                    array_of_pointers: array_of_pointers as ash::vk::Bool32,
+                    data: ash::vk::DeviceOrHostAddressConstKHR {
+                        device_address: data
+                            .as_ref()
+                            .map_or(0, |buffer| device_address(buffer.as_ref())),
+                    },
+                    ..Default::default()
+                },
+            },
+            flags: flags.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// The data of instances in a top-level acceleration structure.
+#[derive(Clone, Debug)]
+pub enum AccelerationStructureGeometryInstancesDataType {
+    /// The data buffer contains an array of [`AccelerationStructureInstance`] structures
+    /// directly.
+    ///
+    /// The inner value can be `None` when querying build sizes, but must be `Some` when
+    /// building.
+    Values(Option<Arc<dyn BufferAccess>>),
+
+    /// The data buffer contains an array of device addresses, each pointing to an
+    /// [`AccelerationStructureInstance`] structure.
+    ///
+    /// The inner value can be `None` when querying build sizes, but must be `Some` when
+    /// building.
+    Pointers(Option<Arc<dyn BufferAccess>>),
+}
+
+impl AccelerationStructureGeometryInstancesDataType {
+    /// Returns the buffer holding the instance data, if any.
+    #[inline]
+    pub fn buffer(&self) -> Option<&Arc<dyn BufferAccess>> {
+        match self {
+            Self::Values(data) | Self::Pointers(data) => data.as_ref(),
+        }
+    }
+}
+
+/// An instance of a bottom-level acceleration structure, as it is stored in the instance data of
+/// a top-level acceleration structure.
+#[derive(Clone, Copy, Debug, PartialEq, Zeroable, Pod)]
+#[repr(C)]
+pub struct AccelerationStructureInstance {
+    /// A 3x4 row-major transformation matrix to be applied to the bottom-level acceleration
+    /// structure.
+    ///
+    /// The default value is the identity matrix.
+    pub transform: [[f32; 4]; 3],
+
+    /// The low 24 bits are a custom index value that is accessible in the shader, the high 8 bits
+    /// are a visibility mask that is bitwise-ANDed with the cull mask of the ray.
+    ///
+    /// The default value is `Packed24_8::new(0, 0xff)`.
+    pub instance_custom_index_and_mask: Packed24_8,
+
+    /// The low 24 bits are an offset used in calculating the index of the hit shader group in the
+    /// shader binding table, the high 8 bits are [`GeometryInstanceFlags`].
+    ///
+    /// The default value is `Packed24_8::new(0, 0)`.
+    pub instance_shader_binding_table_record_offset_and_flags: Packed24_8,
+
+    /// The device address of the bottom-level acceleration structure in this instance, as
+    /// returned by [`AccelerationStructure::device_address`].
+    ///
+    /// The default value is `0` (null).
+    pub acceleration_structure_reference: DeviceSize,
+}
+
+impl Default for AccelerationStructureInstance {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            transform: [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+            ],
+            instance_custom_index_and_mask: Packed24_8::new(0, 0xff),
+            instance_shader_binding_table_record_offset_and_flags: Packed24_8::new(0, 0),
+            acceleration_structure_reference: 0,
+        }
+    }
+}
+
+/// A 32-bit integer where the low 24 bits and high 8 bits hold separate values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Zeroable, Pod)]
+#[repr(transparent)]
+pub struct Packed24_8(u32);
+
+impl Packed24_8 {
+    /// Returns a new `Packed24_8` value. The upper 8 bits of `low_24` are discarded.
+    #[inline]
+    pub fn new(low_24: u32, high_8: u8) -> Self {
+        Self((low_24 & 0x00ff_ffff) | ((high_8 as u32) << 24))
+    }
+
+    /// Returns the value of the low 24 bits.
+    #[inline]
+    pub fn low_24(&self) -> u32 {
+        self.0 & 0xffffff
+    }
+
+    /// Returns the value of the high 8 bits.
+    #[inline]
+    pub fn high_8(&self) -> u8 {
+        (self.0 >> 24) as u8
+    }
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags for an instance in a top-level acceleration structure.
+    GeometryInstanceFlags = GeometryInstanceFlagsKHR(u32);
+
+    /// Disable face culling for the instance.
+    TRIANGLE_FACING_CULL_DISABLE = TRIANGLE_FACING_CULL_DISABLE,
+
+    /// Flip the facing (front vs back) of triangles.
+    TRIANGLE_FLIP_FACING = TRIANGLE_FLIP_FACING,
+
+    /// Geometries in this instance will act as if [`GeometryFlags::OPAQUE`] were specified.
+    FORCE_OPAQUE = FORCE_OPAQUE,
+
+    /// Geometries in this instance will act as if [`GeometryFlags::OPAQUE`] were not specified.
+    FORCE_NO_OPAQUE = FORCE_NO_OPAQUE,
+}
+
+impl From<GeometryInstanceFlags> for u8 {
+    #[inline]
+    fn from(value: GeometryInstanceFlags) -> Self {
+        ash::vk::GeometryInstanceFlagsKHR::from(value).as_raw() as u8
+    }
+}
+
+/// Counts and offsets for an acceleration structure build operation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Zeroable, Pod)]
+#[repr(C)]
+pub struct AccelerationStructureBuildRangeInfo {
+    /// The number of primitives.
+    ///
+    /// The default value is `0`.
+    pub primitive_count: u32,
+
+    /// The offset (in bytes) into the buffer holding geometry data,
+    /// to where the first primitive is stored.
+    ///
+    /// The default value is `0`.
+    pub primitive_offset: u32,
+
+    /// The index of the first vertex to build from.
+    ///
+    /// This is used only for triangle geometries.
+    ///
+    /// The default value is `0`.
+    pub first_vertex: u32,
+
+    /// The offset (in bytes) into the buffer holding transform matrices,
+    /// to where the matrix is stored.
+    ///
+    /// This is used only for triangle geometries.
+    ///
+    /// The default value is `0`.
+    pub transform_offset: u32,
+}
+
+impl From<AccelerationStructureBuildRangeInfo> for ash::vk::AccelerationStructureBuildRangeInfoKHR {
+    #[inline]
+    fn from(val: AccelerationStructureBuildRangeInfo) -> Self {
+        Self {
+            primitive_count: val.primitive_count,
+            primitive_offset: val.primitive_offset,
+            first_vertex: val.first_vertex,
+            transform_offset: val.transform_offset,
+        }
+    }
+}
+
+/// The sizes that are needed to build an acceleration structure.
+#[derive(Clone, Debug)]
+pub struct AccelerationStructureBuildSizesInfo {
+    /// The minimum required size of the acceleration structure for a build or update operation.
+    pub acceleration_structure_size: DeviceSize,
+
+    /// The minimum required size of the scratch data buffer for an update operation.
+    pub update_scratch_size: DeviceSize,
+
+    /// The minimum required size of the scratch data buffer for a build operation.
+    pub build_scratch_size: DeviceSize,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// What mode an acceleration structure copy command should operate in.
+    CopyAccelerationStructureMode = CopyAccelerationStructureModeKHR(i32);
+
+    /// Copy the source into the destination.
+    /// This is a shallow copy: if the source holds references to other acceleration structures,
+    /// only the references are copied, not the other acceleration structures.
+    ///
+    /// Both source and destination must have been created with the same
+    /// [`AccelerationStructureCreateInfo`].
+    Clone = CLONE,
+
+    /// Create a more compact version of the source in the destination.
+    /// This is a shallow copy: if the source holds references to other acceleration structures,
+    /// only the references are copied, not the other acceleration structures.
+    ///
+    /// The source acceleration structure must have been built with the
+    /// [`BuildAccelerationStructureFlags::ALLOW_COMPACTION`] flag.
+    Compact = COMPACT,
+}
+
+// Returns the device address of a buffer that was validated to have the `SHADER_DEVICE_ADDRESS`
+// usage, or that is only used to query build sizes.
+fn device_address(buffer: &dyn BufferAccess) -> DeviceSize {
+    buffer.raw_device_address().map_or(0, NonZeroU64::get)
+}
+
+/// Error that can happen when creating or querying an acceleration structure.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AccelerationStructureError {
+    /// Not enough memory available.
+    OomError(OomError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// The buffer is missing a usage that is required.
+    BufferMissingUsage { usage: &'static str },
+
+    /// The offset of the buffer within its memory is not a multiple of the required alignment.
+    BufferOffsetNotAligned {
+        offset: DeviceSize,
+        required_alignment: DeviceSize,
+    },
+
+    /// The provided number of maximum primitive counts does not match the number of geometries.
+    MaxPrimitiveCountsLengthMismatch {
+        max_primitive_counts_len: usize,
+        geometry_count: usize,
+    },
+
+    /// The maximum number of geometries in a bottom-level acceleration structure was exceeded.
+    MaxGeometryCountExceeded { count: u64, max: u64 },
+
+    /// The maximum number of instances in a top-level acceleration structure was exceeded.
+    MaxInstanceCountExceeded { count: u64, max: u64 },
+}
+
+impl Error for AccelerationStructureError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for AccelerationStructureError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::BufferMissingUsage { usage } => {
+                write!(f, "the buffer is missing the required usage {}", usage)
+            }
+            Self::BufferOffsetNotAligned {
+                offset,
+                required_alignment,
+            } => write!(
+                f,
+                "the offset of the buffer within its memory ({}) is not a multiple of the required \
+                alignment ({})",
+                offset, required_alignment,
+            ),
+            Self::MaxPrimitiveCountsLengthMismatch {
+                max_primitive_counts_len,
+                geometry_count,
+            } => write!(
+                f,
+                "the provided number of maximum primitive counts ({}) does not match the number \
+                of geometries ({})",
+                max_primitive_counts_len, geometry_count,
+            ),
+            Self::MaxGeometryCountExceeded { count, max } => write!(
+                f,
+                "the number of geometries ({}) exceeds the maximum for a bottom-level \
+                acceleration structure ({})",
+                count, max,
+            ),
+            Self::MaxInstanceCountExceeded { count, max } => write!(
+                f,
+                "the number of instances ({}) exceeds the maximum for a top-level acceleration \
+                structure ({})",
+                count, max,
+            ),
+        }
+    }
+}
+
+impl From<VulkanError> for AccelerationStructureError {
+    fn from(err: VulkanError) -> Self {
+        match err {
+            e @ VulkanError::OutOfHostMemory | e @ VulkanError::OutOfDeviceMemory => {
+                Self::OomError(e.into())
+            }
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+impl From<OomError> for AccelerationStructureError {
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+impl From<RequirementNotMet> for AccelerationStructureError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AccelerationStructure, AccelerationStructureCreateInfo, AccelerationStructureError,
+        Packed24_8,
+    };
+    use crate::{
+        buffer::{BufferUsage, CpuAccessibleBuffer},
+        memory::allocator::StandardMemoryAllocator,
+    };
+
+    #[test]
+    fn packed_24_8() {
+        let packed = Packed24_8::new(0x0123_4567, 0x89);
+        assert_eq!(packed.low_24(), 0x23_4567);
+        assert_eq!(packed.high_8(), 0x89);
+    }
+
+    #[test]
+    fn feature_not_enabled() {
+        let (device, _queue) = gfx_dev_and_queue!();
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+
+        let buffer = CpuAccessibleBuffer::from_iter(
+            &memory_allocator,
+            BufferUsage::STORAGE_BUFFER,
+            false,
+            [0u8; 256],
+        )
+        .unwrap();
+
+        match AccelerationStructure::new(device, AccelerationStructureCreateInfo::buffer(buffer)) {
+            Err(AccelerationStructureError::RequirementNotMet { .. }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -26,7 +26,7 @@ use crate::{
     memory::{
         allocator::{
             AllocationCreateInfo, AllocationCreationError, AllocationType,
-            MemoryAllocatePreference, MemoryAllocator, MemoryUsage,
+            MemoryAllocatePreference, MemoryAllocator, MemoryUsage, MovableBuffer,
         },
         DedicatedAllocation, DeviceMemoryError, ExternalMemoryHandleType,
         ExternalMemoryHandleTypes,
@@ -63,6 +63,12 @@ use std::{
 /// the host, then there may be significant performance gains by using a `DeviceLocalBuffer` over a
 /// buffer type which allows host access.
 ///
+/// The memory of the buffer is always allocated with the default priority. If you need a
+/// different priority, create a [`RawBuffer`] and allocate memory for it yourself with
+/// [`AllocationCreateInfo::priority`] set.
+///
+/// [`RawBuffer`]: crate::buffer::sys::RawBuffer
+///
 /// # Examples
 ///
 /// The following example outlines the general strategy one may take when initializing a
@@ -423,6 +429,7 @@ where
             requirements.size,
             Some(DedicatedAllocation::Buffer(&raw_buffer)),
             external_memory_handle_types,
+            0.5,
         ) {
             Ok(alloc) => {
                 debug_assert!(alloc.offset() % requirements.alignment == 0);
@@ -491,6 +498,21 @@ where
     }
 }
 
+unsafe impl<T> MovableBuffer for Arc<DeviceLocalBuffer<T>>
+where
+    T: BufferContents + ?Sized,
+{
+    #[inline]
+    fn buffer_access(&self) -> Arc<dyn BufferAccess> {
+        self.clone()
+    }
+
+    #[inline]
+    fn buffer_mut(&mut self) -> Option<&mut Buffer> {
+        Arc::get_mut(self).and_then(|buffer| Arc::get_mut(&mut buffer.inner))
+    }
+}
+
 unsafe impl<T> TypedBufferAccess for DeviceLocalBuffer<T>
 where
     T: BufferContents + ?Sized,
@@ -15,14 +15,14 @@
 
 use super::{
     cpu_access::{ReadLockError, WriteLockError},
-    BufferContents, BufferCreateFlags, BufferUsage,
+    BufferContents, BufferCreateFlags, BufferDeviceAddressError, BufferUsage,
 };
 use crate::{
     device::{Device, DeviceOwned},
     memory::{
         allocator::{AllocationCreationError, MemoryAlloc},
-        DedicatedTo, ExternalMemoryHandleType, ExternalMemoryHandleTypes, MemoryAllocateFlags,
-        MemoryPropertyFlags, MemoryRequirements,
+        DedicatedTo, DeviceMemory, ExternalMemoryHandleType, ExternalMemoryHandleTypes,
+        MemoryAllocateFlags, MemoryPropertyFlags, MemoryRequirements,
     },
     range_map::RangeMap,
     sync::{future::AccessError, CurrentAccess, Sharing},
@@ -37,7 +37,6 @@ use std::{
     mem::{size_of_val, MaybeUninit},
     num::NonZeroU64,
     ops::{Deref, DerefMut, Range},
-    ptr,
     sync::Arc,
 };
 
@@ -51,6 +50,7 @@ pub struct RawBuffer {
     handle: ash::vk::Buffer,
     device: Arc<Device>,
     id: NonZeroU64,
+    imported_handle: bool,
 
     flags: BufferCreateFlags,
     size: DeviceSize,
@@ -70,6 +70,9 @@ impl RawBuffer {
     ///   items.
     /// - Panics if `create_info.size` is zero.
     /// - Panics if `create_info.usage` is empty.
+    /// - Panics if `create_info.flags` contains `SPARSE_RESIDENCY` or `SPARSE_ALIASED`, but not
+    ///   `SPARSE_BINDING`.
+    /// - Panics if `create_info.flags` contains `PROTECTED` as well as any of the sparse flags.
     #[inline]
     pub fn new(
         device: Arc<Device>,
@@ -96,12 +99,29 @@ impl RawBuffer {
             size,
             usage,
             external_memory_handle_types,
+            opaque_capture_address,
             _ne: _,
         } = create_info;
 
         // VUID-VkBufferCreateInfo-flags-parameter
         flags.validate_device(device)?;
 
+        // VUID-VkBufferCreateInfo-flags-03338
+        if flags.intersects(BufferCreateFlags::DEVICE_ADDRESS_CAPTURE_REPLAY)
+            && !device
+                .enabled_features()
+                .buffer_device_address_capture_replay
+        {
+            return Err(BufferError::RequirementNotMet {
+                required_for: "`create_info.flags` contains \
+                    `BufferCreateFlags::DEVICE_ADDRESS_CAPTURE_REPLAY`",
+                requires_one_of: RequiresOneOf {
+                    features: &["buffer_device_address_capture_replay"],
+                    ..Default::default()
+                },
+            });
+        }
+
         // VUID-VkBufferCreateInfo-usage-parameter
         usage.validate_device(device)?;
 
@@ -111,46 +131,74 @@ impl RawBuffer {
         // VUID-VkBufferCreateInfo-size-00912
         assert!(size != 0);
 
-        /* Enable when sparse binding is properly handled
-        if let Some(sparse_level) = sparse {
+        if flags.intersects(BufferCreateFlags::SPARSE_BINDING) {
             // VUID-VkBufferCreateInfo-flags-00915
             if !device.enabled_features().sparse_binding {
                 return Err(BufferError::RequirementNotMet {
-                    required_for: "`create_info.sparse` is `Some`",
+                    required_for:
+                        "`create_info.flags` contains `BufferCreateFlags::SPARSE_BINDING`",
                     requires_one_of: RequiresOneOf {
                         features: &["sparse_binding"],
                         ..Default::default()
                     },
                 });
             }
+        }
 
+        if flags.intersects(BufferCreateFlags::SPARSE_RESIDENCY) {
             // VUID-VkBufferCreateInfo-flags-00916
-            if sparse_level.sparse_residency && !device.enabled_features().sparse_residency_buffer {
+            if !device.enabled_features().sparse_residency_buffer {
                 return Err(BufferError::RequirementNotMet {
-                    required_for: "`create_info.sparse` is `Some(sparse_level)`, where \
-                        `sparse_level` contains `BufferCreateFlags::SPARSE_RESIDENCY`",
+                    required_for: "`create_info.flags` contains \
+                        `BufferCreateFlags::SPARSE_RESIDENCY`",
                     requires_one_of: RequiresOneOf {
                         features: &["sparse_residency_buffer"],
                         ..Default::default()
                     },
                 });
             }
+        }
 
+        if flags.intersects(BufferCreateFlags::SPARSE_ALIASED) {
             // VUID-VkBufferCreateInfo-flags-00917
-            if sparse_level.sparse_aliased && !device.enabled_features().sparse_residency_aliased {
+            if !device.enabled_features().sparse_residency_aliased {
                 return Err(BufferError::RequirementNotMet {
-                    required_for: "`create_info.sparse` is `Some(sparse_level)`, where \
-                        `sparse_level` contains `BufferCreateFlags::SPARSE_ALIASED`",
+                    required_for:
+                        "`create_info.flags` contains `BufferCreateFlags::SPARSE_ALIASED`",
                     requires_one_of: RequiresOneOf {
                         features: &["sparse_residency_aliased"],
                         ..Default::default()
                     },
                 });
             }
+        }
 
-            // VUID-VkBufferCreateInfo-flags-00918
+        // VUID-VkBufferCreateInfo-flags-00918
+        assert!(
+            !flags.intersects(
+                BufferCreateFlags::SPARSE_RESIDENCY | BufferCreateFlags::SPARSE_ALIASED
+            ) || flags.intersects(BufferCreateFlags::SPARSE_BINDING)
+        );
+
+        if flags.intersects(BufferCreateFlags::PROTECTED) {
+            // VUID-VkBufferCreateInfo-flags-01887
+            if !device.enabled_features().protected_memory {
+                return Err(BufferError::RequirementNotMet {
+                    required_for: "`create_info.flags` contains `BufferCreateFlags::PROTECTED`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["protected_memory"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkBufferCreateInfo-None-01888
+            assert!(!flags.intersects(
+                BufferCreateFlags::SPARSE_BINDING
+                    | BufferCreateFlags::SPARSE_RESIDENCY
+                    | BufferCreateFlags::SPARSE_ALIASED
+            ));
         }
-        */
 
         match sharing {
             Sharing::Exclusive => (),
@@ -206,6 +254,26 @@ impl RawBuffer {
             // TODO:
         }
 
+        if opaque_capture_address.is_some() {
+            if !(device.api_version() >= Version::V1_2
+                || device.enabled_extensions().khr_buffer_device_address)
+            {
+                return Err(BufferError::RequirementNotMet {
+                    required_for: "`create_info.opaque_capture_address` is `Some`",
+                    requires_one_of: RequiresOneOf {
+                        api_version: Some(Version::V1_2),
+                        device_extensions: &["khr_buffer_device_address"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkBufferCreateInfo-opaqueCaptureAddress-03337
+            if !flags.intersects(BufferCreateFlags::DEVICE_ADDRESS_CAPTURE_REPLAY) {
+                return Err(BufferError::OpaqueCaptureAddressNoCaptureReplayFlag);
+            }
+        }
+
         Ok(())
     }
 
@@ -220,6 +288,7 @@ impl RawBuffer {
             size,
             usage,
             external_memory_handle_types,
+            opaque_capture_address,
             _ne: _,
         } = &create_info;
 
@@ -255,13 +324,26 @@ impl RawBuffer {
             create_info_vk.p_next = next as *const _ as *const _;
         }
 
+        let mut opaque_capture_address_info_vk =
+            opaque_capture_address.map(|opaque_capture_address| {
+                ash::vk::BufferOpaqueCaptureAddressCreateInfo {
+                    opaque_capture_address,
+                    ..Default::default()
+                }
+            });
+
+        if let Some(next) = opaque_capture_address_info_vk.as_mut() {
+            next.p_next = create_info_vk.p_next;
+            create_info_vk.p_next = next as *const _ as *const _;
+        }
+
         let handle = {
             let fns = device.fns();
             let mut output = MaybeUninit::uninit();
             (fns.v1_0.create_buffer)(
                 device.handle(),
                 &create_info_vk,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -269,7 +351,7 @@ impl RawBuffer {
             output.assume_init()
         };
 
-        Ok(Self::from_handle(device, handle, create_info))
+        Ok(Self::from_handle_inner(device, handle, create_info, false))
     }
 
     /// Creates a new `RawBuffer` from a raw object handle.
@@ -279,11 +361,22 @@ impl RawBuffer {
     /// - `handle` must be a valid Vulkan object handle created from `device`.
     /// - `handle` must refer to a buffer that has not yet had memory bound to it.
     /// - `create_info` must match the info used to create the object.
+    /// - `handle` must have been created with a null `pAllocator`, as it is destroyed with a null
+    ///   `pAllocator` when dropped.
     #[inline]
     pub unsafe fn from_handle(
         device: Arc<Device>,
         handle: ash::vk::Buffer,
         create_info: BufferCreateInfo,
+    ) -> Self {
+        Self::from_handle_inner(device, handle, create_info, true)
+    }
+
+    unsafe fn from_handle_inner(
+        device: Arc<Device>,
+        handle: ash::vk::Buffer,
+        create_info: BufferCreateInfo,
+        imported_handle: bool,
     ) -> Self {
         fn align(val: DeviceSize, al: DeviceSize) -> DeviceSize {
             al * (1 + (val - 1) / al)
@@ -295,6 +388,7 @@ impl RawBuffer {
             usage,
             sharing,
             external_memory_handle_types,
+            opaque_capture_address: _,
             _ne: _,
         } = create_info;
 
@@ -330,6 +424,7 @@ impl RawBuffer {
             handle,
             device,
             id: Self::next_id(),
+            imported_handle,
             flags,
             size,
             usage,
@@ -437,8 +532,9 @@ impl RawBuffer {
         // Ensured by taking ownership of `RawBuffer`.
 
         // VUID-VkBindBufferMemoryInfo-buffer-01030
-        // Currently ensured by not having sparse binding flags, but this needs to be checked once
-        // those are enabled.
+        if self.flags.intersects(BufferCreateFlags::SPARSE_BINDING) {
+            return Err(BufferError::SparseBindingEnabled);
+        }
 
         // VUID-VkBindBufferMemoryInfo-memoryOffset-01031
         // Assume that `allocation` was created correctly.
@@ -481,14 +577,17 @@ impl RawBuffer {
             }
         }
 
+        // VUID-VkBindBufferMemoryInfo-None-01898
         // VUID-VkBindBufferMemoryInfo-None-01899
-        if memory_type
+        let buffer_protected = self.flags.intersects(BufferCreateFlags::PROTECTED);
+        let memory_protected = memory_type
             .property_flags
-            .intersects(MemoryPropertyFlags::PROTECTED)
-        {
+            .intersects(MemoryPropertyFlags::PROTECTED);
+
+        if buffer_protected != memory_protected {
             return Err(BufferError::MemoryProtectedMismatch {
-                buffer_protected: false,
-                memory_protected: true,
+                buffer_protected,
+                memory_protected,
             });
         }
 
@@ -579,6 +678,23 @@ impl RawBuffer {
         Ok(Buffer::from_raw(self, BufferMemory::Normal(allocation)))
     }
 
+    /// Converts the raw buffer into a sparse `Buffer`, without binding any memory to it.
+    ///
+    /// The buffer must have been created with the [`BufferCreateFlags::SPARSE_BINDING`] flag.
+    /// Memory can then be bound to the returned buffer with [`bind_sparse`]. If the buffer was not
+    /// also created with the [`BufferCreateFlags::SPARSE_RESIDENCY`] flag, then it must be fully
+    /// bound before it is used, otherwise submitting a command buffer that uses it returns an
+    /// error.
+    ///
+    /// [`bind_sparse`]: crate::device::QueueGuard::bind_sparse
+    pub fn into_sparse(self) -> Result<Buffer, (BufferError, RawBuffer)> {
+        if !self.flags.intersects(BufferCreateFlags::SPARSE_BINDING) {
+            return Err((BufferError::SparseBindingNotEnabled, self));
+        }
+
+        Ok(Buffer::from_raw(self, BufferMemory::Sparse))
+    }
+
     /// Returns the memory requirements for this buffer.
     pub fn memory_requirements(&self) -> &MemoryRequirements {
         &self.memory_requirements
@@ -620,7 +736,12 @@ impl Drop for RawBuffer {
     fn drop(&mut self) {
         unsafe {
             let fns = self.device.fns();
-            (fns.v1_0.destroy_buffer)(self.device.handle(), self.handle, ptr::null());
+            (fns.v1_0.destroy_buffer)(
+                self.device.handle(),
+                self.handle,
+                self.device
+                    .destroy_allocation_callbacks(self.imported_handle),
+            );
         }
     }
 }
@@ -676,6 +797,19 @@ pub struct BufferCreateInfo {
     /// [`khr_external_memory`]: crate::device::DeviceExtensions::khr_external_memory
     pub external_memory_handle_types: ExternalMemoryHandleTypes,
 
+    /// The opaque capture address to create the buffer with, when replaying a previously
+    /// captured buffer.
+    ///
+    /// The value should be one that was previously returned by
+    /// [`Buffer::opaque_capture_address`]. If `Some`, `flags` must contain
+    /// [`BufferCreateFlags::DEVICE_ADDRESS_CAPTURE_REPLAY`], and the device API version must be at
+    /// least 1.2, or the [`khr_buffer_device_address`] extension must be enabled on the device.
+    ///
+    /// The default value is `None`.
+    ///
+    /// [`khr_buffer_device_address`]: crate::device::DeviceExtensions::khr_buffer_device_address
+    pub opaque_capture_address: Option<u64>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -688,6 +822,7 @@ impl Default for BufferCreateInfo {
             size: 0,
             usage: BufferUsage::empty(),
             external_memory_handle_types: ExternalMemoryHandleTypes::empty(),
+            opaque_capture_address: None,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -770,6 +905,101 @@ impl Buffer {
         self.inner.external_memory_handle_types
     }
 
+    /// Returns whether all of `range` currently has memory bound to it.
+    ///
+    /// For a buffer backed by sparse memory, `range` is given in bytes from the start of the
+    /// buffer's memory, and the residency reflects all sparse bind operations that have been
+    /// submitted with [`bind_sparse`] so far. Buffers backed by normal memory are always fully
+    /// resident.
+    ///
+    /// [`bind_sparse`]: crate::device::QueueGuard::bind_sparse
+    #[inline]
+    pub fn is_resident(&self, range: Range<DeviceSize>) -> bool {
+        match self.memory {
+            BufferMemory::Normal(_) => true,
+            BufferMemory::Sparse => range.is_empty() || self.state().is_resident(&range),
+        }
+    }
+
+    /// Returns the opaque capture address of the buffer, which can be passed to
+    /// [`BufferCreateInfo::opaque_capture_address`] to create a buffer with the same device
+    /// address on a subsequent run.
+    ///
+    /// The [`buffer_device_address_capture_replay`] feature must be enabled on the device, and
+    /// the buffer must have been created with the
+    /// [`SHADER_DEVICE_ADDRESS`](BufferUsage::SHADER_DEVICE_ADDRESS) usage. If the buffer was not
+    /// created with the [`BufferCreateFlags::DEVICE_ADDRESS_CAPTURE_REPLAY`] flag, then the
+    /// returned value is `0`.
+    ///
+    /// [`buffer_device_address_capture_replay`]: crate::device::Features::buffer_device_address_capture_replay
+    #[inline]
+    pub fn opaque_capture_address(&self) -> Result<u64, BufferDeviceAddressError> {
+        self.validate_opaque_capture_address()?;
+
+        unsafe { Ok(self.opaque_capture_address_unchecked()) }
+    }
+
+    fn validate_opaque_capture_address(&self) -> Result<(), BufferDeviceAddressError> {
+        let device = self.device();
+
+        if !(device.api_version() >= Version::V1_2
+            || device.enabled_extensions().khr_buffer_device_address)
+        {
+            return Err(BufferDeviceAddressError::RequirementNotMet {
+                required_for: "`Buffer::opaque_capture_address`",
+                requires_one_of: RequiresOneOf {
+                    api_version: Some(Version::V1_2),
+                    device_extensions: &["khr_buffer_device_address"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkGetBufferOpaqueCaptureAddress-None-03326
+        if !device
+            .enabled_features()
+            .buffer_device_address_capture_replay
+        {
+            return Err(BufferDeviceAddressError::RequirementNotMet {
+                required_for: "`Buffer::opaque_capture_address`",
+                requires_one_of: RequiresOneOf {
+                    features: &["buffer_device_address_capture_replay"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-VkBufferDeviceAddressInfo-buffer-02601
+        if !self
+            .inner
+            .usage
+            .intersects(BufferUsage::SHADER_DEVICE_ADDRESS)
+        {
+            return Err(BufferDeviceAddressError::BufferMissingUsage);
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn opaque_capture_address_unchecked(&self) -> u64 {
+        let device = self.device();
+        let info_vk = ash::vk::BufferDeviceAddressInfo {
+            buffer: self.handle(),
+            ..Default::default()
+        };
+
+        let fns = device.fns();
+
+        if device.api_version() >= Version::V1_2 {
+            (fns.v1_2.get_buffer_opaque_capture_address)(device.handle(), &info_vk)
+        } else {
+            (fns.khr_buffer_device_address
+                .get_buffer_opaque_capture_address_khr)(device.handle(), &info_vk)
+        }
+    }
+
     /// Locks the buffer in order to read its content from the host.
     ///
     /// If the buffer is currently used in exclusive mode by the device, this function will return
@@ -784,7 +1014,7 @@ impl Buffer {
 
         let allocation = match &self.memory {
             BufferMemory::Normal(a) => a,
-            BufferMemory::Sparse => todo!("`Buffer::read` doesn't support sparse binding yet"),
+            BufferMemory::Sparse => return Err(BufferError::SparseMemoryNotHostAccessible),
         };
 
         if allocation.mapped_ptr().is_none() {
@@ -828,7 +1058,7 @@ impl Buffer {
 
         let allocation = match &self.memory {
             BufferMemory::Normal(a) => a,
-            BufferMemory::Sparse => todo!("`Buffer::write` doesn't support sparse binding yet"),
+            BufferMemory::Sparse => return Err(BufferError::SparseMemoryNotHostAccessible),
         };
 
         if allocation.mapped_ptr().is_none() {
@@ -894,6 +1124,9 @@ impl Hash for Buffer {
 #[derive(Debug)]
 pub(crate) struct BufferState {
     ranges: RangeMap<DeviceSize, BufferRangeState>,
+
+    // For sparse buffers, the memory that is bound to each range of the buffer.
+    sparse_residency: RangeMap<DeviceSize, Arc<DeviceMemory>>,
 }
 
 impl BufferState {
@@ -910,6 +1143,36 @@ impl BufferState {
             )]
             .into_iter()
             .collect(),
+            sparse_residency: RangeMap::new(),
+        }
+    }
+
+    pub(crate) fn is_resident(&self, range: &Range<DeviceSize>) -> bool {
+        self.sparse_residency.contains_all(range)
+    }
+
+    /// Records that `memory` has been bound to `range` of a sparse buffer, or that the range has
+    /// been unbound if `memory` is `None`. Memory that was previously bound to the range is pushed
+    /// onto `unbound_memory`.
+    pub(crate) fn bind_sparse(
+        &mut self,
+        range: Range<DeviceSize>,
+        memory: Option<&Arc<DeviceMemory>>,
+        unbound_memory: &mut Vec<Arc<DeviceMemory>>,
+    ) {
+        if range.is_empty() {
+            return;
+        }
+
+        unbound_memory.extend(
+            self.sparse_residency
+                .range(&range)
+                .map(|(_, memory)| memory.clone()),
+        );
+
+        match memory {
+            Some(memory) => self.sparse_residency.insert(range, memory.clone()),
+            None => self.sparse_residency.remove(range),
         }
     }
 
@@ -1255,12 +1518,27 @@ pub enum BufferError {
         allowed_memory_type_bits: u32,
     },
 
+    /// `opaque_capture_address` was `Some`, but `flags` did not contain
+    /// `BufferCreateFlags::DEVICE_ADDRESS_CAPTURE_REPLAY`.
+    OpaqueCaptureAddressNoCaptureReplayFlag,
+
     /// The sharing mode was set to `Concurrent`, but one of the specified queue family indices was
     /// out of range.
     SharingQueueFamilyIndexOutOfRange {
         queue_family_index: u32,
         queue_family_count: u32,
     },
+
+    /// The buffer was created with the `SPARSE_BINDING` flag, so its memory must be bound with
+    /// sparse bind operations instead.
+    SparseBindingEnabled,
+
+    /// The buffer was not created with the `SPARSE_BINDING` flag.
+    SparseBindingNotEnabled,
+
+    /// The buffer is backed by sparse memory, which can't be accessed from the host through the
+    /// buffer.
+    SparseMemoryNotHostAccessible,
 }
 
 impl Error for BufferError {
@@ -1376,11 +1654,30 @@ impl Display for BufferError {
                 Ok(())
             })
             .and_then(|_| write!(f, ") that can be bound to this buffer")),
+            Self::OpaqueCaptureAddressNoCaptureReplayFlag => write!(
+                f,
+                "`opaque_capture_address` was `Some`, but `flags` did not contain \
+                `BufferCreateFlags::DEVICE_ADDRESS_CAPTURE_REPLAY`",
+            ),
             Self::SharingQueueFamilyIndexOutOfRange { .. } => write!(
                 f,
                 "the sharing mode was set to `Concurrent`, but one of the specified queue family \
                 indices was out of range",
             ),
+            Self::SparseBindingEnabled => write!(
+                f,
+                "the buffer was created with the `SPARSE_BINDING` flag, so its memory must be \
+                bound with sparse bind operations instead",
+            ),
+            Self::SparseBindingNotEnabled => write!(
+                f,
+                "the buffer was not created with the `SPARSE_BINDING` flag",
+            ),
+            Self::SparseMemoryNotHostAccessible => write!(
+                f,
+                "the buffer is backed by sparse memory, which can't be accessed from the host \
+                through the buffer",
+            ),
         }
     }
 }
@@ -1428,8 +1725,15 @@ impl From<WriteLockError> for BufferError {
 
 #[cfg(test)]
 mod tests {
-    use super::{BufferCreateInfo, BufferUsage, RawBuffer};
-    use crate::device::{Device, DeviceOwned};
+    use super::{
+        BufferCreateFlags, BufferCreateInfo, BufferError, BufferState, BufferUsage, RawBuffer,
+    };
+    use crate::{
+        device::{Device, DeviceOwned},
+        memory::{DeviceMemory, MemoryAllocateInfo},
+        RequiresOneOf,
+    };
+    use std::sync::Arc;
 
     #[test]
     fn create() {
@@ -1450,16 +1754,33 @@ mod tests {
         assert_eq!(&**buf.device() as *const Device, &*device as *const Device);
     }
 
-    /* Re-enable when sparse binding is properly implemented
+    #[test]
+    fn missing_feature_capture_replay() {
+        let (device, _) = gfx_dev_and_queue!();
+        match RawBuffer::new(
+            device,
+            BufferCreateInfo {
+                flags: BufferCreateFlags::DEVICE_ADDRESS_CAPTURE_REPLAY,
+                size: 128,
+                usage: BufferUsage::SHADER_DEVICE_ADDRESS,
+                opaque_capture_address: Some(0x1000),
+                ..Default::default()
+            },
+        ) {
+            Err(BufferError::RequirementNotMet { .. }) => (),
+            _ => panic!(),
+        }
+    }
+
     #[test]
     fn missing_feature_sparse_binding() {
         let (device, _) = gfx_dev_and_queue!();
         match RawBuffer::new(
             device,
             BufferCreateInfo {
+                flags: BufferCreateFlags::SPARSE_BINDING,
                 size: 128,
-                sparse: Some(BufferCreateFlags::empty()),
-                usage: BufferUsage::transfer_dst,
+                usage: BufferUsage::TRANSFER_DST,
                 ..Default::default()
             },
         ) {
@@ -1477,13 +1798,9 @@ mod tests {
         match RawBuffer::new(
             device,
             BufferCreateInfo {
+                flags: BufferCreateFlags::SPARSE_BINDING | BufferCreateFlags::SPARSE_RESIDENCY,
                 size: 128,
-                sparse: Some(BufferCreateFlags {
-                    sparse_residency: true,
-                    sparse_aliased: false,
-                    ..Default::default()
-                }),
-                usage: BufferUsage::transfer_dst,
+                usage: BufferUsage::TRANSFER_DST,
                 ..Default::default()
             },
         ) {
@@ -1501,13 +1818,9 @@ mod tests {
         match RawBuffer::new(
             device,
             BufferCreateInfo {
+                flags: BufferCreateFlags::SPARSE_BINDING | BufferCreateFlags::SPARSE_ALIASED,
                 size: 128,
-                sparse: Some(BufferCreateFlags {
-                    sparse_residency: false,
-                    sparse_aliased: true,
-                    ..Default::default()
-                }),
-                usage: BufferUsage::transfer_dst,
+                usage: BufferUsage::TRANSFER_DST,
                 ..Default::default()
             },
         ) {
@@ -1518,7 +1831,24 @@ mod tests {
             _ => panic!(),
         }
     }
-    */
+
+    #[test]
+    fn missing_feature_protected_memory() {
+        let (device, _) = gfx_dev_and_queue!();
+        match RawBuffer::new(
+            device,
+            BufferCreateInfo {
+                flags: BufferCreateFlags::PROTECTED,
+                size: 128,
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+        ) {
+            // Either the API version or the feature is missing.
+            Err(BufferError::RequirementNotMet { .. }) => (),
+            _ => panic!(),
+        }
+    }
 
     #[test]
     fn create_empty_buffer() {
@@ -1535,4 +1865,52 @@ mod tests {
             )
         });
     }
+
+    #[test]
+    fn bind_sparse_residency() {
+        let (device, _) = gfx_dev_and_queue!();
+        let allocate = || {
+            Arc::new(
+                DeviceMemory::allocate(
+                    device.clone(),
+                    MemoryAllocateInfo {
+                        allocation_size: 1024,
+                        memory_type_index: 0,
+                        ..Default::default()
+                    },
+                )
+                .unwrap(),
+            )
+        };
+        let (memory_a, memory_b) = (allocate(), allocate());
+
+        let mut state = BufferState::new(1024);
+        let mut unbound_memory = Vec::new();
+        assert!(!state.is_resident(&(0..1)));
+
+        state.bind_sparse(0..512, Some(&memory_a), &mut unbound_memory);
+        assert!(state.is_resident(&(0..512)));
+        assert!(!state.is_resident(&(0..1024)));
+        assert!(unbound_memory.is_empty());
+
+        // Binding over a bound range hands back the memory that was bound there before.
+        state.bind_sparse(256..1024, Some(&memory_b), &mut unbound_memory);
+        assert!(state.is_resident(&(0..1024)));
+        assert_eq!(unbound_memory.len(), 1);
+        assert!(Arc::ptr_eq(&unbound_memory[0], &memory_a));
+
+        unbound_memory.clear();
+        state.bind_sparse(0..128, None, &mut unbound_memory);
+        assert!(!state.is_resident(&(0..1024)));
+        assert!(state.is_resident(&(128..1024)));
+        assert_eq!(unbound_memory.len(), 1);
+        assert!(Arc::ptr_eq(&unbound_memory[0], &memory_a));
+
+        // Unbinding a range that has nothing bound, or an empty range, does nothing.
+        unbound_memory.clear();
+        state.bind_sparse(0..128, None, &mut unbound_memory);
+        state.bind_sparse(512..512, None, &mut unbound_memory);
+        assert!(unbound_memory.is_empty());
+        assert!(state.is_resident(&(128..1024)));
+    }
 }
@@ -69,41 +69,38 @@ vulkan_bitflags! {
         device_extensions: [khr_video_decode_queue],
     },*/
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be bound as a transform feedback buffer, to capture the vertex data that is
+    /// output by a shader.
     TRANSFORM_FEEDBACK_BUFFER = TRANSFORM_FEEDBACK_BUFFER_EXT {
         device_extensions: [ext_transform_feedback],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be used as a counter buffer for transform feedback, or as the source of the
+    /// byte count in an indirect draw.
     TRANSFORM_FEEDBACK_COUNTER_BUFFER = TRANSFORM_FEEDBACK_COUNTER_BUFFER_EXT {
         device_extensions: [ext_transform_feedback],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be used as the predicate for conditional rendering.
     CONDITIONAL_RENDERING = CONDITIONAL_RENDERING_EXT {
         device_extensions: [ext_conditional_rendering],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
-    ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY_KHR = ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY_KHR {
+    /// The buffer can be used as a read-only input for building an acceleration structure: as
+    /// vertex, index, transform, AABB or instance data.
+    ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY = ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY_KHR {
         device_extensions: [khr_acceleration_structure],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be used to store an acceleration structure.
     ACCELERATION_STRUCTURE_STORAGE = ACCELERATION_STRUCTURE_STORAGE_KHR {
         device_extensions: [khr_acceleration_structure],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be used as a shader binding table for ray tracing.
     SHADER_BINDING_TABLE = SHADER_BINDING_TABLE_KHR {
         device_extensions: [khr_ray_tracing_pipeline, nv_ray_tracing],
-    },*/
+    },
 
     /* TODO: enable
     // TODO: document
@@ -56,7 +56,6 @@ use std::{
     mem::MaybeUninit,
     num::NonZeroU64,
     ops::Range,
-    ptr,
     sync::Arc,
 };
 
@@ -235,7 +234,7 @@ where
             (fns.v1_0.create_buffer_view)(
                 device.handle(),
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -269,7 +268,7 @@ where
             (fns.v1_0.destroy_buffer_view)(
                 self.buffer.inner().buffer.device().handle(),
                 self.handle,
-                ptr::null(),
+                self.buffer.inner().buffer.device().allocation_callbacks(),
             );
         }
     }
@@ -22,7 +22,7 @@ use super::{
 };
 use crate::{
     device::{Device, DeviceOwned},
-    OomError,
+    OomError, RequiresOneOf, Version,
 };
 use crossbeam_queue::ArrayQueue;
 use smallvec::{IntoIter, SmallVec};
@@ -135,13 +135,58 @@ pub struct StandardCommandBufferAllocator {
 
 impl StandardCommandBufferAllocator {
     /// Creates a new `StandardCommandBufferAllocator`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.protected` is set, but the requirements for protected command
+    ///   pools are not met. Use [`try_new`] to get an error instead.
+    ///
+    /// [`try_new`]: Self::try_new
     #[inline]
     pub fn new(device: Arc<Device>, create_info: StandardCommandBufferAllocatorCreateInfo) -> Self {
-        StandardCommandBufferAllocator {
+        Self::try_new(device, create_info).unwrap()
+    }
+
+    /// Creates a new `StandardCommandBufferAllocator`, returning an error if
+    /// `create_info.protected` is set but the requirements for protected command pools are not
+    /// met.
+    ///
+    /// Protected command pools require the device API version to be at least 1.1, and the
+    /// [`protected_memory`] feature to be enabled on the device.
+    ///
+    /// [`protected_memory`]: crate::device::Features::protected_memory
+    pub fn try_new(
+        device: Arc<Device>,
+        create_info: StandardCommandBufferAllocatorCreateInfo,
+    ) -> Result<Self, CommandPoolCreationError> {
+        if create_info.protected {
+            if device.api_version() < Version::V1_1 {
+                return Err(CommandPoolCreationError::RequirementNotMet {
+                    required_for: "`create_info.protected` is set",
+                    requires_one_of: RequiresOneOf {
+                        api_version: Some(Version::V1_1),
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkCommandPoolCreateInfo-flags-02860
+            if !device.enabled_features().protected_memory {
+                return Err(CommandPoolCreationError::RequirementNotMet {
+                    required_for: "`create_info.protected` is set",
+                    requires_one_of: RequiresOneOf {
+                        features: &["protected_memory"],
+                        ..Default::default()
+                    },
+                });
+            }
+        }
+
+        Ok(StandardCommandBufferAllocator {
             device,
             pools: ThreadLocal::new(),
             create_info,
-        }
+        })
     }
 
     /// Tries to reset the [`CommandPool`] that's currently in use for the given queue family index
@@ -356,6 +401,7 @@ impl Pool {
             device,
             CommandPoolCreateInfo {
                 queue_family_index,
+                protected: create_info.protected,
                 ..Default::default()
             },
         )
@@ -364,6 +410,8 @@ impl Pool {
             // We check that the provided queue family index is active on the device, so it can't
             // be out of range.
             CommandPoolCreationError::QueueFamilyIndexOutOfRange { .. } => unreachable!(),
+            // We check the requirements for protected pools when creating the allocator.
+            CommandPoolCreationError::RequirementNotMet { .. } => unreachable!(),
         })?;
 
         let primary_pool = if create_info.primary_buffer_count > 0 {
@@ -532,6 +580,15 @@ pub struct StandardCommandBufferAllocatorCreateInfo {
     /// The default value is `256`.
     pub secondary_buffer_count: usize,
 
+    /// Whether the command buffers allocated by the allocator are protected.
+    ///
+    /// If set to `true`, the device API version must be at least 1.1, and the
+    /// [`protected_memory`](crate::device::Features::protected_memory) feature must be enabled on
+    /// the device.
+    ///
+    /// The default value is `false`.
+    pub protected: bool,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -541,6 +598,7 @@ impl Default for StandardCommandBufferAllocatorCreateInfo {
         StandardCommandBufferAllocatorCreateInfo {
             primary_buffer_count: 256,
             secondary_buffer_count: 256,
+            protected: false,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -687,4 +745,24 @@ mod tests {
         .join()
         .unwrap();
     }
+
+    #[test]
+    fn protected_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        if device.enabled_features().protected_memory {
+            return;
+        }
+
+        assert!(matches!(
+            StandardCommandBufferAllocator::try_new(
+                device,
+                StandardCommandBufferAllocatorCreateInfo {
+                    protected: true,
+                    ..Default::default()
+                },
+            ),
+            Err(CommandPoolCreationError::RequirementNotMet { .. }),
+        ));
+    }
 }
@@ -17,13 +17,15 @@ use super::{
     CommandBufferExecError, CommandBufferInheritanceInfo, CommandBufferInheritanceRenderPassInfo,
     CommandBufferInheritanceRenderPassType, CommandBufferLevel, CommandBufferResourcesUsage,
     CommandBufferState, CommandBufferUsage, PrimaryCommandBufferAbstract, RenderingAttachmentInfo,
-    SecondaryCommandBufferAbstract, SecondaryCommandBufferResourcesUsage, SubpassContents,
+    ResourceUseRef, SecondaryCommandBufferAbstract, SecondaryCommandBufferResourcesUsage,
+    SubpassContents,
 };
 use crate::{
+    buffer::BufferCreateFlags,
     command_buffer::CommandBufferInheritanceRenderingInfo,
     device::{Device, DeviceOwned, QueueFamilyProperties},
     format::{Format, FormatFeatures},
-    image::ImageAspects,
+    image::{ImageAspects, ImageCreateFlags},
     query::{QueryControlFlags, QueryType},
     render_pass::{Framebuffer, Subpass},
     OomError, RequirementNotMet, RequiresOneOf, VulkanObject,
@@ -67,6 +69,17 @@ where
     // If any queries are active, this hashmap contains their state.
     pub(super) query_state: HashMap<ash::vk::QueryType, QueryState>,
 
+    // If conditional rendering is active, contains its state.
+    pub(super) conditional_rendering_state: Option<ConditionalRenderingState>,
+
+    // Whether the first render pass instance of the command buffer resumes a render pass instance
+    // that was suspended in an earlier command buffer.
+    // `None` if no render pass instance has been begun yet.
+    pub(super) resumes_render_pass: Option<bool>,
+
+    // Whether the last render pass instance of the command buffer was suspended.
+    pub(super) suspends_render_pass: bool,
+
     _data: PhantomData<L>,
 }
 
@@ -107,6 +120,7 @@ pub(super) struct BeginRenderingState {
     pub(super) depth_attachment_format: Option<Format>,
     pub(super) stencil_attachment_format: Option<Format>,
     pub(super) pipeline_used: bool,
+    pub(super) suspending: bool,
 }
 
 pub(super) struct BeginRenderingAttachments {
@@ -124,6 +138,10 @@ pub(super) struct QueryState {
     pub(super) in_subpass: bool,
 }
 
+pub(super) struct ConditionalRenderingState {
+    pub(super) in_subpass: bool,
+}
+
 impl<A> AutoCommandBufferBuilder<PrimaryAutoCommandBuffer, A>
 where
     A: CommandBufferAllocator,
@@ -212,6 +230,7 @@ where
                 ref render_pass,
                 occlusion_query: _,
                 query_statistics_flags: _,
+                conditional_rendering: _,
                 _ne: _,
             } = inheritance_info;
 
@@ -251,6 +270,7 @@ where
                                 depth_attachment_format: info.depth_attachment_format,
                                 stencil_attachment_format: info.stencil_attachment_format,
                                 pipeline_used: false,
+                                suspending: false,
                             }
                             .into(),
                             view_mask: info.view_mask,
@@ -273,6 +293,9 @@ where
             queue_family_index,
             render_pass_state,
             query_state: HashMap::default(),
+            conditional_rendering_state: None,
+            resumes_render_pass: None,
+            suspends_render_pass: false,
             inheritance_info,
             usage,
             _data: PhantomData,
@@ -301,6 +324,7 @@ where
                 ref render_pass,
                 occlusion_query,
                 query_statistics_flags,
+                conditional_rendering,
                 _ne: _,
             } = inheritance_info;
 
@@ -501,6 +525,17 @@ where
                     },
                 });
             }
+
+            // VUID-VkCommandBufferInheritanceConditionalRenderingInfoEXT-conditionalRenderingEnable-01977
+            if conditional_rendering && !device.enabled_features().inherited_conditional_rendering {
+                return Err(CommandBufferBeginError::RequirementNotMet {
+                    required_for: "`inheritance_info.conditional_rendering` is `true`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["inherited_conditional_rendering"],
+                        ..Default::default()
+                    },
+                });
+            }
         } else {
             debug_assert!(level == CommandBufferLevel::Primary);
 
@@ -619,10 +654,53 @@ where
             return Err(BuildError::QueryActive);
         }
 
+        // VUID-vkEndCommandBuffer-None-01978
+        if self.conditional_rendering_state.is_some() {
+            return Err(BuildError::ConditionalRenderingActive);
+        }
+
+        let protected = self.protected();
+        let protected_no_fault = self.protected_no_fault();
+        let inner = self.inner.build()?;
+
+        for usage in &inner.resources_usage().buffers {
+            let resource_protected = usage
+                .buffer
+                .flags()
+                .intersects(BufferCreateFlags::PROTECTED);
+
+            for (_range, range_usage) in usage.ranges.iter() {
+                validate_protected_access(
+                    protected,
+                    protected_no_fault,
+                    resource_protected,
+                    range_usage.mutable,
+                    range_usage.first_use,
+                )?;
+            }
+        }
+
+        for usage in &inner.resources_usage().images {
+            let resource_protected = usage.image.flags().intersects(ImageCreateFlags::PROTECTED);
+
+            for (_range, range_usage) in usage.ranges.iter() {
+                validate_protected_access(
+                    protected,
+                    protected_no_fault,
+                    resource_protected,
+                    range_usage.mutable,
+                    range_usage.first_use,
+                )?;
+            }
+        }
+
         Ok(PrimaryAutoCommandBuffer {
-            inner: self.inner.build()?,
+            inner,
             _alloc: self.builder_alloc.into_alloc(),
+            protected,
             usage: self.usage,
+            resumes_render_pass: self.resumes_render_pass.unwrap_or(false),
+            suspends_render_pass: self.suspends_render_pass,
 
             state: Mutex::new(Default::default()),
         })
@@ -639,6 +717,45 @@ where
             return Err(BuildError::QueryActive);
         }
 
+        // VUID-vkEndCommandBuffer-None-01978
+        if self.conditional_rendering_state.is_some() {
+            return Err(BuildError::ConditionalRenderingActive);
+        }
+
+        let protected = self.protected();
+        let protected_no_fault = self.protected_no_fault();
+        let inner = self.inner.build()?;
+
+        for usage in &inner.secondary_resources_usage().buffers {
+            validate_protected_access(
+                protected,
+                protected_no_fault,
+                usage
+                    .buffer
+                    .inner()
+                    .buffer
+                    .flags()
+                    .intersects(BufferCreateFlags::PROTECTED),
+                usage.memory.exclusive,
+                usage.use_ref,
+            )?;
+        }
+
+        for usage in &inner.secondary_resources_usage().images {
+            validate_protected_access(
+                protected,
+                protected_no_fault,
+                usage
+                    .image
+                    .inner()
+                    .image
+                    .flags()
+                    .intersects(ImageCreateFlags::PROTECTED),
+                usage.memory.exclusive,
+                usage.use_ref,
+            )?;
+        }
+
         let submit_state = match self.usage {
             CommandBufferUsage::MultipleSubmit => SubmitState::ExclusiveUse {
                 in_use: AtomicBool::new(false),
@@ -650,8 +767,9 @@ where
         };
 
         Ok(SecondaryAutoCommandBuffer {
-            inner: self.inner.build()?,
+            inner,
             _alloc: self.builder_alloc.into_alloc(),
+            protected,
             usage: self.usage,
             inheritance_info: self.inheritance_info.unwrap(),
             submit_state,
@@ -669,6 +787,19 @@ pub enum BuildError {
 
     /// A query is still active on the command buffer.
     QueryActive,
+
+    /// Conditional rendering is still active on the command buffer.
+    ConditionalRenderingActive,
+
+    /// The command buffer is unprotected, but it accesses a protected resource.
+    ProtectedResourceAccessed {
+        use_ref: ResourceUseRef,
+    },
+
+    /// The command buffer is protected, but it writes to an unprotected resource.
+    UnprotectedResourceWritten {
+        use_ref: ResourceUseRef,
+    },
 }
 
 impl Error for BuildError {
@@ -688,6 +819,22 @@ impl Display for BuildError {
                 write!(f, "a render pass is still active on the command buffer")
             }
             Self::QueryActive => write!(f, "a query is still active on the command buffer"),
+            Self::ConditionalRenderingActive => write!(
+                f,
+                "conditional rendering is still active on the command buffer",
+            ),
+            Self::ProtectedResourceAccessed { use_ref } => write!(
+                f,
+                "the command buffer is unprotected, but command {} (`{}`) accesses a protected \
+                resource",
+                use_ref.command_index, use_ref.command_name,
+            ),
+            Self::UnprotectedResourceWritten { use_ref } => write!(
+                f,
+                "the command buffer is protected, but command {} (`{}`) writes to an unprotected \
+                resource",
+                use_ref.command_index, use_ref.command_name,
+            ),
         }
     }
 }
@@ -698,6 +845,33 @@ impl From<OomError> for BuildError {
     }
 }
 
+fn validate_protected_access(
+    protected: bool,
+    protected_no_fault: bool,
+    resource_protected: bool,
+    mutable: bool,
+    use_ref: ResourceUseRef,
+) -> Result<(), BuildError> {
+    if protected {
+        // VUID-vkCmdDraw-commandBuffer-02712
+        // VUID-vkCmdDraw-commandBuffer-02713
+        // VUID-vkCmdCopyBuffer-commandBuffer-01823
+        // VUID-vkCmdCopyImage-commandBuffer-01827
+        if mutable && !resource_protected {
+            return Err(BuildError::UnprotectedResourceWritten { use_ref });
+        }
+    } else {
+        // VUID-vkCmdDraw-commandBuffer-02707
+        // VUID-vkCmdCopyBuffer-commandBuffer-01822
+        // VUID-vkCmdCopyImage-commandBuffer-01825
+        if resource_protected && !protected_no_fault {
+            return Err(BuildError::ProtectedResourceAccessed { use_ref });
+        }
+    }
+
+    Ok(())
+}
+
 impl<L, A> AutoCommandBufferBuilder<L, A>
 where
     A: CommandBufferAllocator,
@@ -706,6 +880,21 @@ where
         &self.device().physical_device().queue_family_properties()[self.queue_family_index as usize]
     }
 
+    /// Returns whether the command buffer is protected.
+    #[inline]
+    pub fn protected(&self) -> bool {
+        self.builder_alloc.inner().protected()
+    }
+
+    // Whether an unprotected command buffer is allowed to access protected resources.
+    fn protected_no_fault(&self) -> bool {
+        self.device()
+            .physical_device()
+            .properties()
+            .protected_no_fault
+            .unwrap_or(false)
+    }
+
     /// Returns the binding/setting state.
     pub fn state(&self) -> CommandBufferBuilderState<'_> {
         self.inner.state()
@@ -724,7 +913,10 @@ where
 pub struct PrimaryAutoCommandBuffer<A = StandardCommandBufferAlloc> {
     inner: SyncCommandBuffer,
     _alloc: A, // Safety: must be dropped after `inner`
+    protected: bool,
     usage: CommandBufferUsage,
+    resumes_render_pass: bool,
+    suspends_render_pass: bool,
 
     state: Mutex<CommandBufferState>,
 }
@@ -751,6 +943,10 @@ where
         self.usage
     }
 
+    fn protected(&self) -> bool {
+        self.protected
+    }
+
     fn state(&self) -> MutexGuard<'_, CommandBufferState> {
         self.state.lock()
     }
@@ -758,11 +954,20 @@ where
     fn resources_usage(&self) -> &CommandBufferResourcesUsage {
         self.inner.resources_usage()
     }
+
+    fn resumes_render_pass(&self) -> bool {
+        self.resumes_render_pass
+    }
+
+    fn suspends_render_pass(&self) -> bool {
+        self.suspends_render_pass
+    }
 }
 
 pub struct SecondaryAutoCommandBuffer<A = StandardCommandBufferAlloc> {
     inner: SyncCommandBuffer,
     _alloc: A, // Safety: must be dropped after `inner`
+    protected: bool,
     usage: CommandBufferUsage,
     inheritance_info: CommandBufferInheritanceInfo,
 
@@ -792,6 +997,10 @@ where
         self.usage
     }
 
+    fn protected(&self) -> bool {
+        self.protected
+    }
+
     fn inheritance_info(&self) -> &CommandBufferInheritanceInfo {
         &self.inheritance_info
     }
@@ -865,8 +1074,10 @@ mod tests {
     use crate::{
         buffer::{BufferUsage, CpuAccessibleBuffer},
         command_buffer::{
-            synced::SyncCommandBufferBuilderError, BufferCopy, CopyBufferInfoTyped, CopyError,
-            ExecuteCommandsError,
+            synced::SyncCommandBufferBuilderError, BufferCopy, ConditionalRenderingError,
+            CopyBufferInfoTyped, CopyError, DrawIndirectCommand, ExecuteCommandsError,
+            MultiDrawInfo, PipelineExecutionError, RenderPassError, RenderingFlags, RenderingInfo,
+            TransformFeedbackError,
         },
         device::{DeviceCreateInfo, QueueCreateInfo},
         memory::allocator::StandardMemoryAllocator,
@@ -1107,4 +1318,196 @@ mod tests {
             })
         ));
     }
+
+    #[test]
+    fn draw_mesh_tasks_feature_not_enabled() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            builder.draw_mesh_tasks([1, 1, 1]),
+            Err(PipelineExecutionError::RequirementNotMet { .. })
+        ));
+    }
+
+    #[test]
+    fn draw_indirect_count_feature_not_enabled() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let indirect_buffer = CpuAccessibleBuffer::from_iter(
+            &memory_allocator,
+            BufferUsage::INDIRECT_BUFFER,
+            true,
+            [DrawIndirectCommand {
+                vertex_count: 3,
+                instance_count: 1,
+                first_vertex: 0,
+                first_instance: 0,
+            }],
+        )
+        .unwrap();
+        let count_buffer = CpuAccessibleBuffer::from_data(
+            &memory_allocator,
+            BufferUsage::INDIRECT_BUFFER,
+            true,
+            1u32,
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            builder.draw_indirect_count(indirect_buffer, count_buffer, 1),
+            Err(PipelineExecutionError::RequirementNotMet { .. })
+        ));
+    }
+
+    #[test]
+    fn draw_multi_feature_not_enabled() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            builder.draw_multi(
+                [MultiDrawInfo {
+                    first_vertex: 0,
+                    vertex_count: 3,
+                }],
+                1,
+                0,
+            ),
+            Err(PipelineExecutionError::RequirementNotMet { .. })
+        ));
+    }
+
+    #[test]
+    fn conditional_rendering_feature_not_enabled() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let buffer = CpuAccessibleBuffer::from_data(
+            &memory_allocator,
+            BufferUsage::CONDITIONAL_RENDERING,
+            true,
+            1u32,
+        );
+
+        // The `conditional_rendering` usage requires the extension to be enabled.
+        let buffer = match buffer {
+            Ok(buffer) => buffer,
+            Err(_) => return,
+        };
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            builder.begin_conditional_rendering(buffer, 0, false),
+            Err(ConditionalRenderingError::RequirementNotMet { .. })
+        ));
+        assert!(matches!(
+            builder.end_conditional_rendering(),
+            Err(ConditionalRenderingError::ConditionalRenderingNotActive)
+        ));
+    }
+
+    #[test]
+    fn transform_feedback_feature_not_enabled() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            builder.bind_transform_feedback_buffers(0, []),
+            Err(TransformFeedbackError::RequirementNotMet { .. })
+        ));
+        assert!(matches!(
+            builder.begin_transform_feedback(0, []),
+            Err(TransformFeedbackError::RequirementNotMet { .. })
+        ));
+        assert!(matches!(
+            builder.end_transform_feedback(0, []),
+            Err(TransformFeedbackError::ForbiddenOutsideRenderPass)
+        ));
+    }
+
+    #[test]
+    fn rendering_suspend_resume() {
+        let (device, queue) = gfx_dev_and_queue!(dynamic_rendering);
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let rendering_info = |flags| RenderingInfo {
+            render_area_extent: [1, 1],
+            layer_count: 1,
+            flags,
+            ..Default::default()
+        };
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        builder
+            .begin_rendering(rendering_info(RenderingFlags::SUSPENDING))
+            .unwrap()
+            .end_rendering()
+            .unwrap();
+
+        // A suspended render pass instance can only be resumed in the next command buffer.
+        assert!(matches!(
+            builder.begin_rendering(rendering_info(RenderingFlags::RESUMING)),
+            Err(RenderPassError::RenderPassSuspended)
+        ));
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        builder
+            .begin_rendering(rendering_info(RenderingFlags::empty()))
+            .unwrap()
+            .end_rendering()
+            .unwrap();
+
+        assert!(matches!(
+            builder.begin_rendering(rendering_info(RenderingFlags::RESUMING)),
+            Err(RenderPassError::ResumingNotFirstRenderPass)
+        ));
+    }
 }
@@ -0,0 +1,843 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{
+    acceleration_structure::{
+        AccelerationStructure, AccelerationStructureBuildGeometryInfo,
+        AccelerationStructureBuildRangeInfo, AccelerationStructureGeometries,
+        AccelerationStructureType, BuildAccelerationStructureMode, CopyAccelerationStructureMode,
+    },
+    buffer::{BufferAccess, BufferUsage},
+    command_buffer::{
+        allocator::CommandBufferAllocator,
+        synced::{Command, Resource, SyncCommandBufferBuilder, SyncCommandBufferBuilderError},
+        sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder, ResourceInCommand, ResourceUseRef,
+    },
+    device::{DeviceOwned, QueueFlags},
+    sync::{AccessFlags, PipelineMemoryAccess, PipelineStages},
+    DeviceSize, RequirementNotMet, RequiresOneOf, VulkanObject,
+};
+use smallvec::SmallVec;
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    sync::Arc,
+};
+
+/// # Commands to build and copy acceleration structures.
+///
+/// These commands require the [`acceleration_structure`] feature to be enabled on the device.
+///
+/// [`acceleration_structure`]: crate::device::Features::acceleration_structure
+impl<L, A> AutoCommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Builds or updates an acceleration structure on the device.
+    ///
+    /// `build_range_infos` must contain one element for each geometry in `info.geometries`.
+    ///
+    /// # Safety
+    ///
+    /// - If `info.geometries` is [`AccelerationStructureGeometries::Instances`], then the
+    ///   `acceleration_structure_reference` of every instance in the instance buffer must be the
+    ///   device address of a bottom-level acceleration structure that has been built by the time
+    ///   this command executes, and that is kept alive for as long as the built acceleration
+    ///   structure is used.
+    /// - The primitive offsets and counts in `build_range_infos` must not cause the geometry
+    ///   buffers to be read out of bounds.
+    pub unsafe fn build_acceleration_structure(
+        &mut self,
+        info: AccelerationStructureBuildGeometryInfo,
+        build_range_infos: SmallVec<[AccelerationStructureBuildRangeInfo; 8]>,
+    ) -> Result<&mut Self, AccelerationStructureCommandError> {
+        self.validate_build_acceleration_structure(&info, &build_range_infos)?;
+
+        self.inner
+            .build_acceleration_structure(info, build_range_infos)?;
+
+        Ok(self)
+    }
+
+    fn validate_build_acceleration_structure(
+        &self,
+        info: &AccelerationStructureBuildGeometryInfo,
+        build_range_infos: &[AccelerationStructureBuildRangeInfo],
+    ) -> Result<(), AccelerationStructureCommandError> {
+        let device = self.device();
+
+        if !device.enabled_features().acceleration_structure {
+            return Err(AccelerationStructureCommandError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::build_acceleration_structure`",
+                requires_one_of: RequiresOneOf {
+                    features: &["acceleration_structure"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdBuildAccelerationStructuresKHR-renderpass
+        if self.render_pass_state.is_some() {
+            return Err(AccelerationStructureCommandError::ForbiddenInsideRenderPass);
+        }
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdBuildAccelerationStructuresKHR-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::COMPUTE)
+        {
+            return Err(AccelerationStructureCommandError::NotSupportedByQueueFamily);
+        }
+
+        let &AccelerationStructureBuildGeometryInfo {
+            flags,
+            ref mode,
+            ref dst_acceleration_structure,
+            ref geometries,
+            ref scratch_data,
+            _ne: _,
+        } = info;
+
+        // VUID-VkAccelerationStructureBuildGeometryInfoKHR-flags-parameter
+        flags.validate_device(device)?;
+
+        // VUID-vkCmdBuildAccelerationStructuresKHR-dstAccelerationStructure-03800
+        let dst_acceleration_structure = dst_acceleration_structure
+            .as_ref()
+            .ok_or(AccelerationStructureCommandError::DstAccelerationStructureMissing)?;
+
+        // VUID-VkAccelerationStructureBuildGeometryInfoKHR-commonparent
+        assert_eq!(device, dst_acceleration_structure.device());
+
+        // VUID-VkAccelerationStructureBuildGeometryInfoKHR-type-03789
+        // VUID-VkAccelerationStructureBuildGeometryInfoKHR-type-03791
+        if !matches!(
+            dst_acceleration_structure.ty(),
+            AccelerationStructureType::Generic
+        ) && dst_acceleration_structure.ty() != info.ty()
+        {
+            return Err(
+                AccelerationStructureCommandError::AccelerationStructureTypeMismatch {
+                    provided: dst_acceleration_structure.ty(),
+                    required: info.ty(),
+                },
+            );
+        }
+
+        if let BuildAccelerationStructureMode::Update(src_acceleration_structure) = mode {
+            // VUID-VkAccelerationStructureBuildGeometryInfoKHR-commonparent
+            assert_eq!(device, src_acceleration_structure.device());
+        }
+
+        // VUID-vkCmdBuildAccelerationStructuresKHR-ppBuildRangeInfos-03676
+        if build_range_infos.len() != geometries.len() {
+            return Err(
+                AccelerationStructureCommandError::BuildRangeInfosLengthMismatch {
+                    build_range_infos_len: build_range_infos.len() as u32,
+                    geometries_len: geometries.len() as u32,
+                },
+            );
+        }
+
+        let properties = device.physical_device().properties();
+
+        match geometries {
+            AccelerationStructureGeometries::Triangles(_)
+            | AccelerationStructureGeometries::Aabbs(_) => {
+                // VUID-VkAccelerationStructureBuildGeometryInfoKHR-type-03793
+                let max_geometry_count = properties.max_geometry_count.unwrap_or(0);
+
+                if geometries.len() as u64 > max_geometry_count {
+                    return Err(
+                        AccelerationStructureCommandError::MaxGeometryCountExceeded {
+                            count: geometries.len() as u64,
+                            max: max_geometry_count,
+                        },
+                    );
+                }
+            }
+            AccelerationStructureGeometries::Instances(_) => {
+                // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03801
+                let max_instance_count = properties.max_instance_count.unwrap_or(0);
+
+                if build_range_infos[0].primitive_count as u64 > max_instance_count {
+                    return Err(
+                        AccelerationStructureCommandError::MaxInstanceCountExceeded {
+                            count: build_range_infos[0].primitive_count as u64,
+                            max: max_instance_count,
+                        },
+                    );
+                }
+            }
+        }
+
+        // Only the transform data of triangle geometries may be null when building.
+        match geometries {
+            AccelerationStructureGeometries::Triangles(geometries) => {
+                for (index, geometry) in geometries.iter().enumerate() {
+                    // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03804
+                    if geometry.vertex_data.is_none() {
+                        return Err(AccelerationStructureCommandError::GeometryDataMissing {
+                            geometry_index: index as u32,
+                            data: "vertex_data",
+                        });
+                    }
+
+                    // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03806
+                    if geometry.index_type.is_some() && geometry.index_data.is_none() {
+                        return Err(AccelerationStructureCommandError::GeometryDataMissing {
+                            geometry_index: index as u32,
+                            data: "index_data",
+                        });
+                    }
+                }
+            }
+            AccelerationStructureGeometries::Aabbs(geometries) => {
+                for (index, geometry) in geometries.iter().enumerate() {
+                    // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03811
+                    if geometry.data.is_none() {
+                        return Err(AccelerationStructureCommandError::GeometryDataMissing {
+                            geometry_index: index as u32,
+                            data: "data",
+                        });
+                    }
+                }
+            }
+            AccelerationStructureGeometries::Instances(geometry) => {
+                // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03813
+                if geometry.data.buffer().is_none() {
+                    return Err(AccelerationStructureCommandError::GeometryDataMissing {
+                        geometry_index: 0,
+                        data: "data",
+                    });
+                }
+            }
+        }
+
+        for (index, buffer) in geometry_buffers(geometries) {
+            // VUID-VkAccelerationStructureBuildGeometryInfoKHR-commonparent
+            assert_eq!(device, buffer.device());
+
+            // VUID-vkCmdBuildAccelerationStructuresKHR-geometry-03673
+            if !buffer.usage().contains(
+                BufferUsage::ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY
+                    | BufferUsage::SHADER_DEVICE_ADDRESS,
+            ) {
+                return Err(
+                    AccelerationStructureCommandError::GeometryBufferMissingUsage {
+                        geometry_index: index,
+                    },
+                );
+            }
+        }
+
+        // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03802
+        let scratch_data = scratch_data
+            .as_ref()
+            .ok_or(AccelerationStructureCommandError::ScratchDataMissing)?;
+
+        // VUID-VkAccelerationStructureBuildGeometryInfoKHR-commonparent
+        assert_eq!(device, scratch_data.device());
+
+        // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03674
+        if !scratch_data
+            .usage()
+            .contains(BufferUsage::STORAGE_BUFFER | BufferUsage::SHADER_DEVICE_ADDRESS)
+        {
+            return Err(AccelerationStructureCommandError::MissingUsage {
+                usage: "storage_buffer and shader_device_address",
+            });
+        }
+
+        // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03710
+        let scratch_offset_alignment = properties
+            .min_acceleration_structure_scratch_offset_alignment
+            .unwrap_or(1) as DeviceSize;
+        let scratch_address = scratch_data
+            .raw_device_address()
+            .map_or(0, |address| address.get());
+
+        if scratch_address % scratch_offset_alignment != 0 {
+            return Err(AccelerationStructureCommandError::ScratchDataNotAligned {
+                device_address: scratch_address,
+                required_alignment: scratch_offset_alignment,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Copies an acceleration structure to another acceleration structure.
+    ///
+    /// # Safety
+    ///
+    /// - `copy_acceleration_structure_info.src` must have been built by the time this command
+    ///   executes.
+    /// - If `copy_acceleration_structure_info.mode` is
+    ///   [`CopyAccelerationStructureMode::Compact`], then `copy_acceleration_structure_info.dst`
+    ///   must be large enough to hold the compacted acceleration structure.
+    pub unsafe fn copy_acceleration_structure(
+        &mut self,
+        copy_acceleration_structure_info: CopyAccelerationStructureInfo,
+    ) -> Result<&mut Self, AccelerationStructureCommandError> {
+        self.validate_copy_acceleration_structure(&copy_acceleration_structure_info)?;
+
+        self.inner
+            .copy_acceleration_structure(copy_acceleration_structure_info)?;
+
+        Ok(self)
+    }
+
+    fn validate_copy_acceleration_structure(
+        &self,
+        copy_acceleration_structure_info: &CopyAccelerationStructureInfo,
+    ) -> Result<(), AccelerationStructureCommandError> {
+        let device = self.device();
+
+        if !device.enabled_features().acceleration_structure {
+            return Err(AccelerationStructureCommandError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::copy_acceleration_structure`",
+                requires_one_of: RequiresOneOf {
+                    features: &["acceleration_structure"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdCopyAccelerationStructureKHR-renderpass
+        if self.render_pass_state.is_some() {
+            return Err(AccelerationStructureCommandError::ForbiddenInsideRenderPass);
+        }
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdCopyAccelerationStructureKHR-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::COMPUTE)
+        {
+            return Err(AccelerationStructureCommandError::NotSupportedByQueueFamily);
+        }
+
+        let &CopyAccelerationStructureInfo {
+            ref src,
+            ref dst,
+            mode,
+            _ne: _,
+        } = copy_acceleration_structure_info;
+
+        // VUID-VkCopyAccelerationStructureInfoKHR-mode-parameter
+        mode.validate_device(device)?;
+
+        // VUID-VkCopyAccelerationStructureInfoKHR-commonparent
+        assert_eq!(device, src.device());
+        assert_eq!(device, dst.device());
+
+        // VUID-VkCopyAccelerationStructureInfoKHR-dst-07791
+        if Arc::ptr_eq(src.buffer(), dst.buffer()) {
+            return Err(AccelerationStructureCommandError::OverlappingAccelerationStructures);
+        }
+
+        Ok(())
+    }
+}
+
+impl SyncCommandBufferBuilder {
+    /// Calls `vkCmdBuildAccelerationStructuresKHR` on the builder.
+    pub unsafe fn build_acceleration_structure(
+        &mut self,
+        info: AccelerationStructureBuildGeometryInfo,
+        build_range_infos: SmallVec<[AccelerationStructureBuildRangeInfo; 8]>,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            info: AccelerationStructureBuildGeometryInfo,
+            build_range_infos: SmallVec<[AccelerationStructureBuildRangeInfo; 8]>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "build_acceleration_structure"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.build_acceleration_structure(&self.info, &self.build_range_infos);
+            }
+        }
+
+        let command_index = self.commands.len();
+        let command_name = "build_acceleration_structure";
+        let use_ref = |resource_in_command| ResourceUseRef {
+            command_index,
+            command_name,
+            resource_in_command,
+            secondary_use_ref: None,
+        };
+
+        let mut resources: SmallVec<[_; 8]> = SmallVec::new();
+
+        for (index, buffer) in geometry_buffers(&info.geometries) {
+            resources.push((
+                use_ref(ResourceInCommand::AccelerationStructureGeometry { index }),
+                Resource::Buffer {
+                    buffer: buffer.clone(),
+                    range: 0..buffer.size(),
+                    memory: PipelineMemoryAccess {
+                        stages: PipelineStages::ACCELERATION_STRUCTURE_BUILD,
+                        access: AccessFlags::SHADER_READ,
+                        exclusive: false,
+                    },
+                },
+            ));
+        }
+
+        let dst_acceleration_structure = info.dst_acceleration_structure.as_ref().unwrap();
+
+        if let BuildAccelerationStructureMode::Update(src_acceleration_structure) = &info.mode {
+            // An in-place update only writes to the destination.
+            if !Arc::ptr_eq(src_acceleration_structure, dst_acceleration_structure) {
+                resources.push((
+                    use_ref(ResourceInCommand::Source),
+                    acceleration_structure_resource(src_acceleration_structure, false),
+                ));
+            }
+        }
+
+        resources.push((
+            use_ref(ResourceInCommand::Destination),
+            acceleration_structure_resource(dst_acceleration_structure, true),
+        ));
+
+        let scratch_data = info.scratch_data.as_ref().unwrap();
+        resources.push((
+            use_ref(ResourceInCommand::ScratchBuffer),
+            Resource::Buffer {
+                buffer: scratch_data.clone(),
+                range: 0..scratch_data.size(),
+                memory: PipelineMemoryAccess {
+                    stages: PipelineStages::ACCELERATION_STRUCTURE_BUILD,
+                    access: AccessFlags::ACCELERATION_STRUCTURE_READ
+                        | AccessFlags::ACCELERATION_STRUCTURE_WRITE,
+                    exclusive: true,
+                },
+            },
+        ));
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            info,
+            build_range_infos,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdCopyAccelerationStructureKHR` on the builder.
+    pub unsafe fn copy_acceleration_structure(
+        &mut self,
+        copy_acceleration_structure_info: CopyAccelerationStructureInfo,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            copy_acceleration_structure_info: CopyAccelerationStructureInfo,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "copy_acceleration_structure"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.copy_acceleration_structure(&self.copy_acceleration_structure_info);
+            }
+        }
+
+        let &CopyAccelerationStructureInfo {
+            ref src,
+            ref dst,
+            mode: _,
+            _ne: _,
+        } = &copy_acceleration_structure_info;
+
+        let command_index = self.commands.len();
+        let command_name = "copy_acceleration_structure";
+        let resources = [
+            (
+                ResourceUseRef {
+                    command_index,
+                    command_name,
+                    resource_in_command: ResourceInCommand::Source,
+                    secondary_use_ref: None,
+                },
+                acceleration_structure_resource(src, false),
+            ),
+            (
+                ResourceUseRef {
+                    command_index,
+                    command_name,
+                    resource_in_command: ResourceInCommand::Destination,
+                    secondary_use_ref: None,
+                },
+                acceleration_structure_resource(dst, true),
+            ),
+        ];
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            copy_acceleration_structure_info,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+}
+
+impl UnsafeCommandBufferBuilder {
+    /// Calls `vkCmdBuildAccelerationStructuresKHR` on the builder.
+    pub unsafe fn build_acceleration_structure(
+        &mut self,
+        info: &AccelerationStructureBuildGeometryInfo,
+        build_range_infos: &[AccelerationStructureBuildRangeInfo],
+    ) {
+        let (mut info_vk, geometries_vk) = info.to_vulkan();
+        info_vk.geometry_count = geometries_vk.len() as u32;
+        info_vk.p_geometries = geometries_vk.as_ptr();
+
+        let build_range_infos_vk: SmallVec<[_; 8]> = build_range_infos
+            .iter()
+            .copied()
+            .map(ash::vk::AccelerationStructureBuildRangeInfoKHR::from)
+            .collect();
+
+        let fns = self.device.fns();
+        (fns.khr_acceleration_structure
+            .cmd_build_acceleration_structures_khr)(
+            self.handle,
+            1,
+            &info_vk,
+            &build_range_infos_vk.as_ptr(),
+        );
+    }
+
+    /// Calls `vkCmdCopyAccelerationStructureKHR` on the builder.
+    pub unsafe fn copy_acceleration_structure(
+        &mut self,
+        copy_acceleration_structure_info: &CopyAccelerationStructureInfo,
+    ) {
+        let &CopyAccelerationStructureInfo {
+            ref src,
+            ref dst,
+            mode,
+            _ne: _,
+        } = copy_acceleration_structure_info;
+
+        let info_vk = ash::vk::CopyAccelerationStructureInfoKHR {
+            src: src.handle(),
+            dst: dst.handle(),
+            mode: mode.into(),
+            ..Default::default()
+        };
+
+        let fns = self.device.fns();
+        (fns.khr_acceleration_structure
+            .cmd_copy_acceleration_structure_khr)(self.handle, &info_vk);
+    }
+}
+
+// Returns the buffers that are read by a build command, along with the index of the geometry
+// that they belong to.
+fn geometry_buffers(
+    geometries: &AccelerationStructureGeometries,
+) -> SmallVec<[(u32, &Arc<dyn BufferAccess>); 8]> {
+    match geometries {
+        AccelerationStructureGeometries::Triangles(geometries) => geometries
+            .iter()
+            .enumerate()
+            .flat_map(|(index, geometry)| {
+                [
+                    &geometry.vertex_data,
+                    &geometry.index_data,
+                    &geometry.transform_data,
+                ]
+                .into_iter()
+                .flatten()
+                .map(move |buffer| (index as u32, buffer))
+            })
+            .collect(),
+        AccelerationStructureGeometries::Aabbs(geometries) => geometries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, geometry)| {
+                geometry.data.as_ref().map(|buffer| (index as u32, buffer))
+            })
+            .collect(),
+        AccelerationStructureGeometries::Instances(geometry) => geometry
+            .data
+            .buffer()
+            .map(|buffer| (0, buffer))
+            .into_iter()
+            .collect(),
+    }
+}
+
+fn acceleration_structure_resource(
+    acceleration_structure: &AccelerationStructure,
+    write: bool,
+) -> Resource {
+    let buffer = acceleration_structure.buffer();
+
+    Resource::Buffer {
+        buffer: buffer.clone(),
+        range: 0..buffer.size(),
+        memory: PipelineMemoryAccess {
+            stages: PipelineStages::ACCELERATION_STRUCTURE_BUILD,
+            access: if write {
+                AccessFlags::ACCELERATION_STRUCTURE_WRITE
+            } else {
+                AccessFlags::ACCELERATION_STRUCTURE_READ
+            },
+            exclusive: write,
+        },
+    }
+}
+
+/// Parameters to copy an acceleration structure.
+#[derive(Clone, Debug)]
+pub struct CopyAccelerationStructureInfo {
+    /// The acceleration structure to copy from.
+    ///
+    /// There is no default value.
+    pub src: Arc<AccelerationStructure>,
+
+    /// The acceleration structure to copy to.
+    ///
+    /// There is no default value.
+    pub dst: Arc<AccelerationStructure>,
+
+    /// Additional operations to perform during the copy.
+    ///
+    /// The default value is [`CopyAccelerationStructureMode::Clone`].
+    pub mode: CopyAccelerationStructureMode,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl CopyAccelerationStructureInfo {
+    /// Returns a `CopyAccelerationStructureInfo` with the specified `src` and `dst`.
+    #[inline]
+    pub fn acceleration_structures(
+        src: Arc<AccelerationStructure>,
+        dst: Arc<AccelerationStructure>,
+    ) -> Self {
+        Self {
+            src,
+            dst,
+            mode: CopyAccelerationStructureMode::Clone,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// Error that can happen when recording an acceleration structure command.
+#[derive(Clone, Debug)]
+pub enum AccelerationStructureCommandError {
+    SyncCommandBufferBuilderError(SyncCommandBufferBuilderError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// Operation forbidden inside of a render pass.
+    ForbiddenInsideRenderPass,
+
+    /// The queue family doesn't allow this operation.
+    NotSupportedByQueueFamily,
+
+    /// The type of the destination acceleration structure does not match the type of the
+    /// provided geometries.
+    AccelerationStructureTypeMismatch {
+        provided: AccelerationStructureType,
+        required: AccelerationStructureType,
+    },
+
+    /// The number of elements in `build_range_infos` does not match the number of geometries.
+    BuildRangeInfosLengthMismatch {
+        build_range_infos_len: u32,
+        geometries_len: u32,
+    },
+
+    /// `dst_acceleration_structure` was not provided.
+    DstAccelerationStructureMissing,
+
+    /// A buffer of a geometry did not have the required usages enabled.
+    GeometryBufferMissingUsage {
+        geometry_index: u32,
+    },
+
+    /// The data of a geometry was not provided, but it is required when building.
+    GeometryDataMissing {
+        geometry_index: u32,
+        data: &'static str,
+    },
+
+    /// The number of geometries exceeds the
+    /// [`max_geometry_count`](crate::device::Properties::max_geometry_count) limit.
+    MaxGeometryCountExceeded {
+        count: u64,
+        max: u64,
+    },
+
+    /// The number of instances exceeds the
+    /// [`max_instance_count`](crate::device::Properties::max_instance_count) limit.
+    MaxInstanceCountExceeded {
+        count: u64,
+        max: u64,
+    },
+
+    /// A resource did not have a required usage enabled.
+    MissingUsage {
+        usage: &'static str,
+    },
+
+    /// The source and destination of a copy are backed by the same buffer.
+    OverlappingAccelerationStructures,
+
+    /// `scratch_data` was not provided.
+    ScratchDataMissing,
+
+    /// The device address of `scratch_data` is not a multiple of the
+    /// [`min_acceleration_structure_scratch_offset_alignment`] limit.
+    ///
+    /// [`min_acceleration_structure_scratch_offset_alignment`]: crate::device::Properties::min_acceleration_structure_scratch_offset_alignment
+    ScratchDataNotAligned {
+        device_address: DeviceSize,
+        required_alignment: DeviceSize,
+    },
+}
+
+impl Error for AccelerationStructureCommandError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::SyncCommandBufferBuilderError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for AccelerationStructureCommandError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::SyncCommandBufferBuilderError(_) => write!(f, "a SyncCommandBufferBuilderError"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::ForbiddenInsideRenderPass => {
+                write!(f, "operation forbidden inside of a render pass")
+            }
+            Self::NotSupportedByQueueFamily => {
+                write!(f, "the queue family doesn't allow this operation")
+            }
+            Self::AccelerationStructureTypeMismatch { provided, required } => write!(
+                f,
+                "the type of the destination acceleration structure ({:?}) does not match the \
+                type of the provided geometries ({:?})",
+                provided, required,
+            ),
+            Self::BuildRangeInfosLengthMismatch {
+                build_range_infos_len,
+                geometries_len,
+            } => write!(
+                f,
+                "the number of elements in `build_range_infos` ({}) does not match the number of \
+                geometries ({})",
+                build_range_infos_len, geometries_len,
+            ),
+            Self::DstAccelerationStructureMissing => {
+                write!(f, "`dst_acceleration_structure` was not provided")
+            }
+            Self::GeometryBufferMissingUsage { geometry_index } => write!(
+                f,
+                "a buffer of geometry {} did not have the required usages enabled",
+                geometry_index,
+            ),
+            Self::GeometryDataMissing {
+                geometry_index,
+                data,
+            } => write!(
+                f,
+                "the `{}` of geometry {} was not provided",
+                data, geometry_index,
+            ),
+            Self::MaxGeometryCountExceeded { count, max } => write!(
+                f,
+                "the number of geometries ({}) exceeds the `max_geometry_count` limit ({})",
+                count, max,
+            ),
+            Self::MaxInstanceCountExceeded { count, max } => write!(
+                f,
+                "the number of instances ({}) exceeds the `max_instance_count` limit ({})",
+                count, max,
+            ),
+            Self::MissingUsage { usage } => write!(
+                f,
+                "a resource did not have a required usage enabled: {}",
+                usage,
+            ),
+            Self::OverlappingAccelerationStructures => write!(
+                f,
+                "the source and destination of a copy are backed by the same buffer",
+            ),
+            Self::ScratchDataMissing => write!(f, "`scratch_data` was not provided"),
+            Self::ScratchDataNotAligned {
+                device_address,
+                required_alignment,
+            } => write!(
+                f,
+                "the device address of `scratch_data` ({}) is not a multiple of the \
+                `min_acceleration_structure_scratch_offset_alignment` limit ({})",
+                device_address, required_alignment,
+            ),
+        }
+    }
+}
+
+impl From<SyncCommandBufferBuilderError> for AccelerationStructureCommandError {
+    fn from(err: SyncCommandBufferBuilderError) -> Self {
+        Self::SyncCommandBufferBuilderError(err)
+    }
+}
+
+impl From<RequirementNotMet> for AccelerationStructureCommandError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
@@ -29,7 +29,7 @@ use crate::{
             render_pass::PipelineRenderPassType,
             vertex_input::VertexBuffersCollection,
         },
-        ComputePipeline, GraphicsPipeline, PipelineBindPoint, PipelineLayout,
+        ComputePipeline, GraphicsPipeline, PipelineBindPoint, PipelineLayout, RayTracingPipeline,
     },
     shader::ShaderStages,
     DeviceSize, RequirementNotMet, RequiresOneOf, VulkanObject,
@@ -105,7 +105,7 @@ where
         // VUID-vkCmdBindDescriptorSets-commandBuffer-cmdpool
         // VUID-vkCmdBindDescriptorSets-pipelineBindPoint-00361
         match pipeline_bind_point {
-            PipelineBindPoint::Compute => {
+            PipelineBindPoint::Compute | PipelineBindPoint::RayTracing => {
                 if !queue_family_properties
                     .queue_flags
                     .intersects(QueueFlags::COMPUTE)
@@ -359,6 +359,11 @@ where
         // VUID-vkCmdBindPipeline-commonparent
         assert_eq!(self.device(), pipeline.device());
 
+        // VUID-vkCmdBindPipeline-None-02323
+        if self.state().transform_feedback_active() {
+            return Err(BindPushError::TransformFeedbackActive);
+        }
+
         if let Some(last_pipeline) = self
             .render_pass_state
             .as_ref()
@@ -404,6 +409,42 @@ where
         Ok(())
     }
 
+    /// Binds a ray tracing pipeline for future ray tracing calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support compute operations.
+    /// - Panics if `self` and `pipeline` do not belong to the same device.
+    pub fn bind_pipeline_ray_tracing(&mut self, pipeline: Arc<RayTracingPipeline>) -> &mut Self {
+        self.validate_bind_pipeline_ray_tracing(&pipeline).unwrap();
+
+        unsafe {
+            self.inner.bind_pipeline_ray_tracing(pipeline);
+        }
+
+        self
+    }
+
+    fn validate_bind_pipeline_ray_tracing(
+        &self,
+        pipeline: &RayTracingPipeline,
+    ) -> Result<(), BindPushError> {
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdBindPipeline-pipelineBindPoint-02391
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::COMPUTE)
+        {
+            return Err(BindPushError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdBindPipeline-commonparent
+        assert_eq!(self.device(), pipeline.device());
+
+        Ok(())
+    }
+
     /// Binds vertex buffers for future draw calls.
     ///
     /// # Panics
@@ -663,7 +704,7 @@ where
         // VUID-vkCmdPushDescriptorSetKHR-commandBuffer-cmdpool
         // VUID-vkCmdPushDescriptorSetKHR-pipelineBindPoint-00363
         match pipeline_bind_point {
-            PipelineBindPoint::Compute => {
+            PipelineBindPoint::Compute | PipelineBindPoint::RayTracing => {
                 if !queue_family_properties
                     .queue_flags
                     .intersects(QueueFlags::COMPUTE)
@@ -794,6 +835,27 @@ impl SyncCommandBufferBuilder {
         self.commands.push(Box::new(Cmd { pipeline }));
     }
 
+    /// Calls `vkCmdBindPipeline` on the builder with a ray tracing pipeline.
+    #[inline]
+    pub unsafe fn bind_pipeline_ray_tracing(&mut self, pipeline: Arc<RayTracingPipeline>) {
+        struct Cmd {
+            pipeline: Arc<RayTracingPipeline>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "bind_pipeline_ray_tracing"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.bind_pipeline_ray_tracing(&self.pipeline);
+            }
+        }
+
+        self.current_state.pipeline_ray_tracing = Some(pipeline.clone());
+        self.commands.push(Box::new(Cmd { pipeline }));
+    }
+
     /// Starts the process of binding vertex buffers. Returns an intermediate struct which can be
     /// used to add the buffers.
     #[inline]
@@ -1130,6 +1192,17 @@ impl UnsafeCommandBufferBuilder {
         );
     }
 
+    /// Calls `vkCmdBindPipeline` on the builder with a ray tracing pipeline.
+    #[inline]
+    pub unsafe fn bind_pipeline_ray_tracing(&mut self, pipeline: &RayTracingPipeline) {
+        let fns = self.device.fns();
+        (fns.v1_0.cmd_bind_pipeline)(
+            self.handle,
+            ash::vk::PipelineBindPoint::RAY_TRACING_KHR,
+            pipeline.handle(),
+        );
+    }
+
     /// Calls `vkCmdBindVertexBuffers` on the builder.
     ///
     /// Does nothing if the list of buffers is empty, as it would be a no-op and isn't a valid
@@ -1228,7 +1301,16 @@ impl UnsafeCommandBufferBuilder {
         }
 
         // Set the info pointers separately.
-        for (info, write) in infos.iter().zip(writes.iter_mut()) {
+        let acceleration_structure_infos: SmallVec<[_; 8]> = infos
+            .iter()
+            .map(DescriptorWriteInfo::to_vulkan_acceleration_structure)
+            .collect();
+
+        for ((info, acceleration_structure_info), write) in infos
+            .iter()
+            .zip(&acceleration_structure_infos)
+            .zip(writes.iter_mut())
+        {
             match info {
                 DescriptorWriteInfo::Image(info) => {
                     write.descriptor_count = info.len() as u32;
@@ -1242,6 +1324,10 @@ impl UnsafeCommandBufferBuilder {
                     write.descriptor_count = info.len() as u32;
                     write.p_texel_buffer_view = info.as_ptr();
                 }
+                DescriptorWriteInfo::AccelerationStructure(info) => {
+                    write.descriptor_count = info.len() as u32;
+                    write.p_next = acceleration_structure_info as *const _ as *const _;
+                }
             }
 
             debug_assert!(write.descriptor_count != 0);
@@ -1386,6 +1472,9 @@ pub(in super::super) enum BindPushError {
     /// The push constants size is not a multiple of 4.
     PushConstantsSizeNotAligned,
 
+    /// Transform feedback is active.
+    TransformFeedbackActive,
+
     /// A vertex buffer is missing the `vertex_buffer` usage.
     VertexBufferMissingUsage,
 }
@@ -1507,6 +1596,7 @@ impl Display for BindPushError {
             Self::PushConstantsSizeNotAligned => {
                 write!(f, "the push constants size is not a multiple of 4")
             }
+            Self::TransformFeedbackActive => write!(f, "transform feedback is active"),
             Self::VertexBufferMissingUsage => {
                 write!(f, "a vertex buffer is missing the `vertex_buffer` usage")
             }
@@ -0,0 +1,400 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{
+    buffer::{BufferAccess, BufferUsage},
+    command_buffer::{
+        allocator::CommandBufferAllocator,
+        auto::ConditionalRenderingState,
+        synced::{Command, Resource, SyncCommandBufferBuilder, SyncCommandBufferBuilderError},
+        sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder, ResourceInCommand, ResourceUseRef,
+    },
+    device::{DeviceOwned, QueueFlags},
+    sync::{AccessFlags, PipelineMemoryAccess, PipelineStages},
+    DeviceSize, RequiresOneOf, VulkanObject,
+};
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    mem::size_of,
+    sync::Arc,
+};
+
+/// # Commands for conditional rendering.
+///
+/// These commands require the [`conditional_rendering`] feature to be enabled on the device.
+///
+/// [`conditional_rendering`]: crate::device::Features::conditional_rendering
+impl<L, A> AutoCommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Begins conditional rendering.
+    ///
+    /// While conditional rendering is active, draw and dispatch commands, as well as
+    /// [`clear_attachments`](Self::clear_attachments), are discarded by the device if the 32-bit
+    /// value at `offset` in `buffer` is zero. If `inverted` is `true`, they are discarded if the
+    /// value is nonzero instead. The value is read when the commands are executed, so it can be
+    /// written on the device beforehand, for example with
+    /// [`copy_query_pool_results`](Self::copy_query_pool_results).
+    ///
+    /// Conditional rendering stays active until
+    /// [`end_conditional_rendering`](Self::end_conditional_rendering) is called. If it is begun
+    /// inside a subpass, it must be ended in the same subpass.
+    pub fn begin_conditional_rendering(
+        &mut self,
+        buffer: Arc<dyn BufferAccess>,
+        offset: DeviceSize,
+        inverted: bool,
+    ) -> Result<&mut Self, ConditionalRenderingError> {
+        self.validate_begin_conditional_rendering(buffer.as_ref(), offset)?;
+
+        unsafe {
+            self.inner
+                .begin_conditional_rendering(buffer, offset, inverted)?;
+        }
+
+        self.conditional_rendering_state = Some(ConditionalRenderingState {
+            in_subpass: self.render_pass_state.is_some(),
+        });
+
+        Ok(self)
+    }
+
+    fn validate_begin_conditional_rendering(
+        &self,
+        buffer: &dyn BufferAccess,
+        offset: DeviceSize,
+    ) -> Result<(), ConditionalRenderingError> {
+        let device = self.device();
+
+        if !device.enabled_features().conditional_rendering {
+            return Err(ConditionalRenderingError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::begin_conditional_rendering`",
+                requires_one_of: RequiresOneOf {
+                    features: &["conditional_rendering"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdBeginConditionalRenderingEXT-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+        {
+            return Err(ConditionalRenderingError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdBeginConditionalRenderingEXT-None-01980
+        if self.conditional_rendering_state.is_some() {
+            return Err(ConditionalRenderingError::ConditionalRenderingActive);
+        }
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-buffer-parameter
+        assert_eq!(device, buffer.device());
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-buffer-01981
+        if !buffer
+            .usage()
+            .intersects(BufferUsage::CONDITIONAL_RENDERING)
+        {
+            return Err(ConditionalRenderingError::BufferMissingUsage);
+        }
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-offset-01984
+        if offset % 4 != 0 {
+            return Err(ConditionalRenderingError::OffsetNotAligned { offset });
+        }
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-offset-01983
+        if offset + size_of::<u32>() as DeviceSize > buffer.size() {
+            return Err(ConditionalRenderingError::OffsetOutOfBounds {
+                offset,
+                buffer_size: buffer.size(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Ends conditional rendering, that was previously begun with
+    /// [`begin_conditional_rendering`](Self::begin_conditional_rendering).
+    pub fn end_conditional_rendering(&mut self) -> Result<&mut Self, ConditionalRenderingError> {
+        self.validate_end_conditional_rendering()?;
+
+        unsafe {
+            self.inner.end_conditional_rendering();
+        }
+
+        self.conditional_rendering_state = None;
+
+        Ok(self)
+    }
+
+    fn validate_end_conditional_rendering(&self) -> Result<(), ConditionalRenderingError> {
+        // VUID-vkCmdEndConditionalRenderingEXT-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+        {
+            return Err(ConditionalRenderingError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdEndConditionalRenderingEXT-None-01985
+        let state = self
+            .conditional_rendering_state
+            .as_ref()
+            .ok_or(ConditionalRenderingError::ConditionalRenderingNotActive)?;
+
+        if state.in_subpass {
+            // VUID-vkCmdEndConditionalRenderingEXT-None-01987
+            if self.render_pass_state.is_none() {
+                return Err(ConditionalRenderingError::ForbiddenOutsideRenderPass);
+            }
+        } else {
+            // VUID-vkCmdEndConditionalRenderingEXT-None-01986
+            if self.render_pass_state.is_some() {
+                return Err(ConditionalRenderingError::ForbiddenInsideRenderPass);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl SyncCommandBufferBuilder {
+    /// Calls `vkCmdBeginConditionalRenderingEXT` on the builder.
+    #[inline]
+    pub unsafe fn begin_conditional_rendering(
+        &mut self,
+        buffer: Arc<dyn BufferAccess>,
+        offset: DeviceSize,
+        inverted: bool,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            buffer: Arc<dyn BufferAccess>,
+            offset: DeviceSize,
+            inverted: bool,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "begin_conditional_rendering"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.begin_conditional_rendering(self.buffer.as_ref(), self.offset, self.inverted);
+            }
+        }
+
+        let command_index = self.commands.len();
+        let command_name = "begin_conditional_rendering";
+        let resources = [(
+            ResourceUseRef {
+                command_index,
+                command_name,
+                resource_in_command: ResourceInCommand::ConditionalRenderingBuffer,
+                secondary_use_ref: None,
+            },
+            Resource::Buffer {
+                buffer: buffer.clone(),
+                range: offset..offset + size_of::<u32>() as DeviceSize,
+                memory: PipelineMemoryAccess {
+                    stages: PipelineStages::CONDITIONAL_RENDERING,
+                    access: AccessFlags::CONDITIONAL_RENDERING_READ,
+                    exclusive: false,
+                },
+            },
+        )];
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            buffer,
+            offset,
+            inverted,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdEndConditionalRenderingEXT` on the builder.
+    #[inline]
+    pub unsafe fn end_conditional_rendering(&mut self) {
+        struct Cmd;
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "end_conditional_rendering"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.end_conditional_rendering();
+            }
+        }
+
+        self.commands.push(Box::new(Cmd));
+    }
+}
+
+impl UnsafeCommandBufferBuilder {
+    /// Calls `vkCmdBeginConditionalRenderingEXT` on the builder.
+    #[inline]
+    pub unsafe fn begin_conditional_rendering(
+        &mut self,
+        buffer: &dyn BufferAccess,
+        offset: DeviceSize,
+        inverted: bool,
+    ) {
+        let buffer_inner = buffer.inner();
+        debug_assert!(buffer_inner
+            .buffer
+            .usage()
+            .intersects(BufferUsage::CONDITIONAL_RENDERING));
+        debug_assert!(offset % 4 == 0);
+
+        let mut flags = ash::vk::ConditionalRenderingFlagsEXT::empty();
+
+        if inverted {
+            flags |= ash::vk::ConditionalRenderingFlagsEXT::INVERTED;
+        }
+
+        let begin_info = ash::vk::ConditionalRenderingBeginInfoEXT {
+            buffer: buffer_inner.buffer.handle(),
+            offset: buffer_inner.offset + offset,
+            flags,
+            ..Default::default()
+        };
+
+        let fns = self.device.fns();
+        (fns.ext_conditional_rendering
+            .cmd_begin_conditional_rendering_ext)(self.handle, &begin_info);
+    }
+
+    /// Calls `vkCmdEndConditionalRenderingEXT` on the builder.
+    #[inline]
+    pub unsafe fn end_conditional_rendering(&mut self) {
+        let fns = self.device.fns();
+        (fns.ext_conditional_rendering
+            .cmd_end_conditional_rendering_ext)(self.handle);
+    }
+}
+
+/// Error that can happen when recording a conditional rendering command.
+#[derive(Clone, Debug)]
+pub enum ConditionalRenderingError {
+    SyncCommandBufferBuilderError(SyncCommandBufferBuilderError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// The buffer is missing the `conditional_rendering` usage.
+    BufferMissingUsage,
+
+    /// Conditional rendering is already active.
+    ConditionalRenderingActive,
+
+    /// Conditional rendering is not active.
+    ConditionalRenderingNotActive,
+
+    /// Conditional rendering was begun outside a render pass, and can't be ended inside one.
+    ForbiddenInsideRenderPass,
+
+    /// Conditional rendering was begun inside a subpass, and must be ended in the same subpass.
+    ForbiddenOutsideRenderPass,
+
+    /// The queue family doesn't allow this operation.
+    NotSupportedByQueueFamily,
+
+    /// The offset is not a multiple of 4.
+    OffsetNotAligned {
+        offset: DeviceSize,
+    },
+
+    /// The offset plus the size of the predicate is greater than the size of the buffer.
+    OffsetOutOfBounds {
+        offset: DeviceSize,
+        buffer_size: DeviceSize,
+    },
+}
+
+impl Error for ConditionalRenderingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::SyncCommandBufferBuilderError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ConditionalRenderingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::SyncCommandBufferBuilderError(_) => write!(f, "a SyncCommandBufferBuilderError"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::BufferMissingUsage => {
+                write!(f, "the buffer is missing the `conditional_rendering` usage",)
+            }
+            Self::ConditionalRenderingActive => {
+                write!(f, "conditional rendering is already active")
+            }
+            Self::ConditionalRenderingNotActive => write!(f, "conditional rendering is not active"),
+            Self::ForbiddenInsideRenderPass => write!(
+                f,
+                "conditional rendering was begun outside a render pass, and can't be ended inside \
+                one",
+            ),
+            Self::ForbiddenOutsideRenderPass => write!(
+                f,
+                "conditional rendering was begun inside a subpass, and must be ended in the same \
+                subpass",
+            ),
+            Self::NotSupportedByQueueFamily => {
+                write!(f, "the queue family doesn't allow this operation")
+            }
+            Self::OffsetNotAligned { offset } => {
+                write!(f, "the offset ({}) is not a multiple of 4", offset)
+            }
+            Self::OffsetOutOfBounds {
+                offset,
+                buffer_size,
+            } => write!(
+                f,
+                "the offset ({}) plus the size of the predicate is greater than the size of the \
+                buffer ({})",
+                offset, buffer_size,
+            ),
+        }
+    }
+}
+
+impl From<SyncCommandBufferBuilderError> for ConditionalRenderingError {
+    fn from(err: SyncCommandBufferBuilderError) -> Self {
+        Self::SyncCommandBufferBuilderError(err)
+    }
+}
@@ -14,18 +14,23 @@ use crate::{
         sys::UnsafeCommandBufferBuilder,
         AutoCommandBufferBuilder,
     },
-    device::{DeviceOwned, QueueFlags},
+    device::{Device, DeviceOwned, QueueFlags},
+    format::{Format, FormatFeatures},
     pipeline::{
         graphics::{
             color_blend::LogicOp,
             depth_stencil::{CompareOp, StencilFaces, StencilOp, StencilOps},
             input_assembly::PrimitiveTopology,
             rasterization::{CullMode, DepthBias, FrontFace, LineStipple},
+            vertex_input::{
+                VertexInputAttributeDescription, VertexInputBindingDescription, VertexInputRate,
+                VertexInputState,
+            },
             viewport::{Scissor, Viewport},
         },
         DynamicState,
     },
-    RequirementNotMet, RequiresOneOf, Version,
+    DeviceSize, RequirementNotMet, RequiresOneOf, Version,
 };
 use parking_lot::Mutex;
 use smallvec::SmallVec;
@@ -1563,6 +1568,61 @@ where
         Ok(())
     }
 
+    /// Sets the dynamic vertex input for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`vertex_input_dynamic_state`](crate::device::Features::vertex_input_dynamic_state)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if the number of bindings or attributes, or a binding number, location, stride,
+    ///   offset or divisor in `vertex_input_state` exceeds the corresponding device limit.
+    /// - Panics if an attribute in `vertex_input_state` refers to a binding that does not exist,
+    ///   or has a format that does not support vertex buffer usage.
+    pub fn set_vertex_input(&mut self, vertex_input_state: VertexInputState) -> &mut Self {
+        self.validate_set_vertex_input(&vertex_input_state).unwrap();
+
+        unsafe {
+            self.inner.set_vertex_input(vertex_input_state);
+        }
+
+        self
+    }
+
+    fn validate_set_vertex_input(
+        &self,
+        vertex_input_state: &VertexInputState,
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::VertexInput)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetVertexInputEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        let device = self.device();
+
+        // VUID-vkCmdSetVertexInputEXT-None-04790
+        if !device.enabled_features().vertex_input_dynamic_state {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_vertex_input`",
+                requires_one_of: RequiresOneOf {
+                    features: &["vertex_input_dynamic_state"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        validate_vertex_input_state(device, vertex_input_state)
+    }
+
     /// Sets the dynamic viewports for future draw calls.
     ///
     /// # Panics
@@ -2399,6 +2459,27 @@ impl SyncCommandBufferBuilder {
         }));
     }
 
+    /// Calls `vkCmdSetVertexInputEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_vertex_input(&mut self, vertex_input_state: VertexInputState) {
+        struct Cmd {
+            vertex_input_state: VertexInputState,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_vertex_input"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_vertex_input(&self.vertex_input_state);
+            }
+        }
+
+        self.current_state.vertex_input = Some(vertex_input_state.clone());
+        self.commands.push(Box::new(Cmd { vertex_input_state }));
+    }
+
     /// Calls `vkCmdSetViewport` on the builder.
     ///
     /// If the list is empty then the command is automatically ignored.
@@ -2850,6 +2931,53 @@ impl UnsafeCommandBufferBuilder {
         }
     }
 
+    /// Calls `vkCmdSetVertexInputEXT` on the builder.
+    pub unsafe fn set_vertex_input(&mut self, vertex_input_state: &VertexInputState) {
+        let vertex_binding_descriptions_vk: SmallVec<[_; 8]> = vertex_input_state
+            .bindings
+            .iter()
+            .map(
+                |(&binding, binding_desc)| ash::vk::VertexInputBindingDescription2EXT {
+                    binding,
+                    stride: binding_desc.stride,
+                    input_rate: binding_desc.input_rate.into(),
+                    divisor: match binding_desc.input_rate {
+                        VertexInputRate::Vertex => 1,
+                        VertexInputRate::Instance { divisor } => divisor,
+                    },
+                    ..Default::default()
+                },
+            )
+            .collect();
+        let vertex_attribute_descriptions_vk: SmallVec<[_; 8]> = vertex_input_state
+            .attributes
+            .iter()
+            .map(
+                |(&location, attribute_desc)| ash::vk::VertexInputAttributeDescription2EXT {
+                    location,
+                    binding: attribute_desc.binding,
+                    format: attribute_desc.format.into(),
+                    offset: attribute_desc.offset,
+                    ..Default::default()
+                },
+            )
+            .collect();
+
+        debug_assert!(
+            self.device
+                .enabled_extensions()
+                .ext_vertex_input_dynamic_state
+        );
+        let fns = self.device.fns();
+        (fns.ext_vertex_input_dynamic_state.cmd_set_vertex_input_ext)(
+            self.handle,
+            vertex_binding_descriptions_vk.len() as u32,
+            vertex_binding_descriptions_vk.as_ptr(),
+            vertex_attribute_descriptions_vk.len() as u32,
+            vertex_attribute_descriptions_vk.as_ptr(),
+        );
+    }
+
     /// Calls `vkCmdSetViewport` on the builder.
     ///
     /// If the list is empty then the command is automatically ignored.
@@ -2910,6 +3038,181 @@ impl UnsafeCommandBufferBuilder {
     }
 }
 
+// Shared between the command buffer builders.
+pub(in crate::command_buffer) fn validate_vertex_input_state(
+    device: &Device,
+    vertex_input_state: &VertexInputState,
+) -> Result<(), SetDynamicStateError> {
+    let properties = device.physical_device().properties();
+    let VertexInputState {
+        bindings,
+        attributes,
+    } = vertex_input_state;
+
+    // VUID-vkCmdSetVertexInputEXT-vertexBindingDescriptionCount-04791
+    if bindings.len() > properties.max_vertex_input_bindings as usize {
+        return Err(SetDynamicStateError::MaxVertexInputBindingsExceeded {
+            provided: bindings.len() as u32,
+            max: properties.max_vertex_input_bindings,
+        });
+    }
+
+    // VUID-vkCmdSetVertexInputEXT-pVertexBindingDescriptions-04794
+    // Ensured by HashMap.
+
+    for (&binding, binding_desc) in bindings {
+        let &VertexInputBindingDescription { stride, input_rate } = binding_desc;
+
+        // VUID-VkVertexInputBindingDescription2EXT-binding-04796
+        if binding >= properties.max_vertex_input_bindings {
+            return Err(SetDynamicStateError::MaxVertexInputBindingsExceeded {
+                provided: binding,
+                max: properties.max_vertex_input_bindings,
+            });
+        }
+
+        // VUID-VkVertexInputBindingDescription2EXT-stride-04797
+        if stride > properties.max_vertex_input_binding_stride {
+            return Err(SetDynamicStateError::MaxVertexInputBindingStrideExceeded {
+                binding,
+                provided: stride,
+                max: properties.max_vertex_input_binding_stride,
+            });
+        }
+
+        match input_rate {
+            VertexInputRate::Instance { divisor } if divisor != 1 => {
+                // VUID-VkVertexInputBindingDescription2EXT-divisor-04798
+                if !device
+                    .enabled_features()
+                    .vertex_attribute_instance_rate_divisor
+                {
+                    return Err(SetDynamicStateError::RequirementNotMet {
+                        required_for: "`vertex_input_state.bindings` has an element where \
+                            `input_rate` is `VertexInputRate::Instance`, where `divisor` is \
+                            not `1`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["vertex_attribute_instance_rate_divisor"],
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                // VUID-VkVertexInputBindingDescription2EXT-divisor-04799
+                if divisor == 0
+                    && !device
+                        .enabled_features()
+                        .vertex_attribute_instance_rate_zero_divisor
+                {
+                    return Err(SetDynamicStateError::RequirementNotMet {
+                        required_for: "`vertex_input_state.bindings` has an element where \
+                            `input_rate` is `VertexInputRate::Instance`, where `divisor` is \
+                            `0`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["vertex_attribute_instance_rate_zero_divisor"],
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                // VUID-VkVertexInputBindingDescription2EXT-divisor-06226
+                if divisor > properties.max_vertex_attrib_divisor.unwrap() {
+                    return Err(SetDynamicStateError::MaxVertexAttribDivisorExceeded {
+                        binding,
+                        provided: divisor,
+                        max: properties.max_vertex_attrib_divisor.unwrap(),
+                    });
+                }
+            }
+            _ => (),
+        }
+    }
+
+    // VUID-vkCmdSetVertexInputEXT-vertexAttributeDescriptionCount-04792
+    if attributes.len() > properties.max_vertex_input_attributes as usize {
+        return Err(SetDynamicStateError::MaxVertexInputAttributesExceeded {
+            provided: attributes.len() as u32,
+            max: properties.max_vertex_input_attributes,
+        });
+    }
+
+    // VUID-vkCmdSetVertexInputEXT-pVertexAttributeDescriptions-04795
+    // Ensured by HashMap.
+
+    for (&location, attribute_desc) in attributes {
+        let &VertexInputAttributeDescription {
+            binding,
+            format,
+            offset,
+        } = attribute_desc;
+
+        // VUID-VkVertexInputAttributeDescription2EXT-format-parameter
+        format.validate_device(device)?;
+
+        // VUID-VkVertexInputAttributeDescription2EXT-location-06228
+        if location >= properties.max_vertex_input_attributes {
+            return Err(SetDynamicStateError::MaxVertexInputAttributesExceeded {
+                provided: location,
+                max: properties.max_vertex_input_attributes,
+            });
+        }
+
+        // VUID-VkVertexInputAttributeDescription2EXT-binding-06229
+        // VUID-vkCmdSetVertexInputEXT-binding-04793
+        let binding_desc = bindings.get(&binding).ok_or(
+            SetDynamicStateError::VertexInputAttributeInvalidBinding { location, binding },
+        )?;
+
+        // VUID-VkVertexInputAttributeDescription2EXT-offset-06230
+        if offset > properties.max_vertex_input_attribute_offset {
+            return Err(
+                SetDynamicStateError::MaxVertexInputAttributeOffsetExceeded {
+                    location,
+                    provided: offset,
+                    max: properties.max_vertex_input_attribute_offset,
+                },
+            );
+        }
+
+        // Use unchecked, because all validation has been done above.
+        let format_features = unsafe {
+            device
+                .physical_device()
+                .format_properties_unchecked(format)
+                .buffer_features
+        };
+
+        // VUID-VkVertexInputAttributeDescription2EXT-format-04805
+        if !format_features.intersects(FormatFeatures::VERTEX_BUFFER) {
+            return Err(
+                SetDynamicStateError::VertexInputAttributeUnsupportedFormat { location, format },
+            );
+        }
+
+        // VUID-VkVertexInputAttributeDescription2EXT-vertexAttributeAccessBeyondStride-04806
+        if device.enabled_extensions().khr_portability_subset
+            && !device
+                .enabled_features()
+                .vertex_attribute_access_beyond_stride
+            && offset as DeviceSize + format.block_size().unwrap()
+                > binding_desc.stride as DeviceSize
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "this device is a portability subset device, and \
+                    `vertex_input_state.attributes` has an element where \
+                    `offset + format.block_size()` is greater than the `stride` of \
+                    `binding`",
+                requires_one_of: RequiresOneOf {
+                    features: &["vertex_attribute_access_beyond_stride"],
+                    ..Default::default()
+                },
+            });
+        }
+    }
+
+    Ok(())
+}
+
 #[derive(Clone, Debug)]
 #[allow(dead_code)]
 pub(in super::super) enum SetDynamicStateError {
@@ -2929,6 +3232,38 @@ pub(in super::super) enum SetDynamicStateError {
     /// limit has been exceeded.
     MaxTessellationPatchSizeExceeded { provided: u32, max: u32 },
 
+    /// The [`max_vertex_attrib_divisor`](crate::device::Properties::max_vertex_attrib_divisor)
+    /// limit has been exceeded.
+    MaxVertexAttribDivisorExceeded {
+        binding: u32,
+        provided: u32,
+        max: u32,
+    },
+
+    /// The [`max_vertex_input_attribute_offset`](crate::device::Properties::max_vertex_input_attribute_offset)
+    /// limit has been exceeded.
+    MaxVertexInputAttributeOffsetExceeded {
+        location: u32,
+        provided: u32,
+        max: u32,
+    },
+
+    /// The [`max_vertex_input_attributes`](crate::device::Properties::max_vertex_input_attributes)
+    /// limit has been exceeded.
+    MaxVertexInputAttributesExceeded { provided: u32, max: u32 },
+
+    /// The [`max_vertex_input_binding_stride`](crate::device::Properties::max_vertex_input_binding_stride)
+    /// limit has been exceeded.
+    MaxVertexInputBindingStrideExceeded {
+        binding: u32,
+        provided: u32,
+        max: u32,
+    },
+
+    /// The [`max_vertex_input_bindings`](crate::device::Properties::max_vertex_input_bindings)
+    /// limit has been exceeded.
+    MaxVertexInputBindingsExceeded { provided: u32, max: u32 },
+
     /// The [`max_viewports`](crate::device::Properties::max_viewports)
     /// limit has been exceeded.
     MaxViewportsExceeded { provided: u32, max: u32 },
@@ -2946,6 +3281,13 @@ pub(in super::super) enum SetDynamicStateError {
     /// The currently bound pipeline contains this state as internally fixed state, which cannot be
     /// overridden with dynamic state.
     PipelineHasFixedState,
+
+    /// The binding number specified by a vertex input attribute does not exist in the provided
+    /// list of binding descriptions.
+    VertexInputAttributeInvalidBinding { location: u32, binding: u32 },
+
+    /// The format specified by a vertex input attribute is not supported for vertex buffers.
+    VertexInputAttributeUnsupportedFormat { location: u32, format: Format },
 }
 
 impl Error for SetDynamicStateError {}
@@ -2972,6 +3314,26 @@ impl Display for SetDynamicStateError {
                 f,
                 "the `max_tessellation_patch_size` limit has been exceeded",
             ),
+            Self::MaxVertexAttribDivisorExceeded { .. } => {
+                write!(f, "the `max_vertex_attrib_divisor` limit has been exceeded")
+            }
+            Self::MaxVertexInputAttributeOffsetExceeded { .. } => write!(
+                f,
+                "the `max_vertex_input_attribute_offset` limit has been exceeded",
+            ),
+            Self::MaxVertexInputAttributesExceeded { .. } => {
+                write!(
+                    f,
+                    "the `max_vertex_input_attributes` limit has been exceeded"
+                )
+            }
+            Self::MaxVertexInputBindingStrideExceeded { .. } => write!(
+                f,
+                "the `max_vertex_input_binding_stride` limit has been exceeded",
+            ),
+            Self::MaxVertexInputBindingsExceeded { .. } => {
+                write!(f, "the `max_vertex_input_bindings` limit has been exceeded")
+            }
             Self::MaxViewportsExceeded { .. } => {
                 write!(f, "the `max_viewports` limit has been exceeded")
             }
@@ -2992,6 +3354,18 @@ impl Display for SetDynamicStateError {
                 "the currently bound pipeline contains this state as internally fixed state, which \
                 cannot be overridden with dynamic state",
             ),
+            Self::VertexInputAttributeInvalidBinding { location, binding } => write!(
+                f,
+                "the binding number {} specified by vertex input attribute location {} does not \
+                exist in the provided list of binding descriptions",
+                binding, location,
+            ),
+            Self::VertexInputAttributeUnsupportedFormat { location, format } => write!(
+                f,
+                "the format {:?} specified by vertex input attribute location {} is not supported \
+                for vertex buffers",
+                format, location,
+            ),
         }
     }
 }
@@ -3004,3 +3378,105 @@ impl From<RequirementNotMet> for SetDynamicStateError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_vertex_input_state, SetDynamicStateError};
+    use crate::{
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferUsage,
+        },
+        format::Format,
+        pipeline::graphics::vertex_input::{
+            VertexInputAttributeDescription, VertexInputBindingDescription, VertexInputRate,
+            VertexInputState,
+        },
+        RequiresOneOf,
+    };
+
+    #[test]
+    fn set_vertex_input_requires_feature() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        match builder.validate_set_vertex_input(&VertexInputState::new()) {
+            Err(SetDynamicStateError::RequirementNotMet {
+                requires_one_of:
+                    RequiresOneOf {
+                        features: ["vertex_input_dynamic_state"],
+                        ..
+                    },
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn vertex_input_limits() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let properties = device.physical_device().properties();
+        let max_bindings = properties.max_vertex_input_bindings;
+        let max_attributes = properties.max_vertex_input_attributes;
+        let binding_desc = VertexInputBindingDescription {
+            stride: 16,
+            input_rate: VertexInputRate::Vertex,
+        };
+        let attribute_desc = VertexInputAttributeDescription {
+            binding: 0,
+            format: Format::R32G32B32A32_SFLOAT,
+            offset: 0,
+        };
+
+        match validate_vertex_input_state(
+            &device,
+            &VertexInputState::new().binding(max_bindings, binding_desc.clone()),
+        ) {
+            Err(SetDynamicStateError::MaxVertexInputBindingsExceeded { provided, max }) => {
+                assert_eq!(provided, max_bindings);
+                assert_eq!(max, max_bindings);
+            }
+            _ => panic!(),
+        }
+
+        match validate_vertex_input_state(
+            &device,
+            &VertexInputState::new()
+                .binding(0, binding_desc.clone())
+                .attribute(max_attributes, attribute_desc),
+        ) {
+            Err(SetDynamicStateError::MaxVertexInputAttributesExceeded { provided, max }) => {
+                assert_eq!(provided, max_attributes);
+                assert_eq!(max, max_attributes);
+            }
+            _ => panic!(),
+        }
+
+        match validate_vertex_input_state(
+            &device,
+            &VertexInputState::new().attribute(0, attribute_desc),
+        ) {
+            Err(SetDynamicStateError::VertexInputAttributeInvalidBinding {
+                location: 0,
+                binding: 0,
+            }) => (),
+            _ => panic!(),
+        }
+
+        assert!(validate_vertex_input_state(
+            &device,
+            &VertexInputState::new()
+                .binding(0, binding_desc)
+                .attribute(0, attribute_desc),
+        )
+        .is_ok());
+    }
+}
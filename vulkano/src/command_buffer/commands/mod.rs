@@ -7,8 +7,10 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+pub(super) mod acceleration_structure;
 pub(super) mod bind_push;
 pub(super) mod clear;
+pub(super) mod conditional_rendering;
 pub(super) mod copy;
 pub(super) mod debug;
 pub(super) mod dynamic_state;
@@ -17,3 +19,4 @@ pub(super) mod query;
 pub(super) mod render_pass;
 pub(super) mod secondary;
 pub(super) mod sync;
+pub(super) mod transform_feedback;
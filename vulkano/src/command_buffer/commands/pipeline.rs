@@ -15,11 +15,12 @@ use crate::{
         synced::{Command, Resource, SyncCommandBufferBuilder, SyncCommandBufferBuilderError},
         sys::UnsafeCommandBufferBuilder,
         AutoCommandBufferBuilder, DispatchIndirectCommand, DrawIndexedIndirectCommand,
-        DrawIndirectCommand, ResourceInCommand, ResourceUseRef, SubpassContents,
+        DrawIndirectCommand, DrawMeshTasksIndirectCommand, MultiDrawIndexedInfo, MultiDrawInfo,
+        ResourceInCommand, ResourceUseRef, SubpassContents,
     },
     descriptor_set::{layout::DescriptorType, DescriptorBindingResources},
     device::{DeviceOwned, QueueFlags},
-    format::{Format, FormatFeatures},
+    format::{Format, FormatFeatures, NumericType},
     image::{
         view::ImageViewType, ImageAccess, ImageAspects, ImageSubresourceRange, ImageViewAbstract,
         SampleCount,
@@ -28,21 +29,26 @@ use crate::{
         graphics::{
             input_assembly::{PrimitiveTopology, PrimitiveTopologyClass},
             render_pass::PipelineRenderPassType,
-            vertex_input::VertexInputRate,
+            validate_vertex_input_attributes,
+            vertex_input::{VertexInputRate, VertexInputState},
+            VertexInputAttributeError,
         },
+        ray_tracing::ShaderBindingTable,
         DynamicState, GraphicsPipeline, PartialStateMode, Pipeline, PipelineLayout,
     },
     sampler::{Sampler, SamplerImageViewIncompatibleError},
-    shader::{DescriptorBindingRequirements, ShaderScalarType, ShaderStage},
+    shader::{DescriptorBindingRequirements, ShaderInterface, ShaderScalarType, ShaderStage},
     sync::{AccessFlags, PipelineMemoryAccess, PipelineStages},
-    DeviceSize, RequiresOneOf, VulkanObject,
+    DeviceSize, RequiresOneOf, Version, VulkanObject,
 };
+use smallvec::SmallVec;
 use std::{
     cmp::min,
     error::Error,
     fmt::{Display, Error as FmtError, Formatter},
     mem::size_of,
     ops::Range,
+    ptr,
     sync::Arc,
 };
 
@@ -222,6 +228,11 @@ where
             None => return Err(PipelineExecutionError::PipelineNotBound),
         };
 
+        // VUID-vkCmdDraw-stage-06481
+        if pipeline.shader(ShaderStage::Mesh).is_some() {
+            return Err(PipelineExecutionError::MeshShaderPipelineBound);
+        }
+
         self.validate_pipeline_descriptor_sets(pipeline)?;
         self.validate_pipeline_push_constants(pipeline.layout())?;
         self.validate_pipeline_graphics_dynamic_state(pipeline)?;
@@ -293,6 +304,11 @@ where
             None => return Err(PipelineExecutionError::PipelineNotBound),
         };
 
+        // VUID-vkCmdDrawIndirect-stage-06481
+        if pipeline.shader(ShaderStage::Mesh).is_some() {
+            return Err(PipelineExecutionError::MeshShaderPipelineBound);
+        }
+
         self.validate_pipeline_descriptor_sets(pipeline)?;
         self.validate_pipeline_push_constants(pipeline.layout())?;
         self.validate_pipeline_graphics_dynamic_state(pipeline)?;
@@ -404,6 +420,11 @@ where
             None => return Err(PipelineExecutionError::PipelineNotBound),
         };
 
+        // VUID-vkCmdDrawIndexed-stage-06481
+        if pipeline.shader(ShaderStage::Mesh).is_some() {
+            return Err(PipelineExecutionError::MeshShaderPipelineBound);
+        }
+
         self.validate_pipeline_descriptor_sets(pipeline)?;
         self.validate_pipeline_push_constants(pipeline.layout())?;
         self.validate_pipeline_graphics_dynamic_state(pipeline)?;
@@ -482,6 +503,11 @@ where
             None => return Err(PipelineExecutionError::PipelineNotBound),
         };
 
+        // VUID-vkCmdDrawIndexedIndirect-stage-06481
+        if pipeline.shader(ShaderStage::Mesh).is_some() {
+            return Err(PipelineExecutionError::MeshShaderPipelineBound);
+        }
+
         self.validate_pipeline_descriptor_sets(pipeline)?;
         self.validate_pipeline_push_constants(pipeline.layout())?;
         self.validate_pipeline_graphics_dynamic_state(pipeline)?;
@@ -519,507 +545,1538 @@ where
         Ok(())
     }
 
-    fn validate_index_buffer(
+    /// Perform multiple draw operations using a graphics pipeline, reading the number of draws
+    /// from a buffer.
+    ///
+    /// One draw is performed for each [`DrawIndirectCommand`] struct in `indirect_buffer`, up to
+    /// the value stored in `count_buffer` or `max_draw_count`, whichever is smaller. This allows
+    /// the number of draws to be determined on the device.
+    ///
+    /// A graphics pipeline must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
+    /// pipeline, such as descriptor sets, vertex buffers and dynamic state, must have been set
+    /// beforehand. If the bound graphics pipeline uses vertex buffers, then the vertex and instance
+    /// ranges of each `DrawIndirectCommand` in the indirect buffer must be in range of the bound
+    /// vertex buffers.
+    pub fn draw_indirect_count<Inb, Cb>(
+        &mut self,
+        indirect_buffer: Arc<Inb>,
+        count_buffer: Arc<Cb>,
+        max_draw_count: u32,
+    ) -> Result<&mut Self, PipelineExecutionError>
+    where
+        Inb: TypedBufferAccess<Content = [DrawIndirectCommand]> + Send + Sync + 'static,
+        Cb: TypedBufferAccess<Content = u32> + Send + Sync + 'static,
+    {
+        let stride = size_of::<DrawIndirectCommand>() as u32;
+        self.validate_draw_indirect_count(&indirect_buffer, &count_buffer, max_draw_count, stride)?;
+
+        unsafe {
+            self.inner.draw_indirect_count(
+                indirect_buffer,
+                count_buffer,
+                max_draw_count,
+                stride,
+            )?;
+        }
+
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.render_pass_state.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        Ok(self)
+    }
+
+    fn validate_draw_indirect_count(
         &self,
-        indices: Option<(u32, u32)>,
+        indirect_buffer: &dyn BufferAccess,
+        count_buffer: &dyn BufferAccess,
+        max_draw_count: u32,
+        stride: u32,
     ) -> Result<(), PipelineExecutionError> {
-        let current_state = self.state();
+        // VUID-vkCmdDrawIndirectCount-None-04445
+        if !(self.device().enabled_features().draw_indirect_count
+            || self.device().enabled_extensions().khr_draw_indirect_count)
+        {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::draw_indirect_count`",
+                requires_one_of: RequiresOneOf {
+                    features: &["draw_indirect_count"],
+                    device_extensions: &["khr_draw_indirect_count"],
+                    ..Default::default()
+                },
+            });
+        }
 
-        // VUID?
-        let (index_buffer, index_type) = match current_state.index_buffer() {
-            Some(x) => x,
-            None => return Err(PipelineExecutionError::IndexBufferNotBound),
-        };
+        // VUID-vkCmdDrawIndirectCount-renderpass
+        let render_pass_state = self
+            .render_pass_state
+            .as_ref()
+            .ok_or(PipelineExecutionError::ForbiddenOutsideRenderPass)?;
 
-        if let Some((first_index, index_count)) = indices {
-            let max_index_count = (index_buffer.size() / index_type.size()) as u32;
+        // VUID-vkCmdDrawIndirectCount-None-02700
+        let pipeline = match self.state().pipeline_graphics() {
+            Some(x) => x.as_ref(),
+            None => return Err(PipelineExecutionError::PipelineNotBound),
+        };
 
-            // // VUID-vkCmdDrawIndexed-firstIndex-04932
-            if first_index + index_count > max_index_count {
-                return Err(PipelineExecutionError::IndexBufferRangeOutOfBounds {
-                    highest_index: first_index + index_count,
-                    max_index_count,
-                });
-            }
+        // VUID-vkCmdDrawIndirectCount-stage-06481
+        if pipeline.shader(ShaderStage::Mesh).is_some() {
+            return Err(PipelineExecutionError::MeshShaderPipelineBound);
         }
 
+        self.validate_pipeline_descriptor_sets(pipeline)?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(pipeline)?;
+        self.validate_pipeline_graphics_render_pass(pipeline, render_pass_state)?;
+        self.validate_pipeline_graphics_vertex_buffers(pipeline, None, None)?;
+
+        self.validate_indirect_buffer(indirect_buffer)?;
+        self.validate_indirect_count_buffer(indirect_buffer, count_buffer, max_draw_count, stride)?;
+
         Ok(())
     }
 
-    fn validate_indirect_buffer(
-        &self,
-        buffer: &dyn BufferAccess,
-    ) -> Result<(), PipelineExecutionError> {
-        // VUID-vkCmdDispatchIndirect-commonparent
-        assert_eq!(self.device(), buffer.device());
+    /// Perform multiple draw operations using a graphics pipeline, using an index buffer,
+    /// reading the number of draws from a buffer.
+    ///
+    /// One draw is performed for each [`DrawIndexedIndirectCommand`] struct in `indirect_buffer`,
+    /// up to the value stored in `count_buffer` or `max_draw_count`, whichever is smaller. This
+    /// allows the number of draws to be determined on the device.
+    ///
+    /// An index buffer must have been bound using
+    /// [`bind_index_buffer`](Self::bind_index_buffer), and the index ranges of each
+    /// `DrawIndexedIndirectCommand` in the indirect buffer must be in range of the bound index
+    /// buffer.
+    ///
+    /// A graphics pipeline must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
+    /// pipeline, such as descriptor sets, vertex buffers and dynamic state, must have been set
+    /// beforehand. If the bound graphics pipeline uses vertex buffers, then the instance ranges of
+    /// each `DrawIndexedIndirectCommand` in the indirect buffer must be in range of the bound
+    /// vertex buffers.
+    pub fn draw_indexed_indirect_count<Inb, Cb>(
+        &mut self,
+        indirect_buffer: Arc<Inb>,
+        count_buffer: Arc<Cb>,
+        max_draw_count: u32,
+    ) -> Result<&mut Self, PipelineExecutionError>
+    where
+        Inb: TypedBufferAccess<Content = [DrawIndexedIndirectCommand]> + Send + Sync + 'static,
+        Cb: TypedBufferAccess<Content = u32> + Send + Sync + 'static,
+    {
+        let stride = size_of::<DrawIndexedIndirectCommand>() as u32;
+        self.validate_draw_indexed_indirect_count(
+            &indirect_buffer,
+            &count_buffer,
+            max_draw_count,
+            stride,
+        )?;
 
-        // VUID-vkCmdDispatchIndirect-buffer-02709
-        if !buffer.usage().intersects(BufferUsage::INDIRECT_BUFFER) {
-            return Err(PipelineExecutionError::IndirectBufferMissingUsage);
+        unsafe {
+            self.inner.draw_indexed_indirect_count(
+                indirect_buffer,
+                count_buffer,
+                max_draw_count,
+                stride,
+            )?;
         }
 
-        // VUID-vkCmdDispatchIndirect-offset-02710
-        // TODO:
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.render_pass_state.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
 
-        Ok(())
+        Ok(self)
     }
 
-    fn validate_pipeline_descriptor_sets<Pl: Pipeline>(
+    fn validate_draw_indexed_indirect_count(
         &self,
-        pipeline: &Pl,
+        indirect_buffer: &dyn BufferAccess,
+        count_buffer: &dyn BufferAccess,
+        max_draw_count: u32,
+        stride: u32,
     ) -> Result<(), PipelineExecutionError> {
-        fn validate_resources<T>(
-            set_num: u32,
-            binding_num: u32,
-            binding_reqs: &DescriptorBindingRequirements,
-            elements: &[Option<T>],
-            mut extra_check: impl FnMut(u32, &T) -> Result<(), DescriptorResourceInvalidError>,
-        ) -> Result<(), PipelineExecutionError> {
-            let elements_to_check = if let Some(descriptor_count) = binding_reqs.descriptor_count {
-                // The shader has a fixed-sized array, so it will never access more than
-                // the first `descriptor_count` elements.
-                elements.get(..descriptor_count as usize).ok_or({
-                    // There are less than `descriptor_count` elements in `elements`
-                    PipelineExecutionError::DescriptorResourceInvalid {
-                        set_num,
-                        binding_num,
-                        index: elements.len() as u32,
-                        error: DescriptorResourceInvalidError::Missing,
-                    }
-                })?
-            } else {
-                // The shader has a runtime-sized array, so any element could potentially
-                // be accessed. We must check them all.
-                elements
-            };
+        // VUID-vkCmdDrawIndexedIndirectCount-None-04445
+        if !(self.device().enabled_features().draw_indirect_count
+            || self.device().enabled_extensions().khr_draw_indirect_count)
+        {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::draw_indexed_indirect_count`",
+                requires_one_of: RequiresOneOf {
+                    features: &["draw_indirect_count"],
+                    device_extensions: &["khr_draw_indirect_count"],
+                    ..Default::default()
+                },
+            });
+        }
 
-            for (index, element) in elements_to_check.iter().enumerate() {
-                let index = index as u32;
+        // VUID-vkCmdDrawIndexedIndirectCount-renderpass
+        let render_pass_state = self
+            .render_pass_state
+            .as_ref()
+            .ok_or(PipelineExecutionError::ForbiddenOutsideRenderPass)?;
 
-                // VUID-vkCmdDispatch-None-02699
-                let element = match element {
-                    Some(x) => x,
-                    None => {
-                        return Err(PipelineExecutionError::DescriptorResourceInvalid {
-                            set_num,
-                            binding_num,
-                            index,
-                            error: DescriptorResourceInvalidError::Missing,
-                        })
-                    }
-                };
+        // VUID-vkCmdDrawIndexedIndirectCount-None-02700
+        let pipeline = match self.state().pipeline_graphics() {
+            Some(x) => x.as_ref(),
+            None => return Err(PipelineExecutionError::PipelineNotBound),
+        };
 
-                if let Err(error) = extra_check(index, element) {
-                    return Err(PipelineExecutionError::DescriptorResourceInvalid {
-                        set_num,
-                        binding_num,
-                        index,
-                        error,
-                    });
-                }
-            }
+        // VUID-vkCmdDrawIndexedIndirectCount-stage-06481
+        if pipeline.shader(ShaderStage::Mesh).is_some() {
+            return Err(PipelineExecutionError::MeshShaderPipelineBound);
+        }
 
-            Ok(())
+        self.validate_pipeline_descriptor_sets(pipeline)?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(pipeline)?;
+        self.validate_pipeline_graphics_render_pass(pipeline, render_pass_state)?;
+        self.validate_pipeline_graphics_vertex_buffers(pipeline, None, None)?;
+
+        self.validate_index_buffer(None)?;
+        self.validate_indirect_buffer(indirect_buffer)?;
+        self.validate_indirect_count_buffer(indirect_buffer, count_buffer, max_draw_count, stride)?;
+
+        Ok(())
+    }
+
+    /// Perform a single draw operation using a graphics pipeline, reading the number of vertices
+    /// from a transform feedback counter buffer.
+    ///
+    /// The number of vertices is calculated by subtracting `counter_offset` from the byte count
+    /// stored in `counter_buffer`, and dividing the result by `vertex_stride`. The counter
+    /// buffer is typically written by [`end_transform_feedback`](Self::end_transform_feedback),
+    /// which allows the vertices that were captured by transform feedback to be drawn without
+    /// reading the vertex count back to the host.
+    ///
+    /// The [`transform_feedback`](crate::device::Features::transform_feedback) feature must be
+    /// enabled on the device, and the
+    /// [`transform_feedback_draw`](crate::device::Properties::transform_feedback_draw) property
+    /// must be supported.
+    ///
+    /// A graphics pipeline must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
+    /// pipeline, such as descriptor sets, vertex buffers and dynamic state, must have been set
+    /// beforehand. If the bound graphics pipeline uses vertex buffers, then the provided instance
+    /// range must be in range of the bound vertex buffers.
+    pub fn draw_indirect_byte_count<Cb>(
+        &mut self,
+        instance_count: u32,
+        first_instance: u32,
+        counter_buffer: Arc<Cb>,
+        counter_offset: u32,
+        vertex_stride: u32,
+    ) -> Result<&mut Self, PipelineExecutionError>
+    where
+        Cb: TypedBufferAccess<Content = u32> + Send + Sync + 'static,
+    {
+        self.validate_draw_indirect_byte_count(
+            instance_count,
+            first_instance,
+            &counter_buffer,
+            vertex_stride,
+        )?;
+
+        unsafe {
+            self.inner.draw_indirect_byte_count(
+                instance_count,
+                first_instance,
+                counter_buffer,
+                counter_offset,
+                vertex_stride,
+            )?;
         }
 
-        if pipeline.num_used_descriptor_sets() == 0 {
-            return Ok(());
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.render_pass_state.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
         }
 
-        let current_state = self.state();
+        Ok(self)
+    }
 
-        // VUID-vkCmdDispatch-None-02697
-        let bindings_pipeline_layout =
-            match current_state.descriptor_sets_pipeline_layout(pipeline.bind_point()) {
-                Some(x) => x,
-                None => return Err(PipelineExecutionError::PipelineLayoutNotCompatible),
-            };
+    fn validate_draw_indirect_byte_count(
+        &self,
+        instance_count: u32,
+        first_instance: u32,
+        counter_buffer: &dyn BufferAccess,
+        vertex_stride: u32,
+    ) -> Result<(), PipelineExecutionError> {
+        let device = self.device();
+        let properties = device.physical_device().properties();
 
-        // VUID-vkCmdDispatch-None-02697
-        if !pipeline.layout().is_compatible_with(
-            bindings_pipeline_layout,
-            pipeline.num_used_descriptor_sets(),
-        ) {
-            return Err(PipelineExecutionError::PipelineLayoutNotCompatible);
+        // VUID-vkCmdDrawIndirectByteCountEXT-transformFeedback-02287
+        if !device.enabled_features().transform_feedback {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::draw_indirect_byte_count`",
+                requires_one_of: RequiresOneOf {
+                    features: &["transform_feedback"],
+                    ..Default::default()
+                },
+            });
         }
 
-        for (&(set_num, binding_num), binding_reqs) in pipeline.descriptor_binding_requirements() {
-            let layout_binding =
-                &pipeline.layout().set_layouts()[set_num as usize].bindings()[&binding_num];
+        // VUID-vkCmdDrawIndirectByteCountEXT-transformFeedbackDraw-02288
+        if !properties.transform_feedback_draw.unwrap_or(false) {
+            return Err(PipelineExecutionError::TransformFeedbackDrawNotSupported);
+        }
 
-            let check_buffer =
-                |_index: u32, (_buffer, _range): &(Arc<dyn BufferAccess>, Range<DeviceSize>)| Ok(());
+        // VUID-vkCmdDrawIndirectByteCountEXT-vertexStride-02289
+        let max_stride = properties
+            .max_transform_feedback_buffer_data_stride
+            .unwrap_or(0);
 
-            let check_buffer_view = |index: u32, buffer_view: &Arc<dyn BufferViewAbstract>| {
-                for desc_reqs in (binding_reqs.descriptors.get(&Some(index)).into_iter())
-                    .chain(binding_reqs.descriptors.get(&None))
-                {
-                    if layout_binding.descriptor_type == DescriptorType::StorageTexelBuffer {
-                        // VUID-vkCmdDispatch-OpTypeImage-06423
-                        if binding_reqs.image_format.is_none()
-                            && !desc_reqs.memory_write.is_empty()
-                            && !buffer_view
-                                .format_features()
-                                .intersects(FormatFeatures::STORAGE_WRITE_WITHOUT_FORMAT)
-                        {
-                            return Err(DescriptorResourceInvalidError::StorageWriteWithoutFormatNotSupported);
-                        }
+        if vertex_stride > max_stride {
+            return Err(
+                PipelineExecutionError::MaxTransformFeedbackBufferDataStrideExceeded {
+                    provided: vertex_stride,
+                    max: max_stride,
+                },
+            );
+        }
 
-                        // VUID-vkCmdDispatch-OpTypeImage-06424
-                        if binding_reqs.image_format.is_none()
-                            && !desc_reqs.memory_read.is_empty()
-                            && !buffer_view
-                                .format_features()
-                                .intersects(FormatFeatures::STORAGE_READ_WITHOUT_FORMAT)
-                        {
-                            return Err(DescriptorResourceInvalidError::StorageReadWithoutFormatNotSupported);
-                        }
-                    }
-                }
+        // VUID-vkCmdDrawIndirectByteCountEXT-vertexStride-09474
+        if vertex_stride % 4 != 0 {
+            return Err(PipelineExecutionError::VertexStrideNotAligned { vertex_stride });
+        }
 
-                Ok(())
-            };
+        // VUID-vkCmdDrawIndirectByteCountEXT-renderpass
+        let render_pass_state = self
+            .render_pass_state
+            .as_ref()
+            .ok_or(PipelineExecutionError::ForbiddenOutsideRenderPass)?;
 
-            let check_image_view_common = |index: u32, image_view: &Arc<dyn ImageViewAbstract>| {
-                for desc_reqs in (binding_reqs.descriptors.get(&Some(index)).into_iter())
-                    .chain(binding_reqs.descriptors.get(&None))
-                {
-                    // VUID-vkCmdDispatch-None-02691
-                    if desc_reqs.storage_image_atomic
-                        && !image_view
-                            .format_features()
-                            .intersects(FormatFeatures::STORAGE_IMAGE_ATOMIC)
-                    {
-                        return Err(DescriptorResourceInvalidError::StorageImageAtomicNotSupported);
-                    }
+        // VUID-vkCmdDrawIndirectByteCountEXT-None-02700
+        let pipeline = match self.state().pipeline_graphics() {
+            Some(x) => x.as_ref(),
+            None => return Err(PipelineExecutionError::PipelineNotBound),
+        };
 
-                    if layout_binding.descriptor_type == DescriptorType::StorageImage {
-                        // VUID-vkCmdDispatch-OpTypeImage-06423
-                        if binding_reqs.image_format.is_none()
-                            && !desc_reqs.memory_write.is_empty()
-                            && !image_view
-                                .format_features()
-                                .intersects(FormatFeatures::STORAGE_WRITE_WITHOUT_FORMAT)
-                        {
-                            return Err(
-                            DescriptorResourceInvalidError::StorageWriteWithoutFormatNotSupported,
-                        );
-                        }
+        // VUID-vkCmdDrawIndirectByteCountEXT-stage-06481
+        if pipeline.shader(ShaderStage::Mesh).is_some() {
+            return Err(PipelineExecutionError::MeshShaderPipelineBound);
+        }
 
-                        // VUID-vkCmdDispatch-OpTypeImage-06424
-                        if binding_reqs.image_format.is_none()
-                            && !desc_reqs.memory_read.is_empty()
-                            && !image_view
-                                .format_features()
-                                .intersects(FormatFeatures::STORAGE_READ_WITHOUT_FORMAT)
-                        {
-                            return Err(
-                            DescriptorResourceInvalidError::StorageReadWithoutFormatNotSupported,
-                        );
-                        }
-                    }
-                }
+        self.validate_pipeline_descriptor_sets(pipeline)?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(pipeline)?;
+        self.validate_pipeline_graphics_render_pass(pipeline, render_pass_state)?;
+        self.validate_pipeline_graphics_vertex_buffers(
+            pipeline,
+            None,
+            Some((first_instance, instance_count)),
+        )?;
 
-                /*
-                   Instruction/Sampler/Image View Validation
-                   https://registry.khronos.org/vulkan/specs/1.3-extensions/html/chap16.html#textures-input-validation
-                */
+        // VUID-vkCmdDrawIndirectByteCountEXT-counterBuffer-02290
+        // VUID-vkCmdDrawIndirectByteCountEXT-counterBuffer-04567
+        self.validate_indirect_buffer(counter_buffer)?;
 
-                // The SPIR-V Image Format is not compatible with the image view’s format.
-                if let Some(format) = binding_reqs.image_format {
-                    if image_view.format() != Some(format) {
-                        return Err(DescriptorResourceInvalidError::ImageViewFormatMismatch {
-                            required: format,
-                            provided: image_view.format(),
-                        });
-                    }
-                }
+        // VUID-vkCmdDrawIndirectByteCountEXT-counterBufferOffset-04568
+        let offset = counter_buffer.inner().offset;
 
-                // Rules for viewType
-                if let Some(image_view_type) = binding_reqs.image_view_type {
-                    if image_view.view_type() != image_view_type {
-                        return Err(DescriptorResourceInvalidError::ImageViewTypeMismatch {
-                            required: image_view_type,
-                            provided: image_view.view_type(),
-                        });
-                    }
-                }
+        if offset % 4 != 0 {
+            return Err(PipelineExecutionError::CounterBufferOffsetNotAligned { offset });
+        }
 
-                // - If the image was created with VkImageCreateInfo::samples equal to
-                //   VK_SAMPLE_COUNT_1_BIT, the instruction must have MS = 0.
-                // - If the image was created with VkImageCreateInfo::samples not equal to
-                //   VK_SAMPLE_COUNT_1_BIT, the instruction must have MS = 1.
-                if binding_reqs.image_multisampled
-                    != (image_view.image().samples() != SampleCount::Sample1)
-                {
-                    return Err(
-                        DescriptorResourceInvalidError::ImageViewMultisampledMismatch {
-                            required: binding_reqs.image_multisampled,
-                            provided: image_view.image().samples() != SampleCount::Sample1,
-                        },
-                    );
-                }
+        Ok(())
+    }
 
-                // - If the Sampled Type of the OpTypeImage does not match the numeric format of the
-                //   image, as shown in the SPIR-V Sampled Type column of the
-                //   Interpretation of Numeric Format table.
-                // - If the signedness of any read or sample operation does not match the signedness of
-                //   the image’s format.
-                if let Some(scalar_type) = binding_reqs.image_scalar_type {
-                    let aspects = image_view.subresource_range().aspects;
-                    let view_scalar_type = ShaderScalarType::from(
-                        if aspects.intersects(
-                            ImageAspects::COLOR
-                                | ImageAspects::PLANE_0
-                                | ImageAspects::PLANE_1
-                                | ImageAspects::PLANE_2,
-                        ) {
-                            image_view.format().unwrap().type_color().unwrap()
-                        } else if aspects.intersects(ImageAspects::DEPTH) {
-                            image_view.format().unwrap().type_depth().unwrap()
-                        } else if aspects.intersects(ImageAspects::STENCIL) {
-                            image_view.format().unwrap().type_stencil().unwrap()
-                        } else {
-                            // Per `ImageViewBuilder::aspects` and
-                            // VUID-VkDescriptorImageInfo-imageView-01976
-                            unreachable!()
-                        },
-                    );
+    /// Perform multiple draw operations using a graphics pipeline, sharing the same instance
+    /// range.
+    ///
+    /// One draw is performed for each element of `vertex_info`, which specifies the first vertex
+    /// and the number of vertices to draw. All draws use the same `instance_count` and
+    /// `first_instance`. This is equivalent to calling [`draw`](Self::draw) once for each element,
+    /// but with less overhead. The number of draws is limited by the
+    /// [`max_multi_draw_count`](crate::device::Properties::max_multi_draw_count) limit.
+    ///
+    /// The [`multi_draw`](crate::device::Features::multi_draw) feature must be enabled on the
+    /// device.
+    ///
+    /// A graphics pipeline must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
+    /// pipeline, such as descriptor sets, vertex buffers and dynamic state, must have been set
+    /// beforehand. If the bound graphics pipeline uses vertex buffers, then the vertex range of
+    /// each element of `vertex_info`, and the provided instance range, must be in range of the
+    /// bound vertex buffers.
+    pub fn draw_multi(
+        &mut self,
+        vertex_info: impl IntoIterator<Item = MultiDrawInfo>,
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<&mut Self, PipelineExecutionError> {
+        let vertex_info: SmallVec<[MultiDrawInfo; 4]> = vertex_info.into_iter().collect();
+        self.validate_draw_multi(&vertex_info, instance_count, first_instance)?;
 
-                    if scalar_type != view_scalar_type {
-                        return Err(
-                            DescriptorResourceInvalidError::ImageViewScalarTypeMismatch {
-                                required: scalar_type,
-                                provided: view_scalar_type,
-                            },
-                        );
-                    }
-                }
+        unsafe {
+            self.inner
+                .draw_multi(vertex_info, instance_count, first_instance)?;
+        }
 
-                Ok(())
-            };
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.render_pass_state.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
 
-            let check_sampler_common = |index: u32, sampler: &Arc<Sampler>| {
-                for desc_reqs in (binding_reqs.descriptors.get(&Some(index)).into_iter())
-                    .chain(binding_reqs.descriptors.get(&None))
-                {
-                    // VUID-vkCmdDispatch-None-02703
-                    // VUID-vkCmdDispatch-None-02704
-                    if desc_reqs.sampler_no_unnormalized_coordinates
-                        && sampler.unnormalized_coordinates()
-                    {
-                        return Err(
-                        DescriptorResourceInvalidError::SamplerUnnormalizedCoordinatesNotAllowed,
-                    );
-                    }
+        Ok(self)
+    }
 
-                    // - OpImageFetch, OpImageSparseFetch, OpImage*Gather, and OpImageSparse*Gather must not
-                    //   be used with a sampler that enables sampler Y′CBCR conversion.
-                    // - The ConstOffset and Offset operands must not be used with a sampler that enables
-                    //   sampler Y′CBCR conversion.
-                    if desc_reqs.sampler_no_ycbcr_conversion
-                        && sampler.sampler_ycbcr_conversion().is_some()
-                    {
-                        return Err(
-                            DescriptorResourceInvalidError::SamplerYcbcrConversionNotAllowed,
-                        );
-                    }
+    fn validate_draw_multi(
+        &self,
+        vertex_info: &[MultiDrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<(), PipelineExecutionError> {
+        // VUID-vkCmdDrawMultiEXT-None-04933
+        if !self.device().enabled_features().multi_draw {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::draw_multi`",
+                requires_one_of: RequiresOneOf {
+                    features: &["multi_draw"],
+                    ..Default::default()
+                },
+            });
+        }
 
-                    /*
-                        Instruction/Sampler/Image View Validation
-                        https://registry.khronos.org/vulkan/specs/1.3-extensions/html/chap16.html#textures-input-validation
-                    */
+        // VUID-vkCmdDrawMultiEXT-drawCount-04934
+        self.validate_multi_draw_count(vertex_info.len())?;
 
-                    // - The SPIR-V instruction is one of the OpImage*Dref* instructions and the sampler
-                    //   compareEnable is VK_FALSE
-                    // - The SPIR-V instruction is not one of the OpImage*Dref* instructions and the sampler
-                    //   compareEnable is VK_TRUE
-                    if desc_reqs.sampler_compare != sampler.compare().is_some() {
-                        return Err(DescriptorResourceInvalidError::SamplerCompareMismatch {
-                            required: desc_reqs.sampler_compare,
-                            provided: sampler.compare().is_some(),
-                        });
-                    }
-                }
+        // VUID-vkCmdDrawMultiEXT-renderpass
+        let render_pass_state = self
+            .render_pass_state
+            .as_ref()
+            .ok_or(PipelineExecutionError::ForbiddenOutsideRenderPass)?;
 
-                Ok(())
-            };
+        // VUID-vkCmdDrawMultiEXT-None-02700
+        let pipeline = match self.state().pipeline_graphics() {
+            Some(x) => x.as_ref(),
+            None => return Err(PipelineExecutionError::PipelineNotBound),
+        };
 
-            let check_image_view = |index: u32, image_view: &Arc<dyn ImageViewAbstract>| {
-                check_image_view_common(index, image_view)?;
+        // VUID-vkCmdDrawMultiEXT-stage-06481
+        if pipeline.shader(ShaderStage::Mesh).is_some() {
+            return Err(PipelineExecutionError::MeshShaderPipelineBound);
+        }
 
-                if let Some(sampler) = layout_binding.immutable_samplers.get(index as usize) {
-                    check_sampler_common(index, sampler)?;
-                }
+        self.validate_pipeline_descriptor_sets(pipeline)?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(pipeline)?;
+        self.validate_pipeline_graphics_render_pass(pipeline, render_pass_state)?;
+        self.validate_pipeline_graphics_vertex_buffers(
+            pipeline,
+            None,
+            Some((first_instance, instance_count)),
+        )?;
 
-                Ok(())
-            };
+        for info in vertex_info {
+            self.validate_pipeline_graphics_vertex_buffers(
+                pipeline,
+                Some((info.first_vertex, info.vertex_count)),
+                None,
+            )?;
+        }
 
-            let check_image_view_sampler =
-                |index: u32, (image_view, sampler): &(Arc<dyn ImageViewAbstract>, Arc<Sampler>)| {
-                    check_image_view_common(index, image_view)?;
-                    check_sampler_common(index, sampler)?;
+        Ok(())
+    }
 
-                    Ok(())
-                };
+    /// Perform multiple draw operations using a graphics pipeline, using an index buffer and
+    /// sharing the same instance range.
+    ///
+    /// One draw is performed for each element of `index_info`, which specifies the first index,
+    /// the number of indices to draw and the vertex offset. All draws use the same
+    /// `instance_count` and `first_instance`. If `vertex_offset` is `Some`, then it is used for
+    /// every draw, and the `vertex_offset` of each element of `index_info` is ignored. This is
+    /// equivalent to calling [`draw_indexed`](Self::draw_indexed) once for each element, but with
+    /// less overhead. The number of draws is limited by the
+    /// [`max_multi_draw_count`](crate::device::Properties::max_multi_draw_count) limit.
+    ///
+    /// The [`multi_draw`](crate::device::Features::multi_draw) feature must be enabled on the
+    /// device.
+    ///
+    /// An index buffer must have been bound using
+    /// [`bind_index_buffer`](Self::bind_index_buffer), and the index range of each element of
+    /// `index_info` must be in range of the bound index buffer.
+    ///
+    /// A graphics pipeline must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
+    /// pipeline, such as descriptor sets, vertex buffers and dynamic state, must have been set
+    /// beforehand. If the bound graphics pipeline uses vertex buffers, then the provided instance
+    /// range must be in range of the bound vertex buffers. The vertex indices in the index buffer
+    /// must be in range of the bound vertex buffers.
+    pub fn draw_multi_indexed(
+        &mut self,
+        index_info: impl IntoIterator<Item = MultiDrawIndexedInfo>,
+        instance_count: u32,
+        first_instance: u32,
+        vertex_offset: Option<i32>,
+    ) -> Result<&mut Self, PipelineExecutionError> {
+        let index_info: SmallVec<[MultiDrawIndexedInfo; 4]> = index_info.into_iter().collect();
+        self.validate_draw_multi_indexed(&index_info, instance_count, first_instance)?;
 
-            let check_sampler = |index: u32, sampler: &Arc<Sampler>| {
-                check_sampler_common(index, sampler)?;
+        unsafe {
+            self.inner.draw_multi_indexed(
+                index_info,
+                instance_count,
+                first_instance,
+                vertex_offset,
+            )?;
+        }
 
-                for desc_reqs in (binding_reqs.descriptors.get(&Some(index)).into_iter())
-                    .chain(binding_reqs.descriptors.get(&None))
-                {
-                    // Check sampler-image compatibility. Only done for separate samplers;
-                    // combined image samplers are checked when updating the descriptor set.
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.render_pass_state.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
 
-                    // If the image view isn't actually present in the resources, then just skip it.
-                    // It will be caught later by check_resources.
-                    let iter = desc_reqs.sampler_with_images.iter().filter_map(|id| {
-                        current_state
-                            .descriptor_set(pipeline.bind_point(), id.set)
-                            .and_then(|set| set.resources().binding(id.binding))
-                            .and_then(|res| match res {
-                                DescriptorBindingResources::ImageView(elements) => elements
-                                    .get(id.index as usize)
-                                    .and_then(|opt| opt.as_ref().map(|opt| (id, opt))),
-                                _ => None,
-                            })
-                    });
+        Ok(self)
+    }
 
-                    for (id, image_view) in iter {
-                        if let Err(error) = sampler.check_can_sample(image_view.as_ref()) {
-                            return Err(
-                                DescriptorResourceInvalidError::SamplerImageViewIncompatible {
-                                    image_view_set_num: id.set,
-                                    image_view_binding_num: id.binding,
-                                    image_view_index: id.index,
-                                    error,
-                                },
-                            );
-                        }
-                    }
-                }
+    fn validate_draw_multi_indexed(
+        &self,
+        index_info: &[MultiDrawIndexedInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<(), PipelineExecutionError> {
+        // TODO: how to handle an index out of range of the vertex buffers?
 
-                Ok(())
-            };
+        // VUID-vkCmdDrawMultiIndexedEXT-None-04937
+        if !self.device().enabled_features().multi_draw {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::draw_multi_indexed`",
+                requires_one_of: RequiresOneOf {
+                    features: &["multi_draw"],
+                    ..Default::default()
+                },
+            });
+        }
 
-            let check_none = |index: u32, _: &()| {
-                if let Some(sampler) = layout_binding.immutable_samplers.get(index as usize) {
-                    check_sampler(index, sampler)?;
-                }
+        // VUID-vkCmdDrawMultiIndexedEXT-drawCount-04939
+        self.validate_multi_draw_count(index_info.len())?;
 
-                Ok(())
-            };
+        // VUID-vkCmdDrawMultiIndexedEXT-renderpass
+        let render_pass_state = self
+            .render_pass_state
+            .as_ref()
+            .ok_or(PipelineExecutionError::ForbiddenOutsideRenderPass)?;
 
-            let set_resources = match current_state.descriptor_set(pipeline.bind_point(), set_num) {
-                Some(x) => x.resources(),
-                None => return Err(PipelineExecutionError::DescriptorSetNotBound { set_num }),
-            };
+        // VUID-vkCmdDrawMultiIndexedEXT-None-02700
+        let pipeline = match self.state().pipeline_graphics() {
+            Some(x) => x.as_ref(),
+            None => return Err(PipelineExecutionError::PipelineNotBound),
+        };
 
-            let binding_resources = set_resources.binding(binding_num).unwrap();
+        // VUID-vkCmdDrawMultiIndexedEXT-stage-06481
+        if pipeline.shader(ShaderStage::Mesh).is_some() {
+            return Err(PipelineExecutionError::MeshShaderPipelineBound);
+        }
 
-            match binding_resources {
-                DescriptorBindingResources::None(elements) => {
-                    validate_resources(set_num, binding_num, binding_reqs, elements, check_none)?;
-                }
-                DescriptorBindingResources::Buffer(elements) => {
-                    validate_resources(set_num, binding_num, binding_reqs, elements, check_buffer)?;
-                }
-                DescriptorBindingResources::BufferView(elements) => {
-                    validate_resources(
-                        set_num,
-                        binding_num,
-                        binding_reqs,
-                        elements,
-                        check_buffer_view,
-                    )?;
-                }
-                DescriptorBindingResources::ImageView(elements) => {
-                    validate_resources(
-                        set_num,
-                        binding_num,
-                        binding_reqs,
-                        elements,
-                        check_image_view,
-                    )?;
-                }
-                DescriptorBindingResources::ImageViewSampler(elements) => {
-                    validate_resources(
-                        set_num,
-                        binding_num,
-                        binding_reqs,
-                        elements,
-                        check_image_view_sampler,
-                    )?;
-                }
-                DescriptorBindingResources::Sampler(elements) => {
-                    validate_resources(
-                        set_num,
-                        binding_num,
-                        binding_reqs,
-                        elements,
-                        check_sampler,
-                    )?;
-                }
-            }
+        self.validate_pipeline_descriptor_sets(pipeline)?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(pipeline)?;
+        self.validate_pipeline_graphics_render_pass(pipeline, render_pass_state)?;
+        self.validate_pipeline_graphics_vertex_buffers(
+            pipeline,
+            None,
+            Some((first_instance, instance_count)),
+        )?;
+
+        self.validate_index_buffer(None)?;
+
+        // VUID-vkCmdDrawMultiIndexedEXT-firstIndex-04938
+        for info in index_info {
+            self.validate_index_buffer(Some((info.first_index, info.index_count)))?;
         }
 
         Ok(())
     }
 
-    fn validate_pipeline_push_constants(
+    /// Perform a single draw operation using a mesh shading graphics pipeline.
+    ///
+    /// `group_counts` specifies the number of task shader workgroups to dispatch, or the number
+    /// of mesh shader workgroups if the pipeline has no task shader.
+    ///
+    /// A graphics pipeline containing a mesh shader must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the
+    /// graphics pipeline, such as descriptor sets and dynamic state, must have been set
+    /// beforehand.
+    pub fn draw_mesh_tasks(
+        &mut self,
+        group_counts: [u32; 3],
+    ) -> Result<&mut Self, PipelineExecutionError> {
+        self.validate_draw_mesh_tasks(group_counts)?;
+
+        unsafe {
+            self.inner.draw_mesh_tasks(group_counts)?;
+        }
+
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.render_pass_state.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        Ok(self)
+    }
+
+    fn validate_draw_mesh_tasks(
         &self,
-        pipeline_layout: &PipelineLayout,
+        group_counts: [u32; 3],
     ) -> Result<(), PipelineExecutionError> {
-        if pipeline_layout.push_constant_ranges().is_empty()
-            || self.device().enabled_features().maintenance4
-        {
-            return Ok(());
+        // VUID-vkCmdDrawMeshTasksEXT-None-07074
+        if !self.device().enabled_features().mesh_shader {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::draw_mesh_tasks`",
+                requires_one_of: RequiresOneOf {
+                    features: &["mesh_shader"],
+                    ..Default::default()
+                },
+            });
         }
 
-        let current_state = self.state();
+        // VUID-vkCmdDrawMeshTasksEXT-renderpass
+        let render_pass_state = self
+            .render_pass_state
+            .as_ref()
+            .ok_or(PipelineExecutionError::ForbiddenOutsideRenderPass)?;
 
-        // VUID-vkCmdDispatch-maintenance4-06425
-        let constants_pipeline_layout = match current_state.push_constants_pipeline_layout() {
-            Some(x) => x,
-            None => return Err(PipelineExecutionError::PushConstantsMissing),
+        // VUID-vkCmdDrawMeshTasksEXT-None-02700
+        let pipeline = match self.state().pipeline_graphics() {
+            Some(x) => x.as_ref(),
+            None => return Err(PipelineExecutionError::PipelineNotBound),
         };
 
-        // VUID-vkCmdDispatch-maintenance4-06425
-        if pipeline_layout.handle() != constants_pipeline_layout.handle()
-            && pipeline_layout.push_constant_ranges()
-                != constants_pipeline_layout.push_constant_ranges()
-        {
-            return Err(PipelineExecutionError::PushConstantsNotCompatible);
+        self.validate_pipeline_graphics_mesh_shading(pipeline)?;
+        self.validate_pipeline_descriptor_sets(pipeline)?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(pipeline)?;
+        self.validate_pipeline_graphics_render_pass(pipeline, render_pass_state)?;
+
+        let properties = self.device().physical_device().properties();
+        let (max, max_total) = if pipeline.shader(ShaderStage::Task).is_some() {
+            (
+                properties.max_task_work_group_count.unwrap_or_default(),
+                properties
+                    .max_task_work_group_total_count
+                    .unwrap_or_default(),
+            )
+        } else {
+            (
+                properties.max_mesh_work_group_count.unwrap_or_default(),
+                properties
+                    .max_mesh_work_group_total_count
+                    .unwrap_or_default(),
+            )
+        };
+
+        // VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07322
+        // VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07323
+        // VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07324
+        // VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07326
+        // VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07327
+        // VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07328
+        if group_counts[0] > max[0] || group_counts[1] > max[1] || group_counts[2] > max[2] {
+            return Err(PipelineExecutionError::MaxMeshTasksWorkGroupCountExceeded {
+                requested: group_counts,
+                max,
+            });
         }
 
-        let set_bytes = current_state.push_constants();
+        // VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07325
+        // VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07329
+        let total_count = group_counts[0] as u64 * group_counts[1] as u64 * group_counts[2] as u64;
 
-        // VUID-vkCmdDispatch-maintenance4-06425
-        if !pipeline_layout
-            .push_constant_ranges()
-            .iter()
-            .all(|pc_range| set_bytes.contains(pc_range.offset..pc_range.offset + pc_range.size))
-        {
-            return Err(PipelineExecutionError::PushConstantsMissing);
+        if total_count > max_total as u64 {
+            return Err(
+                PipelineExecutionError::MaxMeshTasksWorkGroupTotalCountExceeded {
+                    requested: total_count,
+                    max: max_total,
+                },
+            );
         }
 
         Ok(())
     }
 
-    fn validate_pipeline_graphics_dynamic_state(
-        &self,
-        pipeline: &GraphicsPipeline,
-    ) -> Result<(), PipelineExecutionError> {
-        let device = pipeline.device();
-        let current_state = self.state();
+    /// Perform multiple draw operations using a mesh shading graphics pipeline.
+    ///
+    /// One draw is performed for each [`DrawMeshTasksIndirectCommand`] struct in
+    /// `indirect_buffer`. The maximum number of draw commands in the buffer is limited by the
+    /// [`max_draw_indirect_count`](crate::device::Properties::max_draw_indirect_count) limit.
+    /// This limit is 1 unless the
+    /// [`multi_draw_indirect`](crate::device::Features::multi_draw_indirect) feature has been
+    /// enabled.
+    ///
+    /// A graphics pipeline containing a mesh shader must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the
+    /// graphics pipeline, such as descriptor sets and dynamic state, must have been set
+    /// beforehand.
+    pub fn draw_mesh_tasks_indirect<Inb>(
+        &mut self,
+        indirect_buffer: Arc<Inb>,
+    ) -> Result<&mut Self, PipelineExecutionError>
+    where
+        Inb: TypedBufferAccess<Content = [DrawMeshTasksIndirectCommand]> + Send + Sync + 'static,
+    {
+        let draw_count = indirect_buffer.len() as u32;
+        let stride = size_of::<DrawMeshTasksIndirectCommand>() as u32;
+        self.validate_draw_mesh_tasks_indirect(&indirect_buffer, draw_count, stride)?;
 
-        // VUID-vkCmdDraw-commandBuffer-02701
-        for dynamic_state in pipeline
-            .dynamic_states()
-            .filter(|(_, d)| *d)
-            .map(|(s, _)| s)
-        {
+        unsafe {
+            self.inner
+                .draw_mesh_tasks_indirect(indirect_buffer, draw_count, stride)?;
+        }
+
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.render_pass_state.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        Ok(self)
+    }
+
+    fn validate_draw_mesh_tasks_indirect(
+        &self,
+        indirect_buffer: &dyn BufferAccess,
+        draw_count: u32,
+        _stride: u32,
+    ) -> Result<(), PipelineExecutionError> {
+        // VUID-vkCmdDrawMeshTasksIndirectEXT-None-07074
+        if !self.device().enabled_features().mesh_shader {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::draw_mesh_tasks_indirect`",
+                requires_one_of: RequiresOneOf {
+                    features: &["mesh_shader"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdDrawMeshTasksIndirectEXT-renderpass
+        let render_pass_state = self
+            .render_pass_state
+            .as_ref()
+            .ok_or(PipelineExecutionError::ForbiddenOutsideRenderPass)?;
+
+        // VUID-vkCmdDrawMeshTasksIndirectEXT-None-02700
+        let pipeline = match self.state().pipeline_graphics() {
+            Some(x) => x.as_ref(),
+            None => return Err(PipelineExecutionError::PipelineNotBound),
+        };
+
+        self.validate_pipeline_graphics_mesh_shading(pipeline)?;
+        self.validate_pipeline_descriptor_sets(pipeline)?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(pipeline)?;
+        self.validate_pipeline_graphics_render_pass(pipeline, render_pass_state)?;
+
+        self.validate_indirect_buffer(indirect_buffer)?;
+
+        // VUID-vkCmdDrawMeshTasksIndirectEXT-drawCount-02718
+        if draw_count > 1 && !self.device().enabled_features().multi_draw_indirect {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`draw_count` is greater than `1`",
+                requires_one_of: RequiresOneOf {
+                    features: &["multi_draw_indirect"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let max = self
+            .device()
+            .physical_device()
+            .properties()
+            .max_draw_indirect_count;
+
+        // VUID-vkCmdDrawMeshTasksIndirectEXT-drawCount-02719
+        if draw_count > max {
+            return Err(PipelineExecutionError::MaxDrawIndirectCountExceeded {
+                provided: draw_count,
+                max,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Perform multiple draw operations using a mesh shading graphics pipeline, reading the
+    /// number of draws from a buffer.
+    ///
+    /// One draw is performed for each [`DrawMeshTasksIndirectCommand`] struct in
+    /// `indirect_buffer`, up to the value stored in `count_buffer` or `max_draw_count`, whichever
+    /// is smaller.
+    ///
+    /// A graphics pipeline containing a mesh shader must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the
+    /// graphics pipeline, such as descriptor sets and dynamic state, must have been set
+    /// beforehand.
+    pub fn draw_mesh_tasks_indirect_count<Inb, Cb>(
+        &mut self,
+        indirect_buffer: Arc<Inb>,
+        count_buffer: Arc<Cb>,
+        max_draw_count: u32,
+    ) -> Result<&mut Self, PipelineExecutionError>
+    where
+        Inb: TypedBufferAccess<Content = [DrawMeshTasksIndirectCommand]> + Send + Sync + 'static,
+        Cb: TypedBufferAccess<Content = u32> + Send + Sync + 'static,
+    {
+        let stride = size_of::<DrawMeshTasksIndirectCommand>() as u32;
+        self.validate_draw_mesh_tasks_indirect_count(
+            &indirect_buffer,
+            &count_buffer,
+            max_draw_count,
+            stride,
+        )?;
+
+        unsafe {
+            self.inner.draw_mesh_tasks_indirect_count(
+                indirect_buffer,
+                count_buffer,
+                max_draw_count,
+                stride,
+            )?;
+        }
+
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.render_pass_state.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        Ok(self)
+    }
+
+    fn validate_draw_mesh_tasks_indirect_count(
+        &self,
+        indirect_buffer: &dyn BufferAccess,
+        count_buffer: &dyn BufferAccess,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> Result<(), PipelineExecutionError> {
+        // VUID-vkCmdDrawMeshTasksIndirectCountEXT-None-07074
+        if !self.device().enabled_features().mesh_shader {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::draw_mesh_tasks_indirect_count`",
+                requires_one_of: RequiresOneOf {
+                    features: &["mesh_shader"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdDrawMeshTasksIndirectCountEXT-None-04445
+        if !(self.device().enabled_features().draw_indirect_count
+            || self.device().enabled_extensions().khr_draw_indirect_count)
+        {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::draw_mesh_tasks_indirect_count`",
+                requires_one_of: RequiresOneOf {
+                    features: &["draw_indirect_count"],
+                    device_extensions: &["khr_draw_indirect_count"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdDrawMeshTasksIndirectCountEXT-renderpass
+        let render_pass_state = self
+            .render_pass_state
+            .as_ref()
+            .ok_or(PipelineExecutionError::ForbiddenOutsideRenderPass)?;
+
+        // VUID-vkCmdDrawMeshTasksIndirectCountEXT-None-02700
+        let pipeline = match self.state().pipeline_graphics() {
+            Some(x) => x.as_ref(),
+            None => return Err(PipelineExecutionError::PipelineNotBound),
+        };
+
+        self.validate_pipeline_graphics_mesh_shading(pipeline)?;
+        self.validate_pipeline_descriptor_sets(pipeline)?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(pipeline)?;
+        self.validate_pipeline_graphics_render_pass(pipeline, render_pass_state)?;
+
+        self.validate_indirect_buffer(indirect_buffer)?;
+        self.validate_indirect_count_buffer(indirect_buffer, count_buffer, max_draw_count, stride)?;
+
+        Ok(())
+    }
+
+    /// Perform a ray tracing operation using a ray tracing pipeline.
+    ///
+    /// The ray generation shader of `shader_binding_table` is invoked once for each element of
+    /// `dimensions`.
+    ///
+    /// A ray tracing pipeline must have been bound using
+    /// [`bind_pipeline_ray_tracing`](Self::bind_pipeline_ray_tracing), and `shader_binding_table`
+    /// must have been created for that pipeline. Any resources used by the ray tracing pipeline,
+    /// such as descriptor sets, must have been set beforehand.
+    pub fn trace_rays(
+        &mut self,
+        shader_binding_table: &ShaderBindingTable,
+        dimensions: [u32; 3],
+    ) -> Result<&mut Self, PipelineExecutionError> {
+        self.validate_trace_rays(shader_binding_table, dimensions)?;
+
+        unsafe {
+            self.inner.trace_rays(shader_binding_table, dimensions)?;
+        }
+
+        Ok(self)
+    }
+
+    fn validate_trace_rays(
+        &self,
+        shader_binding_table: &ShaderBindingTable,
+        dimensions: [u32; 3],
+    ) -> Result<(), PipelineExecutionError> {
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdTraceRaysKHR-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::COMPUTE)
+        {
+            return Err(PipelineExecutionError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdTraceRaysKHR-renderpass
+        if self.render_pass_state.is_some() {
+            return Err(PipelineExecutionError::ForbiddenInsideRenderPass);
+        }
+
+        // VUID-vkCmdTraceRaysKHR-None-02700
+        let pipeline = match self.state().pipeline_ray_tracing() {
+            Some(x) => x.as_ref(),
+            None => return Err(PipelineExecutionError::PipelineNotBound),
+        };
+
+        self.validate_pipeline_descriptor_sets(pipeline)?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+
+        // VUID-vkCmdTraceRaysKHR-commonparent
+        assert_eq!(self.device(), shader_binding_table.buffer().device());
+
+        // VUID-vkCmdTraceRaysKHR-pRaygenShaderBindingTable-03681
+        if !shader_binding_table.is_for_pipeline(pipeline) {
+            return Err(PipelineExecutionError::ShaderBindingTablePipelineMismatch);
+        }
+
+        let properties = self.device().physical_device().properties();
+        let max_group_count = properties.max_compute_work_group_count;
+        let max_group_size = properties.max_compute_work_group_size;
+
+        // VUID-vkCmdTraceRaysKHR-width-03638
+        // VUID-vkCmdTraceRaysKHR-height-03639
+        // VUID-vkCmdTraceRaysKHR-depth-03640
+        let max_dimensions = [
+            max_group_count[0].saturating_mul(max_group_size[0]),
+            max_group_count[1].saturating_mul(max_group_size[1]),
+            max_group_count[2].saturating_mul(max_group_size[2]),
+        ];
+
+        if dimensions[0] > max_dimensions[0]
+            || dimensions[1] > max_dimensions[1]
+            || dimensions[2] > max_dimensions[2]
+        {
+            return Err(PipelineExecutionError::MaxTraceRaysDimensionsExceeded {
+                requested: dimensions,
+                max: max_dimensions,
+            });
+        }
+
+        // VUID-vkCmdTraceRaysKHR-width-03641
+        let max_invocation_count = properties.max_ray_dispatch_invocation_count.unwrap_or(0);
+        let invocation_count = dimensions[0] as u64 * dimensions[1] as u64 * dimensions[2] as u64;
+
+        if invocation_count > max_invocation_count as u64 {
+            return Err(
+                PipelineExecutionError::MaxRayDispatchInvocationCountExceeded {
+                    requested: invocation_count,
+                    max: max_invocation_count,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    fn validate_multi_draw_count(&self, draw_count: usize) -> Result<(), PipelineExecutionError> {
+        let max = self
+            .device()
+            .physical_device()
+            .properties()
+            .max_multi_draw_count
+            .unwrap_or(0);
+
+        if draw_count as u64 >= max as u64 {
+            return Err(PipelineExecutionError::MaxMultiDrawCountExceeded {
+                provided: draw_count.try_into().unwrap_or(u32::MAX),
+                max,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn validate_index_buffer(
+        &self,
+        indices: Option<(u32, u32)>,
+    ) -> Result<(), PipelineExecutionError> {
+        let current_state = self.state();
+
+        // VUID?
+        let (index_buffer, index_type) = match current_state.index_buffer() {
+            Some(x) => x,
+            None => return Err(PipelineExecutionError::IndexBufferNotBound),
+        };
+
+        if let Some((first_index, index_count)) = indices {
+            let max_index_count = (index_buffer.size() / index_type.size()) as u32;
+
+            // // VUID-vkCmdDrawIndexed-firstIndex-04932
+            if first_index + index_count > max_index_count {
+                return Err(PipelineExecutionError::IndexBufferRangeOutOfBounds {
+                    highest_index: first_index + index_count,
+                    max_index_count,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_indirect_buffer(
+        &self,
+        buffer: &dyn BufferAccess,
+    ) -> Result<(), PipelineExecutionError> {
+        // VUID-vkCmdDispatchIndirect-commonparent
+        assert_eq!(self.device(), buffer.device());
+
+        // VUID-vkCmdDispatchIndirect-buffer-02709
+        if !buffer.usage().intersects(BufferUsage::INDIRECT_BUFFER) {
+            return Err(PipelineExecutionError::IndirectBufferMissingUsage);
+        }
+
+        // VUID-vkCmdDispatchIndirect-offset-02710
+        // TODO:
+
+        Ok(())
+    }
+
+    fn validate_indirect_count_buffer(
+        &self,
+        indirect_buffer: &dyn BufferAccess,
+        count_buffer: &dyn BufferAccess,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> Result<(), PipelineExecutionError> {
+        // VUID-vkCmdDrawIndirectCount-commonparent
+        assert_eq!(self.device(), count_buffer.device());
+
+        // VUID-vkCmdDrawIndirectCount-countBuffer-02714
+        if !count_buffer
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER)
+        {
+            return Err(PipelineExecutionError::IndirectCountBufferMissingUsage);
+        }
+
+        let offset = count_buffer.inner().offset;
+
+        // VUID-vkCmdDrawIndirectCount-countBufferOffset-02716
+        if offset % 4 != 0 {
+            return Err(PipelineExecutionError::IndirectCountBufferOffsetNotAligned { offset });
+        }
+
+        // VUID-vkCmdDrawIndirectCount-countBufferOffset-04129
+        // Ensured by the type of the count buffer.
+
+        // VUID-vkCmdDrawIndirectCount-maxDrawCount-03111
+        let commands_in_buffer = (indirect_buffer.size() / stride as DeviceSize) as u32;
+
+        if max_draw_count > commands_in_buffer {
+            return Err(PipelineExecutionError::IndirectBufferRangeOutOfBounds {
+                max_draw_count,
+                commands_in_buffer,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn validate_pipeline_descriptor_sets<Pl: Pipeline>(
+        &self,
+        pipeline: &Pl,
+    ) -> Result<(), PipelineExecutionError> {
+        fn validate_resources<T>(
+            set_num: u32,
+            binding_num: u32,
+            binding_reqs: &DescriptorBindingRequirements,
+            elements: &[Option<T>],
+            mut extra_check: impl FnMut(u32, &T) -> Result<(), DescriptorResourceInvalidError>,
+        ) -> Result<(), PipelineExecutionError> {
+            let elements_to_check = if let Some(descriptor_count) = binding_reqs.descriptor_count {
+                // The shader has a fixed-sized array, so it will never access more than
+                // the first `descriptor_count` elements.
+                elements.get(..descriptor_count as usize).ok_or({
+                    // There are less than `descriptor_count` elements in `elements`
+                    PipelineExecutionError::DescriptorResourceInvalid {
+                        set_num,
+                        binding_num,
+                        index: elements.len() as u32,
+                        error: DescriptorResourceInvalidError::Missing,
+                    }
+                })?
+            } else {
+                // The shader has a runtime-sized array, so any element could potentially
+                // be accessed. We must check them all.
+                elements
+            };
+
+            for (index, element) in elements_to_check.iter().enumerate() {
+                let index = index as u32;
+
+                // VUID-vkCmdDispatch-None-02699
+                let element = match element {
+                    Some(x) => x,
+                    None => {
+                        return Err(PipelineExecutionError::DescriptorResourceInvalid {
+                            set_num,
+                            binding_num,
+                            index,
+                            error: DescriptorResourceInvalidError::Missing,
+                        })
+                    }
+                };
+
+                if let Err(error) = extra_check(index, element) {
+                    return Err(PipelineExecutionError::DescriptorResourceInvalid {
+                        set_num,
+                        binding_num,
+                        index,
+                        error,
+                    });
+                }
+            }
+
+            Ok(())
+        }
+
+        if pipeline.num_used_descriptor_sets() == 0 {
+            return Ok(());
+        }
+
+        let current_state = self.state();
+
+        // VUID-vkCmdDispatch-None-02697
+        let bindings_pipeline_layout =
+            match current_state.descriptor_sets_pipeline_layout(pipeline.bind_point()) {
+                Some(x) => x,
+                None => return Err(PipelineExecutionError::PipelineLayoutNotCompatible),
+            };
+
+        // VUID-vkCmdDispatch-None-02697
+        if !pipeline.layout().is_compatible_with(
+            bindings_pipeline_layout,
+            pipeline.num_used_descriptor_sets(),
+        ) {
+            return Err(PipelineExecutionError::PipelineLayoutNotCompatible);
+        }
+
+        for (&(set_num, binding_num), binding_reqs) in pipeline.descriptor_binding_requirements() {
+            let layout_binding =
+                &pipeline.layout().set_layouts()[set_num as usize].bindings()[&binding_num];
+
+            let check_buffer =
+                |_index: u32, (_buffer, _range): &(Arc<dyn BufferAccess>, Range<DeviceSize>)| Ok(());
+
+            let check_buffer_view = |index: u32, buffer_view: &Arc<dyn BufferViewAbstract>| {
+                for desc_reqs in (binding_reqs.descriptors.get(&Some(index)).into_iter())
+                    .chain(binding_reqs.descriptors.get(&None))
+                {
+                    if layout_binding.descriptor_type == DescriptorType::StorageTexelBuffer {
+                        // VUID-vkCmdDispatch-OpTypeImage-06423
+                        if binding_reqs.image_format.is_none()
+                            && !desc_reqs.memory_write.is_empty()
+                            && !buffer_view
+                                .format_features()
+                                .intersects(FormatFeatures::STORAGE_WRITE_WITHOUT_FORMAT)
+                        {
+                            return Err(DescriptorResourceInvalidError::StorageWriteWithoutFormatNotSupported);
+                        }
+
+                        // VUID-vkCmdDispatch-OpTypeImage-06424
+                        if binding_reqs.image_format.is_none()
+                            && !desc_reqs.memory_read.is_empty()
+                            && !buffer_view
+                                .format_features()
+                                .intersects(FormatFeatures::STORAGE_READ_WITHOUT_FORMAT)
+                        {
+                            return Err(DescriptorResourceInvalidError::StorageReadWithoutFormatNotSupported);
+                        }
+                    }
+                }
+
+                Ok(())
+            };
+
+            let check_image_view_common = |index: u32, image_view: &Arc<dyn ImageViewAbstract>| {
+                for desc_reqs in (binding_reqs.descriptors.get(&Some(index)).into_iter())
+                    .chain(binding_reqs.descriptors.get(&None))
+                {
+                    // VUID-vkCmdDispatch-None-02691
+                    if desc_reqs.storage_image_atomic
+                        && !image_view
+                            .format_features()
+                            .intersects(FormatFeatures::STORAGE_IMAGE_ATOMIC)
+                    {
+                        return Err(DescriptorResourceInvalidError::StorageImageAtomicNotSupported);
+                    }
+
+                    if layout_binding.descriptor_type == DescriptorType::StorageImage {
+                        // VUID-vkCmdDispatch-OpTypeImage-06423
+                        if binding_reqs.image_format.is_none()
+                            && !desc_reqs.memory_write.is_empty()
+                            && !image_view
+                                .format_features()
+                                .intersects(FormatFeatures::STORAGE_WRITE_WITHOUT_FORMAT)
+                        {
+                            return Err(
+                            DescriptorResourceInvalidError::StorageWriteWithoutFormatNotSupported,
+                        );
+                        }
+
+                        // VUID-vkCmdDispatch-OpTypeImage-06424
+                        if binding_reqs.image_format.is_none()
+                            && !desc_reqs.memory_read.is_empty()
+                            && !image_view
+                                .format_features()
+                                .intersects(FormatFeatures::STORAGE_READ_WITHOUT_FORMAT)
+                        {
+                            return Err(
+                            DescriptorResourceInvalidError::StorageReadWithoutFormatNotSupported,
+                        );
+                        }
+                    }
+                }
+
+                /*
+                   Instruction/Sampler/Image View Validation
+                   https://registry.khronos.org/vulkan/specs/1.3-extensions/html/chap16.html#textures-input-validation
+                */
+
+                // The SPIR-V Image Format is not compatible with the image view’s format.
+                if let Some(format) = binding_reqs.image_format {
+                    if image_view.format() != Some(format) {
+                        return Err(DescriptorResourceInvalidError::ImageViewFormatMismatch {
+                            required: format,
+                            provided: image_view.format(),
+                        });
+                    }
+                }
+
+                // Rules for viewType
+                if let Some(image_view_type) = binding_reqs.image_view_type {
+                    if image_view.view_type() != image_view_type {
+                        return Err(DescriptorResourceInvalidError::ImageViewTypeMismatch {
+                            required: image_view_type,
+                            provided: image_view.view_type(),
+                        });
+                    }
+                }
+
+                // - If the image was created with VkImageCreateInfo::samples equal to
+                //   VK_SAMPLE_COUNT_1_BIT, the instruction must have MS = 0.
+                // - If the image was created with VkImageCreateInfo::samples not equal to
+                //   VK_SAMPLE_COUNT_1_BIT, the instruction must have MS = 1.
+                if binding_reqs.image_multisampled
+                    != (image_view.image().samples() != SampleCount::Sample1)
+                {
+                    return Err(
+                        DescriptorResourceInvalidError::ImageViewMultisampledMismatch {
+                            required: binding_reqs.image_multisampled,
+                            provided: image_view.image().samples() != SampleCount::Sample1,
+                        },
+                    );
+                }
+
+                // - If the Sampled Type of the OpTypeImage does not match the numeric format of the
+                //   image, as shown in the SPIR-V Sampled Type column of the
+                //   Interpretation of Numeric Format table.
+                // - If the signedness of any read or sample operation does not match the signedness of
+                //   the image’s format.
+                if let Some(scalar_type) = binding_reqs.image_scalar_type {
+                    let aspects = image_view.subresource_range().aspects;
+                    let view_scalar_type = ShaderScalarType::from(
+                        if aspects.intersects(
+                            ImageAspects::COLOR
+                                | ImageAspects::PLANE_0
+                                | ImageAspects::PLANE_1
+                                | ImageAspects::PLANE_2,
+                        ) {
+                            image_view.format().unwrap().type_color().unwrap()
+                        } else if aspects.intersects(ImageAspects::DEPTH) {
+                            image_view.format().unwrap().type_depth().unwrap()
+                        } else if aspects.intersects(ImageAspects::STENCIL) {
+                            image_view.format().unwrap().type_stencil().unwrap()
+                        } else {
+                            // Per `ImageViewBuilder::aspects` and
+                            // VUID-VkDescriptorImageInfo-imageView-01976
+                            unreachable!()
+                        },
+                    );
+
+                    if scalar_type != view_scalar_type {
+                        return Err(
+                            DescriptorResourceInvalidError::ImageViewScalarTypeMismatch {
+                                required: scalar_type,
+                                provided: view_scalar_type,
+                            },
+                        );
+                    }
+                }
+
+                Ok(())
+            };
+
+            let check_sampler_common = |index: u32, sampler: &Arc<Sampler>| {
+                for desc_reqs in (binding_reqs.descriptors.get(&Some(index)).into_iter())
+                    .chain(binding_reqs.descriptors.get(&None))
+                {
+                    // VUID-vkCmdDispatch-None-02703
+                    // VUID-vkCmdDispatch-None-02704
+                    if desc_reqs.sampler_no_unnormalized_coordinates
+                        && sampler.unnormalized_coordinates()
+                    {
+                        return Err(
+                        DescriptorResourceInvalidError::SamplerUnnormalizedCoordinatesNotAllowed,
+                    );
+                    }
+
+                    // - OpImageFetch, OpImageSparseFetch, OpImage*Gather, and OpImageSparse*Gather must not
+                    //   be used with a sampler that enables sampler Y′CBCR conversion.
+                    // - The ConstOffset and Offset operands must not be used with a sampler that enables
+                    //   sampler Y′CBCR conversion.
+                    if desc_reqs.sampler_no_ycbcr_conversion
+                        && sampler.sampler_ycbcr_conversion().is_some()
+                    {
+                        return Err(
+                            DescriptorResourceInvalidError::SamplerYcbcrConversionNotAllowed,
+                        );
+                    }
+
+                    /*
+                        Instruction/Sampler/Image View Validation
+                        https://registry.khronos.org/vulkan/specs/1.3-extensions/html/chap16.html#textures-input-validation
+                    */
+
+                    // - The SPIR-V instruction is one of the OpImage*Dref* instructions and the sampler
+                    //   compareEnable is VK_FALSE
+                    // - The SPIR-V instruction is not one of the OpImage*Dref* instructions and the sampler
+                    //   compareEnable is VK_TRUE
+                    if desc_reqs.sampler_compare != sampler.compare().is_some() {
+                        return Err(DescriptorResourceInvalidError::SamplerCompareMismatch {
+                            required: desc_reqs.sampler_compare,
+                            provided: sampler.compare().is_some(),
+                        });
+                    }
+                }
+
+                Ok(())
+            };
+
+            let check_image_view = |index: u32, image_view: &Arc<dyn ImageViewAbstract>| {
+                check_image_view_common(index, image_view)?;
+
+                if let Some(sampler) = layout_binding.immutable_samplers.get(index as usize) {
+                    check_sampler_common(index, sampler)?;
+                }
+
+                Ok(())
+            };
+
+            let check_image_view_sampler =
+                |index: u32, (image_view, sampler): &(Arc<dyn ImageViewAbstract>, Arc<Sampler>)| {
+                    check_image_view_common(index, image_view)?;
+                    check_sampler_common(index, sampler)?;
+
+                    Ok(())
+                };
+
+            let check_sampler = |index: u32, sampler: &Arc<Sampler>| {
+                check_sampler_common(index, sampler)?;
+
+                for desc_reqs in (binding_reqs.descriptors.get(&Some(index)).into_iter())
+                    .chain(binding_reqs.descriptors.get(&None))
+                {
+                    // Check sampler-image compatibility. Only done for separate samplers;
+                    // combined image samplers are checked when updating the descriptor set.
+
+                    // If the image view isn't actually present in the resources, then just skip it.
+                    // It will be caught later by check_resources.
+                    let iter = desc_reqs.sampler_with_images.iter().filter_map(|id| {
+                        current_state
+                            .descriptor_set(pipeline.bind_point(), id.set)
+                            .and_then(|set| set.resources().binding(id.binding))
+                            .and_then(|res| match res {
+                                DescriptorBindingResources::ImageView(elements) => elements
+                                    .get(id.index as usize)
+                                    .and_then(|opt| opt.as_ref().map(|opt| (id, opt))),
+                                _ => None,
+                            })
+                    });
+
+                    for (id, image_view) in iter {
+                        if let Err(error) = sampler.check_can_sample(image_view.as_ref()) {
+                            return Err(
+                                DescriptorResourceInvalidError::SamplerImageViewIncompatible {
+                                    image_view_set_num: id.set,
+                                    image_view_binding_num: id.binding,
+                                    image_view_index: id.index,
+                                    error,
+                                },
+                            );
+                        }
+                    }
+                }
+
+                Ok(())
+            };
+
+            let check_none = |index: u32, _: &()| {
+                if let Some(sampler) = layout_binding.immutable_samplers.get(index as usize) {
+                    check_sampler(index, sampler)?;
+                }
+
+                Ok(())
+            };
+
+            let set_resources = match current_state.descriptor_set(pipeline.bind_point(), set_num) {
+                Some(x) => x.resources(),
+                None => return Err(PipelineExecutionError::DescriptorSetNotBound { set_num }),
+            };
+
+            let binding_resources = set_resources.binding(binding_num).unwrap();
+
+            match binding_resources {
+                DescriptorBindingResources::None(elements) => {
+                    validate_resources(set_num, binding_num, binding_reqs, elements, check_none)?;
+                }
+                DescriptorBindingResources::Buffer(elements) => {
+                    validate_resources(set_num, binding_num, binding_reqs, elements, check_buffer)?;
+                }
+                DescriptorBindingResources::BufferView(elements) => {
+                    validate_resources(
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        elements,
+                        check_buffer_view,
+                    )?;
+                }
+                DescriptorBindingResources::ImageView(elements) => {
+                    validate_resources(
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        elements,
+                        check_image_view,
+                    )?;
+                }
+                DescriptorBindingResources::ImageViewSampler(elements) => {
+                    validate_resources(
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        elements,
+                        check_image_view_sampler,
+                    )?;
+                }
+                DescriptorBindingResources::Sampler(elements) => {
+                    validate_resources(
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        elements,
+                        check_sampler,
+                    )?;
+                }
+                DescriptorBindingResources::AccelerationStructure(elements) => {
+                    validate_resources(
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        elements,
+                        |_, _| Ok(()),
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_pipeline_push_constants(
+        &self,
+        pipeline_layout: &PipelineLayout,
+    ) -> Result<(), PipelineExecutionError> {
+        if pipeline_layout.push_constant_ranges().is_empty()
+            || self.device().enabled_features().maintenance4
+        {
+            return Ok(());
+        }
+
+        let current_state = self.state();
+
+        // VUID-vkCmdDispatch-maintenance4-06425
+        let constants_pipeline_layout = match current_state.push_constants_pipeline_layout() {
+            Some(x) => x,
+            None => return Err(PipelineExecutionError::PushConstantsMissing),
+        };
+
+        // VUID-vkCmdDispatch-maintenance4-06425
+        if pipeline_layout.handle() != constants_pipeline_layout.handle()
+            && pipeline_layout.push_constant_ranges()
+                != constants_pipeline_layout.push_constant_ranges()
+        {
+            return Err(PipelineExecutionError::PushConstantsNotCompatible);
+        }
+
+        let set_bytes = current_state.push_constants();
+
+        // VUID-vkCmdDispatch-maintenance4-06425
+        if !pipeline_layout
+            .push_constant_ranges()
+            .iter()
+            .all(|pc_range| set_bytes.contains(pc_range.offset..pc_range.offset + pc_range.size))
+        {
+            return Err(PipelineExecutionError::PushConstantsMissing);
+        }
+
+        Ok(())
+    }
+
+    fn validate_pipeline_graphics_dynamic_state(
+        &self,
+        pipeline: &GraphicsPipeline,
+    ) -> Result<(), PipelineExecutionError> {
+        let device = pipeline.device();
+        let current_state = self.state();
+
+        // VUID-vkCmdDraw-commandBuffer-02701
+        for dynamic_state in pipeline
+            .dynamic_states()
+            .filter(|(_, d)| *d)
+            .map(|(s, _)| s)
+        {
             match dynamic_state {
                 DynamicState::BlendConstants => {
                     // VUID?
@@ -1210,502 +2267,912 @@ where
                         }
                     }
                 }
-                DynamicState::PrimitiveTopology => {
-                    // VUID-vkCmdDraw-primitiveTopology-03420
-                    let topology = if let Some(topology) = current_state.primitive_topology() {
-                        topology
+                DynamicState::PrimitiveTopology => {
+                    // VUID-vkCmdDraw-primitiveTopology-03420
+                    let topology = if let Some(topology) = current_state.primitive_topology() {
+                        topology
+                    } else {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    };
+
+                    if pipeline.shader(ShaderStage::TessellationControl).is_some() {
+                        // VUID?
+                        if !matches!(topology, PrimitiveTopology::PatchList) {
+                            return Err(PipelineExecutionError::DynamicPrimitiveTopologyInvalid {
+                                topology,
+                            });
+                        }
+                    } else {
+                        // VUID?
+                        if matches!(topology, PrimitiveTopology::PatchList) {
+                            return Err(PipelineExecutionError::DynamicPrimitiveTopologyInvalid {
+                                topology,
+                            });
+                        }
+                    }
+
+                    let required_topology_class = match pipeline.input_assembly_state().topology {
+                        PartialStateMode::Dynamic(topology_class) => topology_class,
+                        _ => unreachable!(),
+                    };
+
+                    // VUID-vkCmdDraw-primitiveTopology-03420
+                    if topology.class() != required_topology_class {
+                        return Err(
+                            PipelineExecutionError::DynamicPrimitiveTopologyClassMismatch {
+                                provided_class: topology.class(),
+                                required_class: required_topology_class,
+                            },
+                        );
+                    }
+
+                    // TODO: check that the topology matches the geometry shader
+                }
+                DynamicState::RasterizerDiscardEnable => {
+                    // VUID-vkCmdDraw-None-04876
+                    if current_state.rasterizer_discard_enable().is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::RayTracingPipelineStackSize => unreachable!(
+                    "RayTracingPipelineStackSize dynamic state should not occur on a graphics pipeline"
+                ),
+                DynamicState::SampleLocations => todo!(),
+                DynamicState::Scissor => {
+                    for num in 0..pipeline.viewport_state().unwrap().count().unwrap() {
+                        // VUID?
+                        if current_state.scissor(num).is_none() {
+                            return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                        }
+                    }
+                }
+                DynamicState::ScissorWithCount => {
+                    // VUID-vkCmdDraw-scissorCount-03418
+                    // VUID-vkCmdDraw-viewportCount-03419
+                    let scissor_count = if let Some(scissors) = current_state.scissor_with_count() {
+                        scissors.len() as u32
+                    } else {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    };
+
+                    // Check if the counts match, but only if the viewport count is fixed.
+                    // If the viewport count is also dynamic, then the
+                    // DynamicState::ViewportWithCount match arm will handle it.
+                    if let Some(viewport_count) = pipeline.viewport_state().unwrap().count() {
+                        // VUID-vkCmdDraw-scissorCount-03418
+                        if viewport_count != scissor_count {
+                            return Err(
+                                PipelineExecutionError::DynamicViewportScissorCountMismatch {
+                                    viewport_count,
+                                    scissor_count,
+                                },
+                            );
+                        }
+                    }
+                }
+                DynamicState::StencilCompareMask => {
+                    let state = current_state.stencil_compare_mask();
+
+                    // VUID?
+                    if state.front.is_none() || state.back.is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::StencilOp => {
+                    let state = current_state.stencil_op();
+
+                    // VUID?
+                    if state.front.is_none() || state.back.is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::StencilReference => {
+                    let state = current_state.stencil_reference();
+
+                    // VUID?
+                    if state.front.is_none() || state.back.is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::StencilTestEnable => {
+                    // VUID?
+                    if current_state.stencil_test_enable().is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+
+                    // TODO: Check if the stencil buffer is writable
+                }
+                DynamicState::StencilWriteMask => {
+                    let state = current_state.stencil_write_mask();
+
+                    // VUID?
+                    if state.front.is_none() || state.back.is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::VertexInput => {
+                    // VUID-vkCmdDraw-None-04914
+                    let vertex_input_state = if let Some(state) = current_state.vertex_input() {
+                        state
                     } else {
                         return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
                     };
 
-                    if pipeline.shader(ShaderStage::TessellationControl).is_some() {
+                    // VUID-vkCmdDraw-None-04914
+                    // VUID-vkCmdDraw-Input-08734
+                    if let Some(interface) = pipeline.vertex_shader_input_interface() {
+                        validate_dynamic_vertex_input(interface, vertex_input_state)?;
+                    }
+                }
+                DynamicState::VertexInputBindingStride => todo!(),
+                DynamicState::Viewport => {
+                    for num in 0..pipeline.viewport_state().unwrap().count().unwrap() {
                         // VUID?
-                        if !matches!(topology, PrimitiveTopology::PatchList) {
-                            return Err(PipelineExecutionError::DynamicPrimitiveTopologyInvalid {
-                                topology,
-                            });
+                        if current_state.viewport(num).is_none() {
+                            return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
                         }
+                    }
+                }
+                DynamicState::ViewportCoarseSampleOrder => todo!(),
+                DynamicState::ViewportShadingRatePalette => todo!(),
+                DynamicState::ViewportWithCount => {
+                    // VUID-vkCmdDraw-viewportCount-03417
+                    let viewport_count = if let Some(viewports) = current_state.viewport_with_count() {
+                        viewports.len() as u32
                     } else {
-                        // VUID?
-                        if matches!(topology, PrimitiveTopology::PatchList) {
-                            return Err(PipelineExecutionError::DynamicPrimitiveTopologyInvalid {
-                                topology,
-                            });
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    };
+
+                    let scissor_count = if let Some(scissor_count) =
+                        pipeline.viewport_state().unwrap().count()
+                    {
+                        // The scissor count is fixed.
+                        scissor_count
+                    } else {
+                        // VUID-vkCmdDraw-viewportCount-03419
+                        // The scissor count is also dynamic.
+                        if let Some(scissors) = current_state.scissor_with_count() {
+                            scissors.len() as u32
+                        } else {
+                            return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
                         }
+                    };
+
+                    // VUID-vkCmdDraw-viewportCount-03417
+                    // VUID-vkCmdDraw-viewportCount-03419
+                    if viewport_count != scissor_count {
+                        return Err(
+                            PipelineExecutionError::DynamicViewportScissorCountMismatch {
+                                viewport_count,
+                                scissor_count,
+                            },
+                        );
                     }
 
-                    let required_topology_class = match pipeline.input_assembly_state().topology {
-                        PartialStateMode::Dynamic(topology_class) => topology_class,
-                        _ => unreachable!(),
-                    };
+                    // TODO: VUID-vkCmdDrawIndexed-primitiveFragmentShadingRateWithMultipleViewports-04552
+                    // If the primitiveFragmentShadingRateWithMultipleViewports limit is not supported,
+                    // the bound graphics pipeline was created with the
+                    // VK_DYNAMIC_STATE_VIEWPORT_WITH_COUNT_EXT dynamic state enabled, and any of the
+                    // shader stages of the bound graphics pipeline write to the PrimitiveShadingRateKHR
+                    // built-in, then vkCmdSetViewportWithCountEXT must have been called in the current
+                    // command buffer prior to this drawing command, and the viewportCount parameter of
+                    // vkCmdSetViewportWithCountEXT must be 1
+                }
+                DynamicState::ViewportWScaling => todo!(),
+                DynamicState::TessellationDomainOrigin => todo!(),
+                DynamicState::DepthClampEnable => todo!(),
+                DynamicState::PolygonMode => todo!(),
+                DynamicState::RasterizationSamples => todo!(),
+                DynamicState::SampleMask => todo!(),
+                DynamicState::AlphaToCoverageEnable => todo!(),
+                DynamicState::AlphaToOneEnable => todo!(),
+                DynamicState::LogicOpEnable => todo!(),
+                DynamicState::ColorBlendEnable => todo!(),
+                DynamicState::ColorBlendEquation => todo!(),
+                DynamicState::ColorWriteMask => todo!(),
+                DynamicState::RasterizationStream => todo!(),
+                DynamicState::ConservativeRasterizationMode => todo!(),
+                DynamicState::ExtraPrimitiveOverestimationSize => todo!(),
+                DynamicState::DepthClipEnable => todo!(),
+                DynamicState::SampleLocationsEnable => todo!(),
+                DynamicState::ColorBlendAdvanced => todo!(),
+                DynamicState::ProvokingVertexMode => todo!(),
+                DynamicState::LineRasterizationMode => todo!(),
+                DynamicState::LineStippleEnable => todo!(),
+                DynamicState::DepthClipNegativeOneToOne => todo!(),
+                DynamicState::ViewportWScalingEnable => todo!(),
+                DynamicState::ViewportSwizzle => todo!(),
+                DynamicState::CoverageToColorEnable => todo!(),
+                DynamicState::CoverageToColorLocation => todo!(),
+                DynamicState::CoverageModulationMode => todo!(),
+                DynamicState::CoverageModulationTableEnable => todo!(),
+                DynamicState::CoverageModulationTable => todo!(),
+                DynamicState::ShadingRateImageEnable => todo!(),
+                DynamicState::RepresentativeFragmentTestEnable => todo!(),
+                DynamicState::CoverageReductionMode => todo!(),
+            }
+        }
 
-                    // VUID-vkCmdDraw-primitiveTopology-03420
-                    if topology.class() != required_topology_class {
+        Ok(())
+    }
+
+    fn validate_pipeline_graphics_render_pass(
+        &self,
+        pipeline: &GraphicsPipeline,
+        render_pass_state: &RenderPassState,
+    ) -> Result<(), PipelineExecutionError> {
+        // VUID?
+        if render_pass_state.contents != SubpassContents::Inline {
+            return Err(PipelineExecutionError::ForbiddenWithSubpassContents {
+                subpass_contents: render_pass_state.contents,
+            });
+        }
+
+        match (&render_pass_state.render_pass, pipeline.render_pass()) {
+            (
+                RenderPassStateType::BeginRenderPass(state),
+                PipelineRenderPassType::BeginRenderPass(pipeline_subpass),
+            ) => {
+                // VUID-vkCmdDraw-renderPass-02684
+                if !pipeline_subpass
+                    .render_pass()
+                    .is_compatible_with(state.subpass.render_pass())
+                {
+                    return Err(PipelineExecutionError::PipelineRenderPassNotCompatible);
+                }
+
+                // VUID-vkCmdDraw-subpass-02685
+                if pipeline_subpass.index() != state.subpass.index() {
+                    return Err(PipelineExecutionError::PipelineSubpassMismatch {
+                        pipeline: pipeline_subpass.index(),
+                        current: state.subpass.index(),
+                    });
+                }
+            }
+            (
+                RenderPassStateType::BeginRendering(current_rendering_info),
+                PipelineRenderPassType::BeginRendering(pipeline_rendering_info),
+            ) => {
+                // VUID-vkCmdDraw-viewMask-06178
+                if pipeline_rendering_info.view_mask != render_pass_state.view_mask {
+                    return Err(PipelineExecutionError::PipelineViewMaskMismatch {
+                        pipeline_view_mask: pipeline_rendering_info.view_mask,
+                        required_view_mask: render_pass_state.view_mask,
+                    });
+                }
+
+                // VUID-vkCmdDraw-colorAttachmentCount-06179
+                if pipeline_rendering_info.color_attachment_formats.len()
+                    != current_rendering_info.color_attachment_formats.len()
+                {
+                    return Err(
+                        PipelineExecutionError::PipelineColorAttachmentCountMismatch {
+                            pipeline_count: pipeline_rendering_info.color_attachment_formats.len()
+                                as u32,
+                            required_count: current_rendering_info.color_attachment_formats.len()
+                                as u32,
+                        },
+                    );
+                }
+
+                for (color_attachment_index, required_format, pipeline_format) in
+                    current_rendering_info
+                        .color_attachment_formats
+                        .iter()
+                        .zip(
+                            pipeline_rendering_info
+                                .color_attachment_formats
+                                .iter()
+                                .copied(),
+                        )
+                        .enumerate()
+                        .filter_map(|(i, (r, p))| r.map(|r| (i as u32, r, p)))
+                {
+                    // VUID-vkCmdDraw-colorAttachmentCount-06180
+                    if Some(required_format) != pipeline_format {
                         return Err(
-                            PipelineExecutionError::DynamicPrimitiveTopologyClassMismatch {
-                                provided_class: topology.class(),
-                                required_class: required_topology_class,
+                            PipelineExecutionError::PipelineColorAttachmentFormatMismatch {
+                                color_attachment_index,
+                                pipeline_format,
+                                required_format,
+                            },
+                        );
+                    }
+                }
+
+                if let Some((required_format, pipeline_format)) = current_rendering_info
+                    .depth_attachment_format
+                    .map(|r| (r, pipeline_rendering_info.depth_attachment_format))
+                {
+                    // VUID-vkCmdDraw-pDepthAttachment-06181
+                    if Some(required_format) != pipeline_format {
+                        return Err(
+                            PipelineExecutionError::PipelineDepthAttachmentFormatMismatch {
+                                pipeline_format,
+                                required_format,
                             },
                         );
                     }
-
-                    // TODO: check that the topology matches the geometry shader
                 }
-                DynamicState::RasterizerDiscardEnable => {
-                    // VUID-vkCmdDraw-None-04876
-                    if current_state.rasterizer_discard_enable().is_none() {
-                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+
+                if let Some((required_format, pipeline_format)) = current_rendering_info
+                    .stencil_attachment_format
+                    .map(|r| (r, pipeline_rendering_info.stencil_attachment_format))
+                {
+                    // VUID-vkCmdDraw-pStencilAttachment-06182
+                    if Some(required_format) != pipeline_format {
+                        return Err(
+                            PipelineExecutionError::PipelineStencilAttachmentFormatMismatch {
+                                pipeline_format,
+                                required_format,
+                            },
+                        );
                     }
                 }
-                DynamicState::RayTracingPipelineStackSize => unreachable!(
-                    "RayTracingPipelineStackSize dynamic state should not occur on a graphics pipeline"
-                ),
-                DynamicState::SampleLocations => todo!(),
-                DynamicState::Scissor => {
-                    for num in 0..pipeline.viewport_state().unwrap().count().unwrap() {
-                        // VUID?
-                        if current_state.scissor(num).is_none() {
-                            return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+
+                // VUID-vkCmdDraw-imageView-06172
+                // VUID-vkCmdDraw-imageView-06173
+                // VUID-vkCmdDraw-imageView-06174
+                // VUID-vkCmdDraw-imageView-06175
+                // VUID-vkCmdDraw-imageView-06176
+                // VUID-vkCmdDraw-imageView-06177
+                // TODO:
+            }
+            _ => return Err(PipelineExecutionError::PipelineRenderPassTypeMismatch),
+        }
+
+        // VUID-vkCmdDraw-None-02686
+        // TODO:
+
+        Ok(())
+    }
+
+    fn validate_pipeline_graphics_mesh_shading(
+        &self,
+        pipeline: &GraphicsPipeline,
+    ) -> Result<(), PipelineExecutionError> {
+        // VUID-vkCmdDrawMeshTasksEXT-stage-06480
+        if pipeline.shader(ShaderStage::Mesh).is_none() {
+            return Err(PipelineExecutionError::MeshShaderPipelineNotBound);
+        }
+
+        Ok(())
+    }
+
+    fn validate_pipeline_graphics_vertex_buffers(
+        &self,
+        pipeline: &GraphicsPipeline,
+        vertices: Option<(u32, u32)>,
+        instances: Option<(u32, u32)>,
+    ) -> Result<(), PipelineExecutionError> {
+        let current_state = self.state();
+        // If the vertex input state is dynamic, the state that was set is used instead of the
+        // (empty) state of the pipeline.
+        let vertex_input = if pipeline.dynamic_state(DynamicState::VertexInput) == Some(true) {
+            current_state.vertex_input().unwrap()
+        } else {
+            pipeline.vertex_input_state()
+        };
+        let mut vertices_in_buffers: Option<u64> = None;
+        let mut instances_in_buffers: Option<u64> = None;
+
+        for (&binding_num, binding_desc) in &vertex_input.bindings {
+            // VUID-vkCmdDraw-None-04007
+            let vertex_buffer = match current_state.vertex_buffer(binding_num) {
+                Some(x) => x,
+                None => return Err(PipelineExecutionError::VertexBufferNotBound { binding_num }),
+            };
+
+            let mut num_elements = vertex_buffer.size() / binding_desc.stride as u64;
+
+            match binding_desc.input_rate {
+                VertexInputRate::Vertex => {
+                    vertices_in_buffers = Some(if let Some(x) = vertices_in_buffers {
+                        min(x, num_elements)
+                    } else {
+                        num_elements
+                    });
+                }
+                VertexInputRate::Instance { divisor } => {
+                    if divisor == 0 {
+                        // A divisor of 0 means the same instance data is used for all instances,
+                        // so we can draw any number of instances from a single element.
+                        // The buffer must contain at least one element though.
+                        if num_elements != 0 {
+                            num_elements = u64::MAX;
                         }
+                    } else {
+                        // If divisor is e.g. 2, we use only half the amount of data from the source
+                        // buffer, so the number of instances that can be drawn is twice as large.
+                        num_elements = num_elements.saturating_mul(divisor as u64);
                     }
-                }
-                DynamicState::ScissorWithCount => {
-                    // VUID-vkCmdDraw-scissorCount-03418
-                    // VUID-vkCmdDraw-viewportCount-03419
-                    let scissor_count = if let Some(scissors) = current_state.scissor_with_count() {
-                        scissors.len() as u32
+
+                    instances_in_buffers = Some(if let Some(x) = instances_in_buffers {
+                        min(x, num_elements)
                     } else {
-                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
-                    };
+                        num_elements
+                    });
+                }
+            };
+        }
 
-                    // Check if the counts match, but only if the viewport count is fixed.
-                    // If the viewport count is also dynamic, then the
-                    // DynamicState::ViewportWithCount match arm will handle it.
-                    if let Some(viewport_count) = pipeline.viewport_state().unwrap().count() {
-                        // VUID-vkCmdDraw-scissorCount-03418
-                        if viewport_count != scissor_count {
-                            return Err(
-                                PipelineExecutionError::DynamicViewportScissorCountMismatch {
-                                    viewport_count,
-                                    scissor_count,
-                                },
-                            );
-                        }
-                    }
+        if let Some((first_vertex, vertex_count)) = vertices {
+            let vertices_needed = first_vertex as u64 + vertex_count as u64;
+
+            if let Some(vertices_in_buffers) = vertices_in_buffers {
+                // VUID-vkCmdDraw-None-02721
+                if vertices_needed > vertices_in_buffers {
+                    return Err(PipelineExecutionError::VertexBufferVertexRangeOutOfBounds {
+                        vertices_needed,
+                        vertices_in_buffers,
+                    });
                 }
-                DynamicState::StencilCompareMask => {
-                    let state = current_state.stencil_compare_mask();
+            }
+        }
 
-                    // VUID?
-                    if state.front.is_none() || state.back.is_none() {
-                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
-                    }
+        if let Some((first_instance, instance_count)) = instances {
+            let instances_needed = first_instance as u64 + instance_count as u64;
+
+            if let Some(instances_in_buffers) = instances_in_buffers {
+                // VUID-vkCmdDraw-None-02721
+                if instances_needed > instances_in_buffers {
+                    return Err(
+                        PipelineExecutionError::VertexBufferInstanceRangeOutOfBounds {
+                            instances_needed,
+                            instances_in_buffers,
+                        },
+                    );
                 }
-                DynamicState::StencilOp => {
-                    let state = current_state.stencil_op();
+            }
 
-                    // VUID?
-                    if state.front.is_none() || state.back.is_none() {
-                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
-                    }
+            let view_mask = match pipeline.render_pass() {
+                PipelineRenderPassType::BeginRenderPass(subpass) => {
+                    subpass.render_pass().views_used()
                 }
-                DynamicState::StencilReference => {
-                    let state = current_state.stencil_reference();
+                PipelineRenderPassType::BeginRendering(rendering_info) => rendering_info.view_mask,
+            };
 
-                    // VUID?
-                    if state.front.is_none() || state.back.is_none() {
-                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
-                    }
+            if view_mask != 0 {
+                let max = pipeline
+                    .device()
+                    .physical_device()
+                    .properties()
+                    .max_multiview_instance_index
+                    .unwrap_or(0);
+
+                let highest_instance = instances_needed.saturating_sub(1);
+
+                // VUID-vkCmdDraw-maxMultiviewInstanceIndex-02688
+                if highest_instance > max as u64 {
+                    return Err(PipelineExecutionError::MaxMultiviewInstanceIndexExceeded {
+                        highest_instance,
+                        max,
+                    });
                 }
-                DynamicState::StencilTestEnable => {
-                    // VUID?
-                    if current_state.stencil_test_enable().is_none() {
-                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
-                    }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl SyncCommandBufferBuilder {
+    /// Calls `vkCmdDispatch` on the builder.
+    #[inline]
+    pub unsafe fn dispatch(
+        &mut self,
+        group_counts: [u32; 3],
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            group_counts: [u32; 3],
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "dispatch"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.dispatch(self.group_counts);
+            }
+        }
+
+        let command_index = self.commands.len();
+        let command_name = "dispatch";
+        let pipeline = self
+            .current_state
+            .pipeline_compute
+            .as_ref()
+            .unwrap()
+            .as_ref();
+
+        let mut resources = Vec::new();
+        self.add_descriptor_sets(&mut resources, command_index, command_name, pipeline);
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd { group_counts }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdDispatchIndirect` on the builder.
+    #[inline]
+    pub unsafe fn dispatch_indirect(
+        &mut self,
+        indirect_buffer: Arc<dyn BufferAccess>,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            indirect_buffer: Arc<dyn BufferAccess>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "dispatch_indirect"
+            }
 
-                    // TODO: Check if the stencil buffer is writable
-                }
-                DynamicState::StencilWriteMask => {
-                    let state = current_state.stencil_write_mask();
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.dispatch_indirect(self.indirect_buffer.as_ref());
+            }
+        }
 
-                    // VUID?
-                    if state.front.is_none() || state.back.is_none() {
-                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
-                    }
-                }
-                DynamicState::VertexInput => todo!(),
-                DynamicState::VertexInputBindingStride => todo!(),
-                DynamicState::Viewport => {
-                    for num in 0..pipeline.viewport_state().unwrap().count().unwrap() {
-                        // VUID?
-                        if current_state.viewport(num).is_none() {
-                            return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
-                        }
-                    }
-                }
-                DynamicState::ViewportCoarseSampleOrder => todo!(),
-                DynamicState::ViewportShadingRatePalette => todo!(),
-                DynamicState::ViewportWithCount => {
-                    // VUID-vkCmdDraw-viewportCount-03417
-                    let viewport_count = if let Some(viewports) = current_state.viewport_with_count() {
-                        viewports.len() as u32
-                    } else {
-                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
-                    };
+        let command_index = self.commands.len();
+        let command_name = "dispatch_indirect";
+        let pipeline = self
+            .current_state
+            .pipeline_compute
+            .as_ref()
+            .unwrap()
+            .as_ref();
 
-                    let scissor_count = if let Some(scissor_count) =
-                        pipeline.viewport_state().unwrap().count()
-                    {
-                        // The scissor count is fixed.
-                        scissor_count
-                    } else {
-                        // VUID-vkCmdDraw-viewportCount-03419
-                        // The scissor count is also dynamic.
-                        if let Some(scissors) = current_state.scissor_with_count() {
-                            scissors.len() as u32
-                        } else {
-                            return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
-                        }
-                    };
+        let mut resources = Vec::new();
+        self.add_descriptor_sets(&mut resources, command_index, command_name, pipeline);
+        self.add_indirect_buffer(
+            &mut resources,
+            command_index,
+            command_name,
+            &indirect_buffer,
+        );
 
-                    // VUID-vkCmdDraw-viewportCount-03417
-                    // VUID-vkCmdDraw-viewportCount-03419
-                    if viewport_count != scissor_count {
-                        return Err(
-                            PipelineExecutionError::DynamicViewportScissorCountMismatch {
-                                viewport_count,
-                                scissor_count,
-                            },
-                        );
-                    }
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
 
-                    // TODO: VUID-vkCmdDrawIndexed-primitiveFragmentShadingRateWithMultipleViewports-04552
-                    // If the primitiveFragmentShadingRateWithMultipleViewports limit is not supported,
-                    // the bound graphics pipeline was created with the
-                    // VK_DYNAMIC_STATE_VIEWPORT_WITH_COUNT_EXT dynamic state enabled, and any of the
-                    // shader stages of the bound graphics pipeline write to the PrimitiveShadingRateKHR
-                    // built-in, then vkCmdSetViewportWithCountEXT must have been called in the current
-                    // command buffer prior to this drawing command, and the viewportCount parameter of
-                    // vkCmdSetViewportWithCountEXT must be 1
-                }
-                DynamicState::ViewportWScaling => todo!(),
-                DynamicState::TessellationDomainOrigin => todo!(),
-                DynamicState::DepthClampEnable => todo!(),
-                DynamicState::PolygonMode => todo!(),
-                DynamicState::RasterizationSamples => todo!(),
-                DynamicState::SampleMask => todo!(),
-                DynamicState::AlphaToCoverageEnable => todo!(),
-                DynamicState::AlphaToOneEnable => todo!(),
-                DynamicState::LogicOpEnable => todo!(),
-                DynamicState::ColorBlendEnable => todo!(),
-                DynamicState::ColorBlendEquation => todo!(),
-                DynamicState::ColorWriteMask => todo!(),
-                DynamicState::RasterizationStream => todo!(),
-                DynamicState::ConservativeRasterizationMode => todo!(),
-                DynamicState::ExtraPrimitiveOverestimationSize => todo!(),
-                DynamicState::DepthClipEnable => todo!(),
-                DynamicState::SampleLocationsEnable => todo!(),
-                DynamicState::ColorBlendAdvanced => todo!(),
-                DynamicState::ProvokingVertexMode => todo!(),
-                DynamicState::LineRasterizationMode => todo!(),
-                DynamicState::LineStippleEnable => todo!(),
-                DynamicState::DepthClipNegativeOneToOne => todo!(),
-                DynamicState::ViewportWScalingEnable => todo!(),
-                DynamicState::ViewportSwizzle => todo!(),
-                DynamicState::CoverageToColorEnable => todo!(),
-                DynamicState::CoverageToColorLocation => todo!(),
-                DynamicState::CoverageModulationMode => todo!(),
-                DynamicState::CoverageModulationTableEnable => todo!(),
-                DynamicState::CoverageModulationTable => todo!(),
-                DynamicState::ShadingRateImageEnable => todo!(),
-                DynamicState::RepresentativeFragmentTestEnable => todo!(),
-                DynamicState::CoverageReductionMode => todo!(),
-            }
+        self.commands.push(Box::new(Cmd { indirect_buffer }));
+
+        for resource in resources {
+            self.add_resource(resource);
         }
 
         Ok(())
     }
 
-    fn validate_pipeline_graphics_render_pass(
-        &self,
-        pipeline: &GraphicsPipeline,
-        render_pass_state: &RenderPassState,
-    ) -> Result<(), PipelineExecutionError> {
-        // VUID?
-        if render_pass_state.contents != SubpassContents::Inline {
-            return Err(PipelineExecutionError::ForbiddenWithSubpassContents {
-                subpass_contents: render_pass_state.contents,
-            });
+    /// Calls `vkCmdDraw` on the builder.
+    #[inline]
+    pub unsafe fn draw(
+        &mut self,
+        vertex_count: u32,
+        instance_count: u32,
+        first_vertex: u32,
+        first_instance: u32,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            vertex_count: u32,
+            instance_count: u32,
+            first_vertex: u32,
+            first_instance: u32,
         }
 
-        match (&render_pass_state.render_pass, pipeline.render_pass()) {
-            (
-                RenderPassStateType::BeginRenderPass(state),
-                PipelineRenderPassType::BeginRenderPass(pipeline_subpass),
-            ) => {
-                // VUID-vkCmdDraw-renderPass-02684
-                if !pipeline_subpass
-                    .render_pass()
-                    .is_compatible_with(state.subpass.render_pass())
-                {
-                    return Err(PipelineExecutionError::PipelineRenderPassNotCompatible);
-                }
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "draw"
+            }
 
-                // VUID-vkCmdDraw-subpass-02685
-                if pipeline_subpass.index() != state.subpass.index() {
-                    return Err(PipelineExecutionError::PipelineSubpassMismatch {
-                        pipeline: pipeline_subpass.index(),
-                        current: state.subpass.index(),
-                    });
-                }
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.draw(
+                    self.vertex_count,
+                    self.instance_count,
+                    self.first_vertex,
+                    self.first_instance,
+                );
             }
-            (
-                RenderPassStateType::BeginRendering(current_rendering_info),
-                PipelineRenderPassType::BeginRendering(pipeline_rendering_info),
-            ) => {
-                // VUID-vkCmdDraw-viewMask-06178
-                if pipeline_rendering_info.view_mask != render_pass_state.view_mask {
-                    return Err(PipelineExecutionError::PipelineViewMaskMismatch {
-                        pipeline_view_mask: pipeline_rendering_info.view_mask,
-                        required_view_mask: render_pass_state.view_mask,
-                    });
-                }
+        }
 
-                // VUID-vkCmdDraw-colorAttachmentCount-06179
-                if pipeline_rendering_info.color_attachment_formats.len()
-                    != current_rendering_info.color_attachment_formats.len()
-                {
-                    return Err(
-                        PipelineExecutionError::PipelineColorAttachmentCountMismatch {
-                            pipeline_count: pipeline_rendering_info.color_attachment_formats.len()
-                                as u32,
-                            required_count: current_rendering_info.color_attachment_formats.len()
-                                as u32,
-                        },
-                    );
-                }
+        let command_index = self.commands.len();
+        let command_name = "draw";
+        let pipeline = self
+            .current_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
 
-                for (color_attachment_index, required_format, pipeline_format) in
-                    current_rendering_info
-                        .color_attachment_formats
-                        .iter()
-                        .zip(
-                            pipeline_rendering_info
-                                .color_attachment_formats
-                                .iter()
-                                .copied(),
-                        )
-                        .enumerate()
-                        .filter_map(|(i, (r, p))| r.map(|r| (i as u32, r, p)))
-                {
-                    // VUID-vkCmdDraw-colorAttachmentCount-06180
-                    if Some(required_format) != pipeline_format {
-                        return Err(
-                            PipelineExecutionError::PipelineColorAttachmentFormatMismatch {
-                                color_attachment_index,
-                                pipeline_format,
-                                required_format,
-                            },
-                        );
-                    }
-                }
+        let mut resources = Vec::new();
+        self.add_descriptor_sets(&mut resources, command_index, command_name, pipeline);
+        self.add_vertex_buffers(&mut resources, command_index, command_name, pipeline);
+        self.add_transform_feedback_buffers(&mut resources, command_index, command_name, pipeline);
 
-                if let Some((required_format, pipeline_format)) = current_rendering_info
-                    .depth_attachment_format
-                    .map(|r| (r, pipeline_rendering_info.depth_attachment_format))
-                {
-                    // VUID-vkCmdDraw-pDepthAttachment-06181
-                    if Some(required_format) != pipeline_format {
-                        return Err(
-                            PipelineExecutionError::PipelineDepthAttachmentFormatMismatch {
-                                pipeline_format,
-                                required_format,
-                            },
-                        );
-                    }
-                }
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            vertex_count,
+            instance_count,
+            first_vertex,
+            first_instance,
+        }));
 
-                if let Some((required_format, pipeline_format)) = current_rendering_info
-                    .stencil_attachment_format
-                    .map(|r| (r, pipeline_rendering_info.stencil_attachment_format))
-                {
-                    // VUID-vkCmdDraw-pStencilAttachment-06182
-                    if Some(required_format) != pipeline_format {
-                        return Err(
-                            PipelineExecutionError::PipelineStencilAttachmentFormatMismatch {
-                                pipeline_format,
-                                required_format,
-                            },
-                        );
-                    }
-                }
+        for resource in resources {
+            self.add_resource(resource);
+        }
 
-                // VUID-vkCmdDraw-imageView-06172
-                // VUID-vkCmdDraw-imageView-06173
-                // VUID-vkCmdDraw-imageView-06174
-                // VUID-vkCmdDraw-imageView-06175
-                // VUID-vkCmdDraw-imageView-06176
-                // VUID-vkCmdDraw-imageView-06177
-                // TODO:
+        Ok(())
+    }
+
+    /// Calls `vkCmdDrawIndexed` on the builder.
+    #[inline]
+    pub unsafe fn draw_indexed(
+        &mut self,
+        index_count: u32,
+        instance_count: u32,
+        first_index: u32,
+        vertex_offset: i32,
+        first_instance: u32,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            index_count: u32,
+            instance_count: u32,
+            first_index: u32,
+            vertex_offset: i32,
+            first_instance: u32,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "draw_indexed"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.draw_indexed(
+                    self.index_count,
+                    self.instance_count,
+                    self.first_index,
+                    self.vertex_offset,
+                    self.first_instance,
+                );
             }
-            _ => return Err(PipelineExecutionError::PipelineRenderPassTypeMismatch),
         }
 
-        // VUID-vkCmdDraw-None-02686
-        // TODO:
+        let command_index = self.commands.len();
+        let command_name = "draw_indexed";
+        let pipeline = self
+            .current_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+
+        let mut resources = Vec::new();
+        self.add_descriptor_sets(&mut resources, command_index, command_name, pipeline);
+        self.add_vertex_buffers(&mut resources, command_index, command_name, pipeline);
+        self.add_transform_feedback_buffers(&mut resources, command_index, command_name, pipeline);
+        self.add_index_buffer(&mut resources, command_index, command_name);
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            index_count,
+            instance_count,
+            first_index,
+            vertex_offset,
+            first_instance,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
 
         Ok(())
     }
 
-    fn validate_pipeline_graphics_vertex_buffers(
-        &self,
-        pipeline: &GraphicsPipeline,
-        vertices: Option<(u32, u32)>,
-        instances: Option<(u32, u32)>,
-    ) -> Result<(), PipelineExecutionError> {
-        let vertex_input = pipeline.vertex_input_state();
-        let mut vertices_in_buffers: Option<u64> = None;
-        let mut instances_in_buffers: Option<u64> = None;
-        let current_state = self.state();
+    /// Calls `vkCmdDrawIndirect` on the builder.
+    #[inline]
+    pub unsafe fn draw_indirect(
+        &mut self,
+        indirect_buffer: Arc<dyn BufferAccess>,
+        draw_count: u32,
+        stride: u32,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            indirect_buffer: Arc<dyn BufferAccess>,
+            draw_count: u32,
+            stride: u32,
+        }
 
-        for (&binding_num, binding_desc) in &vertex_input.bindings {
-            // VUID-vkCmdDraw-None-04007
-            let vertex_buffer = match current_state.vertex_buffer(binding_num) {
-                Some(x) => x,
-                None => return Err(PipelineExecutionError::VertexBufferNotBound { binding_num }),
-            };
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "draw_indirect"
+            }
 
-            let mut num_elements = vertex_buffer.size() / binding_desc.stride as u64;
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.draw_indirect(self.indirect_buffer.as_ref(), self.draw_count, self.stride);
+            }
+        }
 
-            match binding_desc.input_rate {
-                VertexInputRate::Vertex => {
-                    vertices_in_buffers = Some(if let Some(x) = vertices_in_buffers {
-                        min(x, num_elements)
-                    } else {
-                        num_elements
-                    });
-                }
-                VertexInputRate::Instance { divisor } => {
-                    if divisor == 0 {
-                        // A divisor of 0 means the same instance data is used for all instances,
-                        // so we can draw any number of instances from a single element.
-                        // The buffer must contain at least one element though.
-                        if num_elements != 0 {
-                            num_elements = u64::MAX;
-                        }
-                    } else {
-                        // If divisor is e.g. 2, we use only half the amount of data from the source
-                        // buffer, so the number of instances that can be drawn is twice as large.
-                        num_elements = num_elements.saturating_mul(divisor as u64);
-                    }
+        let command_index = self.commands.len();
+        let command_name = "draw_indirect";
+        let pipeline = self
+            .current_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
 
-                    instances_in_buffers = Some(if let Some(x) = instances_in_buffers {
-                        min(x, num_elements)
-                    } else {
-                        num_elements
-                    });
-                }
-            };
+        let mut resources = Vec::new();
+        self.add_descriptor_sets(&mut resources, command_index, command_name, pipeline);
+        self.add_vertex_buffers(&mut resources, command_index, command_name, pipeline);
+        self.add_transform_feedback_buffers(&mut resources, command_index, command_name, pipeline);
+        self.add_indirect_buffer(
+            &mut resources,
+            command_index,
+            command_name,
+            &indirect_buffer,
+        );
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
         }
 
-        if let Some((first_vertex, vertex_count)) = vertices {
-            let vertices_needed = first_vertex as u64 + vertex_count as u64;
+        self.commands.push(Box::new(Cmd {
+            indirect_buffer,
+            draw_count,
+            stride,
+        }));
 
-            if let Some(vertices_in_buffers) = vertices_in_buffers {
-                // VUID-vkCmdDraw-None-02721
-                if vertices_needed > vertices_in_buffers {
-                    return Err(PipelineExecutionError::VertexBufferVertexRangeOutOfBounds {
-                        vertices_needed,
-                        vertices_in_buffers,
-                    });
-                }
-            }
+        for resource in resources {
+            self.add_resource(resource);
         }
 
-        if let Some((first_instance, instance_count)) = instances {
-            let instances_needed = first_instance as u64 + instance_count as u64;
+        Ok(())
+    }
 
-            if let Some(instances_in_buffers) = instances_in_buffers {
-                // VUID-vkCmdDraw-None-02721
-                if instances_needed > instances_in_buffers {
-                    return Err(
-                        PipelineExecutionError::VertexBufferInstanceRangeOutOfBounds {
-                            instances_needed,
-                            instances_in_buffers,
-                        },
-                    );
-                }
+    /// Calls `vkCmdDrawIndexedIndirect` on the builder.
+    #[inline]
+    pub unsafe fn draw_indexed_indirect(
+        &mut self,
+        indirect_buffer: Arc<dyn BufferAccess>,
+        draw_count: u32,
+        stride: u32,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            indirect_buffer: Arc<dyn BufferAccess>,
+            draw_count: u32,
+            stride: u32,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "draw_indexed_indirect"
             }
 
-            let view_mask = match pipeline.render_pass() {
-                PipelineRenderPassType::BeginRenderPass(subpass) => {
-                    subpass.render_pass().views_used()
-                }
-                PipelineRenderPassType::BeginRendering(rendering_info) => rendering_info.view_mask,
-            };
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.draw_indexed_indirect(
+                    self.indirect_buffer.as_ref(),
+                    self.draw_count,
+                    self.stride,
+                );
+            }
+        }
+
+        let command_index = self.commands.len();
+        let command_name = "draw_indexed_indirect";
+        let pipeline = self
+            .current_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+
+        let mut resources = Vec::new();
+        self.add_descriptor_sets(&mut resources, command_index, command_name, pipeline);
+        self.add_vertex_buffers(&mut resources, command_index, command_name, pipeline);
+        self.add_transform_feedback_buffers(&mut resources, command_index, command_name, pipeline);
+        self.add_index_buffer(&mut resources, command_index, command_name);
+        self.add_indirect_buffer(
+            &mut resources,
+            command_index,
+            command_name,
+            &indirect_buffer,
+        );
 
-            if view_mask != 0 {
-                let max = pipeline
-                    .device()
-                    .physical_device()
-                    .properties()
-                    .max_multiview_instance_index
-                    .unwrap_or(0);
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
 
-                let highest_instance = instances_needed.saturating_sub(1);
+        self.commands.push(Box::new(Cmd {
+            indirect_buffer,
+            draw_count,
+            stride,
+        }));
 
-                // VUID-vkCmdDraw-maxMultiviewInstanceIndex-02688
-                if highest_instance > max as u64 {
-                    return Err(PipelineExecutionError::MaxMultiviewInstanceIndexExceeded {
-                        highest_instance,
-                        max,
-                    });
-                }
-            }
+        for resource in resources {
+            self.add_resource(resource);
         }
 
         Ok(())
     }
-}
 
-impl SyncCommandBufferBuilder {
-    /// Calls `vkCmdDispatch` on the builder.
+    /// Calls `vkCmdDrawIndirectCount` on the builder.
     #[inline]
-    pub unsafe fn dispatch(
+    pub unsafe fn draw_indirect_count(
         &mut self,
-        group_counts: [u32; 3],
+        indirect_buffer: Arc<dyn BufferAccess>,
+        count_buffer: Arc<dyn BufferAccess>,
+        max_draw_count: u32,
+        stride: u32,
     ) -> Result<(), SyncCommandBufferBuilderError> {
         struct Cmd {
-            group_counts: [u32; 3],
+            indirect_buffer: Arc<dyn BufferAccess>,
+            count_buffer: Arc<dyn BufferAccess>,
+            max_draw_count: u32,
+            stride: u32,
         }
 
         impl Command for Cmd {
             fn name(&self) -> &'static str {
-                "dispatch"
+                "draw_indirect_count"
             }
 
             unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
-                out.dispatch(self.group_counts);
+                out.draw_indirect_count(
+                    self.indirect_buffer.as_ref(),
+                    self.count_buffer.as_ref(),
+                    self.max_draw_count,
+                    self.stride,
+                );
             }
         }
 
         let command_index = self.commands.len();
-        let command_name = "dispatch";
+        let command_name = "draw_indirect_count";
         let pipeline = self
             .current_state
-            .pipeline_compute
+            .pipeline_graphics
             .as_ref()
             .unwrap()
             .as_ref();
 
         let mut resources = Vec::new();
         self.add_descriptor_sets(&mut resources, command_index, command_name, pipeline);
+        self.add_vertex_buffers(&mut resources, command_index, command_name, pipeline);
+        self.add_transform_feedback_buffers(&mut resources, command_index, command_name, pipeline);
+        self.add_indirect_buffer(
+            &mut resources,
+            command_index,
+            command_name,
+            &indirect_buffer,
+        );
+        self.add_indirect_count_buffer(&mut resources, command_index, command_name, &count_buffer);
 
         for resource in &resources {
             self.check_resource_conflicts(resource)?;
         }
 
-        self.commands.push(Box::new(Cmd { group_counts }));
+        self.commands.push(Box::new(Cmd {
+            indirect_buffer,
+            count_buffer,
+            max_draw_count,
+            stride,
+        }));
 
         for resource in resources {
             self.add_resource(resource);
@@ -1714,49 +3181,69 @@ impl SyncCommandBufferBuilder {
         Ok(())
     }
 
-    /// Calls `vkCmdDispatchIndirect` on the builder.
+    /// Calls `vkCmdDrawIndexedIndirectCount` on the builder.
     #[inline]
-    pub unsafe fn dispatch_indirect(
+    pub unsafe fn draw_indexed_indirect_count(
         &mut self,
         indirect_buffer: Arc<dyn BufferAccess>,
+        count_buffer: Arc<dyn BufferAccess>,
+        max_draw_count: u32,
+        stride: u32,
     ) -> Result<(), SyncCommandBufferBuilderError> {
         struct Cmd {
             indirect_buffer: Arc<dyn BufferAccess>,
+            count_buffer: Arc<dyn BufferAccess>,
+            max_draw_count: u32,
+            stride: u32,
         }
 
         impl Command for Cmd {
             fn name(&self) -> &'static str {
-                "dispatch_indirect"
+                "draw_indexed_indirect_count"
             }
 
             unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
-                out.dispatch_indirect(self.indirect_buffer.as_ref());
+                out.draw_indexed_indirect_count(
+                    self.indirect_buffer.as_ref(),
+                    self.count_buffer.as_ref(),
+                    self.max_draw_count,
+                    self.stride,
+                );
             }
         }
 
         let command_index = self.commands.len();
-        let command_name = "dispatch_indirect";
+        let command_name = "draw_indexed_indirect_count";
         let pipeline = self
             .current_state
-            .pipeline_compute
+            .pipeline_graphics
             .as_ref()
             .unwrap()
             .as_ref();
 
         let mut resources = Vec::new();
         self.add_descriptor_sets(&mut resources, command_index, command_name, pipeline);
+        self.add_vertex_buffers(&mut resources, command_index, command_name, pipeline);
+        self.add_transform_feedback_buffers(&mut resources, command_index, command_name, pipeline);
+        self.add_index_buffer(&mut resources, command_index, command_name);
         self.add_indirect_buffer(
             &mut resources,
             command_index,
             command_name,
             &indirect_buffer,
         );
+        self.add_indirect_count_buffer(&mut resources, command_index, command_name, &count_buffer);
 
         for resource in &resources {
             self.check_resource_conflicts(resource)?;
         }
 
-        self.commands.push(Box::new(Cmd { indirect_buffer }));
+        self.commands.push(Box::new(Cmd {
+            indirect_buffer,
+            count_buffer,
+            max_draw_count,
+            stride,
+        }));
 
         for resource in resources {
             self.add_resource(resource);
@@ -1765,39 +3252,42 @@ impl SyncCommandBufferBuilder {
         Ok(())
     }
 
-    /// Calls `vkCmdDraw` on the builder.
+    /// Calls `vkCmdDrawIndirectByteCountEXT` on the builder.
     #[inline]
-    pub unsafe fn draw(
+    pub unsafe fn draw_indirect_byte_count(
         &mut self,
-        vertex_count: u32,
         instance_count: u32,
-        first_vertex: u32,
         first_instance: u32,
+        counter_buffer: Arc<dyn BufferAccess>,
+        counter_offset: u32,
+        vertex_stride: u32,
     ) -> Result<(), SyncCommandBufferBuilderError> {
         struct Cmd {
-            vertex_count: u32,
             instance_count: u32,
-            first_vertex: u32,
             first_instance: u32,
+            counter_buffer: Arc<dyn BufferAccess>,
+            counter_offset: u32,
+            vertex_stride: u32,
         }
 
         impl Command for Cmd {
             fn name(&self) -> &'static str {
-                "draw"
+                "draw_indirect_byte_count"
             }
 
             unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
-                out.draw(
-                    self.vertex_count,
+                out.draw_indirect_byte_count(
                     self.instance_count,
-                    self.first_vertex,
                     self.first_instance,
+                    self.counter_buffer.as_ref(),
+                    self.counter_offset,
+                    self.vertex_stride,
                 );
             }
         }
 
         let command_index = self.commands.len();
-        let command_name = "draw";
+        let command_name = "draw_indirect_byte_count";
         let pipeline = self
             .current_state
             .pipeline_graphics
@@ -1808,16 +3298,35 @@ impl SyncCommandBufferBuilder {
         let mut resources = Vec::new();
         self.add_descriptor_sets(&mut resources, command_index, command_name, pipeline);
         self.add_vertex_buffers(&mut resources, command_index, command_name, pipeline);
+        self.add_transform_feedback_buffers(&mut resources, command_index, command_name, pipeline);
+        resources.push((
+            ResourceUseRef {
+                command_index,
+                command_name,
+                resource_in_command: ResourceInCommand::TransformFeedbackCounterBuffer { index: 0 },
+                secondary_use_ref: None,
+            },
+            Resource::Buffer {
+                buffer: counter_buffer.clone(),
+                range: 0..size_of::<u32>() as DeviceSize,
+                memory: PipelineMemoryAccess {
+                    stages: PipelineStages::DRAW_INDIRECT,
+                    access: AccessFlags::TRANSFORM_FEEDBACK_COUNTER_READ,
+                    exclusive: false,
+                },
+            },
+        ));
 
         for resource in &resources {
             self.check_resource_conflicts(resource)?;
         }
 
         self.commands.push(Box::new(Cmd {
-            vertex_count,
             instance_count,
-            first_vertex,
             first_instance,
+            counter_buffer,
+            counter_offset,
+            vertex_stride,
         }));
 
         for resource in resources {
@@ -1827,42 +3336,200 @@ impl SyncCommandBufferBuilder {
         Ok(())
     }
 
-    /// Calls `vkCmdDrawIndexed` on the builder.
+    /// Calls `vkCmdDrawMultiEXT` on the builder.
     #[inline]
-    pub unsafe fn draw_indexed(
+    pub unsafe fn draw_multi(
         &mut self,
-        index_count: u32,
+        vertex_info: SmallVec<[MultiDrawInfo; 4]>,
         instance_count: u32,
-        first_index: u32,
-        vertex_offset: i32,
         first_instance: u32,
     ) -> Result<(), SyncCommandBufferBuilderError> {
         struct Cmd {
-            index_count: u32,
+            vertex_info: SmallVec<[MultiDrawInfo; 4]>,
             instance_count: u32,
-            first_index: u32,
-            vertex_offset: i32,
             first_instance: u32,
         }
 
         impl Command for Cmd {
             fn name(&self) -> &'static str {
-                "draw_indexed"
+                "draw_multi"
             }
 
             unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
-                out.draw_indexed(
-                    self.index_count,
+                out.draw_multi(&self.vertex_info, self.instance_count, self.first_instance);
+            }
+        }
+
+        let command_index = self.commands.len();
+        let command_name = "draw_multi";
+        let pipeline = self
+            .current_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+
+        let mut resources = Vec::new();
+        self.add_descriptor_sets(&mut resources, command_index, command_name, pipeline);
+        self.add_vertex_buffers(&mut resources, command_index, command_name, pipeline);
+        self.add_transform_feedback_buffers(&mut resources, command_index, command_name, pipeline);
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            vertex_info,
+            instance_count,
+            first_instance,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdDrawMultiIndexedEXT` on the builder.
+    #[inline]
+    pub unsafe fn draw_multi_indexed(
+        &mut self,
+        index_info: SmallVec<[MultiDrawIndexedInfo; 4]>,
+        instance_count: u32,
+        first_instance: u32,
+        vertex_offset: Option<i32>,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            index_info: SmallVec<[MultiDrawIndexedInfo; 4]>,
+            instance_count: u32,
+            first_instance: u32,
+            vertex_offset: Option<i32>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "draw_multi_indexed"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.draw_multi_indexed(
+                    &self.index_info,
                     self.instance_count,
-                    self.first_index,
-                    self.vertex_offset,
                     self.first_instance,
+                    self.vertex_offset,
+                );
+            }
+        }
+
+        let command_index = self.commands.len();
+        let command_name = "draw_multi_indexed";
+        let pipeline = self
+            .current_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+
+        let mut resources = Vec::new();
+        self.add_descriptor_sets(&mut resources, command_index, command_name, pipeline);
+        self.add_vertex_buffers(&mut resources, command_index, command_name, pipeline);
+        self.add_transform_feedback_buffers(&mut resources, command_index, command_name, pipeline);
+        self.add_index_buffer(&mut resources, command_index, command_name);
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            index_info,
+            instance_count,
+            first_instance,
+            vertex_offset,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdDrawMeshTasksEXT` on the builder.
+    #[inline]
+    pub unsafe fn draw_mesh_tasks(
+        &mut self,
+        group_counts: [u32; 3],
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            group_counts: [u32; 3],
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "draw_mesh_tasks"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.draw_mesh_tasks(self.group_counts);
+            }
+        }
+
+        let command_index = self.commands.len();
+        let command_name = "draw_mesh_tasks";
+        let pipeline = self
+            .current_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+
+        let mut resources = Vec::new();
+        self.add_descriptor_sets(&mut resources, command_index, command_name, pipeline);
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd { group_counts }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdDrawMeshTasksIndirectEXT` on the builder.
+    #[inline]
+    pub unsafe fn draw_mesh_tasks_indirect(
+        &mut self,
+        indirect_buffer: Arc<dyn BufferAccess>,
+        draw_count: u32,
+        stride: u32,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            indirect_buffer: Arc<dyn BufferAccess>,
+            draw_count: u32,
+            stride: u32,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "draw_mesh_tasks_indirect"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.draw_mesh_tasks_indirect(
+                    self.indirect_buffer.as_ref(),
+                    self.draw_count,
+                    self.stride,
                 );
             }
         }
 
         let command_index = self.commands.len();
-        let command_name = "draw_indexed";
+        let command_name = "draw_mesh_tasks_indirect";
         let pipeline = self
             .current_state
             .pipeline_graphics
@@ -1872,19 +3539,21 @@ impl SyncCommandBufferBuilder {
 
         let mut resources = Vec::new();
         self.add_descriptor_sets(&mut resources, command_index, command_name, pipeline);
-        self.add_vertex_buffers(&mut resources, command_index, command_name, pipeline);
-        self.add_index_buffer(&mut resources, command_index, command_name);
+        self.add_indirect_buffer(
+            &mut resources,
+            command_index,
+            command_name,
+            &indirect_buffer,
+        );
 
         for resource in &resources {
             self.check_resource_conflicts(resource)?;
         }
 
         self.commands.push(Box::new(Cmd {
-            index_count,
-            instance_count,
-            first_index,
-            vertex_offset,
-            first_instance,
+            indirect_buffer,
+            draw_count,
+            stride,
         }));
 
         for resource in resources {
@@ -1894,32 +3563,39 @@ impl SyncCommandBufferBuilder {
         Ok(())
     }
 
-    /// Calls `vkCmdDrawIndirect` on the builder.
+    /// Calls `vkCmdDrawMeshTasksIndirectCountEXT` on the builder.
     #[inline]
-    pub unsafe fn draw_indirect(
+    pub unsafe fn draw_mesh_tasks_indirect_count(
         &mut self,
         indirect_buffer: Arc<dyn BufferAccess>,
-        draw_count: u32,
+        count_buffer: Arc<dyn BufferAccess>,
+        max_draw_count: u32,
         stride: u32,
     ) -> Result<(), SyncCommandBufferBuilderError> {
         struct Cmd {
             indirect_buffer: Arc<dyn BufferAccess>,
-            draw_count: u32,
+            count_buffer: Arc<dyn BufferAccess>,
+            max_draw_count: u32,
             stride: u32,
         }
 
         impl Command for Cmd {
             fn name(&self) -> &'static str {
-                "draw_indirect"
+                "draw_mesh_tasks_indirect_count"
             }
 
             unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
-                out.draw_indirect(self.indirect_buffer.as_ref(), self.draw_count, self.stride);
+                out.draw_mesh_tasks_indirect_count(
+                    self.indirect_buffer.as_ref(),
+                    self.count_buffer.as_ref(),
+                    self.max_draw_count,
+                    self.stride,
+                );
             }
         }
 
         let command_index = self.commands.len();
-        let command_name = "draw_indirect";
+        let command_name = "draw_mesh_tasks_indirect_count";
         let pipeline = self
             .current_state
             .pipeline_graphics
@@ -1929,13 +3605,13 @@ impl SyncCommandBufferBuilder {
 
         let mut resources = Vec::new();
         self.add_descriptor_sets(&mut resources, command_index, command_name, pipeline);
-        self.add_vertex_buffers(&mut resources, command_index, command_name, pipeline);
         self.add_indirect_buffer(
             &mut resources,
             command_index,
             command_name,
             &indirect_buffer,
         );
+        self.add_indirect_count_buffer(&mut resources, command_index, command_name, &count_buffer);
 
         for resource in &resources {
             self.check_resource_conflicts(resource)?;
@@ -1943,7 +3619,8 @@ impl SyncCommandBufferBuilder {
 
         self.commands.push(Box::new(Cmd {
             indirect_buffer,
-            draw_count,
+            count_buffer,
+            max_draw_count,
             stride,
         }));
 
@@ -1954,62 +3631,66 @@ impl SyncCommandBufferBuilder {
         Ok(())
     }
 
-    /// Calls `vkCmdDrawIndexedIndirect` on the builder.
+    /// Calls `vkCmdTraceRaysKHR` on the builder.
     #[inline]
-    pub unsafe fn draw_indexed_indirect(
+    pub unsafe fn trace_rays(
         &mut self,
-        indirect_buffer: Arc<dyn BufferAccess>,
-        draw_count: u32,
-        stride: u32,
+        shader_binding_table: &ShaderBindingTable,
+        dimensions: [u32; 3],
     ) -> Result<(), SyncCommandBufferBuilderError> {
         struct Cmd {
-            indirect_buffer: Arc<dyn BufferAccess>,
-            draw_count: u32,
-            stride: u32,
+            shader_binding_table: ShaderBindingTable,
+            dimensions: [u32; 3],
         }
 
         impl Command for Cmd {
             fn name(&self) -> &'static str {
-                "draw_indexed_indirect"
+                "trace_rays"
             }
 
             unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
-                out.draw_indexed_indirect(
-                    self.indirect_buffer.as_ref(),
-                    self.draw_count,
-                    self.stride,
-                );
+                out.trace_rays(&self.shader_binding_table, self.dimensions);
             }
         }
 
         let command_index = self.commands.len();
-        let command_name = "draw_indexed_indirect";
+        let command_name = "trace_rays";
         let pipeline = self
             .current_state
-            .pipeline_graphics
+            .pipeline_ray_tracing
             .as_ref()
             .unwrap()
             .as_ref();
 
         let mut resources = Vec::new();
         self.add_descriptor_sets(&mut resources, command_index, command_name, pipeline);
-        self.add_vertex_buffers(&mut resources, command_index, command_name, pipeline);
-        self.add_index_buffer(&mut resources, command_index, command_name);
-        self.add_indirect_buffer(
-            &mut resources,
-            command_index,
-            command_name,
-            &indirect_buffer,
-        );
+
+        let buffer = shader_binding_table.buffer();
+        resources.push((
+            ResourceUseRef {
+                command_index,
+                command_name,
+                resource_in_command: ResourceInCommand::ShaderBindingTable,
+                secondary_use_ref: None,
+            },
+            Resource::Buffer {
+                buffer: buffer.clone(),
+                range: 0..buffer.size(),
+                memory: PipelineMemoryAccess {
+                    stages: PipelineStages::RAY_TRACING_SHADER,
+                    access: AccessFlags::SHADER_BINDING_TABLE_READ,
+                    exclusive: false,
+                },
+            },
+        ));
 
         for resource in &resources {
             self.check_resource_conflicts(resource)?;
         }
 
         self.commands.push(Box::new(Cmd {
-            indirect_buffer,
-            draw_count,
-            stride,
+            shader_binding_table: shader_binding_table.clone(),
+            dimensions,
         }));
 
         for resource in resources {
@@ -2064,6 +3745,9 @@ impl SyncCommandBufferBuilder {
                 DescriptorType::UniformBuffer | DescriptorType::UniformBufferDynamic => {
                     (Some(AccessFlags::UNIFORM_READ), None)
                 }
+                DescriptorType::AccelerationStructure => {
+                    (Some(AccessFlags::ACCELERATION_STRUCTURE_READ), None)
+                }
             };
 
             let memory_iter = move |index: u32| {
@@ -2225,6 +3909,19 @@ impl SyncCommandBufferBuilder {
                     );
                 }
                 DescriptorBindingResources::Sampler(_) => (),
+                DescriptorBindingResources::AccelerationStructure(elements) => {
+                    resources.extend(
+                        (elements.iter().enumerate())
+                            .filter_map(|(index, element)| {
+                                element.as_ref().map(|acceleration_structure| {
+                                    let buffer = acceleration_structure.buffer().clone();
+                                    let range = 0..buffer.size();
+                                    (index as u32, buffer, range)
+                                })
+                            })
+                            .flat_map(buffer_resource),
+                    );
+                }
             }
         }
     }
@@ -2236,32 +3933,76 @@ impl SyncCommandBufferBuilder {
         command_name: &'static str,
         pipeline: &GraphicsPipeline,
     ) {
-        resources.extend(
-            pipeline
-                .vertex_input_state()
-                .bindings
-                .iter()
-                .map(|(&binding, _)| {
-                    let vertex_buffer = &self.current_state.vertex_buffers[&binding];
-                    (
-                        ResourceUseRef {
-                            command_index,
-                            command_name,
-                            resource_in_command: ResourceInCommand::VertexBuffer { binding },
-                            secondary_use_ref: None,
-                        },
-                        Resource::Buffer {
-                            buffer: vertex_buffer.clone(),
-                            range: 0..vertex_buffer.size(), // TODO:
-                            memory: PipelineMemoryAccess {
-                                stages: PipelineStages::VERTEX_INPUT,
-                                access: AccessFlags::VERTEX_ATTRIBUTE_READ,
-                                exclusive: false,
-                            },
-                        },
-                    )
-                }),
-        );
+        // If the vertex input state is dynamic, the state that was set is used instead of the
+        // (empty) state of the pipeline.
+        let vertex_input = if pipeline.dynamic_state(DynamicState::VertexInput) == Some(true) {
+            self.current_state.vertex_input.as_ref().unwrap()
+        } else {
+            pipeline.vertex_input_state()
+        };
+
+        resources.extend(vertex_input.bindings.iter().map(|(&binding, _)| {
+            let vertex_buffer = &self.current_state.vertex_buffers[&binding];
+            (
+                ResourceUseRef {
+                    command_index,
+                    command_name,
+                    resource_in_command: ResourceInCommand::VertexBuffer { binding },
+                    secondary_use_ref: None,
+                },
+                Resource::Buffer {
+                    buffer: vertex_buffer.clone(),
+                    range: 0..vertex_buffer.size(), // TODO:
+                    memory: PipelineMemoryAccess {
+                        stages: PipelineStages::VERTEX_INPUT,
+                        access: AccessFlags::VERTEX_ATTRIBUTE_READ,
+                        exclusive: false,
+                    },
+                },
+            )
+        }));
+    }
+
+    fn add_transform_feedback_buffers(
+        &self,
+        resources: &mut Vec<(ResourceUseRef, Resource)>,
+        command_index: usize,
+        command_name: &'static str,
+        pipeline: &GraphicsPipeline,
+    ) {
+        if !self.current_state.transform_feedback_active {
+            return;
+        }
+
+        let mut bindings: SmallVec<[u32; 4]> = pipeline
+            .transform_feedback_outputs()
+            .into_iter()
+            .flatten()
+            .map(|output| output.buffer)
+            .collect();
+        bindings.sort_unstable();
+        bindings.dedup();
+
+        resources.extend(bindings.into_iter().map(|binding| {
+            let buffer = &self.current_state.transform_feedback_buffers[&binding];
+            (
+                ResourceUseRef {
+                    command_index,
+                    command_name,
+                    resource_in_command: ResourceInCommand::TransformFeedbackBuffer { binding },
+                    secondary_use_ref: None,
+                },
+                Resource::Buffer {
+                    buffer: buffer.clone(),
+                    range: 0..buffer.size(),
+                    memory: PipelineMemoryAccess {
+                        stages: PipelineStages::TRANSFORM_FEEDBACK,
+                        access: AccessFlags::TRANSFORM_FEEDBACK_WRITE,
+                        exclusive: true,
+                    },
+                },
+            )
+        }));
     }
 
     fn add_index_buffer(
@@ -2315,91 +4056,383 @@ impl SyncCommandBufferBuilder {
             },
         ));
     }
-}
+    fn add_indirect_count_buffer(
+        &self,
+        resources: &mut Vec<(ResourceUseRef, Resource)>,
+        command_index: usize,
+        command_name: &'static str,
+        count_buffer: &Arc<dyn BufferAccess>,
+    ) {
+        resources.push((
+            ResourceUseRef {
+                command_index,
+                command_name,
+                resource_in_command: ResourceInCommand::IndirectCountBuffer,
+                secondary_use_ref: None,
+            },
+            Resource::Buffer {
+                buffer: count_buffer.clone(),
+                range: 0..count_buffer.size(), // TODO:
+                memory: PipelineMemoryAccess {
+                    stages: PipelineStages::DRAW_INDIRECT,
+                    access: AccessFlags::INDIRECT_COMMAND_READ,
+                    exclusive: false,
+                },
+            },
+        ));
+    }
+}
+
+impl UnsafeCommandBufferBuilder {
+    /// Calls `vkCmdDispatch` on the builder.
+    #[inline]
+    pub unsafe fn dispatch(&mut self, group_counts: [u32; 3]) {
+        debug_assert!({
+            let max_group_counts = self
+                .device
+                .physical_device()
+                .properties()
+                .max_compute_work_group_count;
+            group_counts[0] <= max_group_counts[0]
+                && group_counts[1] <= max_group_counts[1]
+                && group_counts[2] <= max_group_counts[2]
+        });
+
+        let fns = self.device.fns();
+        (fns.v1_0.cmd_dispatch)(
+            self.handle,
+            group_counts[0],
+            group_counts[1],
+            group_counts[2],
+        );
+    }
+
+    /// Calls `vkCmdDispatchIndirect` on the builder.
+    #[inline]
+    pub unsafe fn dispatch_indirect(&mut self, buffer: &dyn BufferAccess) {
+        let fns = self.device.fns();
+
+        let inner = buffer.inner();
+        debug_assert!(inner.offset < inner.buffer.size());
+        debug_assert!(inner
+            .buffer
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER));
+        debug_assert_eq!(inner.offset % 4, 0);
+
+        (fns.v1_0.cmd_dispatch_indirect)(self.handle, inner.buffer.handle(), inner.offset);
+    }
+
+    /// Calls `vkCmdDraw` on the builder.
+    #[inline]
+    pub unsafe fn draw(
+        &mut self,
+        vertex_count: u32,
+        instance_count: u32,
+        first_vertex: u32,
+        first_instance: u32,
+    ) {
+        let fns = self.device.fns();
+        (fns.v1_0.cmd_draw)(
+            self.handle,
+            vertex_count,
+            instance_count,
+            first_vertex,
+            first_instance,
+        );
+    }
+
+    /// Calls `vkCmdDrawIndexed` on the builder.
+    #[inline]
+    pub unsafe fn draw_indexed(
+        &mut self,
+        index_count: u32,
+        instance_count: u32,
+        first_index: u32,
+        vertex_offset: i32,
+        first_instance: u32,
+    ) {
+        let fns = self.device.fns();
+        (fns.v1_0.cmd_draw_indexed)(
+            self.handle,
+            index_count,
+            instance_count,
+            first_index,
+            vertex_offset,
+            first_instance,
+        );
+    }
+
+    /// Calls `vkCmdDrawIndirect` on the builder.
+    #[inline]
+    pub unsafe fn draw_indirect(
+        &mut self,
+        buffer: &dyn BufferAccess,
+        draw_count: u32,
+        stride: u32,
+    ) {
+        let fns = self.device.fns();
+
+        debug_assert!(
+            draw_count == 0
+                || ((stride % 4) == 0)
+                    && stride as usize >= size_of::<ash::vk::DrawIndirectCommand>()
+        );
+
+        let inner = buffer.inner();
+        debug_assert!(inner.offset < inner.buffer.size());
+        debug_assert!(inner
+            .buffer
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER));
+
+        (fns.v1_0.cmd_draw_indirect)(
+            self.handle,
+            inner.buffer.handle(),
+            inner.offset,
+            draw_count,
+            stride,
+        );
+    }
+
+    /// Calls `vkCmdDrawIndexedIndirect` on the builder.
+    #[inline]
+    pub unsafe fn draw_indexed_indirect(
+        &mut self,
+        buffer: &dyn BufferAccess,
+        draw_count: u32,
+        stride: u32,
+    ) {
+        let fns = self.device.fns();
+
+        let inner = buffer.inner();
+        debug_assert!(inner.offset < inner.buffer.size());
+        debug_assert!(inner
+            .buffer
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER));
+
+        (fns.v1_0.cmd_draw_indexed_indirect)(
+            self.handle,
+            inner.buffer.handle(),
+            inner.offset,
+            draw_count,
+            stride,
+        );
+    }
 
-impl UnsafeCommandBufferBuilder {
-    /// Calls `vkCmdDispatch` on the builder.
+    /// Calls `vkCmdDrawIndirectCount` on the builder.
     #[inline]
-    pub unsafe fn dispatch(&mut self, group_counts: [u32; 3]) {
-        debug_assert!({
-            let max_group_counts = self
-                .device
-                .physical_device()
-                .properties()
-                .max_compute_work_group_count;
-            group_counts[0] <= max_group_counts[0]
-                && group_counts[1] <= max_group_counts[1]
-                && group_counts[2] <= max_group_counts[2]
-        });
-
+    pub unsafe fn draw_indirect_count(
+        &mut self,
+        buffer: &dyn BufferAccess,
+        count_buffer: &dyn BufferAccess,
+        max_draw_count: u32,
+        stride: u32,
+    ) {
         let fns = self.device.fns();
-        (fns.v1_0.cmd_dispatch)(
-            self.handle,
-            group_counts[0],
-            group_counts[1],
-            group_counts[2],
+
+        debug_assert!(
+            ((stride % 4) == 0) && stride as usize >= size_of::<ash::vk::DrawIndirectCommand>()
         );
+
+        let inner = buffer.inner();
+        debug_assert!(inner.offset < inner.buffer.size());
+        debug_assert!(inner
+            .buffer
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER));
+
+        let count_inner = count_buffer.inner();
+        debug_assert!(count_inner.offset < count_inner.buffer.size());
+        debug_assert!(count_inner
+            .buffer
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER));
+        debug_assert_eq!(count_inner.offset % 4, 0);
+
+        if self.device.api_version() >= Version::V1_2 {
+            (fns.v1_2.cmd_draw_indirect_count)(
+                self.handle,
+                inner.buffer.handle(),
+                inner.offset,
+                count_inner.buffer.handle(),
+                count_inner.offset,
+                max_draw_count,
+                stride,
+            );
+        } else {
+            debug_assert!(self.device.enabled_extensions().khr_draw_indirect_count);
+            (fns.khr_draw_indirect_count.cmd_draw_indirect_count_khr)(
+                self.handle,
+                inner.buffer.handle(),
+                inner.offset,
+                count_inner.buffer.handle(),
+                count_inner.offset,
+                max_draw_count,
+                stride,
+            );
+        }
     }
 
-    /// Calls `vkCmdDispatchIndirect` on the builder.
+    /// Calls `vkCmdDrawIndexedIndirectCount` on the builder.
     #[inline]
-    pub unsafe fn dispatch_indirect(&mut self, buffer: &dyn BufferAccess) {
+    pub unsafe fn draw_indexed_indirect_count(
+        &mut self,
+        buffer: &dyn BufferAccess,
+        count_buffer: &dyn BufferAccess,
+        max_draw_count: u32,
+        stride: u32,
+    ) {
         let fns = self.device.fns();
 
+        debug_assert!(
+            ((stride % 4) == 0)
+                && stride as usize >= size_of::<ash::vk::DrawIndexedIndirectCommand>()
+        );
+
         let inner = buffer.inner();
         debug_assert!(inner.offset < inner.buffer.size());
         debug_assert!(inner
             .buffer
             .usage()
             .intersects(BufferUsage::INDIRECT_BUFFER));
-        debug_assert_eq!(inner.offset % 4, 0);
 
-        (fns.v1_0.cmd_dispatch_indirect)(self.handle, inner.buffer.handle(), inner.offset);
+        let count_inner = count_buffer.inner();
+        debug_assert!(count_inner.offset < count_inner.buffer.size());
+        debug_assert!(count_inner
+            .buffer
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER));
+        debug_assert_eq!(count_inner.offset % 4, 0);
+
+        if self.device.api_version() >= Version::V1_2 {
+            (fns.v1_2.cmd_draw_indexed_indirect_count)(
+                self.handle,
+                inner.buffer.handle(),
+                inner.offset,
+                count_inner.buffer.handle(),
+                count_inner.offset,
+                max_draw_count,
+                stride,
+            );
+        } else {
+            debug_assert!(self.device.enabled_extensions().khr_draw_indirect_count);
+            (fns.khr_draw_indirect_count
+                .cmd_draw_indexed_indirect_count_khr)(
+                self.handle,
+                inner.buffer.handle(),
+                inner.offset,
+                count_inner.buffer.handle(),
+                count_inner.offset,
+                max_draw_count,
+                stride,
+            );
+        }
     }
 
-    /// Calls `vkCmdDraw` on the builder.
+    /// Calls `vkCmdDrawIndirectByteCountEXT` on the builder.
     #[inline]
-    pub unsafe fn draw(
+    pub unsafe fn draw_indirect_byte_count(
         &mut self,
-        vertex_count: u32,
         instance_count: u32,
-        first_vertex: u32,
         first_instance: u32,
+        counter_buffer: &dyn BufferAccess,
+        counter_offset: u32,
+        vertex_stride: u32,
     ) {
+        let counter_inner = counter_buffer.inner();
+        debug_assert!(counter_inner
+            .buffer
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER));
+        debug_assert_eq!(counter_inner.offset % 4, 0);
+
         let fns = self.device.fns();
-        (fns.v1_0.cmd_draw)(
+        (fns.ext_transform_feedback.cmd_draw_indirect_byte_count_ext)(
             self.handle,
-            vertex_count,
             instance_count,
-            first_vertex,
             first_instance,
+            counter_inner.buffer.handle(),
+            counter_inner.offset,
+            counter_offset,
+            vertex_stride,
         );
     }
 
-    /// Calls `vkCmdDrawIndexed` on the builder.
+    /// Calls `vkCmdDrawMultiEXT` on the builder.
+    ///
+    /// If the list is empty then the command is automatically ignored.
     #[inline]
-    pub unsafe fn draw_indexed(
+    pub unsafe fn draw_multi(
         &mut self,
-        index_count: u32,
+        vertex_info: &[MultiDrawInfo],
         instance_count: u32,
-        first_index: u32,
-        vertex_offset: i32,
         first_instance: u32,
     ) {
+        if vertex_info.is_empty() {
+            return;
+        }
+
+        debug_assert!(self.device.enabled_extensions().ext_multi_draw);
+
         let fns = self.device.fns();
-        (fns.v1_0.cmd_draw_indexed)(
+        (fns.ext_multi_draw.cmd_draw_multi_ext)(
             self.handle,
-            index_count,
+            vertex_info.len() as u32,
+            vertex_info.as_ptr() as *const ash::vk::MultiDrawInfoEXT,
             instance_count,
-            first_index,
-            vertex_offset,
             first_instance,
+            size_of::<MultiDrawInfo>() as u32,
         );
     }
 
-    /// Calls `vkCmdDrawIndirect` on the builder.
+    /// Calls `vkCmdDrawMultiIndexedEXT` on the builder.
+    ///
+    /// If the list is empty then the command is automatically ignored.
     #[inline]
-    pub unsafe fn draw_indirect(
+    pub unsafe fn draw_multi_indexed(
+        &mut self,
+        index_info: &[MultiDrawIndexedInfo],
+        instance_count: u32,
+        first_instance: u32,
+        vertex_offset: Option<i32>,
+    ) {
+        if index_info.is_empty() {
+            return;
+        }
+
+        debug_assert!(self.device.enabled_extensions().ext_multi_draw);
+
+        let fns = self.device.fns();
+        (fns.ext_multi_draw.cmd_draw_multi_indexed_ext)(
+            self.handle,
+            index_info.len() as u32,
+            index_info.as_ptr() as *const ash::vk::MultiDrawIndexedInfoEXT,
+            instance_count,
+            first_instance,
+            size_of::<MultiDrawIndexedInfo>() as u32,
+            vertex_offset
+                .as_ref()
+                .map_or(ptr::null(), |vertex_offset| vertex_offset),
+        );
+    }
+
+    /// Calls `vkCmdDrawMeshTasksEXT` on the builder.
+    #[inline]
+    pub unsafe fn draw_mesh_tasks(&mut self, group_counts: [u32; 3]) {
+        let fns = self.device.fns();
+        (fns.ext_mesh_shader.cmd_draw_mesh_tasks_ext)(
+            self.handle,
+            group_counts[0],
+            group_counts[1],
+            group_counts[2],
+        );
+    }
+
+    /// Calls `vkCmdDrawMeshTasksIndirectEXT` on the builder.
+    #[inline]
+    pub unsafe fn draw_mesh_tasks_indirect(
         &mut self,
         buffer: &dyn BufferAccess,
         draw_count: u32,
@@ -2410,7 +4443,7 @@ impl UnsafeCommandBufferBuilder {
         debug_assert!(
             draw_count == 0
                 || ((stride % 4) == 0)
-                    && stride as usize >= size_of::<ash::vk::DrawIndirectCommand>()
+                    && stride as usize >= size_of::<ash::vk::DrawMeshTasksIndirectCommandEXT>()
         );
 
         let inner = buffer.inner();
@@ -2420,7 +4453,7 @@ impl UnsafeCommandBufferBuilder {
             .usage()
             .intersects(BufferUsage::INDIRECT_BUFFER));
 
-        (fns.v1_0.cmd_draw_indirect)(
+        (fns.ext_mesh_shader.cmd_draw_mesh_tasks_indirect_ext)(
             self.handle,
             inner.buffer.handle(),
             inner.offset,
@@ -2429,16 +4462,22 @@ impl UnsafeCommandBufferBuilder {
         );
     }
 
-    /// Calls `vkCmdDrawIndexedIndirect` on the builder.
+    /// Calls `vkCmdDrawMeshTasksIndirectCountEXT` on the builder.
     #[inline]
-    pub unsafe fn draw_indexed_indirect(
+    pub unsafe fn draw_mesh_tasks_indirect_count(
         &mut self,
         buffer: &dyn BufferAccess,
-        draw_count: u32,
+        count_buffer: &dyn BufferAccess,
+        max_draw_count: u32,
         stride: u32,
     ) {
         let fns = self.device.fns();
 
+        debug_assert!(
+            ((stride % 4) == 0)
+                && stride as usize >= size_of::<ash::vk::DrawMeshTasksIndirectCommandEXT>()
+        );
+
         let inner = buffer.inner();
         debug_assert!(inner.offset < inner.buffer.size());
         debug_assert!(inner
@@ -2446,14 +4485,69 @@ impl UnsafeCommandBufferBuilder {
             .usage()
             .intersects(BufferUsage::INDIRECT_BUFFER));
 
-        (fns.v1_0.cmd_draw_indexed_indirect)(
+        let count_inner = count_buffer.inner();
+        debug_assert!(count_inner.offset < count_inner.buffer.size());
+        debug_assert!(count_inner
+            .buffer
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER));
+        debug_assert_eq!(count_inner.offset % 4, 0);
+
+        (fns.ext_mesh_shader.cmd_draw_mesh_tasks_indirect_count_ext)(
             self.handle,
             inner.buffer.handle(),
             inner.offset,
-            draw_count,
+            count_inner.buffer.handle(),
+            count_inner.offset,
+            max_draw_count,
             stride,
         );
     }
+
+    /// Calls `vkCmdTraceRaysKHR` on the builder.
+    #[inline]
+    pub unsafe fn trace_rays(
+        &mut self,
+        shader_binding_table: &ShaderBindingTable,
+        dimensions: [u32; 3],
+    ) {
+        let fns = self.device.fns();
+        (fns.khr_ray_tracing_pipeline.cmd_trace_rays_khr)(
+            self.handle,
+            &shader_binding_table.raygen().into(),
+            &shader_binding_table.miss().into(),
+            &shader_binding_table.hit().into(),
+            &shader_binding_table.callable().into(),
+            dimensions[0],
+            dimensions[1],
+            dimensions[2],
+        );
+    }
+}
+
+// Checks a dynamic vertex input state against the input interface of the vertex shader, in the
+// same way as is done when creating a pipeline with a fixed vertex input state.
+pub(in crate::command_buffer) fn validate_dynamic_vertex_input(
+    interface: &ShaderInterface,
+    vertex_input_state: &VertexInputState,
+) -> Result<(), PipelineExecutionError> {
+    validate_vertex_input_attributes(interface, vertex_input_state).map_err(|err| match err {
+        VertexInputAttributeError::IncompatibleFormat {
+            location,
+            shader_type,
+            attribute_type,
+        } => PipelineExecutionError::VertexInputAttributeIncompatibleFormat {
+            location,
+            shader_type,
+            attribute_type,
+        },
+        VertexInputAttributeError::Missing { location } => {
+            PipelineExecutionError::VertexInputAttributeMissing { location }
+        }
+        VertexInputAttributeError::Unsupported64Bit { location } => {
+            PipelineExecutionError::VertexInputAttributeUnsupported64Bit { location }
+        }
+    })
 }
 
 /// Error that can happen when recording a bound pipeline execution command.
@@ -2523,6 +4617,11 @@ pub enum PipelineExecutionError {
         subpass_contents: SubpassContents,
     },
 
+    /// The offset of the counter buffer is not a multiple of 4.
+    CounterBufferOffsetNotAligned {
+        offset: DeviceSize,
+    },
+
     /// An indexed draw command was recorded, but no index buffer was bound.
     IndexBufferNotBound,
 
@@ -2536,6 +4635,21 @@ pub enum PipelineExecutionError {
     /// The `indirect_buffer` usage was not enabled on the indirect buffer.
     IndirectBufferMissingUsage,
 
+    /// The `max_draw_count` of an indirect count draw exceeds the number of draw commands that
+    /// fit in the indirect buffer.
+    IndirectBufferRangeOutOfBounds {
+        max_draw_count: u32,
+        commands_in_buffer: u32,
+    },
+
+    /// The `indirect_buffer` usage was not enabled on the count buffer.
+    IndirectCountBufferMissingUsage,
+
+    /// The offset of the count buffer is not a multiple of 4.
+    IndirectCountBufferOffsetNotAligned {
+        offset: DeviceSize,
+    },
+
     /// The `max_compute_work_group_count` limit has been exceeded.
     MaxComputeWorkGroupCountExceeded {
         requested: [u32; 3],
@@ -2548,12 +4662,59 @@ pub enum PipelineExecutionError {
         max: u32,
     },
 
+    /// The `max_task_work_group_count` or `max_mesh_work_group_count` limit has been exceeded.
+    MaxMeshTasksWorkGroupCountExceeded {
+        requested: [u32; 3],
+        max: [u32; 3],
+    },
+
+    /// The `max_task_work_group_total_count` or `max_mesh_work_group_total_count` limit has been
+    /// exceeded.
+    MaxMeshTasksWorkGroupTotalCountExceeded {
+        requested: u64,
+        max: u32,
+    },
+
+    /// The `max_multi_draw_count` limit has been exceeded.
+    MaxMultiDrawCountExceeded {
+        provided: u32,
+        max: u32,
+    },
+
     /// The `max_multiview_instance_index` limit has been exceeded.
     MaxMultiviewInstanceIndexExceeded {
         highest_instance: u64,
         max: u32,
     },
 
+    /// The `max_ray_dispatch_invocation_count` limit has been exceeded.
+    MaxRayDispatchInvocationCountExceeded {
+        requested: u64,
+        max: u32,
+    },
+
+    /// The maximum dimensions of a ray tracing operation, derived from the
+    /// `max_compute_work_group_count` and `max_compute_work_group_size` limits, have been
+    /// exceeded.
+    MaxTraceRaysDimensionsExceeded {
+        requested: [u32; 3],
+        max: [u32; 3],
+    },
+
+    /// The `max_transform_feedback_buffer_data_stride` limit has been exceeded.
+    MaxTransformFeedbackBufferDataStrideExceeded {
+        provided: u32,
+        max: u32,
+    },
+
+    /// The bound graphics pipeline contains a mesh shader, which can only be used with the
+    /// `draw_mesh_tasks` commands.
+    MeshShaderPipelineBound,
+
+    /// The bound graphics pipeline does not contain a mesh shader, but the `draw_mesh_tasks`
+    /// commands require one.
+    MeshShaderPipelineNotBound,
+
     /// The queue family doesn't allow this operation.
     NotSupportedByQueueFamily,
 
@@ -2619,6 +4780,12 @@ pub enum PipelineExecutionError {
     /// Not all push constants used by the pipeline have been set.
     PushConstantsMissing,
 
+    /// The shader binding table was not created for the bound ray tracing pipeline.
+    ShaderBindingTablePipelineMismatch,
+
+    /// The `transform_feedback_draw` property of the physical device is not supported.
+    TransformFeedbackDrawNotSupported,
+
     /// The bound graphics pipeline requires a vertex buffer bound to a binding number, but none
     /// was bound.
     VertexBufferNotBound {
@@ -2638,6 +4805,32 @@ pub enum PipelineExecutionError {
         vertices_needed: u64,
         vertices_in_buffers: u64,
     },
+
+    /// The bound pipeline uses a dynamic vertex input state, and the type of a vertex shader
+    /// input variable is not compatible with the format of the corresponding vertex input
+    /// attribute.
+    VertexInputAttributeIncompatibleFormat {
+        location: u32,
+        shader_type: NumericType,
+        attribute_type: NumericType,
+    },
+
+    /// The bound pipeline uses a dynamic vertex input state, and the vertex shader expects an
+    /// input variable at a location for which no vertex input attribute was set.
+    VertexInputAttributeMissing {
+        location: u32,
+    },
+
+    /// The bound pipeline uses a dynamic vertex input state, and the vertex shader has an input
+    /// variable with a 64-bit type, which is not yet supported by vulkano.
+    VertexInputAttributeUnsupported64Bit {
+        location: u32,
+    },
+
+    /// The vertex stride is not a multiple of 4.
+    VertexStrideNotAligned {
+        vertex_stride: u32,
+    },
 }
 
 impl Error for PipelineExecutionError {
@@ -2729,6 +4922,11 @@ impl Display for PipelineExecutionError {
                 "operation forbidden inside a render subpass with contents {:?}",
                 subpass_contents,
             ),
+            Self::CounterBufferOffsetNotAligned { offset } => write!(
+                f,
+                "the offset of the counter buffer ({}) is not a multiple of 4",
+                offset,
+            ),
             Self::IndexBufferNotBound => write!(
                 f,
                 "an indexed draw command was recorded, but no index buffer was bound",
@@ -2746,6 +4944,24 @@ impl Display for PipelineExecutionError {
                 f,
                 "the `indirect_buffer` usage was not enabled on the indirect buffer",
             ),
+            Self::IndirectBufferRangeOutOfBounds {
+                max_draw_count,
+                commands_in_buffer,
+            } => write!(
+                f,
+                "the `max_draw_count` ({}) exceeds the number of draw commands that fit in the \
+                indirect buffer ({})",
+                max_draw_count, commands_in_buffer,
+            ),
+            Self::IndirectCountBufferMissingUsage => write!(
+                f,
+                "the `indirect_buffer` usage was not enabled on the count buffer",
+            ),
+            Self::IndirectCountBufferOffsetNotAligned { offset } => write!(
+                f,
+                "the offset of the count buffer ({}) is not a multiple of 4",
+                offset,
+            ),
             Self::MaxComputeWorkGroupCountExceeded { .. } => write!(
                 f,
                 "the `max_compute_work_group_count` limit has been exceeded",
@@ -2753,10 +4969,46 @@ impl Display for PipelineExecutionError {
             Self::MaxDrawIndirectCountExceeded { .. } => {
                 write!(f, "the `max_draw_indirect_count` limit has been exceeded")
             }
+            Self::MaxMeshTasksWorkGroupCountExceeded { .. } => write!(
+                f,
+                "the `max_task_work_group_count` or `max_mesh_work_group_count` limit has been \
+                exceeded",
+            ),
+            Self::MaxMeshTasksWorkGroupTotalCountExceeded { .. } => write!(
+                f,
+                "the `max_task_work_group_total_count` or `max_mesh_work_group_total_count` limit \
+                has been exceeded",
+            ),
+            Self::MaxMultiDrawCountExceeded { .. } => {
+                write!(f, "the `max_multi_draw_count` limit has been exceeded")
+            }
             Self::MaxMultiviewInstanceIndexExceeded { .. } => write!(
                 f,
                 "the `max_multiview_instance_index` limit has been exceeded",
             ),
+            Self::MaxRayDispatchInvocationCountExceeded { .. } => write!(
+                f,
+                "the `max_ray_dispatch_invocation_count` limit has been exceeded",
+            ),
+            Self::MaxTraceRaysDimensionsExceeded { .. } => write!(
+                f,
+                "the maximum dimensions of a ray tracing operation have been exceeded",
+            ),
+            Self::MaxTransformFeedbackBufferDataStrideExceeded { provided, max } => write!(
+                f,
+                "the vertex stride ({}) is greater than the \
+                `max_transform_feedback_buffer_data_stride` limit ({})",
+                provided, max,
+            ),
+            Self::MeshShaderPipelineBound => write!(
+                f,
+                "the bound graphics pipeline contains a mesh shader, which can only be used with \
+                the `draw_mesh_tasks` commands",
+            ),
+            Self::MeshShaderPipelineNotBound => write!(
+                f,
+                "the bound graphics pipeline does not contain a mesh shader",
+            ),
             Self::NotSupportedByQueueFamily => {
                 write!(f, "the queue family doesn't allow this operation")
             }
@@ -2839,6 +5091,14 @@ impl Display for PipelineExecutionError {
                 f,
                 "not all push constants used by the pipeline have been set",
             ),
+            Self::ShaderBindingTablePipelineMismatch => write!(
+                f,
+                "the shader binding table was not created for the bound ray tracing pipeline",
+            ),
+            Self::TransformFeedbackDrawNotSupported => write!(
+                f,
+                "the `transform_feedback_draw` property of the physical device is not supported",
+            ),
             Self::VertexBufferNotBound { binding_num } => write!(
                 f,
                 "the bound graphics pipeline requires a vertex buffer bound to binding number {}, \
@@ -2863,6 +5123,33 @@ impl Display for PipelineExecutionError {
                 in the bound vertex buffers ({}) used by the pipeline",
                 vertices_needed, vertices_in_buffers,
             ),
+            Self::VertexInputAttributeIncompatibleFormat {
+                location,
+                shader_type,
+                attribute_type,
+            } => write!(
+                f,
+                "the type of the vertex shader input variable at location {} ({:?}) is not \
+                compatible with the format of the dynamic vertex input attribute ({:?})",
+                location, shader_type, attribute_type,
+            ),
+            Self::VertexInputAttributeMissing { location } => write!(
+                f,
+                "the vertex shader expects an input variable at location {}, but no dynamic \
+                vertex input attribute was set for that location",
+                location,
+            ),
+            Self::VertexInputAttributeUnsupported64Bit { location } => write!(
+                f,
+                "the vertex shader has an input variable with a 64-bit type at location {}, which \
+                is not yet supported",
+                location,
+            ),
+            Self::VertexStrideNotAligned { vertex_stride } => write!(
+                f,
+                "the vertex stride ({}) is not a multiple of 4",
+                vertex_stride,
+            ),
         }
     }
 }
@@ -2981,3 +5268,239 @@ impl Display for DescriptorResourceInvalidError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::PipelineExecutionError;
+    use crate::{
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder,
+            CommandBufferUsage, RenderPassBeginInfo, SubpassContents,
+        },
+        device::{Device, Queue},
+        format::{Format, NumericType},
+        image::{view::ImageView, AttachmentImage},
+        memory::allocator::StandardMemoryAllocator,
+        pipeline::graphics::{
+            vertex_input::{
+                VertexInputAttributeDescription, VertexInputBindingDescription, VertexInputRate,
+                VertexInputState,
+            },
+            viewport::{Viewport, ViewportState},
+            GraphicsPipeline,
+        },
+        render_pass::{Framebuffer, FramebufferCreateInfo, Subpass},
+        shader::ShaderModule,
+    };
+    use std::sync::Arc;
+
+    // Records a draw with a pipeline that has a dynamic vertex input state, after setting
+    // `vertex_input_state` as the vertex input state.
+    fn draw_with_vertex_input(
+        device: Arc<Device>,
+        queue: Arc<Queue>,
+        vertex_input_state: VertexInputState,
+    ) -> Result<(), PipelineExecutionError> {
+        /*
+        #version 450
+
+        #extension GL_ARB_separate_shader_objects : enable
+        #extension GL_ARB_shading_language_420pack : enable
+
+        layout(location = 0) in vec2 position;
+
+        void main() {
+            gl_Position = vec4(position, 0.0, 1.0);
+        }
+        */
+        const VERTEX_MODULE: [u8; 912] = [
+            3, 2, 35, 7, 0, 0, 1, 0, 1, 0, 8, 0, 27, 0, 0, 0, 0, 0, 0, 0, 17, 0, 2, 0, 1, 0, 0, 0,
+            17, 0, 2, 0, 32, 0, 0, 0, 17, 0, 2, 0, 33, 0, 0, 0, 11, 0, 6, 0, 1, 0, 0, 0, 71, 76,
+            83, 76, 46, 115, 116, 100, 46, 52, 53, 48, 0, 0, 0, 0, 14, 0, 3, 0, 0, 0, 0, 0, 1, 0,
+            0, 0, 15, 0, 7, 0, 0, 0, 0, 0, 4, 0, 0, 0, 109, 97, 105, 110, 0, 0, 0, 0, 13, 0, 0, 0,
+            18, 0, 0, 0, 3, 0, 3, 0, 2, 0, 0, 0, 194, 1, 0, 0, 4, 0, 9, 0, 71, 76, 95, 65, 82, 66,
+            95, 115, 101, 112, 97, 114, 97, 116, 101, 95, 115, 104, 97, 100, 101, 114, 95, 111, 98,
+            106, 101, 99, 116, 115, 0, 0, 4, 0, 9, 0, 71, 76, 95, 65, 82, 66, 95, 115, 104, 97,
+            100, 105, 110, 103, 95, 108, 97, 110, 103, 117, 97, 103, 101, 95, 52, 50, 48, 112, 97,
+            99, 107, 0, 5, 0, 4, 0, 4, 0, 0, 0, 109, 97, 105, 110, 0, 0, 0, 0, 5, 0, 6, 0, 11, 0,
+            0, 0, 103, 108, 95, 80, 101, 114, 86, 101, 114, 116, 101, 120, 0, 0, 0, 0, 6, 0, 6, 0,
+            11, 0, 0, 0, 0, 0, 0, 0, 103, 108, 95, 80, 111, 115, 105, 116, 105, 111, 110, 0, 6, 0,
+            7, 0, 11, 0, 0, 0, 1, 0, 0, 0, 103, 108, 95, 80, 111, 105, 110, 116, 83, 105, 122, 101,
+            0, 0, 0, 0, 6, 0, 7, 0, 11, 0, 0, 0, 2, 0, 0, 0, 103, 108, 95, 67, 108, 105, 112, 68,
+            105, 115, 116, 97, 110, 99, 101, 0, 6, 0, 7, 0, 11, 0, 0, 0, 3, 0, 0, 0, 103, 108, 95,
+            67, 117, 108, 108, 68, 105, 115, 116, 97, 110, 99, 101, 0, 5, 0, 3, 0, 13, 0, 0, 0, 0,
+            0, 0, 0, 5, 0, 5, 0, 18, 0, 0, 0, 112, 111, 115, 105, 116, 105, 111, 110, 0, 0, 0, 0,
+            72, 0, 5, 0, 11, 0, 0, 0, 0, 0, 0, 0, 11, 0, 0, 0, 0, 0, 0, 0, 72, 0, 5, 0, 11, 0, 0,
+            0, 1, 0, 0, 0, 11, 0, 0, 0, 1, 0, 0, 0, 72, 0, 5, 0, 11, 0, 0, 0, 2, 0, 0, 0, 11, 0, 0,
+            0, 3, 0, 0, 0, 72, 0, 5, 0, 11, 0, 0, 0, 3, 0, 0, 0, 11, 0, 0, 0, 4, 0, 0, 0, 71, 0, 3,
+            0, 11, 0, 0, 0, 2, 0, 0, 0, 71, 0, 4, 0, 18, 0, 0, 0, 30, 0, 0, 0, 0, 0, 0, 0, 19, 0,
+            2, 0, 2, 0, 0, 0, 33, 0, 3, 0, 3, 0, 0, 0, 2, 0, 0, 0, 22, 0, 3, 0, 6, 0, 0, 0, 32, 0,
+            0, 0, 23, 0, 4, 0, 7, 0, 0, 0, 6, 0, 0, 0, 4, 0, 0, 0, 21, 0, 4, 0, 8, 0, 0, 0, 32, 0,
+            0, 0, 0, 0, 0, 0, 43, 0, 4, 0, 8, 0, 0, 0, 9, 0, 0, 0, 1, 0, 0, 0, 28, 0, 4, 0, 10, 0,
+            0, 0, 6, 0, 0, 0, 9, 0, 0, 0, 30, 0, 6, 0, 11, 0, 0, 0, 7, 0, 0, 0, 6, 0, 0, 0, 10, 0,
+            0, 0, 10, 0, 0, 0, 32, 0, 4, 0, 12, 0, 0, 0, 3, 0, 0, 0, 11, 0, 0, 0, 59, 0, 4, 0, 12,
+            0, 0, 0, 13, 0, 0, 0, 3, 0, 0, 0, 21, 0, 4, 0, 14, 0, 0, 0, 32, 0, 0, 0, 1, 0, 0, 0,
+            43, 0, 4, 0, 14, 0, 0, 0, 15, 0, 0, 0, 0, 0, 0, 0, 23, 0, 4, 0, 16, 0, 0, 0, 6, 0, 0,
+            0, 2, 0, 0, 0, 32, 0, 4, 0, 17, 0, 0, 0, 1, 0, 0, 0, 16, 0, 0, 0, 59, 0, 4, 0, 17, 0,
+            0, 0, 18, 0, 0, 0, 1, 0, 0, 0, 43, 0, 4, 0, 6, 0, 0, 0, 20, 0, 0, 0, 0, 0, 0, 0, 43, 0,
+            4, 0, 6, 0, 0, 0, 21, 0, 0, 0, 0, 0, 128, 63, 32, 0, 4, 0, 25, 0, 0, 0, 3, 0, 0, 0, 7,
+            0, 0, 0, 54, 0, 5, 0, 2, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 248, 0, 2, 0, 5,
+            0, 0, 0, 61, 0, 4, 0, 16, 0, 0, 0, 19, 0, 0, 0, 18, 0, 0, 0, 81, 0, 5, 0, 6, 0, 0, 0,
+            22, 0, 0, 0, 19, 0, 0, 0, 0, 0, 0, 0, 81, 0, 5, 0, 6, 0, 0, 0, 23, 0, 0, 0, 19, 0, 0,
+            0, 1, 0, 0, 0, 80, 0, 7, 0, 7, 0, 0, 0, 24, 0, 0, 0, 22, 0, 0, 0, 23, 0, 0, 0, 20, 0,
+            0, 0, 21, 0, 0, 0, 65, 0, 5, 0, 25, 0, 0, 0, 26, 0, 0, 0, 13, 0, 0, 0, 15, 0, 0, 0, 62,
+            0, 3, 0, 26, 0, 0, 0, 24, 0, 0, 0, 253, 0, 1, 0, 56, 0, 1, 0,
+        ];
+        /*
+        #version 450
+
+        #extension GL_ARB_separate_shader_objects : enable
+        #extension GL_ARB_shading_language_420pack : enable
+
+        layout(location = 0) out vec4 f_color;
+
+        void main() {
+            f_color = vec4(1.0, 0.0, 0.0, 1.0);
+        }
+        */
+        const FRAGMENT_MODULE: [u8; 420] = [
+            3, 2, 35, 7, 0, 0, 1, 0, 1, 0, 8, 0, 13, 0, 0, 0, 0, 0, 0, 0, 17, 0, 2, 0, 1, 0, 0, 0,
+            11, 0, 6, 0, 1, 0, 0, 0, 71, 76, 83, 76, 46, 115, 116, 100, 46, 52, 53, 48, 0, 0, 0, 0,
+            14, 0, 3, 0, 0, 0, 0, 0, 1, 0, 0, 0, 15, 0, 6, 0, 4, 0, 0, 0, 4, 0, 0, 0, 109, 97, 105,
+            110, 0, 0, 0, 0, 9, 0, 0, 0, 16, 0, 3, 0, 4, 0, 0, 0, 7, 0, 0, 0, 3, 0, 3, 0, 2, 0, 0,
+            0, 194, 1, 0, 0, 4, 0, 9, 0, 71, 76, 95, 65, 82, 66, 95, 115, 101, 112, 97, 114, 97,
+            116, 101, 95, 115, 104, 97, 100, 101, 114, 95, 111, 98, 106, 101, 99, 116, 115, 0, 0,
+            4, 0, 9, 0, 71, 76, 95, 65, 82, 66, 95, 115, 104, 97, 100, 105, 110, 103, 95, 108, 97,
+            110, 103, 117, 97, 103, 101, 95, 52, 50, 48, 112, 97, 99, 107, 0, 5, 0, 4, 0, 4, 0, 0,
+            0, 109, 97, 105, 110, 0, 0, 0, 0, 5, 0, 4, 0, 9, 0, 0, 0, 102, 95, 99, 111, 108, 111,
+            114, 0, 71, 0, 4, 0, 9, 0, 0, 0, 30, 0, 0, 0, 0, 0, 0, 0, 19, 0, 2, 0, 2, 0, 0, 0, 33,
+            0, 3, 0, 3, 0, 0, 0, 2, 0, 0, 0, 22, 0, 3, 0, 6, 0, 0, 0, 32, 0, 0, 0, 23, 0, 4, 0, 7,
+            0, 0, 0, 6, 0, 0, 0, 4, 0, 0, 0, 32, 0, 4, 0, 8, 0, 0, 0, 3, 0, 0, 0, 7, 0, 0, 0, 59,
+            0, 4, 0, 8, 0, 0, 0, 9, 0, 0, 0, 3, 0, 0, 0, 43, 0, 4, 0, 6, 0, 0, 0, 10, 0, 0, 0, 0,
+            0, 128, 63, 43, 0, 4, 0, 6, 0, 0, 0, 11, 0, 0, 0, 0, 0, 0, 0, 44, 0, 7, 0, 7, 0, 0, 0,
+            12, 0, 0, 0, 10, 0, 0, 0, 11, 0, 0, 0, 11, 0, 0, 0, 10, 0, 0, 0, 54, 0, 5, 0, 2, 0, 0,
+            0, 4, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 248, 0, 2, 0, 5, 0, 0, 0, 62, 0, 3, 0, 9, 0, 0,
+            0, 12, 0, 0, 0, 253, 0, 1, 0, 56, 0, 1, 0,
+        ];
+
+        let vertex_module =
+            unsafe { ShaderModule::from_bytes(device.clone(), &VERTEX_MODULE).unwrap() };
+        let fragment_module =
+            unsafe { ShaderModule::from_bytes(device.clone(), &FRAGMENT_MODULE).unwrap() };
+        let render_pass = crate::single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: DontCare,
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        )
+        .unwrap();
+        let pipeline = GraphicsPipeline::start()
+            .vertex_input_state_dynamic()
+            .vertex_shader(vertex_module.entry_point("main").unwrap(), ())
+            .viewport_state(ViewportState::viewport_dynamic_scissor_irrelevant())
+            .fragment_shader(fragment_module.entry_point("main").unwrap(), ())
+            .render_pass(Subpass::from(render_pass.clone(), 0).unwrap())
+            .build(device.clone())
+            .unwrap();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let image =
+            AttachmentImage::new(&memory_allocator, [1, 1], Format::R8G8B8A8_UNORM).unwrap();
+        let framebuffer = Framebuffer::new(
+            render_pass,
+            FramebufferCreateInfo {
+                attachments: vec![ImageView::new_default(image).unwrap()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        builder
+            .begin_render_pass(
+                RenderPassBeginInfo {
+                    clear_values: vec![Some([0.0; 4].into())],
+                    ..RenderPassBeginInfo::framebuffer(framebuffer)
+                },
+                SubpassContents::Inline,
+            )
+            .unwrap()
+            .bind_pipeline_graphics(pipeline)
+            .set_viewport(
+                0,
+                [Viewport {
+                    origin: [0.0, 0.0],
+                    dimensions: [1.0, 1.0],
+                    depth_range: 0.0..1.0,
+                }],
+            )
+            .set_vertex_input(vertex_input_state);
+
+        builder.draw(3, 1, 0, 0).map(|_| ())
+    }
+
+    #[test]
+    fn draw_vertex_input_attribute_missing() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [ext_vertex_input_dynamic_state],
+            vertex_input_dynamic_state,
+            shader_clip_distance,
+            shader_cull_distance
+        );
+
+        match draw_with_vertex_input(device, queue, VertexInputState::new()) {
+            Err(PipelineExecutionError::VertexInputAttributeMissing { location: 0 }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn draw_vertex_input_attribute_incompatible_format() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [ext_vertex_input_dynamic_state],
+            vertex_input_dynamic_state,
+            shader_clip_distance,
+            shader_cull_distance
+        );
+
+        let vertex_input_state = VertexInputState::new()
+            .binding(
+                0,
+                VertexInputBindingDescription {
+                    stride: 8,
+                    input_rate: VertexInputRate::Vertex,
+                },
+            )
+            .attribute(
+                0,
+                VertexInputAttributeDescription {
+                    binding: 0,
+                    format: Format::R32G32_UINT,
+                    offset: 0,
+                },
+            );
+
+        match draw_with_vertex_input(device, queue, vertex_input_state) {
+            Err(PipelineExecutionError::VertexInputAttributeIncompatibleFormat {
+                location: 0,
+                shader_type: NumericType::SFLOAT,
+                attribute_type: NumericType::UINT,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+}
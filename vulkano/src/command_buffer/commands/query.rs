@@ -143,6 +143,30 @@ where
                     return Err(QueryError::InvalidFlags);
                 }
             }
+            QueryType::TransformFeedbackStream => {
+                // VUID-vkCmdBeginQuery-queryType-02327
+                if !queue_family_properties
+                    .queue_flags
+                    .intersects(QueueFlags::GRAPHICS)
+                {
+                    return Err(QueryError::NotSupportedByQueueFamily);
+                }
+
+                // VUID-vkCmdBeginQuery-queryType-02328
+                if !device
+                    .physical_device()
+                    .properties()
+                    .transform_feedback_queries
+                    .unwrap_or(false)
+                {
+                    return Err(QueryError::TransformFeedbackQueriesNotSupported);
+                }
+
+                // VUID-vkCmdBeginQuery-queryType-00800
+                if flags.intersects(QueryControlFlags::PRECISE) {
+                    return Err(QueryError::InvalidFlags);
+                }
+            }
             // VUID-vkCmdBeginQuery-queryType-02804
             QueryType::Timestamp => return Err(QueryError::NotPermitted),
         }
@@ -968,6 +992,9 @@ pub enum QueryError {
 
     /// The provided stage is not supported by the queue family.
     StageNotSupported,
+
+    /// The `transform_feedback_queries` property of the physical device is not supported.
+    TransformFeedbackQueriesNotSupported,
 }
 
 impl Error for QueryError {}
@@ -1019,6 +1046,11 @@ impl Display for QueryError {
             Self::StageNotSupported => {
                 write!(f, "the provided stage is not supported by the queue family")
             }
+            Self::TransformFeedbackQueriesNotSupported => write!(
+                f,
+                "the `transform_feedback_queries` property of the physical device is not \
+                supported",
+            ),
         }
     }
 }
@@ -16,7 +16,8 @@ use crate::{
         },
         synced::{Command, Resource, SyncCommandBufferBuilder, SyncCommandBufferBuilderError},
         sys::UnsafeCommandBufferBuilder,
-        AutoCommandBufferBuilder, ResourceInCommand, ResourceUseRef, SubpassContents,
+        AutoCommandBufferBuilder, RenderingFlags, ResourceInCommand, ResourceUseRef,
+        SubpassContents,
     },
     device::{DeviceOwned, QueueFlags},
     format::{ClearColorValue, ClearValue, Format, NumericType},
@@ -85,6 +86,7 @@ where
             self.inner
                 .begin_render_pass(render_pass_begin_info, contents)?;
             self.render_pass_state = Some(render_pass_state);
+            self.resumes_render_pass.get_or_insert(false);
 
             Ok(self)
         }
@@ -115,6 +117,12 @@ where
             return Err(RenderPassError::ForbiddenInsideRenderPass);
         }
 
+        // VUID-VkSubmitInfo-pCommandBuffers-06017
+        // VUID-VkSubmitInfo2-commandBuffer-06012
+        if self.suspends_render_pass {
+            return Err(RenderPassError::RenderPassSuspended);
+        }
+
         let RenderPassBeginInfo {
             render_pass,
             framebuffer,
@@ -470,6 +478,20 @@ where
             return Err(RenderPassError::QueryIsActive);
         }
 
+        // VUID-vkCmdEndConditionalRenderingEXT-None-01987
+        if self
+            .conditional_rendering_state
+            .as_ref()
+            .map_or(false, |state| state.in_subpass)
+        {
+            return Err(RenderPassError::ConditionalRenderingActive);
+        }
+
+        // VUID-vkCmdNextSubpass2-None-02350
+        if self.state().transform_feedback_active() {
+            return Err(RenderPassError::TransformFeedbackActive);
+        }
+
         // VUID-vkCmdNextSubpass2-commandBuffer-cmdpool
         debug_assert!(self
             .queue_family_properties()
@@ -528,6 +550,20 @@ where
             return Err(RenderPassError::QueryIsActive);
         }
 
+        // VUID-vkCmdEndConditionalRenderingEXT-None-01987
+        if self
+            .conditional_rendering_state
+            .as_ref()
+            .map_or(false, |state| state.in_subpass)
+        {
+            return Err(RenderPassError::ConditionalRenderingActive);
+        }
+
+        // VUID-vkCmdEndRenderPass2-None-02352
+        if self.state().transform_feedback_active() {
+            return Err(RenderPassError::TransformFeedbackActive);
+        }
+
         // VUID-vkCmdEndRenderPass2-commandBuffer-cmdpool
         debug_assert!(self
             .queue_family_properties()
@@ -565,6 +601,7 @@ where
                 ref depth_attachment,
                 ref stencil_attachment,
                 contents,
+                flags,
                 _ne: _,
             } = rendering_info;
 
@@ -589,6 +626,7 @@ where
                         .as_ref()
                         .map(|a| a.image_view.format().unwrap()),
                     pipeline_used: false,
+                    suspending: flags.intersects(RenderingFlags::SUSPENDING),
                 }
                 .into(),
                 view_mask,
@@ -597,6 +635,8 @@ where
             self.inner.begin_rendering(rendering_info)?;
 
             self.render_pass_state = Some(render_pass_state);
+            self.resumes_render_pass
+                .get_or_insert(flags.intersects(RenderingFlags::RESUMING));
         }
 
         Ok(self)
@@ -644,11 +684,25 @@ where
             ref depth_attachment,
             ref stencil_attachment,
             contents,
+            flags,
             _ne: _,
         } = rendering_info;
 
         // VUID-VkRenderingInfo-flags-parameter
         contents.validate_device(device)?;
+        flags.validate_device(device)?;
+
+        // VUID-VkSubmitInfo-pCommandBuffers-06017
+        // VUID-VkSubmitInfo2-commandBuffer-06012
+        if self.suspends_render_pass {
+            return Err(RenderPassError::RenderPassSuspended);
+        }
+
+        // VUID-VkSubmitInfo-pCommandBuffers-06193
+        // VUID-VkSubmitInfo2-commandBuffer-06192
+        if flags.intersects(RenderingFlags::RESUMING) && self.resumes_render_pass.is_some() {
+            return Err(RenderPassError::ResumingNotFirstRenderPass);
+        }
 
         // VUID-vkCmdBeginRendering-commandBuffer-06068
         if self.inheritance_info.is_some() && contents == SubpassContents::SecondaryCommandBuffers {
@@ -1166,6 +1220,13 @@ where
 
         unsafe {
             self.inner.end_rendering();
+
+            if let RenderPassStateType::BeginRendering(state) =
+                &self.render_pass_state.as_ref().unwrap().render_pass
+            {
+                self.suspends_render_pass = state.suspending;
+            }
+
             self.render_pass_state = None;
         }
 
@@ -1184,6 +1245,20 @@ where
             return Err(RenderPassError::ForbiddenWithInheritedRenderPass);
         }
 
+        // VUID-vkCmdEndConditionalRenderingEXT-None-01987
+        if self
+            .conditional_rendering_state
+            .as_ref()
+            .map_or(false, |state| state.in_subpass)
+        {
+            return Err(RenderPassError::ConditionalRenderingActive);
+        }
+
+        // VUID-vkCmdEndRendering-None-06781
+        if self.state().transform_feedback_active() {
+            return Err(RenderPassError::TransformFeedbackActive);
+        }
+
         // VUID-vkCmdEndRendering-None-06161
         // VUID-vkCmdEndRendering-commandBuffer-06162
         match &render_pass_state.render_pass {
@@ -1614,6 +1689,7 @@ impl SyncCommandBufferBuilder {
             depth_attachment,
             stencil_attachment,
             contents: _,
+            flags: _,
             _ne,
         } = &rendering_info;
 
@@ -2032,6 +2108,7 @@ impl UnsafeCommandBufferBuilder {
             ref depth_attachment,
             ref stencil_attachment,
             contents,
+            flags,
             _ne: _,
         } = rendering_info;
 
@@ -2089,7 +2166,7 @@ impl UnsafeCommandBufferBuilder {
         let stencil_attachment = map_attachment_info(stencil_attachment);
 
         let rendering_info = ash::vk::RenderingInfo {
-            flags: contents.into(),
+            flags: ash::vk::RenderingFlags::from(contents) | flags.into(),
             render_area: ash::vk::Rect2D {
                 offset: ash::vk::Offset2D {
                     x: render_area_offset[0] as i32,
@@ -2308,6 +2385,19 @@ pub struct RenderingInfo {
     /// The default value is [`SubpassContents::Inline`].
     pub contents: SubpassContents,
 
+    /// Specifies whether the render pass instance is suspended when it is ended, and whether it
+    /// resumes a previously suspended render pass instance.
+    ///
+    /// Suspending and resuming allows a single render pass instance to be recorded across
+    /// multiple primary command buffers. A command buffer whose last render pass instance is
+    /// suspended must be followed, in the same submission, by a command buffer whose first render
+    /// pass instance resumes it. The resuming render pass instance must have the same parameters
+    /// as the suspended one, other than `contents` and `flags`, and no action or synchronization
+    /// commands may be executed between the two.
+    ///
+    /// The default value is empty.
+    pub flags: RenderingFlags,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -2323,6 +2413,7 @@ impl Default for RenderingInfo {
             depth_attachment: None,
             stencil_attachment: None,
             contents: SubpassContents::Inline,
+            flags: RenderingFlags::empty(),
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -2339,6 +2430,7 @@ impl RenderingInfo {
             ref depth_attachment,
             ref stencil_attachment,
             contents: _,
+            flags: _,
             _ne: _,
         } = self;
 
@@ -2788,6 +2880,12 @@ pub enum RenderPassError {
     /// A query is active that conflicts with the current operation.
     QueryIsActive,
 
+    /// Conditional rendering was begun inside the current subpass, and must be ended first.
+    ConditionalRenderingActive,
+
+    /// Transform feedback is active, and must be ended first.
+    TransformFeedbackActive,
+
     /// A clear rectangle's `array_layers` is empty.
     RectArrayLayersEmpty {
         rect_index: usize,
@@ -2812,6 +2910,14 @@ pub enum RenderPassError {
     /// The render area's `offset` and `extent` are outside the extent of the framebuffer.
     RenderAreaOutOfBounds,
 
+    /// A render pass instance was suspended earlier in the command buffer, so no other render
+    /// pass instance can be begun until it is resumed in the next command buffer.
+    RenderPassSuspended,
+
+    /// `RenderingFlags::RESUMING` was set, but the render pass instance is not the first render
+    /// pass instance in the command buffer.
+    ResumingNotFirstRenderPass,
+
     /// The stencil attachment has a format that does not support that usage.
     StencilAttachmentFormatUsageNotSupported,
 
@@ -3082,6 +3188,14 @@ impl Display for RenderPassError {
                 f,
                 "a query is active that conflicts with the current operation",
             ),
+            Self::ConditionalRenderingActive => write!(
+                f,
+                "conditional rendering was begun inside the current subpass, and must be ended \
+                first",
+            ),
+            Self::TransformFeedbackActive => {
+                write!(f, "transform feedback is active, and must be ended first",)
+            }
             Self::RectArrayLayersEmpty { rect_index } => write!(
                 f,
                 "clear rectangle index {} `array_layers` is empty",
@@ -3106,6 +3220,16 @@ impl Display for RenderPassError {
                 f,
                 "the render area's `offset` and `extent` are outside the extent of the framebuffer",
             ),
+            Self::RenderPassSuspended => write!(
+                f,
+                "a render pass instance was suspended earlier in the command buffer, so no other \
+                render pass instance can be begun until it is resumed in the next command buffer",
+            ),
+            Self::ResumingNotFirstRenderPass => write!(
+                f,
+                "`RenderingFlags::RESUMING` was set, but the render pass instance is not the first \
+                render pass instance in the command buffer",
+            ),
             Self::StencilAttachmentFormatUsageNotSupported => write!(
                 f,
                 "the stencil attachment has a format that does not support that usage",
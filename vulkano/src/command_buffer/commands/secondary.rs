@@ -128,9 +128,22 @@ where
             return Err(ExecuteCommandsError::NotSupportedByQueueFamily);
         }
 
+        // VUID-vkCmdExecuteCommands-None-02286
+        if self.state().transform_feedback_active() {
+            return Err(ExecuteCommandsError::TransformFeedbackActive);
+        }
+
         // TODO:
         // VUID-vkCmdExecuteCommands-pCommandBuffers-00094
 
+        // VUID-vkCmdExecuteCommands-commandBuffer-01820
+        // VUID-vkCmdExecuteCommands-commandBuffer-01821
+        if command_buffer.protected() != self.protected() {
+            return Err(ExecuteCommandsError::ProtectedMismatch {
+                command_buffer_index,
+            });
+        }
+
         if let Some(render_pass_state) = &self.render_pass_state {
             // VUID-vkCmdExecuteCommands-contents-06018
             // VUID-vkCmdExecuteCommands-flags-06024
@@ -374,10 +387,21 @@ where
                         );
                     }
                 }
-                QueryType::Timestamp => (),
+                QueryType::Timestamp | QueryType::TransformFeedbackStream => (),
             }
         }
 
+        // VUID?
+        if self.conditional_rendering_state.is_some()
+            && !command_buffer.inheritance_info().conditional_rendering
+        {
+            return Err(
+                ExecuteCommandsError::ConditionalRenderingInheritanceRequired {
+                    command_buffer_index,
+                },
+            );
+        }
+
         // TODO:
         // VUID-vkCmdExecuteCommands-pCommandBuffers-00091
         // VUID-vkCmdExecuteCommands-pCommandBuffers-00092
@@ -600,6 +624,12 @@ pub enum ExecuteCommandsError {
         requires_one_of: RequiresOneOf,
     },
 
+    /// Conditional rendering is active, but a command buffer does not have conditional rendering
+    /// inheritance enabled.
+    ConditionalRenderingInheritanceRequired {
+        command_buffer_index: u32,
+    },
+
     /// Operation forbidden inside a render subpass with the specified contents.
     ForbiddenWithSubpassContents {
         contents: SubpassContents,
@@ -622,6 +652,11 @@ pub enum ExecuteCommandsError {
         inherited_flags: QueryControlFlags,
     },
 
+    /// A command buffer is protected while the primary command buffer is not, or vice versa.
+    ProtectedMismatch {
+        command_buffer_index: u32,
+    },
+
     /// The inherited pipeline statistics query flags of a command buffer are not a superset of the
     /// currently active flags.
     PipelineStatisticsQueryFlagsNotSuperset {
@@ -727,6 +762,9 @@ pub enum ExecuteCommandsError {
         required_view_mask: u32,
         inherited_view_mask: u32,
     },
+
+    /// Transform feedback is active.
+    TransformFeedbackActive,
 }
 
 impl Error for ExecuteCommandsError {
@@ -760,6 +798,14 @@ impl Display for ExecuteCommandsError {
             Self::NotSupportedByQueueFamily => {
                 write!(f, "the queue family doesn't allow this operation")
             }
+            Self::ConditionalRenderingInheritanceRequired {
+                command_buffer_index,
+            } => write!(
+                f,
+                "conditional rendering is active, but command buffer {} does not have conditional \
+                rendering inheritance enabled",
+                command_buffer_index,
+            ),
             Self::OcclusionQueryInheritanceRequired {
                 command_buffer_index,
             } => write!(
@@ -778,6 +824,13 @@ impl Display for ExecuteCommandsError {
                 superset of the currently active flags ({:?})",
                 inherited_flags, command_buffer_index, required_flags,
             ),
+            Self::ProtectedMismatch {
+                command_buffer_index,
+            } => write!(
+                f,
+                "command buffer {} does not have the same protection as the primary command buffer",
+                command_buffer_index,
+            ),
             Self::PipelineStatisticsQueryFlagsNotSuperset {
                 command_buffer_index,
                 required_flags,
@@ -919,6 +972,7 @@ impl Display for ExecuteCommandsError {
                 mask ({})",
                 inherited_view_mask, command_buffer_index, required_view_mask,
             ),
+            Self::TransformFeedbackActive => write!(f, "transform feedback is active"),
         }
     }
 }
@@ -0,0 +1,813 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{
+    buffer::{BufferAccess, BufferUsage},
+    command_buffer::{
+        allocator::CommandBufferAllocator,
+        synced::{Command, Resource, SyncCommandBufferBuilder, SyncCommandBufferBuilderError},
+        sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder, ResourceInCommand, ResourceUseRef,
+    },
+    device::{DeviceOwned, QueueFlags},
+    sync::{AccessFlags, PipelineMemoryAccess, PipelineStages},
+    DeviceSize, RequiresOneOf, VulkanObject,
+};
+use smallvec::SmallVec;
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    mem::size_of,
+    sync::Arc,
+};
+
+/// # Commands for transform feedback.
+///
+/// These commands require the [`transform_feedback`] feature to be enabled on the device.
+///
+/// [`transform_feedback`]: crate::device::Features::transform_feedback
+impl<L, A> AutoCommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Binds transform feedback buffers for future transform feedback operations.
+    ///
+    /// While transform feedback is active, the vertex data that is output by the last
+    /// pre-rasterization shader stage of the bound graphics pipeline is written to the buffers
+    /// bound with this command, as described by the [`transform_feedback_outputs`] of the
+    /// pipeline. Each buffer is written from its start up to its end.
+    ///
+    /// [`transform_feedback_outputs`]: crate::pipeline::GraphicsPipeline::transform_feedback_outputs
+    pub fn bind_transform_feedback_buffers(
+        &mut self,
+        first_binding: u32,
+        buffers: impl IntoIterator<Item = Arc<dyn BufferAccess>>,
+    ) -> Result<&mut Self, TransformFeedbackError> {
+        let buffers: SmallVec<[_; 4]> = buffers.into_iter().collect();
+        self.validate_bind_transform_feedback_buffers(first_binding, &buffers)?;
+
+        unsafe {
+            self.inner
+                .bind_transform_feedback_buffers(first_binding, buffers);
+        }
+
+        Ok(self)
+    }
+
+    fn validate_bind_transform_feedback_buffers(
+        &self,
+        first_binding: u32,
+        buffers: &[Arc<dyn BufferAccess>],
+    ) -> Result<(), TransformFeedbackError> {
+        let device = self.device();
+
+        // VUID-vkCmdBindTransformFeedbackBuffersEXT-transformFeedback-02355
+        if !device.enabled_features().transform_feedback {
+            return Err(TransformFeedbackError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::bind_transform_feedback_buffers`",
+                requires_one_of: RequiresOneOf {
+                    features: &["transform_feedback"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdBindTransformFeedbackBuffersEXT-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(TransformFeedbackError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdBindTransformFeedbackBuffersEXT-None-02365
+        if self.state().transform_feedback_active() {
+            return Err(TransformFeedbackError::TransformFeedbackActive);
+        }
+
+        let properties = device.physical_device().properties();
+        let max_buffers = properties.max_transform_feedback_buffers.unwrap_or(0);
+
+        // VUID-vkCmdBindTransformFeedbackBuffersEXT-firstBinding-02356
+        // VUID-vkCmdBindTransformFeedbackBuffersEXT-firstBinding-02357
+        if first_binding + buffers.len() as u32 > max_buffers {
+            return Err(
+                TransformFeedbackError::MaxTransformFeedbackBuffersExceeded {
+                    binding_count: first_binding + buffers.len() as u32,
+                    max: max_buffers,
+                },
+            );
+        }
+
+        for (index, buffer) in buffers.iter().enumerate() {
+            let index = index as u32;
+
+            // VUID-vkCmdBindTransformFeedbackBuffersEXT-commonparent
+            assert_eq!(device, buffer.device());
+
+            // VUID-vkCmdBindTransformFeedbackBuffersEXT-pBuffers-02360
+            if !buffer
+                .usage()
+                .intersects(BufferUsage::TRANSFORM_FEEDBACK_BUFFER)
+            {
+                return Err(TransformFeedbackError::BufferMissingUsage { index });
+            }
+
+            // VUID-vkCmdBindTransformFeedbackBuffersEXT-pOffsets-02359
+            let offset = buffer.inner().offset;
+
+            if offset % 4 != 0 {
+                return Err(TransformFeedbackError::BufferOffsetNotAligned { index, offset });
+            }
+
+            // VUID-vkCmdBindTransformFeedbackBuffersEXT-pSize-02361
+            let max_size = properties.max_transform_feedback_buffer_size.unwrap_or(0);
+
+            if buffer.size() > max_size {
+                return Err(
+                    TransformFeedbackError::MaxTransformFeedbackBufferSizeExceeded {
+                        index,
+                        size: buffer.size(),
+                        max: max_size,
+                    },
+                );
+            }
+
+            // VUID-vkCmdBindTransformFeedbackBuffersEXT-pOffsets-02358
+            // VUID-vkCmdBindTransformFeedbackBuffersEXT-pSizes-02362
+            // Ensured by the `BufferAccess` trait.
+        }
+
+        Ok(())
+    }
+
+    /// Begins transform feedback.
+    ///
+    /// While transform feedback is active, draw commands write the vertex data that is output by
+    /// the bound graphics pipeline to the buffers bound with
+    /// [`bind_transform_feedback_buffers`](Self::bind_transform_feedback_buffers). The bound
+    /// graphics pipeline and transform feedback buffers can not be changed until transform
+    /// feedback is ended again.
+    ///
+    /// Each element of `counter_buffers` corresponds to a transform feedback buffer binding,
+    /// starting at `first_counter_buffer`. If an element is `Some`, the 32-bit value at the start
+    /// of the counter buffer holds the byte offset in the transform feedback buffer at which
+    /// writing is resumed, as it was written by an earlier call to
+    /// [`end_transform_feedback`](Self::end_transform_feedback). If an element is `None`, or if
+    /// `counter_buffers` is empty, writing starts at the beginning of the buffer.
+    ///
+    /// Transform feedback must be begun and ended inside the same subpass.
+    pub fn begin_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: impl IntoIterator<Item = Option<Arc<dyn BufferAccess>>>,
+    ) -> Result<&mut Self, TransformFeedbackError> {
+        let counter_buffers: SmallVec<[_; 4]> = counter_buffers.into_iter().collect();
+        self.validate_begin_transform_feedback(first_counter_buffer, &counter_buffers)?;
+
+        unsafe {
+            self.inner
+                .begin_transform_feedback(first_counter_buffer, counter_buffers)?;
+        }
+
+        Ok(self)
+    }
+
+    fn validate_begin_transform_feedback(
+        &self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Arc<dyn BufferAccess>>],
+    ) -> Result<(), TransformFeedbackError> {
+        let device = self.device();
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-transformFeedback-02366
+        if !device.enabled_features().transform_feedback {
+            return Err(TransformFeedbackError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::begin_transform_feedback`",
+                requires_one_of: RequiresOneOf {
+                    features: &["transform_feedback"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(TransformFeedbackError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-renderpass
+        let render_pass_state = self
+            .render_pass_state
+            .as_ref()
+            .ok_or(TransformFeedbackError::ForbiddenOutsideRenderPass)?;
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-None-02373
+        if render_pass_state.view_mask != 0 {
+            return Err(TransformFeedbackError::MultiviewEnabled);
+        }
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-None-02367
+        if self.state().transform_feedback_active() {
+            return Err(TransformFeedbackError::TransformFeedbackActive);
+        }
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-None-06233
+        let pipeline = self
+            .state()
+            .pipeline_graphics()
+            .ok_or(TransformFeedbackError::PipelineNotBound)?;
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-None-04128
+        let outputs = pipeline
+            .transform_feedback_outputs()
+            .ok_or(TransformFeedbackError::PipelineTransformFeedbackNotDeclared)?;
+
+        for output in outputs {
+            // VUID?
+            if self
+                .state()
+                .transform_feedback_buffer(output.buffer)
+                .is_none()
+            {
+                return Err(TransformFeedbackError::BufferNotBound {
+                    binding: output.buffer,
+                });
+            }
+        }
+
+        self.validate_transform_feedback_counter_buffers(first_counter_buffer, counter_buffers)?;
+
+        Ok(())
+    }
+
+    /// Ends transform feedback, that was previously begun with
+    /// [`begin_transform_feedback`](Self::begin_transform_feedback).
+    ///
+    /// Each element of `counter_buffers` corresponds to a transform feedback buffer binding,
+    /// starting at `first_counter_buffer`. If an element is `Some`, the byte offset in the
+    /// transform feedback buffer just past the last written vertex is stored as a 32-bit value at
+    /// the start of the counter buffer. This value can be used to resume transform feedback later,
+    /// or to draw the captured vertices with
+    /// [`draw_indirect_byte_count`](Self::draw_indirect_byte_count).
+    pub fn end_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: impl IntoIterator<Item = Option<Arc<dyn BufferAccess>>>,
+    ) -> Result<&mut Self, TransformFeedbackError> {
+        let counter_buffers: SmallVec<[_; 4]> = counter_buffers.into_iter().collect();
+        self.validate_end_transform_feedback(first_counter_buffer, &counter_buffers)?;
+
+        unsafe {
+            self.inner
+                .end_transform_feedback(first_counter_buffer, counter_buffers)?;
+        }
+
+        Ok(self)
+    }
+
+    fn validate_end_transform_feedback(
+        &self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Arc<dyn BufferAccess>>],
+    ) -> Result<(), TransformFeedbackError> {
+        // VUID-vkCmdEndTransformFeedbackEXT-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(TransformFeedbackError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdEndTransformFeedbackEXT-renderpass
+        if self.render_pass_state.is_none() {
+            return Err(TransformFeedbackError::ForbiddenOutsideRenderPass);
+        }
+
+        // VUID-vkCmdEndTransformFeedbackEXT-None-02375
+        if !self.state().transform_feedback_active() {
+            return Err(TransformFeedbackError::TransformFeedbackNotActive);
+        }
+
+        self.validate_transform_feedback_counter_buffers(first_counter_buffer, counter_buffers)?;
+
+        Ok(())
+    }
+
+    fn validate_transform_feedback_counter_buffers(
+        &self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Arc<dyn BufferAccess>>],
+    ) -> Result<(), TransformFeedbackError> {
+        let device = self.device();
+        let max_buffers = device
+            .physical_device()
+            .properties()
+            .max_transform_feedback_buffers
+            .unwrap_or(0);
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-firstCounterBuffer-02368
+        // VUID-vkCmdBeginTransformFeedbackEXT-firstCounterBuffer-02369
+        // VUID-vkCmdEndTransformFeedbackEXT-firstCounterBuffer-02376
+        // VUID-vkCmdEndTransformFeedbackEXT-firstCounterBuffer-02377
+        if first_counter_buffer + counter_buffers.len() as u32 > max_buffers {
+            return Err(
+                TransformFeedbackError::MaxTransformFeedbackBuffersExceeded {
+                    binding_count: first_counter_buffer + counter_buffers.len() as u32,
+                    max: max_buffers,
+                },
+            );
+        }
+
+        for (index, counter_buffer) in counter_buffers.iter().enumerate() {
+            let counter_buffer = match counter_buffer {
+                Some(x) => x,
+                None => continue,
+            };
+            let index = index as u32;
+
+            // VUID-vkCmdBeginTransformFeedbackEXT-commonparent
+            // VUID-vkCmdEndTransformFeedbackEXT-commonparent
+            assert_eq!(device, counter_buffer.device());
+
+            // VUID-vkCmdBeginTransformFeedbackEXT-pCounterBuffers-02372
+            // VUID-vkCmdEndTransformFeedbackEXT-pCounterBuffers-02380
+            if !counter_buffer
+                .usage()
+                .intersects(BufferUsage::TRANSFORM_FEEDBACK_COUNTER_BUFFER)
+            {
+                return Err(TransformFeedbackError::CounterBufferMissingUsage { index });
+            }
+
+            // VUID-vkCmdBeginTransformFeedbackEXT-pCounterBufferOffsets-02370
+            // VUID-vkCmdEndTransformFeedbackEXT-pCounterBufferOffsets-02378
+            if counter_buffer.size() < size_of::<u32>() as DeviceSize {
+                return Err(TransformFeedbackError::CounterBufferTooSmall {
+                    index,
+                    buffer_size: counter_buffer.size(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl SyncCommandBufferBuilder {
+    /// Calls `vkCmdBindTransformFeedbackBuffersEXT` on the builder.
+    #[inline]
+    pub unsafe fn bind_transform_feedback_buffers(
+        &mut self,
+        first_binding: u32,
+        buffers: SmallVec<[Arc<dyn BufferAccess>; 4]>,
+    ) {
+        struct Cmd {
+            first_binding: u32,
+            buffers: SmallVec<[Arc<dyn BufferAccess>; 4]>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "bind_transform_feedback_buffers"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.bind_transform_feedback_buffers(
+                    self.first_binding,
+                    self.buffers.iter().map(|buffer| buffer.as_ref()),
+                );
+            }
+        }
+
+        for (binding, buffer) in (first_binding..).zip(buffers.iter()) {
+            self.current_state
+                .transform_feedback_buffers
+                .insert(binding, buffer.clone());
+        }
+
+        self.commands.push(Box::new(Cmd {
+            first_binding,
+            buffers,
+        }));
+    }
+
+    /// Calls `vkCmdBeginTransformFeedbackEXT` on the builder.
+    #[inline]
+    pub unsafe fn begin_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: SmallVec<[Option<Arc<dyn BufferAccess>>; 4]>,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            first_counter_buffer: u32,
+            counter_buffers: SmallVec<[Option<Arc<dyn BufferAccess>>; 4]>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "begin_transform_feedback"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.begin_transform_feedback(
+                    self.first_counter_buffer,
+                    self.counter_buffers
+                        .iter()
+                        .map(|buffer| buffer.as_ref().map(|buffer| buffer.as_ref())),
+                );
+            }
+        }
+
+        let command_index = self.commands.len();
+        let command_name = "begin_transform_feedback";
+        let resources: SmallVec<[_; 4]> = (0..)
+            .zip(counter_buffers.iter())
+            .filter_map(|(index, counter_buffer)| {
+                let counter_buffer = counter_buffer.as_ref()?;
+
+                Some((
+                    ResourceUseRef {
+                        command_index,
+                        command_name,
+                        resource_in_command: ResourceInCommand::TransformFeedbackCounterBuffer {
+                            index,
+                        },
+                        secondary_use_ref: None,
+                    },
+                    Resource::Buffer {
+                        buffer: counter_buffer.clone(),
+                        range: 0..size_of::<u32>() as DeviceSize,
+                        memory: PipelineMemoryAccess {
+                            stages: PipelineStages::TRANSFORM_FEEDBACK,
+                            access: AccessFlags::TRANSFORM_FEEDBACK_COUNTER_READ,
+                            exclusive: false,
+                        },
+                    },
+                ))
+            })
+            .collect();
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            first_counter_buffer,
+            counter_buffers,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        self.current_state.transform_feedback_active = true;
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdEndTransformFeedbackEXT` on the builder.
+    #[inline]
+    pub unsafe fn end_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: SmallVec<[Option<Arc<dyn BufferAccess>>; 4]>,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            first_counter_buffer: u32,
+            counter_buffers: SmallVec<[Option<Arc<dyn BufferAccess>>; 4]>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "end_transform_feedback"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.end_transform_feedback(
+                    self.first_counter_buffer,
+                    self.counter_buffers
+                        .iter()
+                        .map(|buffer| buffer.as_ref().map(|buffer| buffer.as_ref())),
+                );
+            }
+        }
+
+        let command_index = self.commands.len();
+        let command_name = "end_transform_feedback";
+        let resources: SmallVec<[_; 4]> = (0..)
+            .zip(counter_buffers.iter())
+            .filter_map(|(index, counter_buffer)| {
+                let counter_buffer = counter_buffer.as_ref()?;
+
+                Some((
+                    ResourceUseRef {
+                        command_index,
+                        command_name,
+                        resource_in_command: ResourceInCommand::TransformFeedbackCounterBuffer {
+                            index,
+                        },
+                        secondary_use_ref: None,
+                    },
+                    Resource::Buffer {
+                        buffer: counter_buffer.clone(),
+                        range: 0..size_of::<u32>() as DeviceSize,
+                        memory: PipelineMemoryAccess {
+                            stages: PipelineStages::TRANSFORM_FEEDBACK,
+                            access: AccessFlags::TRANSFORM_FEEDBACK_COUNTER_WRITE,
+                            exclusive: true,
+                        },
+                    },
+                ))
+            })
+            .collect();
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            first_counter_buffer,
+            counter_buffers,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        self.current_state.transform_feedback_active = false;
+
+        Ok(())
+    }
+}
+
+impl UnsafeCommandBufferBuilder {
+    /// Calls `vkCmdBindTransformFeedbackBuffersEXT` on the builder.
+    #[inline]
+    pub unsafe fn bind_transform_feedback_buffers<'a>(
+        &mut self,
+        first_binding: u32,
+        buffers: impl IntoIterator<Item = &'a dyn BufferAccess>,
+    ) {
+        let mut buffers_vk: SmallVec<[_; 4]> = SmallVec::new();
+        let mut offsets_vk: SmallVec<[_; 4]> = SmallVec::new();
+        let mut sizes_vk: SmallVec<[_; 4]> = SmallVec::new();
+
+        for buffer in buffers {
+            let inner = buffer.inner();
+            debug_assert!(inner
+                .buffer
+                .usage()
+                .intersects(BufferUsage::TRANSFORM_FEEDBACK_BUFFER));
+
+            buffers_vk.push(inner.buffer.handle());
+            offsets_vk.push(inner.offset);
+            sizes_vk.push(buffer.size());
+        }
+
+        if buffers_vk.is_empty() {
+            return;
+        }
+
+        let fns = self.device.fns();
+        (fns.ext_transform_feedback
+            .cmd_bind_transform_feedback_buffers_ext)(
+            self.handle,
+            first_binding,
+            buffers_vk.len() as u32,
+            buffers_vk.as_ptr(),
+            offsets_vk.as_ptr(),
+            sizes_vk.as_ptr(),
+        );
+    }
+
+    /// Calls `vkCmdBeginTransformFeedbackEXT` on the builder.
+    #[inline]
+    pub unsafe fn begin_transform_feedback<'a>(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: impl IntoIterator<Item = Option<&'a dyn BufferAccess>>,
+    ) {
+        let (counter_buffers_vk, counter_buffer_offsets_vk) =
+            Self::transform_feedback_counter_buffers_vk(counter_buffers);
+
+        let fns = self.device.fns();
+        (fns.ext_transform_feedback.cmd_begin_transform_feedback_ext)(
+            self.handle,
+            first_counter_buffer,
+            counter_buffers_vk.len() as u32,
+            counter_buffers_vk.as_ptr(),
+            counter_buffer_offsets_vk.as_ptr(),
+        );
+    }
+
+    /// Calls `vkCmdEndTransformFeedbackEXT` on the builder.
+    #[inline]
+    pub unsafe fn end_transform_feedback<'a>(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: impl IntoIterator<Item = Option<&'a dyn BufferAccess>>,
+    ) {
+        let (counter_buffers_vk, counter_buffer_offsets_vk) =
+            Self::transform_feedback_counter_buffers_vk(counter_buffers);
+
+        let fns = self.device.fns();
+        (fns.ext_transform_feedback.cmd_end_transform_feedback_ext)(
+            self.handle,
+            first_counter_buffer,
+            counter_buffers_vk.len() as u32,
+            counter_buffers_vk.as_ptr(),
+            counter_buffer_offsets_vk.as_ptr(),
+        );
+    }
+
+    fn transform_feedback_counter_buffers_vk<'a>(
+        counter_buffers: impl IntoIterator<Item = Option<&'a dyn BufferAccess>>,
+    ) -> (SmallVec<[ash::vk::Buffer; 4]>, SmallVec<[DeviceSize; 4]>) {
+        counter_buffers
+            .into_iter()
+            .map(|counter_buffer| match counter_buffer {
+                Some(counter_buffer) => {
+                    let inner = counter_buffer.inner();
+                    debug_assert!(inner
+                        .buffer
+                        .usage()
+                        .intersects(BufferUsage::TRANSFORM_FEEDBACK_COUNTER_BUFFER));
+
+                    (inner.buffer.handle(), inner.offset)
+                }
+                None => (ash::vk::Buffer::null(), 0),
+            })
+            .unzip()
+    }
+}
+
+/// Error that can happen when recording a transform feedback command.
+#[derive(Clone, Debug)]
+pub enum TransformFeedbackError {
+    SyncCommandBufferBuilderError(SyncCommandBufferBuilderError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// A transform feedback buffer is missing the `transform_feedback_buffer` usage.
+    BufferMissingUsage {
+        index: u32,
+    },
+
+    /// The bound graphics pipeline writes to a transform feedback buffer binding, but no buffer
+    /// is bound to it.
+    BufferNotBound {
+        binding: u32,
+    },
+
+    /// The offset of a transform feedback buffer is not a multiple of 4.
+    BufferOffsetNotAligned {
+        index: u32,
+        offset: DeviceSize,
+    },
+
+    /// A counter buffer is missing the `transform_feedback_counter_buffer` usage.
+    CounterBufferMissingUsage {
+        index: u32,
+    },
+
+    /// A counter buffer is too small to hold the counter value.
+    CounterBufferTooSmall {
+        index: u32,
+        buffer_size: DeviceSize,
+    },
+
+    /// Operation forbidden outside of a render pass.
+    ForbiddenOutsideRenderPass,
+
+    /// The size of a transform feedback buffer is greater than the
+    /// `max_transform_feedback_buffer_size` limit.
+    MaxTransformFeedbackBufferSizeExceeded {
+        index: u32,
+        size: DeviceSize,
+        max: DeviceSize,
+    },
+
+    /// The `max_transform_feedback_buffers` limit has been exceeded.
+    MaxTransformFeedbackBuffersExceeded {
+        binding_count: u32,
+        max: u32,
+    },
+
+    /// Multiview is enabled in the current subpass.
+    MultiviewEnabled,
+
+    /// The queue family doesn't allow this operation.
+    NotSupportedByQueueFamily,
+
+    /// No graphics pipeline is currently bound.
+    PipelineNotBound,
+
+    /// The last pre-rasterization shader stage of the bound graphics pipeline does not declare
+    /// the `Xfb` execution mode.
+    PipelineTransformFeedbackNotDeclared,
+
+    /// Transform feedback is already active.
+    TransformFeedbackActive,
+
+    /// Transform feedback is not active.
+    TransformFeedbackNotActive,
+}
+
+impl Error for TransformFeedbackError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::SyncCommandBufferBuilderError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for TransformFeedbackError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::SyncCommandBufferBuilderError(_) => write!(f, "a SyncCommandBufferBuilderError"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::BufferMissingUsage { index } => write!(
+                f,
+                "transform feedback buffer {} is missing the `transform_feedback_buffer` usage",
+                index,
+            ),
+            Self::BufferNotBound { binding } => write!(
+                f,
+                "the bound graphics pipeline writes to transform feedback buffer binding {}, but \
+                no buffer is bound to it",
+                binding,
+            ),
+            Self::BufferOffsetNotAligned { index, offset } => write!(
+                f,
+                "the offset ({}) of transform feedback buffer {} is not a multiple of 4",
+                offset, index,
+            ),
+            Self::CounterBufferMissingUsage { index } => write!(
+                f,
+                "counter buffer {} is missing the `transform_feedback_counter_buffer` usage",
+                index,
+            ),
+            Self::CounterBufferTooSmall { index, buffer_size } => write!(
+                f,
+                "the size of counter buffer {} ({}) is too small to hold the counter value",
+                index, buffer_size,
+            ),
+            Self::ForbiddenOutsideRenderPass => {
+                write!(f, "operation forbidden outside of a render pass")
+            }
+            Self::MaxTransformFeedbackBufferSizeExceeded { index, size, max } => write!(
+                f,
+                "the size of transform feedback buffer {} ({}) is greater than the \
+                `max_transform_feedback_buffer_size` limit ({})",
+                index, size, max,
+            ),
+            Self::MaxTransformFeedbackBuffersExceeded { binding_count, max } => write!(
+                f,
+                "the number of bindings ({}) is greater than the `max_transform_feedback_buffers` \
+                limit ({})",
+                binding_count, max,
+            ),
+            Self::MultiviewEnabled => write!(f, "multiview is enabled in the current subpass"),
+            Self::NotSupportedByQueueFamily => {
+                write!(f, "the queue family doesn't allow this operation")
+            }
+            Self::PipelineNotBound => write!(f, "no graphics pipeline is currently bound"),
+            Self::PipelineTransformFeedbackNotDeclared => write!(
+                f,
+                "the last pre-rasterization shader stage of the bound graphics pipeline does not \
+                declare the `Xfb` execution mode",
+            ),
+            Self::TransformFeedbackActive => write!(f, "transform feedback is already active"),
+            Self::TransformFeedbackNotActive => write!(f, "transform feedback is not active"),
+        }
+    }
+}
+
+impl From<SyncCommandBufferBuilderError> for TransformFeedbackError {
+    fn from(err: SyncCommandBufferBuilderError) -> Self {
+        Self::SyncCommandBufferBuilderError(err)
+    }
+}
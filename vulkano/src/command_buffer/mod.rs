@@ -112,7 +112,11 @@ pub use self::{
         SecondaryAutoCommandBuffer,
     },
     commands::{
+        acceleration_structure::{
+            AccelerationStructureCommandError, CopyAccelerationStructureInfo,
+        },
         clear::{ClearColorImageInfo, ClearDepthStencilImageInfo, ClearError, FillBufferInfo},
+        conditional_rendering::ConditionalRenderingError,
         copy::{
             BlitImageInfo, BufferCopy, BufferImageCopy, CopyBufferInfo, CopyBufferInfoTyped,
             CopyBufferToImageInfo, CopyError, CopyErrorResource, CopyImageInfo,
@@ -126,6 +130,7 @@ pub use self::{
             RenderingAttachmentInfo, RenderingAttachmentResolveInfo, RenderingInfo,
         },
         secondary::ExecuteCommandsError,
+        transform_feedback::TransformFeedbackError,
     },
     traits::{
         CommandBufferExecError, CommandBufferExecFuture, PrimaryCommandBufferAbstract,
@@ -136,7 +141,7 @@ use crate::{
     buffer::{sys::Buffer, BufferAccess},
     format::Format,
     image::{sys::Image, ImageAccess, ImageLayout, ImageSubresourceRange, SampleCount},
-    macros::vulkan_enum,
+    macros::{vulkan_bitflags, vulkan_enum},
     query::{QueryControlFlags, QueryPipelineStatisticFlags},
     range_map::RangeMap,
     render_pass::{Framebuffer, Subpass},
@@ -175,6 +180,27 @@ pub struct DrawIndexedIndirectCommand {
     pub first_instance: u32,
 }
 
+/// The parameters of a single draw in a [`draw_multi`] command.
+///
+/// [`draw_multi`]: AutoCommandBufferBuilder::draw_multi
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Zeroable, Pod, PartialEq, Eq)]
+pub struct MultiDrawInfo {
+    pub first_vertex: u32,
+    pub vertex_count: u32,
+}
+
+/// The parameters of a single draw in a [`draw_multi_indexed`] command.
+///
+/// [`draw_multi_indexed`]: AutoCommandBufferBuilder::draw_multi_indexed
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Zeroable, Pod, PartialEq, Eq)]
+pub struct MultiDrawIndexedInfo {
+    pub first_index: u32,
+    pub index_count: u32,
+    pub vertex_offset: i32,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, Zeroable, Pod, PartialEq, Eq)]
 pub struct DispatchIndirectCommand {
@@ -183,6 +209,14 @@ pub struct DispatchIndirectCommand {
     pub z: u32,
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Zeroable, Pod, PartialEq, Eq)]
+pub struct DrawMeshTasksIndirectCommand {
+    pub group_count_x: u32,
+    pub group_count_y: u32,
+    pub group_count_z: u32,
+}
+
 vulkan_enum! {
     #[non_exhaustive]
 
@@ -206,6 +240,22 @@ impl From<SubpassContents> for ash::vk::RenderingFlags {
     }
 }
 
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags that specify how a render pass instance that is begun with `begin_rendering` relates
+    /// to other render pass instances.
+    RenderingFlags = RenderingFlags(u32);
+
+    /// When the render pass instance is ended, it is suspended instead of being finished. It must
+    /// be resumed by the next render pass instance in submission order, which must have the
+    /// `RESUMING` flag set.
+    SUSPENDING = SUSPENDING,
+
+    /// The render pass instance resumes a render pass instance that was previously suspended.
+    RESUMING = RESUMING,
+}
+
 vulkan_enum! {
     /// Determines the kind of command buffer to create.
     CommandBufferLevel = CommandBufferLevel(i32);
@@ -253,6 +303,19 @@ pub struct CommandBufferInheritanceInfo {
     /// [`pipeline_statistics_query`]: crate::device::Features::pipeline_statistics_query
     pub query_statistics_flags: QueryPipelineStatisticFlags,
 
+    /// If `true`, the secondary command buffer is allowed to be executed within a primary that has
+    /// conditional rendering active, and will be affected by it.
+    /// If `false`, the primary command buffer cannot have conditional rendering active when this
+    /// secondary command buffer is executed.
+    ///
+    /// If set to `true`, the [`inherited_conditional_rendering`] feature must be enabled on the
+    /// device.
+    ///
+    /// The default value is `false`.
+    ///
+    /// [`inherited_conditional_rendering`]: crate::device::Features::inherited_conditional_rendering
+    pub conditional_rendering: bool,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -263,6 +326,7 @@ impl Default for CommandBufferInheritanceInfo {
             render_pass: None,
             occlusion_query: None,
             query_statistics_flags: QueryPipelineStatisticFlags::empty(),
+            conditional_rendering: false,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -455,6 +519,14 @@ impl Default for SubmitInfo {
     }
 }
 
+impl SubmitInfo {
+    // A submission is protected if its command buffers are protected. All command buffers in a
+    // submission must have the same protection.
+    pub(crate) fn is_protected(&self) -> bool {
+        matches!(self.command_buffers.first(), Some(command_buffer) if command_buffer.protected())
+    }
+}
+
 /// Parameters for a semaphore signal or wait operation in a command buffer submission.
 #[derive(Clone, Debug)]
 pub struct SemaphoreSubmitInfo {
@@ -477,6 +549,18 @@ pub struct SemaphoreSubmitInfo {
     /// [`synchronization2`]: crate::device::Features::synchronization2
     pub stages: PipelineStages,
 
+    /// If `semaphore` is a timeline semaphore, specifies the value that the semaphore's counter
+    /// must reach for a wait operation, or the value that the counter is set to for a signal
+    /// operation.
+    ///
+    /// For a signal operation, this must be greater than the current counter value of the
+    /// semaphore, and than the value of any signal operation that comes before it.
+    ///
+    /// If `semaphore` is a binary semaphore, this value is ignored.
+    ///
+    /// The default value is 0.
+    pub value: u64,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -487,6 +571,7 @@ impl SemaphoreSubmitInfo {
         Self {
             semaphore,
             stages: PipelineStages::ALL_COMMANDS,
+            value: 0,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -590,8 +675,10 @@ impl From<ResourceUseRef> for SecondaryResourceUseRef {
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub enum ResourceInCommand {
+    AccelerationStructureGeometry { index: u32 },
     ColorAttachment { index: u32 },
     ColorResolveAttachment { index: u32 },
+    ConditionalRenderingBuffer,
     DepthStencilAttachment,
     DepthStencilResolveAttachment,
     DescriptorSet { set: u32, binding: u32, index: u32 },
@@ -600,8 +687,13 @@ pub enum ResourceInCommand {
     ImageMemoryBarrier { index: u32 },
     IndexBuffer,
     IndirectBuffer,
+    IndirectCountBuffer,
+    ScratchBuffer,
     SecondaryCommandBuffer { index: u32 },
+    ShaderBindingTable,
     Source,
+    TransformFeedbackBuffer { binding: u32 },
+    TransformFeedbackCounterBuffer { index: u32 },
     VertexBuffer { binding: u32 },
 }
 
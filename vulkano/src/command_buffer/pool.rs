@@ -28,7 +28,6 @@ use std::{
     marker::PhantomData,
     mem::MaybeUninit,
     num::NonZeroU64,
-    ptr,
     sync::Arc,
 };
 
@@ -44,10 +43,12 @@ pub struct CommandPool {
     handle: ash::vk::CommandPool,
     device: Arc<Device>,
     id: NonZeroU64,
+    imported_handle: bool,
 
     queue_family_index: u32,
     _transient: bool,
     _reset_command_buffer: bool,
+    protected: bool,
     // Unimplement `Sync`, as Vulkan command pools are not thread-safe.
     _marker: PhantomData<Cell<ash::vk::CommandPool>>,
 }
@@ -65,6 +66,7 @@ impl CommandPool {
             queue_family_index,
             transient,
             reset_command_buffer,
+            protected,
             _ne: _,
         } = create_info;
 
@@ -72,9 +74,11 @@ impl CommandPool {
             handle,
             device,
             id: Self::next_id(),
+            imported_handle: false,
             queue_family_index,
             _transient: transient,
             _reset_command_buffer: reset_command_buffer,
+            protected,
             _marker: PhantomData,
         })
     }
@@ -85,6 +89,8 @@ impl CommandPool {
     ///
     /// - `handle` must be a valid Vulkan object handle created from `device`.
     /// - `create_info` must match the info used to create the object.
+    /// - `handle` must have been created with a null `pAllocator`, as it is destroyed with a null
+    ///   `pAllocator` when dropped.
     #[inline]
     pub unsafe fn from_handle(
         device: Arc<Device>,
@@ -95,6 +101,7 @@ impl CommandPool {
             queue_family_index,
             transient,
             reset_command_buffer,
+            protected,
             _ne: _,
         } = create_info;
 
@@ -102,9 +109,11 @@ impl CommandPool {
             handle,
             device,
             id: Self::next_id(),
+            imported_handle: true,
             queue_family_index,
             _transient: transient,
             _reset_command_buffer: reset_command_buffer,
+            protected,
             _marker: PhantomData,
         }
     }
@@ -117,6 +126,7 @@ impl CommandPool {
             queue_family_index,
             transient: _,
             reset_command_buffer: _,
+            protected,
             _ne: _,
         } = create_info;
 
@@ -128,6 +138,29 @@ impl CommandPool {
             });
         }
 
+        if protected {
+            if !(device.api_version() >= Version::V1_1) {
+                return Err(CommandPoolCreationError::RequirementNotMet {
+                    required_for: "`create_info.protected` is set",
+                    requires_one_of: RequiresOneOf {
+                        api_version: Some(Version::V1_1),
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkCommandPoolCreateInfo-flags-02860
+            if !device.enabled_features().protected_memory {
+                return Err(CommandPoolCreationError::RequirementNotMet {
+                    required_for: "`create_info.protected` is set",
+                    requires_one_of: RequiresOneOf {
+                        features: &["protected_memory"],
+                        ..Default::default()
+                    },
+                });
+            }
+        }
+
         Ok(())
     }
 
@@ -139,6 +172,7 @@ impl CommandPool {
             queue_family_index,
             transient,
             reset_command_buffer,
+            protected,
             _ne: _,
         } = create_info;
 
@@ -152,6 +186,10 @@ impl CommandPool {
             flags |= ash::vk::CommandPoolCreateFlags::RESET_COMMAND_BUFFER;
         }
 
+        if protected {
+            flags |= ash::vk::CommandPoolCreateFlags::PROTECTED;
+        }
+
         let create_info = ash::vk::CommandPoolCreateInfo {
             flags,
             queue_family_index,
@@ -164,7 +202,7 @@ impl CommandPool {
             (fns.v1_0.create_command_pool)(
                 device.handle(),
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -238,12 +276,14 @@ impl CommandPool {
         };
 
         let device = self.device.clone();
+        let protected = self.protected;
 
         Ok(out.into_iter().map(move |command_buffer| CommandPoolAlloc {
             handle: command_buffer,
             device: device.clone(),
             id: CommandPoolAlloc::next_id(),
             level,
+            protected,
         }))
     }
 
@@ -319,6 +359,12 @@ impl CommandPool {
     pub fn queue_family_index(&self) -> u32 {
         self.queue_family_index
     }
+
+    /// Returns whether command buffers allocated from this pool are protected.
+    #[inline]
+    pub fn protected(&self) -> bool {
+        self.protected
+    }
 }
 
 impl Drop for CommandPool {
@@ -326,7 +372,12 @@ impl Drop for CommandPool {
     fn drop(&mut self) {
         unsafe {
             let fns = self.device.fns();
-            (fns.v1_0.destroy_command_pool)(self.device.handle(), self.handle, ptr::null());
+            (fns.v1_0.destroy_command_pool)(
+                self.device.handle(),
+                self.handle,
+                self.device
+                    .destroy_allocation_callbacks(self.imported_handle),
+            );
         }
     }
 }
@@ -361,6 +412,11 @@ pub enum CommandPoolCreationError {
         queue_family_index: u32,
         queue_family_count: u32,
     },
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
 }
 
 impl Error for CommandPoolCreationError {
@@ -385,6 +441,14 @@ impl Display for CommandPoolCreationError {
                 families in the physical device ({})",
                 queue_family_index, queue_family_count,
             ),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
         }
     }
 }
@@ -418,6 +482,16 @@ pub struct CommandPoolCreateInfo {
     /// The default value is `false`.
     pub reset_command_buffer: bool,
 
+    /// Whether the command buffers allocated from this pool are protected. Protected command
+    /// buffers can access protected resources, and can only be submitted to protected queues.
+    ///
+    /// If set to `true`, the device API version must be at least 1.1, and the
+    /// [`protected_memory`](crate::device::Features::protected_memory) feature must be enabled on
+    /// the device.
+    ///
+    /// The default value is `false`.
+    pub protected: bool,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -428,6 +502,7 @@ impl Default for CommandPoolCreateInfo {
             queue_family_index: u32::MAX,
             transient: false,
             reset_command_buffer: false,
+            protected: false,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -467,6 +542,7 @@ pub struct CommandPoolAlloc {
     device: Arc<Device>,
     id: NonZeroU64,
     level: CommandBufferLevel,
+    protected: bool,
 }
 
 impl CommandPoolAlloc {
@@ -475,6 +551,12 @@ impl CommandPoolAlloc {
     pub fn level(&self) -> CommandBufferLevel {
         self.level
     }
+
+    /// Returns whether the command buffer is protected.
+    #[inline]
+    pub fn protected(&self) -> bool {
+        self.protected
+    }
 }
 
 unsafe impl VulkanObject for CommandPoolAlloc {
@@ -618,6 +700,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn missing_feature_protected_memory() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        match CommandPool::new(
+            device,
+            CommandPoolCreateInfo {
+                queue_family_index: queue.queue_family_index(),
+                protected: true,
+                ..Default::default()
+            },
+        ) {
+            Err(CommandPoolCreationError::RequirementNotMet { .. }) => (),
+            _ => panic!(),
+        }
+    }
+
     // TODO: test that trim works if VK_KHR_maintenance1 if enabled ; the test macro doesn't
     //       support enabling extensions yet
 
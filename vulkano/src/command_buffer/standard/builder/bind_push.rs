@@ -83,7 +83,7 @@ where
         // VUID-vkCmdBindDescriptorSets-commandBuffer-cmdpool
         // VUID-vkCmdBindDescriptorSets-pipelineBindPoint-00361
         match pipeline_bind_point {
-            PipelineBindPoint::Compute => {
+            PipelineBindPoint::Compute | PipelineBindPoint::RayTracing => {
                 if !queue_family_properties
                     .queue_flags
                     .intersects(QueueFlags::COMPUTE)
@@ -429,6 +429,11 @@ where
         // VUID-vkCmdBindPipeline-commonparent
         assert_eq!(self.device(), pipeline.device());
 
+        // VUID-vkCmdBindPipeline-None-02323
+        if self.builder_state.transform_feedback_active {
+            return Err(BindPushError::TransformFeedbackActive);
+        }
+
         if let Some(last_pipeline) =
             self.builder_state
                 .render_pass
@@ -813,7 +818,7 @@ where
         // VUID-vkCmdPushDescriptorSetKHR-commandBuffer-cmdpool
         // VUID-vkCmdPushDescriptorSetKHR-pipelineBindPoint-00363
         match pipeline_bind_point {
-            PipelineBindPoint::Compute => {
+            PipelineBindPoint::Compute | PipelineBindPoint::RayTracing => {
                 if !queue_family_properties
                     .queue_flags
                     .intersects(QueueFlags::COMPUTE)
@@ -887,7 +892,16 @@ where
         }
 
         // Set the info pointers separately.
-        for (info, write) in infos.iter().zip(writes.iter_mut()) {
+        let acceleration_structure_infos: SmallVec<[_; 8]> = infos
+            .iter()
+            .map(DescriptorWriteInfo::to_vulkan_acceleration_structure)
+            .collect();
+
+        for ((info, acceleration_structure_info), write) in infos
+            .iter()
+            .zip(&acceleration_structure_infos)
+            .zip(writes.iter_mut())
+        {
             match info {
                 DescriptorWriteInfo::Image(info) => {
                     write.descriptor_count = info.len() as u32;
@@ -901,6 +915,10 @@ where
                     write.descriptor_count = info.len() as u32;
                     write.p_texel_buffer_view = info.as_ptr();
                 }
+                DescriptorWriteInfo::AccelerationStructure(info) => {
+                    write.descriptor_count = info.len() as u32;
+                    write.p_next = acceleration_structure_info as *const _ as *const _;
+                }
             }
 
             debug_assert!(write.descriptor_count != 0);
@@ -0,0 +1,222 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{CommandBufferBuilder, ConditionalRenderingError, ConditionalRenderingState};
+use crate::{
+    buffer::{BufferAccess, BufferUsage},
+    command_buffer::{allocator::CommandBufferAllocator, ResourceInCommand, ResourceUseRef},
+    device::{DeviceOwned, QueueFlags},
+    sync::PipelineStageAccess,
+    DeviceSize, RequiresOneOf, VulkanObject,
+};
+use std::{mem::size_of, sync::Arc};
+
+impl<L, A> CommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Begins conditional rendering.
+    ///
+    /// While conditional rendering is active, draw and dispatch commands, as well as
+    /// [`clear_attachments`], are discarded by the device if the 32-bit value at `offset` in
+    /// `buffer` is zero. If `inverted` is `true`, they are discarded if the value is nonzero
+    /// instead.
+    ///
+    /// Conditional rendering stays active until [`end_conditional_rendering`] is called. If it is
+    /// begun inside a subpass, it must be ended in the same subpass.
+    ///
+    /// The [`conditional_rendering`] feature must be enabled on the device.
+    ///
+    /// # Safety
+    ///
+    /// - Appropriate synchronization must be provided for `buffer`.
+    ///
+    /// [`clear_attachments`]: Self::clear_attachments
+    /// [`end_conditional_rendering`]: Self::end_conditional_rendering
+    /// [`conditional_rendering`]: crate::device::Features::conditional_rendering
+    #[inline]
+    pub unsafe fn begin_conditional_rendering(
+        &mut self,
+        buffer: Arc<dyn BufferAccess>,
+        offset: DeviceSize,
+        inverted: bool,
+    ) -> Result<&mut Self, ConditionalRenderingError> {
+        self.validate_begin_conditional_rendering(buffer.as_ref(), offset)?;
+
+        unsafe { Ok(self.begin_conditional_rendering_unchecked(buffer, offset, inverted)) }
+    }
+
+    fn validate_begin_conditional_rendering(
+        &self,
+        buffer: &dyn BufferAccess,
+        offset: DeviceSize,
+    ) -> Result<(), ConditionalRenderingError> {
+        let device = self.device();
+
+        if !device.enabled_features().conditional_rendering {
+            return Err(ConditionalRenderingError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::begin_conditional_rendering`",
+                requires_one_of: RequiresOneOf {
+                    features: &["conditional_rendering"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdBeginConditionalRenderingEXT-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+        {
+            return Err(ConditionalRenderingError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdBeginConditionalRenderingEXT-None-01980
+        if self.builder_state.conditional_rendering.is_some() {
+            return Err(ConditionalRenderingError::ConditionalRenderingActive);
+        }
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-buffer-parameter
+        assert_eq!(device, buffer.device());
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-buffer-01981
+        if !buffer
+            .usage()
+            .intersects(BufferUsage::CONDITIONAL_RENDERING)
+        {
+            return Err(ConditionalRenderingError::BufferMissingUsage);
+        }
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-offset-01984
+        if offset % 4 != 0 {
+            return Err(ConditionalRenderingError::OffsetNotAligned { offset });
+        }
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-offset-01983
+        if offset + size_of::<u32>() as DeviceSize > buffer.size() {
+            return Err(ConditionalRenderingError::OffsetOutOfBounds {
+                offset,
+                buffer_size: buffer.size(),
+            });
+        }
+
+        // TODO: sync check
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn begin_conditional_rendering_unchecked(
+        &mut self,
+        buffer: Arc<dyn BufferAccess>,
+        offset: DeviceSize,
+        inverted: bool,
+    ) -> &mut Self {
+        let buffer_inner = buffer.inner();
+
+        let mut flags = ash::vk::ConditionalRenderingFlagsEXT::empty();
+
+        if inverted {
+            flags |= ash::vk::ConditionalRenderingFlagsEXT::INVERTED;
+        }
+
+        let begin_info = ash::vk::ConditionalRenderingBeginInfoEXT {
+            buffer: buffer_inner.buffer.handle(),
+            offset: buffer_inner.offset + offset,
+            flags,
+            ..Default::default()
+        };
+
+        let fns = self.device().fns();
+        (fns.ext_conditional_rendering
+            .cmd_begin_conditional_rendering_ext)(self.handle(), &begin_info);
+
+        let command_index = self.next_command_index;
+        let command_name = "begin_conditional_rendering";
+        let use_ref = ResourceUseRef {
+            command_index,
+            command_name,
+            resource_in_command: ResourceInCommand::ConditionalRenderingBuffer,
+            secondary_use_ref: None,
+        };
+
+        let start = buffer_inner.offset + offset;
+        self.resources_usage_state.record_buffer_access(
+            &use_ref,
+            buffer_inner.buffer,
+            start..start + size_of::<u32>() as DeviceSize,
+            PipelineStageAccess::ConditionalRendering_ConditionalRenderingRead,
+        );
+
+        self.builder_state.conditional_rendering = Some(ConditionalRenderingState {
+            in_subpass: self.builder_state.render_pass.is_some(),
+        });
+
+        self.resources.push(Box::new(buffer));
+
+        self.next_command_index += 1;
+        self
+    }
+
+    /// Ends conditional rendering, that was previously begun with
+    /// [`begin_conditional_rendering`].
+    ///
+    /// [`begin_conditional_rendering`]: Self::begin_conditional_rendering
+    #[inline]
+    pub fn end_conditional_rendering(&mut self) -> Result<&mut Self, ConditionalRenderingError> {
+        self.validate_end_conditional_rendering()?;
+
+        unsafe { Ok(self.end_conditional_rendering_unchecked()) }
+    }
+
+    fn validate_end_conditional_rendering(&self) -> Result<(), ConditionalRenderingError> {
+        // VUID-vkCmdEndConditionalRenderingEXT-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+        {
+            return Err(ConditionalRenderingError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdEndConditionalRenderingEXT-None-01985
+        let state = self
+            .builder_state
+            .conditional_rendering
+            .as_ref()
+            .ok_or(ConditionalRenderingError::ConditionalRenderingNotActive)?;
+
+        if state.in_subpass {
+            // VUID-vkCmdEndConditionalRenderingEXT-None-01987
+            if self.builder_state.render_pass.is_none() {
+                return Err(ConditionalRenderingError::ForbiddenOutsideRenderPass);
+            }
+        } else {
+            // VUID-vkCmdEndConditionalRenderingEXT-None-01986
+            if self.builder_state.render_pass.is_some() {
+                return Err(ConditionalRenderingError::ForbiddenInsideRenderPass);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn end_conditional_rendering_unchecked(&mut self) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_conditional_rendering
+            .cmd_end_conditional_rendering_ext)(self.handle());
+
+        self.builder_state.conditional_rendering = None;
+
+        self.next_command_index += 1;
+        self
+    }
+}
@@ -10,7 +10,8 @@
 use super::CommandBufferBuilder;
 use crate::{
     command_buffer::{
-        allocator::CommandBufferAllocator, commands::dynamic_state::SetDynamicStateError,
+        allocator::CommandBufferAllocator,
+        commands::dynamic_state::{validate_vertex_input_state, SetDynamicStateError},
     },
     device::{DeviceOwned, QueueFlags},
     pipeline::{
@@ -19,6 +20,7 @@ use crate::{
             depth_stencil::{CompareOp, StencilFaces, StencilOp, StencilOps},
             input_assembly::PrimitiveTopology,
             rasterization::{CullMode, DepthBias, FrontFace, LineStipple},
+            vertex_input::{VertexInputRate, VertexInputState},
             viewport::{Scissor, Viewport},
         },
         DynamicState,
@@ -2116,6 +2118,113 @@ where
         self
     }
 
+    /// Sets the dynamic vertex input for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`vertex_input_dynamic_state`](crate::device::Features::vertex_input_dynamic_state)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if the number of bindings or attributes, or a binding number, location, stride,
+    ///   offset or divisor in `vertex_input_state` exceeds the corresponding device limit.
+    /// - Panics if an attribute in `vertex_input_state` refers to a binding that does not exist,
+    ///   or has a format that does not support vertex buffer usage.
+    #[inline]
+    pub fn set_vertex_input(&mut self, vertex_input_state: VertexInputState) -> &mut Self {
+        self.validate_set_vertex_input(&vertex_input_state).unwrap();
+
+        unsafe { self.set_vertex_input_unchecked(vertex_input_state) }
+    }
+
+    fn validate_set_vertex_input(
+        &self,
+        vertex_input_state: &VertexInputState,
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::VertexInput)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetVertexInputEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        let device = self.device();
+
+        // VUID-vkCmdSetVertexInputEXT-None-04790
+        if !device.enabled_features().vertex_input_dynamic_state {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_vertex_input`",
+                requires_one_of: RequiresOneOf {
+                    features: &["vertex_input_dynamic_state"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        validate_vertex_input_state(device, vertex_input_state)
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_vertex_input_unchecked(
+        &mut self,
+        vertex_input_state: VertexInputState,
+    ) -> &mut Self {
+        let vertex_binding_descriptions_vk: SmallVec<[_; 8]> = vertex_input_state
+            .bindings
+            .iter()
+            .map(
+                |(&binding, binding_desc)| ash::vk::VertexInputBindingDescription2EXT {
+                    binding,
+                    stride: binding_desc.stride,
+                    input_rate: binding_desc.input_rate.into(),
+                    divisor: match binding_desc.input_rate {
+                        VertexInputRate::Vertex => 1,
+                        VertexInputRate::Instance { divisor } => divisor,
+                    },
+                    ..Default::default()
+                },
+            )
+            .collect();
+        let vertex_attribute_descriptions_vk: SmallVec<[_; 8]> = vertex_input_state
+            .attributes
+            .iter()
+            .map(
+                |(&location, attribute_desc)| ash::vk::VertexInputAttributeDescription2EXT {
+                    location,
+                    binding: attribute_desc.binding,
+                    format: attribute_desc.format.into(),
+                    offset: attribute_desc.offset,
+                    ..Default::default()
+                },
+            )
+            .collect();
+
+        debug_assert!(
+            self.device()
+                .enabled_extensions()
+                .ext_vertex_input_dynamic_state
+        );
+        let fns = self.device().fns();
+        (fns.ext_vertex_input_dynamic_state.cmd_set_vertex_input_ext)(
+            self.handle(),
+            vertex_binding_descriptions_vk.len() as u32,
+            vertex_binding_descriptions_vk.as_ptr(),
+            vertex_attribute_descriptions_vk.len() as u32,
+            vertex_attribute_descriptions_vk.as_ptr(),
+        );
+
+        self.builder_state.vertex_input = Some(vertex_input_state);
+
+        self.next_command_index += 1;
+        self
+    }
+
     /// Sets the dynamic viewports for future draw calls.
     ///
     /// # Panics
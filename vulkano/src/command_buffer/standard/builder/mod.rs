@@ -8,17 +8,18 @@
 // according to those terms.
 
 pub use self::{
-    bind_push::*, clear::*, copy::*, debug::*, dynamic_state::*, pipeline::*, query::*,
-    render_pass::*, secondary::*, sync::*,
+    bind_push::*, clear::*, conditional_rendering::*, copy::*, debug::*, dynamic_state::*,
+    pipeline::*, query::*, render_pass::*, secondary::*, sync::*, transform_feedback::*,
 };
 use super::{PrimaryCommandBuffer, SecondaryCommandBuffer, SubmitState};
 pub use crate::command_buffer::{
     BlitImageInfo, BufferCopy, BufferImageCopy, ClearAttachment, ClearColorImageInfo,
-    ClearDepthStencilImageInfo, ClearError, ClearRect, CopyBufferInfo, CopyBufferInfoTyped,
-    CopyBufferToImageInfo, CopyError, CopyErrorResource, CopyImageInfo, CopyImageToBufferInfo,
-    DebugUtilsError, ExecuteCommandsError, FillBufferInfo, ImageBlit, ImageCopy, ImageResolve,
-    PipelineExecutionError, QueryError, RenderPassBeginInfo, RenderPassError,
-    RenderingAttachmentInfo, RenderingAttachmentResolveInfo, RenderingInfo, ResolveImageInfo,
+    ClearDepthStencilImageInfo, ClearError, ClearRect, ConditionalRenderingError, CopyBufferInfo,
+    CopyBufferInfoTyped, CopyBufferToImageInfo, CopyError, CopyErrorResource, CopyImageInfo,
+    CopyImageToBufferInfo, DebugUtilsError, ExecuteCommandsError, FillBufferInfo, ImageBlit,
+    ImageCopy, ImageResolve, PipelineExecutionError, QueryError, RenderPassBeginInfo,
+    RenderPassError, RenderingAttachmentInfo, RenderingAttachmentResolveInfo, RenderingInfo,
+    ResolveImageInfo, TransformFeedbackError,
 };
 use crate::{
     buffer::{sys::Buffer, BufferAccess},
@@ -43,6 +44,7 @@ use crate::{
             input_assembly::{IndexType, PrimitiveTopology},
             rasterization::{CullMode, DepthBias, FrontFace, LineStipple},
             render_pass::PipelineRenderingCreateInfo,
+            vertex_input::VertexInputState,
             viewport::{Scissor, Viewport},
         },
         ComputePipeline, DynamicState, GraphicsPipeline, PipelineBindPoint, PipelineLayout,
@@ -71,6 +73,7 @@ use std::{
 
 mod bind_push;
 mod clear;
+mod conditional_rendering;
 mod copy;
 mod debug;
 mod dynamic_state;
@@ -79,6 +82,7 @@ mod query;
 mod render_pass;
 mod secondary;
 mod sync;
+mod transform_feedback;
 
 /// Records commands to a command buffer.
 pub struct CommandBufferBuilder<L, A = StandardCommandBufferAllocator>
@@ -95,6 +99,12 @@ where
     builder_state: CommandBufferBuilderState,
     resources_usage_state: ResourcesState,
 
+    // Whether the first render pass instance resumes a suspended render pass instance.
+    // `None` if no render pass instance has been begun yet.
+    resumes_render_pass: Option<bool>,
+    // Whether the last render pass instance was suspended.
+    suspends_render_pass: bool,
+
     _data: PhantomData<L>,
 }
 
@@ -217,6 +227,7 @@ where
                 ref render_pass,
                 occlusion_query,
                 query_statistics_flags,
+                conditional_rendering,
                 _ne: _,
             } = inheritance_info;
 
@@ -417,6 +428,17 @@ where
                     },
                 });
             }
+
+            // VUID-VkCommandBufferInheritanceConditionalRenderingInfoEXT-conditionalRenderingEnable-01977
+            if conditional_rendering && !device.enabled_features().inherited_conditional_rendering {
+                return Err(CommandBufferBeginError::RequirementNotMet {
+                    required_for: "`inheritance_info.conditional_rendering` is `true`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["inherited_conditional_rendering"],
+                        ..Default::default()
+                    },
+                });
+            }
         } else {
             debug_assert!(level == CommandBufferLevel::Primary);
 
@@ -450,6 +472,7 @@ where
             let mut flags = ash::vk::CommandBufferUsageFlags::from(usage);
             let mut inheritance_info_vk = None;
             let mut inheritance_rendering_info_vk = None;
+            let mut inheritance_conditional_rendering_info_vk = None;
             let mut color_attachment_formats_vk: SmallVec<[_; 4]> = SmallVec::new();
 
             if let Some(inheritance_info) = &inheritance_info {
@@ -457,6 +480,7 @@ where
                     ref render_pass,
                     occlusion_query,
                     query_statistics_flags,
+                    conditional_rendering,
                     _ne: _,
                 } = inheritance_info;
 
@@ -534,6 +558,20 @@ where
                         }
                     }
                 }
+
+                if conditional_rendering {
+                    let inheritance_conditional_rendering_info_vk =
+                        inheritance_conditional_rendering_info_vk.insert(
+                            ash::vk::CommandBufferInheritanceConditionalRenderingInfoEXT {
+                                conditional_rendering_enable: ash::vk::TRUE,
+                                p_next: inheritance_info_vk.p_next,
+                                ..Default::default()
+                            },
+                        );
+
+                    inheritance_info_vk.p_next =
+                        inheritance_conditional_rendering_info_vk as *const _ as *const _;
+                }
             }
 
             let begin_info_vk = ash::vk::CommandBufferBeginInfo {
@@ -558,6 +596,7 @@ where
                 ref render_pass,
                 occlusion_query: _,
                 query_statistics_flags: _,
+                conditional_rendering: _,
                 _ne: _,
             } = inheritance_info;
 
@@ -577,6 +616,9 @@ where
             builder_state,
             resources_usage_state: Default::default(),
 
+            resumes_render_pass: None,
+            suspends_render_pass: false,
+
             _data: PhantomData,
         })
     }
@@ -600,6 +642,11 @@ where
             return Err(BuildError::QueryActive);
         }
 
+        // VUID-vkEndCommandBuffer-None-01978
+        if self.builder_state.conditional_rendering.is_some() {
+            return Err(BuildError::ConditionalRenderingActive);
+        }
+
         Ok(unsafe { self.build_unchecked()? })
     }
 
@@ -630,6 +677,11 @@ where
             return Err(BuildError::QueryActive);
         }
 
+        // VUID-vkEndCommandBuffer-None-01978
+        if self.builder_state.conditional_rendering.is_some() {
+            return Err(BuildError::ConditionalRenderingActive);
+        }
+
         Ok(unsafe { self.build_unchecked()? })
     }
 
@@ -674,6 +726,7 @@ struct CommandBufferBuilderState {
     pipeline_compute: Option<Arc<ComputePipeline>>,
     pipeline_graphics: Option<Arc<GraphicsPipeline>>,
     vertex_buffers: HashMap<u32, Arc<dyn BufferAccess>>,
+    transform_feedback_buffers: HashMap<u32, Arc<dyn BufferAccess>>,
     push_constants: RangeSet<u32>,
     push_constants_pipeline_layout: Option<Arc<PipelineLayout>>,
 
@@ -704,11 +757,18 @@ struct CommandBufferBuilderState {
     stencil_reference: StencilStateDynamic,
     stencil_test_enable: Option<bool>,
     stencil_write_mask: StencilStateDynamic,
+    vertex_input: Option<VertexInputState>,
     viewport: HashMap<u32, Viewport>,
     viewport_with_count: Option<SmallVec<[Viewport; 2]>>,
 
     // Active queries
     queries: HashMap<ash::vk::QueryType, QueryState>,
+
+    // Active conditional rendering
+    conditional_rendering: Option<ConditionalRenderingState>,
+
+    // Active transform feedback
+    transform_feedback_active: bool,
 }
 
 impl CommandBufferBuilderState {
@@ -745,7 +805,7 @@ impl CommandBufferBuilderState {
                 DynamicState::StencilReference => self.stencil_reference = Default::default(),
                 DynamicState::StencilTestEnable => self.stencil_test_enable = None,
                 DynamicState::StencilWriteMask => self.stencil_write_mask = Default::default(),
-                DynamicState::VertexInput => (), // TODO:
+                DynamicState::VertexInput => self.vertex_input = None,
                 DynamicState::VertexInputBindingStride => (), // TODO:
                 DynamicState::Viewport => self.viewport.clear(),
                 DynamicState::ViewportCoarseSampleOrder => (), // TODO:
@@ -888,6 +948,7 @@ impl RenderPassState {
 
                 render_pass: BeginRenderingState {
                     pipeline_used: false,
+                    suspending: false,
                 }
                 .into(),
             },
@@ -921,6 +982,7 @@ struct BeginRenderPassState {
 
 struct BeginRenderingState {
     pipeline_used: bool,
+    suspending: bool,
 }
 
 struct RenderPassStateAttachments {
@@ -1154,6 +1216,10 @@ struct QueryState {
     in_subpass: bool,
 }
 
+struct ConditionalRenderingState {
+    in_subpass: bool,
+}
+
 #[derive(Debug, Default)]
 struct ResourcesState {
     buffers: HashMap<Arc<Buffer>, RangeMap<DeviceSize, BufferRangeState>>,
@@ -14,9 +14,11 @@ use super::{
 use crate::{
     buffer::{view::BufferViewAbstract, BufferAccess, BufferUsage, TypedBufferAccess},
     command_buffer::{
-        allocator::CommandBufferAllocator, commands::pipeline::DescriptorResourceInvalidError,
+        allocator::CommandBufferAllocator,
+        commands::pipeline::{validate_dynamic_vertex_input, DescriptorResourceInvalidError},
         DispatchIndirectCommand, DrawIndexedIndirectCommand, DrawIndirectCommand,
-        ResourceInCommand, ResourceUseRef, SubpassContents,
+        DrawMeshTasksIndirectCommand, MultiDrawIndexedInfo, MultiDrawInfo, ResourceInCommand,
+        ResourceUseRef, SubpassContents,
     },
     descriptor_set::{layout::DescriptorType, DescriptorBindingResources},
     device::{DeviceOwned, QueueFlags},
@@ -37,10 +39,11 @@ use crate::{
         ShaderStages,
     },
     sync::PipelineStageAccess,
-    DeviceSize, RequiresOneOf, VulkanObject,
+    DeviceSize, RequiresOneOf, Version, VulkanObject,
 };
 use ahash::HashMap;
-use std::{cmp::min, mem::size_of, ops::Range, sync::Arc};
+use smallvec::SmallVec;
+use std::{cmp::min, mem::size_of, ops::Range, ptr, sync::Arc};
 
 impl<L, A> CommandBufferBuilder<L, A>
 where
@@ -302,6 +305,11 @@ where
             .ok_or(PipelineExecutionError::PipelineNotBound)?
             .as_ref();
 
+        // VUID-vkCmdDraw-stage-06481
+        if pipeline.shader(ShaderStage::Mesh).is_some() {
+            return Err(PipelineExecutionError::MeshShaderPipelineBound);
+        }
+
         self.validate_pipeline_descriptor_sets(pipeline)?;
         self.validate_pipeline_push_constants(pipeline.layout())?;
         self.validate_pipeline_graphics_dynamic_state(pipeline)?;
@@ -353,7 +361,14 @@ where
             &mut self.resources_usage_state,
             command_index,
             command_name,
-            &self.builder_state.vertex_buffers,
+            &self.builder_state,
+            pipeline,
+        );
+        record_transform_feedback_buffers_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state,
             pipeline,
         );
         record_subpass_attachments_access(
@@ -430,6 +445,11 @@ where
             .ok_or(PipelineExecutionError::PipelineNotBound)?
             .as_ref();
 
+        // VUID-vkCmdDrawIndirect-stage-06481
+        if pipeline.shader(ShaderStage::Mesh).is_some() {
+            return Err(PipelineExecutionError::MeshShaderPipelineBound);
+        }
+
         self.validate_pipeline_descriptor_sets(pipeline)?;
         self.validate_pipeline_push_constants(pipeline.layout())?;
         self.validate_pipeline_graphics_dynamic_state(pipeline)?;
@@ -505,7 +525,14 @@ where
             &mut self.resources_usage_state,
             command_index,
             command_name,
-            &self.builder_state.vertex_buffers,
+            &self.builder_state,
+            pipeline,
+        );
+        record_transform_feedback_buffers_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state,
             pipeline,
         );
         record_indirect_buffer_access(
@@ -613,6 +640,11 @@ where
             .ok_or(PipelineExecutionError::PipelineNotBound)?
             .as_ref();
 
+        // VUID-vkCmdDrawIndexed-stage-06481
+        if pipeline.shader(ShaderStage::Mesh).is_some() {
+            return Err(PipelineExecutionError::MeshShaderPipelineBound);
+        }
+
         self.validate_pipeline_descriptor_sets(pipeline)?;
         self.validate_pipeline_push_constants(pipeline.layout())?;
         self.validate_pipeline_graphics_dynamic_state(pipeline)?;
@@ -642,15 +674,1509 @@ where
         let fns = self.device().fns();
         (fns.v1_0.cmd_draw_indexed)(
             self.handle(),
-            index_count,
-            instance_count,
-            first_index,
-            vertex_offset,
-            first_instance,
+            index_count,
+            instance_count,
+            first_index,
+            vertex_offset,
+            first_instance,
+        );
+
+        let command_index = self.next_command_index;
+        let command_name = "draw_indexed";
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+        record_descriptor_sets_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state.descriptor_sets,
+            pipeline,
+        );
+        record_vertex_buffers_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state,
+            pipeline,
+        );
+        record_transform_feedback_buffers_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state,
+            pipeline,
+        );
+        record_index_buffer_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state.index_buffer,
+        );
+        record_subpass_attachments_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            self.builder_state.render_pass.as_ref().unwrap(),
+            &self.builder_state,
+            pipeline,
+        );
+
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        self.next_command_index += 1;
+        self
+    }
+
+    /// Perform multiple draw operations using a graphics pipeline, using an index buffer.
+    ///
+    /// One draw is performed for each [`DrawIndexedIndirectCommand`] struct in `indirect_buffer`.
+    /// The maximum number of draw commands in the buffer is limited by the
+    /// [`max_draw_indirect_count`] limit.
+    /// This limit is 1 unless the [`multi_draw_indirect`] feature has been enabled on the device.
+    ///
+    /// An index buffer must have been bound using [`bind_index_buffer`], and the index ranges of
+    /// each `DrawIndexedIndirectCommand` in the indirect buffer must be in range of the bound
+    /// index buffer.
+    ///
+    /// A graphics pipeline must have been bound using [`bind_pipeline_graphics`]. Any resources
+    /// used by the graphics pipeline, such as descriptor sets, vertex buffers and dynamic state,
+    /// must have been set beforehand. If the bound graphics pipeline uses vertex buffers, then the
+    /// instance ranges of each `DrawIndexedIndirectCommand` in the indirect buffer must be in
+    /// range of the bound vertex buffers.
+    ///
+    /// # Safety
+    ///
+    /// - Appropriate synchronization must be provided for all buffers and images
+    ///   that are accessed by the command.
+    /// - All images that are accessed by the command must be in the expected image layout.
+    ///
+    /// [`max_draw_indirect_count`]: crate::device::Properties::max_draw_indirect_count
+    /// [`multi_draw_indirect`]: crate::device::Features::multi_draw_indirect
+    /// [`bind_index_buffer`]: Self::bind_index_buffer
+    /// [`bind_pipeline_graphics`]: Self::bind_pipeline_graphics
+    #[inline]
+    pub unsafe fn draw_indexed_indirect(
+        &mut self,
+        indirect_buffer: Arc<
+            impl TypedBufferAccess<Content = [DrawIndexedIndirectCommand]> + 'static,
+        >,
+    ) -> Result<&mut Self, PipelineExecutionError> {
+        let draw_count = indirect_buffer.len() as u32;
+        let stride = size_of::<DrawIndexedIndirectCommand>() as u32;
+        self.validate_draw_indexed_indirect(&indirect_buffer, draw_count, stride)?;
+
+        unsafe { Ok(self.draw_indexed_indirect_unchecked(indirect_buffer, draw_count, stride)) }
+    }
+
+    fn validate_draw_indexed_indirect(
+        &self,
+        indirect_buffer: &dyn BufferAccess,
+        draw_count: u32,
+        _stride: u32,
+    ) -> Result<(), PipelineExecutionError> {
+        // VUID-vkCmdDrawIndexedIndirect-renderpass
+        let render_pass_state = self
+            .builder_state
+            .render_pass
+            .as_ref()
+            .ok_or(PipelineExecutionError::ForbiddenOutsideRenderPass)?;
+
+        // VUID-vkCmdDrawIndexedIndirect-None-02700
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .ok_or(PipelineExecutionError::PipelineNotBound)?
+            .as_ref();
+
+        // VUID-vkCmdDrawIndexedIndirect-stage-06481
+        if pipeline.shader(ShaderStage::Mesh).is_some() {
+            return Err(PipelineExecutionError::MeshShaderPipelineBound);
+        }
+
+        self.validate_pipeline_descriptor_sets(pipeline)?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(pipeline)?;
+        self.validate_pipeline_graphics_render_pass(pipeline, render_pass_state)?;
+        self.validate_pipeline_graphics_vertex_buffers(pipeline, None, None)?;
+
+        self.validate_index_buffer(None)?;
+        self.validate_indirect_buffer(indirect_buffer)?;
+
+        // VUID-vkCmdDrawIndexedIndirect-drawCount-02718
+        if draw_count > 1 && !self.device().enabled_features().multi_draw_indirect {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`draw_count` is greater than `1`",
+                requires_one_of: RequiresOneOf {
+                    features: &["multi_draw_indirect"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let max = self
+            .device()
+            .physical_device()
+            .properties()
+            .max_draw_indirect_count;
+
+        // VUID-vkCmdDrawIndexedIndirect-drawCount-02719
+        if draw_count > max {
+            return Err(PipelineExecutionError::MaxDrawIndirectCountExceeded {
+                provided: draw_count,
+                max,
+            });
+        }
+
+        // TODO: sync check
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_indexed_indirect_unchecked(
+        &mut self,
+        indirect_buffer: Arc<dyn BufferAccess>,
+        draw_count: u32,
+        stride: u32,
+    ) -> &mut Self {
+        let indirect_buffer_inner = indirect_buffer.inner();
+
+        let fns = self.device().fns();
+        (fns.v1_0.cmd_draw_indexed_indirect)(
+            self.handle(),
+            indirect_buffer_inner.buffer.handle(),
+            indirect_buffer_inner.offset,
+            draw_count,
+            stride,
+        );
+
+        let command_index = self.next_command_index;
+        let command_name = "draw_indexed_indirect";
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+        record_descriptor_sets_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state.descriptor_sets,
+            pipeline,
+        );
+        record_vertex_buffers_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state,
+            pipeline,
+        );
+        record_transform_feedback_buffers_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state,
+            pipeline,
+        );
+        record_index_buffer_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state.index_buffer,
+        );
+        record_indirect_buffer_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &indirect_buffer,
+        );
+        record_subpass_attachments_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            self.builder_state.render_pass.as_ref().unwrap(),
+            &self.builder_state,
+            pipeline,
+        );
+
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        self.resources.push(Box::new(indirect_buffer));
+
+        self.next_command_index += 1;
+        self
+    }
+
+    /// Perform multiple draw operations using a graphics pipeline, reading the number of draws
+    /// from a buffer.
+    ///
+    /// One draw is performed for each [`DrawIndirectCommand`] struct in `indirect_buffer`, up to
+    /// the value stored in `count_buffer` or `max_draw_count`, whichever is smaller. This allows
+    /// the number of draws to be determined on the device.
+    ///
+    /// A graphics pipeline must have been bound using [`bind_pipeline_graphics`]. Any resources
+    /// used by the graphics pipeline, such as descriptor sets, vertex buffers and dynamic state,
+    /// must have been set beforehand. If the bound graphics pipeline uses vertex buffers, then the
+    /// vertex and instance ranges of each `DrawIndirectCommand` in the indirect buffer must be in
+    /// range of the bound vertex buffers.
+    ///
+    /// # Safety
+    ///
+    /// - Appropriate synchronization must be provided for all buffers and images
+    ///   that are accessed by the command.
+    /// - All images that are accessed by the command must be in the expected image layout.
+    ///
+    /// [`bind_pipeline_graphics`]: Self::bind_pipeline_graphics
+    #[inline]
+    pub unsafe fn draw_indirect_count(
+        &mut self,
+        indirect_buffer: Arc<impl TypedBufferAccess<Content = [DrawIndirectCommand]> + 'static>,
+        count_buffer: Arc<impl TypedBufferAccess<Content = u32> + 'static>,
+        max_draw_count: u32,
+    ) -> Result<&mut Self, PipelineExecutionError> {
+        let stride = size_of::<DrawIndirectCommand>() as u32;
+        self.validate_draw_indirect_count(&indirect_buffer, &count_buffer, max_draw_count, stride)?;
+
+        unsafe {
+            Ok(self.draw_indirect_count_unchecked(
+                indirect_buffer,
+                count_buffer,
+                max_draw_count,
+                stride,
+            ))
+        }
+    }
+
+    fn validate_draw_indirect_count(
+        &self,
+        indirect_buffer: &dyn BufferAccess,
+        count_buffer: &dyn BufferAccess,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> Result<(), PipelineExecutionError> {
+        // VUID-vkCmdDrawIndirectCount-None-04445
+        if !(self.device().enabled_features().draw_indirect_count
+            || self.device().enabled_extensions().khr_draw_indirect_count)
+        {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::draw_indirect_count`",
+                requires_one_of: RequiresOneOf {
+                    features: &["draw_indirect_count"],
+                    device_extensions: &["khr_draw_indirect_count"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdDrawIndirectCount-renderpass
+        let render_pass_state = self
+            .builder_state
+            .render_pass
+            .as_ref()
+            .ok_or(PipelineExecutionError::ForbiddenOutsideRenderPass)?;
+
+        // VUID-vkCmdDrawIndirectCount-None-02700
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .ok_or(PipelineExecutionError::PipelineNotBound)?
+            .as_ref();
+
+        // VUID-vkCmdDrawIndirectCount-stage-06481
+        if pipeline.shader(ShaderStage::Mesh).is_some() {
+            return Err(PipelineExecutionError::MeshShaderPipelineBound);
+        }
+
+        self.validate_pipeline_descriptor_sets(pipeline)?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(pipeline)?;
+        self.validate_pipeline_graphics_render_pass(pipeline, render_pass_state)?;
+        self.validate_pipeline_graphics_vertex_buffers(pipeline, None, None)?;
+
+        self.validate_indirect_buffer(indirect_buffer)?;
+        self.validate_indirect_count_buffer(indirect_buffer, count_buffer, max_draw_count, stride)?;
+
+        // TODO: sync check
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_indirect_count_unchecked(
+        &mut self,
+        indirect_buffer: Arc<dyn BufferAccess>,
+        count_buffer: Arc<dyn BufferAccess>,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> &mut Self {
+        let indirect_buffer_inner = indirect_buffer.inner();
+        let count_buffer_inner = count_buffer.inner();
+
+        let fns = self.device().fns();
+        let cmd_draw_indirect_count = if self.device().api_version() >= Version::V1_2 {
+            fns.v1_2.cmd_draw_indirect_count
+        } else {
+            fns.khr_draw_indirect_count.cmd_draw_indirect_count_khr
+        };
+        cmd_draw_indirect_count(
+            self.handle(),
+            indirect_buffer_inner.buffer.handle(),
+            indirect_buffer_inner.offset,
+            count_buffer_inner.buffer.handle(),
+            count_buffer_inner.offset,
+            max_draw_count,
+            stride,
+        );
+
+        let command_index = self.next_command_index;
+        let command_name = "draw_indirect_count";
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+        record_descriptor_sets_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state.descriptor_sets,
+            pipeline,
+        );
+        record_vertex_buffers_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state,
+            pipeline,
+        );
+        record_transform_feedback_buffers_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state,
+            pipeline,
+        );
+        record_indirect_buffer_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &indirect_buffer,
+        );
+        record_indirect_count_buffer_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &count_buffer,
+        );
+        record_subpass_attachments_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            self.builder_state.render_pass.as_ref().unwrap(),
+            &self.builder_state,
+            pipeline,
+        );
+
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        self.resources.push(Box::new(indirect_buffer));
+        self.resources.push(Box::new(count_buffer));
+
+        self.next_command_index += 1;
+        self
+    }
+
+    /// Perform multiple draw operations using a graphics pipeline, using an index buffer,
+    /// reading the number of draws from a buffer.
+    ///
+    /// One draw is performed for each [`DrawIndexedIndirectCommand`] struct in `indirect_buffer`,
+    /// up to the value stored in `count_buffer` or `max_draw_count`, whichever is smaller. This
+    /// allows the number of draws to be determined on the device.
+    ///
+    /// An index buffer must have been bound using [`bind_index_buffer`], and the index ranges of
+    /// each `DrawIndexedIndirectCommand` in the indirect buffer must be in range of the bound
+    /// index buffer.
+    ///
+    /// A graphics pipeline must have been bound using [`bind_pipeline_graphics`]. Any resources
+    /// used by the graphics pipeline, such as descriptor sets, vertex buffers and dynamic state,
+    /// must have been set beforehand. If the bound graphics pipeline uses vertex buffers, then the
+    /// instance ranges of each `DrawIndexedIndirectCommand` in the indirect buffer must be in
+    /// range of the bound vertex buffers.
+    ///
+    /// # Safety
+    ///
+    /// - Appropriate synchronization must be provided for all buffers and images
+    ///   that are accessed by the command.
+    /// - All images that are accessed by the command must be in the expected image layout.
+    ///
+    /// [`bind_index_buffer`]: Self::bind_index_buffer
+    /// [`bind_pipeline_graphics`]: Self::bind_pipeline_graphics
+    #[inline]
+    pub unsafe fn draw_indexed_indirect_count(
+        &mut self,
+        indirect_buffer: Arc<
+            impl TypedBufferAccess<Content = [DrawIndexedIndirectCommand]> + 'static,
+        >,
+        count_buffer: Arc<impl TypedBufferAccess<Content = u32> + 'static>,
+        max_draw_count: u32,
+    ) -> Result<&mut Self, PipelineExecutionError> {
+        let stride = size_of::<DrawIndexedIndirectCommand>() as u32;
+        self.validate_draw_indexed_indirect_count(
+            &indirect_buffer,
+            &count_buffer,
+            max_draw_count,
+            stride,
+        )?;
+
+        unsafe {
+            Ok(self.draw_indexed_indirect_count_unchecked(
+                indirect_buffer,
+                count_buffer,
+                max_draw_count,
+                stride,
+            ))
+        }
+    }
+
+    fn validate_draw_indexed_indirect_count(
+        &self,
+        indirect_buffer: &dyn BufferAccess,
+        count_buffer: &dyn BufferAccess,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> Result<(), PipelineExecutionError> {
+        // VUID-vkCmdDrawIndexedIndirectCount-None-04445
+        if !(self.device().enabled_features().draw_indirect_count
+            || self.device().enabled_extensions().khr_draw_indirect_count)
+        {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::draw_indexed_indirect_count`",
+                requires_one_of: RequiresOneOf {
+                    features: &["draw_indirect_count"],
+                    device_extensions: &["khr_draw_indirect_count"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdDrawIndexedIndirectCount-renderpass
+        let render_pass_state = self
+            .builder_state
+            .render_pass
+            .as_ref()
+            .ok_or(PipelineExecutionError::ForbiddenOutsideRenderPass)?;
+
+        // VUID-vkCmdDrawIndexedIndirectCount-None-02700
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .ok_or(PipelineExecutionError::PipelineNotBound)?
+            .as_ref();
+
+        // VUID-vkCmdDrawIndexedIndirectCount-stage-06481
+        if pipeline.shader(ShaderStage::Mesh).is_some() {
+            return Err(PipelineExecutionError::MeshShaderPipelineBound);
+        }
+
+        self.validate_pipeline_descriptor_sets(pipeline)?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(pipeline)?;
+        self.validate_pipeline_graphics_render_pass(pipeline, render_pass_state)?;
+        self.validate_pipeline_graphics_vertex_buffers(pipeline, None, None)?;
+
+        self.validate_index_buffer(None)?;
+        self.validate_indirect_buffer(indirect_buffer)?;
+        self.validate_indirect_count_buffer(indirect_buffer, count_buffer, max_draw_count, stride)?;
+
+        // TODO: sync check
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_indexed_indirect_count_unchecked(
+        &mut self,
+        indirect_buffer: Arc<dyn BufferAccess>,
+        count_buffer: Arc<dyn BufferAccess>,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> &mut Self {
+        let indirect_buffer_inner = indirect_buffer.inner();
+        let count_buffer_inner = count_buffer.inner();
+
+        let fns = self.device().fns();
+        let cmd_draw_indexed_indirect_count = if self.device().api_version() >= Version::V1_2 {
+            fns.v1_2.cmd_draw_indexed_indirect_count
+        } else {
+            fns.khr_draw_indirect_count
+                .cmd_draw_indexed_indirect_count_khr
+        };
+        cmd_draw_indexed_indirect_count(
+            self.handle(),
+            indirect_buffer_inner.buffer.handle(),
+            indirect_buffer_inner.offset,
+            count_buffer_inner.buffer.handle(),
+            count_buffer_inner.offset,
+            max_draw_count,
+            stride,
+        );
+
+        let command_index = self.next_command_index;
+        let command_name = "draw_indexed_indirect_count";
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+        record_descriptor_sets_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state.descriptor_sets,
+            pipeline,
+        );
+        record_vertex_buffers_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state,
+            pipeline,
+        );
+        record_transform_feedback_buffers_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state,
+            pipeline,
+        );
+        record_index_buffer_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state.index_buffer,
+        );
+        record_indirect_buffer_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &indirect_buffer,
+        );
+        record_indirect_count_buffer_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &count_buffer,
+        );
+        record_subpass_attachments_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            self.builder_state.render_pass.as_ref().unwrap(),
+            &self.builder_state,
+            pipeline,
+        );
+
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        self.resources.push(Box::new(indirect_buffer));
+        self.resources.push(Box::new(count_buffer));
+
+        self.next_command_index += 1;
+        self
+    }
+
+    /// Perform a single draw operation using a graphics pipeline, reading the number of vertices
+    /// from a transform feedback counter buffer.
+    ///
+    /// The number of vertices is calculated by subtracting `counter_offset` from the byte count
+    /// stored in `counter_buffer`, and dividing the result by `vertex_stride`. The counter
+    /// buffer is typically written by [`end_transform_feedback`].
+    ///
+    /// The [`transform_feedback`] feature must be enabled on the device, and the
+    /// [`transform_feedback_draw`] property must be supported.
+    ///
+    /// A graphics pipeline must have been bound using [`bind_pipeline_graphics`]. Any resources
+    /// used by the graphics pipeline, such as descriptor sets, vertex buffers and dynamic state,
+    /// must have been set beforehand. If the bound graphics pipeline uses vertex buffers, then the
+    /// provided instance range must be in range of the bound vertex buffers.
+    ///
+    /// # Safety
+    ///
+    /// - Appropriate synchronization must be provided for all buffers and images
+    ///   that are accessed by the command.
+    /// - All images that are accessed by the command must be in the expected image layout.
+    ///
+    /// [`end_transform_feedback`]: Self::end_transform_feedback
+    /// [`transform_feedback`]: crate::device::Features::transform_feedback
+    /// [`transform_feedback_draw`]: crate::device::Properties::transform_feedback_draw
+    /// [`bind_pipeline_graphics`]: Self::bind_pipeline_graphics
+    #[inline]
+    pub unsafe fn draw_indirect_byte_count(
+        &mut self,
+        instance_count: u32,
+        first_instance: u32,
+        counter_buffer: Arc<impl TypedBufferAccess<Content = u32> + 'static>,
+        counter_offset: u32,
+        vertex_stride: u32,
+    ) -> Result<&mut Self, PipelineExecutionError> {
+        self.validate_draw_indirect_byte_count(
+            instance_count,
+            first_instance,
+            &counter_buffer,
+            vertex_stride,
+        )?;
+
+        unsafe {
+            Ok(self.draw_indirect_byte_count_unchecked(
+                instance_count,
+                first_instance,
+                counter_buffer,
+                counter_offset,
+                vertex_stride,
+            ))
+        }
+    }
+
+    fn validate_draw_indirect_byte_count(
+        &self,
+        instance_count: u32,
+        first_instance: u32,
+        counter_buffer: &dyn BufferAccess,
+        vertex_stride: u32,
+    ) -> Result<(), PipelineExecutionError> {
+        let device = self.device();
+        let properties = device.physical_device().properties();
+
+        // VUID-vkCmdDrawIndirectByteCountEXT-transformFeedback-02287
+        if !device.enabled_features().transform_feedback {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::draw_indirect_byte_count`",
+                requires_one_of: RequiresOneOf {
+                    features: &["transform_feedback"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdDrawIndirectByteCountEXT-transformFeedbackDraw-02288
+        if !properties.transform_feedback_draw.unwrap_or(false) {
+            return Err(PipelineExecutionError::TransformFeedbackDrawNotSupported);
+        }
+
+        // VUID-vkCmdDrawIndirectByteCountEXT-vertexStride-02289
+        let max_stride = properties
+            .max_transform_feedback_buffer_data_stride
+            .unwrap_or(0);
+
+        if vertex_stride > max_stride {
+            return Err(
+                PipelineExecutionError::MaxTransformFeedbackBufferDataStrideExceeded {
+                    provided: vertex_stride,
+                    max: max_stride,
+                },
+            );
+        }
+
+        // VUID-vkCmdDrawIndirectByteCountEXT-vertexStride-09474
+        if vertex_stride % 4 != 0 {
+            return Err(PipelineExecutionError::VertexStrideNotAligned { vertex_stride });
+        }
+
+        // VUID-vkCmdDrawIndirectByteCountEXT-renderpass
+        let render_pass_state = self
+            .builder_state
+            .render_pass
+            .as_ref()
+            .ok_or(PipelineExecutionError::ForbiddenOutsideRenderPass)?;
+
+        // VUID-vkCmdDrawIndirectByteCountEXT-None-02700
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .ok_or(PipelineExecutionError::PipelineNotBound)?
+            .as_ref();
+
+        // VUID-vkCmdDrawIndirectByteCountEXT-stage-06481
+        if pipeline.shader(ShaderStage::Mesh).is_some() {
+            return Err(PipelineExecutionError::MeshShaderPipelineBound);
+        }
+
+        self.validate_pipeline_descriptor_sets(pipeline)?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(pipeline)?;
+        self.validate_pipeline_graphics_render_pass(pipeline, render_pass_state)?;
+        self.validate_pipeline_graphics_vertex_buffers(
+            pipeline,
+            None,
+            Some((first_instance, instance_count)),
+        )?;
+
+        // VUID-vkCmdDrawIndirectByteCountEXT-counterBuffer-02290
+        // VUID-vkCmdDrawIndirectByteCountEXT-counterBuffer-04567
+        self.validate_indirect_buffer(counter_buffer)?;
+
+        // VUID-vkCmdDrawIndirectByteCountEXT-counterBufferOffset-04568
+        let offset = counter_buffer.inner().offset;
+
+        if offset % 4 != 0 {
+            return Err(PipelineExecutionError::CounterBufferOffsetNotAligned { offset });
+        }
+
+        // TODO: sync check
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_indirect_byte_count_unchecked(
+        &mut self,
+        instance_count: u32,
+        first_instance: u32,
+        counter_buffer: Arc<dyn BufferAccess>,
+        counter_offset: u32,
+        vertex_stride: u32,
+    ) -> &mut Self {
+        let counter_buffer_inner = counter_buffer.inner();
+
+        let fns = self.device().fns();
+        (fns.ext_transform_feedback.cmd_draw_indirect_byte_count_ext)(
+            self.handle(),
+            instance_count,
+            first_instance,
+            counter_buffer_inner.buffer.handle(),
+            counter_buffer_inner.offset,
+            counter_offset,
+            vertex_stride,
+        );
+
+        let command_index = self.next_command_index;
+        let command_name = "draw_indirect_byte_count";
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+        record_descriptor_sets_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state.descriptor_sets,
+            pipeline,
+        );
+        record_vertex_buffers_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state,
+            pipeline,
+        );
+        record_transform_feedback_buffers_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state,
+            pipeline,
+        );
+        record_counter_buffer_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &counter_buffer,
+        );
+        record_subpass_attachments_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            self.builder_state.render_pass.as_ref().unwrap(),
+            &self.builder_state,
+            pipeline,
+        );
+
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        self.resources.push(Box::new(counter_buffer));
+
+        self.next_command_index += 1;
+        self
+    }
+
+    /// Perform multiple draw operations using a graphics pipeline, sharing the same instance
+    /// range.
+    ///
+    /// One draw is performed for each element of `vertex_info`, which specifies the first vertex
+    /// and the number of vertices to draw. All draws use the same `instance_count` and
+    /// `first_instance`. The number of draws is limited by the [`max_multi_draw_count`] limit.
+    ///
+    /// The [`multi_draw`] feature must be enabled on the device.
+    ///
+    /// A graphics pipeline must have been bound using [`bind_pipeline_graphics`]. Any resources
+    /// used by the graphics pipeline, such as descriptor sets, vertex buffers and dynamic state,
+    /// must have been set beforehand. If the bound graphics pipeline uses vertex buffers, then the
+    /// vertex range of each element of `vertex_info`, and the provided instance range, must be in
+    /// range of the bound vertex buffers.
+    ///
+    /// # Safety
+    ///
+    /// - Appropriate synchronization must be provided for all buffers and images
+    ///   that are accessed by the command.
+    /// - All images that are accessed by the command must be in the expected image layout.
+    ///
+    /// [`max_multi_draw_count`]: crate::device::Properties::max_multi_draw_count
+    /// [`multi_draw`]: crate::device::Features::multi_draw
+    /// [`bind_pipeline_graphics`]: Self::bind_pipeline_graphics
+    #[inline]
+    pub unsafe fn draw_multi(
+        &mut self,
+        vertex_info: impl IntoIterator<Item = MultiDrawInfo>,
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<&mut Self, PipelineExecutionError> {
+        let vertex_info: SmallVec<[MultiDrawInfo; 4]> = vertex_info.into_iter().collect();
+        self.validate_draw_multi(&vertex_info, instance_count, first_instance)?;
+
+        unsafe { Ok(self.draw_multi_unchecked(vertex_info, instance_count, first_instance)) }
+    }
+
+    fn validate_draw_multi(
+        &self,
+        vertex_info: &[MultiDrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<(), PipelineExecutionError> {
+        // VUID-vkCmdDrawMultiEXT-None-04933
+        if !self.device().enabled_features().multi_draw {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::draw_multi`",
+                requires_one_of: RequiresOneOf {
+                    features: &["multi_draw"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdDrawMultiEXT-drawCount-04934
+        self.validate_multi_draw_count(vertex_info.len())?;
+
+        // VUID-vkCmdDrawMultiEXT-renderpass
+        let render_pass_state = self
+            .builder_state
+            .render_pass
+            .as_ref()
+            .ok_or(PipelineExecutionError::ForbiddenOutsideRenderPass)?;
+
+        // VUID-vkCmdDrawMultiEXT-None-02700
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .ok_or(PipelineExecutionError::PipelineNotBound)?
+            .as_ref();
+
+        // VUID-vkCmdDrawMultiEXT-stage-06481
+        if pipeline.shader(ShaderStage::Mesh).is_some() {
+            return Err(PipelineExecutionError::MeshShaderPipelineBound);
+        }
+
+        self.validate_pipeline_descriptor_sets(pipeline)?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(pipeline)?;
+        self.validate_pipeline_graphics_render_pass(pipeline, render_pass_state)?;
+        self.validate_pipeline_graphics_vertex_buffers(
+            pipeline,
+            None,
+            Some((first_instance, instance_count)),
+        )?;
+
+        for info in vertex_info {
+            self.validate_pipeline_graphics_vertex_buffers(
+                pipeline,
+                Some((info.first_vertex, info.vertex_count)),
+                None,
+            )?;
+        }
+
+        // TODO: sync check
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_multi_unchecked(
+        &mut self,
+        vertex_info: impl IntoIterator<Item = MultiDrawInfo>,
+        instance_count: u32,
+        first_instance: u32,
+    ) -> &mut Self {
+        let vertex_info: SmallVec<[MultiDrawInfo; 4]> = vertex_info.into_iter().collect();
+
+        if vertex_info.is_empty() {
+            return self;
+        }
+
+        let fns = self.device().fns();
+        (fns.ext_multi_draw.cmd_draw_multi_ext)(
+            self.handle(),
+            vertex_info.len() as u32,
+            vertex_info.as_ptr() as *const ash::vk::MultiDrawInfoEXT,
+            instance_count,
+            first_instance,
+            size_of::<MultiDrawInfo>() as u32,
+        );
+
+        let command_index = self.next_command_index;
+        let command_name = "draw_multi";
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+        record_descriptor_sets_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state.descriptor_sets,
+            pipeline,
+        );
+        record_vertex_buffers_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state,
+            pipeline,
+        );
+        record_transform_feedback_buffers_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state,
+            pipeline,
+        );
+        record_subpass_attachments_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            self.builder_state.render_pass.as_ref().unwrap(),
+            &self.builder_state,
+            pipeline,
+        );
+
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        self.next_command_index += 1;
+        self
+    }
+
+    /// Perform multiple draw operations using a graphics pipeline, using an index buffer and
+    /// sharing the same instance range.
+    ///
+    /// One draw is performed for each element of `index_info`, which specifies the first index,
+    /// the number of indices to draw and the vertex offset. All draws use the same
+    /// `instance_count` and `first_instance`. If `vertex_offset` is `Some`, then it is used for
+    /// every draw, and the `vertex_offset` of each element of `index_info` is ignored. The number
+    /// of draws is limited by the [`max_multi_draw_count`] limit.
+    ///
+    /// The [`multi_draw`] feature must be enabled on the device.
+    ///
+    /// An index buffer must have been bound using [`bind_index_buffer`], and the index range of
+    /// each element of `index_info` must be in range of the bound index buffer.
+    ///
+    /// A graphics pipeline must have been bound using [`bind_pipeline_graphics`]. Any resources
+    /// used by the graphics pipeline, such as descriptor sets, vertex buffers and dynamic state,
+    /// must have been set beforehand. If the bound graphics pipeline uses vertex buffers, then the
+    /// provided instance range must be in range of the bound vertex buffers. The vertex indices in
+    /// the index buffer must be in range of the bound vertex buffers.
+    ///
+    /// # Safety
+    ///
+    /// - Appropriate synchronization must be provided for all buffers and images
+    ///   that are accessed by the command.
+    /// - All images that are accessed by the command must be in the expected image layout.
+    ///
+    /// [`max_multi_draw_count`]: crate::device::Properties::max_multi_draw_count
+    /// [`multi_draw`]: crate::device::Features::multi_draw
+    /// [`bind_index_buffer`]: Self::bind_index_buffer
+    /// [`bind_pipeline_graphics`]: Self::bind_pipeline_graphics
+    #[inline]
+    pub unsafe fn draw_multi_indexed(
+        &mut self,
+        index_info: impl IntoIterator<Item = MultiDrawIndexedInfo>,
+        instance_count: u32,
+        first_instance: u32,
+        vertex_offset: Option<i32>,
+    ) -> Result<&mut Self, PipelineExecutionError> {
+        let index_info: SmallVec<[MultiDrawIndexedInfo; 4]> = index_info.into_iter().collect();
+        self.validate_draw_multi_indexed(&index_info, instance_count, first_instance)?;
+
+        unsafe {
+            Ok(self.draw_multi_indexed_unchecked(
+                index_info,
+                instance_count,
+                first_instance,
+                vertex_offset,
+            ))
+        }
+    }
+
+    fn validate_draw_multi_indexed(
+        &self,
+        index_info: &[MultiDrawIndexedInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<(), PipelineExecutionError> {
+        // TODO: how to handle an index out of range of the vertex buffers?
+
+        // VUID-vkCmdDrawMultiIndexedEXT-None-04937
+        if !self.device().enabled_features().multi_draw {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::draw_multi_indexed`",
+                requires_one_of: RequiresOneOf {
+                    features: &["multi_draw"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdDrawMultiIndexedEXT-drawCount-04939
+        self.validate_multi_draw_count(index_info.len())?;
+
+        // VUID-vkCmdDrawMultiIndexedEXT-renderpass
+        let render_pass_state = self
+            .builder_state
+            .render_pass
+            .as_ref()
+            .ok_or(PipelineExecutionError::ForbiddenOutsideRenderPass)?;
+
+        // VUID-vkCmdDrawMultiIndexedEXT-None-02700
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .ok_or(PipelineExecutionError::PipelineNotBound)?
+            .as_ref();
+
+        // VUID-vkCmdDrawMultiIndexedEXT-stage-06481
+        if pipeline.shader(ShaderStage::Mesh).is_some() {
+            return Err(PipelineExecutionError::MeshShaderPipelineBound);
+        }
+
+        self.validate_pipeline_descriptor_sets(pipeline)?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(pipeline)?;
+        self.validate_pipeline_graphics_render_pass(pipeline, render_pass_state)?;
+        self.validate_pipeline_graphics_vertex_buffers(
+            pipeline,
+            None,
+            Some((first_instance, instance_count)),
+        )?;
+
+        self.validate_index_buffer(None)?;
+
+        // VUID-vkCmdDrawMultiIndexedEXT-firstIndex-04938
+        for info in index_info {
+            self.validate_index_buffer(Some((info.first_index, info.index_count)))?;
+        }
+
+        // TODO: sync check
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_multi_indexed_unchecked(
+        &mut self,
+        index_info: impl IntoIterator<Item = MultiDrawIndexedInfo>,
+        instance_count: u32,
+        first_instance: u32,
+        vertex_offset: Option<i32>,
+    ) -> &mut Self {
+        let index_info: SmallVec<[MultiDrawIndexedInfo; 4]> = index_info.into_iter().collect();
+
+        if index_info.is_empty() {
+            return self;
+        }
+
+        let fns = self.device().fns();
+        (fns.ext_multi_draw.cmd_draw_multi_indexed_ext)(
+            self.handle(),
+            index_info.len() as u32,
+            index_info.as_ptr() as *const ash::vk::MultiDrawIndexedInfoEXT,
+            instance_count,
+            first_instance,
+            size_of::<MultiDrawIndexedInfo>() as u32,
+            vertex_offset
+                .as_ref()
+                .map_or(ptr::null(), |vertex_offset| vertex_offset),
+        );
+
+        let command_index = self.next_command_index;
+        let command_name = "draw_multi_indexed";
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+        record_descriptor_sets_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state.descriptor_sets,
+            pipeline,
+        );
+        record_vertex_buffers_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state,
+            pipeline,
+        );
+        record_transform_feedback_buffers_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state,
+            pipeline,
+        );
+        record_index_buffer_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state.index_buffer,
+        );
+        record_subpass_attachments_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            self.builder_state.render_pass.as_ref().unwrap(),
+            &self.builder_state,
+            pipeline,
+        );
+
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        self.next_command_index += 1;
+        self
+    }
+
+    /// Perform a single draw operation using a mesh shading graphics pipeline.
+    ///
+    /// `group_counts` specifies the number of task shader workgroups to dispatch, or the number
+    /// of mesh shader workgroups if the pipeline has no task shader.
+    ///
+    /// A graphics pipeline containing a mesh shader must have been bound using
+    /// [`bind_pipeline_graphics`]. Any resources used by the graphics pipeline, such as descriptor
+    /// sets and dynamic state, must have been set beforehand.
+    ///
+    /// # Safety
+    ///
+    /// - Appropriate synchronization must be provided for all buffers and images
+    ///   that are accessed by the command.
+    /// - All images that are accessed by the command must be in the expected image layout.
+    ///
+    /// [`bind_pipeline_graphics`]: Self::bind_pipeline_graphics
+    #[inline]
+    pub unsafe fn draw_mesh_tasks(
+        &mut self,
+        group_counts: [u32; 3],
+    ) -> Result<&mut Self, PipelineExecutionError> {
+        self.validate_draw_mesh_tasks(group_counts)?;
+
+        unsafe { Ok(self.draw_mesh_tasks_unchecked(group_counts)) }
+    }
+
+    fn validate_draw_mesh_tasks(
+        &self,
+        group_counts: [u32; 3],
+    ) -> Result<(), PipelineExecutionError> {
+        // VUID-vkCmdDrawMeshTasksEXT-None-07074
+        if !self.device().enabled_features().mesh_shader {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::draw_mesh_tasks`",
+                requires_one_of: RequiresOneOf {
+                    features: &["mesh_shader"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdDrawMeshTasksEXT-renderpass
+        let render_pass_state = self
+            .builder_state
+            .render_pass
+            .as_ref()
+            .ok_or(PipelineExecutionError::ForbiddenOutsideRenderPass)?;
+
+        // VUID-vkCmdDrawMeshTasksEXT-None-02700
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .ok_or(PipelineExecutionError::PipelineNotBound)?
+            .as_ref();
+
+        self.validate_pipeline_graphics_mesh_shading(pipeline)?;
+        self.validate_pipeline_descriptor_sets(pipeline)?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(pipeline)?;
+        self.validate_pipeline_graphics_render_pass(pipeline, render_pass_state)?;
+
+        let properties = self.device().physical_device().properties();
+        let (max, max_total) = if pipeline.shader(ShaderStage::Task).is_some() {
+            (
+                properties.max_task_work_group_count.unwrap_or_default(),
+                properties
+                    .max_task_work_group_total_count
+                    .unwrap_or_default(),
+            )
+        } else {
+            (
+                properties.max_mesh_work_group_count.unwrap_or_default(),
+                properties
+                    .max_mesh_work_group_total_count
+                    .unwrap_or_default(),
+            )
+        };
+
+        // VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07322
+        // VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07323
+        // VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07324
+        // VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07326
+        // VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07327
+        // VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07328
+        if group_counts[0] > max[0] || group_counts[1] > max[1] || group_counts[2] > max[2] {
+            return Err(PipelineExecutionError::MaxMeshTasksWorkGroupCountExceeded {
+                requested: group_counts,
+                max,
+            });
+        }
+
+        // VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07325
+        // VUID-vkCmdDrawMeshTasksEXT-TaskEXT-07329
+        let total_count = group_counts[0] as u64 * group_counts[1] as u64 * group_counts[2] as u64;
+
+        if total_count > max_total as u64 {
+            return Err(
+                PipelineExecutionError::MaxMeshTasksWorkGroupTotalCountExceeded {
+                    requested: total_count,
+                    max: max_total,
+                },
+            );
+        }
+
+        // TODO: sync check
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_mesh_tasks_unchecked(&mut self, group_counts: [u32; 3]) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_mesh_shader.cmd_draw_mesh_tasks_ext)(
+            self.handle(),
+            group_counts[0],
+            group_counts[1],
+            group_counts[2],
+        );
+
+        let command_index = self.next_command_index;
+        let command_name = "draw_mesh_tasks";
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+        record_descriptor_sets_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &self.builder_state.descriptor_sets,
+            pipeline,
+        );
+        record_subpass_attachments_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            self.builder_state.render_pass.as_ref().unwrap(),
+            &self.builder_state,
+            pipeline,
+        );
+
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        self.next_command_index += 1;
+        self
+    }
+
+    /// Perform multiple draw operations using a mesh shading graphics pipeline.
+    ///
+    /// One draw is performed for each [`DrawMeshTasksIndirectCommand`] struct in
+    /// `indirect_buffer`. The maximum number of draw commands in the buffer is limited by the
+    /// [`max_draw_indirect_count`] limit.
+    /// This limit is 1 unless the [`multi_draw_indirect`] feature has been enabled on the device.
+    ///
+    /// A graphics pipeline containing a mesh shader must have been bound using
+    /// [`bind_pipeline_graphics`]. Any resources used by the graphics pipeline, such as descriptor
+    /// sets and dynamic state, must have been set beforehand.
+    ///
+    /// # Safety
+    ///
+    /// - Appropriate synchronization must be provided for all buffers and images
+    ///   that are accessed by the command.
+    /// - All images that are accessed by the command must be in the expected image layout.
+    ///
+    /// [`max_draw_indirect_count`]: crate::device::Properties::max_draw_indirect_count
+    /// [`multi_draw_indirect`]: crate::device::Features::multi_draw_indirect
+    /// [`bind_pipeline_graphics`]: Self::bind_pipeline_graphics
+    #[inline]
+    pub unsafe fn draw_mesh_tasks_indirect(
+        &mut self,
+        indirect_buffer: Arc<
+            impl TypedBufferAccess<Content = [DrawMeshTasksIndirectCommand]> + 'static,
+        >,
+    ) -> Result<&mut Self, PipelineExecutionError> {
+        let draw_count = indirect_buffer.len() as u32;
+        let stride = size_of::<DrawMeshTasksIndirectCommand>() as u32;
+        self.validate_draw_mesh_tasks_indirect(&indirect_buffer, draw_count, stride)?;
+
+        unsafe { Ok(self.draw_mesh_tasks_indirect_unchecked(indirect_buffer, draw_count, stride)) }
+    }
+
+    fn validate_draw_mesh_tasks_indirect(
+        &self,
+        indirect_buffer: &dyn BufferAccess,
+        draw_count: u32,
+        _stride: u32,
+    ) -> Result<(), PipelineExecutionError> {
+        // VUID-vkCmdDrawMeshTasksIndirectEXT-None-07074
+        if !self.device().enabled_features().mesh_shader {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::draw_mesh_tasks_indirect`",
+                requires_one_of: RequiresOneOf {
+                    features: &["mesh_shader"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdDrawMeshTasksIndirectEXT-renderpass
+        let render_pass_state = self
+            .builder_state
+            .render_pass
+            .as_ref()
+            .ok_or(PipelineExecutionError::ForbiddenOutsideRenderPass)?;
+
+        // VUID-vkCmdDrawMeshTasksIndirectEXT-None-02700
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .ok_or(PipelineExecutionError::PipelineNotBound)?
+            .as_ref();
+
+        self.validate_pipeline_graphics_mesh_shading(pipeline)?;
+        self.validate_pipeline_descriptor_sets(pipeline)?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(pipeline)?;
+        self.validate_pipeline_graphics_render_pass(pipeline, render_pass_state)?;
+
+        self.validate_indirect_buffer(indirect_buffer)?;
+
+        // VUID-vkCmdDrawMeshTasksIndirectEXT-drawCount-02718
+        if draw_count > 1 && !self.device().enabled_features().multi_draw_indirect {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`draw_count` is greater than `1`",
+                requires_one_of: RequiresOneOf {
+                    features: &["multi_draw_indirect"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let max = self
+            .device()
+            .physical_device()
+            .properties()
+            .max_draw_indirect_count;
+
+        // VUID-vkCmdDrawMeshTasksIndirectEXT-drawCount-02719
+        if draw_count > max {
+            return Err(PipelineExecutionError::MaxDrawIndirectCountExceeded {
+                provided: draw_count,
+                max,
+            });
+        }
+
+        // TODO: sync check
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_mesh_tasks_indirect_unchecked(
+        &mut self,
+        indirect_buffer: Arc<dyn BufferAccess>,
+        draw_count: u32,
+        stride: u32,
+    ) -> &mut Self {
+        let indirect_buffer_inner = indirect_buffer.inner();
+
+        let fns = self.device().fns();
+        (fns.ext_mesh_shader.cmd_draw_mesh_tasks_indirect_ext)(
+            self.handle(),
+            indirect_buffer_inner.buffer.handle(),
+            indirect_buffer_inner.offset,
+            draw_count,
+            stride,
         );
 
         let command_index = self.next_command_index;
-        let command_name = "draw_indexed";
+        let command_name = "draw_mesh_tasks_indirect";
         let pipeline = self
             .builder_state
             .pipeline_graphics
@@ -664,18 +2190,11 @@ where
             &self.builder_state.descriptor_sets,
             pipeline,
         );
-        record_vertex_buffers_access(
-            &mut self.resources_usage_state,
-            command_index,
-            command_name,
-            &self.builder_state.vertex_buffers,
-            pipeline,
-        );
-        record_index_buffer_access(
+        record_indirect_buffer_access(
             &mut self.resources_usage_state,
             command_index,
             command_name,
-            &self.builder_state.index_buffer,
+            &indirect_buffer,
         );
         record_subpass_attachments_access(
             &mut self.resources_usage_state,
@@ -692,26 +2211,22 @@ where
             state.pipeline_used = true;
         }
 
+        self.resources.push(Box::new(indirect_buffer));
+
         self.next_command_index += 1;
         self
     }
 
-    /// Perform multiple draw operations using a graphics pipeline, using an index buffer.
-    ///
-    /// One draw is performed for each [`DrawIndexedIndirectCommand`] struct in `indirect_buffer`.
-    /// The maximum number of draw commands in the buffer is limited by the
-    /// [`max_draw_indirect_count`] limit.
-    /// This limit is 1 unless the [`multi_draw_indirect`] feature has been enabled on the device.
+    /// Perform multiple draw operations using a mesh shading graphics pipeline, reading the
+    /// number of draws from a buffer.
     ///
-    /// An index buffer must have been bound using [`bind_index_buffer`], and the index ranges of
-    /// each `DrawIndexedIndirectCommand` in the indirect buffer must be in range of the bound
-    /// index buffer.
+    /// One draw is performed for each [`DrawMeshTasksIndirectCommand`] struct in
+    /// `indirect_buffer`, up to the value stored in `count_buffer` or `max_draw_count`, whichever
+    /// is smaller.
     ///
-    /// A graphics pipeline must have been bound using [`bind_pipeline_graphics`]. Any resources
-    /// used by the graphics pipeline, such as descriptor sets, vertex buffers and dynamic state,
-    /// must have been set beforehand. If the bound graphics pipeline uses vertex buffers, then the
-    /// instance ranges of each `DrawIndexedIndirectCommand` in the indirect buffer must be in
-    /// range of the bound vertex buffers.
+    /// A graphics pipeline containing a mesh shader must have been bound using
+    /// [`bind_pipeline_graphics`]. Any resources used by the graphics pipeline, such as descriptor
+    /// sets and dynamic state, must have been set beforehand.
     ///
     /// # Safety
     ///
@@ -719,38 +2234,71 @@ where
     ///   that are accessed by the command.
     /// - All images that are accessed by the command must be in the expected image layout.
     ///
-    /// [`max_draw_indirect_count`]: crate::device::Properties::max_draw_indirect_count
-    /// [`multi_draw_indirect`]: crate::device::Features::multi_draw_indirect
-    /// [`bind_index_buffer`]: Self::bind_index_buffer
     /// [`bind_pipeline_graphics`]: Self::bind_pipeline_graphics
     #[inline]
-    pub unsafe fn draw_indexed_indirect(
+    pub unsafe fn draw_mesh_tasks_indirect_count(
         &mut self,
         indirect_buffer: Arc<
-            impl TypedBufferAccess<Content = [DrawIndexedIndirectCommand]> + 'static,
+            impl TypedBufferAccess<Content = [DrawMeshTasksIndirectCommand]> + 'static,
         >,
+        count_buffer: Arc<impl TypedBufferAccess<Content = u32> + 'static>,
+        max_draw_count: u32,
     ) -> Result<&mut Self, PipelineExecutionError> {
-        let draw_count = indirect_buffer.len() as u32;
-        let stride = size_of::<DrawIndexedIndirectCommand>() as u32;
-        self.validate_draw_indexed_indirect(&indirect_buffer, draw_count, stride)?;
+        let stride = size_of::<DrawMeshTasksIndirectCommand>() as u32;
+        self.validate_draw_mesh_tasks_indirect_count(
+            &indirect_buffer,
+            &count_buffer,
+            max_draw_count,
+            stride,
+        )?;
 
-        unsafe { Ok(self.draw_indexed_indirect_unchecked(indirect_buffer, draw_count, stride)) }
+        unsafe {
+            Ok(self.draw_mesh_tasks_indirect_count_unchecked(
+                indirect_buffer,
+                count_buffer,
+                max_draw_count,
+                stride,
+            ))
+        }
     }
 
-    fn validate_draw_indexed_indirect(
+    fn validate_draw_mesh_tasks_indirect_count(
         &self,
         indirect_buffer: &dyn BufferAccess,
-        draw_count: u32,
+        count_buffer: &dyn BufferAccess,
+        _max_draw_count: u32,
         _stride: u32,
     ) -> Result<(), PipelineExecutionError> {
-        // VUID-vkCmdDrawIndexedIndirect-renderpass
+        // VUID-vkCmdDrawMeshTasksIndirectCountEXT-None-07074
+        if !self.device().enabled_features().mesh_shader {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::draw_mesh_tasks_indirect_count`",
+                requires_one_of: RequiresOneOf {
+                    features: &["mesh_shader"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdDrawMeshTasksIndirectCountEXT-None-04445
+        if !self.device().enabled_features().draw_indirect_count {
+            return Err(PipelineExecutionError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::draw_mesh_tasks_indirect_count`",
+                requires_one_of: RequiresOneOf {
+                    features: &["draw_indirect_count"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdDrawMeshTasksIndirectCountEXT-renderpass
         let render_pass_state = self
             .builder_state
             .render_pass
             .as_ref()
             .ok_or(PipelineExecutionError::ForbiddenOutsideRenderPass)?;
 
-        // VUID-vkCmdDrawIndexedIndirect-None-02700
+        // VUID-vkCmdDrawMeshTasksIndirectCountEXT-None-02700
         let pipeline = self
             .builder_state
             .pipeline_graphics
@@ -758,39 +2306,16 @@ where
             .ok_or(PipelineExecutionError::PipelineNotBound)?
             .as_ref();
 
+        self.validate_pipeline_graphics_mesh_shading(pipeline)?;
         self.validate_pipeline_descriptor_sets(pipeline)?;
         self.validate_pipeline_push_constants(pipeline.layout())?;
         self.validate_pipeline_graphics_dynamic_state(pipeline)?;
         self.validate_pipeline_graphics_render_pass(pipeline, render_pass_state)?;
-        self.validate_pipeline_graphics_vertex_buffers(pipeline, None, None)?;
 
-        self.validate_index_buffer(None)?;
         self.validate_indirect_buffer(indirect_buffer)?;
 
-        // VUID-vkCmdDrawIndexedIndirect-drawCount-02718
-        if draw_count > 1 && !self.device().enabled_features().multi_draw_indirect {
-            return Err(PipelineExecutionError::RequirementNotMet {
-                required_for: "`draw_count` is greater than `1`",
-                requires_one_of: RequiresOneOf {
-                    features: &["multi_draw_indirect"],
-                    ..Default::default()
-                },
-            });
-        }
-
-        let max = self
-            .device()
-            .physical_device()
-            .properties()
-            .max_draw_indirect_count;
-
-        // VUID-vkCmdDrawIndexedIndirect-drawCount-02719
-        if draw_count > max {
-            return Err(PipelineExecutionError::MaxDrawIndirectCountExceeded {
-                provided: draw_count,
-                max,
-            });
-        }
+        // VUID-vkCmdDrawMeshTasksIndirectCountEXT-countBuffer-02714
+        self.validate_indirect_buffer(count_buffer)?;
 
         // TODO: sync check
 
@@ -798,25 +2323,29 @@ where
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn draw_indexed_indirect_unchecked(
+    pub unsafe fn draw_mesh_tasks_indirect_count_unchecked(
         &mut self,
         indirect_buffer: Arc<dyn BufferAccess>,
-        draw_count: u32,
+        count_buffer: Arc<dyn BufferAccess>,
+        max_draw_count: u32,
         stride: u32,
     ) -> &mut Self {
         let indirect_buffer_inner = indirect_buffer.inner();
+        let count_buffer_inner = count_buffer.inner();
 
         let fns = self.device().fns();
-        (fns.v1_0.cmd_draw_indexed_indirect)(
+        (fns.ext_mesh_shader.cmd_draw_mesh_tasks_indirect_count_ext)(
             self.handle(),
             indirect_buffer_inner.buffer.handle(),
             indirect_buffer_inner.offset,
-            draw_count,
+            count_buffer_inner.buffer.handle(),
+            count_buffer_inner.offset,
+            max_draw_count,
             stride,
         );
 
         let command_index = self.next_command_index;
-        let command_name = "draw_indexed_indirect";
+        let command_name = "draw_mesh_tasks_indirect_count";
         let pipeline = self
             .builder_state
             .pipeline_graphics
@@ -830,24 +2359,17 @@ where
             &self.builder_state.descriptor_sets,
             pipeline,
         );
-        record_vertex_buffers_access(
-            &mut self.resources_usage_state,
-            command_index,
-            command_name,
-            &self.builder_state.vertex_buffers,
-            pipeline,
-        );
-        record_index_buffer_access(
+        record_indirect_buffer_access(
             &mut self.resources_usage_state,
             command_index,
             command_name,
-            &self.builder_state.index_buffer,
+            &indirect_buffer,
         );
-        record_indirect_buffer_access(
+        record_indirect_count_buffer_access(
             &mut self.resources_usage_state,
             command_index,
             command_name,
-            &indirect_buffer,
+            &count_buffer,
         );
         record_subpass_attachments_access(
             &mut self.resources_usage_state,
@@ -865,11 +2387,30 @@ where
         }
 
         self.resources.push(Box::new(indirect_buffer));
+        self.resources.push(Box::new(count_buffer));
 
         self.next_command_index += 1;
         self
     }
 
+    fn validate_multi_draw_count(&self, draw_count: usize) -> Result<(), PipelineExecutionError> {
+        let max = self
+            .device()
+            .physical_device()
+            .properties()
+            .max_multi_draw_count
+            .unwrap_or(0);
+
+        if draw_count as u64 >= max as u64 {
+            return Err(PipelineExecutionError::MaxMultiDrawCountExceeded {
+                provided: draw_count.try_into().unwrap_or(u32::MAX),
+                max,
+            });
+        }
+
+        Ok(())
+    }
+
     fn validate_index_buffer(
         &self,
         indices: Option<(u32, u32)>,
@@ -914,6 +2455,47 @@ where
         Ok(())
     }
 
+    fn validate_indirect_count_buffer(
+        &self,
+        indirect_buffer: &dyn BufferAccess,
+        count_buffer: &dyn BufferAccess,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> Result<(), PipelineExecutionError> {
+        // VUID-vkCmdDrawIndirectCount-commonparent
+        assert_eq!(self.device(), count_buffer.device());
+
+        // VUID-vkCmdDrawIndirectCount-countBuffer-02714
+        if !count_buffer
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER)
+        {
+            return Err(PipelineExecutionError::IndirectCountBufferMissingUsage);
+        }
+
+        let offset = count_buffer.inner().offset;
+
+        // VUID-vkCmdDrawIndirectCount-countBufferOffset-02716
+        if offset % 4 != 0 {
+            return Err(PipelineExecutionError::IndirectCountBufferOffsetNotAligned { offset });
+        }
+
+        // VUID-vkCmdDrawIndirectCount-countBufferOffset-04129
+        // Ensured by the type of the count buffer.
+
+        // VUID-vkCmdDrawIndirectCount-maxDrawCount-03111
+        let commands_in_buffer = (indirect_buffer.size() / stride as DeviceSize) as u32;
+
+        if max_draw_count > commands_in_buffer {
+            return Err(PipelineExecutionError::IndirectBufferRangeOutOfBounds {
+                max_draw_count,
+                commands_in_buffer,
+            });
+        }
+
+        Ok(())
+    }
+
     fn validate_pipeline_descriptor_sets(
         &self,
         pipeline: &impl Pipeline,
@@ -1311,6 +2893,15 @@ where
                         check_sampler,
                     )?;
                 }
+                DescriptorBindingResources::AccelerationStructure(elements) => {
+                    validate_resources(
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        elements,
+                        |_, _| Ok(()),
+                    )?;
+                }
             }
         }
 
@@ -1673,7 +3264,20 @@ where
                         return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
                     }
                 }
-                DynamicState::VertexInput => todo!(),
+                DynamicState::VertexInput => {
+                    // VUID-vkCmdDraw-None-04914
+                    let vertex_input_state = if let Some(state) = self.builder_state.vertex_input.as_ref() {
+                        state
+                    } else {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    };
+
+                    // VUID-vkCmdDraw-None-04914
+                    // VUID-vkCmdDraw-Input-08734
+                    if let Some(interface) = pipeline.vertex_shader_input_interface() {
+                        validate_dynamic_vertex_input(interface, vertex_input_state)?;
+                    }
+                }
                 DynamicState::VertexInputBindingStride => todo!(),
                 DynamicState::Viewport => {
                     for num in 0..pipeline.viewport_state().unwrap().count().unwrap() {
@@ -1896,13 +3500,31 @@ where
         Ok(())
     }
 
+    fn validate_pipeline_graphics_mesh_shading(
+        &self,
+        pipeline: &GraphicsPipeline,
+    ) -> Result<(), PipelineExecutionError> {
+        // VUID-vkCmdDrawMeshTasksEXT-stage-06480
+        if pipeline.shader(ShaderStage::Mesh).is_none() {
+            return Err(PipelineExecutionError::MeshShaderPipelineNotBound);
+        }
+
+        Ok(())
+    }
+
     fn validate_pipeline_graphics_vertex_buffers(
         &self,
         pipeline: &GraphicsPipeline,
         vertices: Option<(u32, u32)>,
         instances: Option<(u32, u32)>,
     ) -> Result<(), PipelineExecutionError> {
-        let vertex_input = pipeline.vertex_input_state();
+        // If the vertex input state is dynamic, the state that was set is used instead of the
+        // (empty) state of the pipeline.
+        let vertex_input = if pipeline.dynamic_state(DynamicState::VertexInput) == Some(true) {
+            self.builder_state.vertex_input.as_ref().unwrap()
+        } else {
+            pipeline.vertex_input_state()
+        };
         let mut vertices_in_buffers: Option<u64> = None;
         let mut instances_in_buffers: Option<u64> = None;
 
@@ -2197,6 +3819,26 @@ fn record_descriptor_sets_access(
                 }
             }
             DescriptorBindingResources::Sampler(_) => (),
+            DescriptorBindingResources::AccelerationStructure(elements) => {
+                for (index, element) in elements.iter().enumerate() {
+                    if let Some(acceleration_structure) = element {
+                        let buffer = acceleration_structure.buffer();
+                        let buffer_inner = buffer.inner();
+                        let (use_ref, stage_access_iter) = use_iter(index as u32);
+
+                        let range = buffer_inner.offset..buffer_inner.offset + buffer.size();
+
+                        for stage_access in stage_access_iter {
+                            resources_usage_state.record_buffer_access(
+                                &use_ref,
+                                buffer_inner.buffer,
+                                range.clone(),
+                                stage_access,
+                            );
+                        }
+                    }
+                }
+            }
         }
     }
 }
@@ -2205,11 +3847,19 @@ fn record_vertex_buffers_access(
     resources_usage_state: &mut ResourcesState,
     command_index: usize,
     command_name: &'static str,
-    vertex_buffers_state: &HashMap<u32, Arc<dyn BufferAccess>>,
+    builder_state: &CommandBufferBuilderState,
     pipeline: &GraphicsPipeline,
 ) {
-    for &binding in pipeline.vertex_input_state().bindings.keys() {
-        let buffer = &vertex_buffers_state[&binding];
+    // If the vertex input state is dynamic, the state that was set is used instead of the
+    // (empty) state of the pipeline.
+    let vertex_input = if pipeline.dynamic_state(DynamicState::VertexInput) == Some(true) {
+        builder_state.vertex_input.as_ref().unwrap()
+    } else {
+        pipeline.vertex_input_state()
+    };
+
+    for &binding in vertex_input.bindings.keys() {
+        let buffer = &builder_state.vertex_buffers[&binding];
         let buffer_inner = buffer.inner();
         let use_ref = ResourceUseRef {
             command_index,
@@ -2281,6 +3931,96 @@ fn record_indirect_buffer_access(
     );
 }
 
+fn record_indirect_count_buffer_access(
+    resources_usage_state: &mut ResourcesState,
+    command_index: usize,
+    command_name: &'static str,
+    buffer: &Arc<dyn BufferAccess>,
+) {
+    let buffer_inner = buffer.inner();
+    let use_ref = ResourceUseRef {
+        command_index,
+        command_name,
+        resource_in_command: ResourceInCommand::IndirectCountBuffer,
+        secondary_use_ref: None,
+    };
+
+    let mut range = 0..buffer.size(); // TODO: take range from draw command
+    range.start += buffer_inner.offset;
+    range.end += buffer_inner.offset;
+    resources_usage_state.record_buffer_access(
+        &use_ref,
+        buffer_inner.buffer,
+        range,
+        PipelineStageAccess::DrawIndirect_IndirectCommandRead,
+    );
+}
+
+fn record_transform_feedback_buffers_access(
+    resources_usage_state: &mut ResourcesState,
+    command_index: usize,
+    command_name: &'static str,
+    builder_state: &CommandBufferBuilderState,
+    pipeline: &GraphicsPipeline,
+) {
+    if !builder_state.transform_feedback_active {
+        return;
+    }
+
+    let outputs = match pipeline.transform_feedback_outputs() {
+        Some(x) => x,
+        None => return,
+    };
+
+    let mut bindings: SmallVec<[u32; 4]> = outputs.iter().map(|output| output.buffer).collect();
+    bindings.sort_unstable();
+    bindings.dedup();
+
+    for binding in bindings {
+        let buffer = &builder_state.transform_feedback_buffers[&binding];
+        let buffer_inner = buffer.inner();
+        let use_ref = ResourceUseRef {
+            command_index,
+            command_name,
+            resource_in_command: ResourceInCommand::TransformFeedbackBuffer { binding },
+            secondary_use_ref: None,
+        };
+
+        let mut range = 0..buffer.size();
+        range.start += buffer_inner.offset;
+        range.end += buffer_inner.offset;
+        resources_usage_state.record_buffer_access(
+            &use_ref,
+            buffer_inner.buffer,
+            range,
+            PipelineStageAccess::TransformFeedback_TransformFeedbackWrite,
+        );
+    }
+}
+
+fn record_counter_buffer_access(
+    resources_usage_state: &mut ResourcesState,
+    command_index: usize,
+    command_name: &'static str,
+    buffer: &Arc<dyn BufferAccess>,
+) {
+    let buffer_inner = buffer.inner();
+    let use_ref = ResourceUseRef {
+        command_index,
+        command_name,
+        resource_in_command: ResourceInCommand::TransformFeedbackCounterBuffer { index: 0 },
+        secondary_use_ref: None,
+    };
+
+    let start = buffer_inner.offset;
+    resources_usage_state.record_buffer_access(
+        &use_ref,
+        buffer_inner.buffer,
+        start..start + size_of::<u32>() as DeviceSize,
+        PipelineStageAccess::DrawIndirect_TransformFeedbackCounterRead,
+    );
+}
+
 fn record_subpass_attachments_access(
     resources_usage_state: &mut ResourcesState,
     command_index: usize,
@@ -116,6 +116,30 @@ where
                     return Err(QueryError::InvalidFlags);
                 }
             }
+            QueryType::TransformFeedbackStream => {
+                // VUID-vkCmdBeginQuery-queryType-02327
+                if !queue_family_properties
+                    .queue_flags
+                    .intersects(QueueFlags::GRAPHICS)
+                {
+                    return Err(QueryError::NotSupportedByQueueFamily);
+                }
+
+                // VUID-vkCmdBeginQuery-queryType-02328
+                if !device
+                    .physical_device()
+                    .properties()
+                    .transform_feedback_queries
+                    .unwrap_or(false)
+                {
+                    return Err(QueryError::TransformFeedbackQueriesNotSupported);
+                }
+
+                // VUID-vkCmdBeginQuery-queryType-00800
+                if flags.intersects(QueryControlFlags::PRECISE) {
+                    return Err(QueryError::InvalidFlags);
+                }
+            }
             // VUID-vkCmdBeginQuery-queryType-02804
             QueryType::Timestamp => return Err(QueryError::NotPermitted),
         }
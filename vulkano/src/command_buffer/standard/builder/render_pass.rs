@@ -15,8 +15,8 @@ use super::{
 };
 use crate::{
     command_buffer::{
-        allocator::CommandBufferAllocator, PrimaryCommandBuffer, ResourceInCommand, ResourceUseRef,
-        SubpassContents,
+        allocator::CommandBufferAllocator, PrimaryCommandBuffer, RenderingFlags, ResourceInCommand,
+        ResourceUseRef, SubpassContents,
     },
     device::{DeviceOwned, QueueFlags},
     format::{ClearColorValue, ClearValue, NumericType},
@@ -81,6 +81,12 @@ where
             return Err(RenderPassError::ForbiddenInsideRenderPass);
         }
 
+        // VUID-VkSubmitInfo-pCommandBuffers-06017
+        // VUID-VkSubmitInfo2-commandBuffer-06012
+        if self.suspends_render_pass {
+            return Err(RenderPassError::RenderPassSuspended);
+        }
+
         let RenderPassBeginInfo {
             render_pass,
             framebuffer,
@@ -472,6 +478,7 @@ where
                 }
                 .into(),
             });
+            self.resumes_render_pass.get_or_insert(false);
         }
 
         // Start of first subpass
@@ -548,6 +555,21 @@ where
             return Err(RenderPassError::QueryIsActive);
         }
 
+        // VUID-vkCmdEndConditionalRenderingEXT-None-01987
+        if self
+            .builder_state
+            .conditional_rendering
+            .as_ref()
+            .map_or(false, |state| state.in_subpass)
+        {
+            return Err(RenderPassError::ConditionalRenderingActive);
+        }
+
+        // VUID-vkCmdNextSubpass2-None-02350
+        if self.builder_state.transform_feedback_active {
+            return Err(RenderPassError::TransformFeedbackActive);
+        }
+
         // VUID-vkCmdNextSubpass2-commandBuffer-cmdpool
         debug_assert!(self
             .queue_family_properties()
@@ -704,6 +726,21 @@ where
             return Err(RenderPassError::QueryIsActive);
         }
 
+        // VUID-vkCmdEndConditionalRenderingEXT-None-01987
+        if self
+            .builder_state
+            .conditional_rendering
+            .as_ref()
+            .map_or(false, |state| state.in_subpass)
+        {
+            return Err(RenderPassError::ConditionalRenderingActive);
+        }
+
+        // VUID-vkCmdEndRenderPass2-None-02352
+        if self.builder_state.transform_feedback_active {
+            return Err(RenderPassError::TransformFeedbackActive);
+        }
+
         // VUID-vkCmdEndRenderPass2-commandBuffer-cmdpool
         debug_assert!(self
             .queue_family_properties()
@@ -834,11 +871,25 @@ where
             ref depth_attachment,
             ref stencil_attachment,
             contents,
+            flags,
             _ne: _,
         } = rendering_info;
 
         // VUID-VkRenderingInfo-flags-parameter
         contents.validate_device(device)?;
+        flags.validate_device(device)?;
+
+        // VUID-VkSubmitInfo-pCommandBuffers-06017
+        // VUID-VkSubmitInfo2-commandBuffer-06012
+        if self.suspends_render_pass {
+            return Err(RenderPassError::RenderPassSuspended);
+        }
+
+        // VUID-VkSubmitInfo-pCommandBuffers-06193
+        // VUID-VkSubmitInfo2-commandBuffer-06192
+        if flags.intersects(RenderingFlags::RESUMING) && self.resumes_render_pass.is_some() {
+            return Err(RenderPassError::ResumingNotFirstRenderPass);
+        }
 
         // VUID-vkCmdBeginRendering-commandBuffer-06068
         if self.inheritance_info.is_some() && contents == SubpassContents::SecondaryCommandBuffers {
@@ -1364,6 +1415,7 @@ where
                 ref depth_attachment,
                 ref stencil_attachment,
                 contents,
+                flags,
                 _ne,
             } = &rendering_info;
 
@@ -1421,7 +1473,7 @@ where
             let stencil_attachment_vk = map_attachment_info(stencil_attachment);
 
             let rendering_info_vk = ash::vk::RenderingInfo {
-                flags: contents.into(),
+                flags: ash::vk::RenderingFlags::from(contents) | flags.into(),
                 render_area: ash::vk::Rect2D {
                     offset: ash::vk::Offset2D {
                         x: render_area_offset[0] as i32,
@@ -1465,9 +1517,12 @@ where
 
                 render_pass: BeginRenderingState {
                     pipeline_used: false,
+                    suspending: flags.intersects(RenderingFlags::SUSPENDING),
                 }
                 .into(),
             });
+            self.resumes_render_pass
+                .get_or_insert(flags.intersects(RenderingFlags::RESUMING));
         }
 
         let RenderingInfo {
@@ -1580,6 +1635,21 @@ where
             return Err(RenderPassError::ForbiddenWithInheritedRenderPass);
         }
 
+        // VUID-vkCmdEndConditionalRenderingEXT-None-01987
+        if self
+            .builder_state
+            .conditional_rendering
+            .as_ref()
+            .map_or(false, |state| state.in_subpass)
+        {
+            return Err(RenderPassError::ConditionalRenderingActive);
+        }
+
+        // VUID-vkCmdEndRendering-None-06781
+        if self.builder_state.transform_feedback_active {
+            return Err(RenderPassError::TransformFeedbackActive);
+        }
+
         // VUID-vkCmdEndRendering-None-06161
         // VUID-vkCmdEndRendering-commandBuffer-06162
         match &render_pass_state.render_pass {
@@ -1628,6 +1698,10 @@ where
             render_pass_state,
         );
 
+        if let RenderPassStateType::BeginRendering(state) = &render_pass_state.render_pass {
+            self.suspends_render_pass = state.suspending;
+        }
+
         self.builder_state.render_pass = None;
 
         self.next_command_index += 1;
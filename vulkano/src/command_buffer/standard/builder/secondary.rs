@@ -64,6 +64,11 @@ where
             return Err(ExecuteCommandsError::NotSupportedByQueueFamily);
         }
 
+        // VUID-vkCmdExecuteCommands-None-02286
+        if self.builder_state.transform_feedback_active {
+            return Err(ExecuteCommandsError::TransformFeedbackActive);
+        }
+
         // TODO:
         // VUID-vkCmdExecuteCommands-pCommandBuffers-00094
 
@@ -312,10 +317,21 @@ where
                         );
                     }
                 }
-                QueryType::Timestamp => (),
+                QueryType::Timestamp | QueryType::TransformFeedbackStream => (),
             }
         }
 
+        // VUID?
+        if self.builder_state.conditional_rendering.is_some()
+            && !command_buffer.inheritance_info().conditional_rendering
+        {
+            return Err(
+                ExecuteCommandsError::ConditionalRenderingInheritanceRequired {
+                    command_buffer_index,
+                },
+            );
+        }
+
         // TODO:
         // VUID-vkCmdExecuteCommands-pCommandBuffers-00091
         // VUID-vkCmdExecuteCommands-pCommandBuffers-00092
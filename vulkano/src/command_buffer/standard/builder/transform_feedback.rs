@@ -0,0 +1,524 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{CommandBufferBuilder, ResourcesState, TransformFeedbackError};
+use crate::{
+    buffer::{BufferAccess, BufferUsage},
+    command_buffer::{allocator::CommandBufferAllocator, ResourceInCommand, ResourceUseRef},
+    device::{DeviceOwned, QueueFlags},
+    sync::PipelineStageAccess,
+    DeviceSize, RequiresOneOf, VulkanObject,
+};
+use smallvec::SmallVec;
+use std::{mem::size_of, sync::Arc};
+
+impl<L, A> CommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Binds transform feedback buffers for future transform feedback operations.
+    ///
+    /// While transform feedback is active, the vertex data that is output by the last
+    /// pre-rasterization shader stage of the bound graphics pipeline is written to the buffers
+    /// bound with this command, as described by the [`transform_feedback_outputs`] of the
+    /// pipeline. Each buffer is written from its start up to its end.
+    ///
+    /// The [`transform_feedback`] feature must be enabled on the device.
+    ///
+    /// [`transform_feedback_outputs`]: crate::pipeline::GraphicsPipeline::transform_feedback_outputs
+    /// [`transform_feedback`]: crate::device::Features::transform_feedback
+    #[inline]
+    pub fn bind_transform_feedback_buffers(
+        &mut self,
+        first_binding: u32,
+        buffers: impl IntoIterator<Item = Arc<dyn BufferAccess>>,
+    ) -> Result<&mut Self, TransformFeedbackError> {
+        let buffers: SmallVec<[_; 4]> = buffers.into_iter().collect();
+        self.validate_bind_transform_feedback_buffers(first_binding, &buffers)?;
+
+        unsafe { Ok(self.bind_transform_feedback_buffers_unchecked(first_binding, buffers)) }
+    }
+
+    fn validate_bind_transform_feedback_buffers(
+        &self,
+        first_binding: u32,
+        buffers: &[Arc<dyn BufferAccess>],
+    ) -> Result<(), TransformFeedbackError> {
+        let device = self.device();
+
+        // VUID-vkCmdBindTransformFeedbackBuffersEXT-transformFeedback-02355
+        if !device.enabled_features().transform_feedback {
+            return Err(TransformFeedbackError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::bind_transform_feedback_buffers`",
+                requires_one_of: RequiresOneOf {
+                    features: &["transform_feedback"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdBindTransformFeedbackBuffersEXT-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(TransformFeedbackError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdBindTransformFeedbackBuffersEXT-None-02365
+        if self.builder_state.transform_feedback_active {
+            return Err(TransformFeedbackError::TransformFeedbackActive);
+        }
+
+        let properties = device.physical_device().properties();
+        let max_buffers = properties.max_transform_feedback_buffers.unwrap_or(0);
+
+        // VUID-vkCmdBindTransformFeedbackBuffersEXT-firstBinding-02356
+        // VUID-vkCmdBindTransformFeedbackBuffersEXT-firstBinding-02357
+        if first_binding + buffers.len() as u32 > max_buffers {
+            return Err(
+                TransformFeedbackError::MaxTransformFeedbackBuffersExceeded {
+                    binding_count: first_binding + buffers.len() as u32,
+                    max: max_buffers,
+                },
+            );
+        }
+
+        for (index, buffer) in buffers.iter().enumerate() {
+            let index = index as u32;
+
+            // VUID-vkCmdBindTransformFeedbackBuffersEXT-commonparent
+            assert_eq!(device, buffer.device());
+
+            // VUID-vkCmdBindTransformFeedbackBuffersEXT-pBuffers-02360
+            if !buffer
+                .usage()
+                .intersects(BufferUsage::TRANSFORM_FEEDBACK_BUFFER)
+            {
+                return Err(TransformFeedbackError::BufferMissingUsage { index });
+            }
+
+            // VUID-vkCmdBindTransformFeedbackBuffersEXT-pOffsets-02359
+            let offset = buffer.inner().offset;
+
+            if offset % 4 != 0 {
+                return Err(TransformFeedbackError::BufferOffsetNotAligned { index, offset });
+            }
+
+            // VUID-vkCmdBindTransformFeedbackBuffersEXT-pSize-02361
+            let max_size = properties.max_transform_feedback_buffer_size.unwrap_or(0);
+
+            if buffer.size() > max_size {
+                return Err(
+                    TransformFeedbackError::MaxTransformFeedbackBufferSizeExceeded {
+                        index,
+                        size: buffer.size(),
+                        max: max_size,
+                    },
+                );
+            }
+
+            // VUID-vkCmdBindTransformFeedbackBuffersEXT-pOffsets-02358
+            // VUID-vkCmdBindTransformFeedbackBuffersEXT-pSizes-02362
+            // Ensured by the `BufferAccess` trait.
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn bind_transform_feedback_buffers_unchecked(
+        &mut self,
+        first_binding: u32,
+        buffers: impl IntoIterator<Item = Arc<dyn BufferAccess>>,
+    ) -> &mut Self {
+        let buffers: SmallVec<[_; 4]> = buffers.into_iter().collect();
+
+        if buffers.is_empty() {
+            return self;
+        }
+
+        let mut buffers_vk: SmallVec<[_; 4]> = SmallVec::with_capacity(buffers.len());
+        let mut offsets_vk: SmallVec<[_; 4]> = SmallVec::with_capacity(buffers.len());
+        let mut sizes_vk: SmallVec<[_; 4]> = SmallVec::with_capacity(buffers.len());
+
+        for buffer in &buffers {
+            let buffer_inner = buffer.inner();
+            buffers_vk.push(buffer_inner.buffer.handle());
+            offsets_vk.push(buffer_inner.offset);
+            sizes_vk.push(buffer.size());
+        }
+
+        let fns = self.device().fns();
+        (fns.ext_transform_feedback
+            .cmd_bind_transform_feedback_buffers_ext)(
+            self.handle(),
+            first_binding,
+            buffers_vk.len() as u32,
+            buffers_vk.as_ptr(),
+            offsets_vk.as_ptr(),
+            sizes_vk.as_ptr(),
+        );
+
+        self.resources.reserve(buffers.len());
+
+        for (binding, buffer) in (first_binding..).zip(buffers) {
+            self.builder_state
+                .transform_feedback_buffers
+                .insert(binding, buffer.clone());
+            self.resources.push(Box::new(buffer));
+        }
+
+        self.next_command_index += 1;
+        self
+    }
+
+    /// Begins transform feedback.
+    ///
+    /// While transform feedback is active, draw commands write the vertex data that is output by
+    /// the bound graphics pipeline to the buffers bound with
+    /// [`bind_transform_feedback_buffers`](Self::bind_transform_feedback_buffers). The bound
+    /// graphics pipeline and transform feedback buffers can not be changed until transform
+    /// feedback is ended again.
+    ///
+    /// Each element of `counter_buffers` corresponds to a transform feedback buffer binding,
+    /// starting at `first_counter_buffer`. If an element is `Some`, the 32-bit value at the start
+    /// of the counter buffer holds the byte offset in the transform feedback buffer at which
+    /// writing is resumed. If an element is `None`, or if `counter_buffers` is empty, writing
+    /// starts at the beginning of the buffer.
+    ///
+    /// Transform feedback must be begun and ended inside the same subpass.
+    ///
+    /// # Safety
+    ///
+    /// - Appropriate synchronization must be provided for all buffers that are accessed by the
+    ///   command.
+    #[inline]
+    pub unsafe fn begin_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: impl IntoIterator<Item = Option<Arc<dyn BufferAccess>>>,
+    ) -> Result<&mut Self, TransformFeedbackError> {
+        let counter_buffers: SmallVec<[_; 4]> = counter_buffers.into_iter().collect();
+        self.validate_begin_transform_feedback(first_counter_buffer, &counter_buffers)?;
+
+        unsafe {
+            Ok(self.begin_transform_feedback_unchecked(first_counter_buffer, counter_buffers))
+        }
+    }
+
+    fn validate_begin_transform_feedback(
+        &self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Arc<dyn BufferAccess>>],
+    ) -> Result<(), TransformFeedbackError> {
+        let device = self.device();
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-transformFeedback-02366
+        if !device.enabled_features().transform_feedback {
+            return Err(TransformFeedbackError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::begin_transform_feedback`",
+                requires_one_of: RequiresOneOf {
+                    features: &["transform_feedback"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(TransformFeedbackError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-renderpass
+        let render_pass_state = self
+            .builder_state
+            .render_pass
+            .as_ref()
+            .ok_or(TransformFeedbackError::ForbiddenOutsideRenderPass)?;
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-None-02373
+        if render_pass_state.rendering_info.view_mask != 0 {
+            return Err(TransformFeedbackError::MultiviewEnabled);
+        }
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-None-02367
+        if self.builder_state.transform_feedback_active {
+            return Err(TransformFeedbackError::TransformFeedbackActive);
+        }
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-None-06233
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .ok_or(TransformFeedbackError::PipelineNotBound)?;
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-None-04128
+        let outputs = pipeline
+            .transform_feedback_outputs()
+            .ok_or(TransformFeedbackError::PipelineTransformFeedbackNotDeclared)?;
+
+        for output in outputs {
+            // VUID?
+            if !self
+                .builder_state
+                .transform_feedback_buffers
+                .contains_key(&output.buffer)
+            {
+                return Err(TransformFeedbackError::BufferNotBound {
+                    binding: output.buffer,
+                });
+            }
+        }
+
+        self.validate_transform_feedback_counter_buffers(first_counter_buffer, counter_buffers)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn begin_transform_feedback_unchecked(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: impl IntoIterator<Item = Option<Arc<dyn BufferAccess>>>,
+    ) -> &mut Self {
+        let counter_buffers: SmallVec<[_; 4]> = counter_buffers.into_iter().collect();
+        let (counter_buffers_vk, counter_buffer_offsets_vk) =
+            transform_feedback_counter_buffers_vk(&counter_buffers);
+
+        let fns = self.device().fns();
+        (fns.ext_transform_feedback.cmd_begin_transform_feedback_ext)(
+            self.handle(),
+            first_counter_buffer,
+            counter_buffers_vk.len() as u32,
+            counter_buffers_vk.as_ptr(),
+            counter_buffer_offsets_vk.as_ptr(),
+        );
+
+        let command_index = self.next_command_index;
+        let command_name = "begin_transform_feedback";
+        record_counter_buffers_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &counter_buffers,
+            PipelineStageAccess::TransformFeedback_TransformFeedbackCounterRead,
+        );
+
+        self.builder_state.transform_feedback_active = true;
+
+        for counter_buffer in counter_buffers.into_iter().flatten() {
+            self.resources.push(Box::new(counter_buffer));
+        }
+
+        self.next_command_index += 1;
+        self
+    }
+
+    /// Ends transform feedback, that was previously begun with
+    /// [`begin_transform_feedback`](Self::begin_transform_feedback).
+    ///
+    /// Each element of `counter_buffers` corresponds to a transform feedback buffer binding,
+    /// starting at `first_counter_buffer`. If an element is `Some`, the byte offset in the
+    /// transform feedback buffer just past the last written vertex is stored as a 32-bit value at
+    /// the start of the counter buffer.
+    ///
+    /// # Safety
+    ///
+    /// - Appropriate synchronization must be provided for all buffers that are accessed by the
+    ///   command.
+    #[inline]
+    pub unsafe fn end_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: impl IntoIterator<Item = Option<Arc<dyn BufferAccess>>>,
+    ) -> Result<&mut Self, TransformFeedbackError> {
+        let counter_buffers: SmallVec<[_; 4]> = counter_buffers.into_iter().collect();
+        self.validate_end_transform_feedback(first_counter_buffer, &counter_buffers)?;
+
+        unsafe { Ok(self.end_transform_feedback_unchecked(first_counter_buffer, counter_buffers)) }
+    }
+
+    fn validate_end_transform_feedback(
+        &self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Arc<dyn BufferAccess>>],
+    ) -> Result<(), TransformFeedbackError> {
+        // VUID-vkCmdEndTransformFeedbackEXT-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(TransformFeedbackError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdEndTransformFeedbackEXT-renderpass
+        if self.builder_state.render_pass.is_none() {
+            return Err(TransformFeedbackError::ForbiddenOutsideRenderPass);
+        }
+
+        // VUID-vkCmdEndTransformFeedbackEXT-None-02375
+        if !self.builder_state.transform_feedback_active {
+            return Err(TransformFeedbackError::TransformFeedbackNotActive);
+        }
+
+        self.validate_transform_feedback_counter_buffers(first_counter_buffer, counter_buffers)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn end_transform_feedback_unchecked(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: impl IntoIterator<Item = Option<Arc<dyn BufferAccess>>>,
+    ) -> &mut Self {
+        let counter_buffers: SmallVec<[_; 4]> = counter_buffers.into_iter().collect();
+        let (counter_buffers_vk, counter_buffer_offsets_vk) =
+            transform_feedback_counter_buffers_vk(&counter_buffers);
+
+        let fns = self.device().fns();
+        (fns.ext_transform_feedback.cmd_end_transform_feedback_ext)(
+            self.handle(),
+            first_counter_buffer,
+            counter_buffers_vk.len() as u32,
+            counter_buffers_vk.as_ptr(),
+            counter_buffer_offsets_vk.as_ptr(),
+        );
+
+        let command_index = self.next_command_index;
+        let command_name = "end_transform_feedback";
+        record_counter_buffers_access(
+            &mut self.resources_usage_state,
+            command_index,
+            command_name,
+            &counter_buffers,
+            PipelineStageAccess::TransformFeedback_TransformFeedbackCounterWrite,
+        );
+
+        self.builder_state.transform_feedback_active = false;
+
+        for counter_buffer in counter_buffers.into_iter().flatten() {
+            self.resources.push(Box::new(counter_buffer));
+        }
+
+        self.next_command_index += 1;
+        self
+    }
+
+    fn validate_transform_feedback_counter_buffers(
+        &self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Arc<dyn BufferAccess>>],
+    ) -> Result<(), TransformFeedbackError> {
+        let device = self.device();
+        let max_buffers = device
+            .physical_device()
+            .properties()
+            .max_transform_feedback_buffers
+            .unwrap_or(0);
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-firstCounterBuffer-02368
+        // VUID-vkCmdBeginTransformFeedbackEXT-firstCounterBuffer-02369
+        // VUID-vkCmdEndTransformFeedbackEXT-firstCounterBuffer-02376
+        // VUID-vkCmdEndTransformFeedbackEXT-firstCounterBuffer-02377
+        if first_counter_buffer + counter_buffers.len() as u32 > max_buffers {
+            return Err(
+                TransformFeedbackError::MaxTransformFeedbackBuffersExceeded {
+                    binding_count: first_counter_buffer + counter_buffers.len() as u32,
+                    max: max_buffers,
+                },
+            );
+        }
+
+        for (index, counter_buffer) in counter_buffers.iter().enumerate() {
+            let counter_buffer = match counter_buffer {
+                Some(x) => x,
+                None => continue,
+            };
+            let index = index as u32;
+
+            // VUID-vkCmdBeginTransformFeedbackEXT-commonparent
+            // VUID-vkCmdEndTransformFeedbackEXT-commonparent
+            assert_eq!(device, counter_buffer.device());
+
+            // VUID-vkCmdBeginTransformFeedbackEXT-pCounterBuffers-02372
+            // VUID-vkCmdEndTransformFeedbackEXT-pCounterBuffers-02380
+            if !counter_buffer
+                .usage()
+                .intersects(BufferUsage::TRANSFORM_FEEDBACK_COUNTER_BUFFER)
+            {
+                return Err(TransformFeedbackError::CounterBufferMissingUsage { index });
+            }
+
+            // VUID-vkCmdBeginTransformFeedbackEXT-pCounterBufferOffsets-02370
+            // VUID-vkCmdEndTransformFeedbackEXT-pCounterBufferOffsets-02378
+            if counter_buffer.size() < size_of::<u32>() as DeviceSize {
+                return Err(TransformFeedbackError::CounterBufferTooSmall {
+                    index,
+                    buffer_size: counter_buffer.size(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn transform_feedback_counter_buffers_vk(
+    counter_buffers: &[Option<Arc<dyn BufferAccess>>],
+) -> (SmallVec<[ash::vk::Buffer; 4]>, SmallVec<[DeviceSize; 4]>) {
+    counter_buffers
+        .iter()
+        .map(|counter_buffer| match counter_buffer {
+            Some(counter_buffer) => {
+                let buffer_inner = counter_buffer.inner();
+                (buffer_inner.buffer.handle(), buffer_inner.offset)
+            }
+            None => (ash::vk::Buffer::null(), 0),
+        })
+        .unzip()
+}
+
+fn record_counter_buffers_access(
+    resources_usage_state: &mut ResourcesState,
+    command_index: usize,
+    command_name: &'static str,
+    counter_buffers: &[Option<Arc<dyn BufferAccess>>],
+    stage_access: PipelineStageAccess,
+) {
+    for (index, counter_buffer) in (0..).zip(counter_buffers) {
+        let counter_buffer = match counter_buffer {
+            Some(x) => x,
+            None => continue,
+        };
+        let buffer_inner = counter_buffer.inner();
+        let use_ref = ResourceUseRef {
+            command_index,
+            command_name,
+            resource_in_command: ResourceInCommand::TransformFeedbackCounterBuffer { index },
+            secondary_use_ref: None,
+        };
+
+        let start = buffer_inner.offset;
+        resources_usage_state.record_buffer_access(
+            &use_ref,
+            buffer_inner.buffer,
+            start..start + size_of::<u32>() as DeviceSize,
+            stage_access,
+        );
+    }
+}
@@ -33,9 +33,11 @@ use crate::{
             depth_stencil::{CompareOp, StencilOps},
             input_assembly::{IndexType, PrimitiveTopology},
             rasterization::{CullMode, DepthBias, FrontFace, LineStipple},
+            vertex_input::VertexInputState,
             viewport::{Scissor, Viewport},
         },
         ComputePipeline, DynamicState, GraphicsPipeline, PipelineBindPoint, PipelineLayout,
+        RayTracingPipeline,
     },
     range_map::RangeMap,
     range_set::RangeSet,
@@ -974,7 +976,10 @@ pub(in crate::command_buffer) struct CurrentState {
     pub(in crate::command_buffer) index_buffer: Option<(Arc<dyn BufferAccess>, IndexType)>,
     pub(in crate::command_buffer) pipeline_compute: Option<Arc<ComputePipeline>>,
     pub(in crate::command_buffer) pipeline_graphics: Option<Arc<GraphicsPipeline>>,
+    pub(in crate::command_buffer) pipeline_ray_tracing: Option<Arc<RayTracingPipeline>>,
     pub(in crate::command_buffer) vertex_buffers: HashMap<u32, Arc<dyn BufferAccess>>,
+    pub(in crate::command_buffer) transform_feedback_buffers: HashMap<u32, Arc<dyn BufferAccess>>,
+    pub(in crate::command_buffer) transform_feedback_active: bool,
 
     pub(in crate::command_buffer) push_constants: RangeSet<u32>,
     pub(in crate::command_buffer) push_constants_pipeline_layout: Option<Arc<PipelineLayout>>,
@@ -1005,6 +1010,7 @@ pub(in crate::command_buffer) struct CurrentState {
     pub(in crate::command_buffer) stencil_reference: StencilStateDynamic,
     pub(in crate::command_buffer) stencil_test_enable: Option<bool>,
     pub(in crate::command_buffer) stencil_write_mask: StencilStateDynamic,
+    pub(in crate::command_buffer) vertex_input: Option<VertexInputState>,
     pub(in crate::command_buffer) viewport: HashMap<u32, Viewport>,
     pub(in crate::command_buffer) viewport_with_count: Option<SmallVec<[Viewport; 2]>>,
 }
@@ -1046,7 +1052,7 @@ impl CurrentState {
                 DynamicState::StencilReference => self.stencil_reference = Default::default(),
                 DynamicState::StencilTestEnable => self.stencil_test_enable = None,
                 DynamicState::StencilWriteMask => self.stencil_write_mask = Default::default(),
-                DynamicState::VertexInput => (), // TODO:
+                DynamicState::VertexInput => self.vertex_input = None,
                 DynamicState::VertexInputBindingStride => (), // TODO:
                 DynamicState::Viewport => self.viewport.clear(),
                 DynamicState::ViewportCoarseSampleOrder => (), // TODO:
@@ -1232,6 +1238,12 @@ impl<'a> CommandBufferBuilderState<'a> {
         self.current_state.pipeline_graphics.as_ref()
     }
 
+    /// Returns the ray tracing pipeline currently bound, or `None` if nothing has been bound yet.
+    #[inline]
+    pub fn pipeline_ray_tracing(&self) -> Option<&'a Arc<RayTracingPipeline>> {
+        self.current_state.pipeline_ray_tracing.as_ref()
+    }
+
     /// Returns the vertex buffer currently bound to a given binding slot number, or `None` if
     /// nothing has been bound yet.
     #[inline]
@@ -1239,6 +1251,21 @@ impl<'a> CommandBufferBuilderState<'a> {
         self.current_state.vertex_buffers.get(&binding_num)
     }
 
+    /// Returns the transform feedback buffer currently bound to a given binding slot number, or
+    /// `None` if nothing has been bound yet.
+    #[inline]
+    pub fn transform_feedback_buffer(&self, binding_num: u32) -> Option<&'a Arc<dyn BufferAccess>> {
+        self.current_state
+            .transform_feedback_buffers
+            .get(&binding_num)
+    }
+
+    /// Returns whether transform feedback is currently active.
+    #[inline]
+    pub fn transform_feedback_active(&self) -> bool {
+        self.current_state.transform_feedback_active
+    }
+
     /// Returns a set containing push constant bytes that have been set.
     #[inline]
     pub fn push_constants(&self) -> &'a RangeSet<u32> {
@@ -1416,6 +1443,12 @@ impl<'a> CommandBufferBuilderState<'a> {
         self.current_state.stencil_write_mask
     }
 
+    /// Returns the current vertex input state, or `None` if nothing has been set yet.
+    #[inline]
+    pub fn vertex_input(&self) -> Option<&'a VertexInputState> {
+        self.current_state.vertex_input.as_ref()
+    }
+
     /// Returns the current viewport for a given viewport slot, or `None` if nothing has been set
     /// yet.
     #[inline]
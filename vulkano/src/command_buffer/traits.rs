@@ -9,18 +9,15 @@
 
 use super::{
     CommandBufferInheritanceInfo, CommandBufferResourcesUsage, CommandBufferState,
-    CommandBufferUsage, SecondaryCommandBufferResourcesUsage, SemaphoreSubmitInfo, SubmitInfo,
+    CommandBufferUsage, SecondaryCommandBufferResourcesUsage, SubmitInfo,
 };
 use crate::{
     buffer::sys::Buffer,
     device::{Device, DeviceOwned, Queue},
     image::{sys::Image, ImageLayout},
     swapchain::Swapchain,
-    sync::{
-        future::{
-            now, AccessCheckError, AccessError, FlushError, GpuFuture, NowFuture, SubmitAnyBuilder,
-        },
-        PipelineStages,
+    sync::future::{
+        now, AccessCheckError, AccessError, FlushError, GpuFuture, NowFuture, SubmitAnyBuilder,
     },
     DeviceSize, SafeDeref, VulkanObject,
 };
@@ -42,6 +39,14 @@ pub unsafe trait PrimaryCommandBufferAbstract:
     /// Returns the usage of this command buffer.
     fn usage(&self) -> CommandBufferUsage;
 
+    /// Returns whether this command buffer is protected.
+    ///
+    /// Protected command buffers can only be submitted to queues that were created with the
+    /// [`QueueCreateFlags::PROTECTED`] flag.
+    ///
+    /// [`QueueCreateFlags::PROTECTED`]: crate::device::QueueCreateFlags::PROTECTED
+    fn protected(&self) -> bool;
+
     /// Executes this command buffer on a queue.
     ///
     /// This function returns an object that implements the `GpuFuture` trait. See the
@@ -123,6 +128,12 @@ pub unsafe trait PrimaryCommandBufferAbstract:
 
     #[doc(hidden)]
     fn resources_usage(&self) -> &CommandBufferResourcesUsage;
+
+    #[doc(hidden)]
+    fn resumes_render_pass(&self) -> bool;
+
+    #[doc(hidden)]
+    fn suspends_render_pass(&self) -> bool;
 }
 
 impl Debug for dyn PrimaryCommandBufferAbstract {
@@ -140,6 +151,10 @@ where
         (**self).usage()
     }
 
+    fn protected(&self) -> bool {
+        (**self).protected()
+    }
+
     fn state(&self) -> MutexGuard<'_, CommandBufferState> {
         (**self).state()
     }
@@ -147,6 +162,14 @@ where
     fn resources_usage(&self) -> &CommandBufferResourcesUsage {
         (**self).resources_usage()
     }
+
+    fn resumes_render_pass(&self) -> bool {
+        (**self).resumes_render_pass()
+    }
+
+    fn suspends_render_pass(&self) -> bool {
+        (**self).suspends_render_pass()
+    }
 }
 
 pub unsafe trait SecondaryCommandBufferAbstract:
@@ -155,6 +178,9 @@ pub unsafe trait SecondaryCommandBufferAbstract:
     /// Returns the usage of this command buffer.
     fn usage(&self) -> CommandBufferUsage;
 
+    /// Returns whether this command buffer is protected.
+    fn protected(&self) -> bool;
+
     /// Returns a `CommandBufferInheritance` value describing the properties that the command
     /// buffer inherits from its parent primary command buffer.
     fn inheritance_info(&self) -> &CommandBufferInheritanceInfo;
@@ -185,6 +211,10 @@ where
         (**self).usage()
     }
 
+    fn protected(&self) -> bool {
+        (**self).protected()
+    }
+
     fn inheritance_info(&self) -> &CommandBufferInheritanceInfo {
         (**self).inheritance_info()
     }
@@ -235,25 +265,14 @@ where
                 },
                 None,
             ),
-            SubmitAnyBuilder::SemaphoresWait(semaphores) => {
-                SubmitAnyBuilder::CommandBuffer(
-                    SubmitInfo {
-                        wait_semaphores: semaphores
-                            .into_iter()
-                            .map(|semaphore| {
-                                SemaphoreSubmitInfo {
-                                    // TODO: correct stages ; hard
-                                    stages: PipelineStages::ALL_COMMANDS,
-                                    ..SemaphoreSubmitInfo::semaphore(semaphore)
-                                }
-                            })
-                            .collect(),
-                        command_buffers: vec![self.command_buffer.clone()],
-                        ..Default::default()
-                    },
-                    None,
-                )
-            }
+            SubmitAnyBuilder::SemaphoresWait(semaphores) => SubmitAnyBuilder::CommandBuffer(
+                SubmitInfo {
+                    wait_semaphores: semaphores.into_iter().collect(),
+                    command_buffers: vec![self.command_buffer.clone()],
+                    ..Default::default()
+                },
+                None,
+            ),
             SubmitAnyBuilder::CommandBuffer(mut submit_info, fence) => {
                 // FIXME: add pipeline barrier
                 submit_info
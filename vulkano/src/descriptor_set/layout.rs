@@ -35,6 +35,7 @@ pub struct DescriptorSetLayout {
     handle: ash::vk::DescriptorSetLayout,
     device: Arc<Device>,
     id: NonZeroU64,
+    imported_handle: bool,
 
     bindings: BTreeMap<u32, DescriptorSetLayoutBinding>,
     push_descriptor: bool,
@@ -62,6 +63,7 @@ impl DescriptorSetLayout {
             handle,
             device,
             id: Self::next_id(),
+            imported_handle: false,
             bindings,
             push_descriptor,
             descriptor_counts,
@@ -74,6 +76,8 @@ impl DescriptorSetLayout {
     ///
     /// - `handle` must be a valid Vulkan object handle created from `device`.
     /// - `create_info` must match the info used to create the object.
+    /// - `handle` must have been created with a null `pAllocator`, as it is destroyed with a null
+    ///   `pAllocator` when dropped.
     #[inline]
     pub unsafe fn from_handle(
         device: Arc<Device>,
@@ -99,6 +103,7 @@ impl DescriptorSetLayout {
             handle,
             device,
             id: Self::next_id(),
+            imported_handle: true,
             bindings,
             push_descriptor,
             descriptor_counts,
@@ -362,7 +367,7 @@ impl DescriptorSetLayout {
             (fns.v1_0.create_descriptor_set_layout)(
                 device.handle(),
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -435,7 +440,8 @@ impl Drop for DescriptorSetLayout {
             (fns.v1_0.destroy_descriptor_set_layout)(
                 self.device.handle(),
                 self.handle,
-                ptr::null(),
+                self.device
+                    .destroy_allocation_callbacks(self.imported_handle),
             );
         }
     }
@@ -873,11 +879,11 @@ vulkan_enum! {
         device_extensions: [ext_inline_uniform_block],
     },*/
 
-    /* TODO: enable
-    // TODO: document
+    /// Gives read-only access to an acceleration structure, for performing ray queries and ray
+    /// tracing.
     AccelerationStructure = ACCELERATION_STRUCTURE_KHR {
         device_extensions: [khr_acceleration_structure],
-    },*/
+    },
 
     /* TODO: enable
     // TODO: document
@@ -86,6 +86,7 @@ pub use self::{
 };
 use self::{layout::DescriptorSetLayout, sys::UnsafeDescriptorSet};
 use crate::{
+    acceleration_structure::AccelerationStructure,
     buffer::{view::BufferViewAbstract, BufferAccess},
     descriptor_set::layout::DescriptorType,
     device::DeviceOwned,
@@ -201,8 +202,14 @@ impl DescriptorSetInner {
         }
 
         if !write_descriptor_set.is_empty() {
-            for (info, write) in descriptor_write_info
+            let acceleration_structure_infos: SmallVec<[_; 8]> = descriptor_write_info
                 .iter()
+                .map(DescriptorWriteInfo::to_vulkan_acceleration_structure)
+                .collect();
+
+            for ((info, acceleration_structure_info), write) in descriptor_write_info
+                .iter()
+                .zip(&acceleration_structure_infos)
                 .zip(write_descriptor_set.iter_mut())
             {
                 match info {
@@ -218,6 +225,10 @@ impl DescriptorSetInner {
                         write.descriptor_count = info.len() as u32;
                         write.p_texel_buffer_view = info.as_ptr();
                     }
+                    DescriptorWriteInfo::AccelerationStructure(info) => {
+                        write.descriptor_count = info.len() as u32;
+                        write.p_next = acceleration_structure_info as *const _ as *const _;
+                    }
                 }
             }
         }
@@ -295,6 +306,9 @@ impl DescriptorSetResources {
                             DescriptorBindingResources::ImageView(smallvec![None; count])
                         }
                     }
+                    DescriptorType::AccelerationStructure => {
+                        DescriptorBindingResources::AccelerationStructure(smallvec![None; count])
+                    }
                     DescriptorType::Sampler => {
                         if binding.immutable_samplers.is_empty() {
                             DescriptorBindingResources::Sampler(smallvec![None; count])
@@ -347,6 +361,7 @@ pub enum DescriptorBindingResources {
     ImageView(Elements<Arc<dyn ImageViewAbstract>>),
     ImageViewSampler(Elements<(Arc<dyn ImageViewAbstract>, Arc<Sampler>)>),
     Sampler(Elements<Arc<Sampler>>),
+    AccelerationStructure(Elements<Arc<AccelerationStructure>>),
 }
 
 type Elements<T> = SmallVec<[Option<T>; 1]>;
@@ -406,6 +421,10 @@ impl DescriptorBindingResources {
                 DescriptorBindingResources::Sampler(resources),
                 WriteDescriptorSetElements::Sampler(elements),
             ) => write_resources(first, resources, elements),
+            (
+                DescriptorBindingResources::AccelerationStructure(resources),
+                WriteDescriptorSetElements::AccelerationStructure(elements),
+            ) => write_resources(first, resources, elements),
             _ => panic!(
                 "descriptor write for binding {} has wrong resource type",
                 write.binding(),
@@ -37,6 +37,7 @@ pub struct DescriptorPool {
     handle: ash::vk::DescriptorPool,
     device: Arc<Device>,
     id: NonZeroU64,
+    imported_handle: bool,
 
     max_sets: u32,
     pool_sizes: HashMap<DescriptorType, u32>,
@@ -104,7 +105,7 @@ impl DescriptorPool {
                 (fns.v1_0.create_descriptor_pool)(
                     device.handle(),
                     &create_info,
-                    ptr::null(),
+                    device.allocation_callbacks(),
                     output.as_mut_ptr(),
                 )
                 .result()
@@ -117,6 +118,7 @@ impl DescriptorPool {
             handle,
             device,
             id: Self::next_id(),
+            imported_handle: false,
             max_sets,
             pool_sizes,
             can_free_descriptor_sets,
@@ -130,6 +132,8 @@ impl DescriptorPool {
     ///
     /// - `handle` must be a valid Vulkan object handle created from `device`.
     /// - `create_info` must match the info used to create the object.
+    /// - `handle` must have been created with a null `pAllocator`, as it is destroyed with a null
+    ///   `pAllocator` when dropped.
     #[inline]
     pub unsafe fn from_handle(
         device: Arc<Device>,
@@ -147,6 +151,7 @@ impl DescriptorPool {
             handle,
             device,
             id: Self::next_id(),
+            imported_handle: true,
             max_sets,
             pool_sizes,
             can_free_descriptor_sets,
@@ -325,7 +330,12 @@ impl Drop for DescriptorPool {
     fn drop(&mut self) {
         unsafe {
             let fns = self.device.fns();
-            (fns.v1_0.destroy_descriptor_pool)(self.device.handle(), self.handle, ptr::null());
+            (fns.v1_0.destroy_descriptor_pool)(
+                self.device.handle(),
+                self.handle,
+                self.device
+                    .destroy_allocation_callbacks(self.imported_handle),
+            );
         }
     }
 }
@@ -79,7 +79,16 @@ impl UnsafeDescriptorSet {
         }
 
         // Set the info pointers separately.
-        for (info, write) in infos.iter().zip(writes.iter_mut()) {
+        let acceleration_structure_infos: SmallVec<[_; 8]> = infos
+            .iter()
+            .map(DescriptorWriteInfo::to_vulkan_acceleration_structure)
+            .collect();
+
+        for ((info, acceleration_structure_info), write) in infos
+            .iter()
+            .zip(&acceleration_structure_infos)
+            .zip(writes.iter_mut())
+        {
             match info {
                 DescriptorWriteInfo::Image(info) => {
                     write.descriptor_count = info.len() as u32;
@@ -93,6 +102,10 @@ impl UnsafeDescriptorSet {
                     write.descriptor_count = info.len() as u32;
                     write.p_texel_buffer_view = info.as_ptr();
                 }
+                DescriptorWriteInfo::AccelerationStructure(info) => {
+                    write.descriptor_count = info.len() as u32;
+                    write.p_next = acceleration_structure_info as *const _ as *const _;
+                }
             }
 
             debug_assert!(write.descriptor_count != 0);
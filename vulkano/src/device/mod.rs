@@ -106,23 +106,35 @@ pub(crate) use self::{features::FeaturesFfi, properties::PropertiesFfi};
 pub use self::{
     features::{FeatureRestriction, FeatureRestrictionError, Features},
     properties::Properties,
-    queue::{Queue, QueueError, QueueFamilyProperties, QueueFlags, QueueGuard},
+    queue::{
+        BindSparseError, Queue, QueueCreateFlags, QueueError, QueueFamilyProperties, QueueFlags,
+        QueueGuard,
+    },
+};
+use crate::{
+    acceleration_structure::{
+        AccelerationStructureBuildGeometryInfo, AccelerationStructureBuildSizesInfo,
+        AccelerationStructureBuildType, AccelerationStructureError, AccelerationStructureType,
+    },
+    instance::Instance,
+    memory::{
+        host::{AllocationCallbacks, HostAllocator},
+        ExternalMemoryHandleType,
+    },
+    sync::fence::FenceWaiter,
+    DeviceSize, OomError, RequirementNotMet, RequiresOneOf, Version, VulkanError, VulkanObject,
 };
 pub use crate::{
     device::extensions::DeviceExtensions,
     extensions::{ExtensionRestriction, ExtensionRestrictionError},
     fns::DeviceFunctions,
 };
-use crate::{
-    instance::Instance, memory::ExternalMemoryHandleType, OomError, RequirementNotMet,
-    RequiresOneOf, Version, VulkanError, VulkanObject,
-};
 use ash::vk::Handle;
 use parking_lot::Mutex;
 use smallvec::SmallVec;
 use std::{
     error::Error,
-    ffi::CString,
+    ffi::{c_void, CString},
     fmt::{Display, Error as FmtError, Formatter},
     fs::File,
     mem::MaybeUninit,
@@ -160,8 +172,10 @@ pub struct Device {
     // in that module.
     pub(crate) allocation_count: AtomicU32,
     fence_pool: Mutex<Vec<ash::vk::Fence>>,
+    fence_waiter: FenceWaiter,
     semaphore_pool: Mutex<Vec<ash::vk::Semaphore>>,
     event_pool: Mutex<Vec<ash::vk::Event>>,
+    allocation_callbacks: Option<AllocationCallbacks>,
 }
 
 impl Device {
@@ -172,7 +186,8 @@ impl Device {
     /// - Panics if `create_info.queues` is empty.
     /// - Panics if one of the queue families in `create_info.queues` doesn't belong to the given
     ///   physical device.
-    /// - Panics if `create_info.queues` contains multiple elements for the same queue family.
+    /// - Panics if `create_info.queues` contains multiple elements for the same queue family and
+    ///   with the same `flags`.
     /// - Panics if `create_info.queues` contains an element where `queues` is empty.
     /// - Panics if `create_info.queues` contains an element where `queues` contains a value that is
     ///   not between 0.0 and 1.0 inclusive.
@@ -184,10 +199,14 @@ impl Device {
             mut enabled_extensions,
             mut enabled_features,
             queue_create_infos,
+            host_allocator,
             _ne: _,
         } = create_info;
 
         let instance = physical_device.instance();
+        let allocation_callbacks = host_allocator
+            .or_else(|| instance.host_allocator().cloned())
+            .map(AllocationCallbacks::new);
         let fns_i = instance.fns();
         let api_version = physical_device.api_version();
 
@@ -196,6 +215,7 @@ impl Device {
         */
 
         struct QueueToGet {
+            flags: QueueCreateFlags,
             queue_family_index: u32,
             id: u32,
         }
@@ -211,11 +231,15 @@ impl Device {
 
         for queue_create_info in &queue_create_infos {
             let &QueueCreateInfo {
+                flags,
                 queue_family_index,
                 ref queues,
                 _ne: _,
             } = queue_create_info;
 
+            // VUID-VkDeviceQueueCreateInfo-flags-parameter
+            flags.validate_physical_device(&physical_device)?;
+
             // VUID-VkDeviceQueueCreateInfo-queueFamilyIndex-00381
             // TODO: return error instead of panicking?
             let queue_family_properties =
@@ -225,11 +249,36 @@ impl Device {
             assert!(
                 queue_create_infos
                     .iter()
-                    .filter(|qc2| qc2.queue_family_index == queue_family_index)
+                    .filter(|qc2| qc2.queue_family_index == queue_family_index
+                        && qc2.flags == flags)
                     .count()
                     == 1
             );
 
+            if flags.intersects(QueueCreateFlags::PROTECTED) {
+                // VUID-VkDeviceQueueCreateInfo-flags-02861
+                if !enabled_features.protected_memory {
+                    return Err(DeviceCreationError::RequirementNotMet {
+                        required_for: "`create_info.queue_create_infos` has an element where \
+                            `flags` contains `QueueCreateFlags::PROTECTED`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["protected_memory"],
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                // VUID-VkDeviceQueueCreateInfo-flags-06449
+                if !queue_family_properties
+                    .queue_flags
+                    .intersects(QueueFlags::PROTECTED)
+                {
+                    return Err(DeviceCreationError::QueueFamilyNotProtected {
+                        queue_family_index,
+                    });
+                }
+            }
+
             // VUID-VkDeviceQueueCreateInfo-queueCount-arraylength
             assert!(!queues.is_empty());
 
@@ -243,7 +292,7 @@ impl Device {
             }
 
             queue_create_infos_vk.push(ash::vk::DeviceQueueCreateInfo {
-                flags: ash::vk::DeviceQueueCreateFlags::empty(),
+                flags: flags.into(),
                 queue_family_index,
                 queue_count: queues.len() as u32,
                 p_queue_priorities: queues.as_ptr(), // borrows from queue_create
@@ -251,6 +300,7 @@ impl Device {
             });
             active_queue_family_indices.push(queue_family_index);
             queues_to_get.extend((0..queues.len() as u32).map(move |id| QueueToGet {
+                flags,
                 queue_family_index,
                 id,
             }));
@@ -390,7 +440,7 @@ impl Device {
             (fns_i.v1_0.create_device)(
                 physical_device.handle(),
                 &create_info,
-                ptr::null(),
+                AllocationCallbacks::as_ptr(allocation_callbacks.as_ref()),
                 output.as_mut_ptr(),
             )
             .result()
@@ -415,8 +465,10 @@ impl Device {
             active_queue_family_indices,
             allocation_count: AtomicU32::new(0),
             fence_pool: Mutex::new(Vec::new()),
+            fence_waiter: FenceWaiter::default(),
             semaphore_pool: Mutex::new(Vec::new()),
             event_pool: Mutex::new(Vec::new()),
+            allocation_callbacks,
         });
 
         // Iterator to return the queues
@@ -424,19 +476,41 @@ impl Device {
             let device = device.clone();
             queues_to_get.into_iter().map(
                 move |QueueToGet {
+                          flags,
                           queue_family_index,
                           id,
                       }| unsafe {
                     let fns = device.fns();
                     let mut output = MaybeUninit::uninit();
-                    (fns.v1_0.get_device_queue)(
-                        handle,
+
+                    if flags.is_empty() {
+                        (fns.v1_0.get_device_queue)(
+                            handle,
+                            queue_family_index,
+                            id,
+                            output.as_mut_ptr(),
+                        );
+                    } else {
+                        // Queues created with non-empty flags can only be retrieved with
+                        // `vkGetDeviceQueue2`.
+                        // VUID-vkGetDeviceQueue-flags-01841
+                        let info_vk = ash::vk::DeviceQueueInfo2 {
+                            flags: flags.into(),
+                            queue_family_index,
+                            queue_index: id,
+                            ..Default::default()
+                        };
+
+                        (fns.v1_1.get_device_queue2)(handle, &info_vk, output.as_mut_ptr());
+                    }
+
+                    Queue::from_handle(
+                        device.clone(),
+                        output.assume_init(),
+                        flags,
                         queue_family_index,
                         id,
-                        output.as_mut_ptr(),
-                    );
-
-                    Queue::from_handle(device.clone(), output.assume_init(), queue_family_index, id)
+                    )
                 },
             )
         };
@@ -490,6 +564,36 @@ impl Device {
         &self.enabled_features
     }
 
+    /// Returns the allocator that the Vulkan implementation uses for host memory, if one was
+    /// provided when creating the device or its instance.
+    #[inline]
+    pub fn host_allocator(&self) -> Option<&Arc<dyn HostAllocator>> {
+        self.allocation_callbacks
+            .as_ref()
+            .map(AllocationCallbacks::allocator)
+    }
+
+    // The `pAllocator` to use when creating and destroying device-level objects.
+    #[inline]
+    pub(crate) fn allocation_callbacks(&self) -> *const ash::vk::AllocationCallbacks {
+        AllocationCallbacks::as_ptr(self.allocation_callbacks.as_ref())
+    }
+
+    // The `pAllocator` to use when destroying a device-level object. Objects that were created
+    // outside of vulkano and imported with `from_handle` are assumed to have been created with a
+    // null `pAllocator`, so they must be destroyed with one as well.
+    #[inline]
+    pub(crate) fn destroy_allocation_callbacks(
+        &self,
+        imported_handle: bool,
+    ) -> *const ash::vk::AllocationCallbacks {
+        if imported_handle {
+            ptr::null()
+        } else {
+            self.allocation_callbacks()
+        }
+    }
+
     /// Returns the current number of active [`DeviceMemory`] allocations the device has.
     ///
     /// [`DeviceMemory`]: crate::memory::DeviceMemory
@@ -502,6 +606,10 @@ impl Device {
         &self.fence_pool
     }
 
+    pub(crate) fn fence_waiter(&self) -> &FenceWaiter {
+        &self.fence_waiter
+    }
+
     pub(crate) fn semaphore_pool(&self) -> &Mutex<Vec<ash::vk::Semaphore>> {
         &self.semaphore_pool
     }
@@ -510,6 +618,134 @@ impl Device {
         &self.event_pool
     }
 
+    /// Returns the sizes that are needed to build an acceleration structure with the given
+    /// geometries.
+    ///
+    /// `max_primitive_counts` must contain one element for each geometry in
+    /// `build_info.geometries`, specifying the maximum number of primitives that will be built
+    /// for that geometry. The buffers in `build_info` do not need to be set.
+    ///
+    /// The [`acceleration_structure`] feature must be enabled on the device.
+    ///
+    /// [`acceleration_structure`]: Features::acceleration_structure
+    #[inline]
+    pub fn acceleration_structure_build_sizes(
+        &self,
+        build_type: AccelerationStructureBuildType,
+        build_info: &AccelerationStructureBuildGeometryInfo,
+        max_primitive_counts: &[u32],
+    ) -> Result<AccelerationStructureBuildSizesInfo, AccelerationStructureError> {
+        self.validate_acceleration_structure_build_sizes(
+            build_type,
+            build_info,
+            max_primitive_counts,
+        )?;
+
+        unsafe {
+            Ok(self.acceleration_structure_build_sizes_unchecked(
+                build_type,
+                build_info,
+                max_primitive_counts,
+            ))
+        }
+    }
+
+    fn validate_acceleration_structure_build_sizes(
+        &self,
+        build_type: AccelerationStructureBuildType,
+        build_info: &AccelerationStructureBuildGeometryInfo,
+        max_primitive_counts: &[u32],
+    ) -> Result<(), AccelerationStructureError> {
+        if !self.enabled_features().acceleration_structure {
+            return Err(AccelerationStructureError::RequirementNotMet {
+                required_for: "`Device::acceleration_structure_build_sizes`",
+                requires_one_of: RequiresOneOf {
+                    features: &["acceleration_structure"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkGetAccelerationStructureBuildSizesKHR-buildType-parameter
+        build_type.validate_device(self)?;
+
+        // VUID-VkAccelerationStructureBuildGeometryInfoKHR-flags-parameter
+        build_info.flags.validate_device(self)?;
+
+        let geometry_count = build_info.geometries.len();
+
+        // VUID-vkGetAccelerationStructureBuildSizesKHR-pBuildInfo-03619
+        if max_primitive_counts.len() != geometry_count {
+            return Err(
+                AccelerationStructureError::MaxPrimitiveCountsLengthMismatch {
+                    max_primitive_counts_len: max_primitive_counts.len(),
+                    geometry_count,
+                },
+            );
+        }
+
+        let properties = self.physical_device().properties();
+
+        match build_info.ty() {
+            AccelerationStructureType::BottomLevel => {
+                let max_geometry_count = properties.max_geometry_count.unwrap_or(0);
+
+                // VUID-VkAccelerationStructureBuildGeometryInfoKHR-type-03793
+                if geometry_count as u64 > max_geometry_count {
+                    return Err(AccelerationStructureError::MaxGeometryCountExceeded {
+                        count: geometry_count as u64,
+                        max: max_geometry_count,
+                    });
+                }
+            }
+            _ => {
+                let max_instance_count = properties.max_instance_count.unwrap_or(0);
+
+                // VUID-vkGetAccelerationStructureBuildSizesKHR-pBuildInfo-03785
+                if max_primitive_counts[0] as u64 > max_instance_count {
+                    return Err(AccelerationStructureError::MaxInstanceCountExceeded {
+                        count: max_primitive_counts[0] as u64,
+                        max: max_instance_count,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn acceleration_structure_build_sizes_unchecked(
+        &self,
+        build_type: AccelerationStructureBuildType,
+        build_info: &AccelerationStructureBuildGeometryInfo,
+        max_primitive_counts: &[u32],
+    ) -> AccelerationStructureBuildSizesInfo {
+        let (mut build_info_vk, geometries_vk) = build_info.to_vulkan();
+        build_info_vk.geometry_count = geometries_vk.len() as u32;
+        build_info_vk.p_geometries = geometries_vk.as_ptr();
+
+        let mut build_sizes_info_vk = ash::vk::AccelerationStructureBuildSizesInfoKHR::default();
+
+        let fns = self.fns();
+        (fns.khr_acceleration_structure
+            .get_acceleration_structure_build_sizes_khr)(
+            self.handle,
+            build_type.into(),
+            &build_info_vk,
+            max_primitive_counts.as_ptr(),
+            &mut build_sizes_info_vk,
+        );
+
+        AccelerationStructureBuildSizesInfo {
+            acceleration_structure_size: build_sizes_info_vk.acceleration_structure_size,
+            update_scratch_size: build_sizes_info_vk.update_scratch_size,
+            build_scratch_size: build_sizes_info_vk.build_scratch_size,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+
     /// Retrieves the properties of an external file descriptor when imported as a given external
     /// handle type.
     ///
@@ -564,6 +800,99 @@ impl Device {
         }
     }
 
+    /// Retrieves the properties of a pointer to host memory when imported as a given external
+    /// handle type.
+    ///
+    /// The [`ext_external_memory_host`](DeviceExtensions::ext_external_memory_host) extension
+    /// must be enabled on the device, and `handle_type` must be either
+    /// [`ExternalMemoryHandleType::HostAllocation`] or
+    /// [`ExternalMemoryHandleType::HostMappedForeignMemory`].
+    ///
+    /// # Safety
+    ///
+    /// - If `handle_type` is [`ExternalMemoryHandleType::HostAllocation`], `host_pointer` must
+    ///   point to host memory that was allocated by the application.
+    /// - If `handle_type` is [`ExternalMemoryHandleType::HostMappedForeignMemory`],
+    ///   `host_pointer` must point to host-mapped foreign memory.
+    #[inline]
+    pub unsafe fn host_pointer_properties(
+        &self,
+        handle_type: ExternalMemoryHandleType,
+        host_pointer: *const c_void,
+    ) -> Result<MemoryHostPointerProperties, MemoryHostPointerPropertiesError> {
+        self.validate_host_pointer_properties(handle_type, host_pointer)?;
+
+        Ok(self.host_pointer_properties_unchecked(handle_type, host_pointer)?)
+    }
+
+    fn validate_host_pointer_properties(
+        &self,
+        handle_type: ExternalMemoryHandleType,
+        host_pointer: *const c_void,
+    ) -> Result<(), MemoryHostPointerPropertiesError> {
+        if !self.enabled_extensions().ext_external_memory_host {
+            return Err(MemoryHostPointerPropertiesError::RequirementNotMet {
+                required_for: "`Device::host_pointer_properties`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["ext_external_memory_host"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkGetMemoryHostPointerPropertiesEXT-handleType-parameter
+        handle_type.validate_device(self)?;
+
+        // VUID-vkGetMemoryHostPointerPropertiesEXT-handleType-01752
+        if !matches!(
+            handle_type,
+            ExternalMemoryHandleType::HostAllocation
+                | ExternalMemoryHandleType::HostMappedForeignMemory
+        ) {
+            return Err(MemoryHostPointerPropertiesError::HandleTypeNotSupported { handle_type });
+        }
+
+        let required_alignment = self
+            .physical_device()
+            .properties()
+            .min_imported_host_pointer_alignment
+            .unwrap();
+
+        // VUID-vkGetMemoryHostPointerPropertiesEXT-pHostPointer-01753
+        if host_pointer as DeviceSize % required_alignment != 0 {
+            return Err(MemoryHostPointerPropertiesError::HostPointerNotAligned {
+                required_alignment,
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn host_pointer_properties_unchecked(
+        &self,
+        handle_type: ExternalMemoryHandleType,
+        host_pointer: *const c_void,
+    ) -> Result<MemoryHostPointerProperties, VulkanError> {
+        let mut memory_host_pointer_properties = ash::vk::MemoryHostPointerPropertiesEXT::default();
+
+        let fns = self.fns();
+        (fns.ext_external_memory_host
+            .get_memory_host_pointer_properties_ext)(
+            self.handle,
+            handle_type.into(),
+            host_pointer,
+            &mut memory_host_pointer_properties,
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        Ok(MemoryHostPointerProperties {
+            memory_type_bits: memory_host_pointer_properties.memory_type_bits,
+        })
+    }
+
     /// Assigns a human-readable name to `object` for debugging purposes.
     ///
     /// If `object_name` is `None`, a previously set object name is removed.
@@ -620,18 +949,21 @@ impl Drop for Device {
     #[inline]
     fn drop(&mut self) {
         let fns = self.fns();
+        self.fence_waiter.shutdown();
 
         unsafe {
+            let allocation_callbacks = self.allocation_callbacks();
+
             for &raw_fence in self.fence_pool.lock().iter() {
-                (fns.v1_0.destroy_fence)(self.handle, raw_fence, ptr::null());
+                (fns.v1_0.destroy_fence)(self.handle, raw_fence, allocation_callbacks);
             }
             for &raw_sem in self.semaphore_pool.lock().iter() {
-                (fns.v1_0.destroy_semaphore)(self.handle, raw_sem, ptr::null());
+                (fns.v1_0.destroy_semaphore)(self.handle, raw_sem, allocation_callbacks);
             }
             for &raw_event in self.event_pool.lock().iter() {
-                (fns.v1_0.destroy_event)(self.handle, raw_event, ptr::null());
+                (fns.v1_0.destroy_event)(self.handle, raw_event, allocation_callbacks);
             }
-            (fns.v1_0.destroy_device)(self.handle, ptr::null());
+            (fns.v1_0.destroy_device)(self.handle, allocation_callbacks);
         }
     }
 }
@@ -673,6 +1005,13 @@ pub enum DeviceCreationError {
     ExtensionRestrictionNotMet(ExtensionRestrictionError),
     /// A restriction for a feature was not met.
     FeatureRestrictionNotMet(FeatureRestrictionError),
+    /// A requirement was not met.
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+    /// Protected queues were requested for a queue family that does not support them.
+    QueueFamilyNotProtected { queue_family_index: u32 },
 }
 
 impl Error for DeviceCreationError {}
@@ -711,6 +1050,20 @@ impl Display for DeviceCreationError {
             ),
             Self::ExtensionRestrictionNotMet(err) => err.fmt(f),
             Self::FeatureRestrictionNotMet(err) => err.fmt(f),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::QueueFamilyNotProtected { queue_family_index } => write!(
+                f,
+                "protected queues were requested for queue family {}, but it does not support \
+                protected queues",
+                queue_family_index,
+            ),
         }
     }
 }
@@ -742,6 +1095,15 @@ impl From<FeatureRestrictionError> for DeviceCreationError {
     }
 }
 
+impl From<RequirementNotMet> for DeviceCreationError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
 /// Parameters to create a new `Device`.
 #[derive(Clone, Debug)]
 pub struct DeviceCreateInfo {
@@ -766,6 +1128,17 @@ pub struct DeviceCreateInfo {
     /// The default value is empty, which must be overridden.
     pub queue_create_infos: Vec<QueueCreateInfo>,
 
+    /// The allocator that the Vulkan implementation should use for host memory.
+    ///
+    /// The allocator is used when creating and destroying the device and all objects that vulkano
+    /// creates from it. Objects that are imported with a `from_handle` function are not created
+    /// by vulkano, so they are destroyed without an allocator, and must therefore have been
+    /// created without one.
+    ///
+    /// The default value is `None`, which means that the allocator of the instance is used, if
+    /// it has one.
+    pub host_allocator: Option<Arc<dyn HostAllocator>>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -776,6 +1149,7 @@ impl Default for DeviceCreateInfo {
             enabled_extensions: DeviceExtensions::empty(),
             enabled_features: Features::empty(),
             queue_create_infos: Vec::new(),
+            host_allocator: None,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -784,6 +1158,14 @@ impl Default for DeviceCreateInfo {
 /// Parameters to create queues in a new `Device`.
 #[derive(Clone, Debug)]
 pub struct QueueCreateInfo {
+    /// Additional properties of the queues.
+    ///
+    /// A queue family can appear twice in `DeviceCreateInfo::queue_create_infos`, but only if
+    /// the two elements have different `flags`.
+    ///
+    /// The default value is empty.
+    pub flags: QueueCreateFlags,
+
     /// The index of the queue family to create queues for.
     ///
     /// The default value is `0`.
@@ -806,6 +1188,7 @@ impl Default for QueueCreateInfo {
     #[inline]
     fn default() -> Self {
         Self {
+            flags: QueueCreateFlags::empty(),
             queue_family_index: 0,
             queues: vec![0.5],
             _ne: crate::NonExhaustive(()),
@@ -909,6 +1292,90 @@ impl From<RequirementNotMet> for MemoryFdPropertiesError {
     }
 }
 
+/// The properties of a pointer to host memory when it is imported.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct MemoryHostPointerProperties {
+    /// A bitmask of the indices of memory types that can be used with the host pointer.
+    pub memory_type_bits: u32,
+}
+
+/// Error that can happen when calling `host_pointer_properties`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MemoryHostPointerPropertiesError {
+    /// No memory available on the host.
+    OutOfHostMemory,
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// The provided host pointer was not valid.
+    InvalidExternalHandle,
+
+    /// The provided external handle type is not supported for host pointers.
+    HandleTypeNotSupported {
+        handle_type: ExternalMemoryHandleType,
+    },
+
+    /// The provided host pointer was not a multiple of the
+    /// [`min_imported_host_pointer_alignment`](Properties::min_imported_host_pointer_alignment)
+    /// device property.
+    HostPointerNotAligned { required_alignment: DeviceSize },
+}
+
+impl Error for MemoryHostPointerPropertiesError {}
+
+impl Display for MemoryHostPointerPropertiesError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::OutOfHostMemory => write!(f, "no memory available on the host"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::InvalidExternalHandle => {
+                write!(f, "the provided host pointer was not valid")
+            }
+            Self::HandleTypeNotSupported { handle_type } => write!(
+                f,
+                "the provided external handle type ({:?}) is not supported for host pointers",
+                handle_type,
+            ),
+            Self::HostPointerNotAligned { required_alignment } => write!(
+                f,
+                "the provided host pointer was not a multiple of the \
+                `min_imported_host_pointer_alignment` device property ({})",
+                required_alignment,
+            ),
+        }
+    }
+}
+
+impl From<VulkanError> for MemoryHostPointerPropertiesError {
+    fn from(err: VulkanError) -> Self {
+        match err {
+            VulkanError::OutOfHostMemory => Self::OutOfHostMemory,
+            VulkanError::InvalidExternalHandle => Self::InvalidExternalHandle,
+            _ => panic!("Unexpected error value"),
+        }
+    }
+}
+
+impl From<RequirementNotMet> for MemoryHostPointerPropertiesError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::device::{
@@ -19,14 +19,14 @@ use crate::{
     },
     instance::Instance,
     macros::{vulkan_bitflags, vulkan_enum},
-    memory::MemoryProperties,
+    memory::{MemoryHeapBudget, MemoryProperties},
     swapchain::{
         ColorSpace, FullScreenExclusive, PresentMode, Surface, SurfaceApi, SurfaceCapabilities,
         SurfaceInfo, SurfaceTransforms,
     },
     sync::{
         fence::{ExternalFenceInfo, ExternalFenceProperties},
-        semaphore::{ExternalSemaphoreInfo, ExternalSemaphoreProperties},
+        semaphore::{ExternalSemaphoreInfo, ExternalSemaphoreProperties, SemaphoreType},
     },
     ExtensionProperties, RequirementNotMet, RequiresOneOf, Version, VulkanError, VulkanObject,
 };
@@ -742,12 +742,16 @@ impl PhysicalDevice {
 
         let &ExternalSemaphoreInfo {
             handle_type,
+            semaphore_type,
             _ne: _,
         } = info;
 
         // VUID-VkPhysicalDeviceExternalSemaphoreInfo-handleType-parameter
         handle_type.validate_physical_device(self)?;
 
+        // VUID-VkSemaphoreTypeCreateInfo-semaphoreType-parameter
+        semaphore_type.validate_physical_device(self)?;
+
         Ok(())
     }
 
@@ -763,13 +767,27 @@ impl PhysicalDevice {
 
                 let &ExternalSemaphoreInfo {
                     handle_type,
+                    semaphore_type,
                     _ne: _,
                 } = info;
 
-                let external_semaphore_info = ash::vk::PhysicalDeviceExternalSemaphoreInfo {
+                let mut external_semaphore_info = ash::vk::PhysicalDeviceExternalSemaphoreInfo {
                     handle_type: handle_type.into(),
                     ..Default::default()
                 };
+                let mut semaphore_type_create_info = None;
+
+                if semaphore_type != SemaphoreType::Binary {
+                    let next =
+                        semaphore_type_create_info.insert(ash::vk::SemaphoreTypeCreateInfo {
+                            semaphore_type: semaphore_type.into(),
+                            initial_value: 0,
+                            ..Default::default()
+                        });
+
+                    next.p_next = external_semaphore_info.p_next;
+                    external_semaphore_info.p_next = next as *const _ as *const _;
+                }
 
                 /* Output */
 
@@ -1194,6 +1212,91 @@ impl PhysicalDevice {
             })
     }
 
+    /// Retrieves the current budget and usage of each memory heap of the physical device.
+    ///
+    /// The returned `Vec` contains one element for each element of
+    /// [`memory_properties().memory_heaps`](MemoryProperties::memory_heaps), in the same order.
+    ///
+    /// These values may change during runtime, so the result only reflects the current situation
+    /// and is not cached.
+    ///
+    /// The [`ext_memory_budget`](crate::device::DeviceExtensions::ext_memory_budget) extension
+    /// must be supported by the physical device, and the instance API version must be at least
+    /// 1.1 or the [`khr_get_physical_device_properties2`] extension must be enabled on the
+    /// instance.
+    ///
+    /// [`khr_get_physical_device_properties2`]: crate::instance::InstanceExtensions::khr_get_physical_device_properties2
+    #[inline]
+    pub fn memory_budget(&self) -> Result<Vec<MemoryHeapBudget>, PhysicalDeviceError> {
+        self.validate_memory_budget()?;
+
+        unsafe { Ok(self.memory_budget_unchecked()) }
+    }
+
+    fn validate_memory_budget(&self) -> Result<(), PhysicalDeviceError> {
+        if !self.supported_extensions().ext_memory_budget {
+            return Err(PhysicalDeviceError::RequirementNotMet {
+                required_for: "`PhysicalDevice::memory_budget`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["ext_memory_budget"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        if !(self.instance.api_version() >= Version::V1_1
+            || self
+                .instance
+                .enabled_extensions()
+                .khr_get_physical_device_properties2)
+        {
+            return Err(PhysicalDeviceError::RequirementNotMet {
+                required_for: "`PhysicalDevice::memory_budget`",
+                requires_one_of: RequiresOneOf {
+                    api_version: Some(Version::V1_1),
+                    instance_extensions: &["khr_get_physical_device_properties2"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn memory_budget_unchecked(&self) -> Vec<MemoryHeapBudget> {
+        let mut memory_budget_properties_vk =
+            ash::vk::PhysicalDeviceMemoryBudgetPropertiesEXT::default();
+        let mut memory_properties2_vk = ash::vk::PhysicalDeviceMemoryProperties2 {
+            p_next: &mut memory_budget_properties_vk as *mut _ as *mut _,
+            ..Default::default()
+        };
+
+        let fns = self.instance.fns();
+
+        if self.instance.api_version() >= Version::V1_1 {
+            (fns.v1_1.get_physical_device_memory_properties2)(
+                self.handle,
+                &mut memory_properties2_vk,
+            );
+        } else {
+            (fns.khr_get_physical_device_properties2
+                .get_physical_device_memory_properties2_khr)(
+                self.handle,
+                &mut memory_properties2_vk,
+            );
+        }
+
+        let memory_heap_count = memory_properties2_vk.memory_properties.memory_heap_count as usize;
+
+        memory_budget_properties_vk.heap_budget[..memory_heap_count]
+            .iter()
+            .zip(&memory_budget_properties_vk.heap_usage[..memory_heap_count])
+            .map(|(&budget, &usage)| MemoryHeapBudget { budget, usage })
+            .collect()
+    }
+
     /// Queries whether the physical device supports presenting to QNX Screen surfaces from queues
     /// of the given queue family.
     ///
@@ -9,24 +9,31 @@
 
 use super::{Device, DeviceOwned};
 use crate::{
-    buffer::{sys::BufferState, BufferAccess},
+    buffer::{
+        sys::{BufferMemory, BufferState},
+        BufferAccess, BufferCreateFlags,
+    },
     command_buffer::{
         CommandBufferResourcesUsage, CommandBufferState, CommandBufferUsage, SemaphoreSubmitInfo,
         SubmitInfo,
     },
-    image::{sys::ImageState, ImageAccess},
+    image::{
+        sys::{ImageMemory, ImageState},
+        ImageAccess, ImageAspects, ImageCreateFlags,
+    },
     instance::debug::DebugUtilsLabel,
     macros::vulkan_bitflags,
     memory::{
-        BindSparseInfo, SparseBufferMemoryBind, SparseImageMemoryBind, SparseImageOpaqueMemoryBind,
+        BindSparseInfo, DeviceMemory, MemoryPropertyFlags, MemoryRequirements,
+        SparseBufferMemoryBind, SparseImageMemoryBind, SparseImageOpaqueMemoryBind,
     },
     swapchain::{PresentInfo, SwapchainPresentInfo},
     sync::{
         fence::{Fence, FenceState},
-        future::{AccessCheckError, FlushError, GpuFuture},
-        semaphore::SemaphoreState,
+        future::{AccessCheckError, AccessError, FlushError, GpuFuture},
+        semaphore::{Semaphore, SemaphoreState, SemaphoreType},
     },
-    OomError, RequirementNotMet, RequiresOneOf, Version, VulkanError, VulkanObject,
+    DeviceSize, OomError, RequirementNotMet, RequiresOneOf, Version, VulkanError, VulkanObject,
 };
 use ahash::HashMap;
 use parking_lot::{Mutex, MutexGuard};
@@ -48,6 +55,7 @@ use std::{
 pub struct Queue {
     handle: ash::vk::Queue,
     device: Arc<Device>,
+    flags: QueueCreateFlags,
     queue_family_index: u32,
     id: u32, // id within family
 
@@ -60,12 +68,14 @@ impl Queue {
     pub(super) fn from_handle(
         device: Arc<Device>,
         handle: ash::vk::Queue,
+        flags: QueueCreateFlags,
         queue_family_index: u32,
         id: u32,
     ) -> Arc<Self> {
         Arc::new(Queue {
             handle,
             device,
+            flags,
             queue_family_index,
             id,
             state: Mutex::new(Default::default()),
@@ -78,6 +88,12 @@ impl Queue {
         &self.device
     }
 
+    /// Returns the flags that the queue was created with.
+    #[inline]
+    pub fn flags(&self) -> QueueCreateFlags {
+        self.flags
+    }
+
     /// Returns the index of the queue family that this queue belongs to.
     #[inline]
     pub fn queue_family_index(&self) -> u32 {
@@ -154,6 +170,10 @@ impl<'a> QueueGuard<'a> {
         self.state.fence_signaled(fence)
     }
 
+    pub(crate) unsafe fn semaphore_signaled(&mut self, semaphore: &Semaphore, value: u64) {
+        self.state.semaphore_signaled(semaphore, value)
+    }
+
     /// Waits until all work on this queue has finished, then releases ownership of all resources
     /// that were in use by the queue.
     ///
@@ -167,6 +187,327 @@ impl<'a> QueueGuard<'a> {
         self.state.wait_idle(&self.queue.device, self.queue.handle)
     }
 
+    /// Binds or unbinds memory to parts of sparse buffers and images.
+    ///
+    /// The buffers and images must have been created with the `sparse_binding` flag, and
+    /// converted into a sparse resource with [`RawBuffer::into_sparse`] or
+    /// [`RawImage::into_sparse`]. The residency of each resource, as returned by
+    /// [`Buffer::is_resident`] and [`Image::is_resident`], is updated as soon as the bind operations
+    /// have been queued. Memory that is unbound or replaced by these operations is kept alive
+    /// until the operations have finished executing.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `fence` or any of the semaphores, resources or memory objects in `bind_infos`
+    ///   were not created from the same device as the queue.
+    /// - Panics if the `size` of a buffer or opaque image bind is zero, or if the `aspects` or
+    ///   any of the values in `extent` of an image bind are zero.
+    ///
+    /// [`RawBuffer::into_sparse`]: crate::buffer::sys::RawBuffer::into_sparse
+    /// [`RawImage::into_sparse`]: crate::image::sys::RawImage::into_sparse
+    /// [`Buffer::is_resident`]: crate::buffer::sys::Buffer::is_resident
+    /// [`Image::is_resident`]: crate::image::sys::Image::is_resident
+    #[inline]
+    pub fn bind_sparse(
+        &mut self,
+        bind_infos: impl IntoIterator<Item = BindSparseInfo>,
+        fence: Option<Arc<Fence>>,
+    ) -> Result<(), BindSparseError> {
+        let bind_infos: SmallVec<[_; 4]> = bind_infos.into_iter().collect();
+        let mut states = States::from_bind_infos(&bind_infos);
+        let fence = fence.as_ref().map(|fence| {
+            let state = fence.state();
+            (fence, state)
+        });
+
+        self.validate_bind_sparse(
+            &bind_infos,
+            fence.as_ref().map(|(fence, state)| (*fence, &**state)),
+            &states,
+        )?;
+
+        unsafe { Ok(self.bind_sparse_unchecked_locked(&bind_infos, fence, &mut states)?) }
+    }
+
+    fn validate_bind_sparse(
+        &self,
+        bind_infos: &[BindSparseInfo],
+        fence: Option<(&Arc<Fence>, &FenceState)>,
+        states: &States<'_>,
+    ) -> Result<(), BindSparseError> {
+        let device = &self.queue.device;
+        let queue_family_properties = &device.physical_device().queue_family_properties()
+            [self.queue.queue_family_index as usize];
+
+        // VUID-vkQueueBindSparse-queuetype
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::SPARSE_BINDING)
+        {
+            return Err(BindSparseError::QueueFamilyNotSupported);
+        }
+
+        if let Some((fence, state)) = fence {
+            // VUID-vkQueueBindSparse-commonparent
+            assert_eq!(device, fence.device());
+
+            // VUID-vkQueueBindSparse-fence-01114
+            if state.is_in_queue() {
+                return Err(BindSparseError::FenceInUse);
+            }
+
+            // VUID-vkQueueBindSparse-fence-01113
+            if state.is_signaled() == Some(true) {
+                return Err(BindSparseError::FenceNotUnsignaled);
+            }
+        }
+
+        // The signal state that binary semaphores will have after the preceding batches.
+        let mut semaphores_signaled: HashMap<ash::vk::Semaphore, bool> = HashMap::default();
+
+        for bind_info in bind_infos {
+            let BindSparseInfo {
+                wait_semaphores,
+                buffer_binds,
+                image_opaque_binds,
+                image_binds,
+                signal_semaphores,
+                _ne: _,
+            } = bind_info;
+
+            for semaphore in wait_semaphores {
+                // VUID-VkBindSparseInfo-commonparent
+                assert_eq!(device, semaphore.device());
+
+                // Timeline semaphores need `VkTimelineSemaphoreSubmitInfo`, which is not supported
+                // yet.
+                if semaphore.semaphore_type() != SemaphoreType::Binary {
+                    return Err(BindSparseError::SemaphoreTypeNotSupported);
+                }
+
+                // VUID-vkQueueBindSparse-pWaitSemaphores-01116
+                let is_signaled = semaphores_signaled
+                    .get(&semaphore.handle())
+                    .copied()
+                    .unwrap_or_else(|| {
+                        let state = &states.semaphores[&semaphore.handle()];
+                        !state.is_wait_pending()
+                            && (state.is_signal_pending() || state.is_signaled() != Some(false))
+                    });
+
+                if !is_signaled {
+                    return Err(BindSparseError::WaitSemaphoreNotSignaled);
+                }
+
+                semaphores_signaled.insert(semaphore.handle(), false);
+            }
+
+            for (buffer, memory_binds) in buffer_binds {
+                let buffer = buffer.inner().buffer;
+
+                // VUID-VkSparseBufferMemoryBindInfo-buffer-parameter
+                assert_eq!(device, buffer.device());
+
+                if !buffer.flags().intersects(BufferCreateFlags::SPARSE_BINDING)
+                    || !matches!(buffer.memory(), BufferMemory::Sparse)
+                {
+                    return Err(BindSparseError::ResourceNotSparse);
+                }
+
+                for memory_bind in memory_binds {
+                    let &SparseBufferMemoryBind {
+                        offset,
+                        size,
+                        ref memory,
+                    } = memory_bind;
+
+                    validate_sparse_memory_bind(
+                        device,
+                        offset,
+                        size,
+                        memory,
+                        buffer.memory_requirements(),
+                    )?;
+                }
+            }
+
+            for (image, memory_binds) in image_opaque_binds {
+                let image = image.inner().image;
+
+                // VUID-VkSparseImageOpaqueMemoryBindInfo-image-parameter
+                assert_eq!(device, image.device());
+
+                if !image.flags().intersects(ImageCreateFlags::SPARSE_BINDING)
+                    || !matches!(image.memory(), ImageMemory::Sparse(_))
+                {
+                    return Err(BindSparseError::ResourceNotSparse);
+                }
+
+                for memory_bind in memory_binds {
+                    let &SparseImageOpaqueMemoryBind {
+                        offset,
+                        size,
+                        ref memory,
+                        metadata,
+                    } = memory_bind;
+
+                    validate_sparse_memory_bind(
+                        device,
+                        offset,
+                        size,
+                        memory,
+                        &image.memory_requirements()[0],
+                    )?;
+
+                    if metadata {
+                        // VUID-VkSparseMemoryBind-flags-01103
+                        let is_in_mip_tail = (0..image.dimensions().array_layers()).any(|layer| {
+                            matches!(
+                                image.sparse_mip_tail_range(ImageAspects::METADATA, layer),
+                                Some(mip_tail_range)
+                                    if mip_tail_range.start <= offset
+                                        && offset + size <= mip_tail_range.end
+                            )
+                        });
+
+                        if !is_in_mip_tail {
+                            return Err(BindSparseError::MetadataBindOutsideMipTail);
+                        }
+                    }
+                }
+            }
+
+            for (image, memory_binds) in image_binds {
+                let image = image.inner().image;
+
+                // VUID-VkSparseImageMemoryBindInfo-image-parameter
+                assert_eq!(device, image.device());
+
+                if !image.flags().intersects(ImageCreateFlags::SPARSE_BINDING)
+                    || !matches!(image.memory(), ImageMemory::Sparse(_))
+                {
+                    return Err(BindSparseError::ResourceNotSparse);
+                }
+
+                // VUID-VkSparseImageMemoryBindInfo-image-02901
+                if !image.flags().intersects(ImageCreateFlags::SPARSE_RESIDENCY) {
+                    return Err(BindSparseError::ImageNotSparseResidency);
+                }
+
+                let memory_requirements = &image.memory_requirements()[0];
+
+                for memory_bind in memory_binds {
+                    let &SparseImageMemoryBind {
+                        aspects,
+                        mip_level,
+                        array_layer,
+                        offset,
+                        extent,
+                        ref memory,
+                    } = memory_bind;
+
+                    // VUID-VkImageSubresource-aspectMask-parameter
+                    aspects.validate_device(device)?;
+
+                    // VUID-VkImageSubresource-aspectMask-requiredbitmask
+                    assert!(!aspects.is_empty());
+
+                    // VUID-VkSparseImageMemoryBindInfo-subresource-01722
+                    // VUID-VkSparseImageMemoryBindInfo-subresource-01723
+                    // VUID-VkSparseImageMemoryBindInfo-subresource-01106
+                    let requirements = match image.sparse_requirements(aspects) {
+                        Some(requirements)
+                            if mip_level < image.mip_levels()
+                                && array_layer < image.dimensions().array_layers()
+                                && !aspects.intersects(ImageAspects::METADATA) =>
+                        {
+                            requirements
+                        }
+                        _ => return Err(BindSparseError::ImageSubresourceOutOfRange),
+                    };
+
+                    if mip_level >= requirements.image_mip_tail_first_lod {
+                        return Err(BindSparseError::ImageBindInMipTail);
+                    }
+
+                    let granularity = requirements.format_properties.image_granularity;
+                    let level_extent = image
+                        .dimensions()
+                        .mip_level_dimensions(mip_level)
+                        .unwrap()
+                        .width_height_depth();
+
+                    for i in 0..3 {
+                        assert!(extent[i] != 0);
+
+                        if offset[i] as u64 + extent[i] as u64 > level_extent[i] as u64 {
+                            return Err(BindSparseError::BindOutOfRange);
+                        }
+
+                        // VUID-VkSparseImageMemoryBind-offset-01107
+                        // VUID-VkSparseImageMemoryBind-offset-01109
+                        // VUID-VkSparseImageMemoryBind-offset-01111
+                        // VUID-VkSparseImageMemoryBind-extent-01108
+                        // VUID-VkSparseImageMemoryBind-extent-01110
+                        // VUID-VkSparseImageMemoryBind-extent-01112
+                        if offset[i] % granularity[i] != 0
+                            || (extent[i] % granularity[i] != 0
+                                && offset[i] + extent[i] != level_extent[i])
+                        {
+                            return Err(BindSparseError::ImageBindNotAligned { granularity });
+                        }
+                    }
+
+                    // VUID-VkSparseImageMemoryBind-memory-01105
+                    if let Some((memory, memory_offset)) = memory {
+                        let tile_count: DeviceSize = image
+                            .sparse_tile_ranges(aspects, mip_level, array_layer, offset, extent)
+                            .unwrap()
+                            .iter()
+                            .map(|tile_range| tile_range.end - tile_range.start)
+                            .sum();
+
+                        validate_sparse_memory(
+                            device,
+                            memory,
+                            *memory_offset,
+                            tile_count * memory_requirements.alignment,
+                            memory_requirements,
+                        )?;
+                    }
+                }
+            }
+
+            for semaphore in signal_semaphores {
+                // VUID-VkBindSparseInfo-commonparent
+                assert_eq!(device, semaphore.device());
+
+                // Timeline semaphores need `VkTimelineSemaphoreSubmitInfo`, which is not supported
+                // yet.
+                if semaphore.semaphore_type() != SemaphoreType::Binary {
+                    return Err(BindSparseError::SemaphoreTypeNotSupported);
+                }
+
+                // VUID-vkQueueBindSparse-pSignalSemaphores-01115
+                let is_unsignaled = semaphores_signaled
+                    .get(&semaphore.handle())
+                    .map(|&is_signaled| !is_signaled)
+                    .unwrap_or_else(|| {
+                        let state = &states.semaphores[&semaphore.handle()];
+                        !state.is_signal_pending() && state.is_signaled() != Some(true)
+                    });
+
+                if !is_unsignaled {
+                    return Err(BindSparseError::SignalSemaphoreNotUnsignaled);
+                }
+
+                semaphores_signaled.insert(semaphore.handle(), true);
+            }
+        }
+
+        Ok(())
+    }
+
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
     pub(crate) unsafe fn bind_sparse_unchecked(
         &mut self,
@@ -467,7 +808,74 @@ impl<'a> QueueGuard<'a> {
 
             for semaphore in signal_semaphores {
                 let state = states.semaphores.get_mut(&semaphore.handle()).unwrap();
-                state.add_queue_wait(self.queue);
+                state.add_queue_signal(self.queue);
+            }
+        }
+
+        // Update the residency of the resources. Any memory that was bound before is kept alive
+        // until the operation has finished.
+        let mut unbound_memory = Vec::new();
+
+        for bind_info in bind_infos {
+            for (buffer, memory_binds) in &bind_info.buffer_binds {
+                let state = states
+                    .buffers
+                    .get_mut(&buffer.inner().buffer.handle())
+                    .unwrap();
+
+                for memory_bind in memory_binds {
+                    state.bind_sparse(
+                        memory_bind.offset..memory_bind.offset + memory_bind.size,
+                        memory_bind.memory.as_ref().map(|(memory, _)| memory),
+                        &mut unbound_memory,
+                    );
+                }
+            }
+
+            for (image, memory_binds) in &bind_info.image_opaque_binds {
+                let image = image.inner().image;
+                let state = states.images.get_mut(&image.handle()).unwrap();
+                let size = image.memory_requirements()[0].size;
+
+                for memory_bind in memory_binds {
+                    let range = memory_bind.offset..memory_bind.offset + memory_bind.size;
+                    let memory = memory_bind.memory.as_ref().map(|(memory, _)| memory);
+                    state.bind_sparse_opaque(range.clone(), memory, &mut unbound_memory);
+
+                    // Outside the mip tail regions, opaque binds on a sparse residency image also
+                    // change the residency of its sparse blocks.
+                    if image.flags().intersects(ImageCreateFlags::SPARSE_RESIDENCY)
+                        && !image.is_sparse_mip_tail_range(&range)
+                    {
+                        state.bind_sparse_opaque_blocks(
+                            memory.is_some(),
+                            range.start == 0 && range.end >= size,
+                        );
+                    }
+                }
+            }
+
+            for (image, memory_binds) in &bind_info.image_binds {
+                let image = image.inner().image;
+                let state = states.images.get_mut(&image.handle()).unwrap();
+
+                for memory_bind in memory_binds {
+                    let tile_ranges = image.sparse_tile_ranges(
+                        memory_bind.aspects,
+                        memory_bind.mip_level,
+                        memory_bind.array_layer,
+                        memory_bind.offset,
+                        memory_bind.extent,
+                    );
+
+                    for tile_range in tile_ranges.into_iter().flatten() {
+                        state.bind_sparse(
+                            tile_range,
+                            memory_bind.memory.as_ref().map(|(memory, _)| memory),
+                            &mut unbound_memory,
+                        );
+                    }
+                }
             }
         }
 
@@ -476,9 +884,10 @@ impl<'a> QueueGuard<'a> {
             fence.clone()
         });
 
-        self.state
-            .operations
-            .push_back((bind_infos.clone().into(), fence));
+        self.state.operations.push_back((
+            QueueOperation::BindSparse(bind_infos.clone(), unbound_memory),
+            fence,
+        ));
 
         Ok(())
     }
@@ -649,7 +1058,38 @@ impl<'a> QueueGuard<'a> {
         let mut states = States::from_submit_infos(&submit_infos);
 
         for submit_info in &submit_infos {
+            let mut render_pass_suspended = false;
+            let protected = submit_info.is_protected();
+
+            // VUID-vkQueueSubmit-queue-06448
+            // VUID-vkQueueSubmit2-queue-06447
+            if protected && !queue.flags().intersects(QueueCreateFlags::PROTECTED) {
+                return Err(FlushError::QueueNotProtected);
+            }
+
             for command_buffer in &submit_info.command_buffers {
+                // VUID-VkSubmitInfo-pNext-04120
+                // VUID-VkSubmitInfo-pNext-04148
+                // VUID-VkSubmitInfo2-flags-03886
+                // VUID-VkSubmitInfo2-flags-03887
+                if command_buffer.protected() != protected {
+                    return Err(FlushError::ProtectedMismatch);
+                }
+
+                // VUID-VkSubmitInfo-pCommandBuffers-06193
+                // VUID-VkSubmitInfo2-commandBuffer-06192
+                if command_buffer.resumes_render_pass() && !render_pass_suspended {
+                    return Err(FlushError::ResumedRenderPassNotSuspended);
+                }
+
+                // VUID-VkSubmitInfo-pCommandBuffers-06015
+                // VUID-VkSubmitInfo2-commandBuffer-06010
+                if render_pass_suspended && !command_buffer.resumes_render_pass() {
+                    return Err(FlushError::SuspendedRenderPassNotResumed);
+                }
+
+                render_pass_suspended = command_buffer.suspends_render_pass();
+
                 let state = states
                     .command_buffers
                     .get(&command_buffer.handle())
@@ -681,7 +1121,22 @@ impl<'a> QueueGuard<'a> {
                 for usage in buffers {
                     let state = states.buffers.get_mut(&usage.buffer.handle()).unwrap();
 
+                    // Sparse buffers without sparse residency must be fully bound before use.
+                    let is_fully_bound = !matches!(usage.buffer.memory(), BufferMemory::Sparse)
+                        || usage
+                            .buffer
+                            .flags()
+                            .intersects(BufferCreateFlags::SPARSE_RESIDENCY)
+                        || state.is_resident(&(0..usage.buffer.size()));
+
                     for (range, range_usage) in usage.ranges.iter() {
+                        if !is_fully_bound {
+                            return Err(FlushError::ResourceAccessError {
+                                error: AccessError::SparseNotFullyBound,
+                                use_ref: range_usage.first_use,
+                            });
+                        }
+
                         match future.check_buffer_access(
                             &usage.buffer,
                             range.clone(),
@@ -716,7 +1171,24 @@ impl<'a> QueueGuard<'a> {
                 for usage in images {
                     let state = states.images.get_mut(&usage.image.handle()).unwrap();
 
+                    // Sparse images without sparse residency must be fully bound before use.
+                    let is_fully_bound = !matches!(usage.image.memory(), ImageMemory::Sparse(_))
+                        || usage
+                            .image
+                            .flags()
+                            .intersects(ImageCreateFlags::SPARSE_RESIDENCY)
+                        || state.is_opaque_range_resident(
+                            &(0..usage.image.memory_requirements()[0].size),
+                        );
+
                     for (range, range_usage) in usage.ranges.iter() {
+                        if !is_fully_bound {
+                            return Err(FlushError::ResourceAccessError {
+                                error: AccessError::SparseNotFullyBound,
+                                use_ref: range_usage.first_use,
+                            });
+                        }
+
                         match future.check_image_access(
                             &usage.image,
                             range.clone(),
@@ -750,6 +1222,12 @@ impl<'a> QueueGuard<'a> {
                     }
                 }
             }
+
+            // VUID-VkSubmitInfo-pCommandBuffers-06015
+            // VUID-VkSubmitInfo2-commandBuffer-06010
+            if render_pass_suspended {
+                return Err(FlushError::SuspendedRenderPassNotResumed);
+            }
         }
 
         Ok(self.submit_unchecked_locked(
@@ -811,12 +1289,13 @@ impl<'a> QueueGuard<'a> {
                                 let &SemaphoreSubmitInfo {
                                     ref semaphore,
                                     stages,
+                                    value,
                                     _ne: _,
                                 } = semaphore_submit_info;
 
                                 ash::vk::SemaphoreSubmitInfo {
                                     semaphore: semaphore.handle(),
-                                    value: 0, // TODO:
+                                    value,
                                     stage_mask: stages.into(),
                                     device_index: 0, // TODO:
                                     ..Default::default()
@@ -839,12 +1318,13 @@ impl<'a> QueueGuard<'a> {
                                 let &SemaphoreSubmitInfo {
                                     ref semaphore,
                                     stages,
+                                    value,
                                     _ne: _,
                                 } = semaphore_submit_info;
 
                                 ash::vk::SemaphoreSubmitInfo {
                                     semaphore: semaphore.handle(),
-                                    value: 0, // TODO:
+                                    value,
                                     stage_mask: stages.into(),
                                     device_index: 0, // TODO:
                                     ..Default::default()
@@ -852,9 +1332,15 @@ impl<'a> QueueGuard<'a> {
                             })
                             .collect();
 
+                        let mut flags = ash::vk::SubmitFlags::empty();
+
+                        if submit_info.is_protected() {
+                            flags |= ash::vk::SubmitFlags::PROTECTED;
+                        }
+
                         (
                             ash::vk::SubmitInfo2 {
-                                flags: ash::vk::SubmitFlags::empty(), // TODO:
+                                flags,
                                 wait_semaphore_info_count: 0,
                                 p_wait_semaphore_infos: ptr::null(),
                                 command_buffer_info_count: 0,
@@ -919,12 +1405,16 @@ impl<'a> QueueGuard<'a> {
         } else {
             struct PerSubmitInfo {
                 wait_semaphores_vk: SmallVec<[ash::vk::Semaphore; 4]>,
+                wait_semaphore_values_vk: SmallVec<[u64; 4]>,
                 wait_dst_stage_mask_vk: SmallVec<[ash::vk::PipelineStageFlags; 4]>,
                 command_buffers_vk: SmallVec<[ash::vk::CommandBuffer; 4]>,
                 signal_semaphores_vk: SmallVec<[ash::vk::Semaphore; 4]>,
+                signal_semaphore_values_vk: SmallVec<[u64; 4]>,
+                timeline_semaphore_submit_info_vk: Option<ash::vk::TimelineSemaphoreSubmitInfo>,
+                protected_submit_info_vk: Option<ash::vk::ProtectedSubmitInfo>,
             }
 
-            let (mut submit_info_vk, per_submit_vk): (SmallVec<[_; 4]>, SmallVec<[_; 4]>) =
+            let (mut submit_info_vk, mut per_submit_vk): (SmallVec<[_; 4]>, SmallVec<[_; 4]>) =
                 submit_infos
                     .iter()
                     .map(|submit_info| {
@@ -935,34 +1425,58 @@ impl<'a> QueueGuard<'a> {
                             _ne: _,
                         } = submit_info;
 
-                        let (wait_semaphores_vk, wait_dst_stage_mask_vk) = wait_semaphores
+                        let (
+                            wait_semaphores_vk,
+                            (wait_semaphore_values_vk, wait_dst_stage_mask_vk),
+                        ) = wait_semaphores
                             .iter()
                             .map(|semaphore_submit_info| {
                                 let &SemaphoreSubmitInfo {
                                     ref semaphore,
                                     stages,
+                                    value,
                                     _ne: _,
                                 } = semaphore_submit_info;
 
-                                (semaphore.handle(), stages.into())
+                                (semaphore.handle(), (value, stages.into()))
                             })
                             .unzip();
 
                         let command_buffers_vk =
                             command_buffers.iter().map(|cb| cb.handle()).collect();
 
-                        let signal_semaphores_vk = signal_semaphores
+                        let (signal_semaphores_vk, signal_semaphore_values_vk) = signal_semaphores
                             .iter()
                             .map(|semaphore_submit_info| {
                                 let &SemaphoreSubmitInfo {
                                     ref semaphore,
                                     stages: _,
+                                    value,
                                     _ne: _,
                                 } = semaphore_submit_info;
 
-                                semaphore.handle()
+                                (semaphore.handle(), value)
                             })
-                            .collect();
+                            .unzip();
+
+                        // The values are only needed if there is at least one timeline semaphore.
+                        let timeline_semaphore_submit_info_vk = wait_semaphores
+                            .iter()
+                            .chain(signal_semaphores)
+                            .any(|semaphore_submit_info| {
+                                semaphore_submit_info.semaphore.semaphore_type()
+                                    == SemaphoreType::Timeline
+                            })
+                            .then(Default::default);
+
+                        // Only needed if the submission is protected.
+                        let protected_submit_info_vk =
+                            submit_info
+                                .is_protected()
+                                .then_some(ash::vk::ProtectedSubmitInfo {
+                                    protected_submit: ash::vk::TRUE,
+                                    ..Default::default()
+                                });
 
                         (
                             ash::vk::SubmitInfo {
@@ -977,9 +1491,13 @@ impl<'a> QueueGuard<'a> {
                             },
                             PerSubmitInfo {
                                 wait_semaphores_vk,
+                                wait_semaphore_values_vk,
                                 wait_dst_stage_mask_vk,
                                 command_buffers_vk,
                                 signal_semaphores_vk,
+                                signal_semaphore_values_vk,
+                                timeline_semaphore_submit_info_vk,
+                                protected_submit_info_vk,
                             },
                         )
                     })
@@ -989,11 +1507,15 @@ impl<'a> QueueGuard<'a> {
                 submit_info_vk,
                 PerSubmitInfo {
                     wait_semaphores_vk,
+                    wait_semaphore_values_vk,
                     wait_dst_stage_mask_vk,
                     command_buffers_vk,
                     signal_semaphores_vk,
+                    signal_semaphore_values_vk,
+                    timeline_semaphore_submit_info_vk,
+                    protected_submit_info_vk,
                 },
-            ) in (submit_info_vk.iter_mut()).zip(per_submit_vk.iter())
+            ) in (submit_info_vk.iter_mut()).zip(per_submit_vk.iter_mut())
             {
                 *submit_info_vk = ash::vk::SubmitInfo {
                     wait_semaphore_count: wait_semaphores_vk.len() as u32,
@@ -1005,6 +1527,24 @@ impl<'a> QueueGuard<'a> {
                     p_signal_semaphores: signal_semaphores_vk.as_ptr(),
                     ..*submit_info_vk
                 };
+
+                if let Some(next) = timeline_semaphore_submit_info_vk {
+                    *next = ash::vk::TimelineSemaphoreSubmitInfo {
+                        wait_semaphore_value_count: wait_semaphore_values_vk.len() as u32,
+                        p_wait_semaphore_values: wait_semaphore_values_vk.as_ptr(),
+                        signal_semaphore_value_count: signal_semaphore_values_vk.len() as u32,
+                        p_signal_semaphore_values: signal_semaphore_values_vk.as_ptr(),
+                        ..Default::default()
+                    };
+
+                    next.p_next = submit_info_vk.p_next;
+                    submit_info_vk.p_next = next as *const _ as *const _;
+                }
+
+                if let Some(next) = protected_submit_info_vk {
+                    next.p_next = submit_info_vk.p_next;
+                    submit_info_vk.p_next = next as *const _ as *const _;
+                }
             }
 
             let fns = self.queue.device.fns();
@@ -1080,7 +1620,13 @@ impl<'a> QueueGuard<'a> {
                     .semaphores
                     .get_mut(&semaphore_submit_info.semaphore.handle())
                     .unwrap();
-                state.add_queue_signal(self.queue);
+
+                match semaphore_submit_info.semaphore.semaphore_type() {
+                    SemaphoreType::Binary => state.add_queue_signal(self.queue),
+                    SemaphoreType::Timeline => {
+                        state.add_queue_signal_value(self.queue, semaphore_submit_info.value)
+                    }
+                }
             }
         }
 
@@ -1265,6 +1811,91 @@ impl<'a> QueueGuard<'a> {
     }
 }
 
+fn validate_sparse_memory_bind(
+    device: &Device,
+    resource_offset: DeviceSize,
+    size: DeviceSize,
+    memory: &Option<(Arc<DeviceMemory>, DeviceSize)>,
+    memory_requirements: &MemoryRequirements,
+) -> Result<(), BindSparseError> {
+    let &MemoryRequirements {
+        size: resource_size,
+        alignment,
+        ..
+    } = memory_requirements;
+
+    // VUID-VkSparseMemoryBind-size-01098
+    assert!(size != 0);
+
+    // VUID-VkSparseMemoryBind-resourceOffset-01099
+    // VUID-VkSparseMemoryBind-size-01100
+    if resource_offset >= resource_size || size > resource_size - resource_offset {
+        return Err(BindSparseError::BindOutOfRange);
+    }
+
+    // Memory can only be bound in whole sparse blocks, except at the end of the resource.
+    if resource_offset % alignment != 0
+        || (size % alignment != 0 && resource_offset + size != resource_size)
+    {
+        return Err(BindSparseError::BindNotAligned {
+            required_alignment: alignment,
+        });
+    }
+
+    if let Some((memory, memory_offset)) = memory {
+        validate_sparse_memory(device, memory, *memory_offset, size, memory_requirements)?;
+    }
+
+    Ok(())
+}
+
+fn validate_sparse_memory(
+    device: &Device,
+    memory: &DeviceMemory,
+    memory_offset: DeviceSize,
+    size: DeviceSize,
+    memory_requirements: &MemoryRequirements,
+) -> Result<(), BindSparseError> {
+    // VUID-VkSparseMemoryBind-commonparent
+    assert_eq!(device, &**memory.device());
+
+    let memory_type_index = memory.memory_type_index();
+    let memory_type =
+        &device.physical_device().memory_properties().memory_types[memory_type_index as usize];
+
+    // VUID-VkSparseMemoryBind-memory-01096
+    if memory_requirements.memory_type_bits & (1 << memory_type_index) == 0 {
+        return Err(BindSparseError::MemoryTypeNotAllowed {
+            provided_memory_type_index: memory_type_index,
+            allowed_memory_type_bits: memory_requirements.memory_type_bits,
+        });
+    }
+
+    // VUID-VkSparseMemoryBind-memory-01096
+    if memory_offset % memory_requirements.alignment != 0 {
+        return Err(BindSparseError::MemoryOffsetNotAligned {
+            required_alignment: memory_requirements.alignment,
+        });
+    }
+
+    // VUID-VkSparseMemoryBind-memory-01097
+    if memory_type
+        .property_flags
+        .intersects(MemoryPropertyFlags::LAZILY_ALLOCATED)
+    {
+        return Err(BindSparseError::MemoryLazilyAllocated);
+    }
+
+    // VUID-VkSparseMemoryBind-memoryOffset-01101
+    // VUID-VkSparseMemoryBind-size-01102
+    if memory_offset >= memory.allocation_size() || size > memory.allocation_size() - memory_offset
+    {
+        return Err(BindSparseError::MemoryOutOfRange);
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Default)]
 struct QueueState {
     operations: VecDeque<(QueueOperation, Option<Arc<Fence>>)>,
@@ -1313,11 +1944,45 @@ impl QueueState {
             }
         }
     }
+
+    /// Called when a timeline `semaphore` is found to have reached `value`.
+    fn semaphore_signaled(&mut self, semaphore: &Semaphore, value: u64) {
+        // Find the most recent operation that signals `semaphore` with a value that has been
+        // reached.
+        let operation_index =
+            self.operations
+                .iter()
+                .enumerate()
+                .rev()
+                .find_map(|(index, (operation, _))| match operation {
+                    QueueOperation::Submit(submit_infos) => submit_infos
+                        .iter()
+                        .flat_map(|submit_info| &submit_info.signal_semaphores)
+                        .any(|signal_info| {
+                            *signal_info.semaphore == *semaphore && signal_info.value <= value
+                        })
+                        .then_some(index),
+                    _ => None,
+                });
+
+        if let Some(index) = operation_index {
+            // Remove all operations up to this index, and perform cleanup if needed.
+            for (operation, fence) in self.operations.drain(..index + 1) {
+                unsafe {
+                    operation.set_finished();
+
+                    if let Some(fence) = fence {
+                        fence.state().set_signal_finished();
+                    }
+                }
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
 enum QueueOperation {
-    BindSparse(SmallVec<[BindSparseInfo; 4]>),
+    BindSparse(SmallVec<[BindSparseInfo; 4]>, Vec<Arc<DeviceMemory>>),
     Present(PresentInfo),
     Submit(SmallVec<[SubmitInfo; 4]>),
 }
@@ -1325,7 +1990,7 @@ enum QueueOperation {
 impl QueueOperation {
     unsafe fn set_finished(self) {
         match self {
-            QueueOperation::BindSparse(bind_infos) => {
+            QueueOperation::BindSparse(bind_infos, _unbound_memory) => {
                 for bind_info in bind_infos {
                     for semaphore in bind_info.wait_semaphores {
                         semaphore.state().set_wait_finished();
@@ -1350,10 +2015,14 @@ impl QueueOperation {
                     }
 
                     for semaphore_submit_info in submit_info.signal_semaphores {
-                        semaphore_submit_info
-                            .semaphore
-                            .state()
-                            .set_signal_finished();
+                        let mut state = semaphore_submit_info.semaphore.state();
+
+                        match semaphore_submit_info.semaphore.semaphore_type() {
+                            SemaphoreType::Binary => state.set_signal_finished(),
+                            SemaphoreType::Timeline => {
+                                state.set_signal_value_finished(semaphore_submit_info.value)
+                            }
+                        }
                     }
 
                     for command_buffer in submit_info.command_buffers {
@@ -1391,13 +2060,6 @@ impl QueueOperation {
     }
 }
 
-impl From<SmallVec<[BindSparseInfo; 4]>> for QueueOperation {
-    #[inline]
-    fn from(val: SmallVec<[BindSparseInfo; 4]>) -> Self {
-        Self::BindSparse(val)
-    }
-}
-
 impl From<PresentInfo> for QueueOperation {
     #[inline]
     fn from(val: PresentInfo) -> Self {
@@ -1646,6 +2308,24 @@ vulkan_bitflags! {
     },
 }
 
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags specifying additional properties of a queue.
+    QueueCreateFlags = DeviceQueueCreateFlags(u32);
+
+    /// The queues are protected, and can be used to submit protected command buffers.
+    ///
+    /// The device API version must be at least 1.1. In addition, the [`protected_memory`] feature
+    /// must be enabled on the device, and the queue family must support
+    /// [`QueueFlags::PROTECTED`].
+    ///
+    /// [`protected_memory`]: crate::device::Features::protected_memory
+    PROTECTED = PROTECTED {
+        api_version: V1_1,
+    },
+}
+
 /// Error that can happen when submitting work to a queue.
 #[derive(Clone, Debug)]
 pub enum QueueError {
@@ -1697,6 +2377,199 @@ impl From<RequirementNotMet> for QueueError {
     }
 }
 
+/// Error that can happen when executing sparse bind operations on a queue.
+#[derive(Clone, Debug)]
+pub enum BindSparseError {
+    VulkanError(VulkanError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// The offset or size of a bind operation is not a multiple of the sparse block size of the
+    /// resource, and the bind operation does not extend to the end of the resource.
+    BindNotAligned {
+        required_alignment: DeviceSize,
+    },
+
+    /// A bind operation extends beyond the end of the resource.
+    BindOutOfRange,
+
+    /// The provided fence is currently in use by a queue.
+    FenceInUse,
+
+    /// The provided fence is not in the unsignaled state.
+    FenceNotUnsignaled,
+
+    /// An image bind operation targets a mip level that is part of the mip tail region, which
+    /// must be bound with an opaque bind operation instead.
+    ImageBindInMipTail,
+
+    /// The offset or extent of an image bind operation is not a multiple of the sparse image
+    /// block size, and the bind operation does not extend to the edge of the subresource.
+    ImageBindNotAligned {
+        granularity: [u32; 3],
+    },
+
+    /// An image bind operation was provided for an image that was not created with the
+    /// `sparse_residency` flag.
+    ImageNotSparseResidency,
+
+    /// The aspects, mip level or array layer of an image bind operation are not part of the
+    /// image.
+    ImageSubresourceOutOfRange,
+
+    /// The memory being bound is lazily allocated.
+    MemoryLazilyAllocated,
+
+    /// The memory offset of a bind operation is not a multiple of the alignment required by the
+    /// resource.
+    MemoryOffsetNotAligned {
+        required_alignment: DeviceSize,
+    },
+
+    /// A bind operation extends beyond the end of the memory being bound.
+    MemoryOutOfRange,
+
+    /// The memory type of the memory being bound is not allowed by the resource.
+    MemoryTypeNotAllowed {
+        provided_memory_type_index: u32,
+        allowed_memory_type_bits: u32,
+    },
+
+    /// A bind operation for the metadata aspect lies outside the metadata mip tail region of the
+    /// image.
+    MetadataBindOutsideMipTail,
+
+    /// The queue family of the queue does not support sparse binding.
+    QueueFamilyNotSupported,
+
+    /// A resource was not created with the `sparse_binding` flag, or it has not been converted
+    /// into a sparse resource.
+    ResourceNotSparse,
+
+    /// A semaphore is not a binary semaphore.
+    SemaphoreTypeNotSupported,
+
+    /// A semaphore to be signaled is already signaled, or has a signal operation pending.
+    SignalSemaphoreNotUnsignaled,
+
+    /// A semaphore to be waited on is not signaled, and has no signal operation pending.
+    WaitSemaphoreNotSignaled,
+}
+
+impl Error for BindSparseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BindSparseError::VulkanError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for BindSparseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::VulkanError(_) => write!(f, "a runtime error occurred"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::BindNotAligned { .. } => write!(
+                f,
+                "the offset or size of a bind operation is not a multiple of the sparse block \
+                size of the resource, and the bind operation does not extend to the end of the \
+                resource",
+            ),
+            Self::BindOutOfRange => {
+                write!(f, "a bind operation extends beyond the end of the resource",)
+            }
+            Self::FenceInUse => write!(f, "the provided fence is currently in use by a queue"),
+            Self::FenceNotUnsignaled => {
+                write!(f, "the provided fence is not in the unsignaled state",)
+            }
+            Self::ImageBindInMipTail => write!(
+                f,
+                "an image bind operation targets a mip level that is part of the mip tail region",
+            ),
+            Self::ImageBindNotAligned { .. } => write!(
+                f,
+                "the offset or extent of an image bind operation is not a multiple of the sparse \
+                image block size, and the bind operation does not extend to the edge of the \
+                subresource",
+            ),
+            Self::ImageNotSparseResidency => write!(
+                f,
+                "an image bind operation was provided for an image that was not created with the \
+                `sparse_residency` flag",
+            ),
+            Self::ImageSubresourceOutOfRange => write!(
+                f,
+                "the aspects, mip level or array layer of an image bind operation are not part of \
+                the image",
+            ),
+            Self::MemoryLazilyAllocated => write!(f, "the memory being bound is lazily allocated"),
+            Self::MemoryOffsetNotAligned { .. } => write!(
+                f,
+                "the memory offset of a bind operation is not a multiple of the alignment \
+                required by the resource",
+            ),
+            Self::MemoryOutOfRange => write!(
+                f,
+                "a bind operation extends beyond the end of the memory being bound",
+            ),
+            Self::MemoryTypeNotAllowed { .. } => write!(
+                f,
+                "the memory type of the memory being bound is not allowed by the resource",
+            ),
+            Self::MetadataBindOutsideMipTail => write!(
+                f,
+                "a bind operation for the metadata aspect lies outside the metadata mip tail \
+                region of the image",
+            ),
+            Self::QueueFamilyNotSupported => write!(
+                f,
+                "the queue family of the queue does not support sparse binding",
+            ),
+            Self::ResourceNotSparse => write!(
+                f,
+                "a resource was not created with the `sparse_binding` flag, or it has not been \
+                converted into a sparse resource",
+            ),
+            Self::SemaphoreTypeNotSupported => write!(f, "a semaphore is not a binary semaphore"),
+            Self::SignalSemaphoreNotUnsignaled => write!(
+                f,
+                "a semaphore to be signaled is already signaled, or has a signal operation \
+                pending",
+            ),
+            Self::WaitSemaphoreNotSignaled => write!(
+                f,
+                "a semaphore to be waited on is not signaled, and has no signal operation pending",
+            ),
+        }
+    }
+}
+
+impl From<VulkanError> for BindSparseError {
+    fn from(err: VulkanError) -> Self {
+        Self::VulkanError(err)
+    }
+}
+
+impl From<RequirementNotMet> for BindSparseError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::sync::fence::Fence;
@@ -20,7 +20,7 @@ use crate::{
     memory::{
         allocator::{
             AllocationCreateInfo, AllocationType, MemoryAllocatePreference, MemoryAllocator,
-            MemoryUsage,
+            MemoryUsage, MovableImage,
         },
         DedicatedAllocation, DeviceMemoryError, ExternalMemoryHandleType,
         ExternalMemoryHandleTypes,
@@ -64,6 +64,11 @@ use std::{
 /// or a depth buffer that is only used once) then use a transient image as it may improve
 /// performance.
 ///
+/// The memory of the image is always allocated with the default priority. If you need a different
+/// priority, create a [`RawImage`] and allocate memory for it yourself with
+/// [`AllocationCreateInfo::priority`] set.
+///
+/// [`RawImage`]: crate::image::sys::RawImage
 // TODO: forbid reading transient images outside render passes?
 #[derive(Debug)]
 pub struct AttachmentImage {
@@ -528,6 +533,7 @@ impl AttachmentImage {
                 requirements.size,
                 Some(DedicatedAllocation::Image(&raw_image)),
                 external_memory_handle_types,
+                0.5,
             )
         } {
             Ok(alloc) => {
@@ -635,6 +641,18 @@ unsafe impl<P> ImageContent<P> for AttachmentImage {
     }
 }
 
+unsafe impl MovableImage for Arc<AttachmentImage> {
+    #[inline]
+    fn image_access(&self) -> Arc<dyn ImageAccess> {
+        self.clone()
+    }
+
+    #[inline]
+    fn image_mut(&mut self) -> Option<&mut Image> {
+        Arc::get_mut(self).and_then(|image| Arc::get_mut(&mut image.inner))
+    }
+}
+
 impl PartialEq for AttachmentImage {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
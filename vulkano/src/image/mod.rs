@@ -83,7 +83,6 @@ vulkan_bitflags! {
     /// Flags that can be set when creating a new image.
     ImageCreateFlags = ImageCreateFlags(u32);
 
-    /* TODO: enable
     /// The image will be backed by sparse memory binding (through queue commands) instead of
     /// regular binding (through [`bind_memory`]).
     ///
@@ -91,9 +90,8 @@ vulkan_bitflags! {
     ///
     /// [`bind_memory`]: sys::RawImage::bind_memory
     /// [`sparse_binding`]: crate::device::Features::sparse_binding
-    SPARSE_BINDING = SPARSE_BINDING,*/
+    SPARSE_BINDING = SPARSE_BINDING,
 
-    /* TODO: enable
     /// The image can be used without being fully resident in memory at the time of use.
     ///
     /// This requires the `sparse_binding` flag as well.
@@ -107,22 +105,21 @@ vulkan_bitflags! {
     ///
     /// [`sparse_binding`]: crate::device::Features::sparse_binding
     /// [`sparse_residency_image2_d`]: crate::device::Features::sparse_residency_image2_d
-    /// [`sparse_residency_image2_3`]: crate::device::Features::sparse_residency_image3_d
+    /// [`sparse_residency_image3_d`]: crate::device::Features::sparse_residency_image3_d
     /// [`sparse_residency2_samples`]: crate::device::Features::sparse_residency2_samples
     /// [`sparse_residency4_samples`]: crate::device::Features::sparse_residency4_samples
     /// [`sparse_residency8_samples`]: crate::device::Features::sparse_residency8_samples
     /// [`sparse_residency16_samples`]: crate::device::Features::sparse_residency16_samples
-    SPARSE_RESIDENCY = SPARSE_RESIDENCY,*/
+    SPARSE_RESIDENCY = SPARSE_RESIDENCY,
 
-    /* TODO: enable
-    /// The buffer's memory can alias with another image or a different part of the same image.
+    /// The image's memory can alias with another image or a different part of the same image.
     ///
     /// This requires the `sparse_binding` flag as well.
     ///
     /// The [`sparse_residency_aliased`] feature must be enabled on the device.
     ///
     /// [`sparse_residency_aliased`]: crate::device::Features::sparse_residency_aliased
-    SPARSE_ALIASED = SPARSE_ALIASED,*/
+    SPARSE_ALIASED = SPARSE_ALIASED,
 
     /// For non-multi-planar formats, whether an image view wrapping the image can have a
     /// different format.
@@ -184,11 +181,16 @@ vulkan_bitflags! {
         device_extensions: [khr_maintenance2],
     },*/
 
-    /* TODO: enable
-    // TODO: document
+    /// The image is protected, and can only be used in combination with protected memory and other
+    /// protected objects.
+    ///
+    /// The device API version must be at least 1.1. In addition, the [`protected_memory`] feature
+    /// must be enabled on the device.
+    ///
+    /// [`protected_memory`]: crate::device::Features::protected_memory
     PROTECTED = PROTECTED {
         api_version: V1_1,
-    },*/
+    },
 
     /// For images with a multi-planar format, whether each plane will have its memory bound
     /// separately, rather than having a single memory binding for the whole image.
@@ -20,7 +20,7 @@ use crate::{
     memory::{
         allocator::{
             AllocationCreateInfo, AllocationType, MemoryAllocatePreference, MemoryAllocator,
-            MemoryUsage,
+            MemoryUsage, MovableImage,
         },
         DedicatedAllocation, DeviceMemoryError, ExternalMemoryHandleType,
         ExternalMemoryHandleTypes,
@@ -37,6 +37,12 @@ use std::{
 
 /// General-purpose image in device memory. Can be used for any usage, but will be slower than a
 /// specialized image.
+///
+/// The memory of the image is always allocated with the default priority. If you need a different
+/// priority, create a [`RawImage`] and allocate memory for it yourself with
+/// [`AllocationCreateInfo::priority`] set.
+///
+/// [`RawImage`]: crate::image::sys::RawImage
 #[derive(Debug)]
 pub struct StorageImage {
     inner: Arc<Image>,
@@ -191,6 +197,7 @@ impl StorageImage {
                 requirements.size,
                 Some(DedicatedAllocation::Image(&raw_image)),
                 external_memory_handle_types,
+                0.5,
             )
         } {
             Ok(alloc) => {
@@ -316,6 +323,18 @@ unsafe impl<P> ImageContent<P> for StorageImage {
     }
 }
 
+unsafe impl MovableImage for Arc<StorageImage> {
+    #[inline]
+    fn image_access(&self) -> Arc<dyn ImageAccess> {
+        self.clone()
+    }
+
+    #[inline]
+    fn image_mut(&mut self) -> Option<&mut Image> {
+        Arc::get_mut(self).and_then(|image| Arc::get_mut(&mut image.inner))
+    }
+}
+
 impl PartialEq for StorageImage {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
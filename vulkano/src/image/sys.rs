@@ -16,7 +16,7 @@
 use super::{
     ImageAspect, ImageAspects, ImageCreateFlags, ImageDimensions, ImageLayout,
     ImageSubresourceLayers, ImageSubresourceRange, ImageTiling, ImageUsage, SampleCount,
-    SampleCounts, SparseImageMemoryRequirements,
+    SampleCounts, SparseImageFormatFlags, SparseImageMemoryRequirements,
 };
 use crate::{
     buffer::cpu_access::{ReadLockError, WriteLockError},
@@ -29,8 +29,8 @@ use crate::{
     },
     memory::{
         allocator::{AllocationCreationError, MemoryAlloc},
-        DedicatedTo, ExternalMemoryHandleType, ExternalMemoryHandleTypes, MemoryPropertyFlags,
-        MemoryRequirements,
+        DedicatedTo, DeviceMemory, ExternalMemoryHandleType, ExternalMemoryHandleTypes,
+        MemoryPropertyFlags, MemoryRequirements,
     },
     range_map::RangeMap,
     swapchain::Swapchain,
@@ -77,6 +77,7 @@ pub struct RawImage {
 
     memory_requirements: SmallVec<[MemoryRequirements; 3]>,
     needs_destruction: bool, // `vkDestroyImage` is called only if true.
+    imported_handle: bool,
     subresource_layout: OnceCache<(ImageAspect, u32, u32), SubresourceLayout>,
 }
 
@@ -90,6 +91,9 @@ impl RawImage {
     /// - Panics if `create_info.block_texel_view_compatible` is set but not
     ///   `create_info.mutable_format`.
     /// - Panics if `create_info.mip_levels` is `0`.
+    /// - Panics if `create_info.flags` contains `sparse_residency` or `sparse_aliased`, but does
+    ///   not also contain `sparse_binding`.
+    /// - Panics if `create_info.flags` contains `protected` as well as any of the sparse flags.
     /// - Panics if `create_info.sharing` is [`Sharing::Concurrent`] with less than 2 items.
     /// - Panics if `create_info.initial_layout` is something other than
     ///   [`ImageLayout::Undefined`] or [`ImageLayout::Preinitialized`].
@@ -538,6 +542,163 @@ impl RawImage {
             }
         }
 
+        if flags.intersects(ImageCreateFlags::SPARSE_BINDING) {
+            // VUID-VkImageCreateInfo-flags-00969
+            if !device.enabled_features().sparse_binding {
+                return Err(ImageError::RequirementNotMet {
+                    required_for: "`create_info.flags` contains `ImageCreateFlags::SPARSE_BINDING`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["sparse_binding"],
+                        ..Default::default()
+                    },
+                });
+            }
+        }
+
+        if flags.intersects(ImageCreateFlags::SPARSE_RESIDENCY) {
+            // VUID-VkImageCreateInfo-imageType-00970
+            if image_type == ImageType::Dim1d {
+                return Err(ImageError::SparseResidencyNot2dOr3d);
+            }
+
+            // VUID-VkImageCreateInfo-tiling-04121
+            if tiling == ImageTiling::Linear {
+                return Err(ImageError::SparseResidencyLinearTiling);
+            }
+
+            match image_type {
+                ImageType::Dim2d => {
+                    // VUID-VkImageCreateInfo-imageType-00971
+                    if !device.enabled_features().sparse_residency_image2_d {
+                        return Err(ImageError::RequirementNotMet {
+                            required_for: "`create_info.flags` contains \
+                                `ImageCreateFlags::SPARSE_RESIDENCY`, and \
+                                `create_info.dimensions` is `ImageDimensions::Dim2d`",
+                            requires_one_of: RequiresOneOf {
+                                features: &["sparse_residency_image2_d"],
+                                ..Default::default()
+                            },
+                        });
+                    }
+                }
+                ImageType::Dim3d => {
+                    // VUID-VkImageCreateInfo-imageType-00972
+                    if !device.enabled_features().sparse_residency_image3_d {
+                        return Err(ImageError::RequirementNotMet {
+                            required_for: "`create_info.flags` contains \
+                                `ImageCreateFlags::SPARSE_RESIDENCY`, and \
+                                `create_info.dimensions` is `ImageDimensions::Dim3d`",
+                            requires_one_of: RequiresOneOf {
+                                features: &["sparse_residency_image3_d"],
+                                ..Default::default()
+                            },
+                        });
+                    }
+                }
+                ImageType::Dim1d => unreachable!(),
+            }
+
+            let (enabled, features): (bool, &'static [&'static str]) = match samples {
+                SampleCount::Sample1 => (true, &[]),
+                // VUID-VkImageCreateInfo-imageType-00973
+                SampleCount::Sample2 => (
+                    device.enabled_features().sparse_residency2_samples,
+                    &["sparse_residency2_samples"],
+                ),
+                // VUID-VkImageCreateInfo-imageType-00974
+                SampleCount::Sample4 => (
+                    device.enabled_features().sparse_residency4_samples,
+                    &["sparse_residency4_samples"],
+                ),
+                // VUID-VkImageCreateInfo-imageType-00975
+                SampleCount::Sample8 => (
+                    device.enabled_features().sparse_residency8_samples,
+                    &["sparse_residency8_samples"],
+                ),
+                // VUID-VkImageCreateInfo-imageType-00976
+                SampleCount::Sample16 => (
+                    device.enabled_features().sparse_residency16_samples,
+                    &["sparse_residency16_samples"],
+                ),
+                SampleCount::Sample32 | SampleCount::Sample64 => {
+                    let features = device.enabled_features();
+                    let mut supported = SampleCounts::SAMPLE_1;
+
+                    if features.sparse_residency2_samples {
+                        supported |= SampleCounts::SAMPLE_2;
+                    }
+
+                    if features.sparse_residency4_samples {
+                        supported |= SampleCounts::SAMPLE_4;
+                    }
+
+                    if features.sparse_residency8_samples {
+                        supported |= SampleCounts::SAMPLE_8;
+                    }
+
+                    if features.sparse_residency16_samples {
+                        supported |= SampleCounts::SAMPLE_16;
+                    }
+
+                    // Sparse residency is never supported for these sample counts.
+
+                    return Err(ImageError::SampleCountNotSupported { samples, supported });
+                }
+            };
+
+            if !enabled {
+                return Err(ImageError::RequirementNotMet {
+                    required_for: "`create_info.flags` contains \
+                        `ImageCreateFlags::SPARSE_RESIDENCY`, and `create_info.samples` is not \
+                        `SampleCount::Sample1`",
+                    requires_one_of: RequiresOneOf {
+                        features,
+                        ..Default::default()
+                    },
+                });
+            }
+        }
+
+        if flags.intersects(ImageCreateFlags::SPARSE_ALIASED) {
+            // VUID-VkImageCreateInfo-flags-00977
+            if !device.enabled_features().sparse_residency_aliased {
+                return Err(ImageError::RequirementNotMet {
+                    required_for: "`create_info.flags` contains `ImageCreateFlags::SPARSE_ALIASED`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["sparse_residency_aliased"],
+                        ..Default::default()
+                    },
+                });
+            }
+        }
+
+        // VUID-VkImageCreateInfo-flags-00987
+        assert!(
+            !flags
+                .intersects(ImageCreateFlags::SPARSE_RESIDENCY | ImageCreateFlags::SPARSE_ALIASED)
+                || flags.intersects(ImageCreateFlags::SPARSE_BINDING)
+        );
+
+        if flags.intersects(ImageCreateFlags::PROTECTED) {
+            // VUID-VkImageCreateInfo-flags-01890
+            if !device.enabled_features().protected_memory {
+                return Err(ImageError::RequirementNotMet {
+                    required_for: "`create_info.flags` contains `ImageCreateFlags::PROTECTED`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["protected_memory"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkImageCreateInfo-None-01891
+            assert!(!flags.intersects(
+                ImageCreateFlags::SPARSE_BINDING
+                    | ImageCreateFlags::SPARSE_RESIDENCY
+                    | ImageCreateFlags::SPARSE_ALIASED
+            ));
+        }
+
         if flags.intersects(ImageCreateFlags::BLOCK_TEXEL_VIEW_COMPATIBLE) {
             // VUID-VkImageCreateInfo-flags-01572
             if format.compression().is_none() {
@@ -955,13 +1116,24 @@ impl RawImage {
         let handle = {
             let fns = device.fns();
             let mut output = MaybeUninit::uninit();
-            (fns.v1_0.create_image)(device.handle(), &info_vk, ptr::null(), output.as_mut_ptr())
-                .result()
-                .map_err(VulkanError::from)?;
+            (fns.v1_0.create_image)(
+                device.handle(),
+                &info_vk,
+                device.allocation_callbacks(),
+                output.as_mut_ptr(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
             output.assume_init()
         };
 
-        Ok(Self::from_handle(device, handle, create_info))
+        Ok(Self::from_handle_with_destruction(
+            device,
+            handle,
+            create_info,
+            true,
+            false,
+        ))
     }
 
     /// Creates a new `RawImage` from a raw object handle.
@@ -971,13 +1143,15 @@ impl RawImage {
     /// - `handle` must be a valid Vulkan object handle created from `device`.
     /// - `handle` must refer to an image that has not yet had memory bound to it.
     /// - `create_info` must match the info used to create the object.
+    /// - `handle` must have been created with a null `pAllocator`, as it is destroyed with a null
+    ///   `pAllocator` when dropped.
     #[inline]
     pub unsafe fn from_handle(
         device: Arc<Device>,
         handle: ash::vk::Image,
         create_info: ImageCreateInfo,
     ) -> Self {
-        Self::from_handle_with_destruction(device, handle, create_info, true)
+        Self::from_handle_with_destruction(device, handle, create_info, true, true)
     }
 
     unsafe fn from_handle_with_destruction(
@@ -985,6 +1159,7 @@ impl RawImage {
         handle: ash::vk::Image,
         create_info: ImageCreateInfo,
         needs_destruction: bool,
+        imported_handle: bool,
     ) -> Self {
         let ImageCreateInfo {
             flags,
@@ -1048,6 +1223,7 @@ impl RawImage {
             external_memory_handle_types,
             memory_requirements,
             needs_destruction,
+            imported_handle,
             subresource_layout: OnceCache::new(),
         }
     }
@@ -1143,7 +1319,6 @@ impl RawImage {
     }
 
     #[inline]
-    #[allow(dead_code)] // Remove when sparse memory is implemented
     fn get_sparse_memory_requirements(&self) -> Vec<SparseImageMemoryRequirements> {
         let device = &self.device;
 
@@ -1398,8 +1573,9 @@ impl RawImage {
             // Ensured by taking ownership of `RawImage`.
 
             // VUID-VkBindImageMemoryInfo-image-01045
-            // Currently ensured by not having sparse binding flags, but this needs to be checked
-            // once those are enabled.
+            if self.flags.intersects(ImageCreateFlags::SPARSE_BINDING) {
+                return Err(ImageError::SparseBindingEnabled);
+            }
 
             // VUID-VkBindImageMemoryInfo-memoryOffset-01046
             // Assume that `allocation` was created correctly.
@@ -1419,14 +1595,17 @@ impl RawImage {
             }
 
             // VUID-VkBindImageMemoryInfo-None-01901
-            if memory_type
+            // VUID-VkBindImageMemoryInfo-None-01902
+            let image_protected = self.flags.intersects(ImageCreateFlags::PROTECTED);
+            let memory_protected = memory_type
                 .property_flags
-                .intersects(MemoryPropertyFlags::PROTECTED)
-            {
+                .intersects(MemoryPropertyFlags::PROTECTED);
+
+            if image_protected != memory_protected {
                 return Err(ImageError::MemoryProtectedMismatch {
                     allocations_index,
-                    image_protected: false,
-                    memory_protected: true,
+                    image_protected,
+                    memory_protected,
                 });
             }
 
@@ -1596,6 +1775,28 @@ impl RawImage {
         Ok(Image::from_raw(self, ImageMemory::Normal(allocations)))
     }
 
+    /// Converts the raw image into a sparse `Image`, without binding any memory to it.
+    ///
+    /// The image must have been created with the [`ImageCreateFlags::SPARSE_BINDING`] flag.
+    /// Memory can then be bound to the returned image with [`bind_sparse`]. If the image was not
+    /// also created with the [`ImageCreateFlags::SPARSE_RESIDENCY`] flag, then it must be fully
+    /// bound before it is used, otherwise submitting a command buffer that uses it returns an
+    /// error.
+    ///
+    /// [`bind_sparse`]: crate::device::QueueGuard::bind_sparse
+    pub fn into_sparse(self) -> Result<Image, (ImageError, RawImage)> {
+        if !self.flags.intersects(ImageCreateFlags::SPARSE_BINDING) {
+            return Err((ImageError::SparseBindingNotEnabled, self));
+        }
+
+        let sparse_requirements = self.get_sparse_memory_requirements();
+
+        Ok(Image::from_raw(
+            self,
+            ImageMemory::Sparse(sparse_requirements),
+        ))
+    }
+
     /// Returns the memory requirements for this image.
     ///
     /// - If `self.flags().disjoint` is not set, this returns a slice with a length of 1.
@@ -1846,7 +2047,12 @@ impl Drop for RawImage {
 
         unsafe {
             let fns = self.device.fns();
-            (fns.v1_0.destroy_image)(self.device.handle(), self.handle, ptr::null());
+            (fns.v1_0.destroy_image)(
+                self.device.handle(),
+                self.handle,
+                self.device
+                    .destroy_allocation_callbacks(self.imported_handle),
+            );
         }
     }
 }
@@ -2063,6 +2269,7 @@ impl Image {
                 handle,
                 create_info,
                 false,
+                false,
             ),
             ImageMemory::Swapchain {
                 swapchain,
@@ -2305,6 +2512,139 @@ impl Image {
         }
     }
 
+    /// Returns whether the given region of a subresource currently has memory bound to it.
+    ///
+    /// For an image backed by sparse memory, the residency reflects all sparse bind operations
+    /// that have been submitted with [`bind_sparse`] so far. If `mip_level` is part of the mip tail
+    /// region, then this returns whether the whole mip tail region that contains it is bound.
+    /// If the image was not created with the [`ImageCreateFlags::SPARSE_RESIDENCY`] flag, then
+    /// this returns whether the whole image is bound. Images backed by other kinds of memory
+    /// are always fully resident.
+    ///
+    /// `offset` and `extent` are given in texels (or for compressed images, texel blocks), and
+    /// are rounded outwards to the image's sparse block size.
+    ///
+    /// [`bind_sparse`]: crate::device::QueueGuard::bind_sparse
+    pub fn is_resident(
+        &self,
+        aspects: ImageAspects,
+        mip_level: u32,
+        array_layer: u32,
+        offset: [u32; 3],
+        extent: [u32; 3],
+    ) -> bool {
+        if !matches!(self.memory, ImageMemory::Sparse(_)) {
+            return true;
+        }
+
+        let state = self.state();
+
+        if !self.flags().intersects(ImageCreateFlags::SPARSE_RESIDENCY) {
+            return state.is_opaque_range_resident(&(0..self.memory_requirements()[0].size));
+        }
+
+        if let Some(tile_ranges) =
+            self.sparse_tile_ranges(aspects, mip_level, array_layer, offset, extent)
+        {
+            tile_ranges
+                .iter()
+                .all(|tile_range| state.is_resident(tile_range))
+        } else if let Some(mip_tail_range) = self.sparse_mip_tail_range(aspects, array_layer) {
+            state.is_opaque_range_resident(&mip_tail_range)
+        } else {
+            false
+        }
+    }
+
+    /// For a sparse image, returns the requirements that apply to `aspects`.
+    pub(crate) fn sparse_requirements(
+        &self,
+        aspects: ImageAspects,
+    ) -> Option<&SparseImageMemoryRequirements> {
+        match &self.memory {
+            ImageMemory::Sparse(sparse_requirements) => sparse_requirements
+                .iter()
+                .find(|requirements| requirements.format_properties.aspects.contains(aspects)),
+            _ => None,
+        }
+    }
+
+    /// For a sparse image, returns the ranges of sparse block indices that cover the given region
+    /// of a subresource. Returns `None` if the subresource is not part of the image, or if it is
+    /// part of the mip tail region.
+    ///
+    /// The blocks of the image are numbered consecutively, first in row-major order within each
+    /// subresource, then by mip level, then by array layer, then by the aspects in the order of
+    /// the image's sparse memory requirements.
+    pub(crate) fn sparse_tile_ranges(
+        &self,
+        aspects: ImageAspects,
+        mip_level: u32,
+        array_layer: u32,
+        offset: [u32; 3],
+        extent: [u32; 3],
+    ) -> Option<SmallVec<[Range<DeviceSize>; 4]>> {
+        match &self.memory {
+            ImageMemory::Sparse(sparse_requirements) => sparse_tile_ranges(
+                self.dimensions(),
+                self.mip_levels(),
+                sparse_requirements,
+                aspects,
+                mip_level,
+                array_layer,
+                offset,
+                extent,
+            ),
+            _ => None,
+        }
+    }
+
+    /// For a sparse image, returns whether `range` of the opaque memory of the image lies
+    /// entirely within one of the mip tail regions.
+    pub(crate) fn is_sparse_mip_tail_range(&self, range: &Range<DeviceSize>) -> bool {
+        let sparse_requirements = match &self.memory {
+            ImageMemory::Sparse(sparse_requirements) => sparse_requirements,
+            _ => return false,
+        };
+
+        sparse_requirements.iter().any(|requirements| {
+            (0..self.dimensions().array_layers()).any(|array_layer| {
+                matches!(
+                    self.sparse_mip_tail_range(requirements.format_properties.aspects, array_layer),
+                    Some(mip_tail_range)
+                        if mip_tail_range.start <= range.start && range.end <= mip_tail_range.end
+                )
+            })
+        })
+    }
+
+    /// For a sparse image, returns the range of the opaque memory of the image that holds the
+    /// mip tail region of `array_layer` for `aspects`.
+    pub(crate) fn sparse_mip_tail_range(
+        &self,
+        aspects: ImageAspects,
+        array_layer: u32,
+    ) -> Option<Range<DeviceSize>> {
+        let requirements = self.sparse_requirements(aspects)?;
+
+        if requirements.image_mip_tail_size == 0 {
+            return None;
+        }
+
+        let offset = if requirements
+            .format_properties
+            .flags
+            .intersects(SparseImageFormatFlags::SINGLE_MIPTAIL)
+        {
+            requirements.image_mip_tail_offset
+        } else {
+            requirements.image_mip_tail_offset
+                + array_layer as DeviceSize * requirements.image_mip_tail_stride.unwrap_or(0)
+        };
+
+        Some(offset..offset + requirements.image_mip_tail_size)
+    }
+
     pub(crate) fn state(&self) -> MutexGuard<'_, ImageState> {
         self.state.lock()
     }
@@ -2341,10 +2681,93 @@ impl Hash for Image {
     }
 }
 
+// The implementation of `Image::sparse_tile_ranges`, separated out so that the numbering can be
+// tested without a device.
+fn sparse_tile_ranges(
+    dimensions: ImageDimensions,
+    mip_levels: u32,
+    sparse_requirements: &[SparseImageMemoryRequirements],
+    aspects: ImageAspects,
+    mip_level: u32,
+    array_layer: u32,
+    offset: [u32; 3],
+    extent: [u32; 3],
+) -> Option<SmallVec<[Range<DeviceSize>; 4]>> {
+    let mut first_tile = 0;
+
+    for requirements in sparse_requirements {
+        let format_properties = &requirements.format_properties;
+
+        if format_properties.aspects.intersects(ImageAspects::METADATA) {
+            continue;
+        }
+
+        let granularity = format_properties
+            .image_granularity
+            .map(|granularity| granularity.max(1) as DeviceSize);
+        let is_target = format_properties.aspects.contains(aspects);
+
+        for layer in 0..dimensions.array_layers() {
+            for level in 0..requirements.image_mip_tail_first_lod.min(mip_levels) {
+                let level_extent = dimensions
+                    .mip_level_dimensions(level)
+                    .unwrap()
+                    .width_height_depth();
+                let tile_counts = [0, 1, 2]
+                    .map(|i| (level_extent[i] as DeviceSize + granularity[i] - 1) / granularity[i]);
+
+                if is_target && layer == array_layer && level == mip_level {
+                    let start = [0, 1, 2].map(|i| offset[i] as DeviceSize / granularity[i]);
+                    let end = [0, 1, 2].map(|i| {
+                        ((offset[i] as DeviceSize + extent[i] as DeviceSize + granularity[i] - 1)
+                            / granularity[i])
+                            .min(tile_counts[i])
+                    });
+
+                    let mut tile_ranges = SmallVec::new();
+
+                    for z in start[2]..end[2] {
+                        for y in start[1]..end[1] {
+                            let row = first_tile + (z * tile_counts[1] + y) * tile_counts[0];
+
+                            if start[0] < end[0] {
+                                tile_ranges.push(row + start[0]..row + end[0]);
+                            }
+                        }
+                    }
+
+                    return Some(tile_ranges);
+                }
+
+                first_tile += tile_counts[0] * tile_counts[1] * tile_counts[2];
+            }
+        }
+
+        if is_target {
+            return None;
+        }
+    }
+
+    None
+}
+
 /// The current state of an image.
 #[derive(Debug)]
 pub(crate) struct ImageState {
     ranges: RangeMap<DeviceSize, ImageRangeState>,
+
+    // For sparse images, the memory that is bound to each sparse block index, as numbered by
+    // `Image::sparse_tile_ranges`.
+    sparse_residency: RangeMap<DeviceSize, Arc<DeviceMemory>>,
+
+    // For sparse residency images, the sparse block indices that are known to have memory bound
+    // to them. This is tracked separately from `sparse_residency`, because opaque bind operations
+    // also bind and unbind blocks, but which blocks they affect is implementation-defined. The
+    // memory of those blocks must then be kept alive even though they are not known to be bound.
+    sparse_resident_blocks: RangeMap<DeviceSize, ()>,
+
+    // For sparse images, the memory that is bound to each range of the image's opaque memory.
+    sparse_opaque_residency: RangeMap<DeviceSize, Arc<DeviceMemory>>,
 }
 
 impl ImageState {
@@ -2362,6 +2785,91 @@ impl ImageState {
             )]
             .into_iter()
             .collect(),
+            sparse_residency: RangeMap::new(),
+            sparse_resident_blocks: RangeMap::new(),
+            sparse_opaque_residency: RangeMap::new(),
+        }
+    }
+
+    pub(crate) fn is_resident(&self, tile_range: &Range<DeviceSize>) -> bool {
+        tile_range.is_empty() || self.sparse_resident_blocks.contains_all(tile_range)
+    }
+
+    pub(crate) fn is_opaque_range_resident(&self, range: &Range<DeviceSize>) -> bool {
+        range.is_empty() || self.sparse_opaque_residency.contains_all(range)
+    }
+
+    /// Records that `memory` has been bound to a range of sparse blocks of a sparse residency
+    /// image, or that the blocks have been unbound if `memory` is `None`. Memory that was
+    /// previously bound to the blocks is pushed onto `unbound_memory`.
+    pub(crate) fn bind_sparse(
+        &mut self,
+        tile_range: Range<DeviceSize>,
+        memory: Option<&Arc<DeviceMemory>>,
+        unbound_memory: &mut Vec<Arc<DeviceMemory>>,
+    ) {
+        if tile_range.is_empty() {
+            return;
+        }
+
+        match memory {
+            Some(_) => self.sparse_resident_blocks.insert(tile_range.clone(), ()),
+            None => self.sparse_resident_blocks.remove(tile_range.clone()),
+        }
+
+        Self::bind_sparse_range(
+            &mut self.sparse_residency,
+            tile_range,
+            memory,
+            unbound_memory,
+        )
+    }
+
+    /// Records that `memory` has been bound to a range of the opaque memory of a sparse image, or
+    /// that the range has been unbound if `memory` is `None`. Memory that was previously bound to
+    /// the range is pushed onto `unbound_memory`.
+    pub(crate) fn bind_sparse_opaque(
+        &mut self,
+        range: Range<DeviceSize>,
+        memory: Option<&Arc<DeviceMemory>>,
+        unbound_memory: &mut Vec<Arc<DeviceMemory>>,
+    ) {
+        Self::bind_sparse_range(
+            &mut self.sparse_opaque_residency,
+            range,
+            memory,
+            unbound_memory,
+        )
+    }
+
+    /// Records the effect on the sparse blocks of a sparse residency image of an opaque bind
+    /// operation outside the mip tail regions. Which blocks such an operation affects is
+    /// implementation-defined, so this is conservative: if memory was bound to the whole image,
+    /// all blocks become resident, and if memory was unbound, no blocks are known to be resident
+    /// anymore. Otherwise, the known residency doesn't change.
+    pub(crate) fn bind_sparse_opaque_blocks(&mut self, memory_bound: bool, whole_image: bool) {
+        if !memory_bound {
+            self.sparse_resident_blocks = RangeMap::new();
+        } else if whole_image {
+            self.sparse_resident_blocks.insert(0..DeviceSize::MAX, ());
+        }
+    }
+
+    fn bind_sparse_range(
+        residency: &mut RangeMap<DeviceSize, Arc<DeviceMemory>>,
+        range: Range<DeviceSize>,
+        memory: Option<&Arc<DeviceMemory>>,
+        unbound_memory: &mut Vec<Arc<DeviceMemory>>,
+    ) {
+        if range.is_empty() {
+            return;
+        }
+
+        unbound_memory.extend(residency.range(&range).map(|(_, memory)| memory.clone()));
+
+        match memory {
+            Some(memory) => residency.insert(range, memory.clone()),
+            None => residency.remove(range),
         }
     }
 
@@ -2927,6 +3435,19 @@ pub enum ImageError {
         queue_family_count: u32,
     },
 
+    /// The image was created with the `sparse_binding` flag, so its memory must be bound with
+    /// sparse bind operations instead.
+    SparseBindingEnabled,
+
+    /// The image was not created with the `sparse_binding` flag.
+    SparseBindingNotEnabled,
+
+    /// The `sparse_residency` flag was set, and tiling was `Linear`.
+    SparseResidencyLinearTiling,
+
+    /// The `sparse_residency` flag was set, but the image type was not 2D or 3D.
+    SparseResidencyNot2dOr3d,
+
     /// The provided `usage` and `stencil_usage` have different values for
     /// `depth_stencil_attachment` or `transient_attachment`.
     StencilUsageMismatch {
@@ -3183,6 +3704,23 @@ impl Display for ImageError {
                 "the sharing mode was set to `Concurrent`, but one of the specified queue family \
                 indices was out of range",
             ),
+            Self::SparseBindingEnabled => write!(
+                f,
+                "the image was created with the `sparse_binding` flag, so its memory must be \
+                bound with sparse bind operations instead",
+            ),
+            Self::SparseBindingNotEnabled => write!(
+                f,
+                "the image was not created with the `sparse_binding` flag",
+            ),
+            Self::SparseResidencyLinearTiling => write!(
+                f,
+                "the `sparse_residency` flag was set, and tiling was `Linear`",
+            ),
+            Self::SparseResidencyNot2dOr3d => write!(
+                f,
+                "the `sparse_residency` flag was set, but the image type was not 2D or 3D",
+            ),
             Self::StencilUsageMismatch {
                 usage: _,
                 stencil_usage: _,
@@ -3234,16 +3772,21 @@ impl From<RequirementNotMet> for ImageError {
 
 #[cfg(test)]
 mod tests {
-    use super::{ImageCreateInfo, ImageError, ImageUsage, RawImage};
+    use super::{
+        sparse_tile_ranges, ImageCreateInfo, ImageError, ImageState, ImageUsage, RawImage,
+    };
     use crate::{
         format::Format,
         image::{
             sys::SubresourceRangeIterator, ImageAspect, ImageAspects, ImageCreateFlags,
-            ImageDimensions, ImageSubresourceRange, SampleCount,
+            ImageDimensions, ImageLayout, ImageSubresourceRange, SampleCount,
+            SparseImageFormatFlags, SparseImageFormatProperties, SparseImageMemoryRequirements,
         },
+        memory::{DeviceMemory, MemoryAllocateInfo},
         DeviceSize, RequiresOneOf,
     };
     use smallvec::SmallVec;
+    use std::sync::Arc;
 
     #[test]
     fn create_sampled() {
@@ -3361,6 +3904,58 @@ mod tests {
         };
     }
 
+    #[test]
+    fn missing_feature_sparse_binding() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let res = RawImage::new(
+            device,
+            ImageCreateInfo {
+                flags: ImageCreateFlags::SPARSE_BINDING,
+                dimensions: ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                usage: ImageUsage::SAMPLED,
+                ..Default::default()
+            },
+        );
+
+        match res {
+            Err(ImageError::RequirementNotMet {
+                requires_one_of: RequiresOneOf { features, .. },
+                ..
+            }) if features.contains(&"sparse_binding") => (),
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn sparse_residency_1d() {
+        let (device, _) = gfx_dev_and_queue!(sparse_binding);
+
+        let res = RawImage::new(
+            device,
+            ImageCreateInfo {
+                flags: ImageCreateFlags::SPARSE_BINDING | ImageCreateFlags::SPARSE_RESIDENCY,
+                dimensions: ImageDimensions::Dim1d {
+                    width: 32,
+                    array_layers: 1,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                usage: ImageUsage::SAMPLED,
+                ..Default::default()
+            },
+        );
+
+        match res {
+            Err(ImageError::SparseResidencyNot2dOr3d) => (),
+            _ => panic!(),
+        };
+    }
+
     #[test]
     fn compressed_not_color_attachment() {
         let (device, _) = gfx_dev_and_queue!();
@@ -3558,4 +4153,194 @@ mod tests {
         );
         assert_eq!(iter.next(), None);
     }
+
+    fn sparse_requirements(
+        aspects: ImageAspects,
+        image_granularity: [u32; 3],
+        image_mip_tail_first_lod: u32,
+    ) -> SparseImageMemoryRequirements {
+        SparseImageMemoryRequirements {
+            format_properties: SparseImageFormatProperties {
+                aspects,
+                image_granularity,
+                flags: SparseImageFormatFlags::empty(),
+            },
+            image_mip_tail_first_lod,
+            image_mip_tail_size: 0,
+            image_mip_tail_offset: 0,
+            image_mip_tail_stride: None,
+        }
+    }
+
+    #[test]
+    fn sparse_tile_ranges_2d() {
+        // Per array layer, mip level 0 has 4x2 blocks and mip level 1 has 2x1 blocks. Mip level 2
+        // is in the mip tail.
+        let dimensions = ImageDimensions::Dim2d {
+            width: 256,
+            height: 128,
+            array_layers: 2,
+        };
+        let requirements = [sparse_requirements(ImageAspects::COLOR, [64, 64, 1], 2)];
+        let tile_ranges = |mip_level, array_layer, offset, extent| {
+            sparse_tile_ranges(
+                dimensions,
+                3,
+                &requirements,
+                ImageAspects::COLOR,
+                mip_level,
+                array_layer,
+                offset,
+                extent,
+            )
+            .map(|tile_ranges| tile_ranges.into_vec())
+        };
+
+        // One range per row of blocks.
+        assert_eq!(
+            tile_ranges(0, 0, [0, 0, 0], [256, 128, 1]),
+            Some(vec![0..4, 4..8]),
+        );
+
+        // The region is rounded outwards to whole blocks.
+        assert_eq!(
+            tile_ranges(0, 0, [64, 64, 0], [100, 64, 1]),
+            Some(vec![5..7]),
+        );
+
+        // Blocks are numbered by mip level, then by array layer.
+        assert_eq!(
+            tile_ranges(1, 0, [0, 0, 0], [128, 64, 1]),
+            Some(vec![8..10]),
+        );
+        assert_eq!(
+            tile_ranges(0, 1, [192, 0, 0], [64, 128, 1]),
+            Some(vec![13..14, 17..18]),
+        );
+        assert_eq!(
+            tile_ranges(1, 1, [0, 0, 0], [128, 64, 1]),
+            Some(vec![18..20]),
+        );
+
+        // The mip tail and subresources outside the image have no blocks.
+        assert_eq!(tile_ranges(2, 0, [0, 0, 0], [64, 32, 1]), None);
+        assert_eq!(tile_ranges(0, 2, [0, 0, 0], [64, 64, 1]), None);
+    }
+
+    #[test]
+    fn sparse_tile_ranges_3d() {
+        let dimensions = ImageDimensions::Dim3d {
+            width: 64,
+            height: 64,
+            depth: 64,
+        };
+        let requirements = [sparse_requirements(ImageAspects::COLOR, [32, 32, 32], 1)];
+
+        // Blocks are numbered by row, then by depth slice.
+        assert_eq!(
+            sparse_tile_ranges(
+                dimensions,
+                1,
+                &requirements,
+                ImageAspects::COLOR,
+                0,
+                0,
+                [0, 0, 32],
+                [64, 64, 32],
+            )
+            .map(|tile_ranges| tile_ranges.into_vec()),
+            Some(vec![4..6, 6..8]),
+        );
+    }
+
+    #[test]
+    fn sparse_tile_ranges_aspects() {
+        let dimensions = ImageDimensions::Dim2d {
+            width: 128,
+            height: 128,
+            array_layers: 1,
+        };
+
+        // The metadata aspect has no blocks, so the stencil blocks follow the depth blocks.
+        let requirements = [
+            sparse_requirements(ImageAspects::METADATA, [0, 0, 0], 0),
+            sparse_requirements(ImageAspects::DEPTH, [64, 64, 1], 2),
+            sparse_requirements(ImageAspects::STENCIL, [128, 64, 1], 2),
+        ];
+        let tile_ranges = |aspects, mip_level| {
+            sparse_tile_ranges(
+                dimensions,
+                2,
+                &requirements,
+                aspects,
+                mip_level,
+                0,
+                [0, 0, 0],
+                [128, 128, 1],
+            )
+            .map(|tile_ranges| tile_ranges.into_vec())
+        };
+
+        assert_eq!(tile_ranges(ImageAspects::DEPTH, 0), Some(vec![0..2, 2..4]));
+        assert_eq!(tile_ranges(ImageAspects::DEPTH, 1), Some(vec![4..5]));
+        assert_eq!(
+            tile_ranges(ImageAspects::STENCIL, 0),
+            Some(vec![5..6, 6..7])
+        );
+        assert_eq!(tile_ranges(ImageAspects::STENCIL, 1), Some(vec![7..8]));
+        assert_eq!(tile_ranges(ImageAspects::METADATA, 0), None);
+    }
+
+    #[test]
+    fn bind_sparse_residency() {
+        let (device, _) = gfx_dev_and_queue!();
+        let allocate = || {
+            Arc::new(
+                DeviceMemory::allocate(
+                    device.clone(),
+                    MemoryAllocateInfo {
+                        allocation_size: 1024,
+                        memory_type_index: 0,
+                        ..Default::default()
+                    },
+                )
+                .unwrap(),
+            )
+        };
+        let (memory_a, memory_b) = (allocate(), allocate());
+
+        let mut state = ImageState::new(1, ImageLayout::Undefined);
+        let mut unbound_memory = Vec::new();
+
+        state.bind_sparse(0..4, Some(&memory_a), &mut unbound_memory);
+        assert!(state.is_resident(&(0..4)));
+        assert!(!state.is_resident(&(0..8)));
+        assert!(!state.is_opaque_range_resident(&(0..1)));
+
+        // Opaque binds are tracked separately. Binding the whole image makes all blocks resident.
+        state.bind_sparse_opaque(0..65536, Some(&memory_b), &mut unbound_memory);
+        assert!(state.is_opaque_range_resident(&(0..65536)));
+        assert!(!state.is_resident(&(0..8)));
+        state.bind_sparse_opaque_blocks(true, true);
+        assert!(state.is_resident(&(0..8)));
+        assert!(unbound_memory.is_empty());
+
+        // Opaquely binding part of the image leaves the blocks as they are, but unbinding part of
+        // it means that no block is known to be resident anymore.
+        state.bind_sparse_opaque_blocks(true, false);
+        assert!(state.is_resident(&(0..8)));
+        state.bind_sparse_opaque(0..4096, None, &mut unbound_memory);
+        state.bind_sparse_opaque_blocks(false, false);
+        assert!(!state.is_resident(&(0..1)));
+        assert!(!state.is_opaque_range_resident(&(0..65536)));
+        assert!(state.is_opaque_range_resident(&(4096..65536)));
+        assert_eq!(unbound_memory.len(), 1);
+        assert!(Arc::ptr_eq(&unbound_memory[0], &memory_b));
+
+        // The memory of the blocks is still kept, until the blocks themselves are unbound.
+        unbound_memory.clear();
+        state.bind_sparse(0..8, None, &mut unbound_memory);
+        assert_eq!(unbound_memory.len(), 1);
+        assert!(Arc::ptr_eq(&unbound_memory[0], &memory_a));
+    }
 }
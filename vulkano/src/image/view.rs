@@ -30,7 +30,6 @@ use std::{
     hash::{Hash, Hasher},
     mem::MaybeUninit,
     num::NonZeroU64,
-    ptr,
     sync::Arc,
 };
 
@@ -43,6 +42,7 @@ where
     handle: ash::vk::ImageView,
     image: Arc<I>,
     id: NonZeroU64,
+    imported_handle: bool,
 
     component_mapping: ComponentMapping,
     format: Option<Format>,
@@ -597,7 +597,7 @@ where
             (fns.v1_0.create_image_view)(
                 device.handle(),
                 &info_vk,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -605,7 +605,7 @@ where
             output.assume_init()
         };
 
-        Self::from_handle_with_format_features(image, handle, create_info, format_features)
+        Self::from_handle_with_format_features(image, handle, create_info, format_features, false)
     }
 
     /// Creates a default `ImageView`. Equivalent to
@@ -621,6 +621,8 @@ where
     ///
     /// - `handle` must be a valid Vulkan object handle created from `image`.
     /// - `create_info` must match the info used to create the object.
+    /// - `handle` must have been created with a null `pAllocator`, as it is destroyed with a null
+    ///   `pAllocator` when dropped.
     pub unsafe fn from_handle(
         image: Arc<I>,
         handle: ash::vk::ImageView,
@@ -628,7 +630,7 @@ where
     ) -> Result<Arc<Self>, VulkanError> {
         let format_features =
             Self::get_format_features(create_info.format.unwrap(), image.inner().image);
-        Self::from_handle_with_format_features(image, handle, create_info, format_features)
+        Self::from_handle_with_format_features(image, handle, create_info, format_features, true)
     }
 
     unsafe fn from_handle_with_format_features(
@@ -636,6 +638,7 @@ where
         handle: ash::vk::ImageView,
         create_info: ImageViewCreateInfo,
         format_features: FormatFeatures,
+        imported_handle: bool,
     ) -> Result<Arc<Self>, VulkanError> {
         let ImageViewCreateInfo {
             view_type,
@@ -687,6 +690,7 @@ where
             handle,
             image,
             id: Self::next_id(),
+            imported_handle,
             view_type,
             format,
             format_features,
@@ -772,7 +776,11 @@ where
         unsafe {
             let device = self.device();
             let fns = device.fns();
-            (fns.v1_0.destroy_image_view)(device.handle(), self.handle, ptr::null());
+            (fns.v1_0.destroy_image_view)(
+                device.handle(),
+                self.handle,
+                device.destroy_allocation_callbacks(self.imported_handle),
+            );
         }
     }
 }
@@ -52,7 +52,6 @@ use std::{
     fmt::{Debug, Display, Error as FmtError, Formatter},
     mem::MaybeUninit,
     panic::{catch_unwind, AssertUnwindSafe, RefUnwindSafe},
-    ptr,
     sync::Arc,
 };
 
@@ -165,7 +164,7 @@ impl DebugUtilsMessenger {
             (fns.ext_debug_utils.create_debug_utils_messenger_ext)(
                 instance.handle(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -185,7 +184,7 @@ impl Drop for DebugUtilsMessenger {
             (fns.ext_debug_utils.destroy_debug_utils_messenger_ext)(
                 self.instance.handle(),
                 self.handle,
-                ptr::null(),
+                self.instance.allocation_callbacks(),
             );
         }
     }
@@ -84,8 +84,10 @@ use self::debug::{
 };
 pub use self::{extensions::InstanceExtensions, layers::LayerProperties};
 use crate::{
-    device::physical::PhysicalDevice, instance::debug::trampoline, OomError, RequiresOneOf,
-    VulkanError, VulkanLibrary, VulkanObject,
+    device::physical::PhysicalDevice,
+    instance::debug::trampoline,
+    memory::host::{AllocationCallbacks, HostAllocator},
+    OomError, RequiresOneOf, VulkanError, VulkanLibrary, VulkanObject,
 };
 pub use crate::{
     extensions::{ExtensionRestriction, ExtensionRestrictionError},
@@ -267,6 +269,7 @@ pub struct Instance {
     enabled_layers: Vec<String>,
     library: Arc<VulkanLibrary>,
     max_api_version: Version,
+    allocation_callbacks: Option<AllocationCallbacks>,
     _user_callbacks: Vec<Box<UserCallback>>,
 }
 
@@ -324,6 +327,7 @@ impl Instance {
             enumerate_portability,
             enabled_validation_features,
             disabled_validation_features,
+            host_allocator,
             _ne: _,
         } = create_info;
 
@@ -511,13 +515,19 @@ impl Instance {
             create_info_vk.p_next = info as *const _ as *const _;
         }
 
+        let allocation_callbacks = host_allocator.map(AllocationCallbacks::new);
+
         // Creating the Vulkan instance.
         let handle = {
             let mut output = MaybeUninit::uninit();
             let fns = library.fns();
-            (fns.v1_0.create_instance)(&create_info_vk, ptr::null(), output.as_mut_ptr())
-                .result()
-                .map_err(VulkanError::from)?;
+            (fns.v1_0.create_instance)(
+                &create_info_vk,
+                AllocationCallbacks::as_ptr(allocation_callbacks.as_ref()),
+                output.as_mut_ptr(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
             output.assume_init()
         };
 
@@ -539,6 +549,7 @@ impl Instance {
             enabled_layers,
             library,
             max_api_version,
+            allocation_callbacks,
             _user_callbacks: user_callbacks,
         }))
     }
@@ -583,6 +594,36 @@ impl Instance {
         &self.enabled_layers
     }
 
+    /// Returns the allocator that the Vulkan implementation uses for host memory, if one was
+    /// provided when creating the instance.
+    #[inline]
+    pub fn host_allocator(&self) -> Option<&Arc<dyn HostAllocator>> {
+        self.allocation_callbacks
+            .as_ref()
+            .map(AllocationCallbacks::allocator)
+    }
+
+    // The `pAllocator` to use when creating and destroying instance-level objects.
+    #[inline]
+    pub(crate) fn allocation_callbacks(&self) -> *const ash::vk::AllocationCallbacks {
+        AllocationCallbacks::as_ptr(self.allocation_callbacks.as_ref())
+    }
+
+    // The `pAllocator` to use when destroying an instance-level object. Objects that were
+    // created outside of vulkano and imported with `from_handle` are assumed to have been created
+    // with a null `pAllocator`, so they must be destroyed with one as well.
+    #[inline]
+    pub(crate) fn destroy_allocation_callbacks(
+        &self,
+        imported_handle: bool,
+    ) -> *const ash::vk::AllocationCallbacks {
+        if imported_handle {
+            ptr::null()
+        } else {
+            self.allocation_callbacks()
+        }
+    }
+
     /// Returns an iterator that enumerates the physical devices available.
     ///
     /// # Examples
@@ -644,7 +685,7 @@ impl Drop for Instance {
         let fns = self.fns();
 
         unsafe {
-            (fns.v1_0.destroy_instance)(self.handle, ptr::null());
+            (fns.v1_0.destroy_instance)(self.handle, self.allocation_callbacks());
         }
     }
 }
@@ -671,6 +712,7 @@ impl Debug for Instance {
             enabled_layers,
             library: function_pointers,
             max_api_version,
+            allocation_callbacks,
             _user_callbacks: _,
         } = self;
 
@@ -682,6 +724,7 @@ impl Debug for Instance {
             .field("enabled_layers", enabled_layers)
             .field("function_pointers", function_pointers)
             .field("max_api_version", max_api_version)
+            .field("allocation_callbacks", allocation_callbacks)
             .finish_non_exhaustive()
     }
 }
@@ -759,6 +802,17 @@ pub struct InstanceCreateInfo {
     /// extension must be enabled on the instance.
     pub disabled_validation_features: Vec<ValidationFeatureDisable>,
 
+    /// The allocator that the Vulkan implementation should use for host memory.
+    ///
+    /// If `Some`, the allocator is used when creating and destroying the instance and all
+    /// instance-level objects that vulkano creates, such as surfaces and debug messengers. It is
+    /// also used by devices created from the instance, unless they provide their own allocator.
+    /// Objects that are imported with a `from_handle` function are not created by vulkano, so they
+    /// are destroyed without an allocator, and must therefore have been created without one.
+    ///
+    /// The default value is `None`, which lets the implementation use its own allocator.
+    pub host_allocator: Option<Arc<dyn HostAllocator>>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -776,6 +830,7 @@ impl Default for InstanceCreateInfo {
             enumerate_portability: false,
             enabled_validation_features: Vec::new(),
             disabled_validation_features: Vec::new(),
+            host_allocator: None,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -0,0 +1,983 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Defragmentation of memory allocated by a [`GenericMemoryAllocator`].
+//!
+//! Over time, allocating and freeing resources of varying sizes leaves holes in the
+//! `DeviceMemory` blocks of an allocator. These holes may be too small to fit new allocations,
+//! which means that new blocks have to be allocated even though there is plenty of free memory in
+//! total. Defragmentation moves resources into fuller blocks, or towards the start of their own
+//! block, so that the free memory is merged into larger ranges and emptied blocks can eventually
+//! be freed. Only allocators whose suballocator implements [`DefragmentableSuballocator`] can be
+//! defragmented.
+//!
+//! Only resources that opt in by implementing [`MovableBuffer`] or [`MovableImage`] can be moved.
+//! Vulkan does not allow changing the memory that a buffer or image is bound to, so moving a
+//! resource means creating a new buffer or image with the same parameters, binding it to a new
+//! allocation, copying the contents over on the device, and finally swapping the new buffer or
+//! image, together with its [`MemoryAlloc`], into the existing resource object. The workflow is as
+//! follows:
+//!
+//! 1. Call [`defragment`] with the resources that may be moved and a command buffer builder. The
+//!    allocator decides which resources to move, creates the new buffers and images and records
+//!    the copy commands into the builder.
+//! 2. Build the command buffer, submit it, and wait for it to finish executing. Then drop the
+//!    command buffer and any other clones of the moved resources.
+//! 3. Call [`DefragmentationPass::finish`]. Each moved resource is rebound to its new buffer or
+//!    image, and the old one is dropped, which frees its allocation. The resources that could not
+//!    be rebound, because they were still shared, are returned.
+//!
+//! The resources are borrowed mutably for the whole pass, so they can't be written to while the
+//! copy is pending.
+//!
+//! [`defragment`]: GenericMemoryAllocator::defragment
+
+use super::{
+    AllocationType, FreeListAllocator, GenericMemoryAllocator, MemoryAlloc,
+    SuballocationCreateInfo, Suballocator, TlsfAllocator,
+};
+use crate::{
+    buffer::{
+        sys::{Buffer, BufferCreateInfo, BufferMemory, RawBuffer},
+        BufferAccess, BufferError, BufferInner, BufferUsage,
+    },
+    command_buffer::{
+        allocator::CommandBufferAllocator, AutoCommandBufferBuilder, CopyBufferInfo, CopyError,
+        CopyImageInfo, ImageCopy,
+    },
+    device::{Device, DeviceOwned},
+    image::{
+        sys::{Image, ImageCreateInfo, ImageMemory, RawImage},
+        ImageAccess, ImageDescriptorLayouts, ImageError, ImageInner, ImageLayout,
+        ImageSubresourceLayers, ImageUsage,
+    },
+    memory::MemoryRequirements,
+    DeviceSize, VulkanObject,
+};
+use std::{
+    cmp::Reverse,
+    error::Error,
+    fmt::{Debug, Display, Error as FmtError, Formatter},
+    hash::{Hash, Hasher},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+impl<S: DefragmentableSuballocator> GenericMemoryAllocator<S> {
+    /// Plans moves of the given resources to reduce the fragmentation of the allocator's memory,
+    /// and records the copy commands into `builder`.
+    ///
+    /// The returned pass keeps the moved resources borrowed. After the command buffer built from
+    /// `builder` has finished executing, call [`DefragmentationPass::finish`] to rebind the
+    /// resources to their new memory. See [the module-level documentation] for details.
+    ///
+    /// A resource is only moved if all of the following are true, and is silently skipped
+    /// otherwise:
+    ///
+    /// - It was allocated from this allocator, and is not a dedicated allocation.
+    /// - It is backed by a single allocation of normal memory.
+    /// - It covers the whole buffer or image, i.e. it is not a slice or a view of a subresource.
+    /// - It was created with both `transfer_src` and `transfer_dst` usage.
+    /// - If it is an image, its format is not multi-planar.
+    /// - A better place for it was found: either in a block that has less free space than its
+    ///   current block, or at a lower offset in its current block.
+    ///
+    /// [the module-level documentation]: crate::memory::allocator::defragmentation
+    pub fn defragment<'a, L, A>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L, A>,
+        defragmentation_info: DefragmentationInfo<'a>,
+    ) -> Result<DefragmentationPass<'a>, DefragmentationError>
+    where
+        A: CommandBufferAllocator,
+    {
+        let DefragmentationInfo {
+            resources,
+            max_bytes_to_move,
+            max_moves,
+            _ne: _,
+        } = defragmentation_info;
+
+        let candidates: Vec<_> = resources
+            .into_iter()
+            .filter_map(|resource| self.defragmentation_candidate(resource))
+            .collect();
+        let order = move_order(
+            &candidates
+                .iter()
+                .map(|candidate| (candidate.block_free_size, candidate.location.offset))
+                .collect::<Vec<_>>(),
+        );
+        let mut candidates: Vec<_> = candidates.into_iter().map(Some).collect();
+
+        let mut moves = Vec::new();
+        let mut bytes_moved: DeviceSize = 0;
+
+        for index in order {
+            if moves.len() >= max_moves {
+                break;
+            }
+
+            // Each index appears exactly once, so the candidate hasn't been taken yet.
+            let candidate = candidates[index].take().unwrap();
+
+            match candidate.resource {
+                DefragmentationResource::Buffer(resource) => {
+                    let source = resource.buffer_access();
+                    let buffer = source.inner().buffer;
+
+                    if bytes_moved.saturating_add(buffer.size()) > max_bytes_to_move {
+                        continue;
+                    }
+
+                    let raw_buffer = RawBuffer::new(
+                        self.device.clone(),
+                        BufferCreateInfo {
+                            flags: buffer.flags(),
+                            sharing: buffer.sharing().clone(),
+                            size: buffer.size(),
+                            usage: *buffer.usage(),
+                            external_memory_handle_types: buffer.external_memory_handle_types(),
+                            ..Default::default()
+                        },
+                    )?;
+
+                    let allocation = match self.allocate_for_move(
+                        &candidate.location,
+                        raw_buffer.memory_requirements(),
+                        AllocationType::Linear,
+                    ) {
+                        Some(allocation) => allocation,
+                        None => continue,
+                    };
+
+                    let destination = Arc::new(MovedBuffer {
+                        inner: Arc::new(
+                            raw_buffer
+                                .bind_memory(allocation)
+                                .map_err(|(err, _, _)| err)?,
+                        ),
+                    });
+
+                    bytes_moved += buffer.size();
+                    moves.push(PendingMove::Buffer {
+                        resource,
+                        destination,
+                        size: buffer.size(),
+                    });
+                }
+                DefragmentationResource::Image(resource) => {
+                    let source = resource.image_access();
+                    let image = source.inner().image;
+                    let raw_image = RawImage::new(
+                        self.device.clone(),
+                        ImageCreateInfo {
+                            flags: image.flags(),
+                            dimensions: image.dimensions(),
+                            format: image.format(),
+                            mip_levels: image.mip_levels(),
+                            samples: image.samples(),
+                            tiling: image.tiling(),
+                            usage: image.usage(),
+                            stencil_usage: image.stencil_usage(),
+                            sharing: image.sharing().clone(),
+                            initial_layout: ImageLayout::Undefined,
+                            external_memory_handle_types: image.external_memory_handle_types(),
+                            ..Default::default()
+                        },
+                    )?;
+                    let requirements = raw_image.memory_requirements()[0];
+
+                    if bytes_moved.saturating_add(requirements.size) > max_bytes_to_move {
+                        continue;
+                    }
+
+                    let allocation = match self.allocate_for_move(
+                        &candidate.location,
+                        &requirements,
+                        image.tiling().into(),
+                    ) {
+                        Some(allocation) => allocation,
+                        None => continue,
+                    };
+
+                    // The copy leaves the new image in the layout that the resource expects to be
+                    // in between uses.
+                    let layout = source.final_layout_requirement();
+                    let destination = Arc::new(MovedImage {
+                        inner: Arc::new(
+                            raw_image
+                                .bind_memory([allocation])
+                                .map_err(|(err, _, _)| err)?,
+                        ),
+                        layout,
+                        descriptor_layouts: source.descriptor_layouts(),
+                        layout_initialized: AtomicBool::new(false),
+                    });
+
+                    bytes_moved += requirements.size;
+                    moves.push(PendingMove::Image {
+                        resource,
+                        destination,
+                        size: requirements.size,
+                    });
+                }
+            }
+        }
+
+        // The copies are only recorded once every move has been planned, so that an error while
+        // planning doesn't leave copies in the builder that no pass refers to. Recording fails
+        // for reasons that apply to all copies alike, such as the builder being inside a render
+        // pass, so in practice either all copies are recorded or none are. Should a later copy
+        // fail anyway, the moves that were not recorded are dropped from the pass.
+        let mut recorded = 0;
+
+        for pending in &moves {
+            match pending.record_copy(builder) {
+                Ok(()) => recorded += 1,
+                Err(err) if recorded == 0 => return Err(err.into()),
+                Err(_) => break,
+            }
+        }
+
+        moves.truncate(recorded);
+        let bytes_moved = moves.iter().map(PendingMove::size).sum();
+
+        Ok(DefragmentationPass { moves, bytes_moved })
+    }
+
+    // Checks whether the resource can be moved, and if so, where it currently lives.
+    fn defragmentation_candidate<'a>(
+        &self,
+        resource: DefragmentationResource<'a>,
+    ) -> Option<DefragmentationCandidate<'a>> {
+        let (location, block_free_size) = {
+            // The allocation is borrowed from these, so they must outlive it.
+            let (buffer, image);
+            let allocation = match &resource {
+                DefragmentationResource::Buffer(resource) => {
+                    buffer = resource.buffer_access();
+                    let inner = buffer.inner();
+
+                    if inner.offset != 0
+                        || buffer.size() != inner.buffer.size()
+                        || !inner
+                            .buffer
+                            .usage()
+                            .contains(BufferUsage::TRANSFER_SRC | BufferUsage::TRANSFER_DST)
+                    {
+                        return None;
+                    }
+
+                    match inner.buffer.memory() {
+                        BufferMemory::Normal(allocation) => allocation,
+                        _ => return None,
+                    }
+                }
+                DefragmentationResource::Image(resource) => {
+                    image = resource.image_access();
+                    let inner = image.inner();
+
+                    if inner.first_layer != 0
+                        || inner.num_layers != inner.image.dimensions().array_layers()
+                        || inner.first_mipmap_level != 0
+                        || inner.num_mipmap_levels != inner.image.mip_levels()
+                        || !inner
+                            .image
+                            .usage()
+                            .contains(ImageUsage::TRANSFER_SRC | ImageUsage::TRANSFER_DST)
+                        || !matches!(
+                            inner.image.format(),
+                            Some(format) if format.planes().is_empty()
+                        )
+                    {
+                        return None;
+                    }
+
+                    match inner.image.memory() {
+                        ImageMemory::Normal(allocations) if allocations.len() == 1 => {
+                            &allocations[0]
+                        }
+                        _ => return None,
+                    }
+                }
+            };
+
+            let device_memory = allocation.device_memory();
+            let memory_type_index = device_memory.memory_type_index();
+            let blocks = self.pools[memory_type_index as usize].blocks.read();
+            let block = blocks
+                .iter()
+                .find(|block| block.region().device_memory().handle() == device_memory.handle())?;
+
+            let location = CandidateLocation {
+                memory_type_index,
+                device_memory: device_memory.handle(),
+                offset: allocation.offset(),
+            };
+
+            (location, block.free_size())
+        };
+
+        Some(DefragmentationCandidate {
+            resource,
+            location,
+            block_free_size,
+        })
+    }
+
+    // Finds a new place for the resource. Blocks that have less free space than the current one
+    // are tried first, fullest first. Otherwise, the resource may be moved within its current
+    // block, but only if that moves it closer to the start.
+    fn allocate_for_move(
+        &self,
+        location: &CandidateLocation,
+        requirements: &MemoryRequirements,
+        allocation_type: AllocationType,
+    ) -> Option<MemoryAlloc> {
+        if requirements.memory_type_bits & (1 << location.memory_type_index) == 0 {
+            return None;
+        }
+
+        let create_info = SuballocationCreateInfo {
+            size: requirements.size,
+            alignment: requirements.alignment,
+            allocation_type,
+            ..Default::default()
+        };
+
+        let blocks = self.pools[location.memory_type_index as usize]
+            .blocks
+            .read();
+        let current = blocks
+            .iter()
+            .position(|block| block.region().device_memory().handle() == location.device_memory)?;
+        let free_sizes: Vec<_> = blocks.iter().map(|block| block.free_size()).collect();
+
+        for index in destination_blocks(&free_sizes, current) {
+            if let Ok(allocation) = blocks[index].allocate(create_info.clone()) {
+                return Some(allocation);
+            }
+        }
+
+        blocks[current]
+            .allocate(create_info)
+            .ok()
+            .filter(|allocation| allocation.offset() < location.offset)
+    }
+}
+
+// Returns the order in which the candidates should be moved, given the free size of the block
+// that each candidate lives in and its offset within that block. Candidates in the emptiest
+// blocks come first, since those are the blocks that can most likely be emptied completely, and
+// within a block, the candidates at the end come first.
+fn move_order(positions: &[(DeviceSize, DeviceSize)]) -> Vec<usize> {
+    let mut order: Vec<_> = (0..positions.len()).collect();
+    order.sort_by_key(|&index| Reverse(positions[index]));
+
+    order
+}
+
+// Returns the blocks that a resource living in the block with index `current` may be moved to,
+// given the free size of each block. Only blocks that have less free space than the current one
+// qualify, so that resources always flow from emptier to fuller blocks. The fullest block comes
+// first.
+fn destination_blocks(free_sizes: &[DeviceSize], current: usize) -> Vec<usize> {
+    let mut blocks: Vec<_> = (0..free_sizes.len())
+        .filter(|&index| free_sizes[index] < free_sizes[current])
+        .collect();
+    blocks.sort_by_key(|&index| free_sizes[index]);
+
+    blocks
+}
+
+struct DefragmentationCandidate<'a> {
+    resource: DefragmentationResource<'a>,
+    location: CandidateLocation,
+    block_free_size: DeviceSize,
+}
+
+struct CandidateLocation {
+    memory_type_index: u32,
+    device_memory: ash::vk::DeviceMemory,
+    offset: DeviceSize,
+}
+/// Parameters to defragment the memory of an allocator.
+#[derive(Debug)]
+pub struct DefragmentationInfo<'a> {
+    /// The resources that the allocator is allowed to move.
+    ///
+    /// The default value is empty.
+    pub resources: Vec<DefragmentationResource<'a>>,
+
+    /// The maximum total number of bytes that may be moved.
+    ///
+    /// The default value is [`DeviceSize::MAX`].
+    pub max_bytes_to_move: DeviceSize,
+
+    /// The maximum number of resources that may be moved.
+    ///
+    /// The default value is [`usize::MAX`].
+    pub max_moves: usize,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for DefragmentationInfo<'_> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            resources: Vec::new(),
+            max_bytes_to_move: DeviceSize::MAX,
+            max_moves: usize::MAX,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl<'a> DefragmentationInfo<'a> {
+    /// Returns a `DefragmentationInfo` with the specified `resources`.
+    #[inline]
+    pub fn resources(resources: impl IntoIterator<Item = DefragmentationResource<'a>>) -> Self {
+        Self {
+            resources: resources.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+}
+
+/// A resource that the allocator is allowed to move during defragmentation.
+pub enum DefragmentationResource<'a> {
+    Buffer(&'a mut dyn MovableBuffer),
+    Image(&'a mut dyn MovableImage),
+}
+
+impl Debug for DefragmentationResource<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::Buffer(buffer) => f
+                .debug_tuple("Buffer")
+                .field(&buffer.buffer_access().inner().buffer)
+                .finish(),
+            Self::Image(image) => f
+                .debug_tuple("Image")
+                .field(&image.image_access().inner().image)
+                .finish(),
+        }
+    }
+}
+
+impl<'a> From<&'a mut dyn MovableBuffer> for DefragmentationResource<'a> {
+    #[inline]
+    fn from(buffer: &'a mut dyn MovableBuffer) -> Self {
+        Self::Buffer(buffer)
+    }
+}
+
+impl<'a> From<&'a mut dyn MovableImage> for DefragmentationResource<'a> {
+    #[inline]
+    fn from(image: &'a mut dyn MovableImage) -> Self {
+        Self::Image(image)
+    }
+}
+
+/// A buffer resource that can be moved to different memory during defragmentation.
+///
+/// # Safety
+///
+/// - `buffer_mut` must return the same buffer that the `BufferAccess` returned by
+///   `buffer_access` refers to.
+/// - The resource must not hold on to the handle or the memory of its buffer anywhere else, since
+///   both change when the buffer is replaced.
+pub unsafe trait MovableBuffer: Send + Sync {
+    /// Returns the resource as a `BufferAccess`, which is used as the source of the copy.
+    fn buffer_access(&self) -> Arc<dyn BufferAccess>;
+
+    /// Returns the buffer backing the resource, or `None` if the buffer is shared with something
+    /// else, such as a command buffer or a clone of the resource.
+    fn buffer_mut(&mut self) -> Option<&mut Buffer>;
+}
+
+/// An image resource that can be moved to different memory during defragmentation.
+///
+/// # Safety
+///
+/// - `image_mut` must return the same image that the `ImageAccess` returned by `image_access`
+///   refers to.
+/// - The resource must not hold on to the handle or the memory of its image anywhere else, since
+///   both change when the image is replaced.
+pub unsafe trait MovableImage: Send + Sync {
+    /// Returns the resource as an `ImageAccess`, which is used as the source of the copy.
+    fn image_access(&self) -> Arc<dyn ImageAccess>;
+
+    /// Returns the image backing the resource, or `None` if the image is shared with something
+    /// else, such as a command buffer or a clone of the resource.
+    fn image_mut(&mut self) -> Option<&mut Image>;
+}
+
+/// A suballocator that can be used to defragment memory.
+///
+/// To find a new place for a resource, defragmentation allocates from the suballocators of the
+/// candidate blocks, and frees the allocation again if the place turns out to be no better than
+/// the current one. This is only suitable for suballocators that can reuse freed memory, which
+/// excludes for example the [`BumpAllocator`].
+///
+/// # Safety
+///
+/// - Memory that is freed must become available for allocation again.
+///
+/// [`BumpAllocator`]: super::BumpAllocator
+pub unsafe trait DefragmentableSuballocator: Suballocator {}
+
+unsafe impl DefragmentableSuballocator for Arc<FreeListAllocator> {}
+
+unsafe impl DefragmentableSuballocator for Arc<TlsfAllocator> {}
+
+/// The moves planned by [`GenericMemoryAllocator::defragment`].
+///
+/// The moved resources stay borrowed until the pass is finished. Dropping the pass without
+/// finishing it leaves the resources where they were, and frees the new allocations.
+#[derive(Debug)]
+pub struct DefragmentationPass<'a> {
+    moves: Vec<PendingMove<'a>>,
+    bytes_moved: DeviceSize,
+}
+
+impl<'a> DefragmentationPass<'a> {
+    /// Returns the number of resources that are being moved.
+    #[inline]
+    pub fn move_count(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// Returns the total number of bytes that are being moved.
+    #[inline]
+    pub fn bytes_moved(&self) -> DeviceSize {
+        self.bytes_moved
+    }
+
+    /// Rebinds each moved resource to its new buffer or image. The old buffer or image is dropped,
+    /// which frees its allocation.
+    ///
+    /// A resource can only be rebound if its buffer or image is no longer shared, and if the new
+    /// buffer or image is no longer in use by a command buffer. The resources that could not be
+    /// rebound are returned. They stay where they were, and the copies that were made of them
+    /// are freed, so they can be passed to a later defragmentation once they are no longer
+    /// shared.
+    ///
+    /// # Safety
+    ///
+    /// - The command buffer that the copy commands were recorded into must have finished
+    ///   executing.
+    pub unsafe fn finish(self) -> Vec<DefragmentationResource<'a>> {
+        let mut skipped = Vec::new();
+
+        for pending in self.moves {
+            match pending {
+                PendingMove::Buffer {
+                    resource,
+                    destination,
+                    size: _,
+                } => {
+                    let buffer = Arc::try_unwrap(destination)
+                        .ok()
+                        .and_then(|destination| Arc::try_unwrap(destination.inner).ok());
+
+                    match (resource.buffer_mut(), buffer) {
+                        (Some(slot), Some(buffer)) => *slot = buffer,
+                        _ => skipped.push(DefragmentationResource::Buffer(resource)),
+                    }
+                }
+                PendingMove::Image {
+                    resource,
+                    destination,
+                    size: _,
+                } => {
+                    let image = Arc::try_unwrap(destination)
+                        .ok()
+                        .and_then(|destination| Arc::try_unwrap(destination.inner).ok());
+
+                    match (resource.image_mut(), image) {
+                        (Some(slot), Some(image)) => *slot = image,
+                        _ => skipped.push(DefragmentationResource::Image(resource)),
+                    }
+                }
+            }
+        }
+
+        skipped
+    }
+}
+
+enum PendingMove<'a> {
+    Buffer {
+        resource: &'a mut dyn MovableBuffer,
+        destination: Arc<MovedBuffer>,
+        size: DeviceSize,
+    },
+    Image {
+        resource: &'a mut dyn MovableImage,
+        destination: Arc<MovedImage>,
+        size: DeviceSize,
+    },
+}
+
+impl PendingMove<'_> {
+    fn size(&self) -> DeviceSize {
+        match *self {
+            Self::Buffer { size, .. } | Self::Image { size, .. } => size,
+        }
+    }
+
+    fn record_copy<L, A>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L, A>,
+    ) -> Result<(), CopyError>
+    where
+        A: CommandBufferAllocator,
+    {
+        match self {
+            Self::Buffer {
+                resource,
+                destination,
+                ..
+            } => {
+                builder.copy_buffer(CopyBufferInfo::buffers(
+                    resource.buffer_access(),
+                    destination.clone(),
+                ))?;
+            }
+            Self::Image {
+                resource,
+                destination,
+                ..
+            } => {
+                let image = &destination.inner;
+                let dimensions = image.dimensions();
+                let subresource = image.subresource_layers();
+                builder.copy_image(CopyImageInfo {
+                    regions: (0..image.mip_levels())
+                        .map(|mip_level| ImageCopy {
+                            src_subresource: ImageSubresourceLayers {
+                                mip_level,
+                                ..subresource.clone()
+                            },
+                            dst_subresource: ImageSubresourceLayers {
+                                mip_level,
+                                ..subresource.clone()
+                            },
+                            extent: dimensions
+                                .mip_level_dimensions(mip_level)
+                                .unwrap()
+                                .width_height_depth(),
+                            ..Default::default()
+                        })
+                        .collect(),
+                    ..CopyImageInfo::images(resource.image_access(), destination.clone())
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for PendingMove<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::Buffer { destination, .. } => f
+                .debug_struct("Buffer")
+                .field("destination", &destination.inner)
+                .finish_non_exhaustive(),
+            Self::Image { destination, .. } => f
+                .debug_struct("Image")
+                .field("destination", &destination.inner)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+// The destination of a buffer copy, until it is swapped into the moved resource.
+#[derive(Debug)]
+struct MovedBuffer {
+    inner: Arc<Buffer>,
+}
+
+unsafe impl BufferAccess for MovedBuffer {
+    #[inline]
+    fn inner(&self) -> BufferInner<'_> {
+        BufferInner {
+            buffer: &self.inner,
+            offset: 0,
+        }
+    }
+
+    #[inline]
+    fn size(&self) -> DeviceSize {
+        self.inner.size()
+    }
+}
+
+unsafe impl DeviceOwned for MovedBuffer {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.inner.device()
+    }
+}
+
+impl PartialEq for MovedBuffer {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner() == other.inner() && self.size() == other.size()
+    }
+}
+
+impl Eq for MovedBuffer {}
+
+impl Hash for MovedBuffer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner().hash(state);
+        self.size().hash(state);
+    }
+}
+
+// The destination of an image copy, until it is swapped into the moved resource. It uses the
+// final layout of the moved resource as both its initial and final layout.
+#[derive(Debug)]
+struct MovedImage {
+    inner: Arc<Image>,
+    layout: ImageLayout,
+    descriptor_layouts: Option<ImageDescriptorLayouts>,
+    layout_initialized: AtomicBool,
+}
+
+unsafe impl ImageAccess for MovedImage {
+    #[inline]
+    fn inner(&self) -> ImageInner<'_> {
+        ImageInner {
+            image: &self.inner,
+            first_layer: 0,
+            num_layers: self.inner.dimensions().array_layers(),
+            first_mipmap_level: 0,
+            num_mipmap_levels: self.inner.mip_levels(),
+        }
+    }
+
+    #[inline]
+    fn initial_layout_requirement(&self) -> ImageLayout {
+        self.layout
+    }
+
+    #[inline]
+    fn final_layout_requirement(&self) -> ImageLayout {
+        self.layout
+    }
+
+    #[inline]
+    fn descriptor_layouts(&self) -> Option<ImageDescriptorLayouts> {
+        self.descriptor_layouts
+    }
+
+    #[inline]
+    unsafe fn layout_initialized(&self) {
+        self.layout_initialized.store(true, Ordering::SeqCst);
+    }
+
+    #[inline]
+    fn is_layout_initialized(&self) -> bool {
+        self.layout_initialized.load(Ordering::SeqCst)
+    }
+}
+
+unsafe impl DeviceOwned for MovedImage {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.inner.device()
+    }
+}
+
+impl PartialEq for MovedImage {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner() == other.inner()
+    }
+}
+
+impl Eq for MovedImage {}
+
+impl Hash for MovedImage {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner().hash(state);
+    }
+}
+
+/// Error that can happen when defragmenting memory.
+#[derive(Clone, Debug)]
+pub enum DefragmentationError {
+    /// Creating or binding a new buffer failed.
+    BufferError(BufferError),
+
+    /// Creating or binding a new image failed.
+    ImageError(ImageError),
+
+    /// Recording a copy command failed.
+    CopyError(CopyError),
+}
+
+impl Error for DefragmentationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::BufferError(err) => Some(err),
+            Self::ImageError(err) => Some(err),
+            Self::CopyError(err) => Some(err),
+        }
+    }
+}
+
+impl Display for DefragmentationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::BufferError(_) => write!(f, "creating or binding a new buffer failed"),
+            Self::ImageError(_) => write!(f, "creating or binding a new image failed"),
+            Self::CopyError(_) => write!(f, "recording a copy command failed"),
+        }
+    }
+}
+
+impl From<BufferError> for DefragmentationError {
+    fn from(err: BufferError) -> Self {
+        Self::BufferError(err)
+    }
+}
+
+impl From<ImageError> for DefragmentationError {
+    fn from(err: ImageError) -> Self {
+        Self::ImageError(err)
+    }
+}
+
+impl From<CopyError> for DefragmentationError {
+    fn from(err: CopyError) -> Self {
+        Self::CopyError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        buffer::DeviceLocalBuffer,
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, CommandBufferUsage,
+            PrimaryCommandBufferAbstract,
+        },
+        memory::allocator::StandardMemoryAllocator,
+        sync::GpuFuture,
+    };
+
+    #[test]
+    fn move_order_emptiest_block_and_highest_offset_first() {
+        // (free size of the block, offset within the block)
+        let positions = [(100, 0), (300, 64), (100, 128), (300, 0), (200, 32)];
+        assert_eq!(move_order(&positions), [1, 3, 4, 2, 0]);
+        assert!(move_order(&[]).is_empty());
+    }
+
+    #[test]
+    fn destination_blocks_fullest_first() {
+        let free_sizes = [500, 100, 300, 0, 300, 700];
+
+        // Only blocks with less free space than the current block qualify.
+        assert_eq!(destination_blocks(&free_sizes, 0), [3, 1, 2, 4]);
+        assert_eq!(destination_blocks(&free_sizes, 2), [3, 1]);
+        assert_eq!(destination_blocks(&free_sizes, 5), [3, 1, 2, 4, 0]);
+
+        // Nothing is fuller than a full block.
+        assert!(destination_blocks(&free_sizes, 3).is_empty());
+    }
+
+    #[test]
+    fn skip_without_transfer_usage() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let command_buffer_allocator =
+            StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut command_buffer_builder = AutoCommandBufferBuilder::primary(
+            &command_buffer_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+
+        let mut buffer =
+            DeviceLocalBuffer::<[u32]>::array(&memory_allocator, 64, BufferUsage::TRANSFER_SRC, [])
+                .unwrap();
+
+        let pass = memory_allocator
+            .defragment(
+                &mut command_buffer_builder,
+                DefragmentationInfo::resources([DefragmentationResource::Buffer(&mut buffer)]),
+            )
+            .unwrap();
+        assert_eq!(pass.move_count(), 0);
+    }
+
+    #[test]
+    fn move_within_block() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let command_buffer_allocator =
+            StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut command_buffer_builder = AutoCommandBufferBuilder::primary(
+            &command_buffer_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+
+        let offset = |buffer: &Arc<DeviceLocalBuffer<[u32]>>| match buffer.inner().buffer.memory() {
+            BufferMemory::Normal(allocation) => allocation.offset(),
+            _ => unreachable!(),
+        };
+        let usage = BufferUsage::TRANSFER_SRC | BufferUsage::TRANSFER_DST;
+
+        let first = DeviceLocalBuffer::<[u32]>::array(&memory_allocator, 1024, usage, []).unwrap();
+        let mut second =
+            DeviceLocalBuffer::<[u32]>::array(&memory_allocator, 1024, usage, []).unwrap();
+        let old_offset = offset(&second);
+        assert!(offset(&first) < old_offset);
+        drop(first);
+
+        let pass = memory_allocator
+            .defragment(
+                &mut command_buffer_builder,
+                DefragmentationInfo::resources([DefragmentationResource::Buffer(&mut second)]),
+            )
+            .unwrap();
+        assert_eq!(pass.move_count(), 1);
+        assert_eq!(pass.bytes_moved(), 4096);
+
+        command_buffer_builder
+            .build()
+            .unwrap()
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        assert!(unsafe { pass.finish() }.is_empty());
+        assert!(offset(&second) < old_offset);
+    }
+}
@@ -216,26 +216,35 @@
 //! [`mem::forget`]: std::mem::forget
 //! [region]: Suballocator#regions
 
+pub mod defragmentation;
 pub mod suballocator;
 
 use self::array_vec::ArrayVec;
+pub use self::defragmentation::{
+    DefragmentableSuballocator, DefragmentationError, DefragmentationInfo, DefragmentationPass,
+    DefragmentationResource, MovableBuffer, MovableImage,
+};
 pub use self::suballocator::{
     AllocationType, BuddyAllocator, BumpAllocator, FreeListAllocator, MemoryAlloc, PoolAllocator,
-    SuballocationCreateInfo, SuballocationCreationError, Suballocator,
+    SuballocationCreateInfo, SuballocationCreationError, SuballocationNode, SuballocationType,
+    Suballocator, TlsfAllocator,
 };
 use super::{
     DedicatedAllocation, DeviceMemory, ExternalMemoryHandleTypes, MemoryAllocateFlags,
-    MemoryAllocateInfo, MemoryProperties, MemoryPropertyFlags, MemoryRequirements, MemoryType,
+    MemoryAllocateInfo, MemoryHeapBudget, MemoryProperties, MemoryPropertyFlags,
+    MemoryRequirements, MemoryType,
 };
 use crate::{
+    buffer::BufferCreateFlags,
     device::{Device, DeviceOwned},
+    image::ImageCreateFlags,
     DeviceSize, RequirementNotMet, RequiresOneOf, Version, VulkanError,
 };
 use ash::vk::{MAX_MEMORY_HEAPS, MAX_MEMORY_TYPES};
 use parking_lot::RwLock;
 use std::{
     error::Error,
-    fmt::{Display, Error as FmtError, Formatter},
+    fmt::{Debug, Display, Error as FmtError, Formatter, Write},
     sync::Arc,
 };
 
@@ -304,6 +313,7 @@ pub unsafe trait MemoryAllocator: DeviceOwned {
     ///   match the memory requirements of the resource.
     /// - If `create_info.dedicated_allocation` is `Some` then the device the resource was created
     ///   with must match the device the allocator was created with.
+    /// - `create_info.priority` must be between 0.0 and 1.0 inclusive.
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
     unsafe fn allocate_unchecked(
         &self,
@@ -322,6 +332,7 @@ pub unsafe trait MemoryAllocator: DeviceOwned {
     ///   reported by [`ExternalBufferProperties::external_memory_properties`] or
     ///   [`ImageFormatProperties::external_memory_properties`], then `dedicated_allocation` must
     ///   not be `None`.
+    /// - `priority` must be between 0.0 and 1.0 inclusive.
     ///
     /// [`ExternalBufferProperties`]: crate::buffer::ExternalBufferProperties
     /// [`ImageFormatProperties`]: crate::image::ImageFormatProperties
@@ -334,11 +345,19 @@ pub unsafe trait MemoryAllocator: DeviceOwned {
         allocation_size: DeviceSize,
         dedicated_allocation: Option<DedicatedAllocation<'_>>,
         export_handle_types: ExternalMemoryHandleTypes,
+        priority: f32,
     ) -> Result<MemoryAlloc, AllocationCreationError>;
 }
 
 /// Describes what memory property flags are required, preferred and not preferred when picking a
 /// memory type index.
+///
+/// Memory types with the [`PROTECTED`] flag are only picked if `required_flags` contains
+/// [`PROTECTED`]. Protected memory can only be bound to protected buffers and images, and an
+/// unprotected resource can't be bound to protected memory, so they are never picked as a
+/// fallback.
+///
+/// [`PROTECTED`]: MemoryPropertyFlags::PROTECTED
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct MemoryTypeFilter {
     pub required_flags: MemoryPropertyFlags,
@@ -433,6 +452,28 @@ pub struct AllocationCreateInfo<'d> {
     /// [`khr_dedicated_allocation`]: crate::device::DeviceExtensions::khr_dedicated_allocation
     pub dedicated_allocation: Option<DedicatedAllocation<'d>>,
 
+    /// The priority of the allocation relative to other allocations, between 0.0 and 1.0.
+    ///
+    /// This is forwarded to [`MemoryAllocateInfo::priority`], and is therefore only honored for
+    /// dedicated allocations. Blocks that allocations are suballocated from are shared between
+    /// many allocations, so they are always created with the default priority of `0.5`. For this
+    /// reason, if this is not `0.5` and [`allocate_preference`] is
+    /// [`MemoryAllocatePreference::Unknown`], the allocator prefers a dedicated allocation. The
+    /// allocation may still end up being suballocated if a dedicated allocation fails or the
+    /// [`max_allocations`] limit is reached, in which case the priority is ignored. If the
+    /// priority must be honored, set [`allocate_preference`] to
+    /// [`MemoryAllocatePreference::AlwaysAllocate`].
+    ///
+    /// This option is silently ignored (treated as `0.5`) if the [`memory_priority`] feature is
+    /// not enabled on the device.
+    ///
+    /// The default value is `0.5`.
+    ///
+    /// [`allocate_preference`]: Self::allocate_preference
+    /// [`max_allocations`]: crate::device::Properties::max_memory_allocation_count
+    /// [`memory_priority`]: crate::device::Features::memory_priority
+    pub priority: f32,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -451,6 +492,7 @@ impl Default for AllocationCreateInfo<'_> {
             usage: MemoryUsage::GpuOnly,
             allocate_preference: MemoryAllocatePreference::Unknown,
             dedicated_allocation: None,
+            priority: 0.5,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -557,6 +599,13 @@ pub enum AllocationCreationError {
     /// This is returned when using [`GenericMemoryAllocator<Arc<PoolAllocator<BLOCK_SIZE>>>`] if
     /// the allocation size exceeded `BLOCK_SIZE`.
     SuballocatorBlockSizeExceeded,
+
+    /// Allocating the memory would exceed the budget of the memory heap.
+    ///
+    /// This is returned when the allocator was created with [`MemoryBudgetPolicy::Fail`] or
+    /// [`MemoryBudgetPolicy::FallBack`] and allocating a new [`DeviceMemory`] block would exceed
+    /// the budget of the heap, for all suitable memory types in the latter case.
+    OutOfBudget,
 }
 
 impl Error for AllocationCreationError {
@@ -586,6 +635,10 @@ impl Display for AllocationCreationError {
                 f,
                 "the allocation size was greater than the suballocator's block size",
             ),
+            Self::OutOfBudget => write!(
+                f,
+                "allocating the memory would exceed the budget of the memory heap",
+            ),
         }
     }
 }
@@ -596,6 +649,88 @@ impl From<VulkanError> for AllocationCreationError {
     }
 }
 
+/// Statistics about the memory of one or more [regions], as returned by
+/// [`Suballocator::statistics`] and [`GenericMemoryAllocator::statistics`].
+///
+/// [regions]: Suballocator#regions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AllocationStatistics {
+    /// The number of regions (blocks of [`DeviceMemory`] in the case of a memory allocator).
+    pub block_count: usize,
+
+    /// The total size of the regions in bytes.
+    pub block_size: DeviceSize,
+
+    /// The number of suballocations that are currently alive.
+    pub allocation_count: usize,
+
+    /// The number of bytes that are not available to the allocator. This includes [internal
+    /// fragmentation] and is always equal to `block_size - free_size`.
+    ///
+    /// [internal fragmentation]: self#internal-fragmentation
+    pub used_size: DeviceSize,
+
+    /// The number of bytes that are available to the allocator.
+    pub free_size: DeviceSize,
+
+    /// The number of free ranges, that is, contiguous portions of free memory.
+    pub free_range_count: usize,
+
+    /// The size of the largest free range in bytes. This is the size of the largest allocation
+    /// that could currently be made without any alignment requirements.
+    pub largest_free_range: DeviceSize,
+}
+
+impl AllocationStatistics {
+    /// Returns the ratio of free memory that can not be used for an allocation as big as the
+    /// total free size due to [external fragmentation], in the range \[0,&nbsp;1\].
+    ///
+    /// A value of 0 means that all the free memory is contiguous (or that there is no free
+    /// memory), and values approaching 1 mean that the free memory is scattered across many small
+    /// ranges. When the statistics are aggregated over multiple regions, the free memory of
+    /// different regions is never considered contiguous.
+    ///
+    /// [external fragmentation]: self#external-fragmentation
+    #[inline]
+    pub fn fragmentation(&self) -> f64 {
+        if self.free_size == 0 {
+            0.0
+        } else {
+            1.0 - self.largest_free_range as f64 / self.free_size as f64
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.block_count += other.block_count;
+        self.block_size += other.block_size;
+        self.allocation_count += other.allocation_count;
+        self.used_size += other.used_size;
+        self.free_size += other.free_size;
+        self.free_range_count += other.free_range_count;
+        self.largest_free_range =
+            DeviceSize::max(self.largest_free_range, other.largest_free_range);
+    }
+
+    fn write_json(&self, out: &mut String) {
+        write!(
+            out,
+            "{{\"BlockCount\":{},\"BlockSize\":{},\"AllocationCount\":{},\"UsedSize\":{},\
+            \"FreeSize\":{},\"FreeRangeCount\":{},\"LargestFreeRange\":{},\
+            \"Fragmentation\":{}}}",
+            self.block_count,
+            self.block_size,
+            self.allocation_count,
+            self.used_size,
+            self.free_size,
+            self.free_range_count,
+            self.largest_free_range,
+            self.fragmentation(),
+        )
+        .unwrap();
+    }
+}
+
 /// Standard memory allocator intended as a global and general-purpose allocator.
 ///
 /// This type of allocator should work well in most cases, it is however **not** to be used when
@@ -697,6 +832,7 @@ pub struct GenericMemoryAllocator<S: Suballocator> {
     memory_type_bits: u32,
     // How many `DeviceMemory` allocations should be allowed before restricting them.
     max_allocations: u32,
+    budget_policy: MemoryBudgetPolicy,
 }
 
 #[derive(Debug)]
@@ -745,6 +881,7 @@ impl<S: Suballocator> GenericMemoryAllocator<S> {
             dedicated_allocation: _,
             export_handle_types,
             device_address: _,
+            ref budget_policy,
             _ne: _,
         } = create_info;
 
@@ -788,6 +925,18 @@ impl<S: Suballocator> GenericMemoryAllocator<S> {
             }
         }
 
+        if !matches!(budget_policy, MemoryBudgetPolicy::Ignore)
+            && !device.enabled_extensions().ext_memory_budget
+        {
+            return Err(GenericMemoryAllocatorCreationError::RequirementNotMet {
+                required_for: "`create_info.budget_policy` is not `MemoryBudgetPolicy::Ignore`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["ext_memory_budget"],
+                    ..Default::default()
+                },
+            });
+        }
+
         Ok(())
     }
 
@@ -802,6 +951,7 @@ impl<S: Suballocator> GenericMemoryAllocator<S> {
             dedicated_allocation,
             export_handle_types,
             mut device_address,
+            budget_policy,
             _ne: _,
         } = create_info;
 
@@ -855,11 +1005,12 @@ impl<S: Suballocator> GenericMemoryAllocator<S> {
         for (index, MemoryType { property_flags, .. }) in memory_types.iter().enumerate() {
             if property_flags.intersects(
                 MemoryPropertyFlags::LAZILY_ALLOCATED
-                    | MemoryPropertyFlags::PROTECTED
                     | MemoryPropertyFlags::DEVICE_COHERENT
                     | MemoryPropertyFlags::DEVICE_UNCACHED
                     | MemoryPropertyFlags::RDMA_CAPABLE,
-            ) {
+            ) || property_flags.intersects(MemoryPropertyFlags::PROTECTED)
+                && !device.enabled_features().protected_memory
+            {
                 // VUID-VkMemoryAllocateInfo-memoryTypeIndex-01872
                 // VUID-vkAllocateMemory-deviceCoherentMemory-02790
                 // Lazily allocated memory would just cause problems for suballocation in general.
@@ -887,7 +1038,128 @@ impl<S: Suballocator> GenericMemoryAllocator<S> {
             },
             memory_type_bits,
             max_allocations,
+            budget_policy,
+        }
+    }
+
+    /// Checks whether allocating `allocation_size` bytes of `DeviceMemory` from the heap with
+    /// index `heap_index` would exceed the heap's budget.
+    fn exceeds_budget(&self, heap_index: u32, allocation_size: DeviceSize) -> bool {
+        if matches!(self.budget_policy, MemoryBudgetPolicy::Ignore) {
+            return false;
+        }
+
+        // SAFETY: The `ext_memory_budget` extension was validated to be enabled when creating the
+        // allocator, which in turn requires the physical device properties query to be available.
+        let budget =
+            unsafe { self.device.physical_device().memory_budget_unchecked() }[heap_index as usize];
+
+        budget.usage.saturating_add(allocation_size) > budget.budget
+    }
+
+    /// Handles the case where allocating `DeviceMemory` would exceed the budget, according to the
+    /// budget policy. Returns `Ok` if the allocation should proceed anyway.
+    fn handle_budget_exceeded(
+        &self,
+        heap_index: u32,
+        allocation_size: DeviceSize,
+    ) -> Result<(), AllocationCreationError> {
+        match &self.budget_policy {
+            MemoryBudgetPolicy::Ignore => Ok(()),
+            MemoryBudgetPolicy::Fail | MemoryBudgetPolicy::FallBack => {
+                Err(AllocationCreationError::OutOfBudget)
+            }
+            MemoryBudgetPolicy::Callback(callback) => {
+                let budget = unsafe { self.device.physical_device().memory_budget_unchecked() }
+                    [heap_index as usize];
+                callback(&MemoryBudgetExceeded {
+                    heap_index,
+                    allocation_size,
+                    budget,
+                    _ne: crate::NonExhaustive(()),
+                });
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns statistics about the [`DeviceMemory`] blocks of all memory types.
+    ///
+    /// Only the blocks that the allocator suballocates are taken into account. Dedicated
+    /// allocations and other allocations that are not suballocated are not tracked by the
+    /// allocator.
+    pub fn statistics(&self) -> AllocationStatistics {
+        let mut statistics = AllocationStatistics::default();
+
+        for memory_type_index in 0..self.pools.len() as u32 {
+            statistics.merge(&self.memory_type_statistics(memory_type_index));
+        }
+
+        statistics
+    }
+
+    /// Returns statistics about the [`DeviceMemory`] blocks of the memory type corresponding to
+    /// `memory_type_index`.
+    ///
+    /// Only the blocks that the allocator suballocates are taken into account. Dedicated
+    /// allocations and other allocations that are not suballocated are not tracked by the
+    /// allocator.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `memory_type_index` is not less than the number of available memory types.
+    pub fn memory_type_statistics(&self, memory_type_index: u32) -> AllocationStatistics {
+        let mut statistics = AllocationStatistics::default();
+
+        for block in self.pools[memory_type_index as usize].blocks.read().iter() {
+            statistics.merge(&block.statistics());
         }
+
+        statistics
+    }
+
+    /// Builds a JSON string describing the state of the allocator, including the statistics of
+    /// every memory type and the full map of suballocations of every [`DeviceMemory`] block.
+    ///
+    /// This is meant for debugging purposes, such as figuring out the cause of an out-of-memory
+    /// error. The format of the string is not guaranteed to stay the same across versions.
+    pub fn build_statistics_json(&self) -> String {
+        let mut out = String::new();
+
+        out.push_str("{\"Total\":");
+        self.statistics().write_json(&mut out);
+        out.push_str(",\"MemoryTypes\":[");
+
+        for (index, pool) in self.pools.iter().enumerate() {
+            if index != 0 {
+                out.push(',');
+            }
+
+            write!(
+                out,
+                "{{\"Index\":{},\"HeapIndex\":{},\"PropertyFlags\":\"{:?}\",\"Statistics\":",
+                index, pool.memory_type.heap_index, pool.memory_type.property_flags,
+            )
+            .unwrap();
+            self.memory_type_statistics(index as u32)
+                .write_json(&mut out);
+            out.push_str(",\"Blocks\":[");
+
+            for (index, block) in pool.blocks.read().iter().enumerate() {
+                if index != 0 {
+                    out.push(',');
+                }
+
+                out.push_str(&block.build_statistics_json());
+            }
+
+            out.push_str("]}");
+        }
+
+        out.push_str("]}");
+
+        out
     }
 
     fn validate_allocate_from_type(
@@ -926,11 +1198,15 @@ impl<S: Suballocator> GenericMemoryAllocator<S> {
             usage: _,
             allocate_preference: _,
             dedicated_allocation,
+            priority,
             _ne: _,
         } = create_info;
 
         SuballocationCreateInfo::from(create_info.clone()).validate();
 
+        // VUID-VkMemoryPriorityAllocateInfoEXT-priority-02602
+        assert!((0.0..=1.0).contains(&priority));
+
         assert!(requirements.memory_type_bits != 0);
         assert!(requirements.memory_type_bits < 1 << self.pools.len());
 
@@ -978,9 +1254,12 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
             .map(|pool| pool.memory_type.property_flags)
             .enumerate()
             // Filter out memory types which are supported by the memory type bits and have the
-            // required flags set.
+            // required flags set. Protected memory types are only picked if they're required.
             .filter(|&(index, flags)| {
-                memory_type_bits & (1 << index) != 0 && flags & required_flags == required_flags
+                memory_type_bits & (1 << index) != 0
+                    && flags & required_flags == required_flags
+                    && (!flags.contains(ash::vk::MemoryPropertyFlags::PROTECTED)
+                        || required_flags.contains(ash::vk::MemoryPropertyFlags::PROTECTED))
             })
             // Rank memory types with more of the preferred flags higher, and ones with more of the
             // not preferred flags lower.
@@ -1008,6 +1287,9 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
     ///
     /// - Returns an error if allocating a new block is required and failed. This can be one of the
     ///   OOM errors or [`TooManyObjects`].
+    /// - Returns [`OutOfBudget`] if allocating a new block is required, even the smallest block
+    ///   that can hold the allocation would exceed the memory budget, and the budget policy
+    ///   doesn't allow exceeding it.
     /// - Returns [`BlockSizeExceeded`] if `create_info.size` is greater than the block size
     ///   corresponding to the heap that the memory type corresponding to `memory_type_index`
     ///   resides in.
@@ -1019,6 +1301,7 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
     /// [`DEVICE_COHERENT`]: MemoryPropertyFlags::DEVICE_COHERENT
     /// [`device_coherent_memory`]: crate::device::Features::device_coherent_memory
     /// [`TooManyObjects`]: VulkanError::TooManyObjects
+    /// [`OutOfBudget`]: AllocationCreationError::OutOfBudget
     /// [`BlockSizeExceeded`]: AllocationCreationError::BlockSizeExceeded
     /// [`SuballocatorBlockSizeExceeded`]: AllocationCreationError::SuballocatorBlockSizeExceeded
     fn allocate_from_type(
@@ -1043,6 +1326,7 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
                     } else {
                         ExternalMemoryHandleTypes::empty()
                     },
+                    0.5,
                 )
             };
         }
@@ -1159,9 +1443,22 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
             } else {
                 ExternalMemoryHandleTypes::empty()
             };
+            let heap_index = pool.memory_type.heap_index;
+            // Retry up to 3 times, halving the allocation size each time, as long as the block
+            // stays large enough for the allocation.
+            let can_halve = |i: u32| i < 3 && block_size >> (i + 1) >= size;
             let mut i = 0;
 
             loop {
+                if self.exceeds_budget(heap_index, block_size >> i) {
+                    if can_halve(i) {
+                        i += 1;
+                        continue;
+                    }
+
+                    self.handle_budget_exceeded(heap_index, block_size >> i)?;
+                }
+
                 let allocate_info = MemoryAllocateInfo {
                     allocation_size: block_size >> i,
                     memory_type_index,
@@ -1174,8 +1471,9 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
                     Ok(device_memory) => {
                         break S::new(MemoryAlloc::new(device_memory)?);
                     }
-                    // Retry up to 3 times, halving the allocation size each time.
-                    Err(VulkanError::OutOfHostMemory | VulkanError::OutOfDeviceMemory) if i < 3 => {
+                    Err(VulkanError::OutOfHostMemory | VulkanError::OutOfDeviceMemory)
+                        if can_halve(i) =>
+                    {
                         i += 1;
                     }
                     Err(err) => return Err(err.into()),
@@ -1188,11 +1486,8 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
 
         match block.allocate_unchecked(create_info) {
             Ok(alloc) => Ok(alloc),
-            // This can happen if the block ended up smaller than advertised because there wasn't
-            // enough memory.
-            Err(SuballocationCreationError::OutOfRegionMemory) => Err(
-                AllocationCreationError::VulkanError(VulkanError::OutOfDeviceMemory),
-            ),
+            // This can not happen as the block is never made smaller than the allocation size.
+            Err(SuballocationCreationError::OutOfRegionMemory) => unreachable!(),
             // This can not happen as the block is fresher than Febreze and we're still holding an
             // exclusive lock.
             Err(SuballocationCreationError::FragmentedRegion) => unreachable!(),
@@ -1216,6 +1511,7 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
     ///   where *n* is the number of available memory types.
     /// - Panics if `create_info.dedicated_allocation` is `Some` and
     ///   `create_info.requirements.size` doesn't match the memory requirements of the resource.
+    /// - Panics if `create_info.priority` is not between 0.0 and 1.0 inclusive.
     /// - Panics if finding a suitable memory type failed. This only happens if the
     ///   `create_info.requirements` correspond to those of an optimal image but
     ///   `create_info.usage` is not [`MemoryUsage::GpuOnly`].
@@ -1268,6 +1564,7 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
             usage,
             allocate_preference,
             mut dedicated_allocation,
+            mut priority,
             _ne: _,
         } = create_info;
 
@@ -1275,7 +1572,20 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
 
         memory_type_bits &= self.memory_type_bits;
 
-        let filter = usage.into();
+        let mut filter = MemoryTypeFilter::from(usage);
+
+        // Protected resources can only be bound to protected memory.
+        if match dedicated_allocation {
+            Some(DedicatedAllocation::Buffer(buffer)) => {
+                buffer.flags().intersects(BufferCreateFlags::PROTECTED)
+            }
+            Some(DedicatedAllocation::Image(image)) => {
+                image.flags().intersects(ImageCreateFlags::PROTECTED)
+            }
+            None => false,
+        } {
+            filter.required_flags |= MemoryPropertyFlags::PROTECTED;
+        }
 
         let mut memory_type_index = self
             .find_memory_type_index(memory_type_bits, filter)
@@ -1283,6 +1593,13 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
         if !self.dedicated_allocation {
             dedicated_allocation = None;
         }
+        if !self.device.enabled_features().memory_priority {
+            priority = 0.5;
+        }
+        // Only dedicated allocations can have a priority other than the default.
+        if priority != 0.5 {
+            prefers_dedicated_allocation = true;
+        }
         let export_handle_types = if self.export_handle_types.is_empty() {
             ExternalMemoryHandleTypes::empty()
         } else {
@@ -1301,6 +1618,7 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
                             size,
                             dedicated_allocation,
                             export_handle_types,
+                            priority,
                         )
                     } else {
                         if size > block_size / 2 {
@@ -1318,6 +1636,7 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
                                 size,
                                 dedicated_allocation,
                                 export_handle_types,
+                                priority,
                             )
                             // Fall back to suballocation.
                             .or_else(|err| {
@@ -1346,6 +1665,7 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
                                     size,
                                     dedicated_allocation,
                                     export_handle_types,
+                                    priority,
                                 )
                             })
                         }
@@ -1363,6 +1683,7 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
                     size,
                     dedicated_allocation,
                     export_handle_types,
+                    priority,
                 ),
             };
 
@@ -1372,6 +1693,12 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
                 Err(AllocationCreationError::SuballocatorBlockSizeExceeded) => {
                     return Err(AllocationCreationError::SuballocatorBlockSizeExceeded);
                 }
+                // The budget policy forbids trying other memory types.
+                Err(AllocationCreationError::OutOfBudget)
+                    if matches!(self.budget_policy, MemoryBudgetPolicy::Fail) =>
+                {
+                    return Err(AllocationCreationError::OutOfBudget);
+                }
                 // Try a different memory type.
                 Err(err) => {
                     memory_type_bits &= !(1 << memory_type_index);
@@ -1389,6 +1716,7 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
         allocation_size: DeviceSize,
         mut dedicated_allocation: Option<DedicatedAllocation<'_>>,
         export_handle_types: ExternalMemoryHandleTypes,
+        mut priority: f32,
     ) -> Result<MemoryAlloc, AllocationCreationError> {
         // Providers of `VkMemoryDedicatedAllocateInfo`
         if !(self.device.api_version() >= Version::V1_1
@@ -1397,12 +1725,25 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
             dedicated_allocation = None;
         }
 
+        if !self.device.enabled_features().memory_priority {
+            priority = 0.5;
+        }
+
+        let heap_index = self.pools[memory_type_index as usize]
+            .memory_type
+            .heap_index;
+
+        if self.exceeds_budget(heap_index, allocation_size) {
+            self.handle_budget_exceeded(heap_index, allocation_size)?;
+        }
+
         let allocate_info = MemoryAllocateInfo {
             allocation_size,
             memory_type_index,
             dedicated_allocation,
             export_handle_types,
             flags: self.flags,
+            priority,
             ..Default::default()
         };
         let mut alloc = MemoryAlloc::new(
@@ -1461,12 +1802,14 @@ unsafe impl<S: Suballocator> MemoryAllocator for Arc<GenericMemoryAllocator<S>>
         allocation_size: DeviceSize,
         dedicated_allocation: Option<DedicatedAllocation<'_>>,
         export_handle_types: ExternalMemoryHandleTypes,
+        priority: f32,
     ) -> Result<MemoryAlloc, AllocationCreationError> {
         (**self).allocate_dedicated_unchecked(
             memory_type_index,
             allocation_size,
             dedicated_allocation,
             export_handle_types,
+            priority,
         )
     }
 }
@@ -1505,9 +1848,9 @@ pub struct GenericMemoryAllocatorCreateInfo<'b, 'e> {
     ///
     /// You only need to worry about this if you're using [`PoolAllocator`] as the suballocator, as
     /// all suballocations that the pool allocator makes inherit their allocation type from the
-    /// parent allocation. For the [`FreeListAllocator`] and the [`BuddyAllocator`] this must be
-    /// [`AllocationType::Unknown`] otherwise you will get panics. It does not matter what this is
-    /// when using the [`BumpAllocator`].
+    /// parent allocation. For the [`FreeListAllocator`], the [`TlsfAllocator`] and the
+    /// [`BuddyAllocator`] this must be [`AllocationType::Unknown`] otherwise you will get panics.
+    /// It does not matter what this is when using the [`BumpAllocator`].
     ///
     /// The default value is [`AllocationType::Unknown`].
     pub allocation_type: AllocationType,
@@ -1568,6 +1911,18 @@ pub struct GenericMemoryAllocatorCreateInfo<'b, 'e> {
     /// [`khr_device_group`]: crate::device::DeviceExtensions::khr_device_group
     pub device_address: bool,
 
+    /// What the allocator should do when allocating a new [`DeviceMemory`] block would exceed the
+    /// [budget] of the memory heap.
+    ///
+    /// If this is not [`MemoryBudgetPolicy::Ignore`], then the [`ext_memory_budget`] extension
+    /// must be enabled on the device.
+    ///
+    /// The default value is [`MemoryBudgetPolicy::Ignore`].
+    ///
+    /// [budget]: crate::device::physical::PhysicalDevice::memory_budget
+    /// [`ext_memory_budget`]: crate::device::DeviceExtensions::ext_memory_budget
+    pub budget_policy: MemoryBudgetPolicy,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -1584,11 +1939,68 @@ impl Default for GenericMemoryAllocatorCreateInfo<'_, '_> {
             dedicated_allocation: true,
             export_handle_types: &[],
             device_address: true,
+            budget_policy: MemoryBudgetPolicy::Ignore,
             _ne: crate::NonExhaustive(()),
         }
     }
 }
 
+/// Describes what a [`GenericMemoryAllocator`] should do when allocating [`DeviceMemory`] would
+/// exceed the [budget] of the memory heap.
+///
+/// The budget is only checked when `DeviceMemory` needs to be allocated, suballocating from
+/// existing blocks is never affected.
+///
+/// [budget]: crate::device::physical::PhysicalDevice::memory_budget
+#[derive(Clone)]
+pub enum MemoryBudgetPolicy {
+    /// The budget is not checked at all.
+    Ignore,
+
+    /// Allocation fails with [`AllocationCreationError::OutOfBudget`] without trying any other
+    /// memory types.
+    Fail,
+
+    /// The allocator tries the next most suitable memory type instead, as it would when
+    /// allocation of `DeviceMemory` fails. If all suitable memory types are exhausted, allocation
+    /// fails with [`AllocationCreationError::OutOfBudget`].
+    ///
+    /// Note that this only applies to [`MemoryAllocator::allocate`]. Allocating from a specific
+    /// memory type using [`MemoryAllocator::allocate_from_type`] fails in the same way as
+    /// [`MemoryBudgetPolicy::Fail`].
+    FallBack,
+
+    /// The callback is called, after which the allocation proceeds as if the budget wasn't
+    /// exceeded.
+    Callback(Arc<dyn Fn(&MemoryBudgetExceeded) + Send + Sync>),
+}
+
+impl Debug for MemoryBudgetPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::Ignore => write!(f, "Ignore"),
+            Self::Fail => write!(f, "Fail"),
+            Self::FallBack => write!(f, "FallBack"),
+            Self::Callback(_) => write!(f, "Callback(..)"),
+        }
+    }
+}
+
+/// Information passed to the callback of [`MemoryBudgetPolicy::Callback`].
+#[derive(Clone, Debug)]
+pub struct MemoryBudgetExceeded {
+    /// The index of the memory heap whose budget would be exceeded.
+    pub heap_index: u32,
+
+    /// The size of the `DeviceMemory` that is about to be allocated.
+    pub allocation_size: DeviceSize,
+
+    /// The budget and usage of the memory heap before the allocation.
+    pub budget: MemoryHeapBudget,
+
+    pub _ne: crate::NonExhaustive,
+}
+
 /// Error that can be returned when creating a [`GenericMemoryAllocator`].
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum GenericMemoryAllocatorCreationError {
@@ -1669,3 +2081,27 @@ mod array_vec {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget_policy_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let res = StandardMemoryAllocator::new(
+            device,
+            GenericMemoryAllocatorCreateInfo {
+                block_sizes: &[(0, 64 * M)],
+                budget_policy: MemoryBudgetPolicy::Fail,
+                ..Default::default()
+            },
+        );
+
+        match res {
+            Err(GenericMemoryAllocatorCreationError::RequirementNotMet { .. }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -16,6 +16,7 @@
 use self::host::SlotId;
 use super::{
     align_down, align_up, array_vec::ArrayVec, AllocationCreateInfo, AllocationCreationError,
+    AllocationStatistics,
 };
 use crate::{
     device::{Device, DeviceOwned},
@@ -29,14 +30,14 @@ use std::{
     cell::Cell,
     error::Error,
     ffi::c_void,
-    fmt::{self, Display},
+    fmt::{self, Display, Write},
     mem::{self, ManuallyDrop, MaybeUninit},
     num::NonZeroU64,
     ops::Range,
     ptr::{self, NonNull},
     slice,
     sync::{
-        atomic::{AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
 };
@@ -72,6 +73,10 @@ enum AllocParent {
         allocator: Arc<FreeListAllocator>,
         id: SlotId,
     },
+    Tlsf {
+        allocator: Arc<TlsfAllocator>,
+        id: SlotId,
+    },
     Buddy {
         allocator: Arc<BuddyAllocator>,
         order: usize,
@@ -357,6 +362,7 @@ impl MemoryAlloc {
     pub fn device_memory(&self) -> &DeviceMemory {
         match &self.parent {
             AllocParent::FreeList { allocator, .. } => &allocator.device_memory,
+            AllocParent::Tlsf { allocator, .. } => &allocator.device_memory,
             AllocParent::Buddy { allocator, .. } => &allocator.device_memory,
             AllocParent::Pool { allocator, .. } => &allocator.device_memory,
             AllocParent::Bump(allocator) => &allocator.device_memory,
@@ -373,6 +379,7 @@ impl MemoryAlloc {
     pub fn parent_allocation(&self) -> Option<&Self> {
         match &self.parent {
             AllocParent::FreeList { allocator, .. } => Some(&allocator.region),
+            AllocParent::Tlsf { allocator, .. } => Some(&allocator.region),
             AllocParent::Buddy { allocator, .. } => Some(&allocator.region),
             AllocParent::Pool { allocator, .. } => Some(&allocator.region),
             AllocParent::Bump(allocator) => Some(&allocator.region),
@@ -449,6 +456,7 @@ impl MemoryAlloc {
     fn root(&self) -> Option<&Arc<DeviceMemory>> {
         match &self.parent {
             AllocParent::FreeList { allocator, .. } => Some(&allocator.device_memory),
+            AllocParent::Tlsf { allocator, .. } => Some(&allocator.device_memory),
             AllocParent::Buddy { allocator, .. } => Some(&allocator.device_memory),
             AllocParent::Pool { allocator, .. } => Some(&allocator.device_memory),
             AllocParent::Bump(allocator) => Some(&allocator.device_memory),
@@ -550,6 +558,9 @@ impl Drop for MemoryAlloc {
             AllocParent::FreeList { allocator, id } => {
                 allocator.free(*id);
             }
+            AllocParent::Tlsf { allocator, id } => {
+                allocator.free(*id);
+            }
             AllocParent::Buddy {
                 allocator,
                 order,
@@ -562,7 +573,9 @@ impl Drop for MemoryAlloc {
             }
             // The bump allocator can't free individually, but we need to keep a reference to it so
             // it don't get reset or dropped while in use.
-            AllocParent::Bump(_) => {}
+            AllocParent::Bump(allocator) => {
+                allocator.allocation_count.fetch_sub(1, Ordering::Relaxed);
+            }
             // A root allocation frees itself once all references to the `DeviceMemory` are dropped.
             AllocParent::Root(_) => {}
             // Dedicated allocations free themselves when the `DeviceMemory` is dropped.
@@ -730,6 +743,64 @@ pub unsafe trait Suballocator: DeviceOwned {
 
     /// Tries to free some space, if applicable.
     fn cleanup(&mut self);
+
+    /// Returns statistics about the [region].
+    ///
+    /// The statistics are only a snapshot and may already be outdated by the time they are
+    /// returned if the allocator is shared between threads.
+    ///
+    /// [region]: Self#regions
+    fn statistics(&self) -> AllocationStatistics;
+
+    /// Returns the map of suballocations and free ranges within the [region], sorted by offset.
+    ///
+    /// Suballocators that don't keep track of individual suballocations, namely the
+    /// [`BuddyAllocator`] and the [`BumpAllocator`], report contiguous used memory as a single
+    /// node of type [`SuballocationType::Unknown`]. The [`PoolAllocator`] reports every block as
+    /// a node, regardless of the size of the suballocation within it.
+    ///
+    /// [region]: Self#regions
+    fn suballocations(&self) -> Vec<SuballocationNode>;
+
+    /// Builds a JSON string describing the state of the [region], including its statistics and
+    /// the full map of [suballocations].
+    ///
+    /// This is meant for debugging purposes. The format of the string is not guaranteed to stay
+    /// the same across versions.
+    ///
+    /// [region]: Self#regions
+    /// [suballocations]: Self::suballocations
+    fn build_statistics_json(&self) -> String {
+        let region = self.region();
+        let mut out = String::new();
+
+        write!(
+            out,
+            "{{\"Offset\":{},\"Size\":{},\"Statistics\":",
+            region.offset(),
+            region.size(),
+        )
+        .unwrap();
+        self.statistics().write_json(&mut out);
+        out.push_str(",\"Suballocations\":[");
+
+        for (index, node) in self.suballocations().into_iter().enumerate() {
+            if index != 0 {
+                out.push(',');
+            }
+
+            write!(
+                out,
+                "{{\"Offset\":{},\"Size\":{},\"Type\":\"{:?}\"}}",
+                node.offset, node.size, node.allocation_type,
+            )
+            .unwrap();
+        }
+
+        out.push_str("]}");
+
+        out
+    }
 }
 
 /// Parameters to create a new [allocation] using a [suballocator].
@@ -821,6 +892,48 @@ impl From<ImageTiling> for AllocationType {
     }
 }
 
+/// A node in the map of suballocations returned by [`Suballocator::suballocations`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SuballocationNode {
+    /// The offset of the node within the [`DeviceMemory`] block.
+    pub offset: DeviceSize,
+
+    /// The size of the node in bytes.
+    pub size: DeviceSize,
+
+    /// Whether the node is free, and if not, the type of the suballocation.
+    pub allocation_type: SuballocationType,
+}
+
+/// Tells us if a suballocation is free, and if not, whether it is linear or not. This is needed in
+/// order to be able to respect the buffer-image granularity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SuballocationType {
+    /// The node is a suballocation of type [`AllocationType::Unknown`].
+    Unknown,
+
+    /// The node is a suballocation of type [`AllocationType::Linear`].
+    Linear,
+
+    /// The node is a suballocation of type [`AllocationType::NonLinear`].
+    NonLinear,
+
+    /// The node is free.
+    Free,
+}
+
+impl From<AllocationType> for SuballocationType {
+    #[inline]
+    fn from(ty: AllocationType) -> Self {
+        match ty {
+            AllocationType::Unknown => SuballocationType::Unknown,
+            AllocationType::Linear => SuballocationType::Linear,
+            AllocationType::NonLinear => SuballocationType::NonLinear,
+        }
+    }
+}
+
 /// Error that can be returned when using a [suballocator].
 ///
 /// [suballocator]: Suballocator
@@ -902,6 +1015,9 @@ impl Display for SuballocationCreationError {
 /// allocations that all have the same size then that seems pretty sus. Sounds like you're in dire
 /// need of a `PoolAllocator`.
 ///
+/// If you create and free allocations so often that the time spent searching the free-list shows
+/// up in your profiles, consider the [`TlsfAllocator`], which does the same in constant time.
+///
 /// # Examples
 ///
 /// Most commonly you will not want to use this suballocator directly but rather use it within
@@ -1046,7 +1162,11 @@ impl FreeListAllocator {
             ty: SuballocationType::Free,
         });
         free_list.push(root_id);
-        let state = Mutex::new(FreeListAllocatorState { nodes, free_list });
+        let state = Mutex::new(FreeListAllocatorState {
+            nodes,
+            free_list,
+            head: root_id,
+        });
 
         Arc::new(FreeListAllocator {
             region,
@@ -1113,16 +1233,6 @@ unsafe impl Suballocator for Arc<FreeListAllocator> {
         &self,
         create_info: SuballocationCreateInfo,
     ) -> Result<MemoryAlloc, SuballocationCreationError> {
-        fn has_granularity_conflict(prev_ty: SuballocationType, ty: AllocationType) -> bool {
-            if prev_ty == SuballocationType::Free {
-                false
-            } else if prev_ty == SuballocationType::Unknown {
-                true
-            } else {
-                prev_ty != ty.into()
-            }
-        }
-
         let SuballocationCreateInfo {
             size,
             alignment,
@@ -1219,6 +1329,55 @@ unsafe impl Suballocator for Arc<FreeListAllocator> {
 
     #[inline]
     fn cleanup(&mut self) {}
+
+    #[inline]
+    fn statistics(&self) -> AllocationStatistics {
+        let state = self.state.lock();
+        let free_size = self.free_size();
+        let mut statistics = AllocationStatistics {
+            block_count: 1,
+            block_size: self.region.size,
+            used_size: self.region.size - free_size,
+            free_size,
+            free_range_count: state.free_list.len(),
+            // The free-list is sorted by size, so the last one is the largest.
+            largest_free_range: state
+                .free_list
+                .last()
+                .map_or(0, |&id| state.nodes.get(id).size),
+            ..Default::default()
+        };
+
+        let mut next = Some(state.head);
+        while let Some(id) = next {
+            let node = state.nodes.get(id);
+            if node.ty != SuballocationType::Free {
+                statistics.allocation_count += 1;
+            }
+            next = node.next;
+        }
+
+        statistics
+    }
+
+    #[inline]
+    fn suballocations(&self) -> Vec<SuballocationNode> {
+        let state = self.state.lock();
+        let mut nodes = Vec::new();
+
+        let mut next = Some(state.head);
+        while let Some(id) = next {
+            let node = state.nodes.get(id);
+            nodes.push(SuballocationNode {
+                offset: node.offset,
+                size: node.size,
+                allocation_type: node.ty,
+            });
+            next = node.next;
+        }
+
+        nodes
+    }
 }
 
 unsafe impl DeviceOwned for FreeListAllocator {
@@ -1234,6 +1393,8 @@ struct FreeListAllocatorState {
     // Free suballocations sorted by size in ascending order. This means we can always find a
     // best-fit in *O*(log(*n*)) time in the worst case, and iterating in order is very efficient.
     free_list: Vec<SlotId>,
+    // The suballocation with the lowest offset, used to traverse the whole list.
+    head: SlotId,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -1245,26 +1406,6 @@ struct SuballocationListNode {
     ty: SuballocationType,
 }
 
-/// Tells us if a suballocation is free, and if not, whether it is linear or not. This is needed in
-/// order to be able to respect the buffer-image granularity.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum SuballocationType {
-    Unknown,
-    Linear,
-    NonLinear,
-    Free,
-}
-
-impl From<AllocationType> for SuballocationType {
-    fn from(ty: AllocationType) -> Self {
-        match ty {
-            AllocationType::Unknown => SuballocationType::Unknown,
-            AllocationType::Linear => SuballocationType::Linear,
-            AllocationType::NonLinear => SuballocationType::NonLinear,
-        }
-    }
-}
-
 impl FreeListAllocatorState {
     /// Removes the target suballocation from the free-list. The free-list must contain it.
     fn allocate(&mut self, node_id: SlotId) {
@@ -1284,47 +1425,644 @@ impl FreeListAllocatorState {
                     return;
                 }
 
-                // Check all previous indices that point to suballocations with the same size.
-                {
-                    let mut index = index;
-                    loop {
-                        index = index.wrapping_sub(1);
-                        if let Some(&id) = self.free_list.get(index) {
-                            if id == node_id {
-                                self.free_list.remove(index);
-                                return;
-                            }
-                            if self.nodes.get(id).size != node.size {
-                                break;
-                            }
-                        } else {
-                            break;
-                        }
-                    }
-                }
+                // Check all previous indices that point to suballocations with the same size.
+                {
+                    let mut index = index;
+                    loop {
+                        index = index.wrapping_sub(1);
+                        if let Some(&id) = self.free_list.get(index) {
+                            if id == node_id {
+                                self.free_list.remove(index);
+                                return;
+                            }
+                            if self.nodes.get(id).size != node.size {
+                                break;
+                            }
+                        } else {
+                            break;
+                        }
+                    }
+                }
+
+                // Check all next indices that point to suballocations with the same size.
+                {
+                    let mut index = index;
+                    loop {
+                        index += 1;
+                        if let Some(&id) = self.free_list.get(index) {
+                            if id == node_id {
+                                self.free_list.remove(index);
+                                return;
+                            }
+                            if self.nodes.get(id).size != node.size {
+                                break;
+                            }
+                        } else {
+                            break;
+                        }
+                    }
+                }
+
+                unreachable!();
+            }
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// Fits a suballocation inside the target one, splitting the target at the ends if required.
+    fn split(&mut self, node_id: SlotId, offset: DeviceSize, size: DeviceSize) {
+        let node = self.nodes.get(node_id);
+
+        debug_assert!(node.ty == SuballocationType::Free);
+        debug_assert!(offset >= node.offset);
+        debug_assert!(offset + size <= node.offset + node.size);
+
+        let padding_front = offset - node.offset;
+        let padding_back = node.offset + node.size - offset - size;
+
+        if padding_front > 0 {
+            let padding = SuballocationListNode {
+                prev: node.prev,
+                next: Some(node_id),
+                offset: node.offset,
+                size: padding_front,
+                ty: SuballocationType::Free,
+            };
+            let padding_id = self.nodes.allocate(padding);
+
+            if let Some(prev_id) = padding.prev {
+                self.nodes.get_mut(prev_id).next = Some(padding_id);
+            } else {
+                self.head = padding_id;
+            }
+
+            let node = self.nodes.get_mut(node_id);
+            node.prev = Some(padding_id);
+            node.offset = offset;
+            node.size -= padding.size;
+
+            self.free(padding_id);
+        }
+
+        if padding_back > 0 {
+            let padding = SuballocationListNode {
+                prev: Some(node_id),
+                next: node.next,
+                offset: offset + size,
+                size: padding_back,
+                ty: SuballocationType::Free,
+            };
+            let padding_id = self.nodes.allocate(padding);
+
+            if let Some(next_id) = padding.next {
+                self.nodes.get_mut(next_id).prev = Some(padding_id);
+            }
+
+            let node = self.nodes.get_mut(node_id);
+            node.next = Some(padding_id);
+            node.size -= padding.size;
+
+            self.free(padding_id);
+        }
+    }
+
+    /// Inserts the target suballocation into the free-list. The free-list must not contain it
+    /// already.
+    fn free(&mut self, node_id: SlotId) {
+        debug_assert!(!self.free_list.contains(&node_id));
+
+        let node = self.nodes.get(node_id);
+        let (Ok(index) | Err(index)) = self
+            .free_list
+            .binary_search_by_key(&node.size, |&x| self.nodes.get(x).size);
+        self.free_list.insert(index, node_id);
+    }
+
+    /// Coalesces the target (free) suballocation with adjacent ones that are also free.
+    fn coalesce(&mut self, node_id: SlotId) {
+        let node = self.nodes.get(node_id);
+
+        debug_assert!(node.ty == SuballocationType::Free);
+
+        if let Some(prev_id) = node.prev {
+            let prev = self.nodes.get(prev_id);
+
+            if prev.ty == SuballocationType::Free {
+                self.allocate(prev_id);
+                self.nodes.free(prev_id);
+
+                let node = self.nodes.get_mut(node_id);
+                node.prev = prev.prev;
+                node.offset = prev.offset;
+                node.size += prev.size; // nom nom nom
+
+                if let Some(prev_id) = node.prev {
+                    self.nodes.get_mut(prev_id).next = Some(node_id);
+                } else {
+                    self.head = node_id;
+                }
+            }
+        }
+
+        if let Some(next_id) = node.next {
+            let next = self.nodes.get(next_id);
+
+            if next.ty == SuballocationType::Free {
+                self.allocate(next_id);
+                self.nodes.free(next_id);
+
+                let node = self.nodes.get_mut(node_id);
+                node.next = next.next;
+                node.size += next.size;
+
+                if let Some(next_id) = node.next {
+                    self.nodes.get_mut(next_id).prev = Some(node_id);
+                }
+            }
+        }
+    }
+}
+
+/// A [suballocator] that uses the *two-level segregated fit* (TLSF) algorithm.
+///
+/// Like the [`FreeListAllocator`], this allocator can create and free allocations of any size in
+/// any order without [internal fragmentation], and is therefore just as prone to [external
+/// fragmentation]. The difference lies in how the free suballocations are kept track of: instead
+/// of a single [free-list] sorted by size, the free suballocations are segregated into many
+/// free-lists by size class, which makes both allocating and freeing take constant time. Use this
+/// allocator over the `FreeListAllocator` if you create and free a lot of allocations, to the point
+/// where searching the free-list becomes a bottleneck.
+///
+/// See also [the `Suballocator` implementation].
+///
+/// # Algorithm
+///
+/// The size classes are organized in two levels. The first level divides the possible sizes into
+/// powers of two, and the second level divides each power of two into 32 linearly-spaced
+/// subranges, with sizes below 32B each getting a size class of their own. Every size class has
+/// its own free-list, and there is a bitmap for each level which tracks which of the free-lists
+/// are non-empty.
+///
+/// When an allocation request is made, the requested size is rounded up to the next size class
+/// boundary, such that any free suballocation in that size class or a larger one is guaranteed to
+/// be big enough. Finding the first non-empty free-list from there takes a few bit-scan
+/// instructions on the bitmaps, and the first suballocation in that free-list is chosen. This is
+/// a good-fit strategy rather than a best-fit one, as the chosen suballocation can be up to 1/32
+/// larger than the smallest one that would fit. Like with the `FreeListAllocator`, the chosen
+/// suballocation is trimmed at the ends and the ends are returned to the free-lists, and when an
+/// allocation is freed, it is coalesced with the adjacent suballocations that are also free.
+///
+/// # Efficiency
+///
+/// The allocator is synchronized internally with a lock, which is held only for a very short
+/// period each time an allocation is created and freed. Both allocation and freeing are *O*(1).
+///
+/// To account for [alignment requirements], the size that is searched for is padded by the
+/// alignment, so that the first suballocation that is found always fits. If there is no
+/// suballocation that fits the padded size, or [buffer-image granularity] requires the offset to
+/// be aligned further, then the suballocations that might still fit are checked one by one, which
+/// is *O*(*n*) in the worst case. This only happens when the region is nearly full or very
+/// fragmented.
+///
+/// # Examples
+///
+/// Basic usage together with [`GenericMemoryAllocator`]:
+///
+/// ```
+/// use std::sync::Arc;
+/// use vulkano::memory::allocator::{
+///     GenericMemoryAllocator, GenericMemoryAllocatorCreateInfo, TlsfAllocator,
+/// };
+///
+/// # let device: std::sync::Arc<vulkano::device::Device> = return;
+/// let memory_allocator = GenericMemoryAllocator::<Arc<TlsfAllocator>>::new(
+///     device.clone(),
+///     GenericMemoryAllocatorCreateInfo {
+///         block_sizes: &[(0, 64 * 1024 * 1024)],
+///         ..Default::default()
+///     },
+/// )
+/// .unwrap();
+///
+/// // Now you can use `memory_allocator` to allocate whatever it is you need.
+/// ```
+///
+/// [suballocator]: Suballocator
+/// [internal fragmentation]: super#internal-fragmentation
+/// [external fragmentation]: super#external-fragmentation
+/// [free-list]: Suballocator#free-lists
+/// [the `Suballocator` implementation]: Suballocator#impl-Suballocator-for-Arc<TlsfAllocator>
+/// [alignment requirements]: super#alignment
+/// [buffer-image granularity]: super#buffer-image-granularity
+/// [`GenericMemoryAllocator`]: super::GenericMemoryAllocator
+#[derive(Debug)]
+pub struct TlsfAllocator {
+    region: MemoryAlloc,
+    device_memory: Arc<DeviceMemory>,
+    buffer_image_granularity: DeviceSize,
+    atom_size: DeviceSize,
+    // Total memory remaining in the region.
+    free_size: AtomicU64,
+    state: Mutex<TlsfAllocatorState>,
+}
+
+impl TlsfAllocator {
+    /// Creates a new `TlsfAllocator` for the given [region].
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `region.allocation_type` is not [`AllocationType::Unknown`]. This is done to
+    ///   avoid checking for a special case of [buffer-image granularity] conflict.
+    /// - Panics if `region` is a [dedicated allocation].
+    ///
+    /// [region]: Suballocator#regions
+    /// [buffer-image granularity]: super#buffer-image-granularity
+    /// [dedicated allocation]: MemoryAlloc::is_dedicated
+    #[inline]
+    pub fn new(region: MemoryAlloc) -> Arc<Self> {
+        const AVERAGE_ALLOCATION_SIZE: DeviceSize = 64 * 1024;
+
+        assert!(region.allocation_type == AllocationType::Unknown);
+
+        let device_memory = region
+            .root()
+            .expect("dedicated allocations can't be suballocated")
+            .clone();
+        let buffer_image_granularity = device_memory
+            .device()
+            .physical_device()
+            .properties()
+            .buffer_image_granularity;
+        let atom_size = region.atom_size.map(NonZeroU64::get).unwrap_or(1);
+        let free_size = AtomicU64::new(region.size);
+
+        let capacity = (region.size / AVERAGE_ALLOCATION_SIZE) as usize;
+        let mut nodes = host::PoolAllocator::new(capacity + 64);
+        let root_id = nodes.allocate(TlsfNode {
+            prev: None,
+            next: None,
+            prev_free: None,
+            next_free: None,
+            offset: region.offset,
+            size: region.size,
+            ty: SuballocationType::Free,
+        });
+        let mut state = TlsfAllocatorState {
+            nodes,
+            free_lists: vec![None; TLSF_FL_COUNT * TLSF_SL_COUNT].into_boxed_slice(),
+            fl_bitmap: 0,
+            sl_bitmaps: [0; TLSF_FL_COUNT],
+            head: root_id,
+        };
+        state.free(root_id);
+
+        Arc::new(TlsfAllocator {
+            region,
+            device_memory,
+            buffer_image_granularity,
+            atom_size,
+            free_size,
+            state: Mutex::new(state),
+        })
+    }
+
+    fn free(&self, id: SlotId) {
+        let mut state = self.state.lock();
+        self.free_size
+            .fetch_add(state.nodes.get(id).size, Ordering::Release);
+        state.nodes.get_mut(id).ty = SuballocationType::Free;
+        state.coalesce(id);
+        state.free(id);
+    }
+
+    /// Returns the offset at which a suballocation would be placed inside the target free
+    /// suballocation, or `None` if it doesn't fit.
+    fn fit(
+        &self,
+        state: &TlsfAllocatorState,
+        node_id: SlotId,
+        size: DeviceSize,
+        alignment: DeviceSize,
+        allocation_type: AllocationType,
+    ) -> Option<DeviceSize> {
+        let node = state.nodes.get(node_id);
+        let mut offset = align_up(node.offset, alignment);
+
+        if let Some(prev_id) = node.prev {
+            let prev = state.nodes.get(prev_id);
+
+            if are_blocks_on_same_page(
+                prev.offset,
+                prev.size,
+                offset,
+                self.buffer_image_granularity,
+            ) && has_granularity_conflict(prev.ty, allocation_type)
+            {
+                offset = align_up(offset, self.buffer_image_granularity);
+            }
+        }
+
+        (offset + size <= node.offset + node.size).then_some(offset)
+    }
+}
+
+unsafe impl Suballocator for Arc<TlsfAllocator> {
+    const IS_BLOCKING: bool = true;
+
+    const NEEDS_CLEANUP: bool = false;
+
+    #[inline]
+    fn new(region: MemoryAlloc) -> Self {
+        TlsfAllocator::new(region)
+    }
+
+    /// Creates a new suballocation within the [region].
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.size` is zero.
+    /// - Panics if `create_info.alignment` is zero.
+    /// - Panics if `create_info.alignment` is not a power of two.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`OutOfRegionMemory`] if there are no free suballocations large enough so satisfy
+    ///   the request.
+    /// - Returns [`FragmentedRegion`] if a suballocation large enough to satisfy the request could
+    ///   have been formed, but wasn't because of [external fragmentation].
+    ///
+    /// [region]: Suballocator#regions
+    /// [`OutOfRegionMemory`]: SuballocationCreationError::OutOfRegionMemory
+    /// [`FragmentedRegion`]: SuballocationCreationError::FragmentedRegion
+    /// [external fragmentation]: super#external-fragmentation
+    #[inline]
+    fn allocate(
+        &self,
+        create_info: SuballocationCreateInfo,
+    ) -> Result<MemoryAlloc, SuballocationCreationError> {
+        create_info.validate();
+
+        unsafe { self.allocate_unchecked(create_info) }
+    }
+
+    #[inline]
+    unsafe fn allocate_unchecked(
+        &self,
+        create_info: SuballocationCreateInfo,
+    ) -> Result<MemoryAlloc, SuballocationCreationError> {
+        let SuballocationCreateInfo {
+            size,
+            alignment,
+            allocation_type,
+            _ne: _,
+        } = create_info;
+
+        let alignment = DeviceSize::max(alignment, self.atom_size);
+        let mut state = self.state.lock();
+
+        // Pad the size such that any free suballocation we find fits the request regardless of
+        // its offset, unless there is a buffer-image granularity conflict.
+        let fast_fit = size
+            .checked_add(alignment - 1)
+            .and_then(tlsf_mapping_search)
+            .and_then(|(fl, sl)| state.find_non_empty(fl, sl))
+            .and_then(|(fl, sl)| state.free_lists[fl * TLSF_SL_COUNT + sl])
+            .and_then(|id| {
+                self.fit(&state, id, size, alignment, allocation_type)
+                    .map(|offset| (id, offset))
+            });
+
+        // Check all the suballocations that might fit one by one.
+        let fit = fast_fit.or_else(|| {
+            let (mut fl, mut sl) = tlsf_mapping(size);
+
+            while let Some((found_fl, found_sl)) = state.find_non_empty(fl, sl) {
+                let mut next = state.free_lists[found_fl * TLSF_SL_COUNT + found_sl];
+
+                while let Some(id) = next {
+                    if let Some(offset) = self.fit(&state, id, size, alignment, allocation_type) {
+                        return Some((id, offset));
+                    }
+
+                    next = state.nodes.get(id).next_free;
+                }
+
+                (fl, sl) = (found_fl, found_sl + 1);
+            }
+
+            None
+        });
+
+        match fit {
+            Some((id, offset)) => {
+                state.allocate(id);
+                state.split(id, offset, size);
+                state.nodes.get_mut(id).ty = allocation_type.into();
+                self.free_size.fetch_sub(size, Ordering::Release);
+
+                Ok(MemoryAlloc {
+                    offset,
+                    size,
+                    allocation_type,
+                    mapped_ptr: self.region.mapped_ptr.and_then(|ptr| {
+                        NonNull::new(ptr.as_ptr().add((offset - self.region.offset) as usize))
+                    }),
+                    atom_size: self.region.atom_size,
+                    parent: AllocParent::Tlsf {
+                        allocator: self.clone(),
+                        id,
+                    },
+                })
+            }
+            // There would be enough space if the region wasn't so fragmented. :(
+            None if self.free_size() >= size => Err(SuballocationCreationError::FragmentedRegion),
+            // There is not enough space.
+            None => Err(SuballocationCreationError::OutOfRegionMemory),
+        }
+    }
+
+    #[inline]
+    fn region(&self) -> &MemoryAlloc {
+        &self.region
+    }
+
+    #[inline]
+    fn try_into_region(self) -> Result<MemoryAlloc, Self> {
+        Arc::try_unwrap(self).map(|allocator| allocator.region)
+    }
+
+    #[inline]
+    fn free_size(&self) -> DeviceSize {
+        self.free_size.load(Ordering::Acquire)
+    }
+
+    #[inline]
+    fn cleanup(&mut self) {}
+
+    #[inline]
+    fn statistics(&self) -> AllocationStatistics {
+        let state = self.state.lock();
+        let free_size = self.free_size();
+        let mut statistics = AllocationStatistics {
+            block_count: 1,
+            block_size: self.region.size,
+            used_size: self.region.size - free_size,
+            free_size,
+            ..Default::default()
+        };
+
+        let mut next = Some(state.head);
+        while let Some(id) = next {
+            let node = state.nodes.get(id);
+            if node.ty == SuballocationType::Free {
+                statistics.free_range_count += 1;
+                statistics.largest_free_range =
+                    DeviceSize::max(statistics.largest_free_range, node.size);
+            } else {
+                statistics.allocation_count += 1;
+            }
+            next = node.next;
+        }
+
+        statistics
+    }
+
+    #[inline]
+    fn suballocations(&self) -> Vec<SuballocationNode> {
+        let state = self.state.lock();
+        let mut nodes = Vec::new();
+
+        let mut next = Some(state.head);
+        while let Some(id) = next {
+            let node = state.nodes.get(id);
+            nodes.push(SuballocationNode {
+                offset: node.offset,
+                size: node.size,
+                allocation_type: node.ty,
+            });
+            next = node.next;
+        }
+
+        nodes
+    }
+}
+
+unsafe impl DeviceOwned for TlsfAllocator {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.device_memory.device()
+    }
+}
+
+/// The base-2 logarithm of the number of second-level size classes per first-level size class.
+const TLSF_SL_INDEX_LOG2: u32 = 5;
+
+/// The number of second-level size classes per first-level size class.
+const TLSF_SL_COUNT: usize = 1 << TLSF_SL_INDEX_LOG2;
+
+/// Sizes below this each have their own size class, all within the first first-level size class.
+const TLSF_SMALL_SIZE: DeviceSize = 1 << TLSF_SL_INDEX_LOG2;
+
+/// The number of first-level size classes, enough to cover every possible `DeviceSize`.
+const TLSF_FL_COUNT: usize = (DeviceSize::BITS - TLSF_SL_INDEX_LOG2 + 1) as usize;
+
+/// Returns the size class that a free suballocation of the given size belongs to.
+fn tlsf_mapping(size: DeviceSize) -> (usize, usize) {
+    if size < TLSF_SMALL_SIZE {
+        (0, size as usize)
+    } else {
+        let fl = DeviceSize::BITS - 1 - size.leading_zeros();
+        let sl = (size >> (fl - TLSF_SL_INDEX_LOG2)) as usize ^ TLSF_SL_COUNT;
+
+        ((fl - TLSF_SL_INDEX_LOG2 + 1) as usize, sl)
+    }
+}
+
+/// Returns the first size class in which all free suballocations are at least the given size, or
+/// `None` if there is no such size class.
+fn tlsf_mapping_search(size: DeviceSize) -> Option<(usize, usize)> {
+    let size = if size < TLSF_SMALL_SIZE {
+        size
+    } else {
+        let fl = DeviceSize::BITS - 1 - size.leading_zeros();
+        size.checked_add((1 << (fl - TLSF_SL_INDEX_LOG2)) - 1)?
+    };
+
+    Some(tlsf_mapping(size))
+}
+
+#[derive(Debug)]
+struct TlsfAllocatorState {
+    nodes: host::PoolAllocator<TlsfNode>,
+    // The head of the free-list of each size class, indexed by `fl * TLSF_SL_COUNT + sl`.
+    free_lists: Box<[Option<SlotId>]>,
+    // Bit `fl` is set if any of the free-lists of first-level size class `fl` is non-empty.
+    fl_bitmap: u64,
+    // Bit `sl` of element `fl` is set if the free-list of size class (`fl`, `sl`) is non-empty.
+    sl_bitmaps: [u32; TLSF_FL_COUNT],
+    // The suballocation with the lowest offset, used to traverse the whole list.
+    head: SlotId,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct TlsfNode {
+    // The neighboring suballocations in the region.
+    prev: Option<SlotId>,
+    next: Option<SlotId>,
+    // The neighboring suballocations in the free-list, if this suballocation is free.
+    prev_free: Option<SlotId>,
+    next_free: Option<SlotId>,
+    offset: DeviceSize,
+    size: DeviceSize,
+    ty: SuballocationType,
+}
+
+impl TlsfAllocatorState {
+    /// Returns the first size class starting from (`fl`, `sl`) whose free-list is non-empty.
+    fn find_non_empty(&self, fl: usize, sl: usize) -> Option<(usize, usize)> {
+        let sl_map = self.sl_bitmaps.get(fl).copied().unwrap_or(0)
+            & (!0u32).checked_shl(sl as u32).unwrap_or(0);
+
+        if sl_map != 0 {
+            return Some((fl, sl_map.trailing_zeros() as usize));
+        }
+
+        let fl_map = self.fl_bitmap & (!0u64).checked_shl(fl as u32 + 1).unwrap_or(0);
+
+        if fl_map != 0 {
+            let fl = fl_map.trailing_zeros() as usize;
+
+            Some((fl, self.sl_bitmaps[fl].trailing_zeros() as usize))
+        } else {
+            None
+        }
+    }
+
+    /// Removes the target suballocation from the free-list of its size class. The free-list must
+    /// contain it.
+    fn allocate(&mut self, node_id: SlotId) {
+        let node = self.nodes.get(node_id);
+        let (fl, sl) = tlsf_mapping(node.size);
+        let index = fl * TLSF_SL_COUNT + sl;
 
-                // Check all next indices that point to suballocations with the same size.
-                {
-                    let mut index = index;
-                    loop {
-                        index += 1;
-                        if let Some(&id) = self.free_list.get(index) {
-                            if id == node_id {
-                                self.free_list.remove(index);
-                                return;
-                            }
-                            if self.nodes.get(id).size != node.size {
-                                break;
-                            }
-                        } else {
-                            break;
-                        }
-                    }
-                }
+        if let Some(prev_id) = node.prev_free {
+            self.nodes.get_mut(prev_id).next_free = node.next_free;
+        } else {
+            debug_assert!(self.free_lists[index] == Some(node_id));
 
-                unreachable!();
+            self.free_lists[index] = node.next_free;
+
+            if node.next_free.is_none() {
+                self.sl_bitmaps[fl] &= !(1 << sl);
+
+                if self.sl_bitmaps[fl] == 0 {
+                    self.fl_bitmap &= !(1 << fl);
+                }
             }
-            Err(_) => unreachable!(),
+        }
+
+        if let Some(next_id) = node.next_free {
+            self.nodes.get_mut(next_id).prev_free = node.prev_free;
         }
     }
 
@@ -1340,9 +2078,11 @@ impl FreeListAllocatorState {
         let padding_back = node.offset + node.size - offset - size;
 
         if padding_front > 0 {
-            let padding = SuballocationListNode {
+            let padding = TlsfNode {
                 prev: node.prev,
                 next: Some(node_id),
+                prev_free: None,
+                next_free: None,
                 offset: node.offset,
                 size: padding_front,
                 ty: SuballocationType::Free,
@@ -1351,6 +2091,8 @@ impl FreeListAllocatorState {
 
             if let Some(prev_id) = padding.prev {
                 self.nodes.get_mut(prev_id).next = Some(padding_id);
+            } else {
+                self.head = padding_id;
             }
 
             let node = self.nodes.get_mut(node_id);
@@ -1362,9 +2104,11 @@ impl FreeListAllocatorState {
         }
 
         if padding_back > 0 {
-            let padding = SuballocationListNode {
+            let padding = TlsfNode {
                 prev: Some(node_id),
                 next: node.next,
+                prev_free: None,
+                next_free: None,
                 offset: offset + size,
                 size: padding_back,
                 ty: SuballocationType::Free,
@@ -1383,16 +2127,24 @@ impl FreeListAllocatorState {
         }
     }
 
-    /// Inserts the target suballocation into the free-list. The free-list must not contain it
-    /// already.
+    /// Inserts the target suballocation into the free-list of its size class. The free-list must
+    /// not contain it already.
     fn free(&mut self, node_id: SlotId) {
-        debug_assert!(!self.free_list.contains(&node_id));
+        let (fl, sl) = tlsf_mapping(self.nodes.get(node_id).size);
+        let index = fl * TLSF_SL_COUNT + sl;
+        let head = self.free_lists[index];
 
-        let node = self.nodes.get(node_id);
-        let (Ok(index) | Err(index)) = self
-            .free_list
-            .binary_search_by_key(&node.size, |&x| self.nodes.get(x).size);
-        self.free_list.insert(index, node_id);
+        let node = self.nodes.get_mut(node_id);
+        node.prev_free = None;
+        node.next_free = head;
+
+        if let Some(head_id) = head {
+            self.nodes.get_mut(head_id).prev_free = Some(node_id);
+        }
+
+        self.free_lists[index] = Some(node_id);
+        self.sl_bitmaps[fl] |= 1 << sl;
+        self.fl_bitmap |= 1 << fl;
     }
 
     /// Coalesces the target (free) suballocation with adjacent ones that are also free.
@@ -1411,10 +2163,12 @@ impl FreeListAllocatorState {
                 let node = self.nodes.get_mut(node_id);
                 node.prev = prev.prev;
                 node.offset = prev.offset;
-                node.size += prev.size; // nom nom nom
+                node.size += prev.size;
 
                 if let Some(prev_id) = node.prev {
                     self.nodes.get_mut(prev_id).next = Some(node_id);
+                } else {
+                    self.head = node_id;
                 }
             }
         }
@@ -1571,7 +2325,10 @@ impl BuddyAllocator {
         let mut free_list = ArrayVec::new(max_order + 1, [EMPTY_FREE_LIST; Self::MAX_ORDERS]);
         // The root node has the lowest offset and highest order, so it's the whole region.
         free_list[max_order].push(region.offset);
-        let state = Mutex::new(BuddyAllocatorState { free_list });
+        let state = Mutex::new(BuddyAllocatorState {
+            free_list,
+            allocation_count: 0,
+        });
 
         Arc::new(BuddyAllocator {
             region,
@@ -1585,6 +2342,7 @@ impl BuddyAllocator {
 
     fn free(&self, min_order: usize, mut offset: DeviceSize) {
         let mut state = self.state.lock();
+        state.allocation_count -= 1;
 
         // Try to coalesce nodes while incrementing the order.
         for (order, free_list) in state.free_list.iter_mut().enumerate().skip(min_order) {
@@ -1711,6 +2469,7 @@ unsafe impl Suballocator for Arc<BuddyAllocator> {
                     }
 
                     self.free_size.fetch_sub(size, Ordering::Release);
+                    state.allocation_count += 1;
 
                     return Ok(MemoryAlloc {
                         offset,
@@ -1760,6 +2519,76 @@ unsafe impl Suballocator for Arc<BuddyAllocator> {
 
     #[inline]
     fn cleanup(&mut self) {}
+
+    #[inline]
+    fn statistics(&self) -> AllocationStatistics {
+        let state = self.state.lock();
+        let free_size = self.free_size();
+
+        AllocationStatistics {
+            block_count: 1,
+            block_size: self.region.size,
+            allocation_count: state.allocation_count,
+            used_size: self.region.size - free_size,
+            free_size,
+            free_range_count: state.free_list.iter().map(Vec::len).sum(),
+            largest_free_range: state
+                .free_list
+                .iter()
+                .rposition(|free_list| !free_list.is_empty())
+                .map_or(0, |order| BuddyAllocator::MIN_NODE_SIZE << order),
+        }
+    }
+
+    #[inline]
+    fn suballocations(&self) -> Vec<SuballocationNode> {
+        let state = self.state.lock();
+        let mut free_nodes: Vec<_> = state
+            .free_list
+            .iter()
+            .enumerate()
+            .flat_map(|(order, free_list)| {
+                free_list
+                    .iter()
+                    .map(move |&offset| (offset, BuddyAllocator::MIN_NODE_SIZE << order))
+            })
+            .collect();
+        drop(state);
+        free_nodes.sort_unstable();
+
+        let mut nodes = Vec::with_capacity(free_nodes.len() * 2 + 1);
+        let mut prev_end = self.region.offset;
+
+        // Nodes that are in use are not tracked, so fill the gaps between the free ones.
+        for (offset, size) in free_nodes {
+            if offset > prev_end {
+                nodes.push(SuballocationNode {
+                    offset: prev_end,
+                    size: offset - prev_end,
+                    allocation_type: SuballocationType::Unknown,
+                });
+            }
+
+            nodes.push(SuballocationNode {
+                offset,
+                size,
+                allocation_type: SuballocationType::Free,
+            });
+            prev_end = offset + size;
+        }
+
+        let region_end = self.region.offset + self.region.size;
+
+        if region_end > prev_end {
+            nodes.push(SuballocationNode {
+                offset: prev_end,
+                size: region_end - prev_end,
+                allocation_type: SuballocationType::Unknown,
+            });
+        }
+
+        nodes
+    }
 }
 
 unsafe impl DeviceOwned for BuddyAllocator {
@@ -1775,6 +2604,7 @@ struct BuddyAllocatorState {
     // Each free-list is sorted by offset because we want to find the first-fit as this strategy
     // minimizes external fragmentation.
     free_list: ArrayVec<Vec<DeviceSize>, { BuddyAllocator::MAX_ORDERS }>,
+    allocation_count: usize,
 }
 
 /// A [suballocator] using a pool of fixed-size blocks as a [free-list].
@@ -2029,6 +2859,48 @@ unsafe impl<const BLOCK_SIZE: DeviceSize> Suballocator for Arc<PoolAllocator<BLO
 
     #[inline]
     fn cleanup(&mut self) {}
+
+    #[inline]
+    fn statistics(&self) -> AllocationStatistics {
+        let free_count = self.free_count();
+        let free_size = free_count as DeviceSize * self.block_size();
+
+        AllocationStatistics {
+            block_count: 1,
+            block_size: self.inner.region.size,
+            allocation_count: self.block_count() - free_count,
+            used_size: self.inner.region.size - free_size,
+            free_size,
+            // Blocks are never coalesced, so every free block is its own free range.
+            free_range_count: free_count,
+            largest_free_range: if free_count != 0 {
+                self.block_size()
+            } else {
+                0
+            },
+        }
+    }
+
+    #[inline]
+    fn suballocations(&self) -> Vec<SuballocationNode> {
+        let inner = &self.inner;
+        let allocation_type = inner.region.allocation_type.into();
+
+        inner
+            .used
+            .iter()
+            .enumerate()
+            .map(|(index, used)| SuballocationNode {
+                offset: inner.region.offset + index as DeviceSize * inner.block_size,
+                size: inner.block_size,
+                allocation_type: if used.load(Ordering::Relaxed) {
+                    allocation_type
+                } else {
+                    SuballocationType::Free
+                },
+            })
+            .collect()
+    }
 }
 
 unsafe impl<const BLOCK_SIZE: DeviceSize> DeviceOwned for PoolAllocator<BLOCK_SIZE> {
@@ -2046,6 +2918,9 @@ struct PoolAllocatorInner {
     block_size: DeviceSize,
     // Unsorted list of free block indices.
     free_list: ArrayQueue<DeviceSize>,
+    // Whether each block is in use. This is only used for debugging purposes, so that the map of
+    // blocks can be inspected without having to lock the free-list.
+    used: Box<[AtomicBool]>,
 }
 
 impl PoolAllocatorInner {
@@ -2074,6 +2949,7 @@ impl PoolAllocatorInner {
         for i in 0..block_count {
             free_list.push(i).unwrap();
         }
+        let used = (0..block_count).map(|_| AtomicBool::new(false)).collect();
 
         PoolAllocatorInner {
             region,
@@ -2081,6 +2957,7 @@ impl PoolAllocatorInner {
             atom_size,
             block_size,
             free_list,
+            used,
         }
     }
 
@@ -2114,6 +2991,8 @@ impl PoolAllocatorInner {
             };
         }
 
+        self.used[index as usize].store(true, Ordering::Relaxed);
+
         Ok(MemoryAlloc {
             offset,
             size,
@@ -2130,6 +3009,7 @@ impl PoolAllocatorInner {
     }
 
     fn free(&self, index: DeviceSize) {
+        self.used[index as usize].store(false, Ordering::Relaxed);
         self.free_list.push(index).unwrap();
     }
 }
@@ -2196,6 +3076,8 @@ pub struct BumpAllocator {
     // Encodes the previous allocation type in the 2 least signifficant bits and the free start in
     // the rest.
     state: AtomicU64,
+    // Number of allocations that are still alive. This is only used for statistics.
+    allocation_count: AtomicUsize,
 }
 
 impl BumpAllocator {
@@ -2227,6 +3109,7 @@ impl BumpAllocator {
             buffer_image_granularity,
             atom_size,
             state,
+            allocation_count: AtomicUsize::new(0),
         })
     }
 
@@ -2385,6 +3268,8 @@ unsafe impl Suballocator for Arc<BumpAllocator> {
                 Ordering::Relaxed,
             ) {
                 Ok(_) => {
+                    self.allocation_count.fetch_add(1, Ordering::Relaxed);
+
                     return Ok(MemoryAlloc {
                         offset,
                         size,
@@ -2423,6 +3308,45 @@ unsafe impl Suballocator for Arc<BumpAllocator> {
     fn cleanup(&mut self) {
         let _ = self.try_reset();
     }
+
+    #[inline]
+    fn statistics(&self) -> AllocationStatistics {
+        let free_size = self.free_size();
+
+        AllocationStatistics {
+            block_count: 1,
+            block_size: self.region.size,
+            allocation_count: self.allocation_count.load(Ordering::Relaxed),
+            used_size: self.region.size - free_size,
+            free_size,
+            free_range_count: (free_size != 0) as usize,
+            largest_free_range: free_size,
+        }
+    }
+
+    fn suballocations(&self) -> Vec<SuballocationNode> {
+        // Everything before the free pointer is used, everything after it is free.
+        let used_size = self.region.size - self.free_size();
+        let mut nodes = Vec::with_capacity(2);
+
+        if used_size != 0 {
+            nodes.push(SuballocationNode {
+                offset: self.region.offset,
+                size: used_size,
+                allocation_type: SuballocationType::Unknown,
+            });
+        }
+
+        if used_size != self.region.size {
+            nodes.push(SuballocationNode {
+                offset: self.region.offset + used_size,
+                size: self.region.size - used_size,
+                allocation_type: SuballocationType::Free,
+            });
+        }
+
+        nodes
+    }
 }
 
 unsafe impl DeviceOwned for BumpAllocator {
@@ -2463,6 +3387,18 @@ fn are_blocks_on_same_page(
     a_end_page == b_start_page
 }
 
+/// Checks if a suballocation of type `ty` placed on the same page as a suballocation of type
+/// `prev_ty` would cause a buffer-image granularity conflict.
+fn has_granularity_conflict(prev_ty: SuballocationType, ty: AllocationType) -> bool {
+    if prev_ty == SuballocationType::Free {
+        false
+    } else if prev_ty == SuballocationType::Unknown {
+        true
+    } else {
+        prev_ty != ty.into()
+    }
+}
+
 /// Allocators for memory on the host, used to speed up the allocators for the device.
 mod host {
     use std::num::NonZeroUsize;
@@ -2714,6 +3650,184 @@ mod tests {
         assert!(allocator.allocate(DUMMY_INFO_LINEAR).is_err());
     }
 
+    #[test]
+    fn free_list_allocator_statistics() {
+        const REGION_SIZE: DeviceSize = 1024;
+
+        let allocator = dummy_allocator!(FreeListAllocator, REGION_SIZE);
+        let alloc1 = allocator
+            .allocate(SuballocationCreateInfo {
+                size: 256,
+                ..DUMMY_INFO_LINEAR
+            })
+            .unwrap();
+        let alloc2 = allocator
+            .allocate(SuballocationCreateInfo {
+                size: 256,
+                ..DUMMY_INFO_LINEAR
+            })
+            .unwrap();
+        let _alloc3 = allocator
+            .allocate(SuballocationCreateInfo {
+                size: 256,
+                ..DUMMY_INFO_LINEAR
+            })
+            .unwrap();
+        drop(alloc2);
+
+        let statistics = allocator.statistics();
+        assert_eq!(statistics.allocation_count, 2);
+        assert_eq!(statistics.used_size, 512);
+        assert_eq!(statistics.free_size, 512);
+        assert_eq!(statistics.free_range_count, 2);
+        assert_eq!(statistics.largest_free_range, 256);
+        assert_eq!(statistics.fragmentation(), 0.5);
+
+        let nodes = allocator.suballocations();
+        assert_eq!(nodes.len(), 4);
+        assert!(nodes
+            .windows(2)
+            .all(|win| win[0].offset + win[0].size == win[1].offset));
+        assert_eq!(nodes[1].allocation_type, SuballocationType::Free);
+
+        drop(alloc1);
+        let statistics = allocator.statistics();
+        assert_eq!(statistics.allocation_count, 1);
+        assert_eq!(statistics.free_range_count, 2);
+        assert_eq!(statistics.largest_free_range, 512);
+        assert_eq!(allocator.suballocations().len(), 3);
+    }
+
+    #[test]
+    fn tlsf_allocator_capacity() {
+        const THREADS: DeviceSize = 12;
+        const ALLOCATIONS_PER_THREAD: DeviceSize = 100;
+        const ALLOCATION_STEP: DeviceSize = 117;
+        const REGION_SIZE: DeviceSize =
+            (ALLOCATION_STEP * (THREADS + 1) * THREADS / 2) * ALLOCATIONS_PER_THREAD;
+
+        let allocator = dummy_allocator!(TlsfAllocator, REGION_SIZE);
+        let allocs = ArrayQueue::new((ALLOCATIONS_PER_THREAD * THREADS) as usize);
+
+        // Using threads to randomize allocation order.
+        thread::scope(|scope| {
+            for i in 1..=THREADS {
+                let (allocator, allocs) = (&allocator, &allocs);
+
+                scope.spawn(move || {
+                    let size = i * ALLOCATION_STEP;
+
+                    for _ in 0..ALLOCATIONS_PER_THREAD {
+                        allocs
+                            .push(
+                                allocator
+                                    .allocate(SuballocationCreateInfo { size, ..DUMMY_INFO })
+                                    .unwrap(),
+                            )
+                            .unwrap();
+                    }
+                });
+            }
+        });
+
+        assert!(allocator.allocate(DUMMY_INFO).is_err());
+        assert!(allocator.free_size() == 0);
+
+        drop(allocs);
+        assert!(allocator.free_size() == REGION_SIZE);
+        assert!(allocator
+            .allocate(SuballocationCreateInfo {
+                size: REGION_SIZE,
+                ..DUMMY_INFO
+            })
+            .is_ok());
+    }
+
+    #[test]
+    fn tlsf_allocator_respects_alignment() {
+        const INFO: SuballocationCreateInfo = SuballocationCreateInfo {
+            alignment: 256,
+            ..DUMMY_INFO
+        };
+        const REGION_SIZE: DeviceSize = 10 * INFO.alignment;
+
+        let allocator = dummy_allocator!(TlsfAllocator, REGION_SIZE);
+        let mut allocs = Vec::with_capacity(10);
+
+        for _ in 0..10 {
+            allocs.push(allocator.allocate(INFO).unwrap());
+        }
+
+        assert!(allocator.allocate(INFO).is_err());
+        assert!(allocator.free_size() == REGION_SIZE - 10);
+    }
+
+    #[test]
+    fn tlsf_allocator_respects_granularity() {
+        const GRANULARITY: DeviceSize = 16;
+        const REGION_SIZE: DeviceSize = 2 * GRANULARITY;
+
+        let allocator = dummy_allocator!(TlsfAllocator, REGION_SIZE, GRANULARITY);
+        let mut linear_allocs = Vec::with_capacity(GRANULARITY as usize);
+        let mut non_linear_allocs = Vec::with_capacity(GRANULARITY as usize);
+
+        for i in 0..REGION_SIZE {
+            if i % 2 == 0 {
+                linear_allocs.push(
+                    allocator
+                        .allocate(SuballocationCreateInfo {
+                            allocation_type: AllocationType::Linear,
+                            ..DUMMY_INFO
+                        })
+                        .unwrap(),
+                );
+            } else {
+                non_linear_allocs.push(
+                    allocator
+                        .allocate(SuballocationCreateInfo {
+                            allocation_type: AllocationType::NonLinear,
+                            ..DUMMY_INFO
+                        })
+                        .unwrap(),
+                );
+            }
+        }
+
+        assert!(allocator.allocate(DUMMY_INFO_LINEAR).is_err());
+        assert!(allocator.free_size() == 0);
+
+        drop(linear_allocs);
+        assert!(allocator
+            .allocate(SuballocationCreateInfo {
+                size: GRANULARITY,
+                ..DUMMY_INFO
+            })
+            .is_ok());
+
+        let _alloc = allocator.allocate(DUMMY_INFO).unwrap();
+        assert!(allocator.allocate(DUMMY_INFO).is_err());
+        assert!(allocator.allocate(DUMMY_INFO_LINEAR).is_err());
+    }
+
+    #[test]
+    fn tlsf_mapping_is_monotonic() {
+        let mut prev = tlsf_mapping(1);
+
+        for size in (2..4096).chain((12..DeviceSize::BITS).map(|shift| 1 << shift)) {
+            let class = tlsf_mapping(size);
+            assert!(class >= prev);
+            assert!(class.0 < TLSF_FL_COUNT && class.1 < TLSF_SL_COUNT);
+
+            if let Some(search) = tlsf_mapping_search(size) {
+                assert!(search >= class);
+            }
+
+            prev = class;
+        }
+
+        assert_eq!(tlsf_mapping(DeviceSize::MAX).0, TLSF_FL_COUNT - 1);
+    }
+
     #[test]
     fn pool_allocator_capacity() {
         const BLOCK_SIZE: DeviceSize = 1024;
@@ -2964,6 +4078,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn buddy_allocator_statistics() {
+        const REGION_SIZE: DeviceSize = 1024;
+
+        let allocator = dummy_allocator!(BuddyAllocator, REGION_SIZE);
+        let _alloc1 = allocator
+            .allocate(SuballocationCreateInfo {
+                size: 100,
+                ..DUMMY_INFO_LINEAR
+            })
+            .unwrap();
+        let alloc2 = allocator
+            .allocate(SuballocationCreateInfo {
+                size: 128,
+                ..DUMMY_INFO_LINEAR
+            })
+            .unwrap();
+
+        let statistics = allocator.statistics();
+        assert_eq!(statistics.allocation_count, 2);
+        assert_eq!(statistics.used_size, 256);
+        assert_eq!(statistics.free_range_count, 2);
+        assert_eq!(statistics.largest_free_range, 512);
+
+        let nodes = allocator.suballocations();
+        assert_eq!(nodes.first().unwrap().offset, 0);
+        assert_eq!(
+            nodes.last().unwrap().offset + nodes.last().unwrap().size,
+            REGION_SIZE
+        );
+        assert!(nodes
+            .windows(2)
+            .all(|win| win[0].offset + win[0].size == win[1].offset));
+
+        drop(alloc2);
+        assert_eq!(allocator.statistics().allocation_count, 1);
+    }
+
     #[test]
     fn bump_allocator_respects_alignment() {
         const INFO: SuballocationCreateInfo = SuballocationCreateInfo {
@@ -22,7 +22,7 @@ use std::{
     mem::MaybeUninit,
     num::NonZeroU64,
     ops::Range,
-    ptr, slice,
+    slice,
     sync::{atomic::Ordering, Arc},
 };
 
@@ -54,6 +54,7 @@ pub struct DeviceMemory {
     handle: ash::vk::DeviceMemory,
     device: Arc<Device>,
     id: NonZeroU64,
+    imported_handle: bool,
 
     allocation_size: DeviceSize,
     memory_type_index: u32,
@@ -72,6 +73,7 @@ impl DeviceMemory {
     /// # Panics
     ///
     /// - Panics if `allocate_info.allocation_size` is 0.
+    /// - Panics if `allocate_info.priority` is not between 0.0 and 1.0 inclusive.
     /// - Panics if `allocate_info.dedicated_allocation` is `Some` and the contained buffer or
     ///   image does not belong to `device`.
     #[inline]
@@ -90,6 +92,8 @@ impl DeviceMemory {
     ///
     /// - `handle` must be a valid Vulkan object handle created from `device`.
     /// - `allocate_info` must match the info used to create the object.
+    /// - `handle` must have been created with a null `pAllocator`, as it is destroyed with a null
+    ///   `pAllocator` when dropped.
     #[inline]
     pub unsafe fn from_handle(
         device: Arc<Device>,
@@ -102,6 +106,8 @@ impl DeviceMemory {
             dedicated_allocation,
             export_handle_types,
             flags,
+            priority: _,
+            opaque_capture_address: _,
             _ne: _,
         } = allocate_info;
 
@@ -109,6 +115,7 @@ impl DeviceMemory {
             handle,
             device,
             id: Self::next_id(),
+            imported_handle: true,
             allocation_size,
             memory_type_index,
             dedicated_to: dedicated_allocation.map(Into::into),
@@ -127,6 +134,7 @@ impl DeviceMemory {
     /// # Panics
     ///
     /// - Panics if `allocate_info.allocation_size` is 0.
+    /// - Panics if `allocate_info.priority` is not between 0.0 and 1.0 inclusive.
     /// - Panics if `allocate_info.dedicated_allocation` is `Some` and the contained buffer or
     ///   image does not belong to `device`.
     #[inline]
@@ -152,6 +160,8 @@ impl DeviceMemory {
             ref mut dedicated_allocation,
             export_handle_types,
             flags,
+            priority,
+            opaque_capture_address,
             _ne: _,
         } = allocate_info;
 
@@ -380,6 +390,68 @@ impl DeviceMemory {
                         // Can't validate, must be ensured by user
                     }
                 }
+                MemoryImportInfo::HostPointer {
+                    handle_type,
+                    host_pointer,
+                } => {
+                    if !device.enabled_extensions().ext_external_memory_host {
+                        return Err(DeviceMemoryError::RequirementNotMet {
+                            required_for: "`allocate_info.import_info` is \
+                                `Some(MemoryImportInfo::HostPointer)`",
+                            requires_one_of: RequiresOneOf {
+                                device_extensions: &["ext_external_memory_host"],
+                                ..Default::default()
+                            },
+                        });
+                    }
+
+                    // VUID-VkImportMemoryHostPointerInfoEXT-handleType-parameter
+                    handle_type.validate_device(device)?;
+
+                    // VUID-VkImportMemoryHostPointerInfoEXT-handleType-01748
+                    if !matches!(
+                        handle_type,
+                        ExternalMemoryHandleType::HostAllocation
+                            | ExternalMemoryHandleType::HostMappedForeignMemory
+                    ) {
+                        return Err(DeviceMemoryError::ImportHostPointerHandleTypeNotSupported {
+                            handle_type,
+                        });
+                    }
+
+                    let required_alignment = device
+                        .physical_device()
+                        .properties()
+                        .min_imported_host_pointer_alignment
+                        .unwrap();
+
+                    // VUID-VkImportMemoryHostPointerInfoEXT-pHostPointer-01749
+                    // VUID-VkMemoryAllocateInfo-allocationSize-01745
+                    if host_pointer as DeviceSize % required_alignment != 0
+                        || allocation_size % required_alignment != 0
+                    {
+                        return Err(DeviceMemoryError::ImportHostPointerNotAligned {
+                            required_alignment,
+                        });
+                    }
+
+                    // VUID-VkMemoryAllocateInfo-memoryTypeIndex-01744
+                    let supported_memory_type_bits = match unsafe {
+                        device.host_pointer_properties_unchecked(handle_type, host_pointer)
+                    } {
+                        Ok(properties) => properties.memory_type_bits,
+                        // The pointer can't be imported into any memory type.
+                        Err(VulkanError::InvalidExternalHandle) => 0,
+                        Err(err) => return Err(err.into()),
+                    };
+
+                    if supported_memory_type_bits & (1 << memory_type_index) == 0 {
+                        return Err(DeviceMemoryError::ImportHostPointerMemoryTypeNotSupported {
+                            memory_type_index,
+                            supported_memory_type_bits,
+                        });
+                    }
+                }
             }
         }
 
@@ -423,6 +495,63 @@ impl DeviceMemory {
             }
         }
 
+        if flags.intersects(MemoryAllocateFlags::DEVICE_ADDRESS_CAPTURE_REPLAY) {
+            // VUID-VkMemoryAllocateInfo-flags-03330
+            if !device
+                .enabled_features()
+                .buffer_device_address_capture_replay
+            {
+                return Err(DeviceMemoryError::RequirementNotMet {
+                    required_for: "`allocate_info.flags` contains \
+                        `MemoryAllocateFlags::DEVICE_ADDRESS_CAPTURE_REPLAY`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["buffer_device_address_capture_replay"],
+                        ..Default::default()
+                    },
+                });
+            }
+        }
+
+        if opaque_capture_address.is_some() {
+            if !(device.api_version() >= Version::V1_2
+                || device.enabled_extensions().khr_buffer_device_address)
+            {
+                return Err(DeviceMemoryError::RequirementNotMet {
+                    required_for: "`allocate_info.opaque_capture_address` is `Some`",
+                    requires_one_of: RequiresOneOf {
+                        api_version: Some(Version::V1_2),
+                        device_extensions: &["khr_buffer_device_address"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkMemoryAllocateInfo-opaqueCaptureAddress-03329
+            if !flags.intersects(MemoryAllocateFlags::DEVICE_ADDRESS_CAPTURE_REPLAY) {
+                return Err(DeviceMemoryError::OpaqueCaptureAddressNoCaptureReplayFlag);
+            }
+
+            // VUID-VkMemoryAllocateInfo-opaqueCaptureAddress-03333
+            if import_info.is_some() {
+                return Err(DeviceMemoryError::OpaqueCaptureAddressWithImport);
+            }
+        }
+
+        if priority != 0.5 {
+            if !device.enabled_features().memory_priority {
+                return Err(DeviceMemoryError::RequirementNotMet {
+                    required_for: "`allocate_info.priority` is not `0.5`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["memory_priority"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkMemoryPriorityAllocateInfoEXT-priority-02602
+            assert!((0.0..=1.0).contains(&priority));
+        }
+
         Ok(())
     }
 
@@ -439,6 +568,8 @@ impl DeviceMemory {
             dedicated_allocation,
             export_handle_types,
             flags,
+            priority,
+            opaque_capture_address,
             _ne: _,
         } = allocate_info;
 
@@ -479,8 +610,25 @@ impl DeviceMemory {
         let imported_handle_type = import_info.as_ref().map(|import_info| match import_info {
             MemoryImportInfo::Fd { handle_type, .. } => *handle_type,
             MemoryImportInfo::Win32 { handle_type, .. } => *handle_type,
+            MemoryImportInfo::HostPointer { handle_type, .. } => *handle_type,
         });
 
+        let mut import_host_pointer_info = match import_info {
+            Some(MemoryImportInfo::HostPointer {
+                handle_type,
+                host_pointer,
+            }) => Some(ash::vk::ImportMemoryHostPointerInfoEXT {
+                handle_type: handle_type.into(),
+                p_host_pointer: host_pointer,
+                ..Default::default()
+            }),
+            _ => None,
+        };
+
+        if let Some(info) = import_host_pointer_info.as_mut() {
+            allocate_info = allocate_info.push_next(info);
+        }
+
         #[cfg(unix)]
         let mut import_fd_info = match import_info {
             Some(MemoryImportInfo::Fd { handle_type, file }) => {
@@ -527,6 +675,27 @@ impl DeviceMemory {
             allocate_info = allocate_info.push_next(&mut flags_info);
         }
 
+        let mut priority_info = ash::vk::MemoryPriorityAllocateInfoEXT {
+            priority,
+            ..Default::default()
+        };
+
+        if priority != 0.5 {
+            allocate_info = allocate_info.push_next(&mut priority_info);
+        }
+
+        let mut opaque_capture_address_info =
+            opaque_capture_address.map(|opaque_capture_address| {
+                ash::vk::MemoryOpaqueCaptureAddressAllocateInfo {
+                    opaque_capture_address,
+                    ..Default::default()
+                }
+            });
+
+        if let Some(info) = opaque_capture_address_info.as_mut() {
+            allocate_info = allocate_info.push_next(info);
+        }
+
         // VUID-vkAllocateMemory-maxMemoryAllocationCount-04101
         let max_allocations = device
             .physical_device()
@@ -545,7 +714,7 @@ impl DeviceMemory {
             (fns.v1_0.allocate_memory)(
                 device.handle(),
                 &allocate_info.build(),
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -561,6 +730,7 @@ impl DeviceMemory {
             handle,
             device,
             id: Self::next_id(),
+            imported_handle: false,
             allocation_size,
             memory_type_index,
             dedicated_to: dedicated_allocation.map(Into::into),
@@ -658,6 +828,122 @@ impl DeviceMemory {
         output
     }
 
+    /// Changes the priority of the memory allocation, which was initially set through
+    /// [`MemoryAllocateInfo::priority`].
+    ///
+    /// The [`pageable_device_local_memory`] feature must be enabled on the device.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `priority` is not between 0.0 and 1.0 inclusive.
+    ///
+    /// [`pageable_device_local_memory`]: crate::device::Features::pageable_device_local_memory
+    #[inline]
+    pub fn set_priority(&self, priority: f32) -> Result<(), DeviceMemoryError> {
+        self.validate_set_priority(priority)?;
+
+        unsafe { self.set_priority_unchecked(priority) };
+
+        Ok(())
+    }
+
+    fn validate_set_priority(&self, priority: f32) -> Result<(), DeviceMemoryError> {
+        if !self.device.enabled_features().pageable_device_local_memory {
+            return Err(DeviceMemoryError::RequirementNotMet {
+                required_for: "`DeviceMemory::set_priority`",
+                requires_one_of: RequiresOneOf {
+                    features: &["pageable_device_local_memory"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkSetDeviceMemoryPriorityEXT-priority-06258
+        assert!((0.0..=1.0).contains(&priority));
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn set_priority_unchecked(&self, priority: f32) {
+        let fns = self.device.fns();
+        (fns.ext_pageable_device_local_memory
+            .set_device_memory_priority_ext)(self.device.handle(), self.handle, priority);
+    }
+
+    /// Returns the opaque capture address of the memory, which can be passed to
+    /// [`MemoryAllocateInfo::opaque_capture_address`] to allocate memory at the same device
+    /// address on a subsequent run.
+    ///
+    /// The [`buffer_device_address_capture_replay`] feature must be enabled on the device, and
+    /// the memory must have been allocated with the [`MemoryAllocateFlags::DEVICE_ADDRESS`] flag.
+    ///
+    /// [`buffer_device_address_capture_replay`]: crate::device::Features::buffer_device_address_capture_replay
+    #[inline]
+    pub fn opaque_capture_address(&self) -> Result<u64, DeviceMemoryError> {
+        self.validate_opaque_capture_address()?;
+
+        unsafe { Ok(self.opaque_capture_address_unchecked()) }
+    }
+
+    fn validate_opaque_capture_address(&self) -> Result<(), DeviceMemoryError> {
+        if !(self.device.api_version() >= Version::V1_2
+            || self.device.enabled_extensions().khr_buffer_device_address)
+        {
+            return Err(DeviceMemoryError::RequirementNotMet {
+                required_for: "`DeviceMemory::opaque_capture_address`",
+                requires_one_of: RequiresOneOf {
+                    api_version: Some(Version::V1_2),
+                    device_extensions: &["khr_buffer_device_address"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkGetDeviceMemoryOpaqueCaptureAddress-None-03334
+        if !self
+            .device
+            .enabled_features()
+            .buffer_device_address_capture_replay
+        {
+            return Err(DeviceMemoryError::RequirementNotMet {
+                required_for: "`DeviceMemory::opaque_capture_address`",
+                requires_one_of: RequiresOneOf {
+                    features: &["buffer_device_address_capture_replay"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-VkDeviceMemoryOpaqueCaptureAddressInfo-memory-03336
+        if !self.flags.intersects(MemoryAllocateFlags::DEVICE_ADDRESS) {
+            return Err(DeviceMemoryError::NoDeviceAddressFlag);
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn opaque_capture_address_unchecked(&self) -> u64 {
+        let info_vk = ash::vk::DeviceMemoryOpaqueCaptureAddressInfo {
+            memory: self.handle,
+            ..Default::default()
+        };
+
+        let fns = self.device.fns();
+
+        if self.device.api_version() >= Version::V1_2 {
+            (fns.v1_2.get_device_memory_opaque_capture_address)(self.device.handle(), &info_vk)
+        } else {
+            (fns.khr_buffer_device_address
+                .get_device_memory_opaque_capture_address_khr)(
+                self.device.handle(), &info_vk
+            )
+        }
+    }
+
     /// Exports the device memory into a Unix file descriptor. The caller owns the returned `File`.
     ///
     /// # Panics
@@ -726,7 +1012,12 @@ impl Drop for DeviceMemory {
     fn drop(&mut self) {
         unsafe {
             let fns = self.device.fns();
-            (fns.v1_0.free_memory)(self.device.handle(), self.handle, ptr::null());
+            (fns.v1_0.free_memory)(
+                self.device.handle(),
+                self.handle,
+                self.device
+                    .destroy_allocation_callbacks(self.imported_handle),
+            );
             self.device.allocation_count.fetch_sub(1, Ordering::Release);
         }
     }
@@ -783,6 +1074,31 @@ pub struct MemoryAllocateInfo<'d> {
     /// The default value is [`MemoryAllocateFlags::empty()`].
     pub flags: MemoryAllocateFlags,
 
+    /// The priority of the memory allocation relative to other allocations, between 0.0 and 1.0.
+    ///
+    /// When the device runs out of memory, the implementation may move lower-priority allocations
+    /// out of device-local memory before higher-priority ones.
+    ///
+    /// If not `0.5`, the
+    /// [`memory_priority`](crate::device::Features::memory_priority) feature must be enabled on
+    /// the device.
+    ///
+    /// The default value is `0.5`.
+    pub priority: f32,
+
+    /// The opaque capture address to allocate the memory at, when replaying a previously
+    /// captured allocation.
+    ///
+    /// The value should be one that was previously returned by
+    /// [`DeviceMemory::opaque_capture_address`]. If `Some`, `flags` must contain
+    /// [`MemoryAllocateFlags::DEVICE_ADDRESS_CAPTURE_REPLAY`], the memory must not be imported,
+    /// and the device API version must be at least 1.2, or the
+    /// [`khr_buffer_device_address`](crate::device::DeviceExtensions::khr_buffer_device_address)
+    /// extension must be enabled on the device.
+    ///
+    /// The default value is `None`.
+    pub opaque_capture_address: Option<u64>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -795,6 +1111,8 @@ impl Default for MemoryAllocateInfo<'static> {
             dedicated_allocation: None,
             export_handle_types: ExternalMemoryHandleTypes::empty(),
             flags: MemoryAllocateFlags::empty(),
+            priority: 0.5,
+            opaque_capture_address: None,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -810,6 +1128,8 @@ impl<'d> MemoryAllocateInfo<'d> {
             dedicated_allocation: Some(dedicated_allocation),
             export_handle_types: ExternalMemoryHandleTypes::empty(),
             flags: MemoryAllocateFlags::empty(),
+            priority: 0.5,
+            opaque_capture_address: None,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -867,6 +1187,33 @@ pub enum MemoryImportInfo {
         handle_type: ExternalMemoryHandleType,
         handle: ash::vk::HANDLE,
     },
+
+    /// Import memory from a pointer to host memory.
+    ///
+    /// `handle_type` must be either [`ExternalMemoryHandleType::HostAllocation`] or
+    /// [`ExternalMemoryHandleType::HostMappedForeignMemory`]. `host_pointer` and
+    /// [`MemoryAllocateInfo::allocation_size`] must both be a multiple of the
+    /// [`min_imported_host_pointer_alignment`] device property.
+    ///
+    /// # Safety
+    ///
+    /// - If `handle_type` is [`ExternalMemoryHandleType::HostAllocation`], `host_pointer` must
+    ///   point to host memory that was allocated by the application.
+    /// - If `handle_type` is [`ExternalMemoryHandleType::HostMappedForeignMemory`],
+    ///   `host_pointer` must point to host-mapped foreign memory.
+    /// - The range of host memory starting at `host_pointer` with a length of
+    ///   [`MemoryAllocateInfo::allocation_size`] must remain valid, and must not be freed or
+    ///   unmapped, for as long as the imported `DeviceMemory` exists.
+    /// - [`MemoryAllocateInfo::dedicated_allocation`] must be `None`.
+    ///
+    /// [`MemoryAllocateInfo::memory_type_index`] must be one of the memory types returned by
+    /// [`Device::host_pointer_properties`], which is checked when importing.
+    ///
+    /// [`min_imported_host_pointer_alignment`]: crate::device::Properties::min_imported_host_pointer_alignment
+    HostPointer {
+        handle_type: ExternalMemoryHandleType,
+        host_pointer: *mut c_void,
+    },
 }
 
 vulkan_bitflags_enum! {
@@ -951,8 +1298,12 @@ vulkan_bitflags! {
     /// [`ext_buffer_device_address`]: crate::device::DeviceExtensions::ext_buffer_device_address
     DEVICE_ADDRESS = DEVICE_ADDRESS,
 
-    /* TODO: enable
-    DEVICE_ADDRESS_CAPTURE_REPLAY = DEVICE_ADDRESS_CAPTURE_REPLAY,*/
+    /// Specifies that the device address of the allocated device memory can be saved and reused
+    /// on a subsequent run, for example for trace capture and replay. This requires that the
+    /// [`buffer_device_address_capture_replay`] feature is enabled on the device.
+    ///
+    /// [`buffer_device_address_capture_replay`]: crate::device::Features::buffer_device_address_capture_replay
+    DEVICE_ADDRESS_CAPTURE_REPLAY = DEVICE_ADDRESS_CAPTURE_REPLAY,
 }
 
 /// Error type returned by functions related to `DeviceMemory`.
@@ -995,6 +1346,38 @@ pub enum DeviceMemoryError {
         handle_type: ExternalMemoryHandleType,
     },
 
+    /// `opaque_capture_address` was `Some`, but `flags` did not contain
+    /// `MemoryAllocateFlags::DEVICE_ADDRESS_CAPTURE_REPLAY`.
+    OpaqueCaptureAddressNoCaptureReplayFlag,
+
+    /// `opaque_capture_address` was `Some`, but the memory is being imported.
+    OpaqueCaptureAddressWithImport,
+
+    /// The memory was not allocated with the `MemoryAllocateFlags::DEVICE_ADDRESS` flag.
+    NoDeviceAddressFlag,
+
+    /// The provided `MemoryImportInfo::HostPointer::handle_type` is not supported for host
+    /// pointers.
+    ImportHostPointerHandleTypeNotSupported {
+        handle_type: ExternalMemoryHandleType,
+    },
+
+    /// The provided `MemoryImportInfo::HostPointer::host_pointer` or `allocation_size` was not a
+    /// multiple of the [`min_imported_host_pointer_alignment`] device property.
+    ///
+    /// [`min_imported_host_pointer_alignment`]: crate::device::Properties::min_imported_host_pointer_alignment
+    ImportHostPointerNotAligned { required_alignment: DeviceSize },
+
+    /// The provided `memory_type_index` is not one of the memory types that
+    /// `MemoryImportInfo::HostPointer::host_pointer` can be imported into, as reported by
+    /// [`Device::host_pointer_properties`].
+    ///
+    /// [`Device::host_pointer_properties`]: crate::device::Device::host_pointer_properties
+    ImportHostPointerMemoryTypeNotSupported {
+        memory_type_index: u32,
+        supported_memory_type_bits: u32,
+    },
+
     /// The provided `allocation_size` was greater than the memory type's heap size.
     MemoryTypeHeapSizeExceeded {
         allocation_size: DeviceSize,
@@ -1075,6 +1458,41 @@ impl Display for DeviceMemoryError {
                 "the provided `MemoryImportInfo::Win32::handle_type` ({:?}) is not supported",
                 handle_type,
             ),
+            Self::OpaqueCaptureAddressNoCaptureReplayFlag => write!(
+                f,
+                "`opaque_capture_address` was `Some`, but `flags` did not contain \
+                `MemoryAllocateFlags::DEVICE_ADDRESS_CAPTURE_REPLAY`",
+            ),
+            Self::OpaqueCaptureAddressWithImport => write!(
+                f,
+                "`opaque_capture_address` was `Some`, but the memory is being imported",
+            ),
+            Self::NoDeviceAddressFlag => write!(
+                f,
+                "the memory was not allocated with the `MemoryAllocateFlags::DEVICE_ADDRESS` flag",
+            ),
+            Self::ImportHostPointerHandleTypeNotSupported { handle_type } => write!(
+                f,
+                "the provided `MemoryImportInfo::HostPointer::handle_type` ({:?}) is not \
+                supported for host pointers",
+                handle_type,
+            ),
+            Self::ImportHostPointerNotAligned { required_alignment } => write!(
+                f,
+                "the provided `MemoryImportInfo::HostPointer::host_pointer` or `allocation_size` \
+                was not a multiple of the `min_imported_host_pointer_alignment` device property \
+                ({})",
+                required_alignment,
+            ),
+            Self::ImportHostPointerMemoryTypeNotSupported {
+                memory_type_index,
+                supported_memory_type_bits,
+            } => write!(
+                f,
+                "the provided `memory_type_index` ({}) is not one of the memory types that \
+                `MemoryImportInfo::HostPointer::host_pointer` can be imported into ({:#b})",
+                memory_type_index, supported_memory_type_bits,
+            ),
             Self::MemoryTypeHeapSizeExceeded {
                 allocation_size,
                 heap_size,
@@ -1583,7 +2001,10 @@ impl From<OomError> for MemoryMapError {
 mod tests {
     use super::MemoryAllocateInfo;
     use crate::{
-        memory::{DeviceMemory, DeviceMemoryError, MemoryPropertyFlags},
+        memory::{
+            DeviceMemory, DeviceMemoryError, ExternalMemoryHandleType, MemoryImportInfo,
+            MemoryPropertyFlags,
+        },
         OomError,
     };
 
@@ -1617,6 +2038,47 @@ mod tests {
         });
     }
 
+    #[test]
+    fn priority_feature_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+        match DeviceMemory::allocate(
+            device,
+            MemoryAllocateInfo {
+                allocation_size: 256,
+                memory_type_index: 0,
+                priority: 1.0,
+                ..Default::default()
+            },
+        ) {
+            Err(DeviceMemoryError::RequirementNotMet { .. }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn import_host_pointer_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+        let mut data = [0u8; 256];
+
+        match unsafe {
+            DeviceMemory::import(
+                device,
+                MemoryAllocateInfo {
+                    allocation_size: 256,
+                    memory_type_index: 0,
+                    ..Default::default()
+                },
+                MemoryImportInfo::HostPointer {
+                    handle_type: ExternalMemoryHandleType::HostAllocation,
+                    host_pointer: data.as_mut_ptr().cast(),
+                },
+            )
+        } {
+            Err(DeviceMemoryError::RequirementNotMet { .. }) => (),
+            _ => panic!(),
+        }
+    }
+
     #[test]
     #[cfg(target_pointer_width = "64")]
     fn oom_single() {
@@ -0,0 +1,370 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Allocation of host memory by the Vulkan implementation.
+//!
+//! The Vulkan implementation allocates host memory for its own use, for example to hold the state
+//! of the objects that you create. By default the implementation uses its own allocator for this,
+//! but you can instead provide a [`HostAllocator`] when creating an [`Instance`] or a [`Device`].
+//! Vulkano then passes it to every creation and destruction of a Vulkan object belonging to that
+//! instance or device. This lets you track how much host memory the implementation uses, or limit
+//! how much it can use.
+//!
+//! [`Instance`]: crate::instance::Instance
+//! [`Device`]: crate::device::Device
+
+use crate::macros::vulkan_enum;
+use std::{
+    ffi::c_void,
+    fmt::Debug,
+    panic::{catch_unwind, AssertUnwindSafe},
+    ptr,
+    sync::Arc,
+};
+
+/// Allocates host memory on behalf of the Vulkan implementation.
+///
+/// The allocator is called from whatever thread the implementation happens to be running on, so
+/// it must be thread-safe. The functions should not panic; if they do, the panic is caught and
+/// the allocation is treated as having failed.
+///
+/// # Safety
+///
+/// - `allocate` and `reallocate` must return either a null pointer, or a pointer to at least
+///   `size` bytes of memory that is aligned to `alignment` and is not used by anything else.
+/// - `reallocate` must preserve the contents of the original allocation, up to the smaller of the
+///   old and new sizes. If it returns a null pointer, the original allocation must be left
+///   untouched.
+/// - None of the functions may call into the Vulkan API.
+pub unsafe trait HostAllocator: Debug + Send + Sync {
+    /// Allocates `size` bytes of memory, aligned to `alignment`.
+    ///
+    /// `size` is never zero, and `alignment` is always a power of two. Returns a null pointer if
+    /// the memory could not be allocated, which the implementation reports as an out of host
+    /// memory error.
+    fn allocate(&self, size: usize, alignment: usize, scope: AllocationScope) -> *mut c_void;
+
+    /// Changes the size of an existing allocation to `size` bytes, aligned to `alignment`.
+    ///
+    /// `size` is never zero, and `alignment` is the same as when `ptr` was allocated. Returns a
+    /// null pointer if the memory could not be reallocated.
+    ///
+    /// # Safety
+    ///
+    /// - `ptr` must have been returned by `allocate` or `reallocate` on `self`, and must not
+    ///   have been freed since.
+    unsafe fn reallocate(
+        &self,
+        ptr: *mut c_void,
+        size: usize,
+        alignment: usize,
+        scope: AllocationScope,
+    ) -> *mut c_void;
+
+    /// Frees an allocation.
+    ///
+    /// # Safety
+    ///
+    /// - `ptr` must have been returned by `allocate` or `reallocate` on `self`, and must not
+    ///   have been freed since.
+    unsafe fn free(&self, ptr: *mut c_void);
+
+    /// Called when the implementation has allocated memory by itself, without going through
+    /// this allocator. This is purely informational.
+    ///
+    /// The default implementation does nothing.
+    #[inline]
+    fn internal_allocation_notify(
+        &self,
+        size: usize,
+        allocation_type: InternalAllocationType,
+        scope: AllocationScope,
+    ) {
+        let _ = (size, allocation_type, scope);
+    }
+
+    /// Called when the implementation has freed memory that it allocated by itself. This is
+    /// purely informational.
+    ///
+    /// The default implementation does nothing.
+    #[inline]
+    fn internal_free_notify(
+        &self,
+        size: usize,
+        allocation_type: InternalAllocationType,
+        scope: AllocationScope,
+    ) {
+        let _ = (size, allocation_type, scope);
+    }
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// How long an allocation made by the implementation is expected to live.
+    AllocationScope = SystemAllocationScope(i32);
+
+    /// The allocation only lives for the duration of a single Vulkan command.
+    Command = COMMAND,
+
+    /// The allocation lives as long as the Vulkan object it was made for.
+    Object = OBJECT,
+
+    /// The allocation is used for a cache, such as a pipeline cache.
+    Cache = CACHE,
+
+    /// The allocation lives as long as the device.
+    Device = DEVICE,
+
+    /// The allocation lives as long as the instance.
+    Instance = INSTANCE,
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The purpose of memory that the implementation allocated by itself.
+    InternalAllocationType = InternalAllocationType(i32);
+
+    /// The memory holds executable code.
+    Executable = EXECUTABLE,
+}
+
+/// A `HostAllocator` together with the `VkAllocationCallbacks` that forward to it.
+#[derive(Debug)]
+pub(crate) struct AllocationCallbacks {
+    // Boxed so that the pointer to it in `callbacks_vk` stays valid when `self` is moved.
+    allocator: Box<Arc<dyn HostAllocator>>,
+    callbacks_vk: ash::vk::AllocationCallbacks,
+}
+
+// `callbacks_vk` only points to `allocator`, which is `Send + Sync`.
+unsafe impl Send for AllocationCallbacks {}
+unsafe impl Sync for AllocationCallbacks {}
+
+impl AllocationCallbacks {
+    pub(crate) fn new(allocator: Arc<dyn HostAllocator>) -> Self {
+        let allocator = Box::new(allocator);
+        let callbacks_vk = ash::vk::AllocationCallbacks {
+            p_user_data: &*allocator as *const Arc<dyn HostAllocator> as *mut c_void,
+            pfn_allocation: Some(allocation_trampoline),
+            pfn_reallocation: Some(reallocation_trampoline),
+            pfn_free: Some(free_trampoline),
+            pfn_internal_allocation: Some(internal_allocation_trampoline),
+            pfn_internal_free: Some(internal_free_trampoline),
+        };
+
+        AllocationCallbacks {
+            allocator,
+            callbacks_vk,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn allocator(&self) -> &Arc<dyn HostAllocator> {
+        &self.allocator
+    }
+
+    /// Returns a pointer to pass as `pAllocator`, which is null if `callbacks` is `None`.
+    #[inline]
+    pub(crate) fn as_ptr(callbacks: Option<&Self>) -> *const ash::vk::AllocationCallbacks {
+        callbacks.map_or(ptr::null(), |callbacks| &callbacks.callbacks_vk)
+    }
+}
+
+unsafe fn allocator_from_user_data<'a>(user_data: *mut c_void) -> &'a dyn HostAllocator {
+    &**(user_data as *const Arc<dyn HostAllocator>)
+}
+
+// Unknown values can only come from a newer implementation; treat them as the longest-lived
+// scope, so that nothing is assumed to be freed sooner than it is.
+fn scope_from_vk(scope: ash::vk::SystemAllocationScope) -> AllocationScope {
+    scope.try_into().unwrap_or(AllocationScope::Instance)
+}
+
+unsafe extern "system" fn allocation_trampoline(
+    user_data: *mut c_void,
+    size: usize,
+    alignment: usize,
+    scope: ash::vk::SystemAllocationScope,
+) -> *mut c_void {
+    catch_unwind(AssertUnwindSafe(|| {
+        allocator_from_user_data(user_data).allocate(size, alignment, scope_from_vk(scope))
+    }))
+    .unwrap_or(ptr::null_mut())
+}
+
+unsafe extern "system" fn reallocation_trampoline(
+    user_data: *mut c_void,
+    original: *mut c_void,
+    size: usize,
+    alignment: usize,
+    scope: ash::vk::SystemAllocationScope,
+) -> *mut c_void {
+    catch_unwind(AssertUnwindSafe(|| {
+        let allocator = allocator_from_user_data(user_data);
+        let scope = scope_from_vk(scope);
+
+        // The spec defines these cases in terms of allocating and freeing, so the trait
+        // implementation doesn't need to handle them.
+        if original.is_null() {
+            allocator.allocate(size, alignment, scope)
+        } else if size == 0 {
+            allocator.free(original);
+            ptr::null_mut()
+        } else {
+            allocator.reallocate(original, size, alignment, scope)
+        }
+    }))
+    .unwrap_or(ptr::null_mut())
+}
+
+unsafe extern "system" fn free_trampoline(user_data: *mut c_void, memory: *mut c_void) {
+    if memory.is_null() {
+        return;
+    }
+
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        allocator_from_user_data(user_data).free(memory)
+    }));
+}
+
+unsafe extern "system" fn internal_allocation_trampoline(
+    user_data: *mut c_void,
+    size: usize,
+    allocation_type: ash::vk::InternalAllocationType,
+    scope: ash::vk::SystemAllocationScope,
+) {
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        if let Ok(allocation_type) = allocation_type.try_into() {
+            allocator_from_user_data(user_data).internal_allocation_notify(
+                size,
+                allocation_type,
+                scope_from_vk(scope),
+            );
+        }
+    }));
+}
+
+unsafe extern "system" fn internal_free_trampoline(
+    user_data: *mut c_void,
+    size: usize,
+    allocation_type: ash::vk::InternalAllocationType,
+    scope: ash::vk::SystemAllocationScope,
+) {
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        if let Ok(allocation_type) = allocation_type.try_into() {
+            allocator_from_user_data(user_data).internal_free_notify(
+                size,
+                allocation_type,
+                scope_from_vk(scope),
+            );
+        }
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        alloc::{self, Layout},
+        collections::HashMap,
+        sync::Mutex,
+    };
+
+    #[derive(Debug, Default)]
+    struct TrackingAllocator {
+        allocations: Mutex<HashMap<usize, Layout>>,
+    }
+
+    unsafe impl HostAllocator for TrackingAllocator {
+        fn allocate(&self, size: usize, alignment: usize, _scope: AllocationScope) -> *mut c_void {
+            let layout = Layout::from_size_align(size, alignment).unwrap();
+            let ptr = unsafe { alloc::alloc(layout) };
+            self.allocations
+                .lock()
+                .unwrap()
+                .insert(ptr as usize, layout);
+
+            ptr as *mut c_void
+        }
+
+        unsafe fn reallocate(
+            &self,
+            ptr: *mut c_void,
+            size: usize,
+            _alignment: usize,
+            _scope: AllocationScope,
+        ) -> *mut c_void {
+            let mut allocations = self.allocations.lock().unwrap();
+            let layout = allocations.remove(&(ptr as usize)).unwrap();
+            let new_ptr = alloc::realloc(ptr as *mut u8, layout, size);
+            allocations.insert(
+                new_ptr as usize,
+                Layout::from_size_align(size, layout.align()).unwrap(),
+            );
+
+            new_ptr as *mut c_void
+        }
+
+        unsafe fn free(&self, ptr: *mut c_void) {
+            let layout = self
+                .allocations
+                .lock()
+                .unwrap()
+                .remove(&(ptr as usize))
+                .unwrap();
+            alloc::dealloc(ptr as *mut u8, layout);
+        }
+    }
+
+    #[test]
+    fn callbacks_forward_to_allocator() {
+        let allocator = Arc::new(TrackingAllocator::default());
+        let callbacks = AllocationCallbacks::new(allocator.clone());
+        let callbacks_vk = unsafe { &*AllocationCallbacks::as_ptr(Some(&callbacks)) };
+        let scope = ash::vk::SystemAllocationScope::OBJECT;
+
+        unsafe {
+            let ptr =
+                (callbacks_vk.pfn_allocation.unwrap())(callbacks_vk.p_user_data, 16, 8, scope);
+            assert!(!ptr.is_null());
+            assert_eq!(allocator.allocations.lock().unwrap().len(), 1);
+
+            let ptr = (callbacks_vk.pfn_reallocation.unwrap())(
+                callbacks_vk.p_user_data,
+                ptr,
+                64,
+                8,
+                scope,
+            );
+            assert!(!ptr.is_null());
+            assert_eq!(
+                allocator.allocations.lock().unwrap()[&(ptr as usize)].size(),
+                64
+            );
+
+            // Reallocating to a size of zero frees the allocation.
+            let ptr = (callbacks_vk.pfn_reallocation.unwrap())(
+                callbacks_vk.p_user_data,
+                ptr,
+                0,
+                8,
+                scope,
+            );
+            assert!(ptr.is_null());
+            assert!(allocator.allocations.lock().unwrap().is_empty());
+
+            // Freeing a null pointer does nothing.
+            (callbacks_vk.pfn_free.unwrap())(callbacks_vk.p_user_data, ptr::null_mut());
+        }
+
+        assert!(AllocationCallbacks::as_ptr(None).is_null());
+    }
+}
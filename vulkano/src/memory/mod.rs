@@ -106,6 +106,7 @@ use std::{num::NonZeroU64, sync::Arc};
 
 pub mod allocator;
 mod device_memory;
+pub mod host;
 
 /// Properties of the memory in a physical device.
 #[derive(Clone, Debug)]
@@ -295,6 +296,24 @@ vulkan_bitflags! {
     },
 }
 
+/// The current budget and usage of a memory heap, as returned by
+/// [`PhysicalDevice::memory_budget`].
+///
+/// [`PhysicalDevice::memory_budget`]: crate::device::physical::PhysicalDevice::memory_budget
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MemoryHeapBudget {
+    /// An estimate of how much memory, in bytes, the process can allocate from the heap before
+    /// allocations may fail or cause performance degradation.
+    ///
+    /// This takes into account the memory used by other processes and may be smaller than the
+    /// size of the heap.
+    pub budget: DeviceSize,
+
+    /// An estimate of how much memory, in bytes, the process is currently using in the heap.
+    pub usage: DeviceSize,
+}
+
 /// Represents requirements expressed by the Vulkan implementation when it comes to binding memory
 /// to a resource.
 #[derive(Clone, Copy, Debug)]
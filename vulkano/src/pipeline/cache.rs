@@ -20,9 +20,32 @@
 //! Once that is done, you can extract the data from the cache and store it. See the documentation
 //! of [`get_data`](crate::pipeline::cache::PipelineCache::get_data) for example of how to store the data
 //! on the disk, and [`with_data`](crate::pipeline::cache::PipelineCache::with_data) for how to reload it.
-
-use crate::{device::Device, OomError, VulkanError, VulkanObject};
-use std::{mem::MaybeUninit, ptr, sync::Arc};
+//!
+//! The data of a pipeline cache starts with a header that identifies the physical device that
+//! produced it. [`with_validated_data`](crate::pipeline::cache::PipelineCache::with_validated_data)
+//! checks this header, and creates an empty cache instead if the data was produced by a different
+//! device or driver. [`load_from_file`](crate::pipeline::cache::PipelineCache::load_from_file) and
+//! [`save_to_file`](crate::pipeline::cache::PipelineCache::save_to_file) additionally take care of
+//! writing the data to disk atomically and detecting corrupted files.
+
+use crate::{
+    device::{physical::PhysicalDevice, Device},
+    OomError, VulkanError, VulkanObject,
+};
+use std::{
+    error::Error,
+    ffi::OsString,
+    fmt::{Display, Error as FmtError, Formatter},
+    fs::{self, File},
+    io::{self, Write},
+    mem::MaybeUninit,
+    path::Path,
+    process, ptr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
 
 /// Opaque cache that contains pipeline objects.
 ///
@@ -39,13 +62,14 @@ impl PipelineCache {
     ///
     /// The data passed to this function will most likely be blindly trusted by the Vulkan
     /// implementation. Therefore you can easily crash your application or the system by passing
-    /// wrong data. Hence why this function is unsafe.
+    /// wrong data. Hence why this function is unsafe. If the data comes from a source that you
+    /// don't fully control, use [`with_validated_data`](#method.with_validated_data) instead,
+    /// which checks the header of the data first.
     ///
     /// # Examples
     ///
     /// This example loads a cache from a file, if it exists.
     /// See [`get_data`](#method.get_data) for how to store the data in a file.
-    /// TODO: there's a header in the cached data that must be checked ; talk about this
     ///
     /// ```
     /// # use std::sync::Arc;
@@ -84,6 +108,140 @@ impl PipelineCache {
         PipelineCache::new_impl(device, Some(initial_data))
     }
 
+    /// Builds a new pipeline cache from existing data, if the data is compatible with the
+    /// device. Otherwise, builds a new empty pipeline cache.
+    ///
+    /// The data is compatible if its header, as checked by
+    /// [`is_data_compatible`](#method.is_data_compatible), matches the physical device of
+    /// `device`. This protects against data that was produced by a different device or driver
+    /// version, and against data that is too short to contain a header. It does not protect
+    /// against data that was corrupted after the header; use
+    /// [`load_from_file`](#method.load_from_file) to also detect that.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use vulkano::device::Device;
+    /// use std::fs;
+    /// use vulkano::pipeline::cache::PipelineCache;
+    /// # let device: Arc<Device> = return;
+    ///
+    /// let cache = match fs::read("pipeline_cache.bin") {
+    ///     Ok(data) => PipelineCache::with_validated_data(device.clone(), &data).unwrap(),
+    ///     Err(_) => PipelineCache::empty(device.clone()).unwrap(),
+    /// };
+    /// ```
+    #[inline]
+    pub fn with_validated_data(
+        device: Arc<Device>,
+        initial_data: &[u8],
+    ) -> Result<Arc<PipelineCache>, OomError> {
+        if Self::is_data_compatible(device.physical_device(), initial_data) {
+            unsafe { PipelineCache::new_impl(device, Some(initial_data)) }
+        } else {
+            PipelineCache::empty(device)
+        }
+    }
+
+    /// Returns whether the header of `data` matches `physical_device`.
+    ///
+    /// This checks that `data` starts with a `VkPipelineCacheHeaderVersionOne` header, whose
+    /// vendor ID, device ID and pipeline cache UUID are equal to those in the properties of
+    /// `physical_device`.
+    #[inline]
+    pub fn is_data_compatible(physical_device: &PhysicalDevice, data: &[u8]) -> bool {
+        let properties = physical_device.properties();
+
+        header_matches(
+            data,
+            properties.vendor_id,
+            properties.device_id,
+            &properties.pipeline_cache_uuid,
+        )
+    }
+
+    /// Builds a new pipeline cache from the data in a file that was written by
+    /// [`save_to_file`](#method.save_to_file).
+    ///
+    /// If the file doesn't exist or can't be read, if its checksum doesn't match its contents,
+    /// or if the data isn't compatible with the device, a new empty pipeline cache is built
+    /// instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use vulkano::device::Device;
+    /// use vulkano::pipeline::cache::PipelineCache;
+    /// # let device: Arc<Device> = return;
+    ///
+    /// let cache = PipelineCache::load_from_file(device.clone(), "pipeline_cache.bin").unwrap();
+    ///
+    /// // Create pipelines using the cache...
+    ///
+    /// // If saving fails, the cache is simply rebuilt the next time.
+    /// let _ = cache.save_to_file("pipeline_cache.bin");
+    /// ```
+    pub fn load_from_file(
+        device: Arc<Device>,
+        path: impl AsRef<Path>,
+    ) -> Result<Arc<PipelineCache>, OomError> {
+        match fs::read(path).ok().as_deref().and_then(unwrap_file_data) {
+            Some(data) => Self::with_validated_data(device, data),
+            None => Self::empty(device),
+        }
+    }
+
+    /// Writes the data of the cache to a file, so that it can be reloaded with
+    /// [`load_from_file`](#method.load_from_file).
+    ///
+    /// The data is stored together with a checksum. It is first written to a uniquely named
+    /// temporary file next to `path`, which then replaces `path`, so that `path` never contains
+    /// partially written data, even if several caches are saved to the same path concurrently.
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<(), PipelineCacheSaveError> {
+        static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let path = path.as_ref();
+        let data = self.get_data()?;
+
+        let (temp_path, mut file) = loop {
+            let mut temp_path = OsString::from(path);
+            temp_path.push(format!(
+                ".{}.{}.tmp",
+                process::id(),
+                TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed),
+            ));
+
+            // A leftover file from an earlier process with the same id is never overwritten.
+            match File::options()
+                .write(true)
+                .create_new(true)
+                .open(&temp_path)
+            {
+                Ok(file) => break (temp_path, file),
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(err) => return Err(err.into()),
+            }
+        };
+
+        let result = (|| {
+            file.write_all(FILE_MAGIC)?;
+            file.write_all(&checksum(&data).to_le_bytes())?;
+            file.write_all(&data)?;
+            file.sync_all()?;
+            drop(file);
+
+            fs::rename(&temp_path, path)
+        })();
+
+        if result.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+
+        Ok(result?)
+    }
+
     /// Builds a new empty pipeline cache.
     ///
     /// # Examples
@@ -121,7 +279,7 @@ impl PipelineCache {
             (fns.v1_0.create_pipeline_cache)(
                 device.handle(),
                 &infos,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -240,6 +398,91 @@ impl PipelineCache {
     }
 }
 
+// Identifies files written by `PipelineCache::save_to_file`.
+const FILE_MAGIC: &[u8; 8] = b"VKNOPCv1";
+
+// The size of `VkPipelineCacheHeaderVersionOne`.
+const HEADER_SIZE: usize = 32;
+
+fn header_matches(data: &[u8], vendor_id: u32, device_id: u32, uuid: &[u8; 16]) -> bool {
+    if data.len() < HEADER_SIZE {
+        return false;
+    }
+
+    // The header is always stored with the least significant byte first.
+    let read_u32 = |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+    let header_size = read_u32(0) as usize;
+    let header_version = read_u32(4);
+
+    header_size >= HEADER_SIZE
+        && header_size <= data.len()
+        && header_version == ash::vk::PipelineCacheHeaderVersion::ONE.as_raw() as u32
+        && read_u32(8) == vendor_id
+        && read_u32(12) == device_id
+        && data[16..32] == uuid[..]
+}
+
+// Returns the cache data inside the contents of a file written by `save_to_file`, if the file
+// is intact.
+fn unwrap_file_data(contents: &[u8]) -> Option<&[u8]> {
+    let contents = contents.strip_prefix(FILE_MAGIC)?;
+
+    if contents.len() < 8 {
+        return None;
+    }
+
+    let (stored_checksum, data) = contents.split_at(8);
+    (u64::from_le_bytes(stored_checksum.try_into().unwrap()) == checksum(data)).then_some(data)
+}
+
+// 64-bit FNV-1a. This only needs to detect accidental corruption, and must stay the same across
+// versions so that existing files remain readable.
+fn checksum(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Error that can happen when saving a pipeline cache to a file.
+#[derive(Debug)]
+pub enum PipelineCacheSaveError {
+    /// Not enough memory to retrieve the data of the cache.
+    OomError(OomError),
+
+    /// Writing the file failed.
+    IoError(io::Error),
+}
+
+impl Error for PipelineCacheSaveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            Self::IoError(err) => Some(err),
+        }
+    }
+}
+
+impl Display for PipelineCacheSaveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::IoError(_) => write!(f, "writing the pipeline cache file failed"),
+        }
+    }
+}
+
+impl From<OomError> for PipelineCacheSaveError {
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+impl From<io::Error> for PipelineCacheSaveError {
+    fn from(err: io::Error) -> Self {
+        Self::IoError(err)
+    }
+}
+
 unsafe impl VulkanObject for PipelineCache {
     type Handle = ash::vk::PipelineCache;
 
@@ -254,18 +497,72 @@ impl Drop for PipelineCache {
     fn drop(&mut self) {
         unsafe {
             let fns = self.device.fns();
-            (fns.v1_0.destroy_pipeline_cache)(self.device.handle(), self.cache, ptr::null());
+            (fns.v1_0.destroy_pipeline_cache)(
+                self.device.handle(),
+                self.cache,
+                self.device.allocation_callbacks(),
+            );
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::{checksum, header_matches, unwrap_file_data, FILE_MAGIC};
     use crate::{
         pipeline::{cache::PipelineCache, ComputePipeline},
         shader::ShaderModule,
     };
 
+    fn header(size: u32, version: u32, vendor_id: u32, device_id: u32, uuid: [u8; 16]) -> Vec<u8> {
+        [size, version, vendor_id, device_id]
+            .into_iter()
+            .flat_map(u32::to_le_bytes)
+            .chain(uuid)
+            .collect()
+    }
+
+    #[test]
+    fn header_validation() {
+        let uuid = [7; 16];
+        let mut data = header(32, 1, 0x10de, 0x1234, uuid);
+        assert!(header_matches(&data, 0x10de, 0x1234, &uuid));
+
+        data.extend([1, 2, 3]);
+        assert!(header_matches(&data, 0x10de, 0x1234, &uuid));
+
+        assert!(!header_matches(&data, 0x1002, 0x1234, &uuid));
+        assert!(!header_matches(&data, 0x10de, 0x4321, &uuid));
+        assert!(!header_matches(&data, 0x10de, 0x1234, &[8; 16]));
+        assert!(!header_matches(&data[..31], 0x10de, 0x1234, &uuid));
+        assert!(!header_matches(&[], 0x10de, 0x1234, &uuid));
+
+        let data = header(32, 2, 0x10de, 0x1234, uuid);
+        assert!(!header_matches(&data, 0x10de, 0x1234, &uuid));
+
+        let data = header(16, 1, 0x10de, 0x1234, uuid);
+        assert!(!header_matches(&data, 0x10de, 0x1234, &uuid));
+
+        let data = header(64, 1, 0x10de, 0x1234, uuid);
+        assert!(!header_matches(&data, 0x10de, 0x1234, &uuid));
+    }
+
+    #[test]
+    fn file_data_checksum() {
+        let data = [1, 2, 3, 4, 5];
+        let mut contents = FILE_MAGIC.to_vec();
+        contents.extend(checksum(&data).to_le_bytes());
+        contents.extend(data);
+        assert_eq!(unwrap_file_data(&contents), Some(&data[..]));
+
+        let last = contents.len() - 1;
+        contents[last] ^= 1;
+        assert_eq!(unwrap_file_data(&contents), None);
+
+        assert_eq!(unwrap_file_data(&contents[..12]), None);
+        assert_eq!(unwrap_file_data(&contents[8..]), None);
+    }
+
     #[test]
     fn merge_self_forbidden() {
         let (device, _queue) = gfx_dev_and_queue!();
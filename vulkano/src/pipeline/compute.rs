@@ -30,11 +30,15 @@ use crate::{
     device::{Device, DeviceOwned},
     pipeline::{
         cache::PipelineCache,
+        executable::{
+            self, PipelineExecutableError, PipelineExecutableInternalRepresentation,
+            PipelineExecutableProperties, PipelineExecutableStatistic,
+        },
         layout::{PipelineLayout, PipelineLayoutCreationError, PipelineLayoutSupersetError},
-        Pipeline, PipelineBindPoint,
+        Pipeline, PipelineBindPoint, PipelineCreateFlags, PipelineCreationFeedback,
     },
     shader::{DescriptorBindingRequirements, EntryPoint, SpecializationConstants},
-    DeviceSize, OomError, VulkanError, VulkanObject,
+    DeviceSize, OomError, RequirementNotMet, RequiresOneOf, Version, VulkanError, VulkanObject,
 };
 use ahash::HashMap;
 use std::{
@@ -59,9 +63,12 @@ pub struct ComputePipeline {
     handle: ash::vk::Pipeline,
     device: Arc<Device>,
     id: NonZeroU64,
+    flags: PipelineCreateFlags,
     layout: Arc<PipelineLayout>,
     descriptor_binding_requirements: HashMap<(u32, u32), DescriptorBindingRequirements>,
     num_used_descriptor_sets: u32,
+    creation_feedback: Option<PipelineCreationFeedback>,
+    stage_creation_feedback: Option<PipelineCreationFeedback>,
 }
 
 impl ComputePipeline {
@@ -128,6 +135,74 @@ impl ComputePipeline {
     where
         Css: SpecializationConstants,
     {
+        Self::with_create_info(
+            device,
+            shader,
+            specialization_constants,
+            layout,
+            ComputePipelineCreateInfo {
+                cache,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Builds a new `ComputePipeline` with a specific pipeline layout and additional creation
+    /// parameters.
+    ///
+    /// An error will be returned if the pipeline layout isn't a superset of what the shader
+    /// uses.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.base_pipeline` was not created from `device`.
+    pub fn with_create_info<Css>(
+        device: Arc<Device>,
+        shader: EntryPoint<'_>,
+        specialization_constants: &Css,
+        layout: Arc<PipelineLayout>,
+        create_info: ComputePipelineCreateInfo,
+    ) -> Result<Arc<ComputePipeline>, ComputePipelineCreationError>
+    where
+        Css: SpecializationConstants,
+    {
+        let ComputePipelineCreateInfo {
+            flags,
+            ref base_pipeline,
+            cache: _,
+            _ne: _,
+        } = create_info;
+
+        // VUID-VkComputePipelineCreateInfo-flags-parameter
+        flags.validate_device(&device)?;
+
+        // VUID-VkComputePipelineCreateInfo-pipelineCreationCacheControl-02875
+        if flags.intersects(PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED)
+            && !device.enabled_features().pipeline_creation_cache_control
+        {
+            return Err(ComputePipelineCreationError::RequirementNotMet {
+                required_for:
+                    "`flags` contains `PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED`",
+                requires_one_of: RequiresOneOf {
+                    features: &["pipeline_creation_cache_control"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        if let Some(base_pipeline) = base_pipeline {
+            // VUID-VkComputePipelineCreateInfo-flags-07984
+            assert_eq!(device, *base_pipeline.device());
+
+            // VUID-vkCreateComputePipelines-flags-00696
+            if !base_pipeline
+                .flags()
+                .intersects(PipelineCreateFlags::ALLOW_DERIVATIVES)
+            {
+                return Err(ComputePipelineCreationError::BasePipelineDerivativesNotAllowed);
+            }
+        }
+
         let spec_descriptors = Css::descriptors();
 
         for (constant_id, reqs) in shader.specialization_constant_requirements() {
@@ -147,12 +222,12 @@ impl ComputePipeline {
         )?;
 
         unsafe {
-            ComputePipeline::with_unchecked_pipeline_layout(
+            Self::record_create(
                 device,
                 shader,
                 specialization_constants,
                 layout,
-                cache,
+                create_info,
             )
         }
     }
@@ -169,8 +244,42 @@ impl ComputePipeline {
     where
         Css: SpecializationConstants,
     {
+        Self::record_create(
+            device,
+            shader,
+            specialization_constants,
+            layout,
+            ComputePipelineCreateInfo {
+                cache,
+                ..Default::default()
+            },
+        )
+    }
+
+    unsafe fn record_create<Css>(
+        device: Arc<Device>,
+        shader: EntryPoint<'_>,
+        specialization_constants: &Css,
+        layout: Arc<PipelineLayout>,
+        create_info: ComputePipelineCreateInfo,
+    ) -> Result<Arc<ComputePipeline>, ComputePipelineCreationError>
+    where
+        Css: SpecializationConstants,
+    {
+        let ComputePipelineCreateInfo {
+            flags: create_flags,
+            base_pipeline,
+            cache,
+            _ne: _,
+        } = create_info;
+
         let fns = device.fns();
 
+        let mut creation_feedback_vk = ash::vk::PipelineCreationFeedback::default();
+        let mut stage_creation_feedback_vk = ash::vk::PipelineCreationFeedback::default();
+        let has_creation_feedback = device.api_version() >= Version::V1_3
+            || device.enabled_extensions().ext_pipeline_creation_feedback;
+
         let handle = {
             let spec_descriptors = Css::descriptors();
             let specialization = ash::vk::SpecializationInfo {
@@ -193,31 +302,56 @@ impl ComputePipeline {
                 ..Default::default()
             };
 
-            let infos = ash::vk::ComputePipelineCreateInfo {
-                flags: ash::vk::PipelineCreateFlags::empty(),
+            let mut flags = ash::vk::PipelineCreateFlags::from(create_flags);
+
+            if base_pipeline.is_some() {
+                flags |= ash::vk::PipelineCreateFlags::DERIVATIVE;
+            }
+
+            let mut infos = ash::vk::ComputePipelineCreateInfo {
+                flags,
                 stage,
                 layout: layout.handle(),
-                base_pipeline_handle: ash::vk::Pipeline::null(),
-                base_pipeline_index: 0,
+                base_pipeline_handle: base_pipeline
+                    .as_ref()
+                    .map_or(ash::vk::Pipeline::null(), |base_pipeline| {
+                        base_pipeline.handle()
+                    }),
+                base_pipeline_index: -1,
                 ..Default::default()
             };
 
+            let creation_feedback_info_vk = ash::vk::PipelineCreationFeedbackCreateInfo {
+                p_pipeline_creation_feedback: &mut creation_feedback_vk,
+                pipeline_stage_creation_feedback_count: 1,
+                p_pipeline_stage_creation_feedbacks: &mut stage_creation_feedback_vk,
+                ..Default::default()
+            };
+
+            if has_creation_feedback {
+                infos.p_next = &creation_feedback_info_vk as *const _ as *const _;
+            }
+
             let cache_handle = match cache {
                 Some(ref cache) => cache.handle(),
                 None => ash::vk::PipelineCache::null(),
             };
 
             let mut output = MaybeUninit::uninit();
-            (fns.v1_0.create_compute_pipelines)(
+            let result = (fns.v1_0.create_compute_pipelines)(
                 device.handle(),
                 cache_handle,
                 1,
                 &infos,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
-            )
-            .result()
-            .map_err(VulkanError::from)?;
+            );
+
+            if result == ash::vk::Result::PIPELINE_COMPILE_REQUIRED {
+                return Err(ComputePipelineCreationError::PipelineCompileRequired);
+            }
+
+            result.result().map_err(VulkanError::from)?;
             output.assume_init()
         };
 
@@ -236,9 +370,16 @@ impl ComputePipeline {
             handle,
             device: device.clone(),
             id: Self::next_id(),
+            flags: create_flags,
             layout,
             descriptor_binding_requirements,
             num_used_descriptor_sets,
+            creation_feedback: has_creation_feedback
+                .then(|| PipelineCreationFeedback::from_vulkan(creation_feedback_vk))
+                .flatten(),
+            stage_creation_feedback: has_creation_feedback
+                .then(|| PipelineCreationFeedback::from_vulkan(stage_creation_feedback_vk))
+                .flatten(),
         }))
     }
 
@@ -247,6 +388,62 @@ impl ComputePipeline {
     pub fn device(&self) -> &Arc<Device> {
         &self.device
     }
+
+    /// Returns the flags that the pipeline was created with.
+    #[inline]
+    pub fn flags(&self) -> PipelineCreateFlags {
+        self.flags
+    }
+
+    /// Returns feedback about the creation of the pipeline, if the implementation provided it.
+    #[inline]
+    pub fn creation_feedback(&self) -> Option<&PipelineCreationFeedback> {
+        self.creation_feedback.as_ref()
+    }
+
+    /// Returns feedback about the creation of the compute shader stage, if the implementation
+    /// provided it.
+    #[inline]
+    pub fn stage_creation_feedback(&self) -> Option<&PipelineCreationFeedback> {
+        self.stage_creation_feedback.as_ref()
+    }
+
+    /// Returns the properties of the executables that the pipeline was compiled into.
+    ///
+    /// The [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info)
+    /// feature must be enabled on the device.
+    #[inline]
+    pub fn executable_properties(
+        &self,
+    ) -> Result<Vec<PipelineExecutableProperties>, PipelineExecutableError> {
+        executable::executable_properties(self)
+    }
+
+    /// Returns the statistics of the executable with index `executable_index`.
+    ///
+    /// The [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info)
+    /// feature must be enabled on the device, and the pipeline must have been created with
+    /// [`PipelineCreateFlags::CAPTURE_STATISTICS`].
+    #[inline]
+    pub fn executable_statistics(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableStatistic>, PipelineExecutableError> {
+        executable::executable_statistics(self, self.flags, executable_index)
+    }
+
+    /// Returns the internal representations of the executable with index `executable_index`.
+    ///
+    /// The [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info)
+    /// feature must be enabled on the device, and the pipeline must have been created with
+    /// [`PipelineCreateFlags::CAPTURE_INTERNAL_REPRESENTATIONS`].
+    #[inline]
+    pub fn executable_internal_representations(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableInternalRepresentation>, PipelineExecutableError> {
+        executable::executable_internal_representations(self, self.flags, executable_index)
+    }
 }
 
 impl Pipeline for ComputePipeline {
@@ -302,7 +499,47 @@ impl Drop for ComputePipeline {
     fn drop(&mut self) {
         unsafe {
             let fns = self.device.fns();
-            (fns.v1_0.destroy_pipeline)(self.device.handle(), self.handle, ptr::null());
+            (fns.v1_0.destroy_pipeline)(
+                self.device.handle(),
+                self.handle,
+                self.device.allocation_callbacks(),
+            );
+        }
+    }
+}
+
+/// Parameters to create a new `ComputePipeline` with [`ComputePipeline::with_create_info`].
+#[derive(Clone, Debug)]
+pub struct ComputePipelineCreateInfo {
+    /// Specifies how to create the pipeline.
+    ///
+    /// The default value is empty.
+    pub flags: PipelineCreateFlags,
+
+    /// The pipeline that the new pipeline is derived from.
+    ///
+    /// If `Some`, the base pipeline must have been created with
+    /// [`PipelineCreateFlags::ALLOW_DERIVATIVES`].
+    ///
+    /// The default value is `None`.
+    pub base_pipeline: Option<Arc<ComputePipeline>>,
+
+    /// The pipeline cache to use when creating the pipeline.
+    ///
+    /// The default value is `None`.
+    pub cache: Option<Arc<PipelineCache>>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for ComputePipelineCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            flags: PipelineCreateFlags::empty(),
+            base_pipeline: None,
+            cache: None,
+            _ne: crate::NonExhaustive(()),
         }
     }
 }
@@ -320,6 +557,15 @@ pub enum ComputePipelineCreationError {
     IncompatiblePipelineLayout(PipelineLayoutSupersetError),
     /// The provided specialization constants are not compatible with what the shader expects.
     IncompatibleSpecializationConstants,
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+    /// The base pipeline was not created with `PipelineCreateFlags::ALLOW_DERIVATIVES`.
+    BasePipelineDerivativesNotAllowed,
+    /// `PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED` was set, and creating the
+    /// pipeline would require compiling shaders.
+    PipelineCompileRequired,
 }
 
 impl Error for ComputePipelineCreationError {
@@ -329,33 +575,57 @@ impl Error for ComputePipelineCreationError {
             Self::DescriptorSetLayoutCreationError(err) => Some(err),
             Self::PipelineLayoutCreationError(err) => Some(err),
             Self::IncompatiblePipelineLayout(err) => Some(err),
-            Self::IncompatibleSpecializationConstants => None,
+            _ => None,
         }
     }
 }
 
 impl Display for ComputePipelineCreationError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
-        write!(
-            f,
-            "{}",
-            match self {
-                ComputePipelineCreationError::OomError(_) => "not enough memory available",
-                ComputePipelineCreationError::DescriptorSetLayoutCreationError(_) => {
-                    "error while creating a descriptor set layout object"
-                }
-                ComputePipelineCreationError::PipelineLayoutCreationError(_) => {
-                    "error while creating the pipeline layout object"
-                }
-                ComputePipelineCreationError::IncompatiblePipelineLayout(_) => {
-                    "the pipeline layout is not compatible with what the shader expects"
-                }
-                ComputePipelineCreationError::IncompatibleSpecializationConstants => {
-                    "the provided specialization constants are not compatible with what the shader \
-                    expects"
-                }
+        match self {
+            Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::DescriptorSetLayoutCreationError(_) => {
+                write!(f, "error while creating a descriptor set layout object")
             }
-        )
+            Self::PipelineLayoutCreationError(_) => {
+                write!(f, "error while creating the pipeline layout object")
+            }
+            Self::IncompatiblePipelineLayout(_) => write!(
+                f,
+                "the pipeline layout is not compatible with what the shader expects",
+            ),
+            Self::IncompatibleSpecializationConstants => write!(
+                f,
+                "the provided specialization constants are not compatible with what the shader \
+                expects",
+            ),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::BasePipelineDerivativesNotAllowed => write!(
+                f,
+                "the base pipeline was not created with `PipelineCreateFlags::ALLOW_DERIVATIVES`",
+            ),
+            Self::PipelineCompileRequired => write!(
+                f,
+                "`PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED` was set, and creating the \
+                pipeline would require compiling shaders",
+            ),
+        }
+    }
+}
+
+impl From<RequirementNotMet> for ComputePipelineCreationError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
     }
 }
 
@@ -403,11 +673,16 @@ mod tests {
         descriptor_set::{
             allocator::StandardDescriptorSetAllocator, PersistentDescriptorSet, WriteDescriptorSet,
         },
+        device::Device,
         memory::allocator::StandardMemoryAllocator,
-        pipeline::{ComputePipeline, Pipeline, PipelineBindPoint},
+        pipeline::{
+            compute::{ComputePipelineCreateInfo, ComputePipelineCreationError},
+            ComputePipeline, Pipeline, PipelineBindPoint, PipelineCreateFlags,
+        },
         shader::{ShaderModule, SpecializationConstants, SpecializationMapEntry},
         sync::{now, GpuFuture},
     };
+    use std::sync::Arc;
 
     // TODO: test for basic creation
     // TODO: test for pipeline layout error
@@ -531,4 +806,87 @@ mod tests {
         let data_buffer_content = data_buffer.read().unwrap();
         assert_eq!(*data_buffer_content, 0x12345678);
     }
+
+    // Returns a compute shader that does nothing.
+    fn empty_module(device: Arc<Device>) -> Arc<ShaderModule> {
+        /*
+        #version 450
+
+        layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+        void main() {}
+        */
+        const MODULE: [u8; 140] = [
+            3, 2, 35, 7, 0, 0, 1, 0, 0, 0, 0, 0, 5, 0, 0, 0, 0, 0, 0, 0, 17, 0, 2, 0, 1, 0, 0, 0,
+            14, 0, 3, 0, 0, 0, 0, 0, 1, 0, 0, 0, 15, 0, 5, 0, 5, 0, 0, 0, 3, 0, 0, 0, 109, 97, 105,
+            110, 0, 0, 0, 0, 16, 0, 6, 0, 3, 0, 0, 0, 17, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0,
+            0, 19, 0, 2, 0, 1, 0, 0, 0, 33, 0, 3, 0, 2, 0, 0, 0, 1, 0, 0, 0, 54, 0, 5, 0, 1, 0, 0,
+            0, 3, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 248, 0, 2, 0, 4, 0, 0, 0, 253, 0, 1, 0, 56, 0,
+            1, 0,
+        ];
+
+        unsafe { ShaderModule::from_bytes(device, &MODULE).unwrap() }
+    }
+
+    #[test]
+    fn base_pipeline_derivatives_not_allowed() {
+        let (device, _) = gfx_dev_and_queue!();
+        let module = empty_module(device.clone());
+
+        let base_pipeline = ComputePipeline::new(
+            device.clone(),
+            module.entry_point("main").unwrap(),
+            &(),
+            None,
+            |_| {},
+        )
+        .unwrap();
+
+        let result = ComputePipeline::with_create_info(
+            device,
+            module.entry_point("main").unwrap(),
+            &(),
+            base_pipeline.layout().clone(),
+            ComputePipelineCreateInfo {
+                base_pipeline: Some(base_pipeline),
+                ..Default::default()
+            },
+        );
+
+        assert!(matches!(
+            result,
+            Err(ComputePipelineCreationError::BasePipelineDerivativesNotAllowed)
+        ));
+    }
+
+    #[test]
+    fn fail_on_pipeline_compile_required_feature_missing() {
+        let (device, _) = gfx_dev_and_queue!();
+        let module = empty_module(device.clone());
+
+        let pipeline = ComputePipeline::new(
+            device.clone(),
+            module.entry_point("main").unwrap(),
+            &(),
+            None,
+            |_| {},
+        )
+        .unwrap();
+
+        let result = ComputePipeline::with_create_info(
+            device,
+            module.entry_point("main").unwrap(),
+            &(),
+            pipeline.layout().clone(),
+            ComputePipelineCreateInfo {
+                flags: PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED,
+                ..Default::default()
+            },
+        );
+
+        assert!(matches!(
+            result,
+            Err(ComputePipelineCreationError::RequirementNotMet { .. })
+        ));
+    }
 }
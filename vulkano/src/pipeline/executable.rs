@@ -0,0 +1,570 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Information about the executables that a pipeline was compiled into.
+//!
+//! When a pipeline is created, the implementation compiles its shaders into one or more
+//! *executables*. Which shader stages end up in which executable is implementation-dependent.
+//! For each executable, the implementation can report statistics, such as the number of
+//! registers used, and internal representations, such as the disassembled machine code. This is
+//! useful for debugging and profiling tools.
+//!
+//! Querying this information requires the
+//! [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info) feature to be
+//! enabled on the device. Statistics and internal representations are only available if the
+//! pipeline was created with [`PipelineCreateFlags::CAPTURE_STATISTICS`] and
+//! [`PipelineCreateFlags::CAPTURE_INTERNAL_REPRESENTATIONS`] respectively.
+
+use super::PipelineCreateFlags;
+use crate::{
+    device::{Device, DeviceOwned},
+    shader::ShaderStages,
+    OomError, RequiresOneOf, VulkanError, VulkanObject,
+};
+use std::{
+    error::Error,
+    ffi::CStr,
+    fmt::{Display, Error as FmtError, Formatter},
+    os::raw::c_char,
+    ptr,
+};
+
+/// Properties of a pipeline executable.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct PipelineExecutableProperties {
+    /// The shader stages that were compiled into the executable.
+    pub stages: ShaderStages,
+
+    /// A short human-readable name for the executable.
+    pub name: String,
+
+    /// A human-readable description of the executable.
+    pub description: String,
+
+    /// The subgroup size that the executable is dispatched with.
+    pub subgroup_size: u32,
+}
+
+impl From<ash::vk::PipelineExecutablePropertiesKHR> for PipelineExecutableProperties {
+    #[inline]
+    fn from(val: ash::vk::PipelineExecutablePropertiesKHR) -> Self {
+        Self {
+            stages: val.stages.into(),
+            name: string_from_array(&val.name),
+            description: string_from_array(&val.description),
+            subgroup_size: val.subgroup_size,
+        }
+    }
+}
+
+/// A statistic about a pipeline executable.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct PipelineExecutableStatistic {
+    /// A short human-readable name for the statistic.
+    pub name: String,
+
+    /// A human-readable description of the statistic.
+    pub description: String,
+
+    /// The value of the statistic.
+    pub value: PipelineExecutableStatisticValue,
+}
+
+impl From<ash::vk::PipelineExecutableStatisticKHR> for PipelineExecutableStatistic {
+    #[inline]
+    fn from(val: ash::vk::PipelineExecutableStatisticKHR) -> Self {
+        let value = unsafe {
+            match val.format {
+                ash::vk::PipelineExecutableStatisticFormatKHR::BOOL32 => {
+                    PipelineExecutableStatisticValue::Bool(val.value.b32 != ash::vk::FALSE)
+                }
+                ash::vk::PipelineExecutableStatisticFormatKHR::INT64 => {
+                    PipelineExecutableStatisticValue::Int(val.value.i64)
+                }
+                ash::vk::PipelineExecutableStatisticFormatKHR::UINT64 => {
+                    PipelineExecutableStatisticValue::Uint(val.value.u64)
+                }
+                ash::vk::PipelineExecutableStatisticFormatKHR::FLOAT64 => {
+                    PipelineExecutableStatisticValue::Float(val.value.f64)
+                }
+                _ => PipelineExecutableStatisticValue::Unknown,
+            }
+        };
+
+        Self {
+            name: string_from_array(&val.name),
+            description: string_from_array(&val.description),
+            value,
+        }
+    }
+}
+
+/// The value of a pipeline executable statistic.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum PipelineExecutableStatisticValue {
+    Bool(bool),
+    Int(i64),
+    Uint(u64),
+    Float(f64),
+
+    /// The implementation returned the value in a format that vulkano doesn't know about.
+    Unknown,
+}
+
+/// An internal representation of a pipeline executable, such as an intermediate representation
+/// or the final machine code.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct PipelineExecutableInternalRepresentation {
+    /// A short human-readable name for the internal representation.
+    pub name: String,
+
+    /// A human-readable description of the internal representation.
+    pub description: String,
+
+    /// Whether `data` contains null-terminated text.
+    pub is_text: bool,
+
+    /// The data of the internal representation.
+    pub data: Vec<u8>,
+}
+
+impl PipelineExecutableInternalRepresentation {
+    /// If the internal representation is text, returns it as a string, without the null
+    /// terminator.
+    ///
+    /// Returns `None` if the internal representation is not text, or not valid UTF-8.
+    #[inline]
+    pub fn text(&self) -> Option<&str> {
+        if !self.is_text {
+            return None;
+        }
+
+        let len = self
+            .data
+            .iter()
+            .position(|&byte| byte == 0)
+            .unwrap_or(self.data.len());
+
+        std::str::from_utf8(&self.data[..len]).ok()
+    }
+}
+
+// Converts a fixed-size, null-terminated string returned by Vulkan into a `String`.
+fn string_from_array(array: &[c_char]) -> String {
+    unsafe {
+        CStr::from_ptr(array.as_ptr())
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+fn validate_feature(
+    device: &Device,
+    required_for: &'static str,
+) -> Result<(), PipelineExecutableError> {
+    if !device.enabled_features().pipeline_executable_info {
+        return Err(PipelineExecutableError::RequirementNotMet {
+            required_for,
+            requires_one_of: RequiresOneOf {
+                features: &["pipeline_executable_info"],
+                ..Default::default()
+            },
+        });
+    }
+
+    Ok(())
+}
+
+fn validate_executable_index<P>(
+    pipeline: &P,
+    executable_index: u32,
+) -> Result<(), PipelineExecutableError>
+where
+    P: DeviceOwned + VulkanObject<Handle = ash::vk::Pipeline>,
+{
+    let executable_count = unsafe { executable_properties_unchecked(pipeline)? }.len() as u32;
+
+    // VUID-VkPipelineExecutableInfoKHR-executableIndex-03275
+    if executable_index >= executable_count {
+        return Err(PipelineExecutableError::ExecutableIndexOutOfRange {
+            executable_index,
+            executable_count,
+        });
+    }
+
+    Ok(())
+}
+
+pub(crate) fn executable_properties<P>(
+    pipeline: &P,
+) -> Result<Vec<PipelineExecutableProperties>, PipelineExecutableError>
+where
+    P: DeviceOwned + VulkanObject<Handle = ash::vk::Pipeline>,
+{
+    // VUID-vkGetPipelineExecutablePropertiesKHR-pipelineExecutableInfo-03270
+    validate_feature(pipeline.device(), "`executable_properties` is called")?;
+
+    unsafe { Ok(executable_properties_unchecked(pipeline)?) }
+}
+
+unsafe fn executable_properties_unchecked<P>(
+    pipeline: &P,
+) -> Result<Vec<PipelineExecutableProperties>, VulkanError>
+where
+    P: DeviceOwned + VulkanObject<Handle = ash::vk::Pipeline>,
+{
+    let device = pipeline.device();
+    let fns = device.fns();
+    let info = ash::vk::PipelineInfoKHR {
+        pipeline: pipeline.handle(),
+        ..Default::default()
+    };
+
+    loop {
+        let mut count = 0;
+        (fns.khr_pipeline_executable_properties
+            .get_pipeline_executable_properties_khr)(
+            device.handle(),
+            &info,
+            &mut count,
+            ptr::null_mut(),
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        let mut output = vec![ash::vk::PipelineExecutablePropertiesKHR::default(); count as usize];
+        let result = (fns
+            .khr_pipeline_executable_properties
+            .get_pipeline_executable_properties_khr)(
+            device.handle(),
+            &info,
+            &mut count,
+            output.as_mut_ptr(),
+        );
+
+        match result {
+            ash::vk::Result::SUCCESS => {
+                output.truncate(count as usize);
+                return Ok(output.into_iter().map(Into::into).collect());
+            }
+            ash::vk::Result::INCOMPLETE => (),
+            err => return Err(VulkanError::from(err)),
+        }
+    }
+}
+
+pub(crate) fn executable_statistics<P>(
+    pipeline: &P,
+    flags: PipelineCreateFlags,
+    executable_index: u32,
+) -> Result<Vec<PipelineExecutableStatistic>, PipelineExecutableError>
+where
+    P: DeviceOwned + VulkanObject<Handle = ash::vk::Pipeline>,
+{
+    // VUID-vkGetPipelineExecutableStatisticsKHR-pipelineExecutableInfo-03272
+    validate_feature(pipeline.device(), "`executable_statistics` is called")?;
+
+    // VUID-vkGetPipelineExecutableStatisticsKHR-pipeline-03274
+    if !flags.intersects(PipelineCreateFlags::CAPTURE_STATISTICS) {
+        return Err(PipelineExecutableError::StatisticsNotCaptured);
+    }
+
+    validate_executable_index(pipeline, executable_index)?;
+
+    unsafe {
+        let device = pipeline.device();
+        let fns = device.fns();
+        let info = ash::vk::PipelineExecutableInfoKHR {
+            pipeline: pipeline.handle(),
+            executable_index,
+            ..Default::default()
+        };
+
+        loop {
+            let mut count = 0;
+            (fns.khr_pipeline_executable_properties
+                .get_pipeline_executable_statistics_khr)(
+                device.handle(),
+                &info,
+                &mut count,
+                ptr::null_mut(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+
+            let mut output =
+                vec![ash::vk::PipelineExecutableStatisticKHR::default(); count as usize];
+            let result = (fns
+                .khr_pipeline_executable_properties
+                .get_pipeline_executable_statistics_khr)(
+                device.handle(),
+                &info,
+                &mut count,
+                output.as_mut_ptr(),
+            );
+
+            match result {
+                ash::vk::Result::SUCCESS => {
+                    output.truncate(count as usize);
+                    return Ok(output.into_iter().map(Into::into).collect());
+                }
+                ash::vk::Result::INCOMPLETE => (),
+                err => return Err(VulkanError::from(err).into()),
+            }
+        }
+    }
+}
+
+pub(crate) fn executable_internal_representations<P>(
+    pipeline: &P,
+    flags: PipelineCreateFlags,
+    executable_index: u32,
+) -> Result<Vec<PipelineExecutableInternalRepresentation>, PipelineExecutableError>
+where
+    P: DeviceOwned + VulkanObject<Handle = ash::vk::Pipeline>,
+{
+    // VUID-vkGetPipelineExecutableInternalRepresentationsKHR-pipelineExecutableInfo-03276
+    validate_feature(
+        pipeline.device(),
+        "`executable_internal_representations` is called",
+    )?;
+
+    // VUID-vkGetPipelineExecutableInternalRepresentationsKHR-pipeline-03278
+    if !flags.intersects(PipelineCreateFlags::CAPTURE_INTERNAL_REPRESENTATIONS) {
+        return Err(PipelineExecutableError::InternalRepresentationsNotCaptured);
+    }
+
+    validate_executable_index(pipeline, executable_index)?;
+
+    unsafe {
+        let device = pipeline.device();
+        let fns = device.fns();
+        let info = ash::vk::PipelineExecutableInfoKHR {
+            pipeline: pipeline.handle(),
+            executable_index,
+            ..Default::default()
+        };
+
+        loop {
+            let mut count = 0;
+            (fns.khr_pipeline_executable_properties
+                .get_pipeline_executable_internal_representations_khr)(
+                device.handle(),
+                &info,
+                &mut count,
+                ptr::null_mut(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+
+            // First query the size of the data of each representation, then the data itself.
+            let mut output = vec![
+                ash::vk::PipelineExecutableInternalRepresentationKHR::default();
+                count as usize
+            ];
+            let result = (fns
+                .khr_pipeline_executable_properties
+                .get_pipeline_executable_internal_representations_khr)(
+                device.handle(),
+                &info,
+                &mut count,
+                output.as_mut_ptr(),
+            );
+
+            match result {
+                ash::vk::Result::SUCCESS => output.truncate(count as usize),
+                ash::vk::Result::INCOMPLETE => continue,
+                err => return Err(VulkanError::from(err).into()),
+            }
+
+            let mut data: Vec<Vec<u8>> = output
+                .iter()
+                .map(|representation| vec![0; representation.data_size])
+                .collect();
+
+            for (representation, data) in output.iter_mut().zip(&mut data) {
+                representation.p_data = data.as_mut_ptr() as *mut _;
+            }
+
+            let result = (fns
+                .khr_pipeline_executable_properties
+                .get_pipeline_executable_internal_representations_khr)(
+                device.handle(),
+                &info,
+                &mut count,
+                output.as_mut_ptr(),
+            );
+
+            match result {
+                ash::vk::Result::SUCCESS => {
+                    return Ok(output
+                        .into_iter()
+                        .zip(data)
+                        .map(|(representation, mut data)| {
+                            data.truncate(representation.data_size);
+
+                            PipelineExecutableInternalRepresentation {
+                                name: string_from_array(&representation.name),
+                                description: string_from_array(&representation.description),
+                                is_text: representation.is_text != ash::vk::FALSE,
+                                data,
+                            }
+                        })
+                        .collect());
+                }
+                ash::vk::Result::INCOMPLETE => (),
+                err => return Err(VulkanError::from(err).into()),
+            }
+        }
+    }
+}
+
+/// Error that can happen when querying information about pipeline executables.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PipelineExecutableError {
+    /// Not enough memory available.
+    OomError(OomError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// The provided `executable_index` is not less than the number of executables in the
+    /// pipeline.
+    ExecutableIndexOutOfRange {
+        executable_index: u32,
+        executable_count: u32,
+    },
+
+    /// The pipeline was not created with `PipelineCreateFlags::CAPTURE_STATISTICS`.
+    StatisticsNotCaptured,
+
+    /// The pipeline was not created with `PipelineCreateFlags::CAPTURE_INTERNAL_REPRESENTATIONS`.
+    InternalRepresentationsNotCaptured,
+}
+
+impl Error for PipelineExecutableError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for PipelineExecutableError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::ExecutableIndexOutOfRange {
+                executable_index,
+                executable_count,
+            } => write!(
+                f,
+                "the provided `executable_index` ({}) is not less than the number of executables \
+                in the pipeline ({})",
+                executable_index, executable_count,
+            ),
+            Self::StatisticsNotCaptured => write!(
+                f,
+                "the pipeline was not created with `PipelineCreateFlags::CAPTURE_STATISTICS`",
+            ),
+            Self::InternalRepresentationsNotCaptured => write!(
+                f,
+                "the pipeline was not created with \
+                `PipelineCreateFlags::CAPTURE_INTERNAL_REPRESENTATIONS`",
+            ),
+        }
+    }
+}
+
+impl From<VulkanError> for PipelineExecutableError {
+    fn from(err: VulkanError) -> Self {
+        match err {
+            e @ VulkanError::OutOfHostMemory | e @ VulkanError::OutOfDeviceMemory => {
+                Self::OomError(e.into())
+            }
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        PipelineExecutableInternalRepresentation, PipelineExecutableStatistic,
+        PipelineExecutableStatisticValue,
+    };
+
+    #[test]
+    fn internal_representation_text() {
+        let representation = PipelineExecutableInternalRepresentation {
+            name: String::new(),
+            description: String::new(),
+            is_text: true,
+            data: b"mov r0, r1\0\0".to_vec(),
+        };
+        assert_eq!(representation.text(), Some("mov r0, r1"));
+
+        let representation = PipelineExecutableInternalRepresentation {
+            is_text: false,
+            ..representation
+        };
+        assert_eq!(representation.text(), None);
+    }
+
+    #[test]
+    fn statistic_from_vulkan() {
+        let mut statistic_vk = ash::vk::PipelineExecutableStatisticKHR {
+            format: ash::vk::PipelineExecutableStatisticFormatKHR::UINT64,
+            value: ash::vk::PipelineExecutableStatisticValueKHR { u64: 42 },
+            ..Default::default()
+        };
+
+        for (dst, &src) in statistic_vk.name.iter_mut().zip(b"Registers\0") {
+            *dst = src as _;
+        }
+
+        let statistic = PipelineExecutableStatistic::from(statistic_vk);
+        assert_eq!(statistic.name, "Registers");
+        assert_eq!(statistic.description, "");
+        assert_eq!(statistic.value, PipelineExecutableStatisticValue::Uint(42));
+
+        let statistic =
+            PipelineExecutableStatistic::from(ash::vk::PipelineExecutableStatisticKHR {
+                format: ash::vk::PipelineExecutableStatisticFormatKHR::BOOL32,
+                value: ash::vk::PipelineExecutableStatisticValueKHR { b32: ash::vk::TRUE },
+                ..Default::default()
+            });
+        assert_eq!(
+            statistic.value,
+            PipelineExecutableStatisticValue::Bool(true)
+        );
+
+        let statistic =
+            PipelineExecutableStatistic::from(ash::vk::PipelineExecutableStatisticKHR {
+                format: ash::vk::PipelineExecutableStatisticFormatKHR::from_raw(0x7fff),
+                ..Default::default()
+            });
+        assert_eq!(statistic.value, PipelineExecutableStatisticValue::Unknown);
+    }
+}
@@ -29,7 +29,8 @@ use super::{
         VertexInputBindingDescription, VertexInputState,
     },
     viewport::{Scissor, Viewport, ViewportState},
-    GraphicsPipeline, GraphicsPipelineCreationError,
+    GraphicsPipeline, GraphicsPipelineCreationError, GraphicsPipelineLibrary,
+    GraphicsPipelineLibraryCreateInfo, GraphicsPipelineLibraryFlags,
 };
 use crate::{
     descriptor_set::layout::{DescriptorSetLayout, DescriptorSetLayoutCreateInfo},
@@ -44,16 +45,18 @@ use crate::{
             vertex_input::VertexInputRate,
         },
         layout::{PipelineLayoutCreateInfo, PushConstantRange},
-        DynamicState, PartialStateMode, PipelineLayout, StateMode,
+        DynamicState, PartialStateMode, PipelineCreateFlags, PipelineCreationFeedback,
+        PipelineLayout, StateMode,
     },
     shader::{
         DescriptorBindingRequirements, EntryPoint, FragmentShaderExecution, FragmentTestsStages,
-        ShaderExecution, ShaderStage, SpecializationConstants, SpecializationMapEntry,
+        ShaderExecution, ShaderInterface, ShaderStage, SpecializationConstants,
+        SpecializationMapEntry,
     },
     DeviceSize, RequiresOneOf, Version, VulkanError, VulkanObject,
 };
 use ahash::HashMap;
-use smallvec::SmallVec;
+use smallvec::{smallvec, SmallVec};
 use std::{
     collections::hash_map::Entry,
     mem::{size_of_val, MaybeUninit},
@@ -63,16 +66,37 @@ use std::{
 
 /// Prototype for a `GraphicsPipeline`.
 #[derive(Debug)]
-pub struct GraphicsPipelineBuilder<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss> {
+pub struct GraphicsPipelineBuilder<
+    'vs,
+    'tcs,
+    'tes,
+    'gs,
+    'fs,
+    'ts,
+    'ms,
+    Vdef,
+    Vss,
+    Tcss,
+    Tess,
+    Gss,
+    Fss,
+    Tss,
+    Mss,
+> {
     render_pass: Option<PipelineRenderPassType>,
     cache: Option<Arc<PipelineCache>>,
+    flags: PipelineCreateFlags,
+    base_pipeline: Option<Arc<GraphicsPipeline>>,
 
     vertex_shader: Option<(EntryPoint<'vs>, Vss)>,
     tessellation_shaders: Option<TessellationShaders<'tcs, 'tes, Tcss, Tess>>,
     geometry_shader: Option<(EntryPoint<'gs>, Gss)>,
     fragment_shader: Option<(EntryPoint<'fs>, Fss)>,
+    task_shader: Option<(EntryPoint<'ts>, Tss)>,
+    mesh_shader: Option<(EntryPoint<'ms>, Mss)>,
 
     vertex_input_state: Vdef,
+    vertex_input_state_dynamic: bool,
     input_assembly_state: InputAssemblyState,
     tessellation_state: TessellationState,
     viewport_state: ViewportState,
@@ -97,12 +121,16 @@ impl
         'static,
         'static,
         'static,
+        'static,
+        'static,
         VertexInputState,
         (),
         (),
         (),
         (),
         (),
+        (),
+        (),
     >
 {
     /// Builds a new empty builder.
@@ -110,13 +138,18 @@ impl
         GraphicsPipelineBuilder {
             render_pass: None,
             cache: None,
+            flags: PipelineCreateFlags::empty(),
+            base_pipeline: None,
 
             vertex_shader: None,
             tessellation_shaders: None,
             geometry_shader: None,
             fragment_shader: None,
+            task_shader: None,
+            mesh_shader: None,
 
             vertex_input_state: Default::default(),
+            vertex_input_state_dynamic: false,
             input_assembly_state: Default::default(),
             tessellation_state: Default::default(),
             viewport_state: Default::default(),
@@ -141,8 +174,24 @@ struct Has {
     color_blend_state: bool,
 }
 
-impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
-    GraphicsPipelineBuilder<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
+impl<'vs, 'tcs, 'tes, 'gs, 'fs, 'ts, 'ms, Vdef, Vss, Tcss, Tess, Gss, Fss, Tss, Mss>
+    GraphicsPipelineBuilder<
+        'vs,
+        'tcs,
+        'tes,
+        'gs,
+        'fs,
+        'ts,
+        'ms,
+        Vdef,
+        Vss,
+        Tcss,
+        Tess,
+        Gss,
+        Fss,
+        Tss,
+        Mss,
+    >
 where
     Vdef: VertexDefinition,
     Vss: SpecializationConstants,
@@ -150,6 +199,8 @@ where
     Tess: SpecializationConstants,
     Gss: SpecializationConstants,
     Fss: SpecializationConstants,
+    Tss: SpecializationConstants,
+    Mss: SpecializationConstants,
 {
     /// Builds the graphics pipeline, using an inferred a pipeline layout.
     #[inline]
@@ -172,11 +223,13 @@ where
         F: FnOnce(&mut [DescriptorSetLayoutCreateInfo]),
     {
         let (set_layout_create_infos, push_constant_ranges) = {
-            let stages: SmallVec<[&EntryPoint<'_>; 5]> = [
+            let stages: SmallVec<[&EntryPoint<'_>; 7]> = [
                 self.vertex_shader.as_ref().map(|s| &s.0),
                 self.tessellation_shaders.as_ref().map(|s| &s.control.0),
                 self.tessellation_shaders.as_ref().map(|s| &s.evaluation.0),
                 self.geometry_shader.as_ref().map(|s| &s.0),
+                self.task_shader.as_ref().map(|s| &s.0),
+                self.mesh_shader.as_ref().map(|s| &s.0),
                 self.fragment_shader.as_ref().map(|s| &s.0),
             ]
             .into_iter()
@@ -272,111 +325,47 @@ where
         device: Arc<Device>,
         pipeline_layout: Arc<PipelineLayout>,
     ) -> Result<Arc<GraphicsPipeline>, GraphicsPipelineCreationError> {
-        let vertex_input_state = self
-            .vertex_input_state
-            .definition(self.vertex_shader.as_ref().unwrap().0.input_interface())?;
-
-        // If there is one element, duplicate it for all attachments.
-        // TODO: this is undocumented and only exists for compatibility with some of the
-        // deprecated builder methods. Remove it when those methods are gone.
-        if self.color_blend_state.attachments.len() == 1 {
-            let color_attachment_count =
-                match self.render_pass.as_ref().expect("Missing render pass") {
-                    PipelineRenderPassType::BeginRenderPass(subpass) => {
-                        subpass.subpass_desc().color_attachments.len()
-                    }
-                    PipelineRenderPassType::BeginRendering(rendering_info) => {
-                        rendering_info.color_attachment_formats.len()
-                    }
-                };
-            let element = self.color_blend_state.attachments.pop().unwrap();
-            self.color_blend_state
-                .attachments
-                .extend(std::iter::repeat(element).take(color_attachment_count));
-        }
-
-        let has = {
-            let Self {
-                render_pass,
-                cache: _,
-
-                vertex_shader,
-                tessellation_shaders,
-                geometry_shader: _,
-                fragment_shader: _,
-
-                vertex_input_state: _,
-                input_assembly_state: _,
-                tessellation_state: _,
-                viewport_state: _,
-                discard_rectangle_state: _,
-                rasterization_state,
-                multisample_state: _,
-                depth_stencil_state: _,
-                color_blend_state: _,
-            } = &self;
-
-            let render_pass = render_pass.as_ref().expect("Missing render pass");
-
-            let has_pre_rasterization_shader_state = true;
-            let has_vertex_input_state = vertex_shader.is_some();
-            let has_fragment_shader_state =
-                rasterization_state.rasterizer_discard_enable != StateMode::Fixed(true);
-            let has_fragment_output_state =
-                rasterization_state.rasterizer_discard_enable != StateMode::Fixed(true);
-
-            let has_tessellation_state =
-                has_pre_rasterization_shader_state && tessellation_shaders.is_some();
-            let has_viewport_state =
-                has_pre_rasterization_shader_state && has_fragment_shader_state;
-            let has_depth_stencil_state = has_fragment_shader_state
-                && match render_pass {
-                    PipelineRenderPassType::BeginRenderPass(subpass) => {
-                        subpass.subpass_desc().depth_stencil_attachment.is_some()
-                    }
-                    PipelineRenderPassType::BeginRendering(rendering_info) => {
-                        !has_fragment_output_state
-                            || rendering_info.depth_attachment_format.is_some()
-                            || rendering_info.stencil_attachment_format.is_some()
-                    }
-                };
-            let has_color_blend_state = has_fragment_output_state
-                && match render_pass {
-                    PipelineRenderPassType::BeginRenderPass(subpass) => {
-                        !subpass.subpass_desc().color_attachments.is_empty()
-                    }
-                    PipelineRenderPassType::BeginRendering(rendering_info) => {
-                        !rendering_info.color_attachment_formats.is_empty()
-                    }
-                };
-
-            Has {
-                vertex_input_state: has_vertex_input_state,
-                pre_rasterization_shader_state: has_pre_rasterization_shader_state,
-                tessellation_state: has_tessellation_state,
-                viewport_state: has_viewport_state,
-                fragment_shader_state: has_fragment_shader_state,
-                depth_stencil_state: has_depth_stencil_state,
-                fragment_output_state: has_fragment_output_state,
-                color_blend_state: has_color_blend_state,
-            }
+        // Mesh shading pipelines have no vertex input state, and dynamic vertex input is checked
+        // against the vertex shader when drawing.
+        let vertex_input_state = match (&self.vertex_shader, &self.mesh_shader) {
+            (None, Some(_)) => VertexInputState::new(),
+            _ if self.vertex_input_state_dynamic => VertexInputState::new(),
+            (vertex_shader, _) => self
+                .vertex_input_state
+                .definition(vertex_shader.as_ref().unwrap().0.input_interface())?,
         };
 
+        self.expand_color_blend_attachments();
+        let has = self.has(None);
+
         self.validate_create(&device, &pipeline_layout, &vertex_input_state, has)?;
 
-        let (handle, descriptor_requirements, dynamic_state, shaders, fragment_tests_stages) =
-            unsafe { self.record_create(&device, &pipeline_layout, &vertex_input_state, has)? };
+        let (
+            handle,
+            descriptor_requirements,
+            dynamic_state,
+            shaders,
+            fragment_tests_stages,
+            (creation_feedback, stage_creation_feedback),
+        ) = unsafe {
+            self.record_create(&device, &pipeline_layout, &vertex_input_state, has, None)?
+        };
 
         let Self {
             mut render_pass,
             cache: _,
+            flags,
+            base_pipeline: _,
 
-            vertex_shader: _,
-            tessellation_shaders: _,
-            geometry_shader: _,
+            vertex_shader,
+            tessellation_shaders,
+            geometry_shader,
             fragment_shader: _,
+            task_shader: _,
+            mesh_shader: _,
 
             vertex_input_state: _,
+            vertex_input_state_dynamic: _,
             input_assembly_state,
             tessellation_state,
             viewport_state,
@@ -394,6 +383,23 @@ where
             .map(|x| x + 1)
             .unwrap_or(0);
 
+        // Transform feedback captures the outputs of the last pre-rasterization shader stage.
+        let transform_feedback_outputs = geometry_shader
+            .as_ref()
+            .map(|(entry_point, _)| entry_point)
+            .or_else(|| {
+                tessellation_shaders
+                    .as_ref()
+                    .map(|shaders| &shaders.evaluation.0)
+            })
+            .or_else(|| vertex_shader.as_ref().map(|(entry_point, _)| entry_point))
+            .and_then(|entry_point| entry_point.transform_feedback_outputs())
+            .map(|outputs| outputs.to_vec());
+
+        let vertex_shader_input_interface = vertex_shader
+            .as_ref()
+            .map(|(entry_point, _)| entry_point.input_interface().clone());
+
         Ok(Arc::new(GraphicsPipeline {
             handle,
             device,
@@ -405,9 +411,11 @@ where
             descriptor_binding_requirements: descriptor_requirements,
             num_used_descriptor_sets,
             fragment_tests_stages,
+            transform_feedback_outputs,
+            vertex_shader_input_interface,
 
-            vertex_input_state, // Can be None if there's a mesh shader, but we don't support that yet
-            input_assembly_state, // Can be None if there's a mesh shader, but we don't support that yet
+            vertex_input_state,   // Empty if there's a mesh shader or it's dynamic
+            input_assembly_state, // Ignored if there's a mesh shader
             tessellation_state: has.tessellation_state.then_some(tessellation_state),
             viewport_state: has.viewport_state.then_some(viewport_state),
             discard_rectangle_state: has
@@ -417,10 +425,283 @@ where
             multisample_state: has.fragment_output_state.then_some(multisample_state),
             depth_stencil_state: has.depth_stencil_state.then_some(depth_stencil_state),
             color_blend_state: has.color_blend_state.then_some(color_blend_state),
+            flags,
+            dynamic_state,
+            creation_feedback,
+            stage_creation_feedback,
+            libraries: Vec::new(),
+        }))
+    }
+
+    /// Builds a graphics pipeline library, containing only the parts of the pipeline given by
+    /// `create_info.flags`.
+    ///
+    /// Only the shaders and state belonging to those parts are used; everything else that was
+    /// set on the builder is ignored. A render pass must always be provided, even if the library
+    /// contains only the vertex input interface.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.flags` is empty.
+    /// - Panics if no render pass was provided.
+    pub fn build_library(
+        mut self,
+        device: Arc<Device>,
+        pipeline_layout: Arc<PipelineLayout>,
+        create_info: GraphicsPipelineLibraryCreateInfo,
+    ) -> Result<Arc<GraphicsPipelineLibrary>, GraphicsPipelineCreationError> {
+        create_info.validate(&device)?;
+
+        let flags = create_info.flags;
+
+        // The vertex shader may be provided by a different library, in which case the
+        // attributes are checked against it when linking.
+        let vertex_input_state = if flags
+            .intersects(GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE)
+            && !self.vertex_input_state_dynamic
+        {
+            match &self.vertex_shader {
+                Some((entry_point, _)) => self
+                    .vertex_input_state
+                    .definition(entry_point.input_interface())?,
+                None => self
+                    .vertex_input_state
+                    .definition(&ShaderInterface::empty())?,
+            }
+        } else {
+            VertexInputState::new()
+        };
+
+        self.expand_color_blend_attachments();
+        let has = self.has(Some(flags));
+
+        self.validate_create(&device, &pipeline_layout, &vertex_input_state, has)?;
+
+        let (
+            handle,
+            descriptor_binding_requirements,
+            dynamic_state,
+            shaders,
+            fragment_tests_stages,
+            _,
+        ) = unsafe {
+            self.record_create(
+                &device,
+                &pipeline_layout,
+                &vertex_input_state,
+                has,
+                Some(&create_info),
+            )?
+        };
+
+        let Self {
+            mut render_pass,
+            cache: _,
+            flags: _,
+            base_pipeline: _,
+
+            vertex_shader,
+            tessellation_shaders,
+            geometry_shader,
+            fragment_shader,
+            task_shader: _,
+            mesh_shader,
+
+            vertex_input_state: _,
+            vertex_input_state_dynamic: _,
+            input_assembly_state,
+            tessellation_state,
+            viewport_state,
+            discard_rectangle_state,
+            rasterization_state,
+            multisample_state,
+            depth_stencil_state,
+            color_blend_state,
+        } = self;
+
+        let pre_rasterization = has.pre_rasterization_shader_state;
+
+        let vertex_shader_input_interface = vertex_shader
+            .as_ref()
+            .filter(|_| pre_rasterization)
+            .map(|(entry_point, _)| entry_point.input_interface().clone());
+
+        // The last pre-rasterization shader stage, whose outputs are passed to the fragment
+        // shader.
+        let last_pre_rasterization_stage = mesh_shader
+            .as_ref()
+            .map(|(entry_point, _)| entry_point)
+            .or_else(|| geometry_shader.as_ref().map(|(entry_point, _)| entry_point))
+            .or_else(|| {
+                tessellation_shaders
+                    .as_ref()
+                    .map(|shaders| &shaders.evaluation.0)
+            })
+            .or_else(|| vertex_shader.as_ref().map(|(entry_point, _)| entry_point))
+            .filter(|_| pre_rasterization);
+
+        let geometry_shader_input = geometry_shader
+            .as_ref()
+            .filter(|_| pre_rasterization)
+            .and_then(|(entry_point, _)| match entry_point.execution() {
+                ShaderExecution::Geometry(execution) => Some(execution.input),
+                _ => None,
+            });
+
+        let fragment_shader_input_interface = fragment_shader
+            .as_ref()
+            .filter(|_| has.fragment_shader_state)
+            .map(|(entry_point, _)| entry_point.input_interface().clone());
+
+        Ok(Arc::new(GraphicsPipelineLibrary {
+            handle,
+            device,
+            id: GraphicsPipelineLibrary::next_id(),
+            flags,
+            retain_link_time_optimization_info: create_info.retain_link_time_optimization_info,
+            layout: pipeline_layout,
+            render_pass: render_pass.take().expect("Missing render pass"),
+
+            shaders,
+            descriptor_binding_requirements,
+            fragment_tests_stages,
+            transform_feedback_outputs: last_pre_rasterization_stage
+                .filter(|_| mesh_shader.is_none())
+                .and_then(|entry_point| entry_point.transform_feedback_outputs())
+                .map(|outputs| outputs.to_vec()),
+
+            vertex_shader_input_interface,
+            pre_rasterization_output_interface: last_pre_rasterization_stage
+                .map(|entry_point| entry_point.output_interface().clone()),
+            fragment_shader_input_interface,
+            geometry_shader_input,
+
+            vertex_input_state: has.vertex_input_state.then_some(vertex_input_state),
+            input_assembly_state: has.vertex_input_state.then_some(input_assembly_state),
+            tessellation_state: has.tessellation_state.then_some(tessellation_state),
+            viewport_state: has.viewport_state.then_some(viewport_state),
+            discard_rectangle_state: pre_rasterization.then_some(discard_rectangle_state),
+            rasterization_state: pre_rasterization.then_some(rasterization_state),
+            multisample_state: (has.fragment_shader_state || has.fragment_output_state)
+                .then_some(multisample_state),
+            depth_stencil_state: has.depth_stencil_state.then_some(depth_stencil_state),
+            color_blend_state: has.color_blend_state.then_some(color_blend_state),
             dynamic_state,
         }))
     }
 
+    // If there is one element, duplicate it for all attachments.
+    // TODO: this is undocumented and only exists for compatibility with some of the
+    // deprecated builder methods. Remove it when those methods are gone.
+    fn expand_color_blend_attachments(&mut self) {
+        if self.color_blend_state.attachments.len() == 1 {
+            let color_attachment_count =
+                match self.render_pass.as_ref().expect("Missing render pass") {
+                    PipelineRenderPassType::BeginRenderPass(subpass) => {
+                        subpass.subpass_desc().color_attachments.len()
+                    }
+                    PipelineRenderPassType::BeginRendering(rendering_info) => {
+                        rendering_info.color_attachment_formats.len()
+                    }
+                };
+            let element = self.color_blend_state.attachments.pop().unwrap();
+            self.color_blend_state
+                .attachments
+                .extend(std::iter::repeat(element).take(color_attachment_count));
+        }
+    }
+
+    // Determines which parts of the pipeline state are provided when creating the pipeline. If
+    // `library_flags` is `Some`, a graphics pipeline library with those flags is being created.
+    fn has(&self, library_flags: Option<GraphicsPipelineLibraryFlags>) -> Has {
+        let Self {
+            render_pass,
+            cache: _,
+            flags: _,
+            base_pipeline: _,
+
+            vertex_shader,
+            tessellation_shaders,
+            geometry_shader: _,
+            fragment_shader: _,
+            task_shader: _,
+            mesh_shader: _,
+
+            vertex_input_state: _,
+            vertex_input_state_dynamic: _,
+            input_assembly_state: _,
+            tessellation_state: _,
+            viewport_state: _,
+            discard_rectangle_state: _,
+            rasterization_state,
+            multisample_state: _,
+            depth_stencil_state: _,
+            color_blend_state: _,
+        } = self;
+
+        let render_pass = render_pass.as_ref().expect("Missing render pass");
+
+        let has_rasterization =
+            rasterization_state.rasterizer_discard_enable != StateMode::Fixed(true);
+
+        let (
+            has_vertex_input_state,
+            has_pre_rasterization_shader_state,
+            has_fragment_shader_state,
+            has_fragment_output_state,
+        ) = match library_flags {
+            None => (
+                vertex_shader.is_some(),
+                true,
+                has_rasterization,
+                has_rasterization,
+            ),
+            // A library only contains the parts that it was requested to contain, regardless of
+            // whether the complete pipeline will need them.
+            Some(flags) => (
+                flags.intersects(GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE),
+                flags.intersects(GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS),
+                flags.intersects(GraphicsPipelineLibraryFlags::FRAGMENT_SHADER),
+                flags.intersects(GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE),
+            ),
+        };
+
+        let has_tessellation_state =
+            has_pre_rasterization_shader_state && tessellation_shaders.is_some();
+        let has_viewport_state = has_pre_rasterization_shader_state && has_rasterization;
+        let has_depth_stencil_state = has_fragment_shader_state
+            && match render_pass {
+                PipelineRenderPassType::BeginRenderPass(subpass) => {
+                    subpass.subpass_desc().depth_stencil_attachment.is_some()
+                }
+                PipelineRenderPassType::BeginRendering(rendering_info) => {
+                    !has_fragment_output_state
+                        || rendering_info.depth_attachment_format.is_some()
+                        || rendering_info.stencil_attachment_format.is_some()
+                }
+            };
+        let has_color_blend_state = has_fragment_output_state
+            && match render_pass {
+                PipelineRenderPassType::BeginRenderPass(subpass) => {
+                    !subpass.subpass_desc().color_attachments.is_empty()
+                }
+                PipelineRenderPassType::BeginRendering(rendering_info) => {
+                    !rendering_info.color_attachment_formats.is_empty()
+                }
+            };
+
+        Has {
+            vertex_input_state: has_vertex_input_state,
+            pre_rasterization_shader_state: has_pre_rasterization_shader_state,
+            tessellation_state: has_tessellation_state,
+            viewport_state: has_viewport_state,
+            fragment_shader_state: has_fragment_shader_state,
+            depth_stencil_state: has_depth_stencil_state,
+            fragment_output_state: has_fragment_output_state,
+            color_blend_state: has_color_blend_state,
+        }
+    }
+
     fn validate_create(
         &self,
         device: &Device,
@@ -434,13 +715,18 @@ where
         let Self {
             render_pass,
             cache: _,
+            flags,
+            base_pipeline,
 
             vertex_shader,
             tessellation_shaders,
             geometry_shader,
             fragment_shader,
+            task_shader,
+            mesh_shader,
 
             vertex_input_state: _,
+            vertex_input_state_dynamic,
             input_assembly_state,
             tessellation_state,
             viewport_state,
@@ -453,11 +739,45 @@ where
 
         let render_pass = render_pass.as_ref().expect("Missing render pass");
 
-        let mut shader_stages: SmallVec<[_; 5]> = SmallVec::new();
+        let mut shader_stages: SmallVec<[_; 7]> = SmallVec::new();
 
         // VUID-VkGraphicsPipelineCreateInfo-layout-01688
         // Checked at pipeline layout creation time.
 
+        /*
+            Flags
+        */
+
+        // VUID-VkGraphicsPipelineCreateInfo-flags-parameter
+        flags.validate_device(device)?;
+
+        // VUID-VkGraphicsPipelineCreateInfo-pipelineCreationCacheControl-02878
+        if flags.intersects(PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED)
+            && !device.enabled_features().pipeline_creation_cache_control
+        {
+            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                required_for:
+                    "`flags` contains `PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED`",
+                requires_one_of: RequiresOneOf {
+                    features: &["pipeline_creation_cache_control"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        if let Some(base_pipeline) = base_pipeline {
+            // VUID-VkGraphicsPipelineCreateInfo-flags-07984
+            assert_eq!(device, base_pipeline.device().as_ref());
+
+            // VUID-vkCreateGraphicsPipelines-flags-00720
+            if !base_pipeline
+                .flags()
+                .intersects(PipelineCreateFlags::ALLOW_DERIVATIVES)
+            {
+                return Err(GraphicsPipelineCreationError::BasePipelineDerivativesNotAllowed);
+            }
+        }
+
         /*
             Render pass
         */
@@ -602,8 +922,19 @@ where
 
         if has.vertex_input_state {
             // Vertex input state
-            // VUID-VkGraphicsPipelineCreateInfo-pVertexInputState-04910
-            {
+            if *vertex_input_state_dynamic {
+                // VUID-VkGraphicsPipelineCreateInfo-pDynamicStates-04807
+                if !device.enabled_features().vertex_input_dynamic_state {
+                    return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                        required_for: "the vertex input state is dynamic",
+                        requires_one_of: RequiresOneOf {
+                            features: &["vertex_input_dynamic_state"],
+                            ..Default::default()
+                        },
+                    });
+                }
+            } else {
+                // VUID-VkGraphicsPipelineCreateInfo-pVertexInputState-04910
                 let VertexInputState {
                     bindings,
                     attributes,
@@ -954,6 +1285,20 @@ where
         */
 
         if has.pre_rasterization_shader_state {
+            // VUID-VkGraphicsPipelineCreateInfo-pStages-02095
+            if mesh_shader.is_some() {
+                if vertex_shader.is_some()
+                    || tessellation_shaders.is_some()
+                    || geometry_shader.is_some()
+                {
+                    return Err(
+                        GraphicsPipelineCreationError::MeshShaderWithPrimitiveShadingStages,
+                    );
+                }
+            } else if task_shader.is_some() {
+                return Err(GraphicsPipelineCreationError::TaskShaderWithoutMeshShader);
+            }
+
             // Vertex shader
             if let Some((entry_point, specialization_data)) = vertex_shader {
                 shader_stages.push(ShaderStageInfo {
@@ -972,65 +1317,18 @@ where
                     _ => return Err(GraphicsPipelineCreationError::WrongShaderType),
                 }
 
-                // VUID?
-                // Check that the vertex input state contains attributes for all the shader's input
-                // variables.
-                for element in entry_point.input_interface().elements() {
-                    assert!(!element.ty.is_64bit); // TODO: implement
-                    let location_range =
-                        element.location..element.location + element.ty.num_locations();
-
-                    for location in location_range {
-                        let attribute_desc =
-                            match vertex_input_state.attributes.get(&location) {
-                                Some(attribute_desc) => attribute_desc,
-                                None => return Err(
-                                    GraphicsPipelineCreationError::VertexInputAttributeMissing {
-                                        location,
-                                    },
-                                ),
-                            };
-
-                        // TODO: Check component assignments too. Multiple variables can occupy the same
-                        // location but in different components.
-
-                        let shader_type = element.ty.to_format().type_color().unwrap();
-                        let attribute_type = attribute_desc.format.type_color().unwrap();
-
-                        if !matches!(
-                            (shader_type, attribute_type),
-                            (
-                                NumericType::SFLOAT
-                                    | NumericType::UFLOAT
-                                    | NumericType::SNORM
-                                    | NumericType::UNORM
-                                    | NumericType::SSCALED
-                                    | NumericType::USCALED
-                                    | NumericType::SRGB,
-                                NumericType::SFLOAT
-                                    | NumericType::UFLOAT
-                                    | NumericType::SNORM
-                                    | NumericType::UNORM
-                                    | NumericType::SSCALED
-                                    | NumericType::USCALED
-                                    | NumericType::SRGB,
-                            ) | (NumericType::SINT, NumericType::SINT)
-                                | (NumericType::UINT, NumericType::UINT)
-                        ) {
-                            return Err(
-                                GraphicsPipelineCreationError::VertexInputAttributeIncompatibleFormat {
-                                    location,
-                                    shader_type,
-                                    attribute_type,
-                                },
-                            );
-                        }
-                    }
+                // The vertex input state is provided by a different library if it's not here. If
+                // it's dynamic, it's checked against the shader when drawing.
+                if has.vertex_input_state && !vertex_input_state_dynamic {
+                    validate_vertex_input_attributes(
+                        entry_point.input_interface(),
+                        vertex_input_state,
+                    )?;
                 }
 
                 // TODO:
                 // VUID-VkPipelineShaderStageCreateInfo-stage-00712
-            } else {
+            } else if mesh_shader.is_none() {
                 // VUID-VkGraphicsPipelineCreateInfo-stage-02096
                 panic!("Missing vertex shader"); // TODO: return error
             }
@@ -1155,12 +1453,15 @@ where
                     _ => return Err(GraphicsPipelineCreationError::WrongShaderType),
                 };
 
-                if let PartialStateMode::Fixed(topology) = input_assembly_state.topology {
-                    // VUID-VkGraphicsPipelineCreateInfo-pStages-00738
-                    if !input.is_compatible_with(topology) {
-                        return Err(
-                            GraphicsPipelineCreationError::TopologyNotMatchingGeometryShader,
-                        );
+                // The input assembly state is provided by a different library if it's not here.
+                if has.vertex_input_state {
+                    if let PartialStateMode::Fixed(topology) = input_assembly_state.topology {
+                        // VUID-VkGraphicsPipelineCreateInfo-pStages-00738
+                        if !input.is_compatible_with(topology) {
+                            return Err(
+                                GraphicsPipelineCreationError::TopologyNotMatchingGeometryShader,
+                            );
+                        }
                     }
                 }
 
@@ -1194,6 +1495,94 @@ where
                 // VUID-VkGraphicsPipelineCreateInfo-pStages-00739
             }
 
+            // Task shader
+            if let Some((entry_point, specialization_data)) = task_shader {
+                shader_stages.push(ShaderStageInfo {
+                    entry_point,
+                    specialization_map_entries: Tss::descriptors(),
+                    _specialization_data: unsafe {
+                        std::slice::from_raw_parts(
+                            specialization_data as *const _ as *const u8,
+                            size_of_val(specialization_data),
+                        )
+                    },
+                });
+
+                // VUID-VkPipelineShaderStageCreateInfo-stage-02092
+                if !device.enabled_features().task_shader {
+                    return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                        required_for: "`task_shader` is provided",
+                        requires_one_of: RequiresOneOf {
+                            features: &["task_shader"],
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                match entry_point.execution() {
+                    ShaderExecution::Task => (),
+                    _ => return Err(GraphicsPipelineCreationError::WrongShaderType),
+                }
+            }
+
+            // Mesh shader
+            if let Some((entry_point, specialization_data)) = mesh_shader {
+                shader_stages.push(ShaderStageInfo {
+                    entry_point,
+                    specialization_map_entries: Mss::descriptors(),
+                    _specialization_data: unsafe {
+                        std::slice::from_raw_parts(
+                            specialization_data as *const _ as *const u8,
+                            size_of_val(specialization_data),
+                        )
+                    },
+                });
+
+                // VUID-VkPipelineShaderStageCreateInfo-stage-02091
+                if !device.enabled_features().mesh_shader {
+                    return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                        required_for: "`mesh_shader` is provided",
+                        requires_one_of: RequiresOneOf {
+                            features: &["mesh_shader"],
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                match entry_point.execution() {
+                    ShaderExecution::Mesh(_) => (),
+                    _ => return Err(GraphicsPipelineCreationError::WrongShaderType),
+                }
+
+                if !device.enabled_features().multiview_mesh_shader {
+                    let view_mask = match render_pass {
+                        PipelineRenderPassType::BeginRenderPass(subpass) => {
+                            subpass.render_pass().views_used()
+                        }
+                        PipelineRenderPassType::BeginRendering(rendering_info) => {
+                            rendering_info.view_mask
+                        }
+                    };
+
+                    // VUID-VkGraphicsPipelineCreateInfo-renderPass-07064
+                    // VUID-VkGraphicsPipelineCreateInfo-renderPass-07720
+                    if view_mask != 0 {
+                        return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                            required_for: "`mesh_shader` is provided and `render_pass` has a \
+                                subpass where `view_mask` is not `0`",
+                            requires_one_of: RequiresOneOf {
+                                features: &["multiview_mesh_shader"],
+                                ..Default::default()
+                            },
+                        });
+                    }
+                }
+
+                // TODO:
+                // VUID-RuntimeSpirv-MeshEXT-07115
+                // VUID-RuntimeSpirv-MeshEXT-07116
+            }
+
             // Rasterization state
             // VUID?
             {
@@ -1579,11 +1968,13 @@ where
             } = tessellation_state;
 
             // VUID-VkGraphicsPipelineCreateInfo-pStages-00736
-            if !matches!(
-                input_assembly_state.topology,
-                PartialStateMode::Dynamic(PrimitiveTopologyClass::Patch)
-                    | PartialStateMode::Fixed(PrimitiveTopology::PatchList)
-            ) {
+            if has.vertex_input_state
+                && !matches!(
+                    input_assembly_state.topology,
+                    PartialStateMode::Dynamic(PrimitiveTopologyClass::Patch)
+                        | PartialStateMode::Fixed(PrimitiveTopology::PatchList)
+                )
+            {
                 return Err(GraphicsPipelineCreationError::InvalidPrimitiveTopology);
             }
 
@@ -2172,7 +2563,8 @@ where
             Fragment output state
         */
 
-        if has.fragment_output_state {
+        // Libraries with fragment shader state also include the multisample state.
+        if has.fragment_shader_state || has.fragment_output_state {
             // Multisample state
             // VUID-VkGraphicsPipelineCreateInfo-rasterizerDiscardEnable-00751
             {
@@ -2539,6 +2931,7 @@ where
         pipeline_layout: &PipelineLayout,
         vertex_input_state: &VertexInputState,
         has: Has,
+        library_create_info: Option<&GraphicsPipelineLibraryCreateInfo>,
     ) -> Result<
         (
             ash::vk::Pipeline,
@@ -2546,19 +2939,25 @@ where
             HashMap<DynamicState, bool>,
             HashMap<ShaderStage, ()>,
             Option<FragmentTestsStages>,
+            CreationFeedback,
         ),
         GraphicsPipelineCreationError,
     > {
         let Self {
             render_pass,
             cache,
+            flags: create_flags,
+            base_pipeline,
 
             vertex_shader,
             tessellation_shaders,
             geometry_shader,
             fragment_shader,
+            task_shader,
+            mesh_shader,
 
             vertex_input_state: _,
+            vertex_input_state_dynamic,
             input_assembly_state,
             tessellation_state,
             viewport_state,
@@ -2636,7 +3035,7 @@ where
         if has.vertex_input_state {
             // Vertex input state
             {
-                dynamic_state.insert(DynamicState::VertexInput, false);
+                dynamic_state.insert(DynamicState::VertexInput, *vertex_input_state_dynamic);
 
                 let VertexInputState {
                     bindings,
@@ -2754,6 +3153,8 @@ where
         let mut tessellation_evaluation_shader_specialization_vk = None;
         let mut tessellation_state_vk = None;
         let mut geometry_shader_specialization_vk = None;
+        let mut task_shader_specialization_vk = None;
+        let mut mesh_shader_specialization_vk = None;
         let mut viewports_vk: SmallVec<[_; 2]> = SmallVec::new();
         let mut scissors_vk: SmallVec<[_; 2]> = SmallVec::new();
         let mut viewport_state_vk = None;
@@ -2880,16 +3281,92 @@ where
                 }
             }
 
-            // Geometry shader
-            if let Some((entry_point, specialization_data)) = geometry_shader {
-                let specialization_map_entries = Gss::descriptors();
+            // Geometry shader
+            if let Some((entry_point, specialization_data)) = geometry_shader {
+                let specialization_map_entries = Gss::descriptors();
+                let specialization_data = slice::from_raw_parts(
+                    specialization_data as *const _ as *const u8,
+                    size_of_val(specialization_data),
+                );
+
+                let specialization_info_vk =
+                    geometry_shader_specialization_vk.insert(ash::vk::SpecializationInfo {
+                        map_entry_count: specialization_map_entries.len() as u32,
+                        p_map_entries: specialization_map_entries.as_ptr() as *const _,
+                        data_size: specialization_data.len(),
+                        p_data: specialization_data.as_ptr() as *const _,
+                    });
+
+                for (loc, reqs) in entry_point.descriptor_binding_requirements() {
+                    match descriptor_binding_requirements.entry(loc) {
+                        Entry::Occupied(entry) => {
+                            entry.into_mut().merge(reqs).expect("Could not produce an intersection of the shader descriptor requirements");
+                        }
+                        Entry::Vacant(entry) => {
+                            entry.insert(reqs.clone());
+                        }
+                    }
+                }
+
+                stages.insert(ShaderStage::Geometry, ());
+                stages_vk.push(ash::vk::PipelineShaderStageCreateInfo {
+                    flags: ash::vk::PipelineShaderStageCreateFlags::empty(),
+                    stage: ash::vk::ShaderStageFlags::GEOMETRY,
+                    module: entry_point.module().handle(),
+                    p_name: entry_point.name().as_ptr(),
+                    p_specialization_info: specialization_info_vk as *const _,
+                    ..Default::default()
+                });
+            }
+
+            // Task shader
+            if let Some((entry_point, specialization_data)) = task_shader {
+                let specialization_map_entries = Tss::descriptors();
+                let specialization_data = slice::from_raw_parts(
+                    specialization_data as *const _ as *const u8,
+                    size_of_val(specialization_data),
+                );
+
+                let specialization_info_vk =
+                    task_shader_specialization_vk.insert(ash::vk::SpecializationInfo {
+                        map_entry_count: specialization_map_entries.len() as u32,
+                        p_map_entries: specialization_map_entries.as_ptr() as *const _,
+                        data_size: specialization_data.len(),
+                        p_data: specialization_data.as_ptr() as *const _,
+                    });
+
+                for (loc, reqs) in entry_point.descriptor_binding_requirements() {
+                    match descriptor_binding_requirements.entry(loc) {
+                        Entry::Occupied(entry) => {
+                            entry.into_mut().merge(reqs).expect("Could not produce an intersection of the shader descriptor requirements");
+                        }
+                        Entry::Vacant(entry) => {
+                            entry.insert(reqs.clone());
+                        }
+                    }
+                }
+
+                stages.insert(ShaderStage::Task, ());
+                stages_vk.push(ash::vk::PipelineShaderStageCreateInfo {
+                    flags: ash::vk::PipelineShaderStageCreateFlags::empty(),
+                    stage: ash::vk::ShaderStageFlags::TASK_EXT,
+                    module: entry_point.module().handle(),
+                    p_name: entry_point.name().as_ptr(),
+                    p_specialization_info: specialization_info_vk as *const _,
+                    ..Default::default()
+                });
+            }
+
+            // Mesh shader
+            if let Some((entry_point, specialization_data)) = mesh_shader {
+                let specialization_map_entries = Mss::descriptors();
                 let specialization_data = slice::from_raw_parts(
                     specialization_data as *const _ as *const u8,
                     size_of_val(specialization_data),
                 );
 
                 let specialization_info_vk =
-                    geometry_shader_specialization_vk.insert(ash::vk::SpecializationInfo {
+                    mesh_shader_specialization_vk.insert(ash::vk::SpecializationInfo {
                         map_entry_count: specialization_map_entries.len() as u32,
                         p_map_entries: specialization_map_entries.as_ptr() as *const _,
                         data_size: specialization_data.len(),
@@ -2907,10 +3384,10 @@ where
                     }
                 }
 
-                stages.insert(ShaderStage::Geometry, ());
+                stages.insert(ShaderStage::Mesh, ());
                 stages_vk.push(ash::vk::PipelineShaderStageCreateInfo {
                     flags: ash::vk::PipelineShaderStageCreateFlags::empty(),
-                    stage: ash::vk::ShaderStageFlags::GEOMETRY,
+                    stage: ash::vk::ShaderStageFlags::MESH_EXT,
                     module: entry_point.module().handle(),
                     p_name: entry_point.name().as_ptr(),
                     p_specialization_info: specialization_info_vk as *const _,
@@ -3463,7 +3940,7 @@ where
         let mut color_write_vk = None;
         let mut color_blend_state_vk = None;
 
-        if has.fragment_output_state {
+        if has.fragment_shader_state || has.fragment_output_state {
             // Multisample state
             {
                 let &MultisampleState {
@@ -3618,12 +4095,42 @@ where
             }
         }
 
+        /*
+            Library
+        */
+
+        let mut flags = ash::vk::PipelineCreateFlags::from(*create_flags);
+        let mut library_create_info_vk = None;
+
+        if base_pipeline.is_some() {
+            flags |= ash::vk::PipelineCreateFlags::DERIVATIVE;
+        }
+
+        if let Some(library_create_info) = library_create_info {
+            let &GraphicsPipelineLibraryCreateInfo {
+                flags: library_flags,
+                retain_link_time_optimization_info,
+                _ne: _,
+            } = library_create_info;
+
+            flags |= ash::vk::PipelineCreateFlags::LIBRARY_KHR;
+
+            if retain_link_time_optimization_info {
+                flags |= ash::vk::PipelineCreateFlags::RETAIN_LINK_TIME_OPTIMIZATION_INFO_EXT;
+            }
+
+            let _ = library_create_info_vk.insert(ash::vk::GraphicsPipelineLibraryCreateInfoEXT {
+                flags: library_flags.into(),
+                ..Default::default()
+            });
+        }
+
         /*
             Create
         */
 
         let mut create_info = ash::vk::GraphicsPipelineCreateInfo {
-            flags: ash::vk::PipelineCreateFlags::empty(), // TODO: some flags are available but none are critical
+            flags,
             stage_count: stages_vk.len() as u32,
             p_stages: stages_vk.as_ptr(),
             p_vertex_input_state: vertex_input_state_vk
@@ -3665,8 +4172,12 @@ where
             layout: pipeline_layout.handle(),
             render_pass: render_pass_vk,
             subpass: subpass_vk,
-            base_pipeline_handle: ash::vk::Pipeline::null(), // TODO:
-            base_pipeline_index: -1,                         // TODO:
+            base_pipeline_handle: base_pipeline
+                .as_ref()
+                .map_or(ash::vk::Pipeline::null(), |base_pipeline| {
+                    base_pipeline.handle()
+                }),
+            base_pipeline_index: -1,
             ..Default::default()
         };
 
@@ -3680,6 +4191,36 @@ where
             create_info.p_next = info as *const _ as *const _;
         }
 
+        if let Some(info) = library_create_info_vk.as_mut() {
+            info.p_next = create_info.p_next as *mut _;
+            create_info.p_next = info as *const _ as *const _;
+        }
+
+        /*
+            Creation feedback
+        */
+
+        let mut creation_feedback_vk = ash::vk::PipelineCreationFeedback::default();
+        let mut stage_creation_feedbacks_vk: SmallVec<[_; 5]> =
+            smallvec![ash::vk::PipelineCreationFeedback::default(); stages_vk.len()];
+        let mut creation_feedback_info_vk = None;
+
+        if device.api_version() >= Version::V1_3
+            || device.enabled_extensions().ext_pipeline_creation_feedback
+        {
+            let info =
+                creation_feedback_info_vk.insert(ash::vk::PipelineCreationFeedbackCreateInfo {
+                    p_pipeline_creation_feedback: &mut creation_feedback_vk,
+                    pipeline_stage_creation_feedback_count: stage_creation_feedbacks_vk.len()
+                        as u32,
+                    p_pipeline_stage_creation_feedbacks: stage_creation_feedbacks_vk.as_mut_ptr(),
+                    ..Default::default()
+                });
+
+            info.p_next = create_info.p_next;
+            create_info.p_next = info as *const _ as *const _;
+        }
+
         let cache_handle = match cache.as_ref() {
             Some(cache) => cache.handle(),
             None => ash::vk::PipelineCache::null(),
@@ -3688,16 +4229,20 @@ where
         let handle = {
             let fns = device.fns();
             let mut output = MaybeUninit::uninit();
-            (fns.v1_0.create_graphics_pipelines)(
+            let result = (fns.v1_0.create_graphics_pipelines)(
                 device.handle(),
                 cache_handle,
                 1,
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
-            )
-            .result()
-            .map_err(VulkanError::from)?;
+            );
+
+            if result == ash::vk::Result::PIPELINE_COMPILE_REQUIRED {
+                return Err(GraphicsPipelineCreationError::PipelineCompileRequired);
+            }
+
+            result.result().map_err(VulkanError::from)?;
 
             output.assume_init()
         };
@@ -3709,28 +4254,185 @@ where
             panic!("vkCreateGraphicsPipelines provided a NULL handle");
         }
 
+        let creation_feedback = if creation_feedback_info_vk.is_some() {
+            (
+                PipelineCreationFeedback::from_vulkan(creation_feedback_vk),
+                stages_vk
+                    .iter()
+                    .zip(stage_creation_feedbacks_vk)
+                    .filter_map(|(stage_vk, feedback_vk)| {
+                        Some((
+                            ShaderStage::try_from(stage_vk.stage).unwrap(),
+                            PipelineCreationFeedback::from_vulkan(feedback_vk)?,
+                        ))
+                    })
+                    .collect(),
+            )
+        } else {
+            (None, HashMap::default())
+        };
+
         Ok((
             handle,
             descriptor_binding_requirements,
             dynamic_state,
             stages,
             fragment_tests_stages,
+            creation_feedback,
         ))
     }
 
     // TODO: add build_with_cache method
 }
 
+// Feedback about the creation of the pipeline as a whole, and of each shader stage.
+type CreationFeedback = (
+    Option<PipelineCreationFeedback>,
+    HashMap<ShaderStage, PipelineCreationFeedback>,
+);
+
+// Checks that the vertex input state contains attributes for all the input variables of the
+// vertex shader.
+pub(crate) fn validate_vertex_input_attributes(
+    interface: &ShaderInterface,
+    vertex_input_state: &VertexInputState,
+) -> Result<(), VertexInputAttributeError> {
+    // VUID?
+    for element in interface.elements() {
+        // TODO: implement
+        if element.ty.is_64bit {
+            return Err(VertexInputAttributeError::Unsupported64Bit {
+                location: element.location,
+            });
+        }
+
+        let location_range = element.location..element.location + element.ty.num_locations();
+
+        for location in location_range {
+            let attribute_desc = match vertex_input_state.attributes.get(&location) {
+                Some(attribute_desc) => attribute_desc,
+                None => return Err(VertexInputAttributeError::Missing { location }),
+            };
+
+            // TODO: Check component assignments too. Multiple variables can occupy the same
+            // location but in different components.
+
+            let shader_type = element.ty.to_format().type_color().unwrap();
+            let attribute_type = attribute_desc.format.type_color().unwrap();
+
+            if !matches!(
+                (shader_type, attribute_type),
+                (
+                    NumericType::SFLOAT
+                        | NumericType::UFLOAT
+                        | NumericType::SNORM
+                        | NumericType::UNORM
+                        | NumericType::SSCALED
+                        | NumericType::USCALED
+                        | NumericType::SRGB,
+                    NumericType::SFLOAT
+                        | NumericType::UFLOAT
+                        | NumericType::SNORM
+                        | NumericType::UNORM
+                        | NumericType::SSCALED
+                        | NumericType::USCALED
+                        | NumericType::SRGB,
+                ) | (NumericType::SINT, NumericType::SINT)
+                    | (NumericType::UINT, NumericType::UINT)
+            ) {
+                return Err(VertexInputAttributeError::IncompatibleFormat {
+                    location,
+                    shader_type,
+                    attribute_type,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when the vertex input state is checked against the input interface of
+/// the vertex shader. It is converted into the error type of the operation doing the check.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum VertexInputAttributeError {
+    /// The type of the shader input variable is not compatible with the format of the attribute.
+    IncompatibleFormat {
+        location: u32,
+        shader_type: NumericType,
+        attribute_type: NumericType,
+    },
+
+    /// No attribute exists for a location that the shader uses.
+    Missing { location: u32 },
+
+    /// The shader input variable has a 64-bit type.
+    Unsupported64Bit { location: u32 },
+}
+
+impl From<VertexInputAttributeError> for GraphicsPipelineCreationError {
+    fn from(err: VertexInputAttributeError) -> Self {
+        match err {
+            VertexInputAttributeError::IncompatibleFormat {
+                location,
+                shader_type,
+                attribute_type,
+            } => Self::VertexInputAttributeIncompatibleFormat {
+                location,
+                shader_type,
+                attribute_type,
+            },
+            VertexInputAttributeError::Missing { location } => {
+                Self::VertexInputAttributeMissing { location }
+            }
+            VertexInputAttributeError::Unsupported64Bit { location } => {
+                Self::VertexInputAttributeUnsupported64Bit { location }
+            }
+        }
+    }
+}
+
 struct ShaderStageInfo<'a> {
     entry_point: &'a EntryPoint<'a>,
     specialization_map_entries: &'a [SpecializationMapEntry],
     _specialization_data: &'a [u8],
 }
 
-impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
-    GraphicsPipelineBuilder<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
+impl<'vs, 'tcs, 'tes, 'gs, 'fs, 'ts, 'ms, Vdef, Vss, Tcss, Tess, Gss, Fss, Tss, Mss>
+    GraphicsPipelineBuilder<
+        'vs,
+        'tcs,
+        'tes,
+        'gs,
+        'fs,
+        'ts,
+        'ms,
+        Vdef,
+        Vss,
+        Tcss,
+        Tess,
+        Gss,
+        Fss,
+        Tss,
+        Mss,
+    >
 {
-    // TODO: add pipeline derivate system
+    /// Sets the flags to create the pipeline with.
+    ///
+    /// The default value is [`PipelineCreateFlags::empty()`].
+    #[inline]
+    pub fn flags(mut self, flags: PipelineCreateFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Creates the pipeline as a derivative of `base_pipeline`, which must have been created
+    /// with [`PipelineCreateFlags::ALLOW_DERIVATIVES`].
+    #[inline]
+    pub fn base_pipeline(mut self, base_pipeline: Arc<GraphicsPipeline>) -> Self {
+        self.base_pipeline = Some(base_pipeline);
+        self
+    }
 
     /// Sets the vertex shader to use.
     // TODO: correct specialization constants
@@ -3738,20 +4440,41 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         self,
         shader: EntryPoint<'vs2>,
         specialization_constants: Vss2,
-    ) -> GraphicsPipelineBuilder<'vs2, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss2, Tcss, Tess, Gss, Fss>
+    ) -> GraphicsPipelineBuilder<
+        'vs2,
+        'tcs,
+        'tes,
+        'gs,
+        'fs,
+        'ts,
+        'ms,
+        Vdef,
+        Vss2,
+        Tcss,
+        Tess,
+        Gss,
+        Fss,
+        Tss,
+        Mss,
+    >
     where
         Vss2: SpecializationConstants,
     {
         GraphicsPipelineBuilder {
             render_pass: self.render_pass,
             cache: self.cache,
+            flags: self.flags,
+            base_pipeline: self.base_pipeline,
 
             vertex_shader: Some((shader, specialization_constants)),
             tessellation_shaders: self.tessellation_shaders,
             geometry_shader: self.geometry_shader,
             fragment_shader: self.fragment_shader,
+            task_shader: self.task_shader,
+            mesh_shader: self.mesh_shader,
 
             vertex_input_state: self.vertex_input_state,
+            vertex_input_state_dynamic: self.vertex_input_state_dynamic,
             input_assembly_state: self.input_assembly_state,
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
@@ -3771,7 +4494,23 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         control_specialization_constants: Tcss2,
         evaluation_shader: EntryPoint<'tes2>,
         evaluation_specialization_constants: Tess2,
-    ) -> GraphicsPipelineBuilder<'vs, 'tcs2, 'tes2, 'gs, 'fs, Vdef, Vss, Tcss2, Tess2, Gss, Fss>
+    ) -> GraphicsPipelineBuilder<
+        'vs,
+        'tcs2,
+        'tes2,
+        'gs,
+        'fs,
+        'ts,
+        'ms,
+        Vdef,
+        Vss,
+        Tcss2,
+        Tess2,
+        Gss,
+        Fss,
+        Tss,
+        Mss,
+    >
     where
         Tcss2: SpecializationConstants,
         Tess2: SpecializationConstants,
@@ -3779,6 +4518,8 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         GraphicsPipelineBuilder {
             render_pass: self.render_pass,
             cache: self.cache,
+            flags: self.flags,
+            base_pipeline: self.base_pipeline,
 
             vertex_shader: self.vertex_shader,
             tessellation_shaders: Some(TessellationShaders {
@@ -3787,8 +4528,11 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             }),
             geometry_shader: self.geometry_shader,
             fragment_shader: self.fragment_shader,
+            task_shader: self.task_shader,
+            mesh_shader: self.mesh_shader,
 
             vertex_input_state: self.vertex_input_state,
+            vertex_input_state_dynamic: self.vertex_input_state_dynamic,
             input_assembly_state: self.input_assembly_state,
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
@@ -3806,20 +4550,41 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         self,
         shader: EntryPoint<'gs2>,
         specialization_constants: Gss2,
-    ) -> GraphicsPipelineBuilder<'vs, 'tcs, 'tes, 'gs2, 'fs, Vdef, Vss, Tcss, Tess, Gss2, Fss>
+    ) -> GraphicsPipelineBuilder<
+        'vs,
+        'tcs,
+        'tes,
+        'gs2,
+        'fs,
+        'ts,
+        'ms,
+        Vdef,
+        Vss,
+        Tcss,
+        Tess,
+        Gss2,
+        Fss,
+        Tss,
+        Mss,
+    >
     where
         Gss2: SpecializationConstants,
     {
         GraphicsPipelineBuilder {
             render_pass: self.render_pass,
             cache: self.cache,
+            flags: self.flags,
+            base_pipeline: self.base_pipeline,
 
             vertex_shader: self.vertex_shader,
             tessellation_shaders: self.tessellation_shaders,
             geometry_shader: Some((shader, specialization_constants)),
             fragment_shader: self.fragment_shader,
+            task_shader: self.task_shader,
+            mesh_shader: self.mesh_shader,
 
             vertex_input_state: self.vertex_input_state,
+            vertex_input_state_dynamic: self.vertex_input_state_dynamic,
             input_assembly_state: self.input_assembly_state,
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
@@ -3839,20 +4604,152 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         self,
         shader: EntryPoint<'fs2>,
         specialization_constants: Fss2,
-    ) -> GraphicsPipelineBuilder<'vs, 'tcs, 'tes, 'gs, 'fs2, Vdef, Vss, Tcss, Tess, Gss, Fss2>
+    ) -> GraphicsPipelineBuilder<
+        'vs,
+        'tcs,
+        'tes,
+        'gs,
+        'fs2,
+        'ts,
+        'ms,
+        Vdef,
+        Vss,
+        Tcss,
+        Tess,
+        Gss,
+        Fss2,
+        Tss,
+        Mss,
+    >
     where
         Fss2: SpecializationConstants,
     {
         GraphicsPipelineBuilder {
             render_pass: self.render_pass,
             cache: self.cache,
+            flags: self.flags,
+            base_pipeline: self.base_pipeline,
 
             vertex_shader: self.vertex_shader,
             tessellation_shaders: self.tessellation_shaders,
             geometry_shader: self.geometry_shader,
             fragment_shader: Some((shader, specialization_constants)),
+            task_shader: self.task_shader,
+            mesh_shader: self.mesh_shader,
+
+            vertex_input_state: self.vertex_input_state,
+            vertex_input_state_dynamic: self.vertex_input_state_dynamic,
+            input_assembly_state: self.input_assembly_state,
+            tessellation_state: self.tessellation_state,
+            viewport_state: self.viewport_state,
+            discard_rectangle_state: self.discard_rectangle_state,
+            rasterization_state: self.rasterization_state,
+            multisample_state: self.multisample_state,
+            depth_stencil_state: self.depth_stencil_state,
+            color_blend_state: self.color_blend_state,
+        }
+    }
+
+    /// Sets the task shader to use.
+    ///
+    /// The task shader is optional, and generates the mesh shader workgroups. It can only be used
+    /// together with a mesh shader.
+    // TODO: correct specialization constants
+    pub fn task_shader<'ts2, Tss2>(
+        self,
+        shader: EntryPoint<'ts2>,
+        specialization_constants: Tss2,
+    ) -> GraphicsPipelineBuilder<
+        'vs,
+        'tcs,
+        'tes,
+        'gs,
+        'fs,
+        'ts2,
+        'ms,
+        Vdef,
+        Vss,
+        Tcss,
+        Tess,
+        Gss,
+        Fss,
+        Tss2,
+        Mss,
+    >
+    where
+        Tss2: SpecializationConstants,
+    {
+        GraphicsPipelineBuilder {
+            render_pass: self.render_pass,
+            cache: self.cache,
+            flags: self.flags,
+            base_pipeline: self.base_pipeline,
+
+            vertex_shader: self.vertex_shader,
+            tessellation_shaders: self.tessellation_shaders,
+            geometry_shader: self.geometry_shader,
+            fragment_shader: self.fragment_shader,
+            task_shader: Some((shader, specialization_constants)),
+            mesh_shader: self.mesh_shader,
+
+            vertex_input_state: self.vertex_input_state,
+            vertex_input_state_dynamic: self.vertex_input_state_dynamic,
+            input_assembly_state: self.input_assembly_state,
+            tessellation_state: self.tessellation_state,
+            viewport_state: self.viewport_state,
+            discard_rectangle_state: self.discard_rectangle_state,
+            rasterization_state: self.rasterization_state,
+            multisample_state: self.multisample_state,
+            depth_stencil_state: self.depth_stencil_state,
+            color_blend_state: self.color_blend_state,
+        }
+    }
+
+    /// Sets the mesh shader to use.
+    ///
+    /// A mesh shader replaces the vertex, tessellation and geometry shaders, and generates
+    /// primitives directly. If a mesh shader is used, the vertex input and input assembly states
+    /// are ignored.
+    // TODO: correct specialization constants
+    pub fn mesh_shader<'ms2, Mss2>(
+        self,
+        shader: EntryPoint<'ms2>,
+        specialization_constants: Mss2,
+    ) -> GraphicsPipelineBuilder<
+        'vs,
+        'tcs,
+        'tes,
+        'gs,
+        'fs,
+        'ts,
+        'ms2,
+        Vdef,
+        Vss,
+        Tcss,
+        Tess,
+        Gss,
+        Fss,
+        Tss,
+        Mss2,
+    >
+    where
+        Mss2: SpecializationConstants,
+    {
+        GraphicsPipelineBuilder {
+            render_pass: self.render_pass,
+            cache: self.cache,
+            flags: self.flags,
+            base_pipeline: self.base_pipeline,
+
+            vertex_shader: self.vertex_shader,
+            tessellation_shaders: self.tessellation_shaders,
+            geometry_shader: self.geometry_shader,
+            fragment_shader: self.fragment_shader,
+            task_shader: self.task_shader,
+            mesh_shader: Some((shader, specialization_constants)),
 
             vertex_input_state: self.vertex_input_state,
+            vertex_input_state_dynamic: self.vertex_input_state_dynamic,
             input_assembly_state: self.input_assembly_state,
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
@@ -3870,20 +4767,41 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
     pub fn vertex_input_state<T>(
         self,
         vertex_input_state: T,
-    ) -> GraphicsPipelineBuilder<'vs, 'tcs, 'tes, 'gs, 'fs, T, Vss, Tcss, Tess, Gss, Fss>
+    ) -> GraphicsPipelineBuilder<
+        'vs,
+        'tcs,
+        'tes,
+        'gs,
+        'fs,
+        'ts,
+        'ms,
+        T,
+        Vss,
+        Tcss,
+        Tess,
+        Gss,
+        Fss,
+        Tss,
+        Mss,
+    >
     where
         T: VertexDefinition,
     {
         GraphicsPipelineBuilder {
             render_pass: self.render_pass,
             cache: self.cache,
+            flags: self.flags,
+            base_pipeline: self.base_pipeline,
 
             vertex_shader: self.vertex_shader,
             tessellation_shaders: self.tessellation_shaders,
             geometry_shader: self.geometry_shader,
             fragment_shader: self.fragment_shader,
+            task_shader: self.task_shader,
+            mesh_shader: self.mesh_shader,
 
             vertex_input_state,
+            vertex_input_state_dynamic: self.vertex_input_state_dynamic,
             input_assembly_state: self.input_assembly_state,
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
@@ -3895,6 +4813,20 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         }
     }
 
+    /// Sets the vertex input state as dynamic, which means that you will need to set it with
+    /// [`set_vertex_input`] before drawing. Any vertex input state set with
+    /// [`vertex_input_state`](Self::vertex_input_state) is ignored.
+    ///
+    /// The [`vertex_input_dynamic_state`](crate::device::Features::vertex_input_dynamic_state)
+    /// feature must be enabled on the device.
+    ///
+    /// [`set_vertex_input`]: crate::command_buffer::AutoCommandBufferBuilder::set_vertex_input
+    #[inline]
+    pub fn vertex_input_state_dynamic(mut self) -> Self {
+        self.vertex_input_state_dynamic = true;
+        self
+    }
+
     /// Sets the input assembly state.
     ///
     /// The default value is [`InputAssemblyState::default()`].
@@ -4000,12 +4932,16 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         'tes,
         'gs,
         'fs,
+        'ts,
+        'ms,
         BuffersDefinition,
         Vss,
         Tcss,
         Tess,
         Gss,
         Fss,
+        Tss,
+        Mss,
     > {
         self.vertex_input_state(BuffersDefinition::new().vertex::<V>())
     }
@@ -4532,13 +5468,18 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         GraphicsPipelineBuilder {
             render_pass: Some(render_pass.into()),
             cache: self.cache,
+            flags: self.flags,
+            base_pipeline: self.base_pipeline,
 
             vertex_shader: self.vertex_shader,
             tessellation_shaders: self.tessellation_shaders,
             geometry_shader: self.geometry_shader,
             fragment_shader: self.fragment_shader,
+            task_shader: self.task_shader,
+            mesh_shader: self.mesh_shader,
 
             vertex_input_state: self.vertex_input_state,
+            vertex_input_state_dynamic: self.vertex_input_state_dynamic,
             input_assembly_state: self.input_assembly_state,
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
@@ -4563,8 +5504,24 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
     }
 }
 
-impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss> Clone
-    for GraphicsPipelineBuilder<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
+impl<'vs, 'tcs, 'tes, 'gs, 'fs, 'ts, 'ms, Vdef, Vss, Tcss, Tess, Gss, Fss, Tss, Mss> Clone
+    for GraphicsPipelineBuilder<
+        'vs,
+        'tcs,
+        'tes,
+        'gs,
+        'fs,
+        'ts,
+        'ms,
+        Vdef,
+        Vss,
+        Tcss,
+        Tess,
+        Gss,
+        Fss,
+        Tss,
+        Mss,
+    >
 where
     Vdef: Clone,
     Vss: Clone,
@@ -4572,18 +5529,25 @@ where
     Tess: Clone,
     Gss: Clone,
     Fss: Clone,
+    Tss: Clone,
+    Mss: Clone,
 {
     fn clone(&self) -> Self {
         GraphicsPipelineBuilder {
             render_pass: self.render_pass.clone(),
             cache: self.cache.clone(),
+            flags: self.flags,
+            base_pipeline: self.base_pipeline.clone(),
 
             vertex_shader: self.vertex_shader.clone(),
             tessellation_shaders: self.tessellation_shaders.clone(),
             geometry_shader: self.geometry_shader.clone(),
             fragment_shader: self.fragment_shader.clone(),
+            task_shader: self.task_shader.clone(),
+            mesh_shader: self.mesh_shader.clone(),
 
             vertex_input_state: self.vertex_input_state.clone(),
+            vertex_input_state_dynamic: self.vertex_input_state_dynamic,
             input_assembly_state: self.input_assembly_state,
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state.clone(),
@@ -7,12 +7,12 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
-use super::vertex_input::IncompatibleVertexDefinitionError;
+use super::{vertex_input::IncompatibleVertexDefinitionError, GraphicsPipelineLibraryFlags};
 use crate::{
     descriptor_set::layout::DescriptorSetLayoutCreationError,
     format::{Format, NumericType},
     pipeline::layout::{PipelineLayoutCreationError, PipelineLayoutSupersetError},
-    shader::ShaderInterfaceMismatchError,
+    shader::{DescriptorBindingRequirementsIncompatible, ShaderInterfaceMismatchError},
     OomError, RequirementNotMet, RequiresOneOf, VulkanError,
 };
 use std::{
@@ -28,6 +28,9 @@ pub enum GraphicsPipelineCreationError {
         requires_one_of: RequiresOneOf,
     },
 
+    /// The base pipeline was not created with `PipelineCreateFlags::ALLOW_DERIVATIVES`.
+    BasePipelineDerivativesNotAllowed,
+
     /// A color attachment has a format that does not support blending.
     ColorAttachmentFormatBlendNotSupported { attachment_index: u32 },
 
@@ -60,6 +63,35 @@ pub enum GraphicsPipelineCreationError {
     /// `patch_control_points` was not greater than 0 and less than or equal to the `max_tessellation_patch_size` limit.
     InvalidNumPatchControlPoints,
 
+    /// The libraries being linked do not contain all the parts that the pipeline needs.
+    LibrariesIncomplete {
+        missing: GraphicsPipelineLibraryFlags,
+    },
+
+    /// The shaders of a library have requirements for a descriptor that are not compatible with
+    /// the requirements of the shaders of another library.
+    LibraryDescriptorRequirementsIncompatible {
+        library_index: usize,
+        set_num: u32,
+        binding_num: u32,
+        error: DescriptorBindingRequirementsIncompatible,
+    },
+
+    /// More than one of the libraries being linked contains the same part of the pipeline.
+    LibraryFlagsOverlap { flags: GraphicsPipelineLibraryFlags },
+
+    /// The pipeline layout of a library is not compatible with the pipeline layout of the
+    /// pre-rasterization shaders library.
+    LibraryLayoutIncompatible { library_index: usize },
+
+    /// Link time optimization was requested, but a library was not created with
+    /// `retain_link_time_optimization_info` enabled.
+    LibraryLinkTimeOptimizationInfoNotRetained { library_index: usize },
+
+    /// The render pass of a library is not compatible with the render pass of the
+    /// pre-rasterization shaders library.
+    LibraryRenderPassIncompatible { library_index: usize },
+
     /// The maximum number of discard rectangles has been exceeded.
     MaxDiscardRectanglesExceeded {
         /// Maximum allowed value.
@@ -138,6 +170,9 @@ pub enum GraphicsPipelineCreationError {
     /// The maximum dimensions of viewports has been exceeded.
     MaxViewportDimensionsExceeded,
 
+    /// A mesh shader was provided together with a vertex, tessellation or geometry shader.
+    MeshShaderWithPrimitiveShadingStages,
+
     /// The number of attachments specified in the blending does not match the number of
     /// attachments in the subpass.
     MismatchBlendingAttachmentsCount,
@@ -163,6 +198,10 @@ pub enum GraphicsPipelineCreationError {
     /// Error while creating the pipeline layout object.
     PipelineLayoutCreationError(PipelineLayoutCreationError),
 
+    /// `PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED` was set, and creating the
+    /// pipeline would require compiling shaders.
+    PipelineCompileRequired,
+
     /// The output interface of one shader and the input interface of the next shader do not match.
     ShaderStagesMismatch(ShaderInterfaceMismatchError),
 
@@ -172,6 +211,9 @@ pub enum GraphicsPipelineCreationError {
     /// The [`strict_lines`](crate::device::Properties::strict_lines) device property was `false`.
     StrictLinesNotSupported,
 
+    /// A task shader was provided without a mesh shader.
+    TaskShaderWithoutMeshShader,
+
     /// The primitives topology does not match what the geometry shader expects.
     TopologyNotMatchingGeometryShader,
 
@@ -194,6 +236,10 @@ pub enum GraphicsPipelineCreationError {
     /// The format specified by a vertex input attribute is not supported for vertex buffers.
     VertexInputAttributeUnsupportedFormat { location: u32, format: Format },
 
+    /// The vertex shader has an input variable with a 64-bit type at the given location, which is
+    /// not yet supported by vulkano.
+    VertexInputAttributeUnsupported64Bit { location: u32 },
+
     /// The minimum or maximum bounds of viewports have been exceeded.
     ViewportBoundsExceeded,
 
@@ -214,6 +260,7 @@ impl Error for GraphicsPipelineCreationError {
             Self::IncompatiblePipelineLayout(err) => Some(err),
             Self::ShaderStagesMismatch(err) => Some(err),
             Self::IncompatibleVertexDefinition(err) => Some(err),
+            Self::LibraryDescriptorRequirementsIncompatible { error, .. } => Some(error),
             _ => None,
         }
     }
@@ -230,6 +277,10 @@ impl Display for GraphicsPipelineCreationError {
                 "a requirement was not met for: {}; requires one of: {}",
                 required_for, requires_one_of,
             ),
+            Self::BasePipelineDerivativesNotAllowed => write!(
+                f,
+                "the base pipeline was not created with `PipelineCreateFlags::ALLOW_DERIVATIVES`",
+            ),
             Self::ColorAttachmentFormatBlendNotSupported { attachment_index } => write!(
                 f,
                 "color attachment {} has a format that does not support blending",
@@ -276,6 +327,47 @@ impl Display for GraphicsPipelineCreationError {
                 "patch_control_points was not greater than 0 and less than or equal to the \
                 max_tessellation_patch_size limit",
             ),
+            Self::LibrariesIncomplete { missing } => write!(
+                f,
+                "the libraries being linked do not contain all the parts that the pipeline needs \
+                (missing: {:?})",
+                missing,
+            ),
+            Self::LibraryDescriptorRequirementsIncompatible {
+                library_index,
+                set_num,
+                binding_num,
+                ..
+            } => write!(
+                f,
+                "the requirements of the shaders of library {} for the descriptor at set {}, \
+                binding {} are not compatible with the requirements of the other libraries",
+                library_index, set_num, binding_num,
+            ),
+            Self::LibraryFlagsOverlap { flags } => write!(
+                f,
+                "more than one of the libraries being linked contains the same part of the \
+                pipeline ({:?})",
+                flags,
+            ),
+            Self::LibraryLayoutIncompatible { library_index } => write!(
+                f,
+                "the pipeline layout of library {} is not compatible with the pipeline layout of \
+                the pre-rasterization shaders library",
+                library_index,
+            ),
+            Self::LibraryLinkTimeOptimizationInfoNotRetained { library_index } => write!(
+                f,
+                "link time optimization was requested, but library {} was not created with \
+                `retain_link_time_optimization_info` enabled",
+                library_index,
+            ),
+            Self::LibraryRenderPassIncompatible { library_index } => write!(
+                f,
+                "the render pass of library {} is not compatible with the render pass of the \
+                pre-rasterization shaders library",
+                library_index,
+            ),
             Self::MaxDiscardRectanglesExceeded { .. } => write!(
                 f,
                 "the maximum number of discard rectangles has been exceeded",
@@ -313,6 +405,11 @@ impl Display for GraphicsPipelineCreationError {
                 f,
                 "the `min_vertex_input_binding_stride_alignment` limit has been exceeded",
             ),
+            Self::MeshShaderWithPrimitiveShadingStages => write!(
+                f,
+                "a mesh shader was provided together with a vertex, tessellation or geometry \
+                shader",
+            ),
             Self::MismatchBlendingAttachmentsCount => write!(
                 f,
                 "the number of attachments specified in the blending does not match the number of \
@@ -338,6 +435,11 @@ impl Display for GraphicsPipelineCreationError {
             Self::PipelineLayoutCreationError(_) => {
                 write!(f, "error while creating the pipeline layout object")
             }
+            Self::PipelineCompileRequired => write!(
+                f,
+                "`PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED` was set, and creating the \
+                pipeline would require compiling shaders",
+            ),
             Self::ShaderStagesMismatch(_) => write!(
                 f,
                 "the output interface of one shader and the input interface of the next shader do \
@@ -350,6 +452,9 @@ impl Display for GraphicsPipelineCreationError {
             Self::StrictLinesNotSupported => {
                 write!(f, "the strict_lines device property was false")
             }
+            Self::TaskShaderWithoutMeshShader => {
+                write!(f, "a task shader was provided without a mesh shader")
+            }
             Self::TopologyNotMatchingGeometryShader => write!(
                 f,
                 "the primitives topology does not match what the geometry shader expects",
@@ -382,6 +487,12 @@ impl Display for GraphicsPipelineCreationError {
                 for vertex buffers",
                 format, location,
             ),
+            Self::VertexInputAttributeUnsupported64Bit { location } => write!(
+                f,
+                "the vertex shader has an input variable with a 64-bit type at location {}, which \
+                is not yet supported",
+                location,
+            ),
             Self::ViewportBoundsExceeded => write!(
                 f,
                 "the minimum or maximum bounds of viewports have been exceeded",
@@ -0,0 +1,1169 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{
+    builder::validate_vertex_input_attributes,
+    color_blend::ColorBlendState,
+    depth_stencil::DepthStencilState,
+    discard_rectangle::DiscardRectangleState,
+    input_assembly::{InputAssemblyState, PrimitiveTopology, PrimitiveTopologyClass},
+    multisample::MultisampleState,
+    rasterization::RasterizationState,
+    render_pass::{PipelineRenderPassType, PipelineRenderingCreateInfo},
+    tessellation::TessellationState,
+    vertex_input::VertexInputState,
+    viewport::ViewportState,
+    GraphicsPipeline, GraphicsPipelineCreationError,
+};
+use crate::{
+    device::{Device, DeviceOwned},
+    macros::vulkan_bitflags,
+    pipeline::{
+        cache::PipelineCache, DynamicState, PartialStateMode, PipelineCreateFlags,
+        PipelineCreationFeedback, PipelineLayout, StateMode,
+    },
+    shader::{
+        DescriptorBindingRequirements, FragmentTestsStages, GeometryShaderInput, ShaderInterface,
+        ShaderStage, ShaderTransformFeedbackOutput,
+    },
+    RequiresOneOf, Version, VulkanError, VulkanObject,
+};
+use ahash::HashMap;
+use smallvec::SmallVec;
+use std::{
+    collections::hash_map::Entry,
+    fmt::{Debug, Error as FmtError, Formatter},
+    mem::MaybeUninit,
+    num::NonZeroU64,
+    sync::Arc,
+};
+
+/// A part of a graphics pipeline, that can be linked together with other parts into a complete
+/// [`GraphicsPipeline`].
+///
+/// Graphics pipeline libraries let you compile the parts of a pipeline separately, and reuse
+/// them between pipelines. Linking libraries is usually much faster than creating a complete
+/// pipeline, which avoids stalls when a new combination of shaders and state is needed.
+///
+/// Libraries are created with [`GraphicsPipelineBuilder::build_library`], and linked with
+/// [`GraphicsPipeline::link`]. The [`graphics_pipeline_library`] feature must be enabled on the
+/// device.
+///
+/// [`GraphicsPipelineBuilder::build_library`]: super::GraphicsPipelineBuilder::build_library
+/// [`graphics_pipeline_library`]: crate::device::Features::graphics_pipeline_library
+pub struct GraphicsPipelineLibrary {
+    pub(super) handle: ash::vk::Pipeline,
+    pub(super) device: Arc<Device>,
+    pub(super) id: NonZeroU64,
+    pub(super) flags: GraphicsPipelineLibraryFlags,
+    pub(super) retain_link_time_optimization_info: bool,
+    pub(super) layout: Arc<PipelineLayout>,
+    pub(super) render_pass: PipelineRenderPassType,
+
+    pub(super) shaders: HashMap<ShaderStage, ()>,
+    pub(super) descriptor_binding_requirements: HashMap<(u32, u32), DescriptorBindingRequirements>,
+    pub(super) fragment_tests_stages: Option<FragmentTestsStages>,
+    pub(super) transform_feedback_outputs: Option<Vec<ShaderTransformFeedbackOutput>>,
+
+    // The interfaces between the parts, which are checked when linking.
+    pub(super) vertex_shader_input_interface: Option<ShaderInterface>,
+    pub(super) pre_rasterization_output_interface: Option<ShaderInterface>,
+    pub(super) fragment_shader_input_interface: Option<ShaderInterface>,
+    pub(super) geometry_shader_input: Option<GeometryShaderInput>,
+
+    pub(super) vertex_input_state: Option<VertexInputState>,
+    pub(super) input_assembly_state: Option<InputAssemblyState>,
+    pub(super) tessellation_state: Option<TessellationState>,
+    pub(super) viewport_state: Option<ViewportState>,
+    pub(super) discard_rectangle_state: Option<DiscardRectangleState>,
+    pub(super) rasterization_state: Option<RasterizationState>,
+    pub(super) multisample_state: Option<MultisampleState>,
+    pub(super) depth_stencil_state: Option<DepthStencilState>,
+    pub(super) color_blend_state: Option<ColorBlendState>,
+    pub(super) dynamic_state: HashMap<DynamicState, bool>,
+}
+
+impl GraphicsPipelineLibrary {
+    /// Returns the parts of a graphics pipeline that the library contains.
+    #[inline]
+    pub fn flags(&self) -> GraphicsPipelineLibraryFlags {
+        self.flags
+    }
+
+    /// Returns whether the library was created with `retain_link_time_optimization_info`
+    /// enabled.
+    #[inline]
+    pub fn retain_link_time_optimization_info(&self) -> bool {
+        self.retain_link_time_optimization_info
+    }
+
+    /// Returns the pipeline layout used to create the library.
+    #[inline]
+    pub fn layout(&self) -> &Arc<PipelineLayout> {
+        &self.layout
+    }
+
+    /// Returns the render pass the library was created for.
+    #[inline]
+    pub fn render_pass(&self) -> &PipelineRenderPassType {
+        &self.render_pass
+    }
+
+    /// Returns the vertex input state, if the library contains the vertex input interface.
+    #[inline]
+    pub fn vertex_input_state(&self) -> Option<&VertexInputState> {
+        self.vertex_input_state.as_ref()
+    }
+
+    /// Returns the input assembly state, if the library contains the vertex input interface.
+    #[inline]
+    pub fn input_assembly_state(&self) -> Option<&InputAssemblyState> {
+        self.input_assembly_state.as_ref()
+    }
+
+    /// Returns the tessellation state, if the library contains the pre-rasterization shaders and
+    /// uses tessellation.
+    #[inline]
+    pub fn tessellation_state(&self) -> Option<&TessellationState> {
+        self.tessellation_state.as_ref()
+    }
+
+    /// Returns the viewport state, if the library contains the pre-rasterization shaders and
+    /// rasterization is not discarded.
+    #[inline]
+    pub fn viewport_state(&self) -> Option<&ViewportState> {
+        self.viewport_state.as_ref()
+    }
+
+    /// Returns the discard rectangle state, if the library contains the pre-rasterization
+    /// shaders.
+    #[inline]
+    pub fn discard_rectangle_state(&self) -> Option<&DiscardRectangleState> {
+        self.discard_rectangle_state.as_ref()
+    }
+
+    /// Returns the rasterization state, if the library contains the pre-rasterization shaders.
+    #[inline]
+    pub fn rasterization_state(&self) -> Option<&RasterizationState> {
+        self.rasterization_state.as_ref()
+    }
+
+    /// Returns the multisample state, if the library contains the fragment shader or the
+    /// fragment output interface.
+    #[inline]
+    pub fn multisample_state(&self) -> Option<&MultisampleState> {
+        self.multisample_state.as_ref()
+    }
+
+    /// Returns the depth/stencil state, if the library contains the fragment shader and the
+    /// render pass has a depth or stencil attachment.
+    #[inline]
+    pub fn depth_stencil_state(&self) -> Option<&DepthStencilState> {
+        self.depth_stencil_state.as_ref()
+    }
+
+    /// Returns the color blend state, if the library contains the fragment output interface and
+    /// the render pass has color attachments.
+    #[inline]
+    pub fn color_blend_state(&self) -> Option<&ColorBlendState> {
+        self.color_blend_state.as_ref()
+    }
+
+    /// Returns whether a particular state is must be dynamically set.
+    ///
+    /// `None` is returned if the library does not contain this state.
+    #[inline]
+    pub fn dynamic_state(&self, state: DynamicState) -> Option<bool> {
+        self.dynamic_state.get(&state).copied()
+    }
+
+    /// Returns all potentially dynamic states in the library, and whether they are dynamic or
+    /// not.
+    #[inline]
+    pub fn dynamic_states(&self) -> impl ExactSizeIterator<Item = (DynamicState, bool)> + '_ {
+        self.dynamic_state.iter().map(|(k, v)| (*k, *v))
+    }
+}
+
+unsafe impl DeviceOwned for GraphicsPipelineLibrary {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+impl Debug for GraphicsPipelineLibrary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(f, "<Vulkan graphics pipeline library {:?}>", self.handle)
+    }
+}
+
+unsafe impl VulkanObject for GraphicsPipelineLibrary {
+    type Handle = ash::vk::Pipeline;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.handle
+    }
+}
+
+impl Drop for GraphicsPipelineLibrary {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let fns = self.device.fns();
+            (fns.v1_0.destroy_pipeline)(
+                self.device.handle(),
+                self.handle,
+                self.device.allocation_callbacks(),
+            );
+        }
+    }
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// The parts of a graphics pipeline that a graphics pipeline library contains.
+    GraphicsPipelineLibraryFlags = GraphicsPipelineLibraryFlagsEXT(u32);
+
+    /// The vertex input state and input assembly state.
+    VERTEX_INPUT_INTERFACE = VERTEX_INPUT_INTERFACE {
+        device_extensions: [ext_graphics_pipeline_library],
+    },
+
+    /// The vertex, tessellation, geometry, task and mesh shaders, and the tessellation,
+    /// viewport, discard rectangle and rasterization state.
+    PRE_RASTERIZATION_SHADERS = PRE_RASTERIZATION_SHADERS {
+        device_extensions: [ext_graphics_pipeline_library],
+    },
+
+    /// The fragment shader, and the depth/stencil state.
+    FRAGMENT_SHADER = FRAGMENT_SHADER {
+        device_extensions: [ext_graphics_pipeline_library],
+    },
+
+    /// The multisample state and color blend state.
+    FRAGMENT_OUTPUT_INTERFACE = FRAGMENT_OUTPUT_INTERFACE {
+        device_extensions: [ext_graphics_pipeline_library],
+    },
+}
+
+/// Parameters to create a new `GraphicsPipelineLibrary`.
+#[derive(Clone, Debug)]
+pub struct GraphicsPipelineLibraryCreateInfo {
+    /// The parts of a graphics pipeline that the library will contain.
+    ///
+    /// The default value is [`GraphicsPipelineLibraryFlags::empty()`], which must be overridden.
+    pub flags: GraphicsPipelineLibraryFlags,
+
+    /// Whether to retain the information that the implementation needs to optimize the library
+    /// when it is linked. This must be enabled on all libraries that are linked with
+    /// [`link_time_optimization`](GraphicsPipelineLinkCreateInfo::link_time_optimization)
+    /// enabled.
+    ///
+    /// The default value is `false`.
+    pub retain_link_time_optimization_info: bool,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for GraphicsPipelineLibraryCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            flags: GraphicsPipelineLibraryFlags::empty(),
+            retain_link_time_optimization_info: false,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl GraphicsPipelineLibraryCreateInfo {
+    /// Returns a `GraphicsPipelineLibraryCreateInfo` with the specified `flags`.
+    #[inline]
+    pub fn flags(flags: GraphicsPipelineLibraryFlags) -> Self {
+        Self {
+            flags,
+            ..Default::default()
+        }
+    }
+
+    pub(super) fn validate(&self, device: &Device) -> Result<(), GraphicsPipelineCreationError> {
+        let &Self {
+            flags,
+            retain_link_time_optimization_info: _,
+            _ne: _,
+        } = self;
+
+        // VUID-VkGraphicsPipelineCreateInfo-graphicsPipelineLibrary-06606
+        if !device.enabled_features().graphics_pipeline_library {
+            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                required_for: "creating a graphics pipeline library",
+                requires_one_of: RequiresOneOf {
+                    features: &["graphics_pipeline_library"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-VkGraphicsPipelineLibraryCreateInfoEXT-flags-parameter
+        flags.validate_device(device)?;
+
+        // VUID-VkGraphicsPipelineLibraryCreateInfoEXT-flags-requiredbitmask
+        assert!(!flags.is_empty());
+
+        Ok(())
+    }
+}
+
+/// Parameters to link graphics pipeline libraries into a complete `GraphicsPipeline`.
+#[derive(Clone, Debug)]
+pub struct GraphicsPipelineLinkCreateInfo {
+    /// Specifies how to create the pipeline.
+    ///
+    /// The default value is empty.
+    pub flags: PipelineCreateFlags,
+
+    /// The libraries to link.
+    ///
+    /// Each part of a graphics pipeline must be contained in at most one library. The libraries
+    /// together must contain all the parts that the pipeline needs:
+    /// - The pre-rasterization shaders are always needed.
+    /// - The vertex input interface is needed if the pipeline has a vertex shader.
+    /// - The fragment shader and fragment output interface are needed unless
+    ///   [`rasterizer_discard_enable`](super::rasterization::RasterizationState::rasterizer_discard_enable)
+    ///   is `StateMode::Fixed(true)`.
+    ///
+    /// The libraries must have been created with compatible pipeline layouts and render passes.
+    ///
+    /// The default value is empty, which must be overridden.
+    pub libraries: Vec<Arc<GraphicsPipelineLibrary>>,
+
+    /// Whether the implementation should optimize the linked pipeline.
+    ///
+    /// Optimizing makes linking slower, but the pipeline may be faster to execute. A common
+    /// approach is to first link without optimizing, so that the pipeline can be used right
+    /// away, and then link again with optimizing on a separate thread, replacing the first
+    /// pipeline once the optimized one is ready.
+    ///
+    /// If enabled, all libraries must have been created with
+    /// [`retain_link_time_optimization_info`](GraphicsPipelineLibraryCreateInfo::retain_link_time_optimization_info)
+    /// enabled.
+    ///
+    /// The default value is `false`.
+    pub link_time_optimization: bool,
+
+    /// The pipeline cache to use when linking.
+    ///
+    /// The default value is `None`.
+    pub cache: Option<Arc<PipelineCache>>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for GraphicsPipelineLinkCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            flags: PipelineCreateFlags::empty(),
+            libraries: Vec::new(),
+            link_time_optimization: false,
+            cache: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl GraphicsPipelineLinkCreateInfo {
+    /// Returns a `GraphicsPipelineLinkCreateInfo` with the specified `libraries`.
+    #[inline]
+    pub fn libraries(libraries: impl IntoIterator<Item = Arc<GraphicsPipelineLibrary>>) -> Self {
+        Self {
+            libraries: libraries.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+}
+
+impl GraphicsPipeline {
+    /// Creates a graphics pipeline by linking together graphics pipeline libraries.
+    ///
+    /// The state of the pipeline is taken from the libraries. The libraries are kept alive for
+    /// as long as the pipeline exists.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if an element of `create_info.libraries` was not created from `device`.
+    ///
+    /// # Examples
+    ///
+    /// Linking a pipeline quickly, and replacing it with an optimized one when that is ready:
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use vulkano::device::Device;
+    /// use std::{sync::mpsc, thread};
+    /// use vulkano::pipeline::graphics::{
+    ///     GraphicsPipeline, GraphicsPipelineLibrary, GraphicsPipelineLinkCreateInfo,
+    /// };
+    /// # let device: Arc<Device> = return;
+    /// # let libraries: Vec<Arc<GraphicsPipelineLibrary>> = return;
+    ///
+    /// let mut pipeline = GraphicsPipeline::link(
+    ///     device.clone(),
+    ///     GraphicsPipelineLinkCreateInfo::libraries(libraries.iter().cloned()),
+    /// )
+    /// .unwrap();
+    ///
+    /// let (sender, receiver) = mpsc::channel();
+    /// thread::spawn(move || {
+    ///     let optimized = GraphicsPipeline::link(
+    ///         device,
+    ///         GraphicsPipelineLinkCreateInfo {
+    ///             link_time_optimization: true,
+    ///             ..GraphicsPipelineLinkCreateInfo::libraries(libraries)
+    ///         },
+    ///     );
+    ///     let _ = sender.send(optimized);
+    /// });
+    ///
+    /// // Each frame:
+    /// if let Ok(Ok(optimized)) = receiver.try_recv() {
+    ///     pipeline = optimized;
+    /// }
+    /// ```
+    pub fn link(
+        device: Arc<Device>,
+        create_info: GraphicsPipelineLinkCreateInfo,
+    ) -> Result<Arc<GraphicsPipeline>, GraphicsPipelineCreationError> {
+        Self::validate_link(&device, &create_info)?;
+
+        unsafe { Self::link_unchecked(device, create_info) }
+    }
+
+    fn validate_link(
+        device: &Device,
+        create_info: &GraphicsPipelineLinkCreateInfo,
+    ) -> Result<(), GraphicsPipelineCreationError> {
+        let &GraphicsPipelineLinkCreateInfo {
+            flags: create_flags,
+            ref libraries,
+            link_time_optimization,
+            cache: _,
+            _ne: _,
+        } = create_info;
+
+        // VUID-VkGraphicsPipelineCreateInfo-flags-parameter
+        create_flags.validate_device(device)?;
+
+        // VUID-VkGraphicsPipelineCreateInfo-pipelineCreationCacheControl-02878
+        if create_flags.intersects(PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED)
+            && !device.enabled_features().pipeline_creation_cache_control
+        {
+            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                required_for: "`create_info.flags` contains \
+                    `PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED`",
+                requires_one_of: RequiresOneOf {
+                    features: &["pipeline_creation_cache_control"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let mut flags = GraphicsPipelineLibraryFlags::empty();
+
+        for (library_index, library) in libraries.iter().enumerate() {
+            // VUID-VkPipelineLibraryCreateInfoKHR-pLibraries-parameter
+            assert_eq!(device, library.device().as_ref());
+
+            // VUID-VkGraphicsPipelineCreateInfo-None-06611
+            if flags.intersects(library.flags) {
+                return Err(GraphicsPipelineCreationError::LibraryFlagsOverlap {
+                    flags: flags & library.flags,
+                });
+            }
+
+            flags |= library.flags;
+
+            // VUID-VkGraphicsPipelineCreateInfo-flags-06609
+            if link_time_optimization && !library.retain_link_time_optimization_info {
+                return Err(
+                    GraphicsPipelineCreationError::LibraryLinkTimeOptimizationInfoNotRetained {
+                        library_index,
+                    },
+                );
+            }
+        }
+
+        let find = |flag: GraphicsPipelineLibraryFlags| {
+            libraries
+                .iter()
+                .enumerate()
+                .find(|(_, library)| library.flags.intersects(flag))
+        };
+
+        let vertex_input = find(GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE);
+        let pre_rasterization = find(GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS);
+        let fragment_shader = find(GraphicsPipelineLibraryFlags::FRAGMENT_SHADER);
+        let fragment_output = find(GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE);
+
+        let (_, pre_rasterization) =
+            pre_rasterization.ok_or(GraphicsPipelineCreationError::LibrariesIncomplete {
+                missing: GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS,
+            })?;
+
+        let mut required = GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS;
+
+        if pre_rasterization.shaders.contains_key(&ShaderStage::Vertex) {
+            required |= GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE;
+        }
+
+        if pre_rasterization
+            .rasterization_state
+            .as_ref()
+            .unwrap()
+            .rasterizer_discard_enable
+            != StateMode::Fixed(true)
+        {
+            required |= GraphicsPipelineLibraryFlags::FRAGMENT_SHADER
+                | GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE;
+        }
+
+        // VUID-VkGraphicsPipelineCreateInfo-flags-06608
+        if !flags.contains(required) {
+            return Err(GraphicsPipelineCreationError::LibrariesIncomplete {
+                missing: required - flags,
+            });
+        }
+
+        for (library_index, library) in [vertex_input, fragment_shader, fragment_output]
+            .into_iter()
+            .flatten()
+        {
+            // Vertex input interface libraries don't use a render pass.
+            if library.flags == GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE {
+                continue;
+            }
+
+            // VUID-VkGraphicsPipelineCreateInfo-renderpass-06625
+            if !render_pass_compatible(&library.render_pass, &pre_rasterization.render_pass) {
+                return Err(
+                    GraphicsPipelineCreationError::LibraryRenderPassIncompatible { library_index },
+                );
+            }
+        }
+
+        if let Some((library_index, library)) = fragment_shader {
+            let layout = &library.layout;
+            let pre_rasterization_layout = &pre_rasterization.layout;
+
+            // VUID-VkGraphicsPipelineCreateInfo-flags-06615
+            if !(layout.set_layouts().len() == pre_rasterization_layout.set_layouts().len()
+                && layout.is_compatible_with(
+                    pre_rasterization_layout,
+                    layout.set_layouts().len() as u32,
+                ))
+            {
+                return Err(GraphicsPipelineCreationError::LibraryLayoutIncompatible {
+                    library_index,
+                });
+            }
+
+            // VUID-VkGraphicsPipelineCreateInfo-pStages-00742
+            // VUID-VkGraphicsPipelineCreateInfo-None-04889
+            if let (Some(input), Some(output)) = (
+                &library.fragment_shader_input_interface,
+                &pre_rasterization.pre_rasterization_output_interface,
+            ) {
+                input
+                    .matches(output)
+                    .map_err(GraphicsPipelineCreationError::ShaderStagesMismatch)?;
+            }
+        }
+
+        if let Some((_, library)) = vertex_input {
+            let vertex_input_state = library.vertex_input_state.as_ref().unwrap();
+            let input_assembly_state = library.input_assembly_state.as_ref().unwrap();
+
+            // Dynamic vertex input is checked against the shader when drawing.
+            if library.dynamic_state.get(&DynamicState::VertexInput) != Some(&true) {
+                if let Some(interface) = &pre_rasterization.vertex_shader_input_interface {
+                    validate_vertex_input_attributes(interface, vertex_input_state)?;
+                }
+            }
+
+            // VUID-VkGraphicsPipelineCreateInfo-pStages-00736
+            if pre_rasterization.tessellation_state.is_some()
+                && !matches!(
+                    input_assembly_state.topology,
+                    PartialStateMode::Dynamic(PrimitiveTopologyClass::Patch)
+                        | PartialStateMode::Fixed(PrimitiveTopology::PatchList)
+                )
+            {
+                return Err(GraphicsPipelineCreationError::InvalidPrimitiveTopology);
+            }
+
+            if let (Some(input), PartialStateMode::Fixed(topology)) = (
+                pre_rasterization.geometry_shader_input,
+                input_assembly_state.topology,
+            ) {
+                // VUID-VkGraphicsPipelineCreateInfo-pStages-00738
+                if !input.is_compatible_with(topology) {
+                    return Err(GraphicsPipelineCreationError::TopologyNotMatchingGeometryShader);
+                }
+            }
+        }
+
+        // The shaders of all the libraries must agree on what they require of each descriptor.
+        let mut descriptor_binding_requirements: HashMap<
+            (u32, u32),
+            DescriptorBindingRequirements,
+        > = HashMap::default();
+
+        for (library_index, library) in libraries.iter().enumerate() {
+            for (&(set_num, binding_num), reqs) in &library.descriptor_binding_requirements {
+                match descriptor_binding_requirements.entry((set_num, binding_num)) {
+                    Entry::Occupied(entry) => {
+                        entry.into_mut().merge(reqs).map_err(|error| {
+                            GraphicsPipelineCreationError::LibraryDescriptorRequirementsIncompatible {
+                                library_index,
+                                set_num,
+                                binding_num,
+                                error,
+                            }
+                        })?;
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(reqs.clone());
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn link_unchecked(
+        device: Arc<Device>,
+        create_info: GraphicsPipelineLinkCreateInfo,
+    ) -> Result<Arc<GraphicsPipeline>, GraphicsPipelineCreationError> {
+        let GraphicsPipelineLinkCreateInfo {
+            flags: create_flags,
+            libraries,
+            link_time_optimization,
+            cache,
+            _ne: _,
+        } = create_info;
+
+        let find = |flag: GraphicsPipelineLibraryFlags| {
+            libraries
+                .iter()
+                .find(|library| library.flags.intersects(flag))
+        };
+
+        let vertex_input = find(GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE);
+        let pre_rasterization = find(GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS)
+            .expect("missing pre-rasterization shaders library");
+        let fragment_shader = find(GraphicsPipelineLibraryFlags::FRAGMENT_SHADER);
+        let fragment_output = find(GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE);
+
+        let layout = pre_rasterization.layout.clone();
+        let render_pass = pre_rasterization.render_pass.clone();
+
+        let mut creation_feedback_vk = ash::vk::PipelineCreationFeedback::default();
+        let mut creation_feedback_info_vk = (device.api_version() >= Version::V1_3
+            || device.enabled_extensions().ext_pipeline_creation_feedback)
+            .then(|| ash::vk::PipelineCreationFeedbackCreateInfo {
+                p_pipeline_creation_feedback: &mut creation_feedback_vk,
+                ..Default::default()
+            });
+
+        let handle = {
+            let libraries_vk: SmallVec<[_; 4]> =
+                libraries.iter().map(|library| library.handle).collect();
+            let library_info_vk = ash::vk::PipelineLibraryCreateInfoKHR {
+                library_count: libraries_vk.len() as u32,
+                p_libraries: libraries_vk.as_ptr(),
+                ..Default::default()
+            };
+
+            let mut render_pass_vk = ash::vk::RenderPass::null();
+            let mut subpass_vk = 0;
+            let mut color_attachment_formats_vk: SmallVec<[_; 4]> = SmallVec::new();
+            let mut rendering_create_info_vk = None;
+
+            match &render_pass {
+                PipelineRenderPassType::BeginRenderPass(subpass) => {
+                    render_pass_vk = subpass.render_pass().handle();
+                    subpass_vk = subpass.index();
+                }
+                PipelineRenderPassType::BeginRendering(rendering_info) => {
+                    let &PipelineRenderingCreateInfo {
+                        view_mask,
+                        ref color_attachment_formats,
+                        depth_attachment_format,
+                        stencil_attachment_format,
+                        _ne: _,
+                    } = rendering_info;
+
+                    color_attachment_formats_vk.extend(
+                        color_attachment_formats
+                            .iter()
+                            .map(|format| format.map_or(ash::vk::Format::UNDEFINED, Into::into)),
+                    );
+
+                    let _ = rendering_create_info_vk.insert(ash::vk::PipelineRenderingCreateInfo {
+                        view_mask,
+                        color_attachment_count: color_attachment_formats_vk.len() as u32,
+                        p_color_attachment_formats: color_attachment_formats_vk.as_ptr(),
+                        depth_attachment_format: depth_attachment_format
+                            .map_or(ash::vk::Format::UNDEFINED, Into::into),
+                        stencil_attachment_format: stencil_attachment_format
+                            .map_or(ash::vk::Format::UNDEFINED, Into::into),
+                        ..Default::default()
+                    });
+                }
+            }
+
+            let mut flags = ash::vk::PipelineCreateFlags::from(create_flags);
+
+            if link_time_optimization {
+                flags |= ash::vk::PipelineCreateFlags::LINK_TIME_OPTIMIZATION_EXT;
+            }
+
+            let mut create_info_vk = ash::vk::GraphicsPipelineCreateInfo {
+                p_next: &library_info_vk as *const _ as *const _,
+                flags,
+                layout: layout.handle(),
+                render_pass: render_pass_vk,
+                subpass: subpass_vk,
+                base_pipeline_handle: ash::vk::Pipeline::null(),
+                base_pipeline_index: -1,
+                ..Default::default()
+            };
+
+            if let Some(info) = rendering_create_info_vk.as_mut() {
+                info.p_next = create_info_vk.p_next;
+                create_info_vk.p_next = info as *const _ as *const _;
+            }
+
+            if let Some(info) = creation_feedback_info_vk.as_mut() {
+                info.p_next = create_info_vk.p_next;
+                create_info_vk.p_next = info as *const _ as *const _;
+            }
+
+            let cache_handle = match cache.as_ref() {
+                Some(cache) => cache.handle(),
+                None => ash::vk::PipelineCache::null(),
+            };
+
+            let fns = device.fns();
+            let mut output = MaybeUninit::uninit();
+            let result = (fns.v1_0.create_graphics_pipelines)(
+                device.handle(),
+                cache_handle,
+                1,
+                &create_info_vk,
+                device.allocation_callbacks(),
+                output.as_mut_ptr(),
+            );
+
+            if result == ash::vk::Result::PIPELINE_COMPILE_REQUIRED {
+                return Err(GraphicsPipelineCreationError::PipelineCompileRequired);
+            }
+
+            result.result().map_err(VulkanError::from)?;
+
+            output.assume_init()
+        };
+
+        let mut shaders = HashMap::default();
+        let mut descriptor_binding_requirements: HashMap<
+            (u32, u32),
+            DescriptorBindingRequirements,
+        > = HashMap::default();
+        let mut dynamic_state = HashMap::default();
+
+        for library in &libraries {
+            shaders.extend(
+                library
+                    .shaders
+                    .iter()
+                    .map(|(&stage, &shader)| (stage, shader)),
+            );
+            dynamic_state.extend(library.dynamic_state.iter().map(|(&k, &v)| (k, v)));
+
+            for (&loc, reqs) in &library.descriptor_binding_requirements {
+                match descriptor_binding_requirements.entry(loc) {
+                    Entry::Occupied(entry) => {
+                        // Checked by `validate_link`.
+                        let _ = entry.into_mut().merge(reqs);
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(reqs.clone());
+                    }
+                }
+            }
+        }
+
+        let num_used_descriptor_sets = descriptor_binding_requirements
+            .keys()
+            .map(|loc| loc.0)
+            .max()
+            .map(|x| x + 1)
+            .unwrap_or(0);
+
+        Ok(Arc::new(GraphicsPipeline {
+            handle,
+            device,
+            id: GraphicsPipeline::next_id(),
+            flags: create_flags,
+            layout,
+            render_pass,
+
+            shaders,
+            descriptor_binding_requirements,
+            num_used_descriptor_sets,
+            fragment_tests_stages: fragment_shader
+                .and_then(|library| library.fragment_tests_stages),
+            transform_feedback_outputs: pre_rasterization.transform_feedback_outputs.clone(),
+            vertex_shader_input_interface: pre_rasterization.vertex_shader_input_interface.clone(),
+
+            vertex_input_state: vertex_input
+                .and_then(|library| library.vertex_input_state.clone())
+                .unwrap_or_default(),
+            input_assembly_state: vertex_input
+                .and_then(|library| library.input_assembly_state)
+                .unwrap_or_default(),
+            tessellation_state: pre_rasterization.tessellation_state,
+            viewport_state: pre_rasterization.viewport_state.clone(),
+            discard_rectangle_state: pre_rasterization.discard_rectangle_state.clone(),
+            rasterization_state: pre_rasterization.rasterization_state.clone().unwrap(),
+            multisample_state: fragment_output.and_then(|library| library.multisample_state),
+            depth_stencil_state: fragment_shader
+                .and_then(|library| library.depth_stencil_state.clone()),
+            color_blend_state: fragment_output
+                .and_then(|library| library.color_blend_state.clone()),
+            dynamic_state,
+            creation_feedback: creation_feedback_info_vk
+                .and_then(|_| PipelineCreationFeedback::from_vulkan(creation_feedback_vk)),
+            stage_creation_feedback: HashMap::default(),
+            libraries,
+        }))
+    }
+}
+
+// Returns whether two libraries were created for compatible render passes.
+fn render_pass_compatible(a: &PipelineRenderPassType, b: &PipelineRenderPassType) -> bool {
+    match (a, b) {
+        (
+            PipelineRenderPassType::BeginRenderPass(a),
+            PipelineRenderPassType::BeginRenderPass(b),
+        ) => a.index() == b.index() && a.render_pass().is_compatible_with(b.render_pass()),
+        (PipelineRenderPassType::BeginRendering(a), PipelineRenderPassType::BeginRendering(b)) => {
+            a.view_mask == b.view_mask
+                && a.color_attachment_formats == b.color_attachment_formats
+                && a.depth_attachment_format == b.depth_attachment_format
+                && a.stencil_attachment_format == b.stencil_attachment_format
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        GraphicsPipelineLibrary, GraphicsPipelineLibraryCreateInfo, GraphicsPipelineLibraryFlags,
+        GraphicsPipelineLinkCreateInfo,
+    };
+    use crate::{
+        descriptor_set::layout::DescriptorSetLayout,
+        device::Device,
+        format::Format,
+        pipeline::{
+            graphics::{viewport::ViewportState, GraphicsPipeline, GraphicsPipelineCreationError},
+            layout::{PipelineLayout, PipelineLayoutCreateInfo},
+            PipelineCreateFlags,
+        },
+        render_pass::{RenderPass, Subpass},
+        shader::ShaderModule,
+        RequiresOneOf,
+    };
+    use std::sync::Arc;
+
+    fn render_pass(device: Arc<Device>, format: Format) -> Arc<RenderPass> {
+        single_pass_renderpass!(device,
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: DontCare,
+                    format: format,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        )
+        .unwrap()
+    }
+
+    fn library(
+        device: Arc<Device>,
+        layout: Arc<PipelineLayout>,
+        render_pass: Arc<RenderPass>,
+        flags: GraphicsPipelineLibraryFlags,
+    ) -> Arc<GraphicsPipelineLibrary> {
+        /*
+        #version 450
+
+        void main() {}
+        */
+        const VERTEX_MODULE: [u8; 116] = [
+            3, 2, 35, 7, 0, 0, 1, 0, 0, 0, 0, 0, 5, 0, 0, 0, 0, 0, 0, 0, 17, 0, 2, 0, 1, 0, 0, 0,
+            14, 0, 3, 0, 0, 0, 0, 0, 1, 0, 0, 0, 15, 0, 5, 0, 0, 0, 0, 0, 3, 0, 0, 0, 109, 97, 105,
+            110, 0, 0, 0, 0, 19, 0, 2, 0, 1, 0, 0, 0, 33, 0, 3, 0, 2, 0, 0, 0, 1, 0, 0, 0, 54, 0,
+            5, 0, 1, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 248, 0, 2, 0, 4, 0, 0, 0, 253, 0,
+            1, 0, 56, 0, 1, 0,
+        ];
+        /*
+        #version 450
+
+        void main() {}
+        */
+        const FRAGMENT_MODULE: [u8; 128] = [
+            3, 2, 35, 7, 0, 0, 1, 0, 0, 0, 0, 0, 5, 0, 0, 0, 0, 0, 0, 0, 17, 0, 2, 0, 1, 0, 0, 0,
+            14, 0, 3, 0, 0, 0, 0, 0, 1, 0, 0, 0, 15, 0, 5, 0, 4, 0, 0, 0, 3, 0, 0, 0, 109, 97, 105,
+            110, 0, 0, 0, 0, 16, 0, 3, 0, 3, 0, 0, 0, 7, 0, 0, 0, 19, 0, 2, 0, 1, 0, 0, 0, 33, 0,
+            3, 0, 2, 0, 0, 0, 1, 0, 0, 0, 54, 0, 5, 0, 1, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0,
+            0, 248, 0, 2, 0, 4, 0, 0, 0, 253, 0, 1, 0, 56, 0, 1, 0,
+        ];
+
+        let vertex_module =
+            unsafe { ShaderModule::from_bytes(device.clone(), &VERTEX_MODULE).unwrap() };
+        let fragment_module =
+            unsafe { ShaderModule::from_bytes(device.clone(), &FRAGMENT_MODULE).unwrap() };
+        let create_info = GraphicsPipelineLibraryCreateInfo::flags(flags);
+        let builder = GraphicsPipeline::start()
+            .viewport_state(ViewportState::viewport_dynamic_scissor_irrelevant())
+            .render_pass(Subpass::from(render_pass, 0).unwrap());
+
+        if flags.intersects(GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS) {
+            builder
+                .vertex_shader(vertex_module.entry_point("main").unwrap(), ())
+                .build_library(device, layout, create_info)
+        } else if flags.intersects(GraphicsPipelineLibraryFlags::FRAGMENT_SHADER) {
+            builder
+                .fragment_shader(fragment_module.entry_point("main").unwrap(), ())
+                .build_library(device, layout, create_info)
+        } else {
+            builder.build_library(device, layout, create_info)
+        }
+        .unwrap()
+    }
+
+    #[test]
+    fn library_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        match GraphicsPipeline::start()
+            .render_pass(
+                Subpass::from(render_pass(device.clone(), Format::R8G8B8A8_UNORM), 0).unwrap(),
+            )
+            .build_library(
+                device,
+                layout,
+                GraphicsPipelineLibraryCreateInfo::flags(
+                    GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE,
+                ),
+            ) {
+            Err(GraphicsPipelineCreationError::RequirementNotMet {
+                requires_one_of:
+                    RequiresOneOf {
+                        features: ["graphics_pipeline_library"],
+                        ..
+                    },
+                ..
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn link_incomplete() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [khr_pipeline_library, ext_graphics_pipeline_library],
+            graphics_pipeline_library
+        );
+
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+        let render_pass = render_pass(device.clone(), Format::R8G8B8A8_UNORM);
+        let vertex_input = library(
+            device.clone(),
+            layout,
+            render_pass,
+            GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE,
+        );
+
+        match GraphicsPipeline::link(
+            device,
+            GraphicsPipelineLinkCreateInfo::libraries([vertex_input]),
+        ) {
+            Err(GraphicsPipelineCreationError::LibrariesIncomplete { missing }) => {
+                assert_eq!(
+                    missing,
+                    GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS,
+                );
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn link_flags() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [khr_pipeline_library, ext_graphics_pipeline_library],
+            graphics_pipeline_library
+        );
+
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+        let render_pass = render_pass(device.clone(), Format::R8G8B8A8_UNORM);
+        let libraries = [
+            GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE,
+            GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS,
+            GraphicsPipelineLibraryFlags::FRAGMENT_SHADER,
+            GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE,
+        ]
+        .map(|flags| library(device.clone(), layout.clone(), render_pass.clone(), flags));
+
+        let pipeline = GraphicsPipeline::link(
+            device,
+            GraphicsPipelineLinkCreateInfo {
+                flags: PipelineCreateFlags::DISABLE_OPTIMIZATION,
+                ..GraphicsPipelineLinkCreateInfo::libraries(libraries)
+            },
+        )
+        .unwrap();
+
+        assert_eq!(pipeline.flags(), PipelineCreateFlags::DISABLE_OPTIMIZATION);
+    }
+
+    #[test]
+    fn link_duplicate() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [khr_pipeline_library, ext_graphics_pipeline_library],
+            graphics_pipeline_library
+        );
+
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+        let render_pass = render_pass(device.clone(), Format::R8G8B8A8_UNORM);
+        let libraries = [
+            GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE,
+            GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS,
+            GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE,
+        ]
+        .map(|flags| library(device.clone(), layout.clone(), render_pass.clone(), flags));
+
+        match GraphicsPipeline::link(device, GraphicsPipelineLinkCreateInfo::libraries(libraries)) {
+            Err(GraphicsPipelineCreationError::LibraryFlagsOverlap { flags }) => {
+                assert_eq!(flags, GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE);
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn link_render_pass_incompatible() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [khr_pipeline_library, ext_graphics_pipeline_library],
+            graphics_pipeline_library
+        );
+
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+        let render_pass_a = render_pass(device.clone(), Format::R8G8B8A8_UNORM);
+        let render_pass_b = render_pass(device.clone(), Format::R8_UNORM);
+        let libraries = [
+            (
+                GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE,
+                &render_pass_a,
+            ),
+            (
+                GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS,
+                &render_pass_a,
+            ),
+            (
+                GraphicsPipelineLibraryFlags::FRAGMENT_SHADER,
+                &render_pass_a,
+            ),
+            (
+                GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE,
+                &render_pass_b,
+            ),
+        ]
+        .map(|(flags, render_pass)| {
+            library(device.clone(), layout.clone(), render_pass.clone(), flags)
+        });
+
+        match GraphicsPipeline::link(device, GraphicsPipelineLinkCreateInfo::libraries(libraries)) {
+            Err(GraphicsPipelineCreationError::LibraryRenderPassIncompatible {
+                library_index: 3,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn link_layout_incompatible() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [khr_pipeline_library, ext_graphics_pipeline_library],
+            graphics_pipeline_library
+        );
+
+        let layout_a = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+        let layout_b = PipelineLayout::new(
+            device.clone(),
+            PipelineLayoutCreateInfo {
+                set_layouts: vec![
+                    DescriptorSetLayout::new(device.clone(), Default::default()).unwrap()
+                ],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let render_pass = render_pass(device.clone(), Format::R8G8B8A8_UNORM);
+        let libraries = [
+            (
+                GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE,
+                &layout_a,
+            ),
+            (
+                GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS,
+                &layout_a,
+            ),
+            (GraphicsPipelineLibraryFlags::FRAGMENT_SHADER, &layout_b),
+            (
+                GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE,
+                &layout_a,
+            ),
+        ]
+        .map(|(flags, layout)| library(device.clone(), layout.clone(), render_pass.clone(), flags));
+
+        match GraphicsPipeline::link(device, GraphicsPipelineLinkCreateInfo::libraries(libraries)) {
+            Err(GraphicsPipelineCreationError::LibraryLayoutIncompatible { library_index: 2 }) => {
+                ()
+            }
+            _ => panic!(),
+        }
+    }
+}
@@ -26,6 +26,10 @@
 //!    newly created vertices.
 //! 4. (Optional) Geometry shading: whole primitives are fed as input and processed into a new set
 //!    of output primitives.
+//!
+//!    Alternatively, steps 1 to 4 can be replaced by mesh shading: an optional task shader
+//!    generates work for a mesh shader, which produces primitives directly without any vertex
+//!    input.
 //! 5. Vertex post-processing, including:
 //!    - Clipping primitives to the view frustum and user-defined clipping planes.
 //!    - Perspective division.
@@ -56,7 +60,15 @@
 //! constants, and setting any dynamic state that the pipeline may need. Then you issue a `draw`
 //! command.
 
-pub use self::{builder::GraphicsPipelineBuilder, creation_error::GraphicsPipelineCreationError};
+pub(crate) use self::builder::{validate_vertex_input_attributes, VertexInputAttributeError};
+pub use self::{
+    builder::GraphicsPipelineBuilder,
+    creation_error::GraphicsPipelineCreationError,
+    library::{
+        GraphicsPipelineLibrary, GraphicsPipelineLibraryCreateInfo, GraphicsPipelineLibraryFlags,
+        GraphicsPipelineLinkCreateInfo,
+    },
+};
 use self::{
     color_blend::ColorBlendState, depth_stencil::DepthStencilState,
     discard_rectangle::DiscardRectangleState, input_assembly::InputAssemblyState,
@@ -64,17 +76,26 @@ use self::{
     render_pass::PipelineRenderPassType, tessellation::TessellationState,
     vertex_input::VertexInputState, viewport::ViewportState,
 };
-use super::{DynamicState, Pipeline, PipelineBindPoint, PipelineLayout};
+use super::{
+    executable::{
+        self, PipelineExecutableError, PipelineExecutableInternalRepresentation,
+        PipelineExecutableProperties, PipelineExecutableStatistic,
+    },
+    DynamicState, Pipeline, PipelineBindPoint, PipelineCreateFlags, PipelineCreationFeedback,
+    PipelineLayout,
+};
 use crate::{
     device::{Device, DeviceOwned},
-    shader::{DescriptorBindingRequirements, FragmentTestsStages, ShaderStage},
+    shader::{
+        DescriptorBindingRequirements, FragmentTestsStages, ShaderInterface, ShaderStage,
+        ShaderTransformFeedbackOutput,
+    },
     VulkanObject,
 };
 use ahash::HashMap;
 use std::{
     fmt::{Debug, Error as FmtError, Formatter},
     num::NonZeroU64,
-    ptr,
     sync::Arc,
 };
 
@@ -84,6 +105,7 @@ mod creation_error;
 pub mod depth_stencil;
 pub mod discard_rectangle;
 pub mod input_assembly;
+mod library;
 pub mod multisample;
 pub mod rasterization;
 pub mod render_pass;
@@ -101,6 +123,7 @@ pub struct GraphicsPipeline {
     handle: ash::vk::Pipeline,
     device: Arc<Device>,
     id: NonZeroU64,
+    flags: PipelineCreateFlags,
     layout: Arc<PipelineLayout>,
     render_pass: PipelineRenderPassType,
 
@@ -109,6 +132,8 @@ pub struct GraphicsPipeline {
     descriptor_binding_requirements: HashMap<(u32, u32), DescriptorBindingRequirements>,
     num_used_descriptor_sets: u32,
     fragment_tests_stages: Option<FragmentTestsStages>,
+    transform_feedback_outputs: Option<Vec<ShaderTransformFeedbackOutput>>,
+    vertex_shader_input_interface: Option<ShaderInterface>,
 
     vertex_input_state: VertexInputState,
     input_assembly_state: InputAssemblyState,
@@ -120,6 +145,11 @@ pub struct GraphicsPipeline {
     depth_stencil_state: Option<DepthStencilState>,
     color_blend_state: Option<ColorBlendState>,
     dynamic_state: HashMap<DynamicState, bool>,
+    creation_feedback: Option<PipelineCreationFeedback>,
+    stage_creation_feedback: HashMap<ShaderStage, PipelineCreationFeedback>,
+
+    // The libraries that the pipeline was linked from, if any.
+    libraries: Vec<Arc<GraphicsPipelineLibrary>>,
 }
 
 impl GraphicsPipeline {
@@ -132,12 +162,16 @@ impl GraphicsPipeline {
         'static,
         'static,
         'static,
+        'static,
+        'static,
         VertexInputState,
         (),
         (),
         (),
         (),
         (),
+        (),
+        (),
     > {
         GraphicsPipelineBuilder::new()
     }
@@ -148,6 +182,12 @@ impl GraphicsPipeline {
         &self.device
     }
 
+    /// Returns the flags that the pipeline was created with.
+    #[inline]
+    pub fn flags(&self) -> PipelineCreateFlags {
+        self.flags
+    }
+
     /// Returns the render pass this graphics pipeline is rendering to.
     #[inline]
     pub fn render_pass(&self) -> &PipelineRenderPassType {
@@ -167,6 +207,8 @@ impl GraphicsPipeline {
     }
 
     /// Returns the vertex input state used to create this pipeline.
+    ///
+    /// If the vertex input state is dynamic, this is empty.
     #[inline]
     pub fn vertex_input_state(&self) -> &VertexInputState {
         &self.vertex_input_state
@@ -235,11 +277,83 @@ impl GraphicsPipeline {
         self.dynamic_state.iter().map(|(k, v)| (*k, *v))
     }
 
+    /// Returns feedback about the creation of the pipeline, if the implementation provided it.
+    #[inline]
+    pub fn creation_feedback(&self) -> Option<&PipelineCreationFeedback> {
+        self.creation_feedback.as_ref()
+    }
+
+    /// Returns feedback about the creation of a shader stage of the pipeline, if the
+    /// implementation provided it.
+    ///
+    /// Pipelines that were linked from libraries have no feedback for individual shader stages.
+    #[inline]
+    pub fn stage_creation_feedback(&self, stage: ShaderStage) -> Option<&PipelineCreationFeedback> {
+        self.stage_creation_feedback.get(&stage)
+    }
+
+    /// Returns the properties of the executables that the pipeline was compiled into.
+    ///
+    /// The [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info)
+    /// feature must be enabled on the device.
+    #[inline]
+    pub fn executable_properties(
+        &self,
+    ) -> Result<Vec<PipelineExecutableProperties>, PipelineExecutableError> {
+        executable::executable_properties(self)
+    }
+
+    /// Returns the statistics of the executable with index `executable_index`.
+    ///
+    /// The [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info)
+    /// feature must be enabled on the device, and the pipeline must have been created with
+    /// [`PipelineCreateFlags::CAPTURE_STATISTICS`].
+    #[inline]
+    pub fn executable_statistics(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableStatistic>, PipelineExecutableError> {
+        executable::executable_statistics(self, self.flags, executable_index)
+    }
+
+    /// Returns the internal representations of the executable with index `executable_index`.
+    ///
+    /// The [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info)
+    /// feature must be enabled on the device, and the pipeline must have been created with
+    /// [`PipelineCreateFlags::CAPTURE_INTERNAL_REPRESENTATIONS`].
+    #[inline]
+    pub fn executable_internal_representations(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableInternalRepresentation>, PipelineExecutableError> {
+        executable::executable_internal_representations(self, self.flags, executable_index)
+    }
+
+    /// Returns the libraries that the pipeline was linked from, if it was created with
+    /// [`GraphicsPipeline::link`].
+    #[inline]
+    pub fn libraries(&self) -> &[Arc<GraphicsPipelineLibrary>] {
+        &self.libraries
+    }
+
     /// If the pipeline has a fragment shader, returns the fragment tests stages used.
     #[inline]
     pub fn fragment_tests_stages(&self) -> Option<FragmentTestsStages> {
         self.fragment_tests_stages
     }
+
+    /// If the last pre-rasterization shader stage of the pipeline declares the `Xfb` execution
+    /// mode, returns the outputs of that stage that are captured by transform feedback.
+    #[inline]
+    pub fn transform_feedback_outputs(&self) -> Option<&[ShaderTransformFeedbackOutput]> {
+        self.transform_feedback_outputs.as_deref()
+    }
+
+    /// If the pipeline has a vertex shader, returns its input interface.
+    #[inline]
+    pub(crate) fn vertex_shader_input_interface(&self) -> Option<&ShaderInterface> {
+        self.vertex_shader_input_interface.as_ref()
+    }
 }
 
 impl Pipeline for GraphicsPipeline {
@@ -293,9 +407,14 @@ impl Drop for GraphicsPipeline {
     fn drop(&mut self) {
         unsafe {
             let fns = self.device.fns();
-            (fns.v1_0.destroy_pipeline)(self.device.handle(), self.handle, ptr::null());
+            (fns.v1_0.destroy_pipeline)(
+                self.device.handle(),
+                self.handle,
+                self.device.allocation_callbacks(),
+            );
         }
     }
 }
 
 crate::impl_id_counter!(GraphicsPipeline);
+crate::impl_id_counter!(GraphicsPipelineLibrary);
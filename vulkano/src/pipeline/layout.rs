@@ -75,7 +75,6 @@ use std::{
     fmt::{Display, Error as FmtError, Formatter},
     mem::MaybeUninit,
     num::NonZeroU64,
-    ptr,
     sync::Arc,
 };
 
@@ -85,6 +84,7 @@ pub struct PipelineLayout {
     handle: ash::vk::PipelineLayout,
     device: Arc<Device>,
     id: NonZeroU64,
+    imported_handle: bool,
 
     set_layouts: Vec<Arc<DescriptorSetLayout>>,
     push_constant_ranges: Vec<PushConstantRange>,
@@ -131,6 +131,7 @@ impl PipelineLayout {
             handle,
             device,
             id: Self::next_id(),
+            imported_handle: false,
             set_layouts,
             push_constant_ranges,
             push_constant_ranges_disjoint,
@@ -212,6 +213,7 @@ impl PipelineLayout {
             let mut num_sampled_images = Counter::default();
             let mut num_storage_images = Counter::default();
             let mut num_input_attachments = Counter::default();
+            let mut num_acceleration_structures = Counter::default();
             let mut push_descriptor_set = None;
 
             for (set_num, set_layout) in set_layouts.iter().enumerate() {
@@ -270,6 +272,10 @@ impl PipelineLayout {
                             num_input_attachments
                                 .increment(layout_binding.descriptor_count, layout_binding.stages);
                         }
+                        DescriptorType::AccelerationStructure => {
+                            num_acceleration_structures
+                                .increment(layout_binding.descriptor_count, layout_binding.stages);
+                        }
                     }
                 }
             }
@@ -351,6 +357,22 @@ impl PipelineLayout {
                 );
             }
 
+            // VUID-VkPipelineLayoutCreateInfo-descriptorType-03571
+            if let Some(max_per_stage_descriptor_acceleration_structures) =
+                properties.max_per_stage_descriptor_acceleration_structures
+            {
+                if num_acceleration_structures.max_per_stage()
+                    > max_per_stage_descriptor_acceleration_structures
+                {
+                    return Err(
+                        PipelineLayoutCreationError::MaxPerStageDescriptorAccelerationStructuresExceeded {
+                            provided: num_acceleration_structures.max_per_stage(),
+                            max_supported: max_per_stage_descriptor_acceleration_structures,
+                        },
+                    );
+                }
+            }
+
             // VUID-VkPipelineLayoutCreateInfo-descriptorType-03028
             if num_samplers.total > properties.max_descriptor_set_samplers {
                 return Err(
@@ -430,6 +452,20 @@ impl PipelineLayout {
                     },
                 );
             }
+
+            // VUID-VkPipelineLayoutCreateInfo-descriptorType-03573
+            if let Some(max_descriptor_set_acceleration_structures) =
+                properties.max_descriptor_set_acceleration_structures
+            {
+                if num_acceleration_structures.total > max_descriptor_set_acceleration_structures {
+                    return Err(
+                        PipelineLayoutCreationError::MaxDescriptorSetAccelerationStructuresExceeded {
+                            provided: num_acceleration_structures.total,
+                            max_supported: max_descriptor_set_acceleration_structures,
+                        },
+                    );
+                }
+            }
         }
 
         /* Check push constant ranges */
@@ -514,7 +550,7 @@ impl PipelineLayout {
             (fns.v1_0.create_pipeline_layout)(
                 device.handle(),
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -531,6 +567,8 @@ impl PipelineLayout {
     ///
     /// - `handle` must be a valid Vulkan object handle created from `device`.
     /// - `create_info` must match the info used to create the object.
+    /// - `handle` must have been created with a null `pAllocator`, as it is destroyed with a null
+    ///   `pAllocator` when dropped.
     #[inline]
     pub unsafe fn from_handle(
         device: Arc<Device>,
@@ -550,6 +588,7 @@ impl PipelineLayout {
             handle,
             device,
             id: Self::next_id(),
+            imported_handle: true,
             set_layouts,
             push_constant_ranges,
             push_constant_ranges_disjoint,
@@ -673,7 +712,12 @@ impl Drop for PipelineLayout {
     fn drop(&mut self) {
         unsafe {
             let fns = self.device.fns();
-            (fns.v1_0.destroy_pipeline_layout)(self.device.handle(), self.handle, ptr::null());
+            (fns.v1_0.destroy_pipeline_layout)(
+                self.device.handle(),
+                self.handle,
+                self.device
+                    .destroy_allocation_callbacks(self.imported_handle),
+            );
         }
     }
 }
@@ -755,6 +799,12 @@ pub enum PipelineLayoutCreationError {
     /// limit.
     MaxDescriptorSetInputAttachmentsExceeded { provided: u32, max_supported: u32 },
 
+    /// The `set_layouts` contain more [`DescriptorType::AccelerationStructure`] descriptors than
+    /// the
+    /// [`max_descriptor_set_acceleration_structures`](crate::device::Properties::max_descriptor_set_acceleration_structures)
+    /// limit.
+    MaxDescriptorSetAccelerationStructuresExceeded { provided: u32, max_supported: u32 },
+
     /// The `set_layouts` contain more bound resources in a single stage than the
     /// [`max_per_stage_resources`](crate::device::Properties::max_per_stage_resources)
     /// limit.
@@ -797,6 +847,12 @@ pub enum PipelineLayoutCreationError {
     /// limit.
     MaxPerStageDescriptorInputAttachmentsExceeded { provided: u32, max_supported: u32 },
 
+    /// The `set_layouts` contain more [`DescriptorType::AccelerationStructure`] descriptors in a
+    /// single stage than the
+    /// [`max_per_stage_descriptor_acceleration_structures`](crate::device::Properties::max_per_stage_descriptor_acceleration_structures)
+    /// limit.
+    MaxPerStageDescriptorAccelerationStructuresExceeded { provided: u32, max_supported: u32 },
+
     /// An element in `push_constant_ranges` has an `offset + size` greater than the
     /// [`max_push_constants_size`](crate::device::Properties::max_push_constants_size) limit.
     MaxPushConstantsSizeExceeded { provided: u32, max_supported: u32 },
@@ -913,6 +969,15 @@ impl Display for PipelineLayoutCreationError {
                 than the `max_descriptor_set_input_attachments` limit ({})",
                 provided, max_supported,
             ),
+            Self::MaxDescriptorSetAccelerationStructuresExceeded {
+                provided,
+                max_supported,
+            } => write!(
+                f,
+                "the `set_layouts` contain more `DescriptorType::AccelerationStructure` \
+                descriptors ({}) than the `max_descriptor_set_acceleration_structures` limit ({})",
+                provided, max_supported,
+            ),
             Self::MaxPerStageResourcesExceeded {
                 provided,
                 max_supported,
@@ -983,6 +1048,16 @@ impl Display for PipelineLayoutCreationError {
                 ({})",
                 provided, max_supported,
             ),
+            Self::MaxPerStageDescriptorAccelerationStructuresExceeded {
+                provided,
+                max_supported,
+            } => write!(
+                f,
+                "the `set_layouts` contain more `DescriptorType::AccelerationStructure` \
+                descriptors ({}) in a single stage than the \
+                `max_per_stage_descriptor_acceleration_structures` limit ({})",
+                provided, max_supported,
+            ),
             Self::MaxPushConstantsSizeExceeded {
                 provided,
                 max_supported,
@@ -17,15 +17,24 @@
 //! the CPU). Consequently it is a CPU-intensive operation that should be performed at
 //! initialization or during a loading screen.
 
-pub use self::{compute::ComputePipeline, graphics::GraphicsPipeline, layout::PipelineLayout};
-use crate::{device::DeviceOwned, macros::vulkan_enum, shader::DescriptorBindingRequirements};
+pub use self::{
+    compute::ComputePipeline, graphics::GraphicsPipeline, layout::PipelineLayout,
+    ray_tracing::RayTracingPipeline,
+};
+use crate::{
+    device::DeviceOwned,
+    macros::{vulkan_bitflags, vulkan_enum},
+    shader::DescriptorBindingRequirements,
+};
 use ahash::HashMap;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 pub mod cache;
 pub mod compute;
+pub mod executable;
 pub mod graphics;
 pub mod layout;
+pub mod ray_tracing;
 
 /// A trait for operations shared between pipeline types.
 pub trait Pipeline: DeviceOwned {
@@ -62,11 +71,10 @@ vulkan_enum! {
     // TODO: document
     Graphics = GRAPHICS,
 
-    /* TODO: enable
     // TODO: document
     RayTracing = RAY_TRACING_KHR {
         device_extensions: [khr_ray_tracing_pipeline, nv_ray_tracing],
-    },*/
+    },
 
     /* TODO: enable
     // TODO: document
@@ -457,3 +465,103 @@ pub enum PartialStateMode<F, D> {
     Fixed(F),
     Dynamic(D),
 }
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags that control how a pipeline is created.
+    PipelineCreateFlags = PipelineCreateFlags(u32);
+
+    /// Disables optimization of the pipeline. This can make creating the pipeline faster, which
+    /// is useful for debug builds.
+    DISABLE_OPTIMIZATION = DISABLE_OPTIMIZATION,
+
+    /// The pipeline can be used as the base pipeline when creating derivative pipelines.
+    ///
+    /// Derivative pipelines are similar to their base pipeline, which may let the
+    /// implementation create them faster.
+    ALLOW_DERIVATIVES = ALLOW_DERIVATIVES,
+
+    /// If creating the pipeline would need shaders to be compiled, because it is not in the
+    /// provided pipeline cache, then creation fails with a `PipelineCompileRequired` error
+    /// instead.
+    ///
+    /// This can be used to check whether a pipeline can be created quickly, and to defer the
+    /// compilation to another thread if not.
+    FAIL_ON_PIPELINE_COMPILE_REQUIRED = FAIL_ON_PIPELINE_COMPILE_REQUIRED {
+        api_version: V1_3,
+        device_extensions: [ext_pipeline_creation_cache_control],
+    },
+
+    /// The implementation captures statistics about the executables that the pipeline is
+    /// compiled into, so that they can be queried with `executable_statistics`.
+    ///
+    /// Capturing statistics can make creating the pipeline slower.
+    CAPTURE_STATISTICS = CAPTURE_STATISTICS_KHR {
+        device_extensions: [khr_pipeline_executable_properties],
+    },
+
+    /// The implementation captures the internal representations of the executables that the
+    /// pipeline is compiled into, so that they can be queried with
+    /// `executable_internal_representations`.
+    ///
+    /// Capturing internal representations can make creating the pipeline slower.
+    CAPTURE_INTERNAL_REPRESENTATIONS = CAPTURE_INTERNAL_REPRESENTATIONS_KHR {
+        device_extensions: [khr_pipeline_executable_properties],
+    },
+}
+
+/// Feedback about the creation of a pipeline, or of one of its shader stages, provided by the
+/// implementation.
+///
+/// Feedback is available if the device API version is at least 1.3, or the
+/// [`ext_pipeline_creation_feedback`](crate::device::DeviceExtensions::ext_pipeline_creation_feedback)
+/// extension is enabled on the device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PipelineCreationFeedback {
+    /// Additional information about the creation.
+    pub flags: PipelineCreationFeedbackFlags,
+
+    /// The time that it took to create the pipeline or shader stage.
+    pub duration: Duration,
+}
+
+impl PipelineCreationFeedback {
+    /// Returns whether the pipeline or shader stage was found in the pipeline cache, so that no
+    /// compilation was needed.
+    #[inline]
+    pub fn is_cache_hit(&self) -> bool {
+        self.flags
+            .intersects(PipelineCreationFeedbackFlags::APPLICATION_PIPELINE_CACHE_HIT)
+    }
+
+    // Returns `None` if the implementation did not provide valid feedback.
+    pub(crate) fn from_vulkan(feedback: ash::vk::PipelineCreationFeedback) -> Option<Self> {
+        let flags = PipelineCreationFeedbackFlags::from(feedback.flags);
+
+        flags
+            .intersects(PipelineCreationFeedbackFlags::VALID)
+            .then(|| PipelineCreationFeedback {
+                flags,
+                duration: Duration::from_nanos(feedback.duration),
+            })
+    }
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags that provide additional information about the creation of a pipeline or shader
+    /// stage.
+    PipelineCreationFeedbackFlags = PipelineCreationFeedbackFlags(u32);
+
+    /// The feedback is valid. This is always set for feedback that vulkano returns.
+    VALID = VALID,
+
+    /// The pipeline cache that was provided when creating the pipeline already contained the
+    /// pipeline or shader stage, so no compilation was needed.
+    APPLICATION_PIPELINE_CACHE_HIT = APPLICATION_PIPELINE_CACHE_HIT,
+
+    /// Creation was accelerated by using the base pipeline of a derivative pipeline.
+    BASE_PIPELINE_ACCELERATION = BASE_PIPELINE_ACCELERATION,
+}
@@ -0,0 +1,1054 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! A pipeline that traces rays through a scene of acceleration structures.
+//!
+//! A ray tracing pipeline consists of a number of shaders, that are grouped together into
+//! *shader groups*. When a ray tracing command is executed, the ray generation shader is invoked
+//! once for each element of the provided dimensions. It can then trace rays through a top-level
+//! [acceleration structure], which invokes the hit shaders of the shader groups that a ray
+//! intersects with, or the miss shader if the ray intersects nothing.
+//!
+//! Which shader group is invoked for which geometry is determined by a *shader binding table*,
+//! which is a buffer holding the opaque handles of the shader groups of a pipeline. The
+//! [`ShaderBindingTable`] type can be used to create such a buffer for a pipeline.
+//!
+//! [acceleration structure]: crate::acceleration_structure
+
+use crate::{
+    buffer::{BufferAccess, BufferDeviceAddressError, BufferUsage, CpuAccessibleBuffer},
+    device::{Device, DeviceOwned},
+    memory::allocator::{AllocationCreationError, MemoryAllocator},
+    pipeline::{
+        cache::PipelineCache,
+        layout::{PipelineLayout, PipelineLayoutSupersetError},
+        Pipeline, PipelineBindPoint,
+    },
+    shader::{
+        DescriptorBindingRequirements, EntryPoint, ShaderStage, SpecializationConstants,
+        SpecializationMapEntry,
+    },
+    DeviceSize, OomError, RequirementNotMet, RequiresOneOf, VulkanError, VulkanObject,
+};
+use ahash::HashMap;
+use smallvec::SmallVec;
+use std::{
+    collections::hash_map::Entry,
+    error::Error,
+    fmt::{Debug, Display, Error as FmtError, Formatter},
+    mem::{size_of_val, MaybeUninit},
+    num::NonZeroU64,
+    ptr, slice,
+    sync::Arc,
+};
+
+/// A pipeline object that describes to the Vulkan implementation how it should perform ray
+/// tracing operations.
+pub struct RayTracingPipeline {
+    handle: ash::vk::Pipeline,
+    device: Arc<Device>,
+    id: NonZeroU64,
+    layout: Arc<PipelineLayout>,
+    descriptor_binding_requirements: HashMap<(u32, u32), DescriptorBindingRequirements>,
+    num_used_descriptor_sets: u32,
+
+    stages: Vec<ShaderStage>,
+    groups: Vec<RayTracingShaderGroupCreateInfo>,
+    max_pipeline_ray_recursion_depth: u32,
+}
+
+impl RayTracingPipeline {
+    /// Creates a new `RayTracingPipeline`.
+    ///
+    /// The [`ray_tracing_pipeline`] feature must be enabled on the device.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.layout` or `cache` is not owned by `device`.
+    ///
+    /// [`ray_tracing_pipeline`]: crate::device::Features::ray_tracing_pipeline
+    pub fn new(
+        device: Arc<Device>,
+        create_info: RayTracingPipelineCreateInfo<'_>,
+        cache: Option<Arc<PipelineCache>>,
+    ) -> Result<Arc<RayTracingPipeline>, RayTracingPipelineCreationError> {
+        Self::validate_new(&device, &create_info, cache.as_deref())?;
+
+        unsafe { Ok(Self::new_unchecked(device, create_info, cache)?) }
+    }
+
+    fn validate_new(
+        device: &Device,
+        create_info: &RayTracingPipelineCreateInfo<'_>,
+        _cache: Option<&PipelineCache>,
+    ) -> Result<(), RayTracingPipelineCreationError> {
+        let &RayTracingPipelineCreateInfo {
+            ref stages,
+            ref groups,
+            max_pipeline_ray_recursion_depth,
+            ref layout,
+            _ne: _,
+        } = create_info;
+
+        // VUID-vkCreateRayTracingPipelinesKHR-rayTracingPipeline-03586
+        if !device.enabled_features().ray_tracing_pipeline {
+            return Err(RayTracingPipelineCreationError::RequirementNotMet {
+                required_for: "`RayTracingPipeline::new`",
+                requires_one_of: RequiresOneOf {
+                    features: &["ray_tracing_pipeline"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-VkRayTracingPipelineCreateInfoKHR-commonparent
+        assert_eq!(device, layout.device().as_ref());
+
+        let properties = device.physical_device().properties();
+
+        // VUID-VkRayTracingPipelineCreateInfoKHR-maxPipelineRayRecursionDepth-03589
+        let max_ray_recursion_depth = properties.max_ray_recursion_depth.unwrap_or(0);
+
+        if max_pipeline_ray_recursion_depth > max_ray_recursion_depth {
+            return Err(
+                RayTracingPipelineCreationError::MaxRayRecursionDepthExceeded {
+                    provided: max_pipeline_ray_recursion_depth,
+                    max_supported: max_ray_recursion_depth,
+                },
+            );
+        }
+
+        let mut has_raygen = false;
+
+        for (stage_index, stage) in stages.iter().enumerate() {
+            let &RayTracingPipelineShaderStage {
+                ref entry_point,
+                ref specialization_map_entries,
+                specialization_data: _,
+                _ne: _,
+            } = stage;
+
+            assert_eq!(device, entry_point.module().device().as_ref());
+
+            // VUID-VkRayTracingPipelineCreateInfoKHR-stage-06899
+            match ShaderStage::from(*entry_point.execution()) {
+                ShaderStage::Raygen => has_raygen = true,
+                ShaderStage::AnyHit
+                | ShaderStage::ClosestHit
+                | ShaderStage::Miss
+                | ShaderStage::Intersection
+                | ShaderStage::Callable => (),
+                _ => {
+                    return Err(RayTracingPipelineCreationError::ShaderStageInvalid {
+                        stage_index: stage_index as u32,
+                    })
+                }
+            }
+
+            // VUID-VkRayTracingPipelineCreateInfoKHR-layout-03427
+            layout.ensure_compatible_with_shader(
+                entry_point.descriptor_binding_requirements(),
+                entry_point.push_constant_requirements(),
+            )?;
+
+            for (constant_id, reqs) in entry_point.specialization_constant_requirements() {
+                let map_entry = specialization_map_entries
+                    .iter()
+                    .find(|desc| desc.constant_id == constant_id)
+                    .ok_or(RayTracingPipelineCreationError::IncompatibleSpecializationConstants)?;
+
+                if map_entry.size as DeviceSize != reqs.size {
+                    return Err(
+                        RayTracingPipelineCreationError::IncompatibleSpecializationConstants,
+                    );
+                }
+            }
+        }
+
+        // VUID-VkRayTracingPipelineCreateInfoKHR-stage-03425
+        if !has_raygen {
+            return Err(RayTracingPipelineCreationError::NoRayGenerationShader);
+        }
+
+        let check_stage = |group_index: usize,
+                           stage_index: u32,
+                           allowed: &[ShaderStage]|
+         -> Result<(), RayTracingPipelineCreationError> {
+            let stage = stages.get(stage_index as usize).ok_or(
+                RayTracingPipelineCreationError::ShaderGroupStageIndexOutOfRange {
+                    group_index: group_index as u32,
+                    stage_index,
+                },
+            )?;
+
+            if !allowed.contains(&ShaderStage::from(*stage.entry_point.execution())) {
+                return Err(RayTracingPipelineCreationError::ShaderGroupStageInvalid {
+                    group_index: group_index as u32,
+                    stage_index,
+                });
+            }
+
+            Ok(())
+        };
+
+        for (group_index, group) in groups.iter().enumerate() {
+            match *group {
+                RayTracingShaderGroupCreateInfo::General { general_shader } => {
+                    // VUID-VkRayTracingShaderGroupCreateInfoKHR-type-03474
+                    check_stage(
+                        group_index,
+                        general_shader,
+                        &[
+                            ShaderStage::Raygen,
+                            ShaderStage::Miss,
+                            ShaderStage::Callable,
+                        ],
+                    )?;
+                }
+                RayTracingShaderGroupCreateInfo::TrianglesHit {
+                    closest_hit_shader,
+                    any_hit_shader,
+                } => {
+                    // VUID-VkRayTracingShaderGroupCreateInfoKHR-closestHitShader-03477
+                    if let Some(closest_hit_shader) = closest_hit_shader {
+                        check_stage(group_index, closest_hit_shader, &[ShaderStage::ClosestHit])?;
+                    }
+
+                    // VUID-VkRayTracingShaderGroupCreateInfoKHR-anyHitShader-03479
+                    if let Some(any_hit_shader) = any_hit_shader {
+                        check_stage(group_index, any_hit_shader, &[ShaderStage::AnyHit])?;
+                    }
+                }
+                RayTracingShaderGroupCreateInfo::ProceduralHit {
+                    intersection_shader,
+                    closest_hit_shader,
+                    any_hit_shader,
+                } => {
+                    // VUID-VkRayTracingShaderGroupCreateInfoKHR-type-03476
+                    check_stage(
+                        group_index,
+                        intersection_shader,
+                        &[ShaderStage::Intersection],
+                    )?;
+
+                    // VUID-VkRayTracingShaderGroupCreateInfoKHR-closestHitShader-03477
+                    if let Some(closest_hit_shader) = closest_hit_shader {
+                        check_stage(group_index, closest_hit_shader, &[ShaderStage::ClosestHit])?;
+                    }
+
+                    // VUID-VkRayTracingShaderGroupCreateInfoKHR-anyHitShader-03479
+                    if let Some(any_hit_shader) = any_hit_shader {
+                        check_stage(group_index, any_hit_shader, &[ShaderStage::AnyHit])?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn new_unchecked(
+        device: Arc<Device>,
+        create_info: RayTracingPipelineCreateInfo<'_>,
+        cache: Option<Arc<PipelineCache>>,
+    ) -> Result<Arc<RayTracingPipeline>, VulkanError> {
+        let RayTracingPipelineCreateInfo {
+            stages,
+            groups,
+            max_pipeline_ray_recursion_depth,
+            layout,
+            _ne: _,
+        } = create_info;
+
+        let specialization_infos_vk: SmallVec<[_; 8]> = stages
+            .iter()
+            .map(|stage| ash::vk::SpecializationInfo {
+                map_entry_count: stage.specialization_map_entries.len() as u32,
+                p_map_entries: stage.specialization_map_entries.as_ptr() as *const _,
+                data_size: stage.specialization_data.len(),
+                p_data: stage.specialization_data.as_ptr() as *const _,
+            })
+            .collect();
+
+        let stages_vk: SmallVec<[_; 8]> = stages
+            .iter()
+            .zip(&specialization_infos_vk)
+            .map(
+                |(stage, specialization_info_vk)| ash::vk::PipelineShaderStageCreateInfo {
+                    flags: ash::vk::PipelineShaderStageCreateFlags::empty(),
+                    stage: ShaderStage::from(*stage.entry_point.execution()).into(),
+                    module: stage.entry_point.module().handle(),
+                    p_name: stage.entry_point.name().as_ptr(),
+                    p_specialization_info: if specialization_info_vk.data_size == 0 {
+                        ptr::null()
+                    } else {
+                        specialization_info_vk
+                    },
+                    ..Default::default()
+                },
+            )
+            .collect();
+
+        let groups_vk: SmallVec<[_; 8]> = groups.iter().map(Into::into).collect();
+
+        let create_info_vk = ash::vk::RayTracingPipelineCreateInfoKHR {
+            flags: ash::vk::PipelineCreateFlags::empty(),
+            stage_count: stages_vk.len() as u32,
+            p_stages: stages_vk.as_ptr(),
+            group_count: groups_vk.len() as u32,
+            p_groups: groups_vk.as_ptr(),
+            max_pipeline_ray_recursion_depth,
+            p_library_info: ptr::null(),
+            p_library_interface: ptr::null(),
+            p_dynamic_state: ptr::null(),
+            layout: layout.handle(),
+            base_pipeline_handle: ash::vk::Pipeline::null(),
+            base_pipeline_index: 0,
+            ..Default::default()
+        };
+
+        let cache_handle = match cache {
+            Some(ref cache) => cache.handle(),
+            None => ash::vk::PipelineCache::null(),
+        };
+
+        let handle = {
+            let fns = device.fns();
+            let mut output = MaybeUninit::uninit();
+            (fns.khr_ray_tracing_pipeline
+                .create_ray_tracing_pipelines_khr)(
+                device.handle(),
+                ash::vk::DeferredOperationKHR::null(),
+                cache_handle,
+                1,
+                &create_info_vk,
+                device.allocation_callbacks(),
+                output.as_mut_ptr(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+            output.assume_init()
+        };
+
+        let mut descriptor_binding_requirements: HashMap<
+            (u32, u32),
+            DescriptorBindingRequirements,
+        > = HashMap::default();
+
+        for (loc, reqs) in stages
+            .iter()
+            .flat_map(|stage| stage.entry_point.descriptor_binding_requirements())
+        {
+            match descriptor_binding_requirements.entry(loc) {
+                Entry::Occupied(entry) => {
+                    entry.into_mut().merge(reqs).expect(
+                        "Could not produce an intersection of the shader descriptor requirements",
+                    );
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(reqs.clone());
+                }
+            }
+        }
+
+        let num_used_descriptor_sets = descriptor_binding_requirements
+            .keys()
+            .map(|loc| loc.0)
+            .max()
+            .map(|x| x + 1)
+            .unwrap_or(0);
+
+        Ok(Arc::new(RayTracingPipeline {
+            handle,
+            device,
+            id: Self::next_id(),
+            layout,
+            descriptor_binding_requirements,
+            num_used_descriptor_sets,
+            stages: stages
+                .iter()
+                .map(|stage| ShaderStage::from(*stage.entry_point.execution()))
+                .collect(),
+            groups,
+            max_pipeline_ray_recursion_depth,
+        }))
+    }
+
+    /// Returns the shader stage of each shader that the pipeline was created with.
+    #[inline]
+    pub fn stages(&self) -> &[ShaderStage] {
+        &self.stages
+    }
+
+    /// Returns the shader groups that the pipeline was created with.
+    #[inline]
+    pub fn groups(&self) -> &[RayTracingShaderGroupCreateInfo] {
+        &self.groups
+    }
+
+    /// Returns the maximum recursion depth of rays traced by the pipeline.
+    #[inline]
+    pub fn max_pipeline_ray_recursion_depth(&self) -> u32 {
+        self.max_pipeline_ray_recursion_depth
+    }
+
+    /// Returns the opaque handles of the shader groups of the pipeline, which can be written to
+    /// a shader binding table.
+    ///
+    /// The returned `Vec` contains the handles of all groups, in order, each one being
+    /// [`shader_group_handle_size`] bytes long.
+    ///
+    /// [`shader_group_handle_size`]: crate::device::Properties::shader_group_handle_size
+    pub fn group_handles(&self) -> Result<Vec<u8>, OomError> {
+        let handle_size = self
+            .device
+            .physical_device()
+            .properties()
+            .shader_group_handle_size
+            .unwrap() as usize;
+        let mut data = vec![0u8; handle_size * self.groups.len()];
+
+        unsafe {
+            let fns = self.device.fns();
+            (fns.khr_ray_tracing_pipeline
+                .get_ray_tracing_shader_group_handles_khr)(
+                self.device.handle(),
+                self.handle,
+                0,
+                self.groups.len() as u32,
+                data.len(),
+                data.as_mut_ptr() as *mut _,
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+        }
+
+        Ok(data)
+    }
+}
+
+impl Pipeline for RayTracingPipeline {
+    #[inline]
+    fn bind_point(&self) -> PipelineBindPoint {
+        PipelineBindPoint::RayTracing
+    }
+
+    #[inline]
+    fn layout(&self) -> &Arc<PipelineLayout> {
+        &self.layout
+    }
+
+    #[inline]
+    fn num_used_descriptor_sets(&self) -> u32 {
+        self.num_used_descriptor_sets
+    }
+
+    #[inline]
+    fn descriptor_binding_requirements(
+        &self,
+    ) -> &HashMap<(u32, u32), DescriptorBindingRequirements> {
+        &self.descriptor_binding_requirements
+    }
+}
+
+impl Debug for RayTracingPipeline {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(f, "<Vulkan ray tracing pipeline {:?}>", self.handle)
+    }
+}
+
+crate::impl_id_counter!(RayTracingPipeline);
+
+unsafe impl VulkanObject for RayTracingPipeline {
+    type Handle = ash::vk::Pipeline;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.handle
+    }
+}
+
+unsafe impl DeviceOwned for RayTracingPipeline {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+impl Drop for RayTracingPipeline {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let fns = self.device.fns();
+            (fns.v1_0.destroy_pipeline)(
+                self.device.handle(),
+                self.handle,
+                self.device.allocation_callbacks(),
+            );
+        }
+    }
+}
+
+/// Parameters to create a new `RayTracingPipeline`.
+#[derive(Clone, Debug)]
+pub struct RayTracingPipelineCreateInfo<'a> {
+    /// The shaders that make up the pipeline.
+    ///
+    /// At least one of the shaders must be a ray generation shader.
+    ///
+    /// The default value is empty.
+    pub stages: Vec<RayTracingPipelineShaderStage<'a>>,
+
+    /// The shader groups of the pipeline, referring to shaders in `stages` by their index.
+    ///
+    /// The default value is empty.
+    pub groups: Vec<RayTracingShaderGroupCreateInfo>,
+
+    /// The maximum recursion depth of rays traced by shaders in the pipeline.
+    ///
+    /// This must not be greater than the
+    /// [`max_ray_recursion_depth`](crate::device::Properties::max_ray_recursion_depth) limit.
+    ///
+    /// The default value is `1`.
+    pub max_pipeline_ray_recursion_depth: u32,
+
+    /// The pipeline layout to use for the pipeline.
+    ///
+    /// There is no default value.
+    pub layout: Arc<PipelineLayout>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl<'a> RayTracingPipelineCreateInfo<'a> {
+    /// Returns a `RayTracingPipelineCreateInfo` with the specified `layout`.
+    #[inline]
+    pub fn layout(layout: Arc<PipelineLayout>) -> Self {
+        Self {
+            stages: Vec::new(),
+            groups: Vec::new(),
+            max_pipeline_ray_recursion_depth: 1,
+            layout,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// A shader in a ray tracing pipeline.
+#[derive(Clone, Debug)]
+pub struct RayTracingPipelineShaderStage<'a> {
+    /// The shader entry point to use.
+    ///
+    /// There is no default value.
+    pub entry_point: EntryPoint<'a>,
+
+    /// Describes where the specialization constants of the shader are located in
+    /// `specialization_data`.
+    ///
+    /// The default value is empty.
+    pub specialization_map_entries: Vec<SpecializationMapEntry>,
+
+    /// The raw data of the specialization constants.
+    ///
+    /// The default value is empty.
+    pub specialization_data: Vec<u8>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl<'a> RayTracingPipelineShaderStage<'a> {
+    /// Returns a `RayTracingPipelineShaderStage` with the specified `entry_point`.
+    #[inline]
+    pub fn entry_point(entry_point: EntryPoint<'a>) -> Self {
+        Self {
+            entry_point,
+            specialization_map_entries: Vec::new(),
+            specialization_data: Vec::new(),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+
+    /// Sets the specialization constants of the shader from a `SpecializationConstants` value.
+    #[inline]
+    pub fn specialization_constants<Css>(mut self, specialization_constants: &Css) -> Self
+    where
+        Css: SpecializationConstants,
+    {
+        self.specialization_map_entries = Css::descriptors().to_vec();
+        self.specialization_data = unsafe {
+            slice::from_raw_parts(
+                specialization_constants as *const Css as *const u8,
+                size_of_val(specialization_constants),
+            )
+        }
+        .to_vec();
+        self
+    }
+}
+
+/// A shader group in a ray tracing pipeline.
+///
+/// The shaders are specified by their index into
+/// [`RayTracingPipelineCreateInfo::stages`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RayTracingShaderGroupCreateInfo {
+    /// A group containing a single ray generation, miss or callable shader.
+    General { general_shader: u32 },
+
+    /// A hit group for triangle geometries, which uses the built-in triangle intersection test.
+    TrianglesHit {
+        closest_hit_shader: Option<u32>,
+        any_hit_shader: Option<u32>,
+    },
+
+    /// A hit group for AABB geometries, which uses a custom intersection shader.
+    ProceduralHit {
+        intersection_shader: u32,
+        closest_hit_shader: Option<u32>,
+        any_hit_shader: Option<u32>,
+    },
+}
+
+impl From<&RayTracingShaderGroupCreateInfo> for ash::vk::RayTracingShaderGroupCreateInfoKHR {
+    #[inline]
+    fn from(val: &RayTracingShaderGroupCreateInfo) -> Self {
+        let unused = ash::vk::SHADER_UNUSED_KHR;
+
+        let (ty, general_shader, closest_hit_shader, any_hit_shader, intersection_shader) =
+            match *val {
+                RayTracingShaderGroupCreateInfo::General { general_shader } => (
+                    ash::vk::RayTracingShaderGroupTypeKHR::GENERAL,
+                    general_shader,
+                    unused,
+                    unused,
+                    unused,
+                ),
+                RayTracingShaderGroupCreateInfo::TrianglesHit {
+                    closest_hit_shader,
+                    any_hit_shader,
+                } => (
+                    ash::vk::RayTracingShaderGroupTypeKHR::TRIANGLES_HIT_GROUP,
+                    unused,
+                    closest_hit_shader.unwrap_or(unused),
+                    any_hit_shader.unwrap_or(unused),
+                    unused,
+                ),
+                RayTracingShaderGroupCreateInfo::ProceduralHit {
+                    intersection_shader,
+                    closest_hit_shader,
+                    any_hit_shader,
+                } => (
+                    ash::vk::RayTracingShaderGroupTypeKHR::PROCEDURAL_HIT_GROUP,
+                    unused,
+                    closest_hit_shader.unwrap_or(unused),
+                    any_hit_shader.unwrap_or(unused),
+                    intersection_shader,
+                ),
+            };
+
+        ash::vk::RayTracingShaderGroupCreateInfoKHR {
+            ty,
+            general_shader,
+            closest_hit_shader,
+            any_hit_shader,
+            intersection_shader,
+            p_shader_group_capture_replay_handle: ptr::null(),
+            ..Default::default()
+        }
+    }
+}
+
+/// A region of device memory, consisting of equally sized elements, that is passed to ray
+/// tracing commands.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct StridedDeviceAddressRegion {
+    /// The device address of the start of the region, or `0` if the region is unused.
+    pub device_address: DeviceSize,
+
+    /// The number of bytes between the start of consecutive elements.
+    pub stride: DeviceSize,
+
+    /// The size of the region in bytes.
+    pub size: DeviceSize,
+}
+
+impl From<StridedDeviceAddressRegion> for ash::vk::StridedDeviceAddressRegionKHR {
+    #[inline]
+    fn from(val: StridedDeviceAddressRegion) -> Self {
+        Self {
+            device_address: val.device_address,
+            stride: val.stride,
+            size: val.size,
+        }
+    }
+}
+
+/// A buffer holding the shader group handles of a ray tracing pipeline, arranged into the
+/// regions that are passed to
+/// [`trace_rays`](crate::command_buffer::AutoCommandBufferBuilder::trace_rays).
+#[derive(Clone, Debug)]
+pub struct ShaderBindingTable {
+    buffer: Arc<dyn BufferAccess>,
+    pipeline_id: NonZeroU64,
+    raygen: StridedDeviceAddressRegion,
+    miss: StridedDeviceAddressRegion,
+    hit: StridedDeviceAddressRegion,
+    callable: StridedDeviceAddressRegion,
+}
+
+impl ShaderBindingTable {
+    /// Creates a shader binding table for `pipeline`.
+    ///
+    /// The shader groups of the pipeline are sorted into regions based on their type, keeping the
+    /// order in which they were provided to the pipeline:
+    /// - The ray generation region holds the first general group with a ray generation shader.
+    /// - The miss region holds all general groups with a miss shader.
+    /// - The hit region holds all hit groups.
+    /// - The callable region holds all general groups with a callable shader.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `pipeline` has no general group with a ray generation shader.
+    pub fn new(
+        allocator: &(impl MemoryAllocator + ?Sized),
+        pipeline: &RayTracingPipeline,
+    ) -> Result<ShaderBindingTable, ShaderBindingTableError> {
+        let properties = pipeline.device().physical_device().properties();
+        let handle_size = properties.shader_group_handle_size.unwrap() as DeviceSize;
+        let handle_alignment = properties.shader_group_handle_alignment.unwrap() as DeviceSize;
+        let base_alignment = properties.shader_group_base_alignment.unwrap() as DeviceSize;
+        let handle_stride = align_up(handle_size, handle_alignment);
+
+        let mut raygen_groups: SmallVec<[usize; 1]> = SmallVec::new();
+        let mut miss_groups: SmallVec<[usize; 8]> = SmallVec::new();
+        let mut hit_groups: SmallVec<[usize; 8]> = SmallVec::new();
+        let mut callable_groups: SmallVec<[usize; 8]> = SmallVec::new();
+
+        for (group_index, group) in pipeline.groups().iter().enumerate() {
+            match *group {
+                RayTracingShaderGroupCreateInfo::General { general_shader } => {
+                    match pipeline.stages()[general_shader as usize] {
+                        ShaderStage::Raygen if raygen_groups.is_empty() => {
+                            raygen_groups.push(group_index)
+                        }
+                        ShaderStage::Miss => miss_groups.push(group_index),
+                        ShaderStage::Callable => callable_groups.push(group_index),
+                        _ => (),
+                    }
+                }
+                RayTracingShaderGroupCreateInfo::TrianglesHit { .. }
+                | RayTracingShaderGroupCreateInfo::ProceduralHit { .. } => {
+                    hit_groups.push(group_index)
+                }
+            }
+        }
+
+        assert!(
+            !raygen_groups.is_empty(),
+            "the pipeline has no general group with a ray generation shader",
+        );
+
+        // Lay out the regions one after another, each starting at a multiple of
+        // `shader_group_base_alignment` relative to the start of the table.
+        let mut table_size = 0;
+        let mut region_layouts = [(0, 0); 4];
+
+        for (layout, groups) in region_layouts.iter_mut().zip([
+            &raygen_groups[..],
+            &miss_groups[..],
+            &hit_groups[..],
+            &callable_groups[..],
+        ]) {
+            let size = groups.len() as DeviceSize * handle_stride;
+            *layout = (table_size, size);
+            table_size = align_up(table_size + size, base_alignment);
+        }
+
+        // The start of the buffer may not be aligned to `shader_group_base_alignment`,
+        // so allocate extra space to be able to align it manually.
+        let buffer = CpuAccessibleBuffer::from_iter(
+            allocator,
+            BufferUsage::SHADER_BINDING_TABLE | BufferUsage::SHADER_DEVICE_ADDRESS,
+            false,
+            vec![0u8; (table_size + base_alignment) as usize],
+        )?;
+
+        let buffer_address = buffer.raw_device_address()?.get();
+        let table_offset = align_up(buffer_address, base_alignment) - buffer_address;
+        let group_handles = pipeline.group_handles()?;
+
+        {
+            let mut data = buffer.write().unwrap();
+
+            for (&(region_offset, _), groups) in region_layouts.iter().zip([
+                &raygen_groups[..],
+                &miss_groups[..],
+                &hit_groups[..],
+                &callable_groups[..],
+            ]) {
+                for (index, &group_index) in groups.iter().enumerate() {
+                    let src_start = group_index * handle_size as usize;
+                    let dst_start =
+                        (table_offset + region_offset + index as DeviceSize * handle_stride)
+                            as usize;
+                    data[dst_start..dst_start + handle_size as usize].copy_from_slice(
+                        &group_handles[src_start..src_start + handle_size as usize],
+                    );
+                }
+            }
+        }
+
+        let region = |(region_offset, size): (DeviceSize, DeviceSize)| {
+            if size == 0 {
+                StridedDeviceAddressRegion::default()
+            } else {
+                StridedDeviceAddressRegion {
+                    device_address: buffer_address + table_offset + region_offset,
+                    stride: handle_stride,
+                    size,
+                }
+            }
+        };
+
+        Ok(ShaderBindingTable {
+            pipeline_id: pipeline.id,
+            raygen: region(region_layouts[0]),
+            miss: region(region_layouts[1]),
+            hit: region(region_layouts[2]),
+            callable: region(region_layouts[3]),
+            buffer,
+        })
+    }
+
+    /// Returns the buffer that holds the table.
+    #[inline]
+    pub fn buffer(&self) -> &Arc<dyn BufferAccess> {
+        &self.buffer
+    }
+
+    /// Returns the region holding the ray generation shader group.
+    #[inline]
+    pub fn raygen(&self) -> StridedDeviceAddressRegion {
+        self.raygen
+    }
+
+    /// Returns the region holding the miss shader groups.
+    #[inline]
+    pub fn miss(&self) -> StridedDeviceAddressRegion {
+        self.miss
+    }
+
+    /// Returns the region holding the hit shader groups.
+    #[inline]
+    pub fn hit(&self) -> StridedDeviceAddressRegion {
+        self.hit
+    }
+
+    /// Returns the region holding the callable shader groups.
+    #[inline]
+    pub fn callable(&self) -> StridedDeviceAddressRegion {
+        self.callable
+    }
+
+    pub(crate) fn is_for_pipeline(&self, pipeline: &RayTracingPipeline) -> bool {
+        self.pipeline_id == pipeline.id
+    }
+}
+
+fn align_up(value: DeviceSize, alignment: DeviceSize) -> DeviceSize {
+    (value + alignment - 1) & !(alignment - 1)
+}
+
+/// Error that can happen when creating a ray tracing pipeline.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RayTracingPipelineCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// The pipeline layout is not compatible with what the shaders expect.
+    IncompatiblePipelineLayout(PipelineLayoutSupersetError),
+
+    /// The provided specialization constants are not compatible with what the shader expects.
+    IncompatibleSpecializationConstants,
+
+    /// `max_pipeline_ray_recursion_depth` is greater than the
+    /// [`max_ray_recursion_depth`](crate::device::Properties::max_ray_recursion_depth) limit.
+    MaxRayRecursionDepthExceeded { provided: u32, max_supported: u32 },
+
+    /// None of the shaders in `stages` is a ray generation shader.
+    NoRayGenerationShader,
+
+    /// A shader group refers to a shader of a type that is not allowed in that group.
+    ShaderGroupStageInvalid { group_index: u32, stage_index: u32 },
+
+    /// A shader group refers to a shader index that is not present in `stages`.
+    ShaderGroupStageIndexOutOfRange { group_index: u32, stage_index: u32 },
+
+    /// A shader in `stages` is not a ray tracing shader.
+    ShaderStageInvalid { stage_index: u32 },
+}
+
+impl Error for RayTracingPipelineCreationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            Self::IncompatiblePipelineLayout(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for RayTracingPipelineCreationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::IncompatiblePipelineLayout(_) => write!(
+                f,
+                "the pipeline layout is not compatible with what the shaders expect",
+            ),
+            Self::IncompatibleSpecializationConstants => write!(
+                f,
+                "the provided specialization constants are not compatible with what the shader \
+                expects",
+            ),
+            Self::MaxRayRecursionDepthExceeded {
+                provided,
+                max_supported,
+            } => write!(
+                f,
+                "`max_pipeline_ray_recursion_depth` ({}) is greater than the \
+                `max_ray_recursion_depth` limit ({})",
+                provided, max_supported,
+            ),
+            Self::NoRayGenerationShader => write!(
+                f,
+                "none of the shaders in `stages` is a ray generation shader",
+            ),
+            Self::ShaderGroupStageInvalid {
+                group_index,
+                stage_index,
+            } => write!(
+                f,
+                "shader group {} refers to shader {}, whose type is not allowed in that group",
+                group_index, stage_index,
+            ),
+            Self::ShaderGroupStageIndexOutOfRange {
+                group_index,
+                stage_index,
+            } => write!(
+                f,
+                "shader group {} refers to shader {}, which is not present in `stages`",
+                group_index, stage_index,
+            ),
+            Self::ShaderStageInvalid { stage_index } => write!(
+                f,
+                "shader {} in `stages` is not a ray tracing shader",
+                stage_index,
+            ),
+        }
+    }
+}
+
+impl From<OomError> for RayTracingPipelineCreationError {
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+impl From<PipelineLayoutSupersetError> for RayTracingPipelineCreationError {
+    fn from(err: PipelineLayoutSupersetError) -> Self {
+        Self::IncompatiblePipelineLayout(err)
+    }
+}
+
+impl From<VulkanError> for RayTracingPipelineCreationError {
+    fn from(err: VulkanError) -> Self {
+        match err {
+            err @ VulkanError::OutOfHostMemory => Self::OomError(OomError::from(err)),
+            err @ VulkanError::OutOfDeviceMemory => Self::OomError(OomError::from(err)),
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+impl From<RequirementNotMet> for RayTracingPipelineCreationError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
+/// Error that can happen when creating a shader binding table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ShaderBindingTableError {
+    /// Allocating memory for the table failed.
+    AllocationCreationError(AllocationCreationError),
+
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// Querying the device address of the table failed.
+    BufferDeviceAddressError(BufferDeviceAddressError),
+}
+
+impl Error for ShaderBindingTableError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::AllocationCreationError(err) => Some(err),
+            Self::OomError(err) => Some(err),
+            Self::BufferDeviceAddressError(err) => Some(err),
+        }
+    }
+}
+
+impl Display for ShaderBindingTableError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::AllocationCreationError(_) => write!(f, "allocating memory for the table failed"),
+            Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::BufferDeviceAddressError(_) => {
+                write!(f, "querying the device address of the table failed")
+            }
+        }
+    }
+}
+
+impl From<AllocationCreationError> for ShaderBindingTableError {
+    fn from(err: AllocationCreationError) -> Self {
+        Self::AllocationCreationError(err)
+    }
+}
+
+impl From<OomError> for ShaderBindingTableError {
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+impl From<BufferDeviceAddressError> for ShaderBindingTableError {
+    fn from(err: BufferDeviceAddressError) -> Self {
+        Self::BufferDeviceAddressError(err)
+    }
+}
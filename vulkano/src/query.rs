@@ -25,7 +25,6 @@ use std::{
     mem::{size_of_val, MaybeUninit},
     num::NonZeroU64,
     ops::Range,
-    ptr,
     sync::Arc,
 };
 
@@ -35,6 +34,7 @@ pub struct QueryPool {
     handle: ash::vk::QueryPool,
     device: Arc<Device>,
     id: NonZeroU64,
+    imported_handle: bool,
 
     query_type: QueryType,
     query_count: u32,
@@ -69,6 +69,20 @@ impl QueryPool {
                 // VUID-VkQueryPoolCreateInfo-queryType-00792
                 flags.into()
             }
+            QueryType::TransformFeedbackStream => {
+                if !device.enabled_extensions().ext_transform_feedback {
+                    return Err(QueryPoolCreationError::RequirementNotMet {
+                        required_for: "`create_info.query_type` is \
+                            `QueryType::TransformFeedbackStream`",
+                        requires_one_of: RequiresOneOf {
+                            device_extensions: &["ext_transform_feedback"],
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                ash::vk::QueryPipelineStatisticFlags::empty()
+            }
             QueryType::Occlusion | QueryType::Timestamp => {
                 ash::vk::QueryPipelineStatisticFlags::empty()
             }
@@ -88,7 +102,7 @@ impl QueryPool {
             (fns.v1_0.create_query_pool)(
                 device.handle(),
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -100,6 +114,7 @@ impl QueryPool {
             handle,
             device,
             id: Self::next_id(),
+            imported_handle: false,
             query_type,
             query_count,
         }))
@@ -111,6 +126,8 @@ impl QueryPool {
     ///
     /// - `handle` must be a valid Vulkan object handle created from `device`.
     /// - `create_info` must match the info used to create the object.
+    /// - `handle` must have been created with a null `pAllocator`, as it is destroyed with a null
+    ///   `pAllocator` when dropped.
     #[inline]
     pub unsafe fn from_handle(
         device: Arc<Device>,
@@ -127,6 +144,7 @@ impl QueryPool {
             handle,
             device,
             id: Self::next_id(),
+            imported_handle: true,
             query_type,
             query_count,
         })
@@ -176,7 +194,12 @@ impl Drop for QueryPool {
     fn drop(&mut self) {
         unsafe {
             let fns = self.device.fns();
-            (fns.v1_0.destroy_query_pool)(self.device.handle(), self.handle, ptr::null());
+            (fns.v1_0.destroy_query_pool)(
+                self.device.handle(),
+                self.handle,
+                self.device
+                    .destroy_allocation_callbacks(self.imported_handle),
+            );
         }
     }
 }
@@ -232,6 +255,12 @@ impl QueryPoolCreateInfo {
 pub enum QueryPoolCreationError {
     /// Not enough memory.
     OomError(OomError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
     /// A pipeline statistics pool was requested but the corresponding feature wasn't enabled.
     PipelineStatisticsQueryFeatureNotEnabled,
 }
@@ -247,17 +276,22 @@ impl Error for QueryPoolCreationError {
 
 impl Display for QueryPoolCreationError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
-        write!(
-            f,
-            "{}",
-            match self {
-                QueryPoolCreationError::OomError(_) => "not enough memory available",
-                QueryPoolCreationError::PipelineStatisticsQueryFeatureNotEnabled => {
-                    "a pipeline statistics pool was requested but the corresponding feature \
-                    wasn't enabled"
-                }
-            }
-        )
+        match self {
+            QueryPoolCreationError::OomError(_) => write!(f, "not enough memory available"),
+            QueryPoolCreationError::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            QueryPoolCreationError::PipelineStatisticsQueryFeatureNotEnabled => write!(
+                f,
+                "a pipeline statistics pool was requested but the corresponding feature wasn't \
+                enabled",
+            ),
+        }
     }
 }
 
@@ -411,6 +445,7 @@ impl<'a> QueriesRange<'a> {
         match self.pool.query_type {
             QueryType::Occlusion => (),
             QueryType::PipelineStatistics(_) => (),
+            QueryType::TransformFeedbackStream => (),
             QueryType::Timestamp => {
                 // VUID-vkGetQueryPoolResults-queryType-00818
                 if flags.intersects(QueryResultFlags::PARTIAL) {
@@ -533,6 +568,13 @@ pub enum QueryType {
     PipelineStatistics(QueryPipelineStatisticFlags),
     /// Writes timestamps at chosen points in a command buffer.
     Timestamp,
+    /// Tracks the number of primitives that are written to transform feedback buffers, and the
+    /// number of primitives that would have been written if the buffers were large enough.
+    ///
+    /// The [`ext_transform_feedback`] extension must be enabled on the device.
+    ///
+    /// [`ext_transform_feedback`]: crate::device::DeviceExtensions::ext_transform_feedback
+    TransformFeedbackStream,
 }
 
 impl QueryType {
@@ -541,6 +583,7 @@ impl QueryType {
     ///
     /// - For [`Occlusion`] and [`Timestamp`] queries, this returns 1.
     /// - For [`PipelineStatistics`] queries, this returns the number of statistics flags enabled.
+    /// - For [`TransformFeedbackStream`] queries, this returns 2.
     ///
     /// If the results are retrieved with [`WITH_AVAILABILITY`] enabled, then an additional element
     /// is required per query.
@@ -548,12 +591,14 @@ impl QueryType {
     /// [`Occlusion`]: QueryType::Occlusion
     /// [`Timestamp`]: QueryType::Timestamp
     /// [`PipelineStatistics`]: QueryType::PipelineStatistics
+    /// [`TransformFeedbackStream`]: QueryType::TransformFeedbackStream
     /// [`WITH_AVAILABILITY`]: QueryResultFlags::WITH_AVAILABILITY
     #[inline]
     pub const fn result_len(self) -> DeviceSize {
         match self {
             Self::Occlusion | Self::Timestamp => 1,
             Self::PipelineStatistics(flags) => flags.count() as DeviceSize,
+            Self::TransformFeedbackStream => 2,
         }
     }
 }
@@ -565,6 +610,7 @@ impl From<QueryType> for ash::vk::QueryType {
             QueryType::Occlusion => ash::vk::QueryType::OCCLUSION,
             QueryType::PipelineStatistics(_) => ash::vk::QueryType::PIPELINE_STATISTICS,
             QueryType::Timestamp => ash::vk::QueryType::TIMESTAMP,
+            QueryType::TransformFeedbackStream => ash::vk::QueryType::TRANSFORM_FEEDBACK_STREAM_EXT,
         }
     }
 }
@@ -1230,14 +1230,14 @@ impl RenderPass {
                 (fns.v1_2.create_render_pass2)(
                     device.handle(),
                     &create_info,
-                    ptr::null(),
+                    device.allocation_callbacks(),
                     output.as_mut_ptr(),
                 )
             } else {
                 (fns.khr_create_renderpass2.create_render_pass2_khr)(
                     device.handle(),
                     &create_info,
-                    ptr::null(),
+                    device.allocation_callbacks(),
                     output.as_mut_ptr(),
                 )
             }
@@ -1487,7 +1487,7 @@ impl RenderPass {
             (fns.v1_0.create_render_pass)(
                 device.handle(),
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -21,7 +21,6 @@ use std::{
     mem::MaybeUninit,
     num::NonZeroU64,
     ops::Range,
-    ptr,
     sync::Arc,
 };
 
@@ -54,6 +53,7 @@ pub struct Framebuffer {
     handle: ash::vk::Framebuffer,
     render_pass: Arc<RenderPass>,
     id: NonZeroU64,
+    imported_handle: bool,
 
     attachments: Vec<Arc<dyn ImageViewAbstract>>,
     extent: [u32; 2],
@@ -316,7 +316,7 @@ impl Framebuffer {
             (fns.v1_0.create_framebuffer)(
                 device.handle(),
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -328,6 +328,7 @@ impl Framebuffer {
             handle,
             render_pass,
             id: Self::next_id(),
+            imported_handle: false,
             attachments,
             extent,
             layers,
@@ -340,6 +341,8 @@ impl Framebuffer {
     ///
     /// - `handle` must be a valid Vulkan object handle created from `render_pass`.
     /// - `create_info` must match the info used to create the object.
+    /// - `handle` must have been created with a null `pAllocator`, as it is destroyed with a null
+    ///   `pAllocator` when dropped.
     #[inline]
     pub unsafe fn from_handle(
         render_pass: Arc<RenderPass>,
@@ -357,6 +360,7 @@ impl Framebuffer {
             handle,
             render_pass,
             id: Self::next_id(),
+            imported_handle: true,
             attachments,
             extent,
             layers,
@@ -401,7 +405,12 @@ impl Drop for Framebuffer {
     fn drop(&mut self) {
         unsafe {
             let fns = self.device().fns();
-            (fns.v1_0.destroy_framebuffer)(self.device().handle(), self.handle, ptr::null());
+            (fns.v1_0.destroy_framebuffer)(
+                self.device().handle(),
+                self.handle,
+                self.device()
+                    .destroy_allocation_callbacks(self.imported_handle),
+            );
         }
     }
 }
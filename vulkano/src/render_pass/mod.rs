@@ -38,7 +38,7 @@ use crate::{
     sync::{AccessFlags, DependencyFlags, PipelineStages},
     Version, VulkanObject,
 };
-use std::{cmp::max, mem::MaybeUninit, num::NonZeroU64, ptr, sync::Arc};
+use std::{cmp::max, mem::MaybeUninit, num::NonZeroU64, sync::Arc};
 
 #[macro_use]
 mod macros;
@@ -103,6 +103,7 @@ pub struct RenderPass {
     handle: ash::vk::RenderPass,
     device: Arc<Device>,
     id: NonZeroU64,
+    imported_handle: bool,
 
     attachments: Vec<AttachmentDescription>,
     subpasses: Vec<SubpassDescription>,
@@ -137,7 +138,7 @@ impl RenderPass {
             }
         };
 
-        unsafe { Ok(Self::from_handle(device, handle, create_info)) }
+        unsafe { Ok(Self::from_handle_inner(device, handle, create_info, false)) }
     }
 
     /// Builds a render pass with one subpass and no attachment.
@@ -162,11 +163,22 @@ impl RenderPass {
     ///
     /// - `handle` must be a valid Vulkan object handle created from `device`.
     /// - `create_info` must match the info used to create the object.
+    /// - `handle` must have been created with a null `pAllocator`, as it is destroyed with a null
+    ///   `pAllocator` when dropped.
     #[inline]
     pub unsafe fn from_handle(
         device: Arc<Device>,
         handle: ash::vk::RenderPass,
         create_info: RenderPassCreateInfo,
+    ) -> Arc<RenderPass> {
+        Self::from_handle_inner(device, handle, create_info, true)
+    }
+
+    unsafe fn from_handle_inner(
+        device: Arc<Device>,
+        handle: ash::vk::RenderPass,
+        create_info: RenderPassCreateInfo,
+        imported_handle: bool,
     ) -> Arc<RenderPass> {
         let RenderPassCreateInfo {
             attachments,
@@ -214,6 +226,7 @@ impl RenderPass {
             handle,
             device,
             id: Self::next_id(),
+            imported_handle,
 
             attachments,
             subpasses,
@@ -298,6 +311,7 @@ impl RenderPass {
             handle: _,
             device: _,
             id: _,
+            imported_handle: _,
             attachments: attachments1,
             subpasses: subpasses1,
             dependencies: dependencies1,
@@ -310,6 +324,7 @@ impl RenderPass {
             handle: _,
             device: _,
             id: _,
+            imported_handle: _,
             attachments: attachments2,
             subpasses: subpasses2,
             dependencies: dependencies2,
@@ -523,7 +538,12 @@ impl Drop for RenderPass {
     fn drop(&mut self) {
         unsafe {
             let fns = self.device.fns();
-            (fns.v1_0.destroy_render_pass)(self.device.handle(), self.handle, ptr::null());
+            (fns.v1_0.destroy_render_pass)(
+                self.device.handle(),
+                self.handle,
+                self.device
+                    .destroy_allocation_callbacks(self.imported_handle),
+            );
         }
     }
 }
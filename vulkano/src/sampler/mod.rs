@@ -62,7 +62,6 @@ use std::{
     mem::MaybeUninit,
     num::NonZeroU64,
     ops::RangeInclusive,
-    ptr,
     sync::Arc,
 };
 
@@ -100,6 +99,7 @@ pub struct Sampler {
     handle: ash::vk::Sampler,
     device: Arc<Device>,
     id: NonZeroU64,
+    imported_handle: bool,
 
     address_mode: [SamplerAddressMode; 3],
     anisotropy: Option<f32>,
@@ -430,7 +430,7 @@ impl Sampler {
             (fns.v1_0.create_sampler)(
                 device.handle(),
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -442,6 +442,7 @@ impl Sampler {
             handle,
             device,
             id: Self::next_id(),
+            imported_handle: false,
             address_mode,
             anisotropy,
             border_color: address_mode
@@ -466,6 +467,8 @@ impl Sampler {
     ///
     /// - `handle` must be a valid Vulkan object handle created from `device`.
     /// - `create_info` must match the info used to create the object.
+    /// - `handle` must have been created with a null `pAllocator`, as it is destroyed with a null
+    ///   `pAllocator` when dropped.
     #[inline]
     pub unsafe fn from_handle(
         device: Arc<Device>,
@@ -492,6 +495,7 @@ impl Sampler {
             handle,
             device,
             id: Self::next_id(),
+            imported_handle: true,
             address_mode,
             anisotropy,
             border_color: address_mode
@@ -758,7 +762,12 @@ impl Drop for Sampler {
     fn drop(&mut self) {
         unsafe {
             let fns = self.device.fns();
-            (fns.v1_0.destroy_sampler)(self.device.handle(), self.handle, ptr::null());
+            (fns.v1_0.destroy_sampler)(
+                self.device.handle(),
+                self.handle,
+                self.device
+                    .destroy_allocation_callbacks(self.imported_handle),
+            );
         }
     }
 }
@@ -101,7 +101,6 @@ use std::{
     fmt::{Display, Error as FmtError, Formatter},
     mem::MaybeUninit,
     num::NonZeroU64,
-    ptr,
     sync::Arc,
 };
 
@@ -111,6 +110,7 @@ pub struct SamplerYcbcrConversion {
     handle: ash::vk::SamplerYcbcrConversion,
     device: Arc<Device>,
     id: NonZeroU64,
+    imported_handle: bool,
 
     format: Option<Format>,
     ycbcr_model: SamplerYcbcrModelConversion,
@@ -346,7 +346,7 @@ impl SamplerYcbcrConversion {
             create_sampler_ycbcr_conversion(
                 device.handle(),
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -358,6 +358,7 @@ impl SamplerYcbcrConversion {
             handle,
             device,
             id: Self::next_id(),
+            imported_handle: false,
             format: Some(format),
             ycbcr_model,
             ycbcr_range,
@@ -375,6 +376,8 @@ impl SamplerYcbcrConversion {
     /// - `handle` must be a valid Vulkan object handle created from `device`.
     /// - `create_info` must match the info used to create the object.
     /// - `create_info.format` must be `Some`.
+    /// - `handle` must have been created with a null `pAllocator`, as it is destroyed with a null
+    ///   `pAllocator` when dropped.
     #[inline]
     pub unsafe fn from_handle(
         device: Arc<Device>,
@@ -396,6 +399,7 @@ impl SamplerYcbcrConversion {
             handle,
             device,
             id: Self::next_id(),
+            imported_handle: true,
             format,
             ycbcr_model,
             ycbcr_range,
@@ -456,6 +460,7 @@ impl SamplerYcbcrConversion {
                 handle: _,
                 device: _,
                 id: _,
+                imported_handle: _,
                 format,
                 ycbcr_model,
                 ycbcr_range,
@@ -488,7 +493,12 @@ impl Drop for SamplerYcbcrConversion {
                     .destroy_sampler_ycbcr_conversion_khr
             };
 
-            destroy_sampler_ycbcr_conversion(self.device.handle(), self.handle, ptr::null());
+            destroy_sampler_ycbcr_conversion(
+                self.device.handle(),
+                self.handle,
+                self.device
+                    .destroy_allocation_callbacks(self.imported_handle),
+            );
         }
     }
 }
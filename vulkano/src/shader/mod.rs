@@ -38,7 +38,6 @@ use std::{
     mem,
     mem::MaybeUninit,
     num::NonZeroU64,
-    ptr,
     sync::Arc,
 };
 
@@ -158,7 +157,7 @@ impl ShaderModule {
             (fns.v1_0.create_shader_module)(
                 device.handle(),
                 &infos,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -266,7 +265,11 @@ impl Drop for ShaderModule {
     fn drop(&mut self) {
         unsafe {
             let fns = self.device.fns();
-            (fns.v1_0.destroy_shader_module)(self.device.handle(), self.handle, ptr::null());
+            (fns.v1_0.destroy_shader_module)(
+                self.device.handle(),
+                self.handle,
+                self.device.allocation_callbacks(),
+            );
         }
     }
 }
@@ -387,6 +390,7 @@ pub struct EntryPointInfo {
     pub specialization_constant_requirements: HashMap<u32, SpecializationConstantRequirements>,
     pub input_interface: ShaderInterface,
     pub output_interface: ShaderInterface,
+    pub transform_feedback_outputs: Option<Vec<ShaderTransformFeedbackOutput>>,
 }
 
 /// Represents a shader entry point in a shader module.
@@ -457,6 +461,13 @@ impl<'a> EntryPoint<'a> {
     pub fn output_interface(&self) -> &ShaderInterface {
         &self.info.output_interface
     }
+
+    /// Returns the outputs of the shader stage that are captured by transform feedback, or `None`
+    /// if the shader does not declare the `Xfb` execution mode.
+    #[inline]
+    pub fn transform_feedback_outputs(&self) -> Option<&[ShaderTransformFeedbackOutput]> {
+        self.info.transform_feedback_outputs.as_deref()
+    }
 }
 
 /// The mode in which a shader executes. This includes both information about the shader type/stage,
@@ -476,7 +487,7 @@ pub enum ShaderExecution {
     Intersection,
     Callable,
     Task,
-    Mesh,
+    Mesh(MeshShaderExecution),
     SubpassShading,
 }
 
@@ -503,6 +514,27 @@ pub struct GeometryShaderExecution {
     pub output: GeometryShaderOutput,*/
 }
 
+/// The mode in which a mesh shader executes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MeshShaderExecution {
+    /// The type of primitive that is output by the shader.
+    pub output: MeshShaderOutput,
+
+    /// The maximum number of vertices that the shader outputs per workgroup.
+    pub max_vertices: u32,
+
+    /// The maximum number of primitives that the shader outputs per workgroup.
+    pub max_primitives: u32,
+}
+
+/// The output primitive type of a mesh shader.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MeshShaderOutput {
+    Points,
+    Lines,
+    Triangles,
+}
+
 /// The input primitive type that is expected by a geometry shader.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum GeometryShaderInput {
@@ -985,6 +1017,28 @@ pub struct ShaderInterfaceEntry {
     pub ty: ShaderInterfaceEntryType,
 }
 
+/// An output of a shader that is captured by transform feedback.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ShaderTransformFeedbackOutput {
+    /// The transform feedback buffer that the output is written to, as given by the `XfbBuffer`
+    /// decoration.
+    pub buffer: u32,
+
+    /// The number of bytes between the data of consecutive vertices in the buffer, as given by
+    /// the `XfbStride` decoration.
+    pub stride: u32,
+
+    /// The offset in bytes of the output within the data of a vertex, as given by the `Offset`
+    /// decoration.
+    pub offset: u32,
+
+    /// The size in bytes of the output.
+    pub size: u32,
+
+    /// The vertex stream that the output is written to, as given by the `Stream` decoration.
+    pub stream: u32,
+}
+
 /// The type of a variable in a shader interface.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct ShaderInterfaceEntryType {
@@ -1212,7 +1266,7 @@ impl From<ShaderExecution> for ShaderStage {
             ShaderExecution::Intersection => Self::Intersection,
             ShaderExecution::Callable => Self::Callable,
             ShaderExecution::Task => Self::Task,
-            ShaderExecution::Mesh => Self::Mesh,
+            ShaderExecution::Mesh(_) => Self::Mesh,
             ShaderExecution::SubpassShading => Self::SubpassShading,
         }
     }
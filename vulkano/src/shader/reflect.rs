@@ -20,9 +20,9 @@ use crate::{
             StorageClass,
         },
         DescriptorIdentifier, DescriptorRequirements, EntryPointInfo, GeometryShaderExecution,
-        GeometryShaderInput, ShaderExecution, ShaderInterface, ShaderInterfaceEntry,
-        ShaderInterfaceEntryType, ShaderScalarType, ShaderStage,
-        SpecializationConstantRequirements,
+        GeometryShaderInput, MeshShaderExecution, MeshShaderOutput, ShaderExecution,
+        ShaderInterface, ShaderInterfaceEntry, ShaderInterfaceEntryType, ShaderScalarType,
+        ShaderStage, ShaderTransformFeedbackOutput, SpecializationConstantRequirements,
     },
     DeviceSize,
 };
@@ -96,8 +96,14 @@ pub fn entry_points(
             spirv,
             interface,
             StorageClass::Output,
-            matches!(execution_model, ExecutionModel::TessellationControl),
+            matches!(
+                execution_model,
+                ExecutionModel::TessellationControl
+                    | ExecutionModel::MeshNV
+                    | ExecutionModel::MeshEXT
+            ),
         );
+        let transform_feedback_outputs = transform_feedback_outputs(spirv, function_id, interface);
 
         Some((
             entry_point_name.clone(),
@@ -109,6 +115,7 @@ pub fn entry_points(
                 specialization_constant_requirements,
                 input_interface,
                 output_interface,
+                transform_feedback_outputs,
             },
         ))
     })
@@ -201,8 +208,50 @@ fn shader_execution(
         ExecutionModel::MissKHR => ShaderExecution::Miss,
         ExecutionModel::CallableKHR => ShaderExecution::Callable,
 
-        ExecutionModel::TaskNV => ShaderExecution::Task,
-        ExecutionModel::MeshNV => ShaderExecution::Mesh,
+        ExecutionModel::TaskNV | ExecutionModel::TaskEXT => ShaderExecution::Task,
+
+        ExecutionModel::MeshNV | ExecutionModel::MeshEXT => {
+            let mut output = None;
+            let mut max_vertices = None;
+            let mut max_primitives = None;
+
+            for instruction in spirv.iter_execution_mode() {
+                let mode = match instruction {
+                    Instruction::ExecutionMode {
+                        entry_point, mode, ..
+                    } if *entry_point == function_id => mode,
+                    _ => continue,
+                };
+
+                match *mode {
+                    ExecutionMode::OutputPoints => {
+                        output = Some(MeshShaderOutput::Points);
+                    }
+                    ExecutionMode::OutputLinesEXT => {
+                        output = Some(MeshShaderOutput::Lines);
+                    }
+                    ExecutionMode::OutputTrianglesEXT => {
+                        output = Some(MeshShaderOutput::Triangles);
+                    }
+                    ExecutionMode::OutputVertices { vertex_count } => {
+                        max_vertices = Some(vertex_count);
+                    }
+                    ExecutionMode::OutputPrimitivesEXT { primitive_count } => {
+                        max_primitives = Some(primitive_count);
+                    }
+                    _ => (),
+                }
+            }
+
+            ShaderExecution::Mesh(MeshShaderExecution {
+                output: output
+                    .expect("Mesh shader does not have an output primitive ExecutionMode"),
+                max_vertices: max_vertices
+                    .expect("Mesh shader does not have an OutputVertices ExecutionMode"),
+                max_primitives: max_primitives
+                    .expect("Mesh shader does not have an OutputPrimitivesEXT ExecutionMode"),
+            })
+        }
 
         ExecutionModel::Kernel => todo!(),
     }
@@ -672,6 +721,15 @@ fn inspect_entry_point(
                             self.instruction_chain([], image);
                         }
 
+                        Instruction::TraceRayKHR { accel, .. }
+                        | Instruction::RayQueryInitializeKHR { accel, .. } => {
+                            if let Some(desc_reqs) =
+                                desc_reqs(self.instruction_chain([inst_load], accel))
+                            {
+                                desc_reqs.memory_read = stage.into();
+                            }
+                        }
+
                         Instruction::ImageRead { image, .. } => {
                             if let Some(desc_reqs) =
                                 desc_reqs(self.instruction_chain([inst_load], image))
@@ -955,7 +1013,11 @@ fn descriptor_binding_requirements_of(spirv: &Spirv, variable_id: Id) -> Descrip
                 Some(element_type)
             }
 
-            Instruction::TypeAccelerationStructureKHR { .. } => None, // FIXME temporary workaround
+            Instruction::TypeAccelerationStructureKHR { .. } => {
+                reqs.descriptor_types = vec![DescriptorType::AccelerationStructure];
+
+                None
+            }
 
             _ => {
                 let name = variable_id_info
@@ -1180,6 +1242,216 @@ fn shader_interface(
     ShaderInterface { elements }
 }
 
+/// Extracts the outputs that are captured by transform feedback for the entry point
+/// `function_id` from `spirv`. Returns `None` if the entry point doesn't declare the `Xfb`
+/// execution mode.
+fn transform_feedback_outputs(
+    spirv: &Spirv,
+    function_id: Id,
+    interface: &[Id],
+) -> Option<Vec<ShaderTransformFeedbackOutput>> {
+    #[derive(Clone, Copy, Default)]
+    struct XfbDecorations {
+        buffer: Option<u32>,
+        stride: Option<u32>,
+        offset: Option<u32>,
+        stream: Option<u32>,
+    }
+
+    impl XfbDecorations {
+        fn new<'a>(instructions: impl Iterator<Item = &'a Instruction>) -> Self {
+            let mut decorations = Self::default();
+
+            for instruction in instructions {
+                let decoration = match instruction {
+                    Instruction::Decorate { decoration, .. }
+                    | Instruction::MemberDecorate { decoration, .. } => decoration,
+                    _ => continue,
+                };
+
+                match *decoration {
+                    Decoration::XfbBuffer { xfb_buffer_number } => {
+                        decorations.buffer = Some(xfb_buffer_number)
+                    }
+                    Decoration::XfbStride { xfb_stride } => decorations.stride = Some(xfb_stride),
+                    Decoration::Offset { byte_offset } => decorations.offset = Some(byte_offset),
+                    Decoration::Stream { stream_number } => {
+                        decorations.stream = Some(stream_number)
+                    }
+                    _ => (),
+                }
+            }
+
+            decorations
+        }
+
+        // Decorations on a block member override those on the block variable.
+        fn or(self, other: Self) -> Self {
+            Self {
+                buffer: self.buffer.or(other.buffer),
+                stride: self.stride.or(other.stride),
+                offset: self.offset.or(other.offset),
+                stream: self.stream.or(other.stream),
+            }
+        }
+    }
+
+    let has_xfb = spirv.iter_execution_mode().any(|instruction| {
+        matches!(
+            instruction,
+            Instruction::ExecutionMode {
+                entry_point,
+                mode: ExecutionMode::Xfb,
+                ..
+            } if *entry_point == function_id
+        )
+    });
+
+    if !has_xfb {
+        return None;
+    }
+
+    // Pairs of decorations and the type of the captured output.
+    let mut captured: Vec<(XfbDecorations, Id)> = Vec::new();
+
+    for &id in interface {
+        let (result_type_id, result_id) = match *spirv.id(id).instruction() {
+            Instruction::Variable {
+                result_type_id,
+                result_id,
+                storage_class: StorageClass::Output,
+                ..
+            } => (result_type_id, result_id),
+            _ => continue,
+        };
+
+        let type_id = match *spirv.id(result_type_id).instruction() {
+            Instruction::TypePointer { ty, .. } => ty,
+            _ => panic!(
+                "Variable {} result_type_id does not refer to a TypePointer instruction",
+                result_id,
+            ),
+        };
+
+        let variable_decorations = XfbDecorations::new(spirv.id(result_id).iter_decoration());
+
+        if variable_decorations.offset.is_some() {
+            captured.push((variable_decorations, type_id));
+            continue;
+        }
+
+        // If the variable itself has no offset, then individual members of a block can be
+        // captured instead.
+        let type_id_info = spirv.id(type_id);
+
+        if let Instruction::TypeStruct {
+            ref member_types, ..
+        } = *type_id_info.instruction()
+        {
+            for (&member_type_id, member_info) in
+                member_types.iter().zip(type_id_info.iter_members())
+            {
+                let member_decorations =
+                    XfbDecorations::new(member_info.iter_decoration()).or(variable_decorations);
+
+                if member_decorations.offset.is_some() {
+                    captured.push((member_decorations, member_type_id));
+                }
+            }
+        }
+    }
+
+    // The stride only needs to be declared once for each buffer.
+    let strides: HashMap<u32, u32> = captured
+        .iter()
+        .filter_map(|(decorations, _)| Some((decorations.buffer?, decorations.stride?)))
+        .collect();
+
+    Some(
+        captured
+            .into_iter()
+            .map(|(decorations, type_id)| {
+                let buffer = decorations
+                    .buffer
+                    .expect("Transform feedback output is missing an XfbBuffer decoration");
+                let stride = *strides.get(&buffer).unwrap_or_else(|| {
+                    panic!(
+                        "Transform feedback buffer {} is missing an XfbStride decoration",
+                        buffer,
+                    )
+                });
+
+                ShaderTransformFeedbackOutput {
+                    buffer,
+                    stride,
+                    offset: decorations.offset.unwrap(),
+                    size: transform_feedback_size_of_type(spirv, type_id),
+                    stream: decorations.stream.unwrap_or(0),
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Returns the number of bytes that a value of type `id` occupies when it is captured by
+/// transform feedback.
+fn transform_feedback_size_of_type(spirv: &Spirv, id: Id) -> u32 {
+    let id_info = spirv.id(id);
+
+    match *id_info.instruction() {
+        Instruction::TypeInt { width, .. } | Instruction::TypeFloat { width, .. } => {
+            assert!(width % 8 == 0);
+            width / 8
+        }
+        Instruction::TypeVector {
+            component_type,
+            component_count,
+            ..
+        } => transform_feedback_size_of_type(spirv, component_type) * component_count,
+        Instruction::TypeMatrix {
+            column_type,
+            column_count,
+            ..
+        } => transform_feedback_size_of_type(spirv, column_type) * column_count,
+        Instruction::TypeArray {
+            element_type,
+            length,
+            ..
+        } => {
+            let length = match *spirv.id(length).instruction() {
+                Instruction::Constant { ref value, .. } => value[0],
+                _ => panic!("Transform feedback output array has a non-constant length"),
+            };
+
+            transform_feedback_size_of_type(spirv, element_type) * length
+        }
+        Instruction::TypeStruct {
+            ref member_types, ..
+        } => {
+            let mut end_of_struct = 0;
+            let mut next_offset = 0;
+
+            for (&member, member_info) in member_types.iter().zip(id_info.iter_members()) {
+                let offset = member_info
+                    .iter_decoration()
+                    .find_map(|instruction| match *instruction {
+                        Instruction::MemberDecorate {
+                            decoration: Decoration::Offset { byte_offset },
+                            ..
+                        } => Some(byte_offset),
+                        _ => None,
+                    })
+                    .unwrap_or(next_offset);
+                next_offset = offset + transform_feedback_size_of_type(spirv, member);
+                end_of_struct = end_of_struct.max(next_offset);
+            }
+
+            end_of_struct
+        }
+        _ => panic!("Type {} can not be captured by transform feedback", id),
+    }
+}
+
 /// Returns the size of a type, or `None` if its size cannot be determined.
 fn size_of_type(spirv: &Spirv, id: Id) -> Option<DeviceSize> {
     let id_info = spirv.id(id);
@@ -30,7 +30,6 @@ use std::{
     fmt::{Debug, Display, Error as FmtError, Formatter},
     mem::MaybeUninit,
     num::NonZeroU64,
-    ptr,
     sync::{atomic::AtomicBool, Arc},
 };
 
@@ -41,6 +40,7 @@ pub struct Surface {
     handle: ash::vk::SurfaceKHR,
     instance: Arc<Instance>,
     id: NonZeroU64,
+    imported_handle: bool,
     api: SurfaceApi,
     object: Option<Arc<dyn Any + Send + Sync>>,
     // If true, a swapchain has been associated to this surface, and that any new swapchain
@@ -67,16 +67,29 @@ impl Surface {
     /// - `handle` must have been created from `api`.
     /// - The window object that `handle` was created from must outlive the created `Surface`.
     ///   The `object` parameter can be used to ensure this.
+    /// - `handle` must have been created with a null `pAllocator`, as it is destroyed with a null
+    ///   `pAllocator` when dropped.
     pub unsafe fn from_handle(
         instance: Arc<Instance>,
         handle: ash::vk::SurfaceKHR,
         api: SurfaceApi,
         object: Option<Arc<dyn Any + Send + Sync>>,
+    ) -> Self {
+        Self::from_handle_inner(instance, handle, api, object, true)
+    }
+
+    unsafe fn from_handle_inner(
+        instance: Arc<Instance>,
+        handle: ash::vk::SurfaceKHR,
+        api: SurfaceApi,
+        object: Option<Arc<dyn Any + Send + Sync>>,
+        imported_handle: bool,
     ) -> Self {
         Surface {
             handle,
             instance,
             id: Self::next_id(),
+            imported_handle,
             api,
             object,
             has_swapchain: AtomicBool::new(false),
@@ -131,7 +144,7 @@ impl Surface {
             (fns.ext_headless_surface.create_headless_surface_ext)(
                 instance.handle(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -139,11 +152,12 @@ impl Surface {
             output.assume_init()
         };
 
-        Ok(Arc::new(Self::from_handle(
+        Ok(Arc::new(Self::from_handle_inner(
             instance,
             handle,
             SurfaceApi::Headless,
             object,
+            false,
         )))
     }
 
@@ -220,7 +234,7 @@ impl Surface {
             (fns.khr_display.create_display_plane_surface_khr)(
                 instance.handle(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -228,11 +242,12 @@ impl Surface {
             output.assume_init()
         };
 
-        Ok(Arc::new(Self::from_handle(
+        Ok(Arc::new(Self::from_handle_inner(
             instance.clone(),
             handle,
             SurfaceApi::DisplayPlane,
             None,
+            false,
         )))
     }
 
@@ -291,7 +306,7 @@ impl Surface {
             (fns.khr_android_surface.create_android_surface_khr)(
                 instance.handle(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -299,11 +314,12 @@ impl Surface {
             output.assume_init()
         };
 
-        Ok(Arc::new(Self::from_handle(
+        Ok(Arc::new(Self::from_handle_inner(
             instance,
             handle,
             SurfaceApi::Android,
             object,
+            false,
         )))
     }
 
@@ -372,7 +388,7 @@ impl Surface {
             (fns.ext_directfb_surface.create_direct_fb_surface_ext)(
                 instance.handle(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -380,11 +396,12 @@ impl Surface {
             output.assume_init()
         };
 
-        Ok(Arc::new(Self::from_handle(
+        Ok(Arc::new(Self::from_handle_inner(
             instance,
             handle,
             SurfaceApi::DirectFB,
             object,
+            false,
         )))
     }
 
@@ -448,7 +465,7 @@ impl Surface {
                 .create_image_pipe_surface_fuchsia)(
                 instance.handle(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -456,11 +473,12 @@ impl Surface {
             output.assume_init()
         };
 
-        Ok(Arc::new(Self::from_handle(
+        Ok(Arc::new(Self::from_handle_inner(
             instance,
             handle,
             SurfaceApi::FuchsiaImagePipe,
             object,
+            false,
         )))
     }
 
@@ -524,7 +542,7 @@ impl Surface {
                 .create_stream_descriptor_surface_ggp)(
                 instance.handle(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -532,11 +550,12 @@ impl Surface {
             output.assume_init()
         };
 
-        Ok(Arc::new(Self::from_handle(
+        Ok(Arc::new(Self::from_handle_inner(
             instance,
             handle,
             SurfaceApi::GgpStreamDescriptor,
             object,
+            false,
         )))
     }
 
@@ -602,7 +621,7 @@ impl Surface {
             (fns.mvk_ios_surface.create_ios_surface_mvk)(
                 instance.handle(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -610,11 +629,12 @@ impl Surface {
             output.assume_init()
         };
 
-        Ok(Arc::new(Self::from_handle(
+        Ok(Arc::new(Self::from_handle_inner(
             instance,
             handle,
             SurfaceApi::Ios,
             object,
+            false,
         )))
     }
 
@@ -680,7 +700,7 @@ impl Surface {
             (fns.mvk_macos_surface.create_mac_os_surface_mvk)(
                 instance.handle(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -688,11 +708,12 @@ impl Surface {
             output.assume_init()
         };
 
-        Ok(Arc::new(Self::from_handle(
+        Ok(Arc::new(Self::from_handle_inner(
             instance,
             handle,
             SurfaceApi::MacOs,
             object,
+            false,
         )))
     }
 
@@ -748,7 +769,7 @@ impl Surface {
             (fns.ext_metal_surface.create_metal_surface_ext)(
                 instance.handle(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -756,11 +777,12 @@ impl Surface {
             output.assume_init()
         };
 
-        Ok(Arc::new(Self::from_handle(
+        Ok(Arc::new(Self::from_handle_inner(
             instance,
             handle,
             SurfaceApi::Metal,
             object,
+            false,
         )))
     }
 
@@ -829,7 +851,7 @@ impl Surface {
             (fns.qnx_screen_surface.create_screen_surface_qnx)(
                 instance.handle(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -837,11 +859,12 @@ impl Surface {
             output.assume_init()
         };
 
-        Ok(Arc::new(Self::from_handle(
+        Ok(Arc::new(Self::from_handle_inner(
             instance,
             handle,
             SurfaceApi::Qnx,
             object,
+            false,
         )))
     }
 
@@ -900,7 +923,7 @@ impl Surface {
             (fns.nn_vi_surface.create_vi_surface_nn)(
                 instance.handle(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -908,11 +931,12 @@ impl Surface {
             output.assume_init()
         };
 
-        Ok(Arc::new(Self::from_handle(
+        Ok(Arc::new(Self::from_handle_inner(
             instance,
             handle,
             SurfaceApi::Vi,
             object,
+            false,
         )))
     }
 
@@ -983,7 +1007,7 @@ impl Surface {
             (fns.khr_wayland_surface.create_wayland_surface_khr)(
                 instance.handle(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -991,11 +1015,12 @@ impl Surface {
             output.assume_init()
         };
 
-        Ok(Arc::new(Self::from_handle(
+        Ok(Arc::new(Self::from_handle_inner(
             instance,
             handle,
             SurfaceApi::Wayland,
             object,
+            false,
         )))
     }
 
@@ -1066,7 +1091,7 @@ impl Surface {
             (fns.khr_win32_surface.create_win32_surface_khr)(
                 instance.handle(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -1074,11 +1099,12 @@ impl Surface {
             output.assume_init()
         };
 
-        Ok(Arc::new(Self::from_handle(
+        Ok(Arc::new(Self::from_handle_inner(
             instance,
             handle,
             SurfaceApi::Win32,
             object,
+            false,
         )))
     }
 
@@ -1149,7 +1175,7 @@ impl Surface {
             (fns.khr_xcb_surface.create_xcb_surface_khr)(
                 instance.handle(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -1157,11 +1183,12 @@ impl Surface {
             output.assume_init()
         };
 
-        Ok(Arc::new(Self::from_handle(
+        Ok(Arc::new(Self::from_handle_inner(
             instance,
             handle,
             SurfaceApi::Xcb,
             object,
+            false,
         )))
     }
 
@@ -1232,7 +1259,7 @@ impl Surface {
             (fns.khr_xlib_surface.create_xlib_surface_khr)(
                 instance.handle(),
                 &create_info,
-                ptr::null(),
+                instance.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -1240,11 +1267,12 @@ impl Surface {
             output.assume_init()
         };
 
-        Ok(Arc::new(Self::from_handle(
+        Ok(Arc::new(Self::from_handle_inner(
             instance,
             handle,
             SurfaceApi::Xlib,
             object,
+            false,
         )))
     }
 
@@ -1291,7 +1319,12 @@ impl Drop for Surface {
     fn drop(&mut self) {
         unsafe {
             let fns = self.instance.fns();
-            (fns.khr_surface.destroy_surface_khr)(self.instance.handle(), self.handle, ptr::null());
+            (fns.khr_surface.destroy_surface_khr)(
+                self.instance.handle(),
+                self.handle,
+                self.instance
+                    .destroy_allocation_callbacks(self.imported_handle),
+            );
         }
     }
 }
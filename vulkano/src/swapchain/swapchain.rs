@@ -13,6 +13,7 @@ use super::{
 };
 use crate::{
     buffer::sys::Buffer,
+    command_buffer::{SemaphoreSubmitInfo, SubmitInfo},
     device::{Device, DeviceOwned, Queue},
     format::Format,
     image::{
@@ -24,7 +25,7 @@ use crate::{
     sync::{
         fence::{Fence, FenceError},
         future::{AccessCheckError, AccessError, FlushError, GpuFuture, SubmitAnyBuilder},
-        semaphore::{Semaphore, SemaphoreError},
+        semaphore::{Semaphore, SemaphoreError, SemaphoreType},
         Sharing,
     },
     DeviceSize, OomError, RequirementNotMet, RequiresOneOf, VulkanError, VulkanObject,
@@ -649,7 +650,7 @@ impl Swapchain {
             (fns.khr_swapchain.create_swapchain_khr)(
                 device.handle(),
                 &info_vk,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -911,7 +912,7 @@ impl Drop for Swapchain {
             (fns.khr_swapchain.destroy_swapchain_khr)(
                 self.device.handle(),
                 self.handle,
-                ptr::null(),
+                self.device.allocation_callbacks(),
             );
             self.surface.flag().store(false, Ordering::Release);
         }
@@ -1545,6 +1546,7 @@ where
         previous: before,
         queue,
         swapchain_info,
+        timeline_bridge: Mutex::new(None),
         flushed: AtomicBool::new(false),
         finished: AtomicBool::new(false),
     }
@@ -1655,7 +1657,7 @@ unsafe impl GpuFuture for SwapchainAcquireFuture {
 
     unsafe fn build_submission(&self) -> Result<SubmitAnyBuilder, FlushError> {
         if let Some(ref semaphore) = self.semaphore {
-            let sem = smallvec![semaphore.clone()];
+            let sem = smallvec![SemaphoreSubmitInfo::semaphore(semaphore.clone())];
             Ok(SubmitAnyBuilder::SemaphoresWait(sem))
         } else {
             Ok(SubmitAnyBuilder::Empty)
@@ -1956,6 +1958,9 @@ where
     previous: P,
     queue: Arc<Queue>,
     swapchain_info: SwapchainPresentInfo,
+    // Binary semaphore that is signaled once the timeline semaphore waits of the previous future
+    // are satisfied, since presenting can only wait on binary semaphores.
+    timeline_bridge: Mutex<Option<Arc<Semaphore>>>,
     // True if `flush()` has been called on the future, which means that the present command has
     // been submitted.
     flushed: AtomicBool,
@@ -2019,8 +2024,45 @@ where
                 ..Default::default()
             }),
             SubmitAnyBuilder::SemaphoresWait(semaphores) => {
+                let (timeline_semaphores, mut wait_semaphores): (Vec<_>, Vec<_>) = semaphores
+                    .into_iter()
+                    .partition(|info| info.semaphore.semaphore_type() == SemaphoreType::Timeline);
+
+                if !timeline_semaphores.is_empty() {
+                    // VUID-vkQueuePresentKHR-pWaitSemaphores-03267
+                    // Presenting can't wait on timeline semaphores, so we submit an empty batch
+                    // that converts the waits into a binary semaphore signal.
+                    let mut timeline_bridge = self.timeline_bridge.lock();
+
+                    let bridge = match &*timeline_bridge {
+                        Some(bridge) => bridge.clone(),
+                        None => {
+                            let bridge = Arc::new(Semaphore::from_pool(device.clone())?);
+                            self.queue.with(|mut q| {
+                                q.submit_unchecked(
+                                    [SubmitInfo {
+                                        wait_semaphores: timeline_semaphores,
+                                        signal_semaphores: vec![SemaphoreSubmitInfo::semaphore(
+                                            bridge.clone(),
+                                        )],
+                                        ..Default::default()
+                                    }],
+                                    None,
+                                )
+                            })?;
+                            *timeline_bridge = Some(bridge.clone());
+                            bridge
+                        }
+                    };
+
+                    wait_semaphores.push(SemaphoreSubmitInfo::semaphore(bridge));
+                }
+
                 SubmitAnyBuilder::QueuePresent(PresentInfo {
-                    wait_semaphores: semaphores.into_iter().collect(),
+                    wait_semaphores: wait_semaphores
+                        .into_iter()
+                        .map(|info| info.semaphore)
+                        .collect(),
                     swapchain_infos: vec![self.swapchain_info.clone()],
                     ..Default::default()
                 })
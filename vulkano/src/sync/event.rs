@@ -33,7 +33,6 @@ use std::{
     fmt::{Display, Error as FmtError, Formatter},
     mem::MaybeUninit,
     num::NonZeroU64,
-    ptr,
     sync::Arc,
 };
 
@@ -49,6 +48,7 @@ pub struct Event {
     device: Arc<Device>,
     id: NonZeroU64,
     must_put_in_pool: bool,
+    imported_handle: bool,
 }
 
 impl Event {
@@ -83,7 +83,7 @@ impl Event {
             (fns.v1_0.create_event)(
                 device.handle(),
                 &create_info,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -96,6 +96,7 @@ impl Event {
             device,
             id: Self::next_id(),
             must_put_in_pool: false,
+            imported_handle: false,
         })
     }
 
@@ -122,6 +123,7 @@ impl Event {
                     device,
                     id: Self::next_id(),
                     must_put_in_pool: true,
+                    imported_handle: false,
                 }
             }
             None => {
@@ -141,6 +143,8 @@ impl Event {
     ///
     /// - `handle` must be a valid Vulkan object handle created from `device`.
     /// - `create_info` must match the info used to create the object.
+    /// - `handle` must have been created with a null `pAllocator`, as it is destroyed with a null
+    ///   `pAllocator` when dropped.
     #[inline]
     pub unsafe fn from_handle(
         device: Arc<Device>,
@@ -152,6 +156,7 @@ impl Event {
             device,
             id: Self::next_id(),
             must_put_in_pool: false,
+            imported_handle: true,
         }
     }
 
@@ -225,7 +230,12 @@ impl Drop for Event {
                 self.device.event_pool().lock().push(raw_event);
             } else {
                 let fns = self.device.fns();
-                (fns.v1_0.destroy_event)(self.device.handle(), self.handle, ptr::null());
+                (fns.v1_0.destroy_event)(
+                    self.device.handle(),
+                    self.handle,
+                    self.device
+                        .destroy_allocation_callbacks(self.imported_handle),
+                );
             }
         }
     }
@@ -15,7 +15,7 @@ use crate::{
     macros::{vulkan_bitflags, vulkan_bitflags_enum},
     OomError, RequirementNotMet, RequiresOneOf, Version, VulkanError, VulkanObject,
 };
-use parking_lot::{Mutex, MutexGuard};
+use parking_lot::{Condvar, Mutex, MutexGuard};
 use smallvec::SmallVec;
 #[cfg(unix)]
 use std::fs::File;
@@ -26,9 +26,9 @@ use std::{
     mem::MaybeUninit,
     num::NonZeroU64,
     pin::Pin,
-    ptr,
     sync::{Arc, Weak},
-    task::{Context, Poll},
+    task::{Context, Poll, Waker},
+    thread,
     time::Duration,
 };
 
@@ -61,6 +61,7 @@ pub struct Fence {
     device: Arc<Device>,
     id: NonZeroU64,
     must_put_in_pool: bool,
+    imported_handle: bool,
 
     export_handle_types: ExternalFenceHandleTypes,
 
@@ -168,7 +169,7 @@ impl Fence {
             (fns.v1_0.create_fence)(
                 device.handle(),
                 &create_info_vk,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -182,6 +183,7 @@ impl Fence {
             device,
             id: Self::next_id(),
             must_put_in_pool: false,
+            imported_handle: false,
             export_handle_types,
             state: Mutex::new(FenceState {
                 is_signaled: signaled,
@@ -214,6 +216,7 @@ impl Fence {
                     device,
                     id: Self::next_id(),
                     must_put_in_pool: true,
+                    imported_handle: false,
                     export_handle_types: ExternalFenceHandleTypes::empty(),
                     state: Mutex::new(Default::default()),
                 }
@@ -235,6 +238,8 @@ impl Fence {
     ///
     /// - `handle` must be a valid Vulkan object handle created from `device`.
     /// - `create_info` must match the info used to create the object.
+    /// - `handle` must have been created with a null `pAllocator`, as it is destroyed with a null
+    ///   `pAllocator` when dropped.
     #[inline]
     pub unsafe fn from_handle(
         device: Arc<Device>,
@@ -252,6 +257,7 @@ impl Fence {
             device,
             id: Self::next_id(),
             must_put_in_pool: false,
+            imported_handle: true,
             export_handle_types,
             state: Mutex::new(FenceState {
                 is_signaled: signaled,
@@ -1064,10 +1070,6 @@ impl Fence {
 
     // Shared by Fence and FenceSignalFuture
     pub(crate) fn poll_impl(&self, cx: &mut Context<'_>) -> Poll<Result<(), OomError>> {
-        // Vulkan only allows polling of the fence status, so we have to use a spin future.
-        // This is still better than blocking in async applications, since a smart-enough async engine
-        // can choose to run some other tasks between probing this one.
-
         // Check if we are done without blocking
         match self.is_signaled() {
             Err(e) => return Poll::Ready(Err(e)),
@@ -1078,8 +1080,11 @@ impl Fence {
             }
         }
 
-        // Otherwise spin
-        cx.waker().wake_by_ref();
+        // Vulkan only allows polling of the fence status, so we let the waiter thread of the
+        // device block on the fence for us, and wake up the task once it is signaled.
+        self.device
+            .fence_waiter()
+            .register(&self.device, self.handle, cx.waker());
         Poll::Pending
     }
 }
@@ -1087,13 +1092,20 @@ impl Fence {
 impl Drop for Fence {
     #[inline]
     fn drop(&mut self) {
+        self.device.fence_waiter().unregister(self.handle);
+
         unsafe {
             if self.must_put_in_pool {
                 let raw_fence = self.handle;
                 self.device.fence_pool().lock().push(raw_fence);
             } else {
                 let fns = self.device.fns();
-                (fns.v1_0.destroy_fence)(self.device.handle(), self.handle, ptr::null());
+                (fns.v1_0.destroy_fence)(
+                    self.device.handle(),
+                    self.handle,
+                    self.device
+                        .destroy_allocation_callbacks(self.imported_handle),
+                );
             }
         }
     }
@@ -1139,7 +1151,7 @@ pub(crate) struct FenceState {
 impl FenceState {
     /// If the fence is not in a queue and has no external references, returns the current status.
     #[inline]
-    fn is_signaled(&self) -> Option<bool> {
+    pub(crate) fn is_signaled(&self) -> Option<bool> {
         // If either of these is true, we can't be certain of the status.
         if self.is_in_queue() || self.has_external_reference() {
             None
@@ -1149,7 +1161,7 @@ impl FenceState {
     }
 
     #[inline]
-    fn is_in_queue(&self) -> bool {
+    pub(crate) fn is_in_queue(&self) -> bool {
         self.pending_signal.is_some()
     }
 
@@ -1243,6 +1255,161 @@ impl From<ExternalFenceHandleType> for ImportType {
     }
 }
 
+/// A background thread that waits on fences on behalf of the futures that poll them, so that
+/// awaiting a fence doesn't need to spin or block a thread of the async runtime.
+///
+/// There is one waiter per device, and the thread is only spawned when a fence is first awaited.
+#[derive(Debug, Default)]
+pub(crate) struct FenceWaiter {
+    shared: Arc<FenceWaiterShared>,
+}
+
+#[derive(Debug, Default)]
+struct FenceWaiterShared {
+    state: Mutex<FenceWaiterState>,
+    condvar: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct FenceWaiterState {
+    // The fences that are being awaited, with the wakers of the tasks awaiting them.
+    fences: Vec<(ash::vk::Fence, Waker)>,
+    // The fences that the thread is currently passing to `vkWaitForFences`.
+    waiting_on: SmallVec<[ash::vk::Fence; 8]>,
+    thread_running: bool,
+    shutdown: bool,
+}
+
+impl FenceWaiter {
+    // The maximum time that the thread waits before picking up newly registered fences.
+    const WAIT_INTERVAL: Duration = Duration::from_millis(5);
+
+    /// Registers `waker` to be woken when `fence` is signaled.
+    pub(crate) fn register(&self, device: &Arc<Device>, fence: ash::vk::Fence, waker: &Waker) {
+        let mut state = self.shared.state.lock();
+
+        if let Some((_, registered_waker)) = state
+            .fences
+            .iter_mut()
+            .find(|(f, w)| *f == fence && w.will_wake(waker))
+        {
+            *registered_waker = waker.clone();
+        } else {
+            state.fences.push((fence, waker.clone()));
+        }
+
+        if !state.thread_running {
+            let shared = self.shared.clone();
+            let device = Arc::downgrade(device);
+
+            let spawned = thread::Builder::new()
+                .name("vulkano fence waiter".into())
+                .spawn(move || Self::run(&shared, &device));
+
+            if spawned.is_err() {
+                // Without a thread, nothing would ever wake the task. Fall back to polling
+                // instead, by having the task poll the fence again right away.
+                state
+                    .fences
+                    .retain(|(f, w)| !(*f == fence && w.will_wake(waker)));
+                drop(state);
+                waker.wake_by_ref();
+
+                return;
+            }
+
+            state.thread_running = true;
+        }
+
+        self.shared.condvar.notify_all();
+    }
+
+    /// Removes all registrations for `fence`, and blocks until the thread no longer uses it.
+    pub(crate) fn unregister(&self, fence: ash::vk::Fence) {
+        let mut state = self.shared.state.lock();
+        state.fences.retain(|&(f, _)| f != fence);
+
+        while state.waiting_on.contains(&fence) {
+            self.shared.condvar.wait(&mut state);
+        }
+    }
+
+    /// Stops the thread, if it is running.
+    pub(crate) fn shutdown(&self) {
+        self.shared.state.lock().shutdown = true;
+        self.shared.condvar.notify_all();
+    }
+
+    fn run(shared: &FenceWaiterShared, device: &Weak<Device>) {
+        let mut state = shared.state.lock();
+
+        while !state.shutdown {
+            if state.fences.is_empty() {
+                shared.condvar.wait(&mut state);
+                continue;
+            }
+
+            // The registered fences keep the device alive, so this only fails if the device is
+            // being destroyed.
+            let device = match device.upgrade() {
+                Some(device) => device,
+                None => break,
+            };
+
+            state.waiting_on = state.fences.iter().map(|&(fence, _)| fence).collect();
+            let handles = state.waiting_on.clone();
+
+            let result = MutexGuard::unlocked(&mut state, || unsafe {
+                let fns = device.fns();
+                (fns.v1_0.wait_for_fences)(
+                    device.handle(),
+                    handles.len() as u32,
+                    handles.as_ptr(),
+                    ash::vk::FALSE,
+                    Self::WAIT_INTERVAL.as_nanos() as u64,
+                )
+            });
+
+            state.waiting_on.clear();
+            shared.condvar.notify_all();
+
+            let mut woken: SmallVec<[Waker; 8]> = SmallVec::new();
+
+            match result {
+                ash::vk::Result::TIMEOUT => (),
+                ash::vk::Result::SUCCESS => {
+                    state.fences.retain(|(fence, waker)| {
+                        let status = unsafe {
+                            let fns = device.fns();
+                            (fns.v1_0.get_fence_status)(device.handle(), *fence)
+                        };
+
+                        if status == ash::vk::Result::NOT_READY {
+                            true
+                        } else {
+                            woken.push(waker.clone());
+                            false
+                        }
+                    });
+                }
+                _ => {
+                    // Let the futures find out about the error themselves.
+                    woken.extend(state.fences.drain(..).map(|(_, waker)| waker));
+                }
+            }
+
+            // Wake the tasks and drop the device without holding the lock, since polling a fence
+            // registers it again, and dropping the device shuts down the waiter.
+            MutexGuard::unlocked(&mut state, || {
+                woken.into_iter().for_each(Waker::wake);
+                drop(device);
+            });
+        }
+
+        state.thread_running = false;
+    }
+}
+
 /// Parameters to create a new `Fence`.
 #[derive(Clone, Debug)]
 pub struct FenceCreateInfo {
@@ -1614,7 +1781,12 @@ mod tests {
         sync::fence::{Fence, FenceCreateInfo},
         VulkanObject,
     };
-    use std::time::Duration;
+    use std::{
+        sync::Arc,
+        task::{Context, Poll, Wake, Waker},
+        thread,
+        time::Duration,
+    };
 
     #[test]
     fn fence_create() {
@@ -1654,6 +1826,36 @@ mod tests {
         fence.wait(Some(Duration::new(0, 10))).unwrap();
     }
 
+    #[test]
+    fn fence_await() {
+        struct ThreadWaker(thread::Thread);
+
+        impl Wake for ThreadWaker {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let fence = Arc::new(Fence::new(device, Default::default()).unwrap());
+        unsafe {
+            queue
+                .with(|mut q| q.submit_unchecked([Default::default()], Some(fence.clone())))
+                .unwrap();
+        }
+
+        // Minimal executor, to not depend on an async runtime.
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+
+        while let Poll::Pending = fence.poll_impl(&mut cx) {
+            thread::park();
+        }
+
+        assert!(fence.is_signaled().unwrap());
+    }
+
     #[test]
     fn fence_reset() {
         let (device, _) = gfx_dev_and_queue!();
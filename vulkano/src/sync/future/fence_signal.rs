@@ -10,14 +10,13 @@
 use super::{AccessCheckError, FlushError, GpuFuture};
 use crate::{
     buffer::sys::Buffer,
-    command_buffer::{SemaphoreSubmitInfo, SubmitInfo},
+    command_buffer::SubmitInfo,
     device::{Device, DeviceOwned, Queue, QueueFlags},
     image::{sys::Image, ImageLayout},
     swapchain::Swapchain,
     sync::{
         fence::Fence,
         future::{AccessError, SubmitAnyBuilder},
-        PipelineStages,
     },
     DeviceSize, OomError,
 };
@@ -258,16 +257,7 @@ where
                         .with(|mut q| {
                             q.submit_unchecked(
                                 [SubmitInfo {
-                                    wait_semaphores: semaphores
-                                        .into_iter()
-                                        .map(|semaphore| {
-                                            SemaphoreSubmitInfo {
-                                                // TODO: correct stages ; hard
-                                                stages: PipelineStages::ALL_COMMANDS,
-                                                ..SemaphoreSubmitInfo::semaphore(semaphore)
-                                            }
-                                        })
-                                        .collect(),
+                                    wait_semaphores: semaphores.into_iter().collect(),
                                     ..Default::default()
                                 }],
                                 None,
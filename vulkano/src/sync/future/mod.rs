@@ -95,16 +95,17 @@ pub use self::{
     join::JoinFuture,
     now::{now, NowFuture},
     semaphore_signal::SemaphoreSignalFuture,
+    timeline_signal::TimelineSignalFuture,
 };
 use super::{
     fence::{Fence, FenceError},
-    semaphore::Semaphore,
+    semaphore::{Semaphore, SemaphoreError},
 };
 use crate::{
     buffer::sys::Buffer,
     command_buffer::{
         CommandBufferExecError, CommandBufferExecFuture, PrimaryCommandBufferAbstract,
-        ResourceUseRef, SubmitInfo,
+        ResourceUseRef, SemaphoreSubmitInfo, SubmitInfo,
     },
     device::{DeviceOwned, Queue},
     image::{sys::Image, ImageLayout},
@@ -124,6 +125,7 @@ mod fence_signal;
 mod join;
 mod now;
 mod semaphore_signal;
+mod timeline_signal;
 
 /// Represents an event that will happen on the GPU in the future.
 ///
@@ -338,6 +340,52 @@ pub unsafe trait GpuFuture: DeviceOwned {
         Ok(f)
     }
 
+    /// Signals a timeline semaphore after this future, setting its counter to `value`. Returns
+    /// another future that represents the signal.
+    ///
+    /// Unlike a fence, the returned future can be waited upon by operations on any queue, and can
+    /// be waited upon by several operations at once by putting it in an `Arc`. The host can also
+    /// wait for the signal, by calling `wait()` on the returned future.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `semaphore` is not a timeline semaphore.
+    /// - Panics if `semaphore` does not belong to the same device as the future.
+    ///
+    /// When the returned future is flushed, `value` must be greater than the current counter
+    /// value of `semaphore`, and greater than the value of any signal operation on `semaphore`
+    /// that is still pending in a queue.
+    #[inline]
+    fn then_signal_timeline(
+        self,
+        semaphore: Arc<Semaphore>,
+        value: u64,
+    ) -> TimelineSignalFuture<Self>
+    where
+        Self: Sized,
+    {
+        timeline_signal::then_signal_timeline(self, semaphore, value)
+    }
+
+    /// Signals a timeline semaphore after this future and flushes it. Returns another future that
+    /// represents the signal.
+    ///
+    /// This is a just a shortcut for `then_signal_timeline()` followed with `flush()`.
+    #[inline]
+    fn then_signal_timeline_and_flush(
+        self,
+        semaphore: Arc<Semaphore>,
+        value: u64,
+    ) -> Result<TimelineSignalFuture<Self>, FlushError>
+    where
+        Self: Sized,
+    {
+        let f = self.then_signal_timeline(semaphore, value);
+        f.flush()?;
+
+        Ok(f)
+    }
+
     /// Presents a swapchain image after this future.
     ///
     /// You should only ever do this indirectly after a `SwapchainAcquireFuture` of the same image,
@@ -466,7 +514,7 @@ where
 #[derive(Debug)]
 pub enum SubmitAnyBuilder {
     Empty,
-    SemaphoresWait(SmallVec<[Arc<Semaphore>; 8]>),
+    SemaphoresWait(SmallVec<[SemaphoreSubmitInfo; 8]>),
     CommandBuffer(SubmitInfo, Option<Arc<Fence>>),
     QueuePresent(PresentInfo),
     BindSparse(SmallVec<[BindSparseInfo; 1]>, Option<Arc<Fence>>),
@@ -506,6 +554,10 @@ pub enum AccessError {
 
     /// Trying to use a swapchain image without depending on a corresponding acquire image future.
     SwapchainImageNotAcquired,
+
+    /// Trying to use a sparse buffer or image that was not created with the `SPARSE_RESIDENCY`
+    /// flag, while not all of it is bound to memory.
+    SparseNotFullyBound,
 }
 
 impl Error for AccessError {}
@@ -534,6 +586,10 @@ impl Display for AccessError {
                     "trying to use a swapchain image without depending on a corresponding acquire \
                     image future"
                 }
+                AccessError::SparseNotFullyBound => {
+                    "trying to use a sparse buffer or image that was not created with the \
+                    `SPARSE_RESIDENCY` flag, while not all of it is bound to memory"
+                }
             }
         )
     }
@@ -612,6 +668,32 @@ pub enum FlushError {
     /// The command buffer or one of the secondary command buffers it executes is already in use by
     /// the GPU and was not created with the "concurrent" flag.
     ExclusiveAlreadyInUse,
+
+    /// The value to signal a timeline semaphore with is not greater than the current counter
+    /// value of the semaphore, or than the value of a signal operation that is pending in a queue.
+    TimelineSignalValueNotGreater,
+
+    /// The difference between the value to signal a timeline semaphore with and the current
+    /// counter value of the semaphore exceeds the [`max_timeline_semaphore_value_difference`]
+    /// device limit.
+    ///
+    /// [`max_timeline_semaphore_value_difference`]: crate::device::Properties::max_timeline_semaphore_value_difference
+    MaxTimelineSemaphoreValueDifferenceExceeded,
+
+    /// A command buffer ends with a suspended render pass instance, but the next command buffer in
+    /// the submission does not resume it.
+    SuspendedRenderPassNotResumed,
+
+    /// A command buffer begins by resuming a render pass instance, but the previous command buffer
+    /// in the submission does not end with a suspended render pass instance.
+    ResumedRenderPassNotSuspended,
+
+    /// The submission contains both protected and unprotected command buffers.
+    ProtectedMismatch,
+
+    /// The submission contains protected command buffers, but the queue was not created with the
+    /// `protected` flag.
+    QueueNotProtected,
 }
 
 impl Error for FlushError {
@@ -655,6 +737,31 @@ impl Display for FlushError {
                     "the command buffer or one of the secondary command buffers it executes is \
                     already in use was not created with the \"concurrent\" flag"
                 }
+                FlushError::TimelineSignalValueNotGreater => {
+                    "the value to signal a timeline semaphore with is not greater than the current \
+                    counter value of the semaphore, or than the value of a pending signal operation"
+                }
+                FlushError::MaxTimelineSemaphoreValueDifferenceExceeded => {
+                    "the difference between the value to signal a timeline semaphore with and the \
+                    current counter value of the semaphore exceeds the \
+                    `max_timeline_semaphore_value_difference` limit"
+                }
+                FlushError::SuspendedRenderPassNotResumed => {
+                    "a command buffer ends with a suspended render pass instance, but the next \
+                    command buffer in the submission does not resume it"
+                }
+                FlushError::ResumedRenderPassNotSuspended => {
+                    "a command buffer begins by resuming a render pass instance, but the previous \
+                    command buffer in the submission does not end with a suspended render pass \
+                    instance"
+                }
+                FlushError::ProtectedMismatch => {
+                    "the submission contains both protected and unprotected command buffers"
+                }
+                FlushError::QueueNotProtected => {
+                    "the submission contains protected command buffers, but the queue was not \
+                    created with the `protected` flag"
+                }
             }
         )
     }
@@ -691,3 +798,36 @@ impl From<FenceError> for FlushError {
         }
     }
 }
+
+impl From<SemaphoreError> for FlushError {
+    fn from(err: SemaphoreError) -> FlushError {
+        match err {
+            SemaphoreError::OomError(err) => FlushError::OomError(err),
+            SemaphoreError::Timeout => FlushError::Timeout,
+            SemaphoreError::DeviceLost => FlushError::DeviceLost,
+            SemaphoreError::MaxTimelineSemaphoreValueDifferenceExceeded { .. } => {
+                FlushError::MaxTimelineSemaphoreValueDifferenceExceeded
+            }
+            SemaphoreError::SignalValueNotGreaterThanCurrent { .. }
+            | SemaphoreError::SignalValueNotLessThanPending { .. } => {
+                FlushError::TimelineSignalValueNotGreater
+            }
+            SemaphoreError::RequirementNotMet { .. }
+            | SemaphoreError::AlreadyExported
+            | SemaphoreError::BinaryInitialValueNotZero
+            | SemaphoreError::ExportFromImportedNotSupported { .. }
+            | SemaphoreError::ExportHandleTypesNotCompatible
+            | SemaphoreError::HandleTypeCopyNotSignaled
+            | SemaphoreError::HandletypeCopyNotTemporary
+            | SemaphoreError::HandleTypeNotEnabled
+            | SemaphoreError::HandleTypeNotExportable { .. }
+            | SemaphoreError::HandleTypeNotFd
+            | SemaphoreError::HandleTypeNotWin32
+            | SemaphoreError::HandleTypeNotZircon
+            | SemaphoreError::ImportedForSwapchainAcquire
+            | SemaphoreError::InQueue
+            | SemaphoreError::NotTimeline
+            | SemaphoreError::QueueIsWaiting => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
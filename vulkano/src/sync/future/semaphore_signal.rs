@@ -14,7 +14,7 @@ use crate::{
     device::{Device, DeviceOwned, Queue},
     image::{sys::Image, ImageLayout},
     swapchain::Swapchain,
-    sync::{future::AccessError, semaphore::Semaphore, PipelineStages},
+    sync::{future::AccessError, semaphore::Semaphore},
     DeviceSize,
 };
 use parking_lot::Mutex;
@@ -71,7 +71,8 @@ where
     unsafe fn build_submission(&self) -> Result<SubmitAnyBuilder, FlushError> {
         // Flushing the signaling part, since it must always be submitted before the waiting part.
         self.flush()?;
-        let sem = smallvec![self.semaphore.clone()];
+        // TODO: correct stages ; hard
+        let sem = smallvec![SemaphoreSubmitInfo::semaphore(self.semaphore.clone())];
 
         Ok(SubmitAnyBuilder::SemaphoresWait(sem))
     }
@@ -104,16 +105,7 @@ where
                     queue.with(|mut q| {
                         q.submit_unchecked(
                             [SubmitInfo {
-                                wait_semaphores: semaphores
-                                    .into_iter()
-                                    .map(|semaphore| {
-                                        SemaphoreSubmitInfo {
-                                            // TODO: correct stages ; hard
-                                            stages: PipelineStages::ALL_COMMANDS,
-                                            ..SemaphoreSubmitInfo::semaphore(semaphore)
-                                        }
-                                    })
-                                    .collect(),
+                                wait_semaphores: semaphores.into_iter().collect(),
                                 signal_semaphores: vec![SemaphoreSubmitInfo::semaphore(
                                     self.semaphore.clone(),
                                 )],
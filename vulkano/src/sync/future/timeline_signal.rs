@@ -0,0 +1,576 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{AccessCheckError, FlushError, GpuFuture, SubmitAnyBuilder};
+use crate::{
+    buffer::sys::Buffer,
+    command_buffer::{SemaphoreSubmitInfo, SubmitInfo},
+    device::{Device, DeviceOwned, Queue},
+    image::{sys::Image, ImageLayout},
+    memory::BindSparseInfo,
+    swapchain::Swapchain,
+    sync::{
+        future::AccessError,
+        semaphore::{Semaphore, SemaphoreType},
+    },
+    DeviceSize,
+};
+use parking_lot::Mutex;
+use smallvec::smallvec;
+use std::{
+    ops::Range,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// Builds a new timeline semaphore signal future.
+pub fn then_signal_timeline<F>(
+    future: F,
+    semaphore: Arc<Semaphore>,
+    value: u64,
+) -> TimelineSignalFuture<F>
+where
+    F: GpuFuture,
+{
+    assert_eq!(semaphore.semaphore_type(), SemaphoreType::Timeline);
+    assert_eq!(future.device(), semaphore.device());
+    assert!(future.queue().is_some()); // TODO: document
+
+    TimelineSignalFuture {
+        previous: future,
+        semaphore,
+        value,
+        signal_submitted: Mutex::new(false),
+        bind_sparse_semaphore: Mutex::new(None),
+        finished: AtomicBool::new(false),
+    }
+}
+
+/// Represents a timeline semaphore being signaled with a value after a previous event.
+///
+/// Contrary to a [`SemaphoreSignalFuture`](super::SemaphoreSignalFuture), the signal can be waited
+/// upon any number of times, by the device as well as by the host. It is therefore possible to put
+/// this future in an `Arc` and to chain multiple operations after it, possibly on different
+/// queues.
+#[must_use = "Dropping this object will immediately block the thread until the GPU has finished \
+              processing the submission"]
+pub struct TimelineSignalFuture<F>
+where
+    F: GpuFuture,
+{
+    previous: F,
+    semaphore: Arc<Semaphore>,
+    value: u64,
+    // True if the signaling command has already been submitted.
+    // If flush is called multiple times, we want to block so that only one flushing is executed.
+    // Therefore we use a `Mutex<bool>` and not an `AtomicBool`.
+    signal_submitted: Mutex<bool>,
+    // If the previous future was a sparse binding operation, the binary semaphore that it signals
+    // once it has been submitted. The submission that waits on it and signals the timeline
+    // semaphore can then be retried on the next flush, without binding memory a second time.
+    bind_sparse_semaphore: Mutex<Option<Arc<Semaphore>>>,
+    finished: AtomicBool,
+}
+
+impl<F> TimelineSignalFuture<F>
+where
+    F: GpuFuture,
+{
+    /// Returns the semaphore that is signaled by this future.
+    #[inline]
+    pub fn semaphore(&self) -> &Arc<Semaphore> {
+        &self.semaphore
+    }
+
+    /// Returns the value that the semaphore is signaled with.
+    #[inline]
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// Returns true if the semaphore has reached the value of this future.
+    ///
+    /// This does not flush the future.
+    #[inline]
+    pub fn is_signaled(&self) -> Result<bool, FlushError> {
+        if self.finished.load(Ordering::SeqCst) {
+            return Ok(true);
+        }
+
+        Ok(self.semaphore.counter_value()? >= self.value)
+    }
+
+    /// Flushes the future if needed, then blocks the current thread until the semaphore has
+    /// reached the value of this future, or until the timeout has elapsed.
+    ///
+    /// If `timeout` is `None`, will potentially block forever.
+    pub fn wait(&self, timeout: Option<Duration>) -> Result<(), FlushError> {
+        if self.finished.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        self.flush()?;
+        self.semaphore.wait(self.value, timeout)?;
+        unsafe { self.set_finished() };
+
+        Ok(())
+    }
+
+    // Releases the resources held by the queue operations up to and including the signal, and
+    // signals the previous future as finished.
+    unsafe fn set_finished(&self) {
+        if !self.finished.swap(true, Ordering::SeqCst) {
+            if let Some(queue) = self.previous.queue() {
+                queue.with(|mut q| q.semaphore_signaled(&self.semaphore, self.value));
+            }
+
+            self.previous.signal_finished();
+        }
+    }
+
+    fn validate_signal_value(&self) -> Result<(), FlushError> {
+        let current_value = unsafe { self.semaphore.counter_value_unchecked()? };
+
+        let pending_value = self.semaphore.state().max_pending_signal_value();
+
+        // VUID-VkSubmitInfo-pSignalSemaphores-03242
+        if self.value <= current_value || pending_value >= Some(self.value) {
+            return Err(FlushError::TimelineSignalValueNotGreater);
+        }
+
+        // VUID-VkSubmitInfo-pSignalSemaphores-03244
+        if let Some(max_difference) = self
+            .device()
+            .physical_device()
+            .properties()
+            .max_timeline_semaphore_value_difference
+        {
+            if self.value - current_value > max_difference {
+                return Err(FlushError::MaxTimelineSemaphoreValueDifferenceExceeded);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn cleanup_finished_impl(&self) -> bool {
+        if self.finished.load(Ordering::SeqCst) {
+            return true;
+        }
+
+        if !*self.signal_submitted.lock() {
+            return false;
+        }
+
+        match self.semaphore.counter_value() {
+            Ok(counter_value) if counter_value >= self.value => {
+                unsafe { self.set_finished() };
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+unsafe impl<F> GpuFuture for TimelineSignalFuture<F>
+where
+    F: GpuFuture,
+{
+    fn cleanup_finished(&mut self) {
+        if !self.cleanup_finished_impl() {
+            self.previous.cleanup_finished();
+        }
+    }
+
+    unsafe fn build_submission(&self) -> Result<SubmitAnyBuilder, FlushError> {
+        // Flushing the signaling part, since it must always be submitted before the waiting part.
+        self.flush()?;
+        // TODO: correct stages ; hard
+        let sem = smallvec![SemaphoreSubmitInfo {
+            value: self.value,
+            ..SemaphoreSubmitInfo::semaphore(self.semaphore.clone())
+        }];
+
+        Ok(SubmitAnyBuilder::SemaphoresWait(sem))
+    }
+
+    fn flush(&self) -> Result<(), FlushError> {
+        unsafe {
+            let mut signal_submitted = self.signal_submitted.lock();
+
+            if *signal_submitted {
+                return Ok(());
+            }
+
+            self.validate_signal_value()?;
+
+            let queue = self.previous.queue().unwrap();
+            let signal_semaphore = SemaphoreSubmitInfo {
+                value: self.value,
+                ..SemaphoreSubmitInfo::semaphore(self.semaphore.clone())
+            };
+            let submit_after_bind_sparse = |bind_semaphore: Arc<Semaphore>| {
+                queue.with(|mut q| {
+                    q.submit_unchecked(
+                        [SubmitInfo {
+                            wait_semaphores: vec![SemaphoreSubmitInfo::semaphore(bind_semaphore)],
+                            signal_semaphores: vec![signal_semaphore.clone()],
+                            ..Default::default()
+                        }],
+                        None,
+                    )
+                })
+            };
+
+            let mut bind_sparse_semaphore = self.bind_sparse_semaphore.lock();
+
+            if let Some(bind_semaphore) = bind_sparse_semaphore.clone() {
+                submit_after_bind_sparse(bind_semaphore)?;
+                *bind_sparse_semaphore = None;
+                *signal_submitted = true;
+                return Ok(());
+            }
+
+            match self.previous.build_submission()? {
+                SubmitAnyBuilder::Empty => {
+                    queue.with(|mut q| {
+                        q.submit_unchecked(
+                            [SubmitInfo {
+                                signal_semaphores: vec![signal_semaphore],
+                                ..Default::default()
+                            }],
+                            None,
+                        )
+                    })?;
+                }
+                SubmitAnyBuilder::SemaphoresWait(semaphores) => {
+                    queue.with(|mut q| {
+                        q.submit_unchecked(
+                            [SubmitInfo {
+                                wait_semaphores: semaphores.into_iter().collect(),
+                                signal_semaphores: vec![signal_semaphore],
+                                ..Default::default()
+                            }],
+                            None,
+                        )
+                    })?;
+                }
+                SubmitAnyBuilder::CommandBuffer(mut submit_info, fence) => {
+                    debug_assert!(submit_info.signal_semaphores.is_empty());
+
+                    submit_info.signal_semaphores.push(signal_semaphore);
+
+                    queue.with(|mut q| {
+                        q.submit_with_future(submit_info, fence, &self.previous, &queue)
+                    })?;
+                }
+                SubmitAnyBuilder::BindSparse(mut bind_infos, fence) => {
+                    // `BindSparseInfo` can only signal binary semaphores, so signal one from the
+                    // last batch and wait on it in a separate submission that signals the
+                    // timeline semaphore.
+                    let bind_semaphore = Arc::new(Semaphore::from_pool(queue.device().clone())?);
+
+                    match bind_infos.last_mut() {
+                        Some(bind_info) => bind_info.signal_semaphores.push(bind_semaphore.clone()),
+                        None => bind_infos.push(BindSparseInfo {
+                            signal_semaphores: vec![bind_semaphore.clone()],
+                            ..Default::default()
+                        }),
+                    }
+
+                    queue.with(|mut q| q.bind_sparse_unchecked(bind_infos, fence))?;
+
+                    // If this submission fails, only it is retried on the next flush.
+                    *bind_sparse_semaphore = Some(bind_semaphore.clone());
+                    submit_after_bind_sparse(bind_semaphore)?;
+                    *bind_sparse_semaphore = None;
+                }
+                SubmitAnyBuilder::QueuePresent(present_info) => {
+                    // VUID-VkPresentIdKHR-presentIds-04999
+                    for swapchain_info in &present_info.swapchain_infos {
+                        if swapchain_info.present_id.map_or(false, |present_id| {
+                            !swapchain_info.swapchain.try_claim_present_id(present_id)
+                        }) {
+                            return Err(FlushError::PresentIdLessThanOrEqual);
+                        }
+
+                        match self.previous.check_swapchain_image_acquired(
+                            &swapchain_info.swapchain,
+                            swapchain_info.image_index,
+                            true,
+                        ) {
+                            Ok(_) => (),
+                            Err(AccessCheckError::Unknown) => {
+                                return Err(AccessError::SwapchainImageNotAcquired.into())
+                            }
+                            Err(AccessCheckError::Denied(e)) => return Err(e.into()),
+                        }
+                    }
+
+                    queue.with(|mut q| {
+                        q.present_unchecked(present_info)?
+                            .map(|r| r.map(|_| ()))
+                            .fold(Ok(()), Result::and)?;
+                        // FIXME: problematic because if we return an error and flush() is called again, then we'll submit the present twice
+                        q.submit_unchecked(
+                            [SubmitInfo {
+                                signal_semaphores: vec![signal_semaphore],
+                                ..Default::default()
+                            }],
+                            None,
+                        )?;
+                        Ok::<_, FlushError>(())
+                    })?;
+                }
+            };
+
+            // Only write `true` here in order to try again next time if an error occurs.
+            *signal_submitted = true;
+            Ok(())
+        }
+    }
+
+    unsafe fn signal_finished(&self) {
+        debug_assert!(*self.signal_submitted.lock());
+        self.set_finished();
+    }
+
+    fn queue_change_allowed(&self) -> bool {
+        true
+    }
+
+    fn queue(&self) -> Option<Arc<Queue>> {
+        self.previous.queue()
+    }
+
+    fn check_buffer_access(
+        &self,
+        buffer: &Buffer,
+        range: Range<DeviceSize>,
+        exclusive: bool,
+        queue: &Queue,
+    ) -> Result<(), AccessCheckError> {
+        self.previous
+            .check_buffer_access(buffer, range, exclusive, queue)
+    }
+
+    fn check_image_access(
+        &self,
+        image: &Image,
+        range: Range<DeviceSize>,
+        exclusive: bool,
+        expected_layout: ImageLayout,
+        queue: &Queue,
+    ) -> Result<(), AccessCheckError> {
+        self.previous
+            .check_image_access(image, range, exclusive, expected_layout, queue)
+    }
+
+    #[inline]
+    fn check_swapchain_image_acquired(
+        &self,
+        swapchain: &Swapchain,
+        image_index: u32,
+        _before: bool,
+    ) -> Result<(), AccessCheckError> {
+        self.previous
+            .check_swapchain_image_acquired(swapchain, image_index, false)
+    }
+}
+
+unsafe impl<F> DeviceOwned for TimelineSignalFuture<F>
+where
+    F: GpuFuture,
+{
+    fn device(&self) -> &Arc<Device> {
+        self.semaphore.device()
+    }
+}
+
+impl<F> Drop for TimelineSignalFuture<F>
+where
+    F: GpuFuture,
+{
+    fn drop(&mut self) {
+        if !*self.finished.get_mut() {
+            // If submitting failed, there is nothing to wait for. The previous future will block
+            // on its own when it is dropped.
+            if self.flush().is_err() {
+                return;
+            }
+
+            // Block until the semaphore reaches the value.
+            // We ignore any possible error while waiting, as there is nothing left to do about it.
+            let _ = self.semaphore.wait(self.value, None);
+            unsafe { self.set_finished() };
+        }
+    }
+}
+
+unsafe impl<F> GpuFuture for Arc<TimelineSignalFuture<F>>
+where
+    F: GpuFuture,
+{
+    fn cleanup_finished(&mut self) {
+        self.cleanup_finished_impl();
+    }
+
+    unsafe fn build_submission(&self) -> Result<SubmitAnyBuilder, FlushError> {
+        // Note that this is sound because waiting on a timeline semaphore value doesn't consume
+        // the signal, so the returned submission can be used any number of times. See the
+        // documentation of `build_submission`.
+        (**self).build_submission()
+    }
+
+    fn flush(&self) -> Result<(), FlushError> {
+        (**self).flush()
+    }
+
+    unsafe fn signal_finished(&self) {
+        (**self).signal_finished()
+    }
+
+    fn queue_change_allowed(&self) -> bool {
+        (**self).queue_change_allowed()
+    }
+
+    fn queue(&self) -> Option<Arc<Queue>> {
+        (**self).queue()
+    }
+
+    fn check_buffer_access(
+        &self,
+        buffer: &Buffer,
+        range: Range<DeviceSize>,
+        exclusive: bool,
+        queue: &Queue,
+    ) -> Result<(), AccessCheckError> {
+        (**self).check_buffer_access(buffer, range, exclusive, queue)
+    }
+
+    fn check_image_access(
+        &self,
+        image: &Image,
+        range: Range<DeviceSize>,
+        exclusive: bool,
+        expected_layout: ImageLayout,
+        queue: &Queue,
+    ) -> Result<(), AccessCheckError> {
+        (**self).check_image_access(image, range, exclusive, expected_layout, queue)
+    }
+
+    #[inline]
+    fn check_swapchain_image_acquired(
+        &self,
+        swapchain: &Swapchain,
+        image_index: u32,
+        before: bool,
+    ) -> Result<(), AccessCheckError> {
+        (**self).check_swapchain_image_acquired(swapchain, image_index, before)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferUsage,
+        },
+        sync::{
+            future::{FlushError, GpuFuture},
+            now,
+            semaphore::{Semaphore, SemaphoreCreateInfo, SemaphoreType},
+        },
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn signal_and_wait() {
+        let (device, queue) = gfx_dev_and_queue!(timeline_semaphore);
+
+        let semaphore = Arc::new(
+            Semaphore::new(
+                device.clone(),
+                SemaphoreCreateInfo {
+                    semaphore_type: SemaphoreType::Timeline,
+                    ..Default::default()
+                },
+            )
+            .unwrap(),
+        );
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let cb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+
+        let future = Arc::new(
+            now(device)
+                .then_execute(queue, cb)
+                .unwrap()
+                .then_signal_timeline_and_flush(semaphore.clone(), 5)
+                .unwrap(),
+        );
+
+        // Two waits on the same signal.
+        let a = future.clone().then_signal_fence_and_flush().unwrap();
+        let b = future.clone().then_signal_fence_and_flush().unwrap();
+        a.wait(None).unwrap();
+        b.wait(None).unwrap();
+
+        future.wait(None).unwrap();
+        assert!(future.is_signaled().unwrap());
+        assert_eq!(semaphore.counter_value().unwrap(), 5);
+    }
+
+    #[test]
+    fn signal_value_not_greater() {
+        let (device, queue) = gfx_dev_and_queue!(timeline_semaphore);
+
+        let semaphore = Arc::new(
+            Semaphore::new(
+                device.clone(),
+                SemaphoreCreateInfo {
+                    semaphore_type: SemaphoreType::Timeline,
+                    initial_value: 3,
+                    ..Default::default()
+                },
+            )
+            .unwrap(),
+        );
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let cb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+
+        let future = now(device)
+            .then_execute(queue, cb)
+            .unwrap()
+            .then_signal_timeline_and_flush(semaphore, 3);
+
+        assert!(matches!(
+            future,
+            Err(FlushError::TimelineSignalValueNotGreater)
+        ));
+    }
+}
@@ -1321,10 +1321,59 @@ impl PipelineStageAccess {
                         )
                     });
 
+            let acceleration_structure_read = [DescriptorType::AccelerationStructure]
+                .into_iter()
+                .map(|descriptor_type| {
+                    (
+                        descriptor_type,
+                        [
+                            (
+                                PipelineStage::VertexShader,
+                                PipelineStageAccess::VertexShader_AccelerationStructureRead,
+                            ),
+                            (
+                                PipelineStage::TessellationControlShader,
+                                PipelineStageAccess::TessellationControlShader_AccelerationStructureRead,
+                            ),
+                            (
+                                PipelineStage::TessellationEvaluationShader,
+                                PipelineStageAccess::TessellationEvaluationShader_AccelerationStructureRead,
+                            ),
+                            (
+                                PipelineStage::GeometryShader,
+                                PipelineStageAccess::GeometryShader_AccelerationStructureRead,
+                            ),
+                            (
+                                PipelineStage::FragmentShader,
+                                PipelineStageAccess::FragmentShader_AccelerationStructureRead,
+                            ),
+                            (
+                                PipelineStage::ComputeShader,
+                                PipelineStageAccess::ComputeShader_AccelerationStructureRead,
+                            ),
+                            (
+                                PipelineStage::RayTracingShader,
+                                PipelineStageAccess::RayTracingShader_AccelerationStructureRead,
+                            ),
+                            (
+                                PipelineStage::TaskShader,
+                                PipelineStageAccess::TaskShader_AccelerationStructureRead,
+                            ),
+                            (
+                                PipelineStage::MeshShader,
+                                PipelineStageAccess::MeshShader_AccelerationStructureRead,
+                            ),
+                        ]
+                        .into_iter()
+                        .collect(),
+                    )
+                });
+
             uniform_read
                 .chain(shader_sampled_read)
                 .chain(shader_storage_read)
                 .chain(input_attachment_read)
+                .chain(acceleration_structure_read)
                 .collect()
         });
         static MAP_WRITE: Lazy<
@@ -12,10 +12,11 @@
 
 use crate::{
     device::{Device, DeviceOwned, Queue},
-    macros::{vulkan_bitflags, vulkan_bitflags_enum},
+    macros::{vulkan_bitflags, vulkan_bitflags_enum, vulkan_enum},
     OomError, RequirementNotMet, RequiresOneOf, Version, VulkanError, VulkanObject,
 };
 use parking_lot::{Mutex, MutexGuard};
+use smallvec::SmallVec;
 #[cfg(unix)]
 use std::fs::File;
 use std::{
@@ -23,21 +24,28 @@ use std::{
     fmt::{Display, Error as FmtError, Formatter},
     mem::MaybeUninit,
     num::NonZeroU64,
-    ptr,
     sync::{Arc, Weak},
+    time::Duration,
 };
 
 /// Used to provide synchronization between command buffers during their execution.
 ///
-/// It is similar to a fence, except that it is purely on the GPU side. The CPU can't query a
-/// semaphore's status or wait for it to be signaled.
+/// A binary semaphore is similar to a fence, except that it is purely on the GPU side. The CPU
+/// can't query a binary semaphore's status or wait for it to be signaled.
+///
+/// A timeline semaphore instead holds a 64-bit counter value that only ever increases. Queue
+/// operations can wait for the counter to reach a particular value, or signal it by setting it to
+/// a new value. Unlike binary semaphores, the counter can also be queried, signaled and waited on
+/// from the host.
 #[derive(Debug)]
 pub struct Semaphore {
     handle: ash::vk::Semaphore,
     device: Arc<Device>,
     id: NonZeroU64,
     must_put_in_pool: bool,
+    imported_handle: bool,
 
+    semaphore_type: SemaphoreType,
     export_handle_types: ExternalSemaphoreHandleTypes,
 
     state: Mutex<SemaphoreState>,
@@ -60,10 +68,21 @@ impl Semaphore {
         create_info: &SemaphoreCreateInfo,
     ) -> Result<(), SemaphoreError> {
         let &SemaphoreCreateInfo {
+            semaphore_type,
+            initial_value,
             export_handle_types,
             _ne: _,
         } = create_info;
 
+        // VUID-VkSemaphoreTypeCreateInfo-semaphoreType-parameter
+        // VUID-VkSemaphoreTypeCreateInfo-timelineSemaphore-03252
+        semaphore_type.validate_device(device)?;
+
+        // VUID-VkSemaphoreTypeCreateInfo-semaphoreType-03279
+        if semaphore_type == SemaphoreType::Binary && initial_value != 0 {
+            return Err(SemaphoreError::BinaryInitialValueNotZero);
+        }
+
         if !export_handle_types.is_empty() {
             if !(device.api_version() >= Version::V1_1
                 || device.enabled_extensions().khr_external_semaphore)
@@ -86,9 +105,10 @@ impl Semaphore {
                 let external_semaphore_properties = unsafe {
                     device
                         .physical_device()
-                        .external_semaphore_properties_unchecked(
-                            ExternalSemaphoreInfo::handle_type(handle_type),
-                        )
+                        .external_semaphore_properties_unchecked(ExternalSemaphoreInfo {
+                            semaphore_type,
+                            ..ExternalSemaphoreInfo::handle_type(handle_type)
+                        })
                 };
 
                 if !external_semaphore_properties.exportable {
@@ -114,6 +134,8 @@ impl Semaphore {
         create_info: SemaphoreCreateInfo,
     ) -> Result<Semaphore, VulkanError> {
         let SemaphoreCreateInfo {
+            semaphore_type,
+            initial_value,
             export_handle_types,
             _ne: _,
         } = create_info;
@@ -122,8 +144,20 @@ impl Semaphore {
             flags: ash::vk::SemaphoreCreateFlags::empty(),
             ..Default::default()
         };
+        let mut semaphore_type_create_info_vk = None;
         let mut export_semaphore_create_info_vk = None;
 
+        if semaphore_type != SemaphoreType::Binary {
+            let next = semaphore_type_create_info_vk.insert(ash::vk::SemaphoreTypeCreateInfo {
+                semaphore_type: semaphore_type.into(),
+                initial_value,
+                ..Default::default()
+            });
+
+            next.p_next = create_info_vk.p_next;
+            create_info_vk.p_next = next as *const _ as *const _;
+        }
+
         if !export_handle_types.is_empty() {
             let _ = export_semaphore_create_info_vk.insert(ash::vk::ExportSemaphoreCreateInfo {
                 handle_types: export_handle_types.into(),
@@ -142,7 +176,7 @@ impl Semaphore {
             (fns.v1_0.create_semaphore)(
                 device.handle(),
                 &create_info_vk,
-                ptr::null(),
+                device.allocation_callbacks(),
                 output.as_mut_ptr(),
             )
             .result()
@@ -155,6 +189,8 @@ impl Semaphore {
             device,
             id: Self::next_id(),
             must_put_in_pool: false,
+            imported_handle: false,
+            semaphore_type,
             export_handle_types,
             state: Mutex::new(Default::default()),
         })
@@ -175,6 +211,8 @@ impl Semaphore {
                 device,
                 id: Self::next_id(),
                 must_put_in_pool: true,
+                imported_handle: false,
+                semaphore_type: SemaphoreType::Binary,
                 export_handle_types: ExternalSemaphoreHandleTypes::empty(),
                 state: Mutex::new(Default::default()),
             },
@@ -195,6 +233,8 @@ impl Semaphore {
     ///
     /// - `handle` must be a valid Vulkan object handle created from `device`.
     /// - `create_info` must match the info used to create the object.
+    /// - `handle` must have been created with a null `pAllocator`, as it is destroyed with a null
+    ///   `pAllocator` when dropped.
     #[inline]
     pub unsafe fn from_handle(
         device: Arc<Device>,
@@ -202,6 +242,8 @@ impl Semaphore {
         create_info: SemaphoreCreateInfo,
     ) -> Semaphore {
         let SemaphoreCreateInfo {
+            semaphore_type,
+            initial_value: _,
             export_handle_types,
             _ne: _,
         } = create_info;
@@ -211,11 +253,264 @@ impl Semaphore {
             device,
             id: Self::next_id(),
             must_put_in_pool: false,
+            imported_handle: true,
+            semaphore_type,
             export_handle_types,
             state: Mutex::new(Default::default()),
         }
     }
 
+    /// Returns the type of the semaphore.
+    #[inline]
+    pub fn semaphore_type(&self) -> SemaphoreType {
+        self.semaphore_type
+    }
+
+    /// Returns the current counter value of a timeline semaphore.
+    ///
+    /// The semaphore must be a timeline semaphore.
+    #[inline]
+    pub fn counter_value(&self) -> Result<u64, SemaphoreError> {
+        self.validate_counter_value()?;
+
+        unsafe { Ok(self.counter_value_unchecked()?) }
+    }
+
+    fn validate_counter_value(&self) -> Result<(), SemaphoreError> {
+        // VUID-vkGetSemaphoreCounterValue-semaphore-03255
+        if self.semaphore_type != SemaphoreType::Timeline {
+            return Err(SemaphoreError::NotTimeline);
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn counter_value_unchecked(&self) -> Result<u64, VulkanError> {
+        let mut output = MaybeUninit::uninit();
+        let fns = self.device.fns();
+
+        if self.device.api_version() >= Version::V1_2 {
+            (fns.v1_2.get_semaphore_counter_value)(
+                self.device.handle(),
+                self.handle,
+                output.as_mut_ptr(),
+            )
+        } else {
+            debug_assert!(self.device.enabled_extensions().khr_timeline_semaphore);
+            (fns.khr_timeline_semaphore.get_semaphore_counter_value_khr)(
+                self.device.handle(),
+                self.handle,
+                output.as_mut_ptr(),
+            )
+        }
+        .result()
+        .map_err(VulkanError::from)?;
+
+        Ok(output.assume_init())
+    }
+
+    /// Signals a timeline semaphore from the host, setting its counter to `value`.
+    ///
+    /// The semaphore must be a timeline semaphore. `value` must be greater than the current
+    /// counter value, and less than the value of any signal operation that is pending in a queue.
+    #[inline]
+    pub fn signal(&self, value: u64) -> Result<(), SemaphoreError> {
+        let mut state = self.state.lock();
+        self.validate_signal(value, &state)?;
+
+        unsafe { Ok(self.signal_unchecked_locked(value, &mut state)?) }
+    }
+
+    fn validate_signal(&self, value: u64, state: &SemaphoreState) -> Result<(), SemaphoreError> {
+        // VUID-VkSemaphoreSignalInfo-semaphore-03257
+        if self.semaphore_type != SemaphoreType::Timeline {
+            return Err(SemaphoreError::NotTimeline);
+        }
+
+        let current_value = unsafe { self.counter_value_unchecked()? };
+
+        // VUID-VkSemaphoreSignalInfo-value-03258
+        if value <= current_value {
+            return Err(SemaphoreError::SignalValueNotGreaterThanCurrent {
+                value,
+                current_value,
+            });
+        }
+
+        // VUID-VkSemaphoreSignalInfo-value-03259
+        if let Some(pending_value) = state.min_pending_signal_value() {
+            if value >= pending_value {
+                return Err(SemaphoreError::SignalValueNotLessThanPending {
+                    value,
+                    pending_value,
+                });
+            }
+        }
+
+        // VUID-VkSemaphoreSignalInfo-value-03260
+        if let Some(max_difference) = self
+            .device
+            .physical_device()
+            .properties()
+            .max_timeline_semaphore_value_difference
+        {
+            if value - current_value > max_difference {
+                return Err(
+                    SemaphoreError::MaxTimelineSemaphoreValueDifferenceExceeded {
+                        difference: value - current_value,
+                        max: max_difference,
+                    },
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn signal_unchecked(&self, value: u64) -> Result<(), VulkanError> {
+        let mut state = self.state.lock();
+        self.signal_unchecked_locked(value, &mut state)
+    }
+
+    unsafe fn signal_unchecked_locked(
+        &self,
+        value: u64,
+        _state: &mut SemaphoreState,
+    ) -> Result<(), VulkanError> {
+        let signal_info_vk = ash::vk::SemaphoreSignalInfo {
+            semaphore: self.handle,
+            value,
+            ..Default::default()
+        };
+
+        let fns = self.device.fns();
+
+        if self.device.api_version() >= Version::V1_2 {
+            (fns.v1_2.signal_semaphore)(self.device.handle(), &signal_info_vk)
+        } else {
+            debug_assert!(self.device.enabled_extensions().khr_timeline_semaphore);
+            (fns.khr_timeline_semaphore.signal_semaphore_khr)(self.device.handle(), &signal_info_vk)
+        }
+        .result()
+        .map_err(VulkanError::from)?;
+
+        Ok(())
+    }
+
+    /// Waits until the counter of a timeline semaphore has reached at least `value`, or at least
+    /// until the timeout duration has elapsed.
+    ///
+    /// Returns `Ok` if the counter has reached the value. Returns `Err` if the timeout was reached
+    /// instead.
+    ///
+    /// If you pass a duration of 0, then the function will return without blocking.
+    ///
+    /// The semaphore must be a timeline semaphore.
+    #[inline]
+    pub fn wait(&self, value: u64, timeout: Option<Duration>) -> Result<(), SemaphoreError> {
+        Self::multi_wait([(self, value)], timeout)
+    }
+
+    /// Waits for multiple timeline semaphores at once, until the counter of each semaphore has
+    /// reached at least the value that is paired with it.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if not all semaphores belong to the same device.
+    pub fn multi_wait<'a>(
+        semaphores: impl IntoIterator<Item = (&'a Semaphore, u64)>,
+        timeout: Option<Duration>,
+    ) -> Result<(), SemaphoreError> {
+        let semaphores: SmallVec<[_; 8]> = semaphores.into_iter().collect();
+        Self::validate_multi_wait(&semaphores, timeout)?;
+
+        unsafe { Self::multi_wait_unchecked(semaphores, timeout) }
+    }
+
+    fn validate_multi_wait(
+        semaphores: &[(&Semaphore, u64)],
+        _timeout: Option<Duration>,
+    ) -> Result<(), SemaphoreError> {
+        if semaphores.is_empty() {
+            return Ok(());
+        }
+
+        let device = &semaphores[0].0.device;
+
+        for (semaphore, _) in semaphores {
+            // VUID-vkWaitSemaphores-commonparent
+            assert_eq!(device, &semaphore.device);
+
+            // VUID-VkSemaphoreWaitInfo-pSemaphores-03256
+            if semaphore.semaphore_type != SemaphoreType::Timeline {
+                return Err(SemaphoreError::NotTimeline);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn multi_wait_unchecked<'a>(
+        semaphores: impl IntoIterator<Item = (&'a Semaphore, u64)>,
+        timeout: Option<Duration>,
+    ) -> Result<(), SemaphoreError> {
+        let (semaphores, (semaphores_vk, values_vk)): (
+            SmallVec<[_; 8]>,
+            (SmallVec<[_; 8]>, SmallVec<[_; 8]>),
+        ) = semaphores
+            .into_iter()
+            .map(|(semaphore, value)| (semaphore, (semaphore.handle, value)))
+            .unzip();
+
+        // VUID-VkSemaphoreWaitInfo-semaphoreCount-arraylength
+        // If there are no semaphores, we don't need to wait.
+        if semaphores.is_empty() {
+            return Ok(());
+        }
+
+        let device = &semaphores[0].device;
+        let timeout_ns = timeout.map_or(u64::MAX, |timeout| {
+            timeout
+                .as_secs()
+                .saturating_mul(1_000_000_000)
+                .saturating_add(timeout.subsec_nanos() as u64)
+        });
+
+        let wait_info_vk = ash::vk::SemaphoreWaitInfo {
+            flags: ash::vk::SemaphoreWaitFlags::empty(),
+            semaphore_count: semaphores_vk.len() as u32,
+            p_semaphores: semaphores_vk.as_ptr(),
+            p_values: values_vk.as_ptr(),
+            ..Default::default()
+        };
+
+        let result = {
+            let fns = device.fns();
+
+            if device.api_version() >= Version::V1_2 {
+                (fns.v1_2.wait_semaphores)(device.handle(), &wait_info_vk, timeout_ns)
+            } else {
+                debug_assert!(device.enabled_extensions().khr_timeline_semaphore);
+                (fns.khr_timeline_semaphore.wait_semaphores_khr)(
+                    device.handle(),
+                    &wait_info_vk,
+                    timeout_ns,
+                )
+            }
+        };
+
+        match result {
+            ash::vk::Result::SUCCESS => Ok(()),
+            ash::vk::Result::TIMEOUT => Err(SemaphoreError::Timeout),
+            err => Err(VulkanError::from(err).into()),
+        }
+    }
+
     /// Exports the semaphore into a POSIX file descriptor. The caller owns the returned `File`.
     #[cfg(unix)]
     #[inline]
@@ -997,7 +1292,12 @@ impl Drop for Semaphore {
                 self.device.semaphore_pool().lock().push(raw_sem);
             } else {
                 let fns = self.device.fns();
-                (fns.v1_0.destroy_semaphore)(self.device.handle(), self.handle, ptr::null());
+                (fns.v1_0.destroy_semaphore)(
+                    self.device.handle(),
+                    self.handle,
+                    self.device
+                        .destroy_allocation_callbacks(self.imported_handle),
+                );
             }
         }
     }
@@ -1027,6 +1327,9 @@ pub(crate) struct SemaphoreState {
     pending_signal: Option<SignalType>,
     pending_wait: Option<Weak<Queue>>,
 
+    // For timeline semaphores, the values of the signal operations that are pending in a queue.
+    pending_signal_values: SmallVec<[u64; 4]>,
+
     reference_exported: bool,
     exported_handle_types: ExternalSemaphoreHandleTypes,
     current_import: Option<ImportType>,
@@ -1037,7 +1340,7 @@ impl SemaphoreState {
     /// If the semaphore does not have a pending operation and has no external references,
     /// returns the current status.
     #[inline]
-    fn is_signaled(&self) -> Option<bool> {
+    pub(crate) fn is_signaled(&self) -> Option<bool> {
         // If any of these is true, we can't be certain of the status.
         if self.pending_signal.is_some()
             || self.pending_wait.is_some()
@@ -1050,15 +1353,25 @@ impl SemaphoreState {
     }
 
     #[inline]
-    fn is_signal_pending(&self) -> bool {
+    pub(crate) fn is_signal_pending(&self) -> bool {
         self.pending_signal.is_some()
     }
 
     #[inline]
-    fn is_wait_pending(&self) -> bool {
+    pub(crate) fn is_wait_pending(&self) -> bool {
         self.pending_wait.is_some()
     }
 
+    #[inline]
+    fn min_pending_signal_value(&self) -> Option<u64> {
+        self.pending_signal_values.iter().copied().min()
+    }
+
+    #[inline]
+    pub(crate) fn max_pending_signal_value(&self) -> Option<u64> {
+        self.pending_signal_values.iter().copied().max()
+    }
+
     #[inline]
     fn is_in_queue(&self) -> bool {
         matches!(self.pending_signal, Some(SignalType::Queue(_))) || self.pending_wait.is_some()
@@ -1082,6 +1395,12 @@ impl SemaphoreState {
         self.pending_signal = Some(SignalType::Queue(Arc::downgrade(queue)));
     }
 
+    #[inline]
+    pub(crate) unsafe fn add_queue_signal_value(&mut self, queue: &Arc<Queue>, value: u64) {
+        self.pending_signal = Some(SignalType::Queue(Arc::downgrade(queue)));
+        self.pending_signal_values.push(value);
+    }
+
     #[inline]
     pub(crate) unsafe fn add_queue_wait(&mut self, queue: &Arc<Queue>) {
         self.pending_wait = Some(Arc::downgrade(queue));
@@ -1094,6 +1413,18 @@ impl SemaphoreState {
         self.is_signaled = true;
     }
 
+    /// Called when a queue is unlocking resources, for a timeline semaphore.
+    #[inline]
+    pub(crate) unsafe fn set_signal_value_finished(&mut self, value: u64) {
+        if let Some(index) = self.pending_signal_values.iter().position(|&v| v == value) {
+            self.pending_signal_values.swap_remove(index);
+        }
+
+        if self.pending_signal_values.is_empty() {
+            self.pending_signal = None;
+        }
+    }
+
     /// Called when a queue is unlocking resources.
     #[inline]
     pub(crate) unsafe fn set_wait_finished(&mut self) {
@@ -1154,6 +1485,19 @@ impl From<ExternalSemaphoreHandleType> for ImportType {
 /// Parameters to create a new `Semaphore`.
 #[derive(Clone, Debug)]
 pub struct SemaphoreCreateInfo {
+    /// The type of semaphore to create.
+    ///
+    /// The default value is [`SemaphoreType::Binary`].
+    pub semaphore_type: SemaphoreType,
+
+    /// If `semaphore_type` is [`SemaphoreType::Timeline`], specifies the initial value of the
+    /// semaphore's counter.
+    ///
+    /// If `semaphore_type` is [`SemaphoreType::Binary`], this must be 0.
+    ///
+    /// The default value is 0.
+    pub initial_value: u64,
+
     /// The handle types that can be exported from the semaphore.
     ///
     /// The default value is [`ExternalSemaphoreHandleTypes::empty()`].
@@ -1166,12 +1510,42 @@ impl Default for SemaphoreCreateInfo {
     #[inline]
     fn default() -> Self {
         Self {
+            semaphore_type: SemaphoreType::Binary,
+            initial_value: 0,
             export_handle_types: ExternalSemaphoreHandleTypes::empty(),
             _ne: crate::NonExhaustive(()),
         }
     }
 }
 
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The type that a semaphore can have.
+    SemaphoreType = SemaphoreType(i32);
+
+    /// A semaphore that can only have two states, unsignaled and signaled.
+    ///
+    /// This is the `Default` value.
+    Binary = BINARY,
+
+    /// A semaphore whose state is a monotonically increasing 64-bit counter value.
+    ///
+    /// The [`timeline_semaphore`] feature must be enabled on the device.
+    ///
+    /// [`timeline_semaphore`]: crate::device::Features::timeline_semaphore
+    Timeline = TIMELINE {
+        features: [timeline_semaphore],
+    },
+}
+
+impl Default for SemaphoreType {
+    #[inline]
+    fn default() -> Self {
+        SemaphoreType::Binary
+    }
+}
+
 vulkan_bitflags_enum! {
     #[non_exhaustive]
 
@@ -1369,6 +1743,11 @@ pub struct ExternalSemaphoreInfo {
     /// The external handle type that will be used with the semaphore.
     pub handle_type: ExternalSemaphoreHandleType,
 
+    /// The type of semaphore that will be used.
+    ///
+    /// The default value is [`SemaphoreType::Binary`].
+    pub semaphore_type: SemaphoreType,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -1378,6 +1757,7 @@ impl ExternalSemaphoreInfo {
     pub fn handle_type(handle_type: ExternalSemaphoreHandleType) -> Self {
         Self {
             handle_type,
+            semaphore_type: SemaphoreType::Binary,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -1411,6 +1791,12 @@ pub enum SemaphoreError {
     /// Not enough memory available.
     OomError(OomError),
 
+    /// The device has been lost.
+    DeviceLost,
+
+    /// The specified timeout wasn't long enough.
+    Timeout,
+
     RequirementNotMet {
         required_for: &'static str,
         requires_one_of: RequiresOneOf,
@@ -1420,6 +1806,9 @@ pub enum SemaphoreError {
     /// and a handle of this type was already exported previously.
     AlreadyExported,
 
+    /// The semaphore type is binary, but a nonzero initial value was provided.
+    BinaryInitialValueNotZero,
+
     /// The provided handle type cannot be exported from the current import handle type.
     ExportFromImportedNotSupported {
         imported_handle_type: ExternalSemaphoreHandleType,
@@ -1460,8 +1849,24 @@ pub enum SemaphoreError {
     /// The semaphore is currently in use by a queue.
     InQueue,
 
+    /// The difference between the provided signal value and the current counter value of the
+    /// semaphore exceeds the [`max_timeline_semaphore_value_difference`] device limit.
+    ///
+    /// [`max_timeline_semaphore_value_difference`]: crate::device::Properties::max_timeline_semaphore_value_difference
+    MaxTimelineSemaphoreValueDifferenceExceeded { difference: u64, max: u64 },
+
+    /// The operation requires a timeline semaphore, but the semaphore is a binary semaphore.
+    NotTimeline,
+
     /// A queue is currently waiting on the semaphore.
     QueueIsWaiting,
+
+    /// The provided signal value is not greater than the current counter value of the semaphore.
+    SignalValueNotGreaterThanCurrent { value: u64, current_value: u64 },
+
+    /// The provided signal value is not less than the value of a signal operation that is
+    /// pending in a queue.
+    SignalValueNotLessThanPending { value: u64, pending_value: u64 },
 }
 
 impl Error for SemaphoreError {
@@ -1477,6 +1882,8 @@ impl Display for SemaphoreError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
         match self {
             Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::DeviceLost => write!(f, "the device was lost"),
+            Self::Timeout => write!(f, "the timeout has been reached"),
             Self::RequirementNotMet {
                 required_for,
                 requires_one_of,
@@ -1491,6 +1898,10 @@ impl Display for SemaphoreError {
                 "the provided handle type does not permit more than one export, and a handle of \
                 this type was already exported previously",
             ),
+            Self::BinaryInitialValueNotZero => write!(
+                f,
+                "the semaphore type is binary, but a nonzero initial value was provided",
+            ),
             Self::ExportFromImportedNotSupported {
                 imported_handle_type,
             } => write!(
@@ -1538,7 +1949,36 @@ impl Display for SemaphoreError {
                 "the semaphore currently has a temporary import for a swapchain acquire operation",
             ),
             Self::InQueue => write!(f, "the semaphore is currently in use by a queue"),
+            Self::MaxTimelineSemaphoreValueDifferenceExceeded { difference, max } => write!(
+                f,
+                "the difference between the provided signal value and the current counter value \
+                ({}) exceeds the `max_timeline_semaphore_value_difference` limit ({})",
+                difference, max,
+            ),
+            Self::NotTimeline => write!(
+                f,
+                "the operation requires a timeline semaphore, but the semaphore is a binary \
+                semaphore",
+            ),
             Self::QueueIsWaiting => write!(f, "a queue is currently waiting on the semaphore"),
+            Self::SignalValueNotGreaterThanCurrent {
+                value,
+                current_value,
+            } => write!(
+                f,
+                "the provided signal value ({}) is not greater than the current counter value of \
+                the semaphore ({})",
+                value, current_value,
+            ),
+            Self::SignalValueNotLessThanPending {
+                value,
+                pending_value,
+            } => write!(
+                f,
+                "the provided signal value ({}) is not less than the value of a signal operation \
+                that is pending in a queue ({})",
+                value, pending_value,
+            ),
         }
     }
 }
@@ -1549,6 +1989,7 @@ impl From<VulkanError> for SemaphoreError {
             e @ VulkanError::OutOfHostMemory | e @ VulkanError::OutOfDeviceMemory => {
                 Self::OomError(e.into())
             }
+            VulkanError::DeviceLost => Self::DeviceLost,
             _ => panic!("unexpected error: {:?}", err),
         }
     }
@@ -1575,12 +2016,14 @@ mod tests {
     use crate::{
         device::{Device, DeviceCreateInfo, DeviceExtensions, QueueCreateInfo},
         instance::{Instance, InstanceCreateInfo, InstanceExtensions},
-        sync::semaphore::{
-            ExternalSemaphoreHandleType, ExternalSemaphoreHandleTypes, SemaphoreCreateInfo,
-        },
+        sync::semaphore::{ExternalSemaphoreHandleType, ExternalSemaphoreHandleTypes},
         VulkanLibrary,
     };
-    use crate::{sync::semaphore::Semaphore, VulkanObject};
+    use crate::{
+        sync::semaphore::{Semaphore, SemaphoreCreateInfo, SemaphoreError, SemaphoreType},
+        VulkanObject,
+    };
+    use std::time::Duration;
 
     #[test]
     fn semaphore_create() {
@@ -1588,6 +2031,59 @@ mod tests {
         let _ = Semaphore::new(device, Default::default());
     }
 
+    #[test]
+    fn binary_initial_value() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        assert!(matches!(
+            Semaphore::new(
+                device,
+                SemaphoreCreateInfo {
+                    initial_value: 1,
+                    ..Default::default()
+                },
+            ),
+            Err(SemaphoreError::BinaryInitialValueNotZero)
+        ));
+    }
+
+    #[test]
+    fn timeline_signal_wait() {
+        let (device, _) = gfx_dev_and_queue!(timeline_semaphore);
+
+        let sem = Semaphore::new(
+            device,
+            SemaphoreCreateInfo {
+                semaphore_type: SemaphoreType::Timeline,
+                initial_value: 5,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(sem.counter_value().unwrap(), 5);
+
+        assert!(matches!(
+            sem.signal(5),
+            Err(SemaphoreError::SignalValueNotGreaterThanCurrent { .. })
+        ));
+        assert!(matches!(
+            sem.wait(6, Some(Duration::new(0, 10))),
+            Err(SemaphoreError::Timeout)
+        ));
+
+        sem.signal(6).unwrap();
+        sem.wait(6, Some(Duration::new(0, 10))).unwrap();
+        assert_eq!(sem.counter_value().unwrap(), 6);
+    }
+
+    #[test]
+    fn binary_counter_value() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let sem = Semaphore::new(device, Default::default()).unwrap();
+        assert_eq!(sem.counter_value(), Err(SemaphoreError::NotTimeline));
+    }
+
     #[test]
     fn semaphore_pool() {
         let (device, _) = gfx_dev_and_queue!();